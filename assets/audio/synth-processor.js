@@ -0,0 +1,102 @@
+// A polyphonic synthesizer that runs on the audio rendering thread.
+// The main thread only sends note events to it, so no audio nodes have to be created for each step.
+
+/** How long it takes for a note to reach its full volume in seconds. */
+const ATTACK_TIME = 0.05;
+/** How long it takes for a note to fade out when all notes are stopped in seconds. */
+const STOP_FADE_TIME = 0.5;
+/** The volume of a single voice, so that chords don't clip before the compressor. */
+const VOICE_GAIN = 0.3;
+
+class SynthProcessor extends AudioWorkletProcessor {
+  constructor() {
+    super();
+    this.voices = [];
+    this.port.onmessage = (e) => this.onMessage(e.data);
+  }
+
+  onMessage(message) {
+    switch (message.type) {
+      case 'play':
+        for (const note of message.notes) {
+          this.voices.push({
+            frequency: note.frequency,
+            waveform: note.waveform,
+            // Equal-power panning
+            leftGain: Math.cos(((note.pan + 1) * Math.PI) / 4),
+            rightGain: Math.sin(((note.pan + 1) * Math.PI) / 4),
+            duration: message.duration,
+            phase: 0,
+            time: 0,
+            stopTime: null,
+            stopGain: 0,
+          });
+        }
+        break;
+      case 'stop':
+        for (const voice of this.voices) {
+          if (voice.stopTime === null) {
+            voice.stopGain = this.envelope(voice);
+            voice.stopTime = voice.time;
+          }
+        }
+        break;
+    }
+  }
+
+  /** The gain of a voice at its current time. */
+  envelope(voice) {
+    if (voice.stopTime !== null) {
+      return voice.stopGain * Math.max(0, 1 - (voice.time - voice.stopTime) / STOP_FADE_TIME);
+    }
+    if (voice.time < ATTACK_TIME) {
+      return voice.time / ATTACK_TIME;
+    }
+    return Math.max(0, 1 - (voice.time - ATTACK_TIME) / Math.max(voice.duration - ATTACK_TIME, 0.001));
+  }
+
+  /** The value of a waveform at a phase from 0 to 1. */
+  oscillate(waveform, phase) {
+    switch (waveform) {
+      case 'square':
+        return phase < 0.5 ? 1 : -1;
+      case 'sawtooth':
+        return 2 * phase - 1;
+      case 'triangle':
+        return 1 - 4 * Math.abs(phase - 0.5);
+      default:
+        return Math.sin(2 * Math.PI * phase);
+    }
+  }
+
+  isFinished(voice) {
+    return voice.stopTime !== null ? voice.time - voice.stopTime >= STOP_FADE_TIME : voice.time >= voice.duration;
+  }
+
+  process(_inputs, outputs) {
+    const output = outputs[0];
+    const left = output[0];
+    const right = output[1] ?? output[0];
+    const dt = 1 / sampleRate;
+
+    left.fill(0);
+    right.fill(0);
+
+    for (const voice of this.voices) {
+      for (let i = 0; i < left.length; i++) {
+        const sample = this.oscillate(voice.waveform, voice.phase) * this.envelope(voice) * VOICE_GAIN;
+        left[i] += sample * voice.leftGain;
+        right[i] += sample * voice.rightGain;
+        voice.phase = (voice.phase + voice.frequency * dt) % 1;
+        voice.time += dt;
+      }
+    }
+
+    this.voices = this.voices.filter((voice) => !this.isFinished(voice));
+
+    // Keep the processor alive even when no notes are playing
+    return true;
+  }
+}
+
+registerProcessor('synth-processor', SynthProcessor);
@@ -0,0 +1,9 @@
+//! A collection of algorithms that build a solution one choice at a time, and undo the latest choice when it can't
+//! lead to a solution.
+mod permutations;
+mod subsets;
+mod sudoku;
+
+pub use permutations::Permutations;
+pub use subsets::Subsets;
+pub use sudoku::Sudoku;
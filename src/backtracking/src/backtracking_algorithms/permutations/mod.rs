@@ -0,0 +1,206 @@
+use diagram::*;
+
+use crate::*;
+
+const MAX_ITEMS: usize = 5;
+const CANVAS_HEIGHT: f32 = 420.0;
+const ITEMS_Y: f32 = 70.0;
+const CELL_SIZE: f32 = 50.0;
+
+/// The permutations of a sequence, which are all of the orders of its items. Heap's algorithm generates them by
+/// permuting the first `k - 1` items recursively between swaps of the `k`th item, so that every permutation differs
+/// from the previous one by a single swap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Permutations {
+    items: Vec<String>,
+}
+impl Permutations {
+    pub fn new(items: &[&str]) -> Self {
+        Self {
+            items: items.iter().map(|item| item.to_string()).collect(),
+        }
+    }
+    fn draw(
+        &self,
+        items: &[String],
+        states: &[(usize, ElementState)],
+        frames: &[String],
+    ) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let left = (CANVAS_WIDTH - items.len() as f32 * CELL_SIZE) / 2.0;
+        for (index, item) in items.iter().enumerate() {
+            let mut node = DiagramNode::rect(
+                format!("item-{}", index),
+                item,
+                (left + (index as f32 + 0.5) * CELL_SIZE, ITEMS_Y),
+                (CELL_SIZE - 6.0, CELL_SIZE - 6.0),
+            )
+            .with_note(index);
+            node.state = states
+                .iter()
+                .find(|(state_index, _)| *state_index == index)
+                .map(|(_, state)| *state);
+            diagram.add_node(node);
+        }
+        draw_call_stack(&mut diagram, frames);
+        diagram
+    }
+    fn heap(&self, steps: &mut DiagramSteps) {
+        let mut generator = Generator {
+            permutations: self,
+            items: self.items.clone(),
+            frames: vec![],
+            output: vec![],
+            swaps: 0,
+            steps,
+        };
+        generator.push(
+            format!(
+                "Generate the {} permutations of the {}. A call with k permutes the first k items by calling itself with k − 1 between swaps of the kth item, and a call with k = 1 outputs the current order",
+                factorial(self.items.len()),
+                plural(self.items.len(), "item")
+            ),
+            vec![],
+        );
+        generator.generate(self.items.len());
+        let description = format!(
+            "All {} permutations have been generated with {}, since every permutation differs from the previous one by a single swap",
+            generator.output.len(),
+            plural(generator.swaps, "swap")
+        );
+        generator.push(description, vec![]);
+    }
+}
+impl Visualizer for Permutations {
+    fn diagram(&self) -> Diagram {
+        self.draw(&self.items, &[], &[])
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![Operation::new(
+            "Heap's algorithm",
+            vec![Input::Text {
+                label: "Items",
+                default: self.items.join(" "),
+            }],
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Heap's algorithm" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                self.items = parse_items(&text, MAX_ITEMS)?;
+                self.heap(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The state of Heap's algorithm, which records a step whenever it swaps two items or outputs a permutation.
+struct Generator<'a> {
+    permutations: &'a Permutations,
+    items: Vec<String>,
+    /// The `k` and the number of swaps done so far of every call.
+    frames: Vec<(usize, usize)>,
+    output: Vec<String>,
+    swaps: usize,
+    steps: &'a mut DiagramSteps,
+}
+impl Generator<'_> {
+    fn push(&mut self, description: String, states: Vec<(usize, ElementState)>) {
+        let frames = self
+            .frames
+            .iter()
+            .map(|(k, swaps)| {
+                if *k == 1 {
+                    "heap(1)".to_string()
+                } else {
+                    format!("heap({}), swap {} of {}", k, swaps, k - 1)
+                }
+            })
+            .collect::<Vec<String>>();
+        let diagram = self.permutations.draw(&self.items, &states, &frames);
+        let step = self.steps.push(description, diagram);
+        step.lists = vec![
+            StepList::new("Depth", vec![self.frames.len().to_string()]),
+            StepList::new("Permutations", self.output.clone()),
+        ];
+    }
+    fn generate(&mut self, k: usize) {
+        self.frames.push((k, 0));
+        if k == 1 {
+            self.output.push(join(&self.items));
+            let total = factorial(self.items.len());
+            let description = format!(
+                "heap(1) outputs {}, which is permutation {} of {}",
+                join(&self.items),
+                self.output.len(),
+                total
+            );
+            let states = (0..self.items.len())
+                .map(|index| (index, ElementState::Selected))
+                .collect();
+            self.push(description, states);
+            self.frames.pop();
+            return;
+        }
+        self.generate(k - 1);
+        for i in 0..k - 1 {
+            self.frames.last_mut().unwrap().1 = i + 1;
+            // An even k swaps the items in turn with the kth one, while an odd k always swaps the first one
+            let even = k.is_multiple_of(2);
+            let j = if even { i } else { 0 };
+            self.items.swap(j, k - 1);
+            self.swaps += 1;
+            let description = format!(
+                "k = {} is {}, so swap the {} item with the {}, which moves {} to index {} and {} to index {}",
+                k,
+                if even { "even" } else { "odd" },
+                if even {
+                    ordinal(i + 1)
+                } else {
+                    "first".to_string()
+                },
+                ordinal(k),
+                self.items[k - 1],
+                k - 1,
+                self.items[j],
+                j
+            );
+            let mut states = (0..k)
+                .filter(|index| *index != j && *index != k - 1)
+                .map(|index| (index, ElementState::Frontier))
+                .collect::<Vec<(usize, ElementState)>>();
+            states.extend([(j, ElementState::Active), (k - 1, ElementState::Active)]);
+            self.push(description, states);
+            self.generate(k - 1);
+        }
+        self.frames.pop();
+    }
+}
+
+fn factorial(n: usize) -> usize {
+    (1..=n).product()
+}
+
+/// The items as a word if they are all single characters, or separated by spaces otherwise.
+fn join(items: &[String]) -> String {
+    if items.iter().all(|item| item.chars().count() == 1) {
+        items.concat()
+    } else {
+        items.join(" ")
+    }
+}
+
+fn ordinal(n: usize) -> String {
+    match n {
+        1 => "first".to_string(),
+        2 => "second".to_string(),
+        3 => "third".to_string(),
+        4 => "fourth".to_string(),
+        5 => "fifth".to_string(),
+        _ => format!("{}th", n),
+    }
+}
@@ -0,0 +1,304 @@
+use diagram::*;
+
+use crate::*;
+
+const MAX_ITEMS: usize = 6;
+const CANVAS_HEIGHT: f32 = 460.0;
+const ITEMS_Y: f32 = 60.0;
+const BITS_Y: f32 = 110.0;
+const CELL_SIZE: f32 = 50.0;
+
+/// The subsets of a set, which are all of the ways to choose some of its items. A set of `n` items has `2ⁿ` subsets,
+/// since every item is either in a subset or not. The bitmask method reads the subsets from the bits of the numbers
+/// from `0` to `2ⁿ - 1`, while backtracking decides to include or leave out every item in turn, and undoes its latest
+/// decision to try the other one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Subsets {
+    items: Vec<String>,
+}
+impl Subsets {
+    pub fn new(items: &[&str]) -> Self {
+        Self {
+            items: items.iter().map(|item| item.to_string()).collect(),
+        }
+    }
+    /// Draws the items, with the bits of the mask below them if there is one, and the call stack of backtracking.
+    fn draw(
+        &self,
+        states: &[(usize, ElementState)],
+        bits: Option<usize>,
+        frames: &[String],
+    ) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let left = (CANVAS_WIDTH - self.items.len() as f32 * CELL_SIZE) / 2.0;
+        for (index, item) in self.items.iter().enumerate() {
+            let x = left + (index as f32 + 0.5) * CELL_SIZE;
+            let mut node = DiagramNode::rect(
+                format!("item-{}", index),
+                item,
+                (x, ITEMS_Y),
+                (CELL_SIZE - 6.0, CELL_SIZE - 6.0),
+            );
+            node.state = states
+                .iter()
+                .find(|(state_index, _)| *state_index == index)
+                .map(|(_, state)| *state);
+            diagram.add_node(node);
+            if let Some(mask) = bits {
+                diagram.add_node(
+                    DiagramNode::rect(
+                        format!("bit-{}", index),
+                        mask >> index & 1,
+                        (x, BITS_Y),
+                        (CELL_SIZE - 6.0, 30.0),
+                    )
+                    .with_note(format!("bit {}", index)),
+                );
+            }
+        }
+        if bits.is_some() {
+            diagram.add_node(DiagramNode::text(
+                "bits-title",
+                "Mask",
+                (left - 40.0, BITS_Y),
+            ));
+        }
+        draw_call_stack(&mut diagram, frames);
+        diagram
+    }
+    /// The items in the subset, like "{A, C}".
+    fn subset(&self, included: &[bool]) -> String {
+        let items = self
+            .items
+            .iter()
+            .zip(included)
+            .filter(|(_, included)| **included)
+            .map(|(item, _)| item.as_str())
+            .collect::<Vec<&str>>();
+        format!("{{{}}}", items.join(", "))
+    }
+    fn push(
+        &self,
+        description: String,
+        diagram: Diagram,
+        depth: usize,
+        output: &[String],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, diagram);
+        step.lists = vec![
+            StepList::new("Depth", vec![depth.to_string()]),
+            StepList::new("Subsets", output.to_vec()),
+        ];
+    }
+    fn bitmask(&self, steps: &mut DiagramSteps) {
+        let n = self.items.len();
+        let mut output = vec![];
+        self.push(
+            format!(
+                "Count from 0 to {} = 2{} − 1. Bit i of every number says whether item i is in its subset, so the numbers go through every subset",
+                (1 << n) - 1,
+                superscript(n)
+            ),
+            self.draw(&[], Some(0), &[]),
+            0,
+            &output,
+            steps,
+        );
+        for mask in 0..1 << n {
+            let included = (0..n)
+                .map(|index| mask >> index & 1 == 1)
+                .collect::<Vec<bool>>();
+            output.push(self.subset(&included));
+            let set_bits = (0..n)
+                .filter(|index| included[*index])
+                .map(|index| index.to_string())
+                .collect::<Vec<String>>();
+            let description = match set_bits.as_slice() {
+                [] => "No bits of 0 are set, so its subset is empty".to_string(),
+                [bit] => format!(
+                    "{} only has bit {} set, so its subset is {}",
+                    mask,
+                    bit,
+                    self.subset(&included)
+                ),
+                [rest @ .., last] => format!(
+                    "{} has bits {} and {} set, so its subset is {}",
+                    mask,
+                    rest.join(", "),
+                    last,
+                    self.subset(&included)
+                ),
+            };
+            let states = (0..n)
+                .filter(|index| included[*index])
+                .map(|index| (index, ElementState::Selected))
+                .collect::<Vec<(usize, ElementState)>>();
+            self.push(
+                description,
+                self.draw(&states, Some(mask), &[]),
+                0,
+                &output,
+                steps,
+            );
+        }
+        self.push(
+            format!(
+                "All {} subsets have been generated, one for every number below 2{}",
+                output.len(),
+                superscript(n)
+            ),
+            self.draw(&[], None, &[]),
+            0,
+            &output,
+            steps,
+        );
+    }
+    fn backtracking(&self, steps: &mut DiagramSteps) {
+        let mut output = vec![];
+        self.push(
+            "Decide whether every item is in the subset, from the first item to the last. When every item has been decided, output the subset, and go back to the latest decision to try the other choice".to_string(),
+            self.draw(&[], None, &[]),
+            0,
+            &output,
+            steps,
+        );
+        self.decide(&mut vec![], &mut output, steps);
+        self.push(
+            format!(
+                "All {} subsets have been generated, since both choices have been tried for every item",
+                output.len()
+            ),
+            self.draw(&[], None, &[]),
+            0,
+            &output,
+            steps,
+        );
+    }
+    /// Decides the rest of the items after the included ones, which have been decided already.
+    fn decide(&self, included: &mut Vec<bool>, output: &mut Vec<String>, steps: &mut DiagramSteps) {
+        let index = included.len();
+        let mut states = included
+            .iter()
+            .enumerate()
+            .map(|(index, included)| {
+                (
+                    index,
+                    if *included {
+                        ElementState::Selected
+                    } else {
+                        ElementState::Rejected
+                    },
+                )
+            })
+            .collect::<Vec<(usize, ElementState)>>();
+        let frames = |included: &[bool]| {
+            included
+                .iter()
+                .zip(&self.items)
+                .map(|(included, item)| {
+                    if *included {
+                        format!("include {}", item)
+                    } else {
+                        format!("leave out {}", item)
+                    }
+                })
+                .collect::<Vec<String>>()
+        };
+        if index == self.items.len() {
+            output.push(self.subset(included));
+            self.push(
+                format!(
+                    "Every item has been decided, so output {}, which is subset {} of {}",
+                    self.subset(included),
+                    output.len(),
+                    1 << self.items.len()
+                ),
+                self.draw(&states, None, &frames(included)),
+                included.len(),
+                output,
+                steps,
+            );
+            return;
+        }
+        let item = &self.items[index];
+        for choice in [true, false] {
+            included.push(choice);
+            states.truncate(index);
+            states.push((
+                index,
+                if choice {
+                    ElementState::Selected
+                } else {
+                    ElementState::Rejected
+                },
+            ));
+            let description = if choice {
+                format!("Include {}, and decide the items after it", item)
+            } else {
+                format!(
+                    "Go back to {} and leave it out instead, and decide the items after it again",
+                    item
+                )
+            };
+            self.push(
+                description,
+                self.draw(&states, None, &frames(included)),
+                included.len(),
+                output,
+                steps,
+            );
+            self.decide(included, output, steps);
+            included.pop();
+        }
+    }
+}
+impl Visualizer for Subsets {
+    fn diagram(&self) -> Diagram {
+        self.draw(&[], None, &[])
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let items = Input::Text {
+            label: "Items",
+            default: self.items.join(" "),
+        };
+        vec![
+            Operation::new("Bitmask", vec![items.clone()]),
+            Operation::new("Backtracking", vec![items]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Bitmask" | "Backtracking" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                self.items = parse_items(&text, MAX_ITEMS)?;
+                if operation == "Bitmask" {
+                    self.bitmask(&mut steps);
+                } else {
+                    self.backtracking(&mut steps);
+                }
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn superscript(n: usize) -> String {
+    n.to_string()
+        .chars()
+        .map(|digit| match digit {
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        })
+        .collect()
+}
@@ -0,0 +1,556 @@
+use diagram::*;
+
+use crate::*;
+
+/// The largest number of steps that are shown. Backtracking can take exponential time,
+/// so the search is stopped if it takes longer than this.
+const MAX_STEPS: usize = 5000;
+const CANVAS_HEIGHT: f32 = 440.0;
+const CELL_SIZE: f32 = 40.0;
+/// The space between the 3 × 3 boxes.
+const BOX_GAP: f32 = 8.0;
+const METHODS: [&str; 2] = ["Backtracking", "Constraint propagation"];
+const PUZZLES: [(&str, &str); 4] = [
+    (
+        "Easy",
+        "534.7.9.267.195.4..98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+    ),
+    (
+        "Medium",
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+    ),
+    (
+        "Hard",
+        "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..",
+    ),
+    ("Empty", ""),
+];
+
+type Grid = [u8; 81];
+
+/// A Sudoku puzzle, where every row, column and 3 × 3 box of the grid must have the digits from 1 to 9 once. The
+/// solver fills the empty cells one at a time with a digit that doesn't conflict with the others, and erases its
+/// latest digit when a cell has no digit left. With constraint propagation, it also fills the cells where only one
+/// digit fits before every guess, and guesses at the cell with the fewest digits left.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sudoku {
+    /// The given digits, with zeros for the empty cells.
+    grid: Grid,
+}
+impl Sudoku {
+    /// The puzzle is read row by row, with dots or zeros for the empty cells. Other characters are skipped, and the
+    /// cells after the end of the puzzle are empty.
+    pub fn new(puzzle: &str) -> Self {
+        let mut grid = [0; 81];
+        for (cell, digit) in grid.iter_mut().zip(cells(puzzle)) {
+            *cell = digit;
+        }
+        Self { grid }
+    }
+    fn draw(&self, grid: &Grid, states: &[(usize, ElementState)]) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        for (cell, digit) in grid.iter().enumerate() {
+            let label = if *digit == 0 {
+                String::new()
+            } else {
+                digit.to_string()
+            };
+            let mut node = DiagramNode::rect(
+                format!("cell-{}", cell),
+                label,
+                cell_position(cell),
+                (CELL_SIZE - 2.0, CELL_SIZE - 2.0),
+            );
+            // The given digits stand out from the guesses
+            if self.grid[cell] != 0 {
+                node = node.with_fill(Fill::Group(0));
+            }
+            node.state = states
+                .iter()
+                .find(|(state_cell, _)| *state_cell == cell)
+                .map(|(_, state)| *state);
+            diagram.add_node(node);
+        }
+        diagram
+    }
+    /// Finds two given digits that are the same in a row, column or box.
+    fn given_conflict(&self) -> Option<(usize, usize)> {
+        (0..81).find_map(|cell| {
+            let digit = self.grid[cell];
+            (digit != 0)
+                .then(|| {
+                    peers(cell)
+                        .into_iter()
+                        .find(|peer| *peer > cell && self.grid[*peer] == digit)
+                        .map(|peer| (cell, peer))
+                })
+                .flatten()
+        })
+    }
+    fn solve(&self, propagate: bool, steps: &mut DiagramSteps) {
+        if let Some((first, second)) = self.given_conflict() {
+            steps.push(
+                format!(
+                    "The puzzle has no solution, since {} and {} are both {} and in the same {}",
+                    cell_name(first),
+                    cell_name(second),
+                    self.grid[first],
+                    if first / 9 == second / 9 {
+                        "row"
+                    } else if first % 9 == second % 9 {
+                        "column"
+                    } else {
+                        "box"
+                    }
+                ),
+                self.draw(
+                    &self.grid,
+                    &[
+                        (first, ElementState::Conflict),
+                        (second, ElementState::Conflict),
+                    ],
+                ),
+            );
+            return;
+        }
+        let mut solver = Solver {
+            sudoku: self,
+            grid: self.grid,
+            propagate,
+            steps,
+            guesses: 0,
+            backtracks: 0,
+        };
+        let description = if propagate {
+            "Before every guess, fill the cells where only one digit isn't already in the same row, column or 3 × 3 box, and then guess at the cell with the fewest digits left. Cells are named by their row and column, like r1c1 for the top left one"
+        } else {
+            "Fill the empty cells one at a time from the top left with the first digit that isn't already in the same row, column or 3 × 3 box. Cells are named by their row and column, like r1c1 for the top left one"
+        };
+        if solver.push(description.to_string(), vec![]).is_none() {
+            return;
+        }
+        match solver.search() {
+            Some(true) => {
+                let states = (0..81)
+                    .filter(|cell| self.grid[*cell] == 0)
+                    .map(|cell| (cell, ElementState::Selected))
+                    .collect();
+                let description = format!(
+                    "Every cell is filled, so the puzzle is solved with {} and {}",
+                    guess_count(solver.guesses),
+                    plural(solver.backtracks, "backtrack")
+                );
+                solver.push(description, states);
+            }
+            Some(false) => {
+                let description = format!(
+                    "Every choice has been tried, so the puzzle has no solution. The search took {} and {}",
+                    guess_count(solver.guesses),
+                    plural(solver.backtracks, "backtrack")
+                );
+                solver.push(description, vec![]);
+            }
+            None => {}
+        }
+    }
+    fn puzzle_text(&self) -> String {
+        self.grid
+            .iter()
+            .map(|digit| {
+                if *digit == 0 {
+                    '.'
+                } else {
+                    (b'0' + digit) as char
+                }
+            })
+            .collect()
+    }
+}
+impl Visualizer for Sudoku {
+    fn diagram(&self) -> Diagram {
+        self.draw(&self.grid, &[])
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Solve",
+                vec![Input::Choice {
+                    label: "Method",
+                    choices: METHODS.iter().map(|method| method.to_string()).collect(),
+                }],
+            ),
+            Operation::new(
+                "Load puzzle",
+                vec![Input::Choice {
+                    label: "Puzzle",
+                    choices: PUZZLES.iter().map(|(name, _)| name.to_string()).collect(),
+                }],
+            ),
+            Operation::new(
+                "Enter puzzle",
+                vec![Input::Text {
+                    label: "Digits",
+                    default: self.puzzle_text(),
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Solve" => {
+                let method = values.first().map(|value| value.text()).unwrap_or_default();
+                self.solve(method == METHODS[1], &mut steps);
+            }
+            "Load puzzle" => {
+                let name = values.first().map(|value| value.text()).unwrap_or_default();
+                let (_, puzzle) = PUZZLES
+                    .iter()
+                    .find(|(puzzle_name, _)| *puzzle_name == name)
+                    .ok_or(format!("Unknown puzzle \"{}\"", name))?;
+                *self = Self::new(puzzle);
+            }
+            "Enter puzzle" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                let count = cells(&text).count();
+                if count != 81 {
+                    return Err(format!(
+                        "The puzzle must have 81 cells row by row, with dots for the empty ones, but it has {}",
+                        count
+                    ));
+                }
+                *self = Self::new(&text);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+    fn clickable(&self) -> bool {
+        true
+    }
+    /// Changes the digit of the clicked cell to the next one, and the cell after 9 is empty.
+    fn click(&mut self, position: (f32, f32)) -> Option<DiagramSteps> {
+        let cell = (0..81).find(|cell| {
+            let (x, y) = cell_position(*cell);
+            (position.0 - x).abs() <= CELL_SIZE / 2.0 && (position.1 - y).abs() <= CELL_SIZE / 2.0
+        })?;
+        self.grid[cell] = (self.grid[cell] + 1) % 10;
+        Some(DiagramSteps::new())
+    }
+}
+
+/// The state of a search, which records a step whenever it fills or erases a cell.
+struct Solver<'a> {
+    sudoku: &'a Sudoku,
+    grid: Grid,
+    propagate: bool,
+    steps: &'a mut DiagramSteps,
+    guesses: usize,
+    backtracks: usize,
+}
+impl Solver<'_> {
+    /// Records a step, or returns `None` if the search has taken too many steps.
+    fn push(&mut self, description: String, states: Vec<(usize, ElementState)>) -> Option<()> {
+        let stopped = self.steps.len() >= MAX_STEPS;
+        let description = if stopped {
+            format!(
+                "The search was stopped after {} steps, since backtracking can take exponential time",
+                MAX_STEPS
+            )
+        } else {
+            description
+        };
+        let diagram = self.sudoku.draw(&self.grid, &states);
+        let step = self.steps.push(description, diagram);
+        step.lists = vec![
+            StepList::new("Guesses", vec![self.guesses.to_string()]),
+            StepList::new("Backtracks", vec![self.backtracks.to_string()]),
+        ];
+        (!stopped).then_some(())
+    }
+    /// The digits that aren't in the same row, column or box as the cell.
+    fn candidates(&self, cell: usize) -> Vec<u8> {
+        (1..=9)
+            .filter(|digit| self.conflicts(cell, *digit).is_empty())
+            .collect()
+    }
+    /// The cells in the same row, column or box that already have the digit.
+    fn conflicts(&self, cell: usize, digit: u8) -> Vec<usize> {
+        peers(cell)
+            .into_iter()
+            .filter(|peer| self.grid[*peer] == digit)
+            .collect()
+    }
+    /// Finds a digit that is missing from a row, column or box and only fits in one of its cells, or in none of them.
+    fn hidden_single(&self) -> Option<(String, u8, Option<usize>)> {
+        units().into_iter().find_map(|(unit, cells)| {
+            (1..=9)
+                .filter(|digit| cells.iter().all(|cell| self.grid[*cell] != *digit))
+                .find_map(|digit| {
+                    let fits = cells
+                        .iter()
+                        .copied()
+                        .filter(|cell| {
+                            self.grid[*cell] == 0 && self.conflicts(*cell, digit).is_empty()
+                        })
+                        .collect::<Vec<usize>>();
+                    match fits.as_slice() {
+                        [] => Some((unit.clone(), digit, None)),
+                        [cell] => Some((unit.clone(), digit, Some(*cell))),
+                        _ => None,
+                    }
+                })
+        })
+    }
+    /// Erases the digits that were forced after the latest guess, since that guess was wrong.
+    fn erase(&mut self, forced: &[usize]) -> String {
+        for cell in forced {
+            self.grid[*cell] = 0;
+        }
+        if forced.is_empty() {
+            String::new()
+        } else {
+            format!(
+                ", erasing the {} forced since then",
+                plural(forced.len(), "digit")
+            )
+        }
+    }
+    /// Fills the rest of the grid, and returns whether it could be filled, or `None` if the search was stopped.
+    fn search(&mut self) -> Option<bool> {
+        let mut forced = vec![];
+        while self.propagate {
+            let empty = (0..81).filter(|cell| self.grid[*cell] == 0);
+            let Some((cell, candidates)) = empty
+                .map(|cell| (cell, self.candidates(cell)))
+                .min_by_key(|(_, candidates)| candidates.len())
+            else {
+                break;
+            };
+            match candidates.len() {
+                0 => {
+                    let erased = self.erase(&forced);
+                    self.backtracks += 1;
+                    self.push(
+                        format!(
+                            "Every digit is already in the row, column or box of {}, so go back to the latest guess{}",
+                            cell_name(cell),
+                            erased
+                        ),
+                        vec![(cell, ElementState::Conflict)],
+                    )?;
+                    return Some(false);
+                }
+                1 => {
+                    self.grid[cell] = candidates[0];
+                    forced.push(cell);
+                    self.push(
+                        format!(
+                            "{} is the only digit that fits in {}, since the others are already in its row, column or box",
+                            candidates[0],
+                            cell_name(cell)
+                        ),
+                        vec![(cell, ElementState::Frontier)],
+                    )?;
+                }
+                _ => match self.hidden_single() {
+                    Some((unit, digit, Some(cell))) => {
+                        self.grid[cell] = digit;
+                        forced.push(cell);
+                        self.push(
+                            format!(
+                                "{} only fits in {} in {}, since every other empty cell of {} already has a {} in its row, column or box",
+                                digit,
+                                cell_name(cell),
+                                unit,
+                                unit,
+                                digit
+                            ),
+                            vec![(cell, ElementState::Frontier)],
+                        )?;
+                    }
+                    Some((unit, digit, None)) => {
+                        let erased = self.erase(&forced);
+                        self.backtracks += 1;
+                        self.push(
+                            format!(
+                                "{} doesn't fit anywhere in the {}, so go back to the latest guess{}",
+                                digit, unit, erased
+                            ),
+                            vec![],
+                        )?;
+                        return Some(false);
+                    }
+                    None => break,
+                },
+            }
+        }
+
+        let cell = if self.propagate {
+            (0..81)
+                .filter(|cell| self.grid[*cell] == 0)
+                .min_by_key(|cell| self.candidates(*cell).len())
+        } else {
+            (0..81).find(|cell| self.grid[*cell] == 0)
+        };
+        let Some(cell) = cell else {
+            return Some(true);
+        };
+
+        // The digits that were skipped since the latest guess at this cell, and the cells that they conflict with
+        let mut skipped = vec![];
+        let mut conflicts = vec![];
+        let mut previous = None;
+        for digit in 1..=9 {
+            let digit_conflicts = self.conflicts(cell, digit);
+            if !digit_conflicts.is_empty() {
+                // Constraint propagation only guesses digits that fit, so the conflicts aren't shown
+                if !self.propagate {
+                    skipped.push(digit.to_string());
+                    conflicts.extend(digit_conflicts);
+                }
+                continue;
+            }
+            self.grid[cell] = digit;
+            self.guesses += 1;
+            let mut description = match previous {
+                Some(previous) => {
+                    format!("Replace {} with {} in {}", previous, digit, cell_name(cell))
+                }
+                None => format!("Guess {} for {}", digit, cell_name(cell)),
+            };
+            if !skipped.is_empty() {
+                description += &format!(
+                    ", since {} already in its row, column or box",
+                    conflict_list(&skipped)
+                );
+            }
+            let mut states = vec![(cell, ElementState::Active)];
+            states.extend(
+                conflicts
+                    .drain(..)
+                    .map(|cell| (cell, ElementState::Conflict)),
+            );
+            skipped.clear();
+            self.push(description, states)?;
+            if self.search()? {
+                return Some(true);
+            }
+            previous = Some(digit);
+        }
+
+        self.grid[cell] = 0;
+        let erased = self.erase(&forced);
+        self.backtracks += 1;
+        let mut description = match previous {
+            Some(previous) => format!("No digit after {} fits in {}", previous, cell_name(cell)),
+            None => format!("No digit fits in {}", cell_name(cell)),
+        };
+        if !skipped.is_empty() {
+            description += &format!(
+                ", since {} already in its row, column or box",
+                conflict_list(&skipped)
+            );
+        }
+        description += &format!(
+            ", so {}go back to the latest guess{}",
+            if previous.is_some() {
+                "erase it and "
+            } else {
+                ""
+            },
+            erased
+        );
+        let mut states = vec![(cell, ElementState::Rejected)];
+        states.extend(
+            conflicts
+                .into_iter()
+                .map(|cell| (cell, ElementState::Conflict)),
+        );
+        self.push(description, states)?;
+        Some(false)
+    }
+}
+
+/// Reads the cells of a puzzle, where digits from 1 to 9 are given and dots or zeros are empty.
+fn cells(text: &str) -> impl Iterator<Item = u8> + '_ {
+    text.chars().filter_map(|character| match character {
+        '.' => Some(0),
+        '0'..='9' => Some(character as u8 - b'0'),
+        _ => None,
+    })
+}
+
+/// The guesses with their count, since "guess" doesn't get just an "s".
+fn guess_count(count: usize) -> String {
+    if count == 1 {
+        "1 guess".to_string()
+    } else {
+        format!("{} guesses", count)
+    }
+}
+
+fn cell_name(cell: usize) -> String {
+    format!("r{}c{}", cell / 9 + 1, cell % 9 + 1)
+}
+
+fn cell_position(cell: usize) -> (f32, f32) {
+    let (row, column) = (cell / 9, cell % 9);
+    let size = 9.0 * CELL_SIZE + 2.0 * BOX_GAP;
+    let left = (CANVAS_WIDTH - size) / 2.0;
+    let top = (CANVAS_HEIGHT - size) / 2.0;
+    (
+        left + (column as f32 + 0.5) * CELL_SIZE + (column / 3) as f32 * BOX_GAP,
+        top + (row as f32 + 0.5) * CELL_SIZE + (row / 3) as f32 * BOX_GAP,
+    )
+}
+
+/// The other cells in the same row, column or box as the cell.
+fn peers(cell: usize) -> Vec<usize> {
+    let (row, column) = (cell / 9, cell % 9);
+    let (box_row, box_column) = (row / 3 * 3, column / 3 * 3);
+    (0..81)
+        .filter(|other| {
+            let (other_row, other_column) = (other / 9, other % 9);
+            *other != cell
+                && (other_row == row
+                    || other_column == column
+                    || (other_row / 3 * 3 == box_row && other_column / 3 * 3 == box_column))
+        })
+        .collect()
+}
+
+/// The rows, columns and boxes with their names and cells.
+fn units() -> Vec<(String, Vec<usize>)> {
+    let rows = (0..9).map(|row| {
+        (
+            format!("row {}", row + 1),
+            (0..9).map(|column| row * 9 + column).collect(),
+        )
+    });
+    let columns = (0..9).map(|column| {
+        (
+            format!("column {}", column + 1),
+            (0..9).map(|row| row * 9 + column).collect(),
+        )
+    });
+    let boxes = (0..9).map(|index| {
+        let (top, left) = (index / 3 * 3, index % 3 * 3);
+        (
+            format!("box {}", index + 1),
+            (0..9)
+                .map(|cell| (top + cell / 3) * 9 + left + cell % 3)
+                .collect(),
+        )
+    });
+    rows.chain(columns).chain(boxes).collect()
+}
+
+/// The skipped digits as a list with a verb, like "1 is" or "1, 2 and 4 are".
+fn conflict_list(digits: &[String]) -> String {
+    match digits {
+        [digit] => format!("{} is", digit),
+        [rest @ .., last] => format!("{} and {} are", rest.join(", "), last),
+        [] => String::new(),
+    }
+}
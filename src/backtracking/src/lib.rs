@@ -0,0 +1,49 @@
+//! This crate contains my implementations of backtracking algorithms, like a Sudoku solver.
+//! Each algorithm records a diagram of its partial solution as it is built and undone at every step.
+pub mod backtracking_algorithms;
+
+use diagram::*;
+
+pub const CANVAS_WIDTH: f32 = 800.0;
+const STACK_X: f32 = 120.0;
+const STACK_TOP: f32 = 190.0;
+const FRAME_HEIGHT: f32 = 36.0;
+
+/// Draws the calls of a recursion as a stack of frames from the first call at the top to the current one at the
+/// bottom, which is active.
+pub fn draw_call_stack(diagram: &mut Diagram, frames: &[String]) {
+    diagram.add_node(DiagramNode::text(
+        "stack-title",
+        "Call stack",
+        (STACK_X, STACK_TOP - FRAME_HEIGHT),
+    ));
+    for (depth, frame) in frames.iter().enumerate() {
+        let mut node = DiagramNode::rect(
+            format!("frame-{}", depth),
+            frame,
+            (STACK_X, STACK_TOP + depth as f32 * FRAME_HEIGHT),
+            (180.0, FRAME_HEIGHT - 6.0),
+        );
+        if depth + 1 == frames.len() {
+            node.state = Some(ElementState::Active);
+        }
+        diagram.add_node(node);
+    }
+}
+
+/// Parses items that are separated by commas or spaces, and checks that there are between one and `max` of them.
+pub fn parse_items(text: &str, max: usize) -> Result<Vec<String>, String> {
+    let items = text
+        .split([',', ' '])
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+        .collect::<Vec<String>>();
+    if items.is_empty() || items.len() > max {
+        return Err(format!(
+            "There must be 1 to {} items separated by spaces, like \"A B C\"",
+            max
+        ));
+    }
+    Ok(items)
+}
@@ -0,0 +1,97 @@
+use rand::Rng;
+
+/// Rules that are known for the patterns that they make, with a description of the pattern.
+pub const ELEMENTARY_RULES: [(u8, &str); 6] = [
+    (30, "Chaotic, and random enough to generate random numbers"),
+    (90, "Draws the Sierpiński triangle from a single cell"),
+    (
+        110,
+        "Structures that move and collide, which can simulate any computer",
+    ),
+    (
+        184,
+        "Traffic flow, where every live cell is a car that moves right when it can",
+    ),
+    (60, "Pascal's triangle modulo 2, leaning to the right"),
+    (
+        150,
+        "Adds up every neighborhood modulo 2, which draws a fractal from a single cell",
+    ),
+];
+
+/// A one-dimensional cellular automaton where every cell is alive or dead, and its next state depends on itself and
+/// its two neighbors. The 8 possible neighborhoods give 256 rules, which are numbered by their new states: bit `k` of
+/// the rule is the new state of a cell whose neighborhood is `k` in binary.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElementaryAutomaton {
+    rule: u8,
+    /// The generations from the first one, which are the rows of the space-time diagram.
+    rows: Vec<Vec<bool>>,
+    /// Whether the cells at the ends are neighbors of each other. Otherwise the cells outside of the row are always
+    /// dead.
+    pub wrap: bool,
+}
+impl ElementaryAutomaton {
+    pub fn new(rule: u8, first_row: Vec<bool>) -> Self {
+        Self {
+            rule,
+            rows: vec![first_row],
+            wrap: true,
+        }
+    }
+    /// A row with a single live cell in the middle.
+    pub fn single_cell(width: usize) -> Vec<bool> {
+        (0..width).map(|x| x == width / 2).collect()
+    }
+    /// A row where every cell is alive with a probability of a half.
+    pub fn random(width: usize) -> Vec<bool> {
+        let mut rng = rand::thread_rng();
+        (0..width).map(|_| rng.gen_bool(0.5)).collect()
+    }
+    pub fn rule(&self) -> u8 {
+        self.rule
+    }
+    pub fn width(&self) -> usize {
+        self.rows[0].len()
+    }
+    pub fn rows(&self) -> &[Vec<bool>] {
+        &self.rows
+    }
+    /// The new state of a cell whose live neighbors and itself are given.
+    pub fn next_state(rule: u8, left: bool, center: bool, right: bool) -> bool {
+        let neighborhood = (left as u8) << 2 | (center as u8) << 1 | right as u8;
+        rule >> neighborhood & 1 == 1
+    }
+    /// Starts over from the first generation with a new rule.
+    pub fn set_rule(&mut self, rule: u8) {
+        self.rule = rule;
+        self.rows.truncate(1);
+    }
+    /// Starts over from a new first generation.
+    pub fn restart(&mut self, first_row: Vec<bool>) {
+        self.rows = vec![first_row];
+    }
+    /// Flips a cell of the first generation, and starts over from it.
+    pub fn toggle_first(&mut self, x: usize) {
+        self.rows.truncate(1);
+        if let Some(cell) = self.rows[0].get_mut(x) {
+            *cell = !*cell;
+        }
+    }
+    /// Computes the next generation from the latest one.
+    pub fn step(&mut self) {
+        let row = self.rows.last().unwrap();
+        let width = row.len();
+        let cell = |x: isize| {
+            if self.wrap {
+                row[x.rem_euclid(width as isize) as usize]
+            } else {
+                x >= 0 && x < width as isize && row[x as usize]
+            }
+        };
+        let next = (0..width as isize)
+            .map(|x| Self::next_state(self.rule, cell(x - 1), cell(x), cell(x + 1)))
+            .collect();
+        self.rows.push(next);
+    }
+}
@@ -0,0 +1,243 @@
+use rand::Rng;
+
+/// A pattern of live cells, written with `O` for live cells and `.` for dead ones like in the plaintext format of
+/// LifeWiki.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    pub name: &'static str,
+    rows: &'static [&'static str],
+    /// Whether the pattern is placed in the top left corner instead of the center, so that the gliders of a gun have
+    /// room to fly away from it.
+    top_left: bool,
+}
+impl Pattern {
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+    /// The positions of the live cells relative to the top left corner of the pattern.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.rows.iter().enumerate().flat_map(|(y, row)| {
+            row.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == 'O')
+                .map(move |(x, _)| (x, y))
+        })
+    }
+}
+
+/// Classic patterns of the Game of Life, from still lifes and oscillators to spaceships, guns and methuselahs.
+pub const LIFE_PATTERNS: [Pattern; 8] = [
+    Pattern {
+        name: "Glider",
+        rows: &[".O.", "..O", "OOO"],
+        top_left: true,
+    },
+    Pattern {
+        name: "Gosper glider gun",
+        rows: &[
+            "........................O...........",
+            "......................O.O...........",
+            "............OO......OO............OO",
+            "...........O...O....OO............OO",
+            "OO........O.....O...OO..............",
+            "OO........O...O.OO....O.O...........",
+            "..........O.....O.......O...........",
+            "...........O...O....................",
+            "............OO......................",
+        ],
+        top_left: true,
+    },
+    Pattern {
+        name: "Lightweight spaceship",
+        rows: &[".O..O", "O....", "O...O", "OOOO."],
+        top_left: false,
+    },
+    Pattern {
+        name: "Pulsar",
+        rows: &[
+            "..OOO...OOO..",
+            ".............",
+            "O....O.O....O",
+            "O....O.O....O",
+            "O....O.O....O",
+            "..OOO...OOO..",
+            ".............",
+            "..OOO...OOO..",
+            "O....O.O....O",
+            "O....O.O....O",
+            "O....O.O....O",
+            ".............",
+            "..OOO...OOO..",
+        ],
+        top_left: false,
+    },
+    Pattern {
+        name: "Pentadecathlon",
+        rows: &["..O....O..", "OO.OOOO.OO", "..O....O.."],
+        top_left: false,
+    },
+    Pattern {
+        name: "R-pentomino",
+        rows: &[".OO", "OO.", ".O."],
+        top_left: false,
+    },
+    Pattern {
+        name: "Diehard",
+        rows: &["......O.", "OO......", ".O...OOO"],
+        top_left: false,
+    },
+    Pattern {
+        name: "Acorn",
+        rows: &[".O.....", "...O...", "OO..OOO"],
+        top_left: false,
+    },
+];
+
+/// Conway's Game of Life on a grid of cells that are either alive or dead. Every generation, a dead cell with
+/// exactly three live neighbors is born, a live cell with two or three live neighbors survives, and every other cell
+/// dies or stays dead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Life {
+    width: usize,
+    height: usize,
+    /// The cells row by row, `true` for live cells.
+    cells: Vec<bool>,
+    /// Whether the edges wrap around to the other side, which makes the grid a torus. Otherwise the cells outside of
+    /// the grid are always dead.
+    pub wrap: bool,
+    generation: usize,
+}
+impl Life {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![false; width * height],
+            wrap: false,
+            generation: 0,
+        }
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// The number of generations since the cells were last changed by hand.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+    /// The number of live cells.
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|alive| **alive).count()
+    }
+    pub fn is_alive(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.cells[y * self.width + x]
+    }
+    /// Sets the state of a cell. Drawing on the grid starts the generations over.
+    pub fn set(&mut self, x: usize, y: usize, alive: bool) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = alive;
+            self.generation = 0;
+        }
+    }
+    /// The cells row by row, `true` for live cells.
+    pub fn cells(&self) -> &[bool] {
+        &self.cells
+    }
+    pub fn clear(&mut self) {
+        self.cells.fill(false);
+        self.generation = 0;
+    }
+    /// Changes the size of the grid. The cells that still fit keep their states.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        let mut cells = vec![false; width * height];
+        for y in 0..height.min(self.height) {
+            for x in 0..width.min(self.width) {
+                cells[y * width + x] = self.cells[y * self.width + x];
+            }
+        }
+        self.cells = cells;
+        self.width = width;
+        self.height = height;
+        self.generation = 0;
+    }
+    /// Makes every cell alive with the given probability.
+    pub fn randomize(&mut self, density: f64) {
+        let mut rng = rand::thread_rng();
+        for cell in self.cells.iter_mut() {
+            *cell = rng.gen_bool(density);
+        }
+        self.generation = 0;
+    }
+    /// Clears the grid and places the pattern on it. Returns `false` if the pattern doesn't fit.
+    pub fn load(&mut self, pattern: &Pattern) -> bool {
+        if pattern.width() > self.width || pattern.height() > self.height {
+            return false;
+        }
+        let (left, top) = if pattern.top_left {
+            (
+                1.min(self.width - pattern.width()),
+                1.min(self.height - pattern.height()),
+            )
+        } else {
+            (
+                (self.width - pattern.width()) / 2,
+                (self.height - pattern.height()) / 2,
+            )
+        };
+        self.clear();
+        for (x, y) in pattern.cells() {
+            self.cells[(top + y) * self.width + left + x] = true;
+        }
+        true
+    }
+    fn live_neighbors(&self, x: usize, y: usize) -> usize {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let mut count = 0;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let (mut nx, mut ny) = (x as isize + dx, y as isize + dy);
+                if self.wrap {
+                    nx = nx.rem_euclid(width);
+                    ny = ny.rem_euclid(height);
+                } else if nx < 0 || ny < 0 || nx >= width || ny >= height {
+                    continue;
+                }
+                if self.cells[(ny * width + nx) as usize] {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+    /// Advances the cells by one generation, and returns the number of births and deaths.
+    pub fn step(&mut self) -> (usize, usize) {
+        let (mut births, mut deaths) = (0, 0);
+        let mut cells = self.cells.clone();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let index = y * self.width + x;
+                let alive = matches!(
+                    (self.cells[index], self.live_neighbors(x, y)),
+                    (true, 2 | 3) | (false, 3)
+                );
+                if alive && !self.cells[index] {
+                    births += 1;
+                } else if !alive && self.cells[index] {
+                    deaths += 1;
+                }
+                cells[index] = alive;
+            }
+        }
+        self.cells = cells;
+        self.generation += 1;
+        (births, deaths)
+    }
+}
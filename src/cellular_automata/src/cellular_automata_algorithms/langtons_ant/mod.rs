@@ -0,0 +1,179 @@
+/// The most colors that a rule can have.
+pub const MAX_COLORS: usize = 12;
+
+/// Rules that are known for the patterns that they make, with a description of the pattern.
+pub const ANT_RULES: [(&str, &str); 6] = [
+    (
+        "RL",
+        "Langton's ant, which builds a highway after about 10 000 steps of chaos",
+    ),
+    (
+        "RLR",
+        "Grows chaotically, and never seems to build a highway",
+    ),
+    ("LLRR", "Grows symmetrically, like a brain"),
+    ("LRRRRRLLR", "Fills a square around itself"),
+    ("LLRRRLRLRLLR", "Builds a convoluted highway"),
+    ("RRLLLRLLLRRR", "Fills a triangle that grows and moves"),
+];
+
+/// How the ant turns on a cell of a color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Turn {
+    Left,
+    Right,
+    /// Keeps going straight ahead.
+    None,
+    /// Turns around.
+    UTurn,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+impl Direction {
+    fn turn(self, turn: Turn) -> Self {
+        let directions = [Self::North, Self::East, Self::South, Self::West];
+        let index = directions.iter().position(|d| *d == self).unwrap();
+        let offset = match turn {
+            Turn::None => 0,
+            Turn::Right => 1,
+            Turn::UTurn => 2,
+            Turn::Left => 3,
+        };
+        directions[(index + offset) % 4]
+    }
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Self::North => (0, -1),
+            Self::East => (1, 0),
+            Self::South => (0, 1),
+            Self::West => (-1, 0),
+        }
+    }
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::North => "north",
+            Self::East => "east",
+            Self::South => "south",
+            Self::West => "west",
+        }
+    }
+}
+
+/// Parses a rule like "RL", where every letter is the turn on a color: `L` for left, `R` for right, `N` for none and
+/// `U` for a U-turn.
+pub fn parse_ant_rule(text: &str) -> Result<Vec<Turn>, String> {
+    let rule = text
+        .trim()
+        .chars()
+        .map(|c| match c.to_ascii_uppercase() {
+            'L' => Ok(Turn::Left),
+            'R' => Ok(Turn::Right),
+            'N' => Ok(Turn::None),
+            'U' => Ok(Turn::UTurn),
+            _ => Err(format!(
+                "\"{}\" isn't a turn. The turns are L for left, R for right, N for none and U for a U-turn",
+                c
+            )),
+        })
+        .collect::<Result<Vec<Turn>, String>>()?;
+    if rule.len() < 2 || rule.len() > MAX_COLORS {
+        return Err(format!(
+            "The rule must have 2 to {} turns, one for every color",
+            MAX_COLORS
+        ));
+    }
+    Ok(rule)
+}
+
+/// Langton's ant, which walks on a grid of colored cells. On every step, the ant turns according to the color of its
+/// cell, changes the cell to the next color and moves forward. With more than two colors, the ant is a multi-color
+/// turmite, and the rule tells which way to turn on every color.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LangtonsAnt {
+    width: usize,
+    height: usize,
+    /// The colors of the cells row by row, from 0 to the number of turns in the rule.
+    cells: Vec<u8>,
+    rule: Vec<Turn>,
+    ant: (usize, usize),
+    direction: Direction,
+    /// Whether the ant walks over an edge to the other side instead of stopping.
+    pub wrap: bool,
+    steps: usize,
+}
+impl LangtonsAnt {
+    /// An ant in the center of an empty grid, facing north.
+    pub fn new(width: usize, height: usize, rule: Vec<Turn>) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![0; width * height],
+            rule,
+            ant: (width / 2, height / 2),
+            direction: Direction::North,
+            wrap: false,
+            steps: 0,
+        }
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn rule(&self) -> &[Turn] {
+        &self.rule
+    }
+    /// The colors of the cells row by row.
+    pub fn cells(&self) -> &[u8] {
+        &self.cells
+    }
+    pub fn ant(&self) -> (usize, usize) {
+        self.ant
+    }
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+    /// Removes the ant's trail, and moves it back to the center of the grid with a new rule.
+    pub fn reset(&mut self, width: usize, height: usize, rule: Vec<Turn>) {
+        *self = Self {
+            wrap: self.wrap,
+            ..Self::new(width, height, rule)
+        };
+    }
+    /// Changes a cell to the next color, which lets the ant start from something other than an empty grid.
+    pub fn cycle_color(&mut self, x: usize, y: usize) {
+        if x < self.width && y < self.height {
+            let cell = &mut self.cells[y * self.width + x];
+            *cell = (*cell + 1) % self.rule.len() as u8;
+        }
+    }
+    /// Moves the ant by one step. Returns `false` if the ant can't move, since it would walk off the grid.
+    pub fn step(&mut self) -> bool {
+        let (x, y) = self.ant;
+        let index = y * self.width + x;
+        let direction = self.direction.turn(self.rule[self.cells[index] as usize]);
+        let (dx, dy) = direction.delta();
+        let (mut nx, mut ny) = (x as isize + dx, y as isize + dy);
+        if self.wrap {
+            nx = nx.rem_euclid(self.width as isize);
+            ny = ny.rem_euclid(self.height as isize);
+        } else if nx < 0 || ny < 0 || nx >= self.width as isize || ny >= self.height as isize {
+            return false;
+        }
+        self.direction = direction;
+        self.cells[index] = (self.cells[index] + 1) % self.rule.len() as u8;
+        self.ant = (nx as usize, ny as usize);
+        self.steps += 1;
+        true
+    }
+}
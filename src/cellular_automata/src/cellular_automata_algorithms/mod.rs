@@ -0,0 +1,9 @@
+//! A collection of automata where simple local rules are applied to every cell at once, and complex patterns
+//! emerge from them.
+mod elementary;
+mod game_of_life;
+mod langtons_ant;
+
+pub use elementary::{ElementaryAutomaton, ELEMENTARY_RULES};
+pub use game_of_life::{Life, Pattern, LIFE_PATTERNS};
+pub use langtons_ant::{parse_ant_rule, Direction, LangtonsAnt, Turn, ANT_RULES, MAX_COLORS};
@@ -0,0 +1,3 @@
+//! This crate contains my implementations of cellular automata, like Conway's Game of Life.
+//! Each automaton is a grid of cells whose states are updated from their neighbors one generation at a time.
+pub mod cellular_automata_algorithms;
@@ -8,6 +8,9 @@ use yew_hooks::{use_async, use_mount};
 #[derive(Clone, Properties, PartialEq, Eq)]
 pub struct AlgoDescProps {
     pub algorithm: String,
+    /// The directory of the README files, which is the name of the page followed by `_algorithms` by default.
+    #[prop_or_default]
+    pub directory: Option<String>,
 }
 
 #[function_component]
@@ -17,25 +20,28 @@ pub fn AlgoDesc(props: &AlgoDescProps) -> Html {
     {
         let url = url.clone();
         use_effect_with_deps(
-            move |algorithm| {
+            move |(algorithm, directory)| {
                 let location = window().unwrap().location();
                 let origin = location.origin().unwrap();
                 let pathname = location.pathname().unwrap();
-                // sorting or pathfinding etc.
-                let algorithm_type = pathname.split('/').collect::<Vec<&str>>()[1];
+                let directory = directory.clone().unwrap_or_else(|| {
+                    // sorting or pathfinding etc.
+                    let algorithm_type = pathname.split('/').collect::<Vec<&str>>()[1];
+                    format!("{}_algorithms", algorithm_type)
+                });
 
                 url.set(format!(
-                    "{}/{}_algorithms/{}/README.md",
+                    "{}/{}/{}/README.md",
                     origin,
-                    algorithm_type,
+                    directory,
                     algorithm
                         .to_lowercase()
-                        .replace(' ', "_")
+                        .replace([' ', '-'], "_")
                         .replace('*', "_star")
                 ));
                 || ()
             },
-            props.algorithm.clone(),
+            (props.algorithm.clone(), props.directory.clone()),
         );
     }
 
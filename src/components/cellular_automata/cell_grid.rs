@@ -0,0 +1,365 @@
+use crate::components::grid_layout::GridLayout;
+use pathfinding::Coord;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, Element, HtmlCanvasElement};
+use yew::prelude::*;
+use yew_hooks::use_size;
+
+const COLOR_CURSOR: &str = "#aa44ff";
+const COLOR_MARKER: &str = "#ff4d4d";
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct CellGridProps {
+    pub width: usize,
+    pub height: usize,
+    /// The states of the cells row by row. Cells in state 0 are empty, and the other states are drawn with the color
+    /// at the state's index minus one.
+    pub cells: Rc<Vec<u8>>,
+    pub colors: Vec<&'static str>,
+    /// Whether the lines between the cells are drawn. Large grids are easier to read without them.
+    #[prop_or(true)]
+    pub show_grid: bool,
+    /// A cell that is marked on top of the others, like the position of Langton's ant.
+    #[prop_or_default]
+    pub marker: Option<Coord>,
+    /// Describes the grid and its keyboard controls to screen readers.
+    pub label: String,
+    #[prop_or_default]
+    pub on_click_cell: Callback<Coord>,
+    #[prop_or_default]
+    pub on_draw_end: Callback<()>,
+}
+
+/// A square grid of colored cells on a canvas, drawn with the same layout as the pathfinding grid. Cells can be
+/// drawn by clicking and dragging, or with the keyboard.
+#[function_component]
+pub fn CellGrid(props: &CellGridProps) -> Html {
+    let CellGridProps {
+        width,
+        height,
+        cells,
+        colors,
+        show_grid,
+        marker,
+        label,
+        on_click_cell,
+        on_draw_end,
+    } = props.clone();
+
+    let cell_canvas_ref = use_node_ref();
+    let cell_canvas_size = use_size(cell_canvas_ref.clone());
+    let cell_canvas: UseStateHandle<Option<HtmlCanvasElement>> = use_state(|| None);
+    let cell_ctx: UseStateHandle<Option<CanvasRenderingContext2d>> = use_state(|| None);
+
+    let foreground_canvas_ref = use_node_ref();
+    let foreground_canvas: UseStateHandle<Option<HtmlCanvasElement>> = use_state(|| None);
+    let foreground_ctx: UseStateHandle<Option<CanvasRenderingContext2d>> = use_state(|| None);
+
+    // The cell selected with the keyboard
+    let cursor: UseStateHandle<Option<Coord>> = use_state_eq(|| None);
+
+    // Move the cursor with the arrow keys and draw with enter or space
+    let onkeydown = {
+        let cursor = cursor.clone();
+        let on_click_cell = on_click_cell.clone();
+        let on_draw_end = on_draw_end.clone();
+
+        Callback::from(move |e: KeyboardEvent| {
+            let Coord { x, y } =
+                cursor.unwrap_or(Coord::new(width as isize / 2, height as isize / 2));
+            let new_cursor = match e.key().as_str() {
+                "ArrowUp" => Coord::new(x, (y - 1).max(0)),
+                "ArrowDown" => Coord::new(x, (y + 1).min(height as isize - 1)),
+                "ArrowLeft" => Coord::new((x - 1).max(0), y),
+                "ArrowRight" => Coord::new((x + 1).min(width as isize - 1), y),
+                "Enter" | " " => {
+                    on_click_cell.emit(Coord::new(x, y));
+                    on_draw_end.emit(());
+                    Coord::new(x, y)
+                }
+                "Escape" => {
+                    cursor.set(None);
+                    return;
+                }
+                _ => return,
+            };
+            e.prevent_default();
+            cursor.set(Some(new_cursor));
+        })
+    };
+
+    let draw_cells = {
+        let cells = cells.clone();
+        let canvas = cell_canvas.clone();
+        let ctx = cell_ctx.clone();
+
+        move || {
+            if let Some(canvas) = canvas.as_ref() as Option<&HtmlCanvasElement> {
+                if let Some(ctx) = ctx.as_ref() {
+                    let canvas_width = canvas.width() as f64;
+                    let canvas_height = canvas.height() as f64;
+                    let layout = GridLayout::new(false, width, height, canvas_width, canvas_height);
+
+                    ctx.clear_rect(0.0, 0.0, canvas_width, canvas_height);
+
+                    // Every state is filled with a single path, which is much faster than filling every cell
+                    for (index, color) in colors.iter().enumerate() {
+                        let state = index as u8 + 1;
+                        ctx.begin_path();
+                        ctx.set_fill_style(&(*color).into());
+                        for (i, _) in cells.iter().enumerate().filter(|(_, s)| **s == state) {
+                            layout.add_cell(
+                                ctx,
+                                Coord::new((i % width) as isize, (i / width) as isize),
+                            );
+                        }
+                        ctx.fill();
+                    }
+                }
+            }
+        }
+    };
+
+    let draw_foreground = {
+        let cursor = cursor.clone();
+        let canvas = foreground_canvas.clone();
+        let ctx = foreground_ctx.clone();
+
+        move || {
+            if let Some(canvas) = canvas.as_ref() as Option<&HtmlCanvasElement> {
+                if let Some(ctx) = ctx.as_ref() {
+                    let canvas_width = canvas.width() as f64;
+                    let canvas_height = canvas.height() as f64;
+                    let layout = GridLayout::new(false, width, height, canvas_width, canvas_height);
+
+                    ctx.clear_rect(0.0, 0.0, canvas_width, canvas_height);
+
+                    // The marker is larger than a cell so that it can be seen on large grids
+                    if let Some(marker) = marker {
+                        let (x, y) = layout.center(marker);
+                        ctx.begin_path();
+                        ctx.set_fill_style(&COLOR_MARKER.into());
+                        let _ = ctx.arc(
+                            x,
+                            y,
+                            layout.cell_width.min(layout.cell_height) * 0.6,
+                            0.0,
+                            std::f64::consts::TAU,
+                        );
+                        ctx.fill();
+                    }
+
+                    if let Some(cursor) = *cursor {
+                        ctx.begin_path();
+                        ctx.set_stroke_style(&COLOR_CURSOR.into());
+                        ctx.set_line_width(layout.cell_width * 0.15);
+                        layout.add_cell(ctx, cursor);
+                        ctx.stroke();
+                    }
+                }
+            }
+        }
+    };
+
+    if (*cell_canvas).is_none() {
+        if let Some(canvas_el) = cell_canvas_ref.cast::<HtmlCanvasElement>() {
+            cell_ctx.set(Some(
+                canvas_el
+                    .get_context("2d")
+                    .unwrap()
+                    .unwrap()
+                    .dyn_into()
+                    .unwrap(),
+            ));
+
+            cell_canvas.set(Some(canvas_el));
+        }
+    }
+
+    if (*foreground_canvas).is_none() {
+        if let Some(canvas_el) = foreground_canvas_ref.cast::<HtmlCanvasElement>() {
+            foreground_ctx.set(Some(
+                canvas_el
+                    .get_context("2d")
+                    .unwrap()
+                    .unwrap()
+                    .dyn_into()
+                    .unwrap(),
+            ));
+
+            foreground_canvas.set(Some(canvas_el));
+        }
+    }
+
+    {
+        let draw_cells = draw_cells.clone();
+
+        use_effect_with_deps(
+            move |_| {
+                draw_cells();
+                || ()
+            },
+            (cells, props.colors.clone()),
+        );
+    }
+
+    {
+        let draw_foreground = draw_foreground.clone();
+
+        use_effect_with_deps(
+            move |_| {
+                draw_foreground();
+                || ()
+            },
+            (*cursor, marker),
+        );
+    }
+
+    use_effect_with_deps(
+        move |_| {
+            draw_cells();
+            draw_foreground();
+            || ()
+        },
+        (width, height, cell_canvas_size, (*cell_canvas).is_some()),
+    );
+
+    // Gets the cell under the pointer, or `None` if the pointer is outside of the grid
+    let cell_at = {
+        let canvas_ref = foreground_canvas_ref.clone();
+
+        move |e: &PointerEvent| {
+            let el = canvas_ref.get().unwrap().dyn_into::<Element>().unwrap();
+            let layout = GridLayout::new(
+                false,
+                width,
+                height,
+                el.client_width() as f64,
+                el.client_height() as f64,
+            );
+            layout.cell_at(e.offset_x() as f64, e.offset_y() as f64, width, height)
+        }
+    };
+
+    // The cell that the pointer was last dragged over, so that every cell is only drawn once per drag
+    let last_cell = use_mut_ref(|| None::<Coord>);
+
+    let onpointerdown = {
+        let cell_at = cell_at.clone();
+        let last_cell = last_cell.clone();
+        let on_click_cell = on_click_cell.clone();
+
+        move |e: PointerEvent| {
+            if e.button() != 0 {
+                return;
+            }
+            e.prevent_default();
+
+            // Keep getting the pointer's events while it is down, even if it leaves the grid
+            if let Some(el) = e.target_dyn_into::<Element>() {
+                let _ = el.set_pointer_capture(e.pointer_id());
+            }
+
+            if let Some(cell) = cell_at(&e) {
+                *last_cell.borrow_mut() = Some(cell);
+                on_click_cell.emit(cell);
+            }
+        }
+    };
+
+    let onpointermove = {
+        let last_cell = last_cell.clone();
+
+        move |e: PointerEvent| {
+            if e.buttons() != 1 {
+                return;
+            }
+            if let Some(cell) = cell_at(&e) {
+                e.prevent_default();
+                if *last_cell.borrow() != Some(cell) {
+                    *last_cell.borrow_mut() = Some(cell);
+                    on_click_cell.emit(cell);
+                }
+            }
+        }
+    };
+
+    let onpointerup = move |_: PointerEvent| {
+        if last_cell.borrow_mut().take().is_some() {
+            on_draw_end.emit(());
+        }
+    };
+
+    let aspect_ratio = GridLayout::aspect_ratio(false, width, height);
+    let (cell_width, cell_height) = (
+        cell_canvas_size.0 as f32 / width as f32,
+        cell_canvas_size.1 as f32 / height as f32,
+    );
+
+    html! {
+        <div
+            class="cell-grid"
+            style={format!("aspect-ratio: {}", aspect_ratio)}
+            tabindex="0"
+            role="application"
+            aria-label={label}
+            aria-describedby="cellGridStatus"
+            {onkeydown}
+        >
+            <canvas
+                ref={cell_canvas_ref}
+                style={format!("z-index: 1; aspect-ratio: {};", aspect_ratio)}
+                width={cell_canvas_size.0.to_string()}
+                height={cell_canvas_size.1.to_string()}
+            >
+            </canvas>
+
+            {
+                if show_grid {
+                    html! {
+                        <svg
+                            xmlns="http://www.w3.org/2000/svg"
+                            style={"z-index: 2"}
+                            width={cell_canvas_size.0.to_string()}
+                            height={cell_canvas_size.1.to_string()}
+                        >
+                            <defs>
+                                <pattern
+                                    id="cellGridPattern"
+                                    width={cell_width.to_string()}
+                                    height={cell_height.to_string()}
+                                    patternUnits="userSpaceOnUse"
+                                >
+                                    <path d={format!("M {} 0 L 0 0 0 {}", cell_width, cell_height)} />
+                                </pattern>
+                            </defs>
+
+                            <rect
+                                class="grid"
+                                fill="url(#cellGridPattern)"
+                                // + 1.5 to make the right and bottom borders visible
+                                width={(cell_canvas_size.0 as f32 + 1.5).to_string()}
+                                height={(cell_canvas_size.1 as f32 + 1.5).to_string()}
+                            />
+                        </svg>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+
+            <canvas
+                ref={foreground_canvas_ref}
+                style={format!("z-index: 3; aspect-ratio: {};", aspect_ratio)}
+                width={cell_canvas_size.0.to_string()}
+                height={cell_canvas_size.1.to_string()}
+                onpointerdown={onpointerdown}
+                onpointermove={onpointermove}
+                onpointerup={onpointerup.clone()}
+                onpointercancel={onpointerup}
+            >
+            </canvas>
+        </div>
+    }
+}
@@ -0,0 +1,3 @@
+mod cell_grid;
+
+pub use cell_grid::CellGrid;
@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+
+use diagram::{Diagram, DiagramEdge, DiagramNode, Figure, FigureKind, Fill, Shape};
+use web_sys::Element;
+use yew::prelude::*;
+
+use crate::components::graph::{element_state_class, GROUP_COLOR_COUNT};
+
+/// The gap between the end of an edge and the outline of the node that it points to.
+const EDGE_GAP: f32 = 3.0;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct DiagramViewProps {
+    pub diagram: Diagram,
+    /// A description of what the diagram shows for screen readers.
+    #[prop_or_default]
+    pub label: String,
+    /// Called with the position of a click in the coordinates of the diagram, if the diagram can be clicked.
+    #[prop_or_default]
+    pub onclick: Option<Callback<(f32, f32)>>,
+}
+
+/// Draws a diagram. The nodes and edges are keyed, so they glide to their new places when the diagram changes.
+#[function_component]
+pub fn DiagramView(props: &DiagramViewProps) -> Html {
+    let diagram = &props.diagram;
+    let nodes = diagram
+        .nodes
+        .iter()
+        .map(|node| (node.key.as_str(), node))
+        .collect::<BTreeMap<&str, &DiagramNode>>();
+    let svg_ref = use_node_ref();
+
+    let onclick = props.onclick.clone().map(|onclick| {
+        let svg_ref = svg_ref.clone();
+        let (width, height) = (diagram.width, diagram.height);
+
+        Callback::from(move |e: MouseEvent| {
+            let Some(svg) = svg_ref.cast::<Element>() else {
+                return;
+            };
+            let rect = svg.get_bounding_client_rect();
+            // The diagram is scaled to fit the element and centered in it, like the view box is by default
+            let scale = (rect.width() as f32 / width).min(rect.height() as f32 / height);
+            if scale <= 0.0 {
+                return;
+            }
+            let x = (e.client_x() as f32
+                - rect.left() as f32
+                - (rect.width() as f32 - width * scale) / 2.0)
+                / scale;
+            let y = (e.client_y() as f32
+                - rect.top() as f32
+                - (rect.height() as f32 - height * scale) / 2.0)
+                / scale;
+            if (0.0..=width).contains(&x) && (0.0..=height).contains(&y) {
+                onclick.emit((x, y));
+            }
+        })
+    });
+
+    html! {
+        <svg
+            ref={svg_ref}
+            class={classes!("diagram", onclick.is_some().then_some("clickable"))}
+            {onclick}
+            viewBox={format!("0 0 {} {}", diagram.width, diagram.height)}
+            role="img"
+            aria-label={props.label.clone()}
+        >
+            <defs>
+                <marker
+                    id="diagramArrow"
+                    viewBox="0 0 10 10"
+                    refX="10"
+                    refY="5"
+                    markerWidth="5"
+                    markerHeight="5"
+                    orient="auto-start-reverse"
+                >
+                    <path d="M 0 0 L 10 5 L 0 10 z" />
+                </marker>
+            </defs>
+            <g class="figures">
+                { diagram.figures.iter().map(view_figure).collect::<Html>() }
+            </g>
+            <g class="edges">
+                {
+                    diagram.edges.iter().filter_map(|edge| {
+                        Some(view_edge(edge, nodes.get(edge.from.as_str())?, nodes.get(edge.to.as_str())?))
+                    }).collect::<Html>()
+                }
+            </g>
+            <g class="nodes">
+                { diagram.nodes.iter().map(view_node).collect::<Html>() }
+            </g>
+        </svg>
+    }
+}
+
+fn state_class(state: Option<diagram::ElementState>) -> Option<&'static str> {
+    state.map(element_state_class)
+}
+
+fn points_attribute(points: &[(f32, f32)]) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("{},{}", x, y))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn view_figure(figure: &Figure) -> Html {
+    let class = classes!(
+        "figure",
+        state_class(figure.state),
+        figure.dashed.then_some("dashed")
+    );
+
+    match &figure.kind {
+        FigureKind::Line((x1, y1), (x2, y2)) => html! {
+            <line {class} x1={x1.to_string()} y1={y1.to_string()} x2={x2.to_string()} y2={y2.to_string()} />
+        },
+        FigureKind::Polyline(points) => html! {
+            <polyline {class} points={points_attribute(points)} />
+        },
+        FigureKind::Polygon(points) => html! {
+            <polygon class={classes!(class, "filled")} points={points_attribute(points)} />
+        },
+        FigureKind::Circle { center, radius } => html! {
+            <circle
+                {class}
+                cx={center.0.to_string()}
+                cy={center.1.to_string()}
+                r={radius.to_string()}
+            />
+        },
+        FigureKind::Rect { position, size } => html! {
+            <rect
+                class={classes!(class, "filled")}
+                x={position.0.to_string()}
+                y={position.1.to_string()}
+                width={size.0.to_string()}
+                height={size.1.to_string()}
+            />
+        },
+    }
+}
+
+/// Moves a point from the center of a node to its outline in the direction of `towards`.
+fn clip_to_outline(node: &DiagramNode, towards: (f32, f32), gap: f32) -> (f32, f32) {
+    let (x, y) = node.position;
+    let (dx, dy) = (towards.0 - x, towards.1 - y);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length < f32::EPSILON {
+        return (x, y);
+    }
+    let distance = match node.shape {
+        Shape::Circle { radius } => radius,
+        Shape::Rect { width, height } => {
+            let to_side = if dx.abs() < f32::EPSILON {
+                f32::INFINITY
+            } else {
+                width / 2.0 / dx.abs()
+            };
+            let to_top = if dy.abs() < f32::EPSILON {
+                f32::INFINITY
+            } else {
+                height / 2.0 / dy.abs()
+            };
+            to_side.min(to_top) * length
+        }
+        Shape::Text => 10.0,
+    } + gap;
+    (x + dx / length * distance, y + dy / length * distance)
+}
+
+fn view_edge(edge: &DiagramEdge, from: &DiagramNode, to: &DiagramNode) -> Html {
+    let ((x1, y1), (x2, y2)) = (from.position, to.position);
+    let (dx, dy) = (x2 - x1, y2 - y1);
+    // The control point of the curve is to the left of the middle of the edge
+    let control = (
+        (x1 + x2) / 2.0 + dy * edge.bend,
+        (y1 + y2) / 2.0 - dx * edge.bend,
+    );
+    let start = clip_to_outline(from, control, 0.0);
+    let end = clip_to_outline(to, control, if edge.directed { EDGE_GAP } else { 0.0 });
+    let path = format!(
+        "M {} {} Q {} {} {} {}",
+        start.0, start.1, control.0, control.1, end.0, end.1
+    );
+    // The middle of the quadratic curve
+    let label_position = (
+        0.25 * start.0 + 0.5 * control.0 + 0.25 * end.0,
+        0.25 * start.1 + 0.5 * control.1 + 0.25 * end.1,
+    );
+
+    html! {
+        <g
+            key={format!("{}->{}", edge.from, edge.to)}
+            class={classes!("edge", state_class(edge.state), edge.dashed.then_some("dashed"))}
+        >
+            // The path is also set in the style so that browsers can animate it
+            <path
+                d={path.clone()}
+                style={format!("d: path('{}')", path)}
+                marker-end={edge.directed.then_some("url(#diagramArrow)")}
+            />
+            {
+                if let Some(label) = &edge.label {
+                    html! {
+                        <text
+                            class="edge-label"
+                            x={label_position.0.to_string()}
+                            y={label_position.1.to_string()}
+                        >{ label }</text>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+        </g>
+    }
+}
+
+fn fill_class(fill: Fill) -> Option<String> {
+    match fill {
+        Fill::Default => None,
+        Fill::Red => Some("fill-red".to_string()),
+        Fill::Black => Some("fill-black".to_string()),
+        Fill::Group(group) => Some(format!("group-{}", group % GROUP_COLOR_COUNT)),
+        Fill::Heat(_) => Some("heat".to_string()),
+    }
+}
+
+fn view_node(node: &DiagramNode) -> Html {
+    let (x, y) = node.position;
+    let mut style = format!("transform: translate({}px, {}px);", x, y);
+    if let Fill::Heat(heat) = node.fill {
+        style.push_str(&format!(" --heat: {};", heat.clamp(0.0, 1.0)));
+    }
+    let (shape, note_y) = match node.shape {
+        Shape::Circle { radius } => (html! { <circle r={radius.to_string()} /> }, radius + 14.0),
+        Shape::Rect { width, height } => (
+            html! {
+                <rect
+                    x={(-width / 2.0).to_string()}
+                    y={(-height / 2.0).to_string()}
+                    width={width.to_string()}
+                    height={height.to_string()}
+                    rx="4"
+                />
+            },
+            height / 2.0 + 14.0,
+        ),
+        Shape::Text => (html! {}, 18.0),
+    };
+
+    html! {
+        <g
+            key={node.key.clone()}
+            class={classes!(
+                "node",
+                state_class(node.state),
+                fill_class(node.fill),
+                matches!(node.shape, Shape::Text).then_some("text-only")
+            )}
+            {style}
+        >
+            { shape }
+            <text class="label">{ &node.label }</text>
+            {
+                if let Some(note) = &node.note {
+                    html! { <text class="note" y={note_y.to_string()}>{ note }</text> }
+                } else {
+                    html! {}
+                }
+            }
+        </g>
+    }
+}
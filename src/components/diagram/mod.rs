@@ -0,0 +1,7 @@
+mod diagram_view;
+mod operation_controls;
+mod visualizer_page;
+
+pub use diagram_view::DiagramView;
+pub use operation_controls::OperationControls;
+pub use visualizer_page::{VisualizerInfo, VisualizerPage};
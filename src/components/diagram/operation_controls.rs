@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use diagram::{Input, Operation, Value};
+use yew::prelude::*;
+
+use crate::components::input_items::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct OperationControlsProps {
+    pub operations: Vec<Operation>,
+    /// Runs the operation with the given name with the values of its inputs.
+    pub on_run: Callback<(String, Vec<Value>)>,
+    /// Why the last operation couldn't be run.
+    #[prop_or_default]
+    pub error: Option<String>,
+}
+
+/// Shows the inputs of every operation of a visualizer along with a button that runs the operation.
+#[function_component]
+pub fn OperationControls(props: &OperationControlsProps) -> Html {
+    let OperationControlsProps {
+        operations,
+        on_run,
+        error,
+    } = props.clone();
+
+    // The values that have been changed from the defaults by the labels of their inputs
+    let values = use_state_eq(BTreeMap::<&'static str, Value>::new);
+
+    let value_of = |input: &Input| {
+        values
+            .get(input.label())
+            .cloned()
+            .unwrap_or_else(|| input.default_value())
+    };
+
+    let set_value = {
+        let values = values.clone();
+
+        move |label: &'static str, value: Value| {
+            let mut new_values = (*values).clone();
+            new_values.insert(label, value);
+            values.set(new_values);
+        }
+    };
+
+    let operation_forms = operations
+        .iter()
+        .map(|operation| {
+            let inputs = operation
+                .inputs
+                .iter()
+                .map(|input| {
+                    let label = input.label();
+                    match input {
+                        Input::Number { min, max, .. } => {
+                            let set_value = set_value.clone();
+                            html! {
+                                <IntInput<i64>
+                                    title={label}
+                                    value={value_of(input).number()}
+                                    min={*min}
+                                    max={*max}
+                                    oninput={Callback::from(move |number| set_value(label, Value::Number(number)))}
+                                />
+                            }
+                        }
+                        Input::Text { .. } => {
+                            let set_value = set_value.clone();
+                            html! {
+                                <TextInput
+                                    title={label}
+                                    value={value_of(input).text()}
+                                    oninput={Callback::from(move |text| set_value(label, Value::Text(text)))}
+                                />
+                            }
+                        }
+                        Input::Choice { choices, .. } => {
+                            let set_value = set_value.clone();
+                            html! {
+                                <SelectInput
+                                    title={label}
+                                    options={choices.clone()}
+                                    selected_value={value_of(input).text()}
+                                    onchange={Callback::from(move |choice| set_value(label, Value::Text(choice)))}
+                                />
+                            }
+                        }
+                        Input::Slider { min, max, step, .. } => {
+                            let set_value = set_value.clone();
+                            let value = value_of(input).decimal();
+                            html! {
+                                <RangeInput<f64>
+                                    title={format!("{} ({})", label, value)}
+                                    {value}
+                                    min={*min}
+                                    max={*max}
+                                    step={*step}
+                                    oninput={Callback::from(move |decimal| set_value(label, Value::Decimal(decimal)))}
+                                />
+                            }
+                        }
+                    }
+                })
+                .collect::<Html>();
+
+            let onclick = {
+                let on_run = on_run.clone();
+                let name = operation.name.to_string();
+                let operation_values = operation.inputs.iter().map(value_of).collect::<Vec<Value>>();
+
+                Callback::from(move |_| {
+                    on_run.emit((name.clone(), operation_values.clone()));
+                })
+            };
+
+            html! {
+                <div class="operation">
+                    { inputs }
+                    <Button title={operation.name} {onclick} />
+                </div>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="operation-controls">
+            { operation_forms }
+            {
+                if let Some(error) = error {
+                    html! { <p class="error" role="alert">{ error }</p> }
+                } else {
+                    html! {}
+                }
+            }
+        </div>
+    }
+}
@@ -0,0 +1,293 @@
+use std::rc::Rc;
+
+use diagram::{DiagramSteps, ElementState, Value, Visualizer};
+use yew::prelude::*;
+use yew_hooks::use_title;
+
+use super::{DiagramView, OperationControls};
+use crate::components::{
+    algo_desc::AlgoDesc,
+    collapsible::Collapsible,
+    graph::{StepLegend, StepLists},
+    input_items::*,
+    sidebar::Sidebar,
+    step_slider::StepSlider,
+};
+use crate::utils::prefers_reduced_motion;
+
+pub type CreateVisualizerFunc = fn() -> Box<dyn Visualizer>;
+
+/// A visualizer that can be chosen on a [`VisualizerPage`].
+#[derive(Clone, Debug)]
+pub struct VisualizerInfo {
+    pub name: String,
+    /// What the states of the elements mean for the visualizer, in the order that they are shown in the legend.
+    pub legend: Vec<(ElementState, &'static str)>,
+    /// What the visualizer can be found with in the catalog of the home page, like its time complexity or technique.
+    pub tags: Vec<&'static str>,
+    create: CreateVisualizerFunc,
+}
+// Function pointers can't be compared reliably, so two `VisualizerInfo`s are considered equal if their names are the same.
+impl PartialEq for VisualizerInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+impl VisualizerInfo {
+    /// `create` makes the visualizer in the state that it is shown in first, e.g. a tree with a few values.
+    pub fn new(name: &str, create: CreateVisualizerFunc) -> Self {
+        Self {
+            name: name.to_string(),
+            legend: vec![],
+            tags: vec![],
+            create,
+        }
+    }
+    pub fn with_legend(mut self, legend: &[(ElementState, &'static str)]) -> Self {
+        self.legend = legend.to_vec();
+        self
+    }
+    pub fn with_tags(mut self, tags: &[&'static str]) -> Self {
+        self.tags = tags.to_vec();
+        self
+    }
+    pub fn create(&self) -> Box<dyn Visualizer> {
+        (self.create)()
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct VisualizerPageProps {
+    /// The id of the page element.
+    pub id: &'static str,
+    /// The name of the section that is shown in the title, like "Data structures".
+    pub section: &'static str,
+    /// The title of the input that chooses the visualizer, like "Data structure".
+    pub select_title: &'static str,
+    /// The names of all of the visualizers of the section.
+    pub names: Vec<String>,
+    pub visualizer: VisualizerInfo,
+    /// Called with the name of the chosen visualizer.
+    pub on_select: Callback<String>,
+    /// The directory of the README files of the visualizers.
+    pub readme_directory: &'static str,
+}
+
+/// A page where the operations of a visualizer can be run and their steps played.
+#[function_component]
+pub fn VisualizerPage(props: &VisualizerPageProps) -> Html {
+    let VisualizerPageProps {
+        id,
+        section,
+        select_title,
+        names,
+        visualizer: info,
+        on_select,
+        readme_directory,
+    } = props.clone();
+
+    let visualizer = {
+        let info = info.clone();
+        use_mut_ref(move || info.create())
+    };
+    // What the visualizer looked like before the latest operation, shown before its first step
+    let diagram_before = {
+        let visualizer = Rc::clone(&visualizer);
+        use_mut_ref(move || visualizer.borrow().diagram())
+    };
+    let steps = use_mut_ref(DiagramSteps::new);
+    let step_index = use_state(|| 0);
+    let playback_time = use_state_eq(|| 5.0);
+    let error = use_state_eq(|| None::<String>);
+    // Changed to start playing the steps of a new operation
+    let play_signal = use_state_eq(|| 0);
+
+    let reset = {
+        let visualizer = Rc::clone(&visualizer);
+        let diagram_before = Rc::clone(&diagram_before);
+        let steps = Rc::clone(&steps);
+        let step_index = step_index.clone();
+        let error = error.clone();
+
+        move |info: &VisualizerInfo| {
+            let new_visualizer = info.create();
+            *diagram_before.borrow_mut() = new_visualizer.diagram();
+            *visualizer.borrow_mut() = new_visualizer;
+            *steps.borrow_mut() = DiagramSteps::new();
+            step_index.set(0);
+            error.set(None);
+        }
+    };
+
+    {
+        let reset = reset.clone();
+
+        use_effect_with_deps(
+            move |info| {
+                reset(info);
+                || ()
+            },
+            info.clone(),
+        );
+    }
+
+    use_title(format!("{} - {}", info.name, section));
+
+    let on_run = {
+        let visualizer = Rc::clone(&visualizer);
+        let diagram_before = Rc::clone(&diagram_before);
+        let steps = Rc::clone(&steps);
+        let step_index = step_index.clone();
+        let error = error.clone();
+        let play_signal = play_signal.clone();
+
+        Callback::from(move |(operation, values): (String, Vec<Value>)| {
+            let before = visualizer.borrow().diagram();
+            let result = visualizer.borrow_mut().run(&operation, &values);
+            match result {
+                Ok(new_steps) => {
+                    let step_count = new_steps.len();
+                    *diagram_before.borrow_mut() = before;
+                    *steps.borrow_mut() = new_steps;
+                    error.set(None);
+                    if prefers_reduced_motion() {
+                        step_index.set(step_count);
+                    } else {
+                        step_index.set(0);
+                        play_signal.set(*play_signal + 1);
+                    }
+                }
+                Err(err) => error.set(Some(err)),
+            }
+        })
+    };
+
+    // Clicks change the visualizer itself, like placing a point, so the steps of the latest operation are replaced
+    // by the steps of the click, if it has any
+    let on_click = visualizer.borrow().clickable().then(|| {
+        let visualizer = Rc::clone(&visualizer);
+        let diagram_before = Rc::clone(&diagram_before);
+        let steps = Rc::clone(&steps);
+        let step_index = step_index.clone();
+        let error = error.clone();
+        let play_signal = play_signal.clone();
+
+        Callback::from(move |position: (f32, f32)| {
+            let before = visualizer.borrow().diagram();
+            let result = visualizer.borrow_mut().click(position);
+            if let Some(new_steps) = result {
+                let step_count = new_steps.len();
+                error.set(None);
+                if new_steps.is_empty() {
+                    *diagram_before.borrow_mut() = visualizer.borrow().diagram();
+                    *steps.borrow_mut() = new_steps;
+                    step_index.set(0);
+                } else {
+                    *diagram_before.borrow_mut() = before;
+                    *steps.borrow_mut() = new_steps;
+                    if prefers_reduced_motion() {
+                        step_index.set(step_count);
+                    } else {
+                        step_index.set(0);
+                        play_signal.set(*play_signal + 1);
+                    }
+                }
+            }
+        })
+    });
+
+    let on_reset = {
+        let info = info.clone();
+        Callback::from(move |_| reset(&info))
+    };
+
+    let on_change_step = {
+        let step_index = step_index.clone();
+        Callback::from(move |val| step_index.set(val))
+    };
+
+    let on_change_playback_time = {
+        let playback_time = playback_time.clone();
+        Callback::from(move |time| playback_time.set(time))
+    };
+
+    let step_count = steps.borrow().len();
+    let (diagram, description, lists) = if *step_index > 0 && *step_index <= step_count {
+        let step = steps.borrow().steps[*step_index - 1].clone();
+        (
+            step.diagram,
+            format!(
+                "Step {} of {}. {}.",
+                *step_index, step_count, step.description
+            ),
+            step.lists,
+        )
+    } else if step_count > 0 {
+        (
+            diagram_before.borrow().clone(),
+            format!(
+                "Step 0 of {}. Play the steps to see the operation.",
+                step_count
+            ),
+            vec![],
+        )
+    } else {
+        (
+            visualizer.borrow().diagram(),
+            "Run an operation to see its steps.".to_string(),
+            vec![],
+        )
+    };
+    let operations = visualizer.borrow().operations();
+
+    html! {
+        <div class="page diagram-page" {id}>
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <SelectInput
+                        title={select_title}
+                        options={names}
+                        selected_value={info.name.clone()}
+                        onchange={on_select}
+                    />
+                    <FloatInput<f32>
+                        title="Playback time (seconds)"
+                        value={*playback_time}
+                        oninput={on_change_playback_time}
+                        min={0.0}
+                    />
+                </Collapsible>
+
+                <Collapsible title="Operations" open={true} class="config-section">
+                    <OperationControls {operations} {on_run} error={(*error).clone()} />
+                    <Button title="Reset" onclick={on_reset} />
+                </Collapsible>
+            </Sidebar>
+
+            <main>
+                <div class="visualization">
+                    <DiagramView {diagram} label={info.name.clone()} onclick={on_click} />
+
+                    <StepLegend states={info.legend.clone()} />
+
+                    <p class="step-description" role="status" aria-live="polite">{ description }</p>
+
+                    <StepLists {lists} />
+
+                    <StepSlider
+                        label={format!("Steps ({}/{})", *step_index, step_count)}
+                        active_step_index={*step_index}
+                        max={step_count}
+                        on_change={on_change_step}
+                        playback_time={*playback_time}
+                        play_signal={*play_signal}
+                    />
+                </div>
+
+                <AlgoDesc algorithm={info.name} directory={readme_directory} />
+            </main>
+        </div>
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::BTreeMap;
+
+use graph::GraphStep;
+use pathfinding::Coord;
+use yew::prelude::*;
+
+use super::{element_state_class, toolbar::GraphPageTool};
+use crate::components::graph_editor::{EditableGraph, GraphEditor, GraphTool, MIN_WEIGHT};
+
+/// How many colors there are for the node groups in the styles.
+pub const GROUP_COLOR_COUNT: usize = 8;
+
+/// The kinds of graphs that algorithms run on. Each kind has its own graph, which is edited separately.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphKind {
+    Undirected,
+    Directed,
+    /// An undirected graph that can have several edges between the same nodes.
+    /// The weight of an edge is the number of edges between its nodes.
+    Multigraph,
+}
+
+/// A graph that the algorithms are run on, along with the nodes that they start and end at.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlgorithmGraph {
+    pub graph: EditableGraph,
+    pub start: usize,
+    pub end: usize,
+}
+impl AlgorithmGraph {
+    /// Removes all edges and all nodes except the start and end nodes.
+    pub fn clear(&mut self) {
+        let (start, end) = (self.start, self.end);
+        self.graph.nodes.retain(|id, _| *id == start || *id == end);
+        self.graph.edges.clear();
+    }
+    /// Gets the graph that is shown before the user has edited it.
+    /// The directed graph is acyclic so that every algorithm can be run on it.
+    pub fn sample(kind: GraphKind) -> Self {
+        if kind == GraphKind::Multigraph {
+            return Self::sample_multigraph();
+        }
+        let directed = kind == GraphKind::Directed;
+        let mut graph = EditableGraph::new(directed);
+        for (x, y) in [
+            (100, 300),
+            (260, 110),
+            (260, 490),
+            (450, 300),
+            (560, 110),
+            (600, 490),
+            (760, 270),
+            (910, 120),
+            (910, 460),
+        ] {
+            graph.add_node(Coord::new(x, y));
+        }
+        let edges = if directed {
+            [
+                (0, 1, 4.0),
+                (0, 2, 8.0),
+                (1, 4, 8.0),
+                (2, 3, 7.0),
+                (2, 5, 1.0),
+                (3, 4, 2.0),
+                (3, 6, 6.0),
+                (5, 6, 2.0),
+                (4, 7, 7.0),
+                (6, 7, 9.0),
+                (6, 8, 10.0),
+                (7, 8, 14.0),
+            ]
+            .to_vec()
+        } else {
+            [
+                (0, 1, 4.0),
+                (0, 2, 8.0),
+                (1, 2, 11.0),
+                (1, 4, 8.0),
+                (2, 3, 7.0),
+                (2, 5, 1.0),
+                (3, 4, 2.0),
+                (3, 5, 6.0),
+                (4, 6, 4.0),
+                (4, 7, 7.0),
+                (5, 6, 2.0),
+                (6, 7, 9.0),
+                (6, 8, 10.0),
+                (7, 8, 14.0),
+            ]
+            .to_vec()
+        };
+        for (a, b, weight) in edges {
+            graph.connect(a, b);
+            graph.set_weight(a, b, weight);
+        }
+        Self {
+            graph,
+            start: 0,
+            end: 8,
+        }
+    }
+    /// Gets a multigraph where every node has an even degree, so that it has an Eulerian circuit.
+    fn sample_multigraph() -> Self {
+        let mut graph = EditableGraph::new(false);
+        for (x, y) in [
+            (120, 300),
+            (320, 150),
+            (520, 300),
+            (700, 130),
+            (720, 450),
+            (920, 450),
+        ] {
+            graph.add_node(Coord::new(x, y));
+        }
+        for (a, b, count) in [
+            (0, 1, 2),
+            (1, 2, 2),
+            (2, 3, 1),
+            (3, 4, 1),
+            (2, 4, 1),
+            (4, 5, 2),
+        ] {
+            for _ in 0..count {
+                graph.add_parallel_edge(a, b);
+            }
+        }
+        Self {
+            graph,
+            start: 0,
+            end: 5,
+        }
+    }
+}
+
+/// The graphs of each [`GraphKind`], which are edited separately.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlgorithmGraphs {
+    pub undirected: AlgorithmGraph,
+    pub directed: AlgorithmGraph,
+    pub multigraph: AlgorithmGraph,
+}
+impl AlgorithmGraphs {
+    pub fn get(&self, kind: GraphKind) -> &AlgorithmGraph {
+        match kind {
+            GraphKind::Undirected => &self.undirected,
+            GraphKind::Directed => &self.directed,
+            GraphKind::Multigraph => &self.multigraph,
+        }
+    }
+    pub fn get_mut(&mut self, kind: GraphKind) -> &mut AlgorithmGraph {
+        match kind {
+            GraphKind::Undirected => &mut self.undirected,
+            GraphKind::Directed => &mut self.directed,
+            GraphKind::Multigraph => &mut self.multigraph,
+        }
+    }
+}
+impl Default for AlgorithmGraphs {
+    fn default() -> Self {
+        Self {
+            undirected: AlgorithmGraph::sample(GraphKind::Undirected),
+            directed: AlgorithmGraph::sample(GraphKind::Directed),
+            multigraph: AlgorithmGraph::sample(GraphKind::Multigraph),
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct AlgorithmGraphEditorProps {
+    pub graph: AlgorithmGraph,
+    /// The state of the graph at the current step.
+    pub step: GraphStep,
+    pub active_tool: GraphPageTool,
+    #[prop_or_default]
+    pub uses_start: bool,
+    #[prop_or_default]
+    pub uses_end: bool,
+    #[prop_or(true)]
+    pub show_weights: bool,
+    /// Allows giving edges zero and negative weights.
+    #[prop_or_default]
+    pub negative_weights: bool,
+    /// Whether the graph is a multigraph, see [`GraphKind::Multigraph`].
+    #[prop_or_default]
+    pub parallel_edges: bool,
+    /// Emitted with the new graph whenever it is edited. The start and end nodes can't be deleted.
+    pub on_change: Callback<AlgorithmGraph>,
+    /// Emitted when a node is clicked with [`GraphPageTool::Start`] or [`GraphPageTool::End`].
+    pub on_click_node: Callback<usize>,
+}
+
+/// A [`GraphEditor`] that shows the states of the nodes and edges at a step of an algorithm.
+#[function_component]
+pub fn AlgorithmGraphEditor(props: &AlgorithmGraphEditorProps) -> Html {
+    let AlgorithmGraphEditorProps {
+        graph,
+        step,
+        active_tool,
+        uses_start,
+        uses_end,
+        show_weights,
+        negative_weights,
+        parallel_edges,
+        on_change,
+        on_click_node,
+    } = props.clone();
+
+    let tool = match active_tool {
+        GraphPageTool::Graph(tool) => tool,
+        _ => GraphTool::Pick,
+    };
+
+    let on_change = {
+        let graph = graph.clone();
+
+        Callback::from(move |new_graph: EditableGraph| {
+            if new_graph.nodes.contains_key(&graph.start)
+                && new_graph.nodes.contains_key(&graph.end)
+            {
+                on_change.emit(AlgorithmGraph {
+                    graph: new_graph,
+                    ..graph.clone()
+                });
+            }
+        })
+    };
+
+    let node_classes = graph
+        .graph
+        .nodes
+        .keys()
+        .map(|id| {
+            (
+                *id,
+                classes!(
+                    step.node_groups
+                        .get(id)
+                        .map(|group| format!("group-{}", group % GROUP_COLOR_COUNT)),
+                    step.node_states
+                        .get(id)
+                        .map(|state| element_state_class(*state)),
+                    (uses_start && *id == graph.start).then_some("start"),
+                    (uses_end && *id == graph.end).then_some("end"),
+                ),
+            )
+        })
+        .collect::<BTreeMap<usize, Classes>>();
+
+    let edge_classes = step
+        .edge_states
+        .iter()
+        .map(|(edge, state)| (*edge, classes!(element_state_class(*state))))
+        .collect::<BTreeMap<(usize, usize), Classes>>();
+
+    // Algorithms that move the nodes are shown with the nodes where they are at the step.
+    // Editing the graph keeps the nodes there.
+    let mut shown_graph = graph.graph;
+    shown_graph.set_positions(&step.node_positions);
+
+    html! {
+        <GraphEditor
+            graph={shown_graph}
+            {tool}
+            {on_change}
+            {on_click_node}
+            {node_classes}
+            node_labels={step.node_labels}
+            node_values={step.node_values}
+            {edge_classes}
+            edge_labels={step.edge_labels}
+            {show_weights}
+            min_weight={(!negative_weights).then_some(MIN_WEIGHT)}
+            {parallel_edges}
+            aria_describedby="graphStepStatus"
+        />
+    }
+}
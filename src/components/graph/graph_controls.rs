@@ -0,0 +1,179 @@
+use std::{cell::RefCell, rc::Rc};
+
+use yew::prelude::*;
+use yew_router::hooks::use_navigator;
+
+use super::GROUP_COLOR_COUNT;
+use crate::{
+    components::input_items::*,
+    pages::graph::{get_graph_algorithms, GraphConfig, GraphRoute},
+};
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct GraphControlsProps {
+    pub config: Rc<RefCell<GraphConfig>>,
+}
+
+#[function_component]
+pub fn GraphControls(props: &GraphControlsProps) -> Html {
+    let GraphControlsProps { config } = props.clone();
+
+    let navigator = use_navigator().unwrap();
+
+    let algorithm_names = use_state_eq(|| {
+        get_graph_algorithms()
+            .values()
+            .map(|algorithm| algorithm.name.to_string())
+            .collect::<Vec<String>>()
+    });
+
+    let change_playback_time = {
+        let config = config.clone();
+
+        Callback::from(move |playback_time| {
+            config.borrow_mut().playback_time = playback_time;
+        })
+    };
+
+    let change_algorithm = Callback::from(move |algorithm: String| {
+        navigator.push(&GraphRoute::GraphAlgorithm {
+            algorithm: algorithm.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <div class="graph-controls">
+            <SelectInput
+                title="Algorithm"
+                options={(*algorithm_names).clone()}
+                selected_value={config.borrow().algorithm.name.to_string()}
+                onchange={change_algorithm}
+            />
+            <FloatInput<f32>
+                title="Playback time (seconds)"
+                value={config.borrow().playback_time}
+                oninput={change_playback_time}
+                min={0.0}
+            />
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct GraphEditControlsProps {
+    pub on_clear_graph: Callback<()>,
+    pub on_reset_graph: Callback<()>,
+}
+
+#[function_component]
+pub fn GraphEditControls(props: &GraphEditControlsProps) -> Html {
+    let GraphEditControlsProps {
+        on_clear_graph,
+        on_reset_graph,
+    } = props.clone();
+
+    let on_clear_graph = Callback::from(move |_| {
+        on_clear_graph.emit(());
+    });
+
+    let on_reset_graph = Callback::from(move |_| {
+        on_reset_graph.emit(());
+    });
+
+    html! {
+        <div class="graph-edit-controls">
+            <Button title="Clear graph" onclick={on_clear_graph} />
+            <Button title="Reset graph" onclick={on_reset_graph} />
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ColoringControlsProps {
+    pub color_count: usize,
+    pub on_change_color_count: Callback<usize>,
+}
+
+/// Chooses how many colors a coloring algorithm can use. Each color is a node group color, so there can't be more of them.
+#[function_component]
+pub fn ColoringControls(props: &ColoringControlsProps) -> Html {
+    let ColoringControlsProps {
+        color_count,
+        on_change_color_count,
+    } = props.clone();
+
+    let change_color_count = Callback::from(move |color_count: f32| {
+        on_change_color_count.emit(color_count as usize);
+    });
+
+    html! {
+        <div class="coloring-controls">
+            <RangeInput<f32>
+                title={format!("Number of colors ({})", color_count)}
+                value={color_count as f32}
+                min={1.0}
+                max={GROUP_COLOR_COUNT as f32}
+                step={1.0}
+                oninput={change_color_count}
+            />
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct DampingControlsProps {
+    pub damping: f32,
+    pub on_change_damping: Callback<f32>,
+}
+
+/// Chooses the damping factor of PageRank, which is how likely the random surfer is to follow an edge.
+#[function_component]
+pub fn DampingControls(props: &DampingControlsProps) -> Html {
+    let DampingControlsProps {
+        damping,
+        on_change_damping,
+    } = props.clone();
+
+    html! {
+        <div class="damping-controls">
+            <RangeInput<f32>
+                title={format!("Damping factor ({:.2})", damping)}
+                value={damping}
+                min={0.0}
+                max={0.99}
+                step={0.01}
+                oninput={on_change_damping}
+            />
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct LayoutControlsProps {
+    pub on_scatter_nodes: Callback<()>,
+    /// Emitted to move the nodes of the graph to where the layout algorithm left them.
+    pub on_use_layout: Callback<()>,
+}
+
+#[function_component]
+pub fn LayoutControls(props: &LayoutControlsProps) -> Html {
+    let LayoutControlsProps {
+        on_scatter_nodes,
+        on_use_layout,
+    } = props.clone();
+
+    let on_scatter_nodes = Callback::from(move |_| {
+        on_scatter_nodes.emit(());
+    });
+
+    let on_use_layout = Callback::from(move |_| {
+        on_use_layout.emit(());
+    });
+
+    html! {
+        <div class="layout-controls">
+            <Button title="Scatter nodes" onclick={on_scatter_nodes} />
+            <Button title="Use this layout" onclick={on_use_layout} />
+        </div>
+    }
+}
@@ -0,0 +1,883 @@
+use std::collections::BTreeMap;
+
+use graph::{ElementState, GraphStep};
+use pathfinding::Coord;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::HtmlInputElement;
+use yew::{platform::spawn_local, prelude::*};
+
+use super::{AlgorithmGraph, GraphKind, GROUP_COLOR_COUNT};
+use crate::components::{
+    graph_editor::{EditableGraph, GRAPH_HEIGHT, GRAPH_WIDTH},
+    input_items::*,
+};
+
+/// The largest number of nodes that an imported graph can have.
+const MAX_SAVED_GRAPH_NODES: usize = 100;
+/// The largest number of edges that there can be between two nodes of an imported multigraph.
+const MAX_SAVED_PARALLEL_EDGES: usize = 20;
+/// How far from the sides of the editor imported nodes are placed.
+const IMPORT_MARGIN: f32 = 40.0;
+/// The largest coordinates that the positions of nodes in imported DOT files can have.
+const MAX_DOT_COORDINATE: f32 = 1e6;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedNode {
+    pub id: usize,
+    pub x: isize,
+    pub y: isize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedEdge {
+    pub from: usize,
+    pub to: usize,
+    /// The weight of the edge, or the number of edges between the nodes in multigraphs.
+    /// Edges without a weight get their length as the weight like edges drawn in the editor.
+    #[serde(default)]
+    pub weight: Option<f32>,
+}
+
+/// A graph that can be saved to a JSON or Graphviz DOT file and loaded later.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedGraph {
+    pub directed: bool,
+    #[serde(default)]
+    pub multigraph: bool,
+    pub nodes: Vec<SavedNode>,
+    #[serde(default)]
+    pub edges: Vec<SavedEdge>,
+    #[serde(default)]
+    pub start: Option<usize>,
+    #[serde(default)]
+    pub end: Option<usize>,
+}
+impl SavedGraph {
+    pub fn new(graph: &AlgorithmGraph, kind: GraphKind) -> Self {
+        Self {
+            directed: graph.graph.directed,
+            multigraph: kind == GraphKind::Multigraph,
+            nodes: graph
+                .graph
+                .nodes
+                .iter()
+                .map(|(id, position)| SavedNode {
+                    id: *id,
+                    x: position.x,
+                    y: position.y,
+                })
+                .collect(),
+            edges: graph
+                .graph
+                .edges
+                .iter()
+                .map(|((from, to), weight)| SavedEdge {
+                    from: *from,
+                    to: *to,
+                    weight: Some(*weight),
+                })
+                .collect(),
+            start: Some(graph.start),
+            end: Some(graph.end),
+        }
+    }
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+    /// Formats the graph in the DOT language of Graphviz along with the state of a step,
+    /// so that results like trees and components can be drawn with other tools.
+    pub fn to_dot(&self, step: &GraphStep) -> String {
+        let (keyword, edge_op) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut dot = format!("{} {{\n", keyword);
+
+        for node in self.nodes.iter() {
+            // Graphviz measures y upwards
+            let mut attributes = vec![format!("pos=\"{},{}!\"", node.x, GRAPH_HEIGHT - node.y)];
+            if let Some(label) = step.node_labels.get(&node.id) {
+                attributes.push(format!("xlabel={}", quote(label)));
+            }
+            if let Some(group) = step.node_groups.get(&node.id) {
+                attributes.push(format!(
+                    "style=filled, colorscheme=set3{}, fillcolor={}",
+                    GROUP_COLOR_COUNT,
+                    group % GROUP_COLOR_COUNT + 1
+                ));
+            }
+            if let Some(state) = step.node_states.get(&node.id) {
+                attributes.push(format!("color={}", dot_color(*state)));
+            }
+            dot.push_str(&format!("  {} [{}];\n", node.id, attributes.join(", ")));
+        }
+
+        for edge in self.edges.iter() {
+            let key = (edge.from, edge.to);
+            let weight = edge.weight.unwrap_or(1.0);
+            let mut attributes = vec![];
+            if !self.multigraph {
+                attributes.push(format!("weight={}", weight));
+            }
+            if let Some(label) = step
+                .edge_labels
+                .get(&key)
+                .cloned()
+                .or_else(|| (!self.multigraph).then(|| weight.to_string()))
+            {
+                attributes.push(format!("label={}", quote(&label)));
+            }
+            if let Some(state) = step.edge_states.get(&key) {
+                attributes.push(format!("color={}, penwidth=2", dot_color(*state)));
+            }
+            let count = if self.multigraph {
+                weight.round().max(1.0) as usize
+            } else {
+                1
+            };
+            for _ in 0..count {
+                dot.push_str(&format!(
+                    "  {} {} {} [{}];\n",
+                    edge.from,
+                    edge_op,
+                    edge.to,
+                    attributes.join(", ")
+                ));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+    /// Parses a graph from JSON or DOT and checks that it can be edited as a graph of the given kind.
+    pub fn from_file(text: &str, kind: GraphKind) -> Result<Self, String> {
+        let graph = if text.trim_start().starts_with('{') {
+            serde_json::from_str(text)
+                .map_err(|err| format!("The file isn't a valid graph: {}", err))?
+        } else {
+            Self::from_dot(text, kind == GraphKind::Multigraph)?
+        };
+        graph.check(kind)?;
+        Ok(graph)
+    }
+    fn check(&self, kind: GraphKind) -> Result<(), String> {
+        if self.directed != (kind == GraphKind::Directed) {
+            return Err(format!(
+                "The file has {} graph, but this algorithm runs on {} graphs",
+                if self.directed {
+                    "a directed"
+                } else {
+                    "an undirected"
+                },
+                if kind == GraphKind::Directed {
+                    "directed"
+                } else {
+                    "undirected"
+                }
+            ));
+        }
+        if self.nodes.is_empty() || self.nodes.len() > MAX_SAVED_GRAPH_NODES {
+            return Err(format!(
+                "The graph must have between 1 and {} nodes",
+                MAX_SAVED_GRAPH_NODES
+            ));
+        }
+        let mut ids = self.nodes.iter().map(|node| node.id).collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        if ids.len() != self.nodes.len() {
+            return Err("Every node must have a different id".to_string());
+        }
+        if self.edges.iter().any(|edge| {
+            ids.binary_search(&edge.from).is_err() || ids.binary_search(&edge.to).is_err()
+        }) {
+            return Err("Every edge must connect nodes of the graph".to_string());
+        }
+        if self
+            .edges
+            .iter()
+            .any(|edge| edge.weight.is_some_and(|weight| !weight.is_finite()))
+        {
+            return Err("The weights of the edges must be numbers".to_string());
+        }
+        if self.multigraph
+            && self.edges.iter().any(|edge| {
+                edge.weight.is_some_and(|count| {
+                    count.fract() != 0.0
+                        || !(1.0..=MAX_SAVED_PARALLEL_EDGES as f32).contains(&count)
+                })
+            })
+        {
+            return Err(format!(
+                "The number of edges between two nodes must be a whole number between 1 and {}",
+                MAX_SAVED_PARALLEL_EDGES
+            ));
+        }
+        Ok(())
+    }
+    /// Gets the graph to edit. Nodes outside of the editor are moved inside it,
+    /// and the first and last nodes are used as the start and end if the graph doesn't have them.
+    pub fn to_algorithm_graph(&self, kind: GraphKind) -> AlgorithmGraph {
+        let mut graph = EditableGraph::new(self.directed);
+        let positions = fit_positions(
+            self.nodes
+                .iter()
+                .map(|node| (node.id, (node.x as f32, node.y as f32)))
+                .collect(),
+        );
+        for node in self.nodes.iter() {
+            let (x, y) = positions[&node.id];
+            graph
+                .nodes
+                .insert(node.id, Coord::new(x.round() as isize, y.round() as isize));
+        }
+        for edge in self.edges.iter().filter(|edge| edge.from != edge.to) {
+            if kind == GraphKind::Multigraph {
+                let count = edge
+                    .weight
+                    .filter(|_| self.multigraph)
+                    .map_or(1, |count| count.round().max(1.0) as usize);
+                for _ in 0..count {
+                    graph.add_parallel_edge(edge.from, edge.to);
+                }
+            } else {
+                graph.connect(edge.from, edge.to);
+                if let Some(weight) = edge.weight {
+                    graph.set_weight(edge.from, edge.to, weight);
+                }
+            }
+        }
+
+        let first = *graph.nodes.keys().next().unwrap();
+        let last = *graph.nodes.keys().last().unwrap();
+        let is_node = |id: &usize| graph.nodes.contains_key(id);
+        AlgorithmGraph {
+            start: self.start.filter(is_node).unwrap_or(first),
+            end: self.end.filter(is_node).unwrap_or(last),
+            graph,
+        }
+    }
+    /// Parses the parts of the DOT language that describe nodes, edges and their positions and weights.
+    /// Nodes are renumbered in the order that they appear in unless all of their names are numbers.
+    fn from_dot(dot: &str, multigraph: bool) -> Result<Self, String> {
+        let mut tokens = tokenize(dot)?.into_iter().peekable();
+
+        let mut keyword = tokens.next();
+        if matches!(&keyword, Some(Token::Id(id)) if id.eq_ignore_ascii_case("strict")) {
+            keyword = tokens.next();
+        }
+        let directed = match keyword {
+            Some(Token::Id(id)) if id.eq_ignore_ascii_case("graph") => false,
+            Some(Token::Id(id)) if id.eq_ignore_ascii_case("digraph") => true,
+            _ => return Err("A DOT file must start with \"graph\" or \"digraph\"".to_string()),
+        };
+        if matches!(tokens.peek(), Some(Token::Id(_))) {
+            tokens.next();
+        }
+        if tokens.next() != Some(Token::Symbol('{')) {
+            return Err("Expected \"{\" after the name of the graph".to_string());
+        }
+
+        let mut names = Vec::<String>::new();
+        let mut positions = BTreeMap::<String, (f32, f32)>::new();
+        let mut edges = Vec::<(String, String, Option<f32>)>::new();
+        let add_name = |names: &mut Vec<String>, name: &str| {
+            if !names.iter().any(|other| other == name) {
+                names.push(name.to_string());
+            }
+        };
+
+        loop {
+            let Some(token) = tokens.next() else {
+                return Err("The graph is missing a closing \"}\"".to_string());
+            };
+            let name = match token {
+                Token::Symbol('}') => break,
+                Token::Symbol(';' | ',') => continue,
+                Token::Id(id) => id,
+                Token::Symbol('{') => {
+                    return Err("Subgraphs aren't supported".to_string());
+                }
+                _ => return Err("Expected a node or an edge".to_string()),
+            };
+
+            if ["graph", "node", "edge"].contains(&name.to_lowercase().as_str())
+                && tokens.peek() == Some(&Token::Symbol('['))
+            {
+                // Default attributes don't affect the graph
+                parse_attributes(&mut tokens)?;
+                continue;
+            }
+            if name.eq_ignore_ascii_case("subgraph") {
+                return Err("Subgraphs aren't supported".to_string());
+            }
+            if tokens.peek() == Some(&Token::Symbol('=')) {
+                tokens.next();
+                tokens.next();
+                continue;
+            }
+
+            let mut chain = vec![name];
+            while let Some(Token::EdgeOp(arrow)) = tokens.peek().cloned() {
+                tokens.next();
+                if arrow != directed {
+                    return Err(format!(
+                        "Edges of {} graphs must be written with \"{}\"",
+                        if directed { "directed" } else { "undirected" },
+                        if directed { "->" } else { "--" }
+                    ));
+                }
+                match tokens.next() {
+                    Some(Token::Id(id)) => chain.push(id),
+                    _ => return Err("Expected a node after an edge".to_string()),
+                }
+            }
+            let attributes = parse_attributes(&mut tokens)?;
+
+            for name in chain.iter() {
+                add_name(&mut names, name);
+            }
+            if chain.len() == 1 {
+                if let Some(position) = attributes
+                    .get("pos")
+                    .map(|pos| parse_position(pos))
+                    .transpose()?
+                    .flatten()
+                {
+                    positions.insert(chain[0].clone(), position);
+                }
+            } else {
+                let weight = attributes
+                    .get("weight")
+                    .or_else(|| attributes.get("label"))
+                    .and_then(|weight| weight.parse::<f32>().ok());
+                for pair in chain.windows(2) {
+                    edges.push((pair[0].clone(), pair[1].clone(), weight));
+                }
+            }
+        }
+
+        let numbered = names.iter().all(|name| name.parse::<usize>().is_ok());
+        let ids = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let id = if numbered { name.parse().unwrap() } else { i };
+                (name.clone(), id)
+            })
+            .collect::<BTreeMap<String, usize>>();
+
+        // Nodes without positions are placed on a circle
+        let center = (GRAPH_WIDTH as f32 / 2.0, GRAPH_HEIGHT as f32 / 2.0);
+        let radius = center.0.min(center.1) - IMPORT_MARGIN;
+        let nodes = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let (x, y) = positions.get(name).copied().unwrap_or_else(|| {
+                    let angle = i as f32 / names.len() as f32 * std::f32::consts::TAU;
+                    (
+                        center.0 + radius * angle.cos(),
+                        GRAPH_HEIGHT as f32 - (center.1 + radius * angle.sin()),
+                    )
+                });
+                SavedNode {
+                    id: ids[name],
+                    x: x.round() as isize,
+                    y: GRAPH_HEIGHT - y.round() as isize,
+                }
+            })
+            .collect();
+
+        // Parallel edges are counted in multigraphs, and otherwise the last one is used
+        let mut edge_weights = BTreeMap::<(usize, usize), Option<f32>>::new();
+        for (from, to, weight) in edges {
+            let (from, to) = (ids[&from], ids[&to]);
+            let key = if directed {
+                (from, to)
+            } else {
+                (from.min(to), from.max(to))
+            };
+            if multigraph {
+                let count = edge_weights.entry(key).or_insert(Some(0.0));
+                *count = count.map(|count| count + 1.0);
+            } else {
+                edge_weights.insert(key, weight);
+            }
+        }
+
+        Ok(Self {
+            directed,
+            multigraph,
+            nodes,
+            edges: edge_weights
+                .into_iter()
+                .map(|((from, to), weight)| SavedEdge { from, to, weight })
+                .collect(),
+            start: None,
+            end: None,
+        })
+    }
+}
+
+/// Scales and moves the positions to fit in the editor if some of them are outside of it.
+fn fit_positions(positions: BTreeMap<usize, (f32, f32)>) -> BTreeMap<usize, (f32, f32)> {
+    let (width, height) = (GRAPH_WIDTH as f32, GRAPH_HEIGHT as f32);
+    let fits = positions.values().all(|(x, y)| {
+        (IMPORT_MARGIN..=width - IMPORT_MARGIN).contains(x)
+            && (IMPORT_MARGIN..=height - IMPORT_MARGIN).contains(y)
+    });
+    if fits {
+        return positions;
+    }
+
+    let min_x = positions
+        .values()
+        .map(|(x, _)| *x)
+        .fold(f32::INFINITY, f32::min);
+    let max_x = positions
+        .values()
+        .map(|(x, _)| *x)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let min_y = positions
+        .values()
+        .map(|(_, y)| *y)
+        .fold(f32::INFINITY, f32::min);
+    let max_y = positions
+        .values()
+        .map(|(_, y)| *y)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let scale = ((width - 2.0 * IMPORT_MARGIN) / (max_x - min_x).max(1.0))
+        .min((height - 2.0 * IMPORT_MARGIN) / (max_y - min_y).max(1.0));
+    let offset = (
+        (width - (max_x - min_x) * scale) / 2.0,
+        (height - (max_y - min_y) * scale) / 2.0,
+    );
+    positions
+        .into_iter()
+        .map(|(id, (x, y))| {
+            (
+                id,
+                (
+                    (x - min_x) * scale + offset.0,
+                    (y - min_y) * scale + offset.1,
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Quotes a string for DOT.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The Graphviz color that an element in a state is drawn with.
+fn dot_color(state: ElementState) -> &'static str {
+    match state {
+        ElementState::Active => "orange",
+        ElementState::Frontier => "deepskyblue",
+        ElementState::Selected => "forestgreen",
+        ElementState::Rejected => "gray",
+        ElementState::Conflict => "red",
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// A name, number or quoted string.
+    Id(String),
+    /// An edge, which is `->` if true and `--` otherwise.
+    EdgeOp(bool),
+    Symbol(char),
+}
+
+fn tokenize(dot: &str) -> Result<Vec<Token>, String> {
+    let chars = dot.chars().collect::<Vec<char>>();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '/' if next == Some('/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '#' if i == 0 || chars[i - 1] == '\n' => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if next == Some('*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            '-' if next == Some('-') || next == Some('>') => {
+                tokens.push(Token::EdgeOp(next == Some('>')));
+                i += 2;
+            }
+            '{' | '}' | '[' | ']' | ';' | ',' | '=' => {
+                tokens.push(Token::Symbol(c));
+                i += 1;
+            }
+            '"' => {
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => {
+                            return Err("A quoted string is missing its closing quote".to_string())
+                        }
+                        Some('"') => break,
+                        Some('\\') if chars.get(i + 1) == Some(&'"') => {
+                            text.push('"');
+                            i += 1;
+                        }
+                        Some(c) => text.push(*c),
+                    }
+                    i += 1;
+                }
+                tokens.push(Token::Id(text));
+                i += 1;
+            }
+            _ if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let start = i;
+                i += 1;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Id(chars[start..i].iter().collect()));
+            }
+            _ => return Err(format!("Unexpected character \"{}\"", c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Parses attribute lists like `[pos="1,2", weight=3]` if there are any.
+fn parse_attributes(
+    tokens: &mut std::iter::Peekable<std::vec::IntoIter<Token>>,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut attributes = BTreeMap::new();
+    while tokens.peek() == Some(&Token::Symbol('[')) {
+        tokens.next();
+        loop {
+            match tokens.next() {
+                Some(Token::Symbol(']')) => break,
+                Some(Token::Symbol(',' | ';')) => continue,
+                Some(Token::Id(key)) => {
+                    if tokens.peek() == Some(&Token::Symbol('=')) {
+                        tokens.next();
+                        match tokens.next() {
+                            Some(Token::Id(value)) => {
+                                attributes.insert(key.to_lowercase(), value);
+                            }
+                            _ => return Err(format!("Expected a value for \"{}\"", key)),
+                        }
+                    }
+                }
+                _ => return Err("An attribute list is missing its closing \"]\"".to_string()),
+            }
+        }
+    }
+    Ok(attributes)
+}
+
+/// Parses a Graphviz position like `"100,250"` or `"100,250!"`. Positions that aren't numbers are ignored,
+/// but positions that are too far away to be moved into the editor are errors.
+fn parse_position(pos: &str) -> Result<Option<(f32, f32)>, String> {
+    let Some((x, y)) = pos.trim_end_matches('!').split_once(',') else {
+        return Ok(None);
+    };
+    let (Ok(x), Ok(y)) = (x.trim().parse::<f32>(), y.trim().parse::<f32>()) else {
+        return Ok(None);
+    };
+    // Also rejects infinities and NaN
+    if !(x.abs() <= MAX_DOT_COORDINATE && y.abs() <= MAX_DOT_COORDINATE) {
+        return Err(format!(
+            "The coordinates of the nodes must be between -{} and {}",
+            MAX_DOT_COORDINATE, MAX_DOT_COORDINATE
+        ));
+    }
+    Ok(Some((x, y)))
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct GraphFileControlsProps {
+    /// The kind of graph that imported graphs are edited as.
+    pub kind: GraphKind,
+    pub on_export_json: Callback<()>,
+    pub on_export_dot: Callback<()>,
+    pub on_import: Callback<SavedGraph>,
+}
+
+#[function_component]
+pub fn GraphFileControls(props: &GraphFileControlsProps) -> Html {
+    let GraphFileControlsProps {
+        kind,
+        on_export_json,
+        on_export_dot,
+        on_import,
+    } = props.clone();
+
+    let file_input_ref = use_node_ref();
+    // The reason why the last imported file couldn't be loaded
+    let error = use_state_eq(|| None::<String>);
+
+    let on_export_json = Callback::from(move |_| {
+        on_export_json.emit(());
+    });
+
+    let on_export_dot = Callback::from(move |_| {
+        on_export_dot.emit(());
+    });
+
+    let on_click_import = {
+        let file_input_ref = file_input_ref.clone();
+
+        Callback::from(move |_| {
+            if let Some(input) = file_input_ref.cast::<HtmlInputElement>() {
+                input.click();
+            }
+        })
+    };
+
+    let on_choose_file = {
+        let error = error.clone();
+
+        Callback::from(move |event: Event| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+            let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                return;
+            };
+            // Allow choosing the same file again
+            input.set_value("");
+
+            let error = error.clone();
+            let on_import = on_import.clone();
+
+            spawn_local(async move {
+                let text = JsFuture::from(file.text())
+                    .await
+                    .ok()
+                    .and_then(|text| text.dyn_into::<js_sys::JsString>().ok())
+                    .map(String::from);
+                match text.map_or(Err("The file couldn't be read".to_string()), |text| {
+                    SavedGraph::from_file(&text, kind)
+                }) {
+                    Ok(graph) => {
+                        error.set(None);
+                        on_import.emit(graph);
+                    }
+                    Err(err) => error.set(Some(err)),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="graph-file-controls">
+            <Button title="Export JSON" onclick={on_export_json} />
+            <Button title="Export DOT" onclick={on_export_dot} />
+            <Button title="Import graph" onclick={on_click_import} />
+            <input
+                ref={file_input_ref}
+                type="file"
+                accept=".json,.dot,.gv,application/json,text/vnd.graphviz"
+                hidden={true}
+                onchange={on_choose_file}
+            />
+            {
+                if let Some(error) = &*error {
+                    html! { <p class="error" role="alert">{ error }</p> }
+                } else {
+                    html! {}
+                }
+            }
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizes_dot() {
+        assert_eq!(
+            tokenize("a -> \"b \\\" c\" [w=1.5]; // comment\n/* block */ d -- e").unwrap(),
+            vec![
+                Token::Id("a".to_string()),
+                Token::EdgeOp(true),
+                Token::Id("b \" c".to_string()),
+                Token::Symbol('['),
+                Token::Id("w".to_string()),
+                Token::Symbol('='),
+                Token::Id("1.5".to_string()),
+                Token::Symbol(']'),
+                Token::Symbol(';'),
+                Token::Id("d".to_string()),
+                Token::EdgeOp(false),
+                Token::Id("e".to_string()),
+            ]
+        );
+        assert!(tokenize("\"unclosed").is_err());
+        assert!(tokenize("a @ b").is_err());
+    }
+
+    #[test]
+    fn parses_dot() {
+        let graph = SavedGraph::from_dot(
+            "strict graph G { node [shape=circle]; a [pos=\"100,200!\"]; a -- b -- c [weight=3]; }",
+            false,
+        )
+        .unwrap();
+        assert!(!graph.directed);
+        assert_eq!(
+            graph.nodes.iter().map(|node| node.id).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        // Graphviz measures y upwards
+        assert_eq!(
+            (graph.nodes[0].x, graph.nodes[0].y),
+            (100, GRAPH_HEIGHT - 200)
+        );
+        assert_eq!(
+            graph.edges,
+            vec![
+                SavedEdge {
+                    from: 0,
+                    to: 1,
+                    weight: Some(3.0)
+                },
+                SavedEdge {
+                    from: 1,
+                    to: 2,
+                    weight: Some(3.0)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_parallel_edges_in_dot_multigraphs() {
+        let graph = SavedGraph::from_dot("graph { 1 -- 2; 2 -- 1; 2 -- 3 }", true).unwrap();
+        assert_eq!(
+            graph
+                .edges
+                .iter()
+                .map(|edge| (edge.from, edge.to, edge.weight))
+                .collect::<Vec<_>>(),
+            vec![(1, 2, Some(2.0)), (2, 3, Some(1.0))]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_dot() {
+        for dot in [
+            "{ a -- b }",
+            "graph { a -- b",
+            "graph { a -> b }",
+            "graph { subgraph { a } }",
+            "graph { a -- }",
+            "graph { a [pos=\"1,2\" }",
+            "graph { a [pos=\"0,-1e30\"] }",
+            "graph { a [pos=\"inf,0\"] }",
+            "graph { a [pos=\"NaN,0\"] }",
+        ] {
+            assert!(SavedGraph::from_dot(dot, false).is_err(), "{}", dot);
+        }
+    }
+
+    #[test]
+    fn round_trips_through_dot() {
+        let graph = SavedGraph {
+            directed: true,
+            multigraph: false,
+            nodes: vec![
+                SavedNode {
+                    id: 3,
+                    x: 50,
+                    y: 60,
+                },
+                SavedNode {
+                    id: 7,
+                    x: 200,
+                    y: 300,
+                },
+            ],
+            edges: vec![SavedEdge {
+                from: 3,
+                to: 7,
+                weight: Some(2.5),
+            }],
+            start: None,
+            end: None,
+        };
+        let dot = graph.to_dot(&GraphStep::default());
+        assert_eq!(
+            SavedGraph::from_file(&dot, GraphKind::Directed).unwrap(),
+            graph
+        );
+    }
+
+    #[test]
+    fn round_trips_multigraphs_through_dot() {
+        let graph = SavedGraph {
+            directed: false,
+            multigraph: true,
+            nodes: vec![
+                SavedNode {
+                    id: 0,
+                    x: 50,
+                    y: 60,
+                },
+                SavedNode {
+                    id: 1,
+                    x: 200,
+                    y: 300,
+                },
+            ],
+            edges: vec![SavedEdge {
+                from: 0,
+                to: 1,
+                weight: Some(3.0),
+            }],
+            start: None,
+            end: None,
+        };
+        let dot = graph.to_dot(&GraphStep::default());
+        assert_eq!(
+            SavedGraph::from_file(&dot, GraphKind::Multigraph).unwrap(),
+            graph
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_parallel_edge_counts() {
+        for count in [1e30, 2.5, 0.0, (MAX_SAVED_PARALLEL_EDGES + 1) as f32] {
+            let json = format!(
+                r#"{{"directed": false, "multigraph": true, "nodes": [{{"id": 0, "x": 0, "y": 0}}, {{"id": 1, "x": 1, "y": 1}}], "edges": [{{"from": 0, "to": 1, "weight": {}}}]}}"#,
+                count
+            );
+            assert!(
+                SavedGraph::from_file(&json, GraphKind::Multigraph).is_err(),
+                "{}",
+                count
+            );
+        }
+    }
+}
@@ -0,0 +1,21 @@
+mod algorithm_graph;
+mod graph_controls;
+mod graph_file_controls;
+mod step_chart;
+mod step_legend;
+mod step_lists;
+mod step_matrix;
+
+pub mod toolbar;
+
+pub use algorithm_graph::{
+    AlgorithmGraph, AlgorithmGraphEditor, AlgorithmGraphs, GraphKind, GROUP_COLOR_COUNT,
+};
+pub use graph_controls::{
+    ColoringControls, DampingControls, GraphControls, GraphEditControls, LayoutControls,
+};
+pub use graph_file_controls::{GraphFileControls, SavedGraph};
+pub use step_chart::StepChartPlot;
+pub use step_legend::{element_state_class, StepLegend};
+pub use step_lists::StepLists;
+pub use step_matrix::StepMatrixGrid;
@@ -0,0 +1,76 @@
+use graph::StepChart;
+use yew::prelude::*;
+
+const CHART_WIDTH: f32 = 320.0;
+const CHART_HEIGHT: f32 = 120.0;
+/// The room that is left for the labels of the axes.
+const CHART_PADDING: f32 = 40.0;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct StepChartPlotProps {
+    pub chart: StepChart,
+}
+
+/// Plots the values of a chart as a line on a logarithmic scale.
+#[function_component]
+pub fn StepChartPlot(props: &StepChartPlotProps) -> Html {
+    let chart = &props.chart;
+    if chart.values.is_empty() {
+        return html! {};
+    }
+
+    // Zero can't be shown on a logarithmic scale, so the values are kept above a tiny positive one
+    let logs = chart
+        .values
+        .iter()
+        .map(|value| value.max(1e-12).log10())
+        .collect::<Vec<f32>>();
+    let (min, max) = logs
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), log| {
+            (min.min(*log), max.max(*log))
+        });
+    let (plot_width, plot_height) = (CHART_WIDTH - CHART_PADDING, CHART_HEIGHT - 20.0);
+    let x =
+        |i: usize| CHART_PADDING + i as f32 / (logs.len() - 1).max(1) as f32 * (plot_width - 10.0);
+    let y = |log: f32| {
+        let t = if max > min {
+            (log - min) / (max - min)
+        } else {
+            0.5
+        };
+        10.0 + (1.0 - t) * plot_height
+    };
+
+    let points = logs
+        .iter()
+        .enumerate()
+        .map(|(i, log)| format!("{:.1},{:.1}", x(i), y(*log)))
+        .collect::<Vec<String>>()
+        .join(" ");
+    let (last_x, last_y) = (x(logs.len() - 1), y(*logs.last().unwrap()));
+
+    html! {
+        <figure class="step-chart">
+            <figcaption>{ &chart.title }</figcaption>
+            <svg
+                xmlns="http://www.w3.org/2000/svg"
+                viewBox={format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)}
+                role="img"
+                aria-label={format!(
+                    "{} over {} iterations, last {:.2e}",
+                    chart.title,
+                    chart.values.len(),
+                    chart.values.last().unwrap()
+                )}
+            >
+                <line class="axis" x1={CHART_PADDING.to_string()} y1="10" x2={CHART_PADDING.to_string()} y2={(10.0 + plot_height).to_string()} />
+                <line class="axis" x1={CHART_PADDING.to_string()} y1={(10.0 + plot_height).to_string()} x2={CHART_WIDTH.to_string()} y2={(10.0 + plot_height).to_string()} />
+                <text class="axis-label" x={(CHART_PADDING - 4.0).to_string()} y={y(max).to_string()}>{ format!("{:.0e}", 10_f32.powf(max)) }</text>
+                <text class="axis-label" x={(CHART_PADDING - 4.0).to_string()} y={y(min).to_string()}>{ format!("{:.0e}", 10_f32.powf(min)) }</text>
+                <polyline {points} />
+                <circle cx={last_x.to_string()} cy={last_y.to_string()} r="3" />
+            </svg>
+        </figure>
+    }
+}
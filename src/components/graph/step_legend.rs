@@ -0,0 +1,54 @@
+use graph::ElementState;
+use yew::prelude::*;
+
+/// The CSS class that colors elements with the color of the state.
+pub fn element_state_class(state: ElementState) -> &'static str {
+    match state {
+        ElementState::Active => "active",
+        ElementState::Frontier => "frontier",
+        ElementState::Selected => "selected",
+        ElementState::Rejected => "rejected",
+        ElementState::Conflict => "conflict",
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct StepLegendProps {
+    /// The states that the algorithm uses along with what they mean for it.
+    pub states: Vec<(ElementState, &'static str)>,
+    /// What the colors of the node groups mean, if the algorithm groups nodes.
+    #[prop_or_default]
+    pub groups: Option<&'static str>,
+}
+
+/// Explains what the colors of the nodes and edges mean.
+#[function_component]
+pub fn StepLegend(props: &StepLegendProps) -> Html {
+    html! {
+        <ul class="step-legend" aria-label="Legend">
+            {
+                props.states
+                    .iter()
+                    .map(|(state, description)| html! {
+                        <li>
+                            <span class={classes!("swatch", element_state_class(*state))} aria-hidden="true" />
+                            { description }
+                        </li>
+                    })
+                    .collect::<Html>()
+            }
+            {
+                if let Some(groups) = props.groups {
+                    html! {
+                        <li>
+                            <span class="swatch groups" aria-hidden="true" />
+                            { groups }
+                        </li>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+        </ul>
+    }
+}
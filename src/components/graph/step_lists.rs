@@ -0,0 +1,34 @@
+use graph::StepList;
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct StepListsProps {
+    pub lists: Vec<StepList>,
+}
+
+/// Shows the lists that the algorithm keeps track of at the current step, like its queue.
+#[function_component]
+pub fn StepLists(props: &StepListsProps) -> Html {
+    html! {
+        <div class="step-lists">
+            {
+                props.lists.iter().map(|list| html! {
+                    <section class="step-list">
+                        <h3>{ &list.title }</h3>
+                        {
+                            if list.items.is_empty() {
+                                html! { <p class="empty">{ "Empty" }</p> }
+                            } else {
+                                html! {
+                                    <ol>
+                                        { list.items.iter().map(|item| html! { <li>{ item }</li> }).collect::<Html>() }
+                                    </ol>
+                                }
+                            }
+                        }
+                    </section>
+                }).collect::<Html>()
+            }
+        </div>
+    }
+}
@@ -0,0 +1,67 @@
+use graph::{format_weight, StepMatrix};
+use yew::prelude::*;
+
+use super::element_state_class;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct StepMatrixGridProps {
+    pub matrix: StepMatrix,
+}
+
+/// Shows a matrix of values between the nodes as a heat grid, where smaller values are colder.
+#[function_component]
+pub fn StepMatrixGrid(props: &StepMatrixGridProps) -> Html {
+    let matrix = &props.matrix;
+    let (min, max) = matrix
+        .values
+        .iter()
+        .flatten()
+        .flatten()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+            (min.min(*value), max.max(*value))
+        });
+
+    let rows = matrix.values.iter().enumerate().map(|(i, row)| {
+        let cells = row.iter().enumerate().map(|(j, value)| {
+            let state = matrix
+                .cell_states
+                .get(&(i, j))
+                .map(|state| element_state_class(*state));
+            match value {
+                Some(value) => {
+                    let heat = if max > min {
+                        (value - min) / (max - min)
+                    } else {
+                        0.0
+                    };
+                    html! {
+                        <td class={classes!(state)} style={format!("--heat: {:.3}", heat)}>
+                            { format_weight(*value) }
+                        </td>
+                    }
+                }
+                None => html! { <td class={classes!("infinite", state)}>{ "∞" }</td> },
+            }
+        });
+
+        html! {
+            <tr>
+                <th scope="row">{ matrix.nodes[i] }</th>
+                { cells.collect::<Html>() }
+            </tr>
+        }
+    });
+
+    html! {
+        <table class="step-matrix">
+            <caption>{ &matrix.title }</caption>
+            <thead>
+                <tr>
+                    <th scope="col"><span class="visually-hidden">{ "From \\ to" }</span></th>
+                    { matrix.nodes.iter().map(|node| html! { <th scope="col">{ node }</th> }).collect::<Html>() }
+                </tr>
+            </thead>
+            <tbody>{ rows.collect::<Html>() }</tbody>
+        </table>
+    }
+}
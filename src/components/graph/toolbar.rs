@@ -0,0 +1,73 @@
+use yew::prelude::*;
+
+use crate::components::graph_editor::GraphTool;
+
+/// Different editing modes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphPageTool {
+    /// Edits the graph with the given tool.
+    Graph(GraphTool),
+    /// Chooses the node that the algorithm starts from.
+    Start,
+    /// Chooses the node that the algorithm ends at.
+    End,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct GraphToolbarProps {
+    pub active_tool: GraphPageTool,
+    /// Shows a tool for choosing the start node.
+    #[prop_or_default]
+    pub uses_start: bool,
+    /// Shows a tool for choosing the end node.
+    #[prop_or_default]
+    pub uses_end: bool,
+    pub on_tool_change: Callback<GraphPageTool>,
+}
+
+#[function_component]
+pub fn GraphToolbar(props: &GraphToolbarProps) -> Html {
+    let edit_buttons = GraphTool::EDITING.into_iter().map(|tool| {
+        (
+            GraphPageTool::Graph(tool),
+            tool.description(),
+            html! { { tool.icon() } },
+        )
+    });
+    let start_button = props.uses_start.then(|| {
+        (
+            GraphPageTool::Start,
+            "Choose the start node",
+            html! { <span style="color: var(--color-accent-1)">{ "◉" }</span> },
+        )
+    });
+    let end_button = props.uses_end.then(|| {
+        (
+            GraphPageTool::End,
+            "Choose the end node",
+            html! { <span style="color: orangered">{ "◉" }</span> },
+        )
+    });
+
+    html! {
+        <div class="graph-toolbar" role="toolbar" aria-label="Editing tools">
+            {
+                edit_buttons.chain(start_button).chain(end_button).map(|(tool, description, icon)| {
+                    let on_tool_change = props.on_tool_change.clone();
+                    let active = tool == props.active_tool;
+
+                    html! {
+                        <button
+                            class={classes!("toolbar-button", active.then_some("active"))}
+                            onclick={move |_| on_tool_change.emit(tool)}
+                            aria-label={description}
+                            aria-pressed={active.to_string()}
+                            title={description}>
+                            { icon }
+                        </button>
+                    }
+                }).collect::<Html>()
+            }
+        </div>
+    }
+}
@@ -0,0 +1,697 @@
+use std::collections::BTreeMap;
+
+use graph::Graph;
+use pathfinding::{graph::AdjacencyList, Coord, Distance, Node};
+use rand::Rng;
+use web_sys::Element;
+use yew::prelude::*;
+
+use crate::components::input_items::*;
+
+/// The width of the coordinate space that node positions are given in.
+pub const GRAPH_WIDTH: isize = 1000;
+/// The height of the coordinate space that node positions are given in.
+pub const GRAPH_HEIGHT: isize = 600;
+/// The smallest weight that an edge can be given, unless negative weights are allowed.
+pub const MIN_WEIGHT: f32 = 0.1;
+const NODE_RADIUS: isize = 22;
+/// How far from an edge a click can be to hit it.
+const EDGE_HIT_DISTANCE: f64 = 10.0;
+/// How far edges going in opposite directions between the same nodes are moved apart.
+const ANTIPARALLEL_EDGE_OFFSET: f64 = 8.0;
+/// How far apart parallel edges between the same nodes are drawn in multigraphs.
+const PARALLEL_EDGE_SPACING: f64 = 10.0;
+
+/// Ways of interacting with a [`GraphEditor`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraphTool {
+    /// Adds nodes by clicking on empty space and connects them by dragging from one node to another.
+    /// Clicking on an edge selects it so that its weight can be changed.
+    #[default]
+    Edit,
+    /// Moves nodes by dragging them.
+    Move,
+    /// Deletes the clicked nodes and edges.
+    Delete,
+    /// Doesn't change the graph, but emits the clicked nodes.
+    Pick,
+}
+impl GraphTool {
+    /// The tools that change the graph.
+    pub const EDITING: [GraphTool; 3] = [GraphTool::Edit, GraphTool::Move, GraphTool::Delete];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            GraphTool::Edit => "Add nodes, drag between them to connect them and click edges to change their weights",
+            GraphTool::Move => "Move nodes",
+            GraphTool::Delete => "Delete nodes and edges",
+            GraphTool::Pick => "Choose nodes",
+        }
+    }
+    pub fn icon(&self) -> &'static str {
+        match self {
+            GraphTool::Edit => "◉",
+            GraphTool::Move => "✥",
+            GraphTool::Delete => "✕",
+            GraphTool::Pick => "◎",
+        }
+    }
+}
+
+/// A graph of nodes connected by weighted edges that can be edited in a [`GraphEditor`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EditableGraph {
+    /// The positions of the nodes by their ids.
+    pub nodes: BTreeMap<usize, Coord>,
+    /// The weights of the edges. In undirected graphs the smaller id of the two nodes is always first.
+    pub edges: BTreeMap<(usize, usize), f32>,
+    pub directed: bool,
+}
+impl EditableGraph {
+    pub fn new(directed: bool) -> Self {
+        Self {
+            directed,
+            ..Default::default()
+        }
+    }
+    pub fn node(&self, id: usize) -> Node {
+        Node::new(id, self.nodes[&id])
+    }
+    /// Adds a node at `position` and returns its id.
+    pub fn add_node(&mut self, position: Coord) -> usize {
+        let id = self.nodes.keys().last().map_or(0, |id| id + 1);
+        self.nodes.insert(id, position);
+        id
+    }
+    /// Gets the key of the edge between two nodes in `edges`.
+    pub fn edge_key(&self, a: usize, b: usize) -> (usize, usize) {
+        if self.directed {
+            (a, b)
+        } else {
+            (a.min(b), a.max(b))
+        }
+    }
+    pub fn weight(&self, a: usize, b: usize) -> Option<f32> {
+        self.edges.get(&self.edge_key(a, b)).copied()
+    }
+    /// Connects two nodes with an edge whose weight is the distance between them in hundreds of units.
+    pub fn connect(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        let length: f32 = self.nodes[&a].distance(self.nodes[&b]);
+        let weight = ((length / 10.0).round() / 10.0).max(MIN_WEIGHT);
+        self.edges.insert(self.edge_key(a, b), weight);
+    }
+    /// Adds another edge between two nodes in a multigraph, where the weight of an edge is the number of edges between its nodes.
+    pub fn add_parallel_edge(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        *self.edges.entry(self.edge_key(a, b)).or_insert(0.0) += 1.0;
+    }
+    pub fn set_weight(&mut self, a: usize, b: usize, weight: f32) {
+        if let Some(old_weight) = self.edges.get_mut(&self.edge_key(a, b)) {
+            *old_weight = weight;
+        }
+    }
+    pub fn move_node(&mut self, id: usize, position: Coord) {
+        if let Some(old_position) = self.nodes.get_mut(&id) {
+            *old_position = position;
+        }
+    }
+    /// Gets the positions of the nodes as floats for the layout algorithms of the `graph` crate.
+    pub fn positions(&self) -> BTreeMap<usize, (f32, f32)> {
+        self.nodes
+            .iter()
+            .map(|(id, position)| (*id, (position.x as f32, position.y as f32)))
+            .collect()
+    }
+    /// Moves the nodes to the given positions. Nodes without a position stay where they are.
+    pub fn set_positions(&mut self, positions: &BTreeMap<usize, (f32, f32)>) {
+        for (id, (x, y)) in positions.iter() {
+            self.move_node(*id, Coord::new(x.round() as isize, y.round() as isize));
+        }
+    }
+    /// Moves the nodes to random positions, which layout algorithms can then untangle.
+    pub fn scatter(&mut self) {
+        let mut rng = rand::thread_rng();
+        for position in self.nodes.values_mut() {
+            *position = Coord::new(
+                rng.gen_range(NODE_RADIUS..=GRAPH_WIDTH - NODE_RADIUS),
+                rng.gen_range(NODE_RADIUS..=GRAPH_HEIGHT - NODE_RADIUS),
+            );
+        }
+    }
+    /// Removes a node along with its edges.
+    pub fn remove_node(&mut self, id: usize) {
+        self.nodes.remove(&id);
+        self.edges.retain(|(a, b), _| *a != id && *b != id);
+    }
+    pub fn remove_edge(&mut self, a: usize, b: usize) {
+        self.edges.remove(&self.edge_key(a, b));
+    }
+    /// Gets the graph without the positions of the nodes for the algorithms of the `graph` crate.
+    pub fn to_graph(&self) -> Graph {
+        Graph::new(
+            self.nodes.keys().copied().collect(),
+            self.edges.clone(),
+            self.directed,
+        )
+    }
+    pub fn to_adjacency_list(&self) -> AdjacencyList<Node, f32> {
+        let mut graph = AdjacencyList::new(BTreeMap::new());
+        for id in self.nodes.keys() {
+            graph.add_vertex(self.node(*id));
+        }
+        for ((a, b), weight) in self.edges.iter() {
+            if self.directed {
+                graph.add_edge_directed(self.node(*a), self.node(*b), *weight);
+            } else {
+                graph.add_edge_undirected(self.node(*a), self.node(*b), *weight);
+            }
+        }
+        graph
+    }
+    /// Gets the end points of an edge as it is drawn.
+    ///
+    /// Directed edges end at the edge of the target node so that their arrowheads are visible,
+    /// and edges going both ways between two nodes are moved apart so that they don't overlap.
+    fn edge_line(&self, (a, b): (usize, usize)) -> ((f64, f64), (f64, f64)) {
+        let (pos_a, pos_b) = (self.nodes[&a], self.nodes[&b]);
+        let (x1, y1, x2, y2) = (
+            pos_a.x as f64,
+            pos_a.y as f64,
+            pos_b.x as f64,
+            pos_b.y as f64,
+        );
+        let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt().max(1.0);
+        let (dx, dy) = ((x2 - x1) / length, (y2 - y1) / length);
+
+        if !self.directed {
+            return ((x1, y1), (x2, y2));
+        }
+
+        let offset = if self.edges.contains_key(&(b, a)) {
+            ANTIPARALLEL_EDGE_OFFSET
+        } else {
+            0.0
+        };
+        // Perpendicular to the edge, on its right side
+        let (nx, ny) = (-dy * offset, dx * offset);
+        let end_distance = NODE_RADIUS as f64 + 2.0;
+        (
+            (x1 + nx, y1 + ny),
+            (x2 - dx * end_distance + nx, y2 - dy * end_distance + ny),
+        )
+    }
+}
+
+/// What is being dragged in the editor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Drag {
+    /// A new edge from a node to the pointer.
+    Edge { from: usize, to: Coord },
+    /// A node that is being moved.
+    Node { id: usize, position: Coord },
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct GraphEditorProps {
+    pub graph: EditableGraph,
+    pub tool: GraphTool,
+    /// Emitted with the new graph whenever the graph is edited.
+    pub on_change: Callback<EditableGraph>,
+    /// Emitted when a node is clicked with [`GraphTool::Pick`].
+    #[prop_or_default]
+    pub on_click_node: Callback<usize>,
+    /// Extra classes of nodes, for example to show their state in an algorithm.
+    #[prop_or_default]
+    pub node_classes: BTreeMap<usize, Classes>,
+    /// Text that is shown under nodes.
+    #[prop_or_default]
+    pub node_labels: BTreeMap<usize, String>,
+    /// Values between 0 and 1 that make nodes bigger and color them from cold to hot.
+    #[prop_or_default]
+    pub node_values: BTreeMap<usize, f32>,
+    /// Extra classes of edges by their keys, see [`EditableGraph::edge_key`].
+    #[prop_or_default]
+    pub edge_classes: BTreeMap<(usize, usize), Classes>,
+    /// Text that is shown on edges instead of their weights.
+    #[prop_or_default]
+    pub edge_labels: BTreeMap<(usize, usize), String>,
+    /// Edges that are drawn with an animation from the first node towards the second.
+    #[prop_or_default]
+    pub animated_edges: Vec<(usize, usize)>,
+    /// The animations of `animated_edges` start again whenever this changes.
+    #[prop_or_default]
+    pub animation_key: usize,
+    /// Whether the weights of the edges are shown and can be changed.
+    #[prop_or(true)]
+    pub show_weights: bool,
+    /// The smallest weight that an edge can be given.
+    #[prop_or(Some(MIN_WEIGHT))]
+    pub min_weight: Option<f32>,
+    /// Whether the graph is a multigraph, where the weights of the edges are the numbers of edges between their nodes.
+    /// The edges are drawn as parallel lines and connecting two nodes again adds another edge between them.
+    #[prop_or_default]
+    pub parallel_edges: bool,
+    /// The id of an element that describes the graph's state.
+    #[prop_or_default]
+    pub aria_describedby: Option<AttrValue>,
+}
+
+/// An editable drawing of a graph.
+#[function_component]
+pub fn GraphEditor(props: &GraphEditorProps) -> Html {
+    let GraphEditorProps {
+        graph,
+        tool,
+        on_change,
+        on_click_node,
+        ..
+    } = props.clone();
+
+    let svg_ref = use_node_ref();
+    let drag = use_state_eq(|| None::<Drag>);
+    // The edge whose weight is being edited
+    let selected_edge = use_state_eq(|| None::<(usize, usize)>);
+
+    let selected =
+        selected_edge.filter(|edge| tool == GraphTool::Edit && graph.edges.contains_key(edge));
+
+    // The graph as it is shown, with the dragged node at the pointer
+    let shown_graph = match *drag {
+        Some(Drag::Node { id, position }) => {
+            let mut graph = graph.clone();
+            graph.move_node(id, position);
+            graph
+        }
+        _ => graph.clone(),
+    };
+
+    // Gets the pointer's position in the graph's coordinate space
+    let point_at = {
+        let svg_ref = svg_ref.clone();
+
+        move |e: &PointerEvent| {
+            let rect = svg_ref.cast::<Element>()?.get_bounding_client_rect();
+            if rect.width() == 0.0 || rect.height() == 0.0 {
+                return None;
+            }
+            let x = (e.client_x() as f64 - rect.left()) / rect.width() * GRAPH_WIDTH as f64;
+            let y = (e.client_y() as f64 - rect.top()) / rect.height() * GRAPH_HEIGHT as f64;
+            Some(Coord::new(
+                (x as isize).clamp(NODE_RADIUS, GRAPH_WIDTH - NODE_RADIUS),
+                (y as isize).clamp(NODE_RADIUS, GRAPH_HEIGHT - NODE_RADIUS),
+            ))
+        }
+    };
+
+    // Gets the node at a position, if there is one
+    let node_at = {
+        let nodes = graph.nodes.clone();
+
+        move |point: Coord| {
+            nodes
+                .iter()
+                .find(|(_, position)| position.distance::<f32>(point) <= NODE_RADIUS as f32)
+                .map(|(id, _)| *id)
+        }
+    };
+
+    // Gets the edge closest to a position if it is close enough
+    let edge_at = {
+        let graph = graph.clone();
+
+        move |point: Coord| {
+            let (px, py) = (point.x as f64, point.y as f64);
+            graph
+                .edges
+                .keys()
+                .map(|edge| {
+                    let ((x1, y1), (x2, y2)) = graph.edge_line(*edge);
+                    let length_squared = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).max(1.0);
+                    let t = (((px - x1) * (x2 - x1) + (py - y1) * (y2 - y1)) / length_squared)
+                        .clamp(0.0, 1.0);
+                    let (cx, cy) = (x1 + t * (x2 - x1), y1 + t * (y2 - y1));
+                    (*edge, ((px - cx).powi(2) + (py - cy).powi(2)).sqrt())
+                })
+                .filter(|(_, distance)| *distance <= EDGE_HIT_DISTANCE)
+                .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                .map(|(edge, _)| edge)
+        }
+    };
+
+    let onpointerdown = {
+        let point_at = point_at.clone();
+        let node_at = node_at.clone();
+        let drag = drag.clone();
+        let selected_edge = selected_edge.clone();
+        let graph = graph.clone();
+        let on_change = on_change.clone();
+
+        move |e: PointerEvent| {
+            if e.button() != 0 {
+                return;
+            }
+            e.prevent_default();
+            let Some(point) = point_at(&e) else {
+                return;
+            };
+
+            // Keep getting the pointer's events while it is down, even if it leaves the graph
+            if let Some(el) = e.target_dyn_into::<Element>() {
+                let _ = el.set_pointer_capture(e.pointer_id());
+            }
+
+            let node = node_at(point);
+            let edge = node.map_or_else(|| edge_at(point), |_| None);
+            let mut new_graph = graph.clone();
+
+            match (tool, node, edge) {
+                (GraphTool::Edit, Some(id), _) => drag.set(Some(Drag::Edge {
+                    from: id,
+                    to: point,
+                })),
+                (GraphTool::Edit, None, Some(edge)) => selected_edge.set(Some(edge)),
+                (GraphTool::Edit, None, None) => {
+                    selected_edge.set(None);
+                    // Leave some room between nodes so that they don't overlap
+                    let too_close = graph
+                        .nodes
+                        .values()
+                        .any(|position| position.distance::<f32>(point) < (NODE_RADIUS * 3) as f32);
+                    if !too_close {
+                        new_graph.add_node(point);
+                        on_change.emit(new_graph);
+                    }
+                }
+                (GraphTool::Move, Some(id), _) => drag.set(Some(Drag::Node {
+                    id,
+                    position: point,
+                })),
+                (GraphTool::Delete, Some(id), _) => {
+                    new_graph.remove_node(id);
+                    on_change.emit(new_graph);
+                }
+                (GraphTool::Delete, None, Some((a, b))) => {
+                    new_graph.remove_edge(a, b);
+                    on_change.emit(new_graph);
+                }
+                (GraphTool::Pick, Some(id), _) => on_click_node.emit(id),
+                _ => (),
+            }
+        }
+    };
+
+    let onpointermove = {
+        let point_at = point_at.clone();
+        let drag = drag.clone();
+
+        move |e: PointerEvent| {
+            let Some(point) = point_at(&e) else {
+                return;
+            };
+            match *drag {
+                Some(Drag::Edge { from, .. }) => drag.set(Some(Drag::Edge { from, to: point })),
+                Some(Drag::Node { id, .. }) => drag.set(Some(Drag::Node {
+                    id,
+                    position: point,
+                })),
+                None => (),
+            }
+        }
+    };
+
+    let onpointerup = {
+        let drag = drag.clone();
+        let graph = graph.clone();
+        let on_change = on_change.clone();
+        let parallel_edges = props.parallel_edges;
+
+        move |e: PointerEvent| {
+            let mut new_graph = graph.clone();
+            match (*drag, point_at(&e)) {
+                (Some(Drag::Edge { from, .. }), Some(point)) => {
+                    if let Some(to) = node_at(point).filter(|to| *to != from) {
+                        if parallel_edges {
+                            new_graph.add_parallel_edge(from, to);
+                        } else {
+                            new_graph.connect(from, to);
+                        }
+                        on_change.emit(new_graph);
+                    }
+                }
+                (Some(Drag::Node { id, position }), _) => {
+                    new_graph.move_node(id, position);
+                    on_change.emit(new_graph);
+                }
+                _ => (),
+            }
+            drag.set(None);
+        }
+    };
+
+    let edges = shown_graph
+        .edges
+        .iter()
+        .map(|(edge, weight)| {
+            let ((x1, y1), (x2, y2)) = shown_graph.edge_line(*edge);
+            let label = props.edge_labels.get(edge).cloned().or_else(|| {
+                (props.show_weights && !props.parallel_edges).then(|| weight.to_string())
+            });
+            let count = if props.parallel_edges {
+                weight.round().max(1.0) as usize
+            } else {
+                1
+            };
+            let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt().max(1.0);
+            let (nx, ny) = (-(y2 - y1) / length, (x2 - x1) / length);
+            let lines = (0..count)
+                .map(|i| {
+                    let offset = (i as f64 - (count - 1) as f64 / 2.0) * PARALLEL_EDGE_SPACING;
+                    let (ox, oy) = (nx * offset, ny * offset);
+                    html! {
+                        <line
+                            x1={(x1 + ox).to_string()}
+                            y1={(y1 + oy).to_string()}
+                            x2={(x2 + ox).to_string()}
+                            y2={(y2 + oy).to_string()}
+                            marker-end={shown_graph.directed.then_some("url(#graphEditorArrow)")}
+                        />
+                    }
+                })
+                .collect::<Html>();
+            html! {
+                <g class={classes!(
+                    "edge",
+                    props.edge_classes.get(edge).cloned(),
+                    (selected == Some(*edge)).then_some("selected"),
+                )}>
+                    { lines }
+                    {
+                        if let Some(label) = label {
+                            html! {
+                                <text class="edge-weight" x={((x1 + x2) / 2.0).to_string()} y={((y1 + y2) / 2.0).to_string()}>
+                                    { label }
+                                </text>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </g>
+            }
+        })
+        .collect::<Html>();
+
+    let animated_edges = props
+        .animated_edges
+        .iter()
+        .filter(|(from, to)| {
+            shown_graph.nodes.contains_key(from) && shown_graph.nodes.contains_key(to)
+        })
+        .map(|(from, to)| {
+            let (pos_from, pos_to) = (shown_graph.nodes[from], shown_graph.nodes[to]);
+            let length: f32 = pos_from.distance(pos_to);
+            html! {
+                <line
+                    key={format!("{}-{}-{}", props.animation_key, from, to)}
+                    class="animated-edge"
+                    style={format!("--length: {};", length)}
+                    x1={pos_from.x.to_string()}
+                    y1={pos_from.y.to_string()}
+                    x2={pos_to.x.to_string()}
+                    y2={pos_to.y.to_string()}
+                />
+            }
+        })
+        .collect::<Html>();
+
+    let dragged_edge = match *drag {
+        Some(Drag::Edge { from, to }) => {
+            let pos_from = shown_graph.nodes[&from];
+            html! {
+                <line
+                    class="dragged-edge"
+                    x1={pos_from.x.to_string()}
+                    y1={pos_from.y.to_string()}
+                    x2={to.x.to_string()}
+                    y2={to.y.to_string()}
+                />
+            }
+        }
+        _ => html! {},
+    };
+
+    let nodes = shown_graph
+        .nodes
+        .iter()
+        .map(|(id, position)| {
+            let value = props.node_values.get(id);
+            let radius = value.map_or(NODE_RADIUS, |value| {
+                (NODE_RADIUS as f32 * (0.6 + 0.8 * value.clamp(0.0, 1.0))).round() as isize
+            });
+            html! {
+                <g
+                    class={classes!(
+                        "node",
+                        props.node_classes.get(id).cloned(),
+                        value.map(|_| "valued"),
+                    )}
+                    style={value.map(|value| format!("--heat: {:.3}", value))}
+                >
+                    <circle
+                        cx={position.x.to_string()}
+                        cy={position.y.to_string()}
+                        r={radius.to_string()}
+                    />
+                    <text x={position.x.to_string()} y={position.y.to_string()}>{ id }</text>
+                    {
+                        if let Some(label) = props.node_labels.get(id) {
+                            html! {
+                                <text class="node-label" x={position.x.to_string()} y={(position.y + radius + 16).to_string()}>
+                                    { label }
+                                </text>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </g>
+            }
+        })
+        .collect::<Html>();
+
+    let edge_panel = if let Some((a, b)) = selected {
+        let graph = graph.clone();
+        let on_change = on_change.clone();
+        let selected_edge = selected_edge.clone();
+        let min_weight = props.min_weight;
+
+        let change_weight = {
+            let graph = graph.clone();
+            let on_change = on_change.clone();
+
+            Callback::from(move |weight: f32| {
+                if min_weight.is_none_or(|min| weight >= min) {
+                    let mut new_graph = graph.clone();
+                    new_graph.set_weight(a, b, weight);
+                    on_change.emit(new_graph);
+                }
+            })
+        };
+
+        let change_count = {
+            let graph = graph.clone();
+            let on_change = on_change.clone();
+
+            Callback::from(move |count: usize| {
+                if count >= 1 {
+                    let mut new_graph = graph.clone();
+                    new_graph.set_weight(a, b, count as f32);
+                    on_change.emit(new_graph);
+                }
+            })
+        };
+
+        let delete_edge = Callback::from(move |_| {
+            let mut new_graph = graph.clone();
+            new_graph.remove_edge(a, b);
+            selected_edge.set(None);
+            on_change.emit(new_graph);
+        });
+
+        let arrow = if props.graph.directed { "→" } else { "–" };
+
+        html! {
+            <div class="graph-editor-edge">
+                {
+                    if props.parallel_edges {
+                        html! {
+                            <IntInput<usize>
+                                title={format!("Number of edges between {} {} {}", a, arrow, b)}
+                                value={props.graph.weight(a, b).map_or(1, |count| count.round() as usize)}
+                                oninput={change_count}
+                                min={Some(1)}
+                            />
+                        }
+                    } else if props.show_weights {
+                        html! {
+                            <FloatInput<f32>
+                                title={format!("Weight of edge {} {} {}", a, arrow, b)}
+                                value={props.graph.weight(a, b).unwrap_or_default()}
+                                oninput={change_weight}
+                                min={min_weight}
+                            />
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                <Button title={if props.parallel_edges { "Delete edges" } else { "Delete edge" }} onclick={delete_edge} />
+            </div>
+        }
+    } else {
+        html! {}
+    };
+
+    html! {
+        <>
+            <svg
+                ref={svg_ref}
+                class={classes!("graph-editor", format!("tool-{:?}", tool).to_lowercase())}
+                xmlns="http://www.w3.org/2000/svg"
+                viewBox={format!("0 0 {} {}", GRAPH_WIDTH, GRAPH_HEIGHT)}
+                style={format!("aspect-ratio: {} / {};", GRAPH_WIDTH, GRAPH_HEIGHT)}
+                role="application"
+                aria-label={format!("Graph editor. {}.", tool.description())}
+                aria-describedby={props.aria_describedby.clone()}
+                onpointerdown={onpointerdown}
+                onpointermove={onpointermove}
+                onpointerup={onpointerup.clone()}
+                onpointercancel={onpointerup}
+            >
+                <defs>
+                    <marker
+                        id="graphEditorArrow"
+                        viewBox="0 0 10 10"
+                        refX="8"
+                        refY="5"
+                        markerWidth="4"
+                        markerHeight="4"
+                        orient="auto-start-reverse"
+                    >
+                        <path d="M 0 0 L 10 5 L 0 10 z" />
+                    </marker>
+                </defs>
+                { edges }
+                { animated_edges }
+                { dragged_edge }
+                { nodes }
+            </svg>
+            { edge_panel }
+        </>
+    }
+}
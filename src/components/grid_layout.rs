@@ -0,0 +1,119 @@
+use pathfinding::Coord;
+use web_sys::CanvasRenderingContext2d;
+
+/// Maps cells to positions on a canvas, for both square and hexagonal grids.
+///
+/// Hexagonal grids use pointy-top hexagons where odd rows are shifted right by half a cell, like [`Coord::to_axial`] expects.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridLayout {
+    pub hex: bool,
+    pub cell_width: f64,
+    pub cell_height: f64,
+}
+impl GridLayout {
+    pub fn new(
+        hex: bool,
+        width: usize,
+        height: usize,
+        canvas_width: f64,
+        canvas_height: f64,
+    ) -> Self {
+        let (width, height) = (width as f64, height as f64);
+        if hex {
+            // Rows overlap by a quarter of a hexagon, and the shifted rows stick out by half a hexagon.
+            Self {
+                hex,
+                cell_width: canvas_width / (width + 0.5),
+                cell_height: canvas_height / (0.75 * (height - 1.0) + 1.0),
+            }
+        } else {
+            Self {
+                hex,
+                cell_width: canvas_width / width,
+                cell_height: canvas_height / height,
+            }
+        }
+    }
+    /// The ratio between the width and height of the whole grid when the cells are regular.
+    pub fn aspect_ratio(hex: bool, width: usize, height: usize) -> f64 {
+        let (width, height) = (width as f64, height as f64);
+        if hex {
+            // The height of a regular pointy-top hexagon is 2 / sqrt(3) times its width.
+            (width + 0.5) / ((0.75 * (height - 1.0) + 1.0) * 2.0 / 3.0_f64.sqrt())
+        } else {
+            width / height
+        }
+    }
+    pub fn center(&self, coord: Coord) -> (f64, f64) {
+        if self.hex {
+            let row_offset = if coord.y & 1 == 1 { 0.5 } else { 0.0 };
+            (
+                (coord.x as f64 + 0.5 + row_offset) * self.cell_width,
+                (coord.y as f64 * 0.75 + 0.5) * self.cell_height,
+            )
+        } else {
+            (
+                (coord.x as f64 + 0.5) * self.cell_width,
+                (coord.y as f64 + 0.5) * self.cell_height,
+            )
+        }
+    }
+    /// Adds the outline of the cell to the current path of the canvas.
+    pub fn add_cell(&self, ctx: &CanvasRenderingContext2d, coord: Coord) {
+        let (x, y) = self.center(coord);
+        let (half_width, half_height) = (self.cell_width / 2.0, self.cell_height / 2.0);
+        if self.hex {
+            ctx.move_to(x, y - half_height);
+            ctx.line_to(x + half_width, y - half_height / 2.0);
+            ctx.line_to(x + half_width, y + half_height / 2.0);
+            ctx.line_to(x, y + half_height);
+            ctx.line_to(x - half_width, y + half_height / 2.0);
+            ctx.line_to(x - half_width, y - half_height / 2.0);
+            ctx.close_path();
+        } else {
+            ctx.rect(
+                x - half_width,
+                y - half_height,
+                self.cell_width,
+                self.cell_height,
+            );
+        }
+    }
+    /// Gets the cell at a position on the canvas, or `None` if there is no cell there.
+    pub fn cell_at(&self, x: f64, y: f64, width: usize, height: usize) -> Option<Coord> {
+        let in_bounds = |coord: &Coord| {
+            coord.x >= 0 && coord.x < width as isize && coord.y >= 0 && coord.y < height as isize
+        };
+        if !self.hex {
+            let coord = Coord::new(
+                (x / self.cell_width).floor() as isize,
+                (y / self.cell_height).floor() as isize,
+            );
+            return Some(coord).filter(in_bounds);
+        }
+
+        // The closest center is in the hexagon under the position. Only the cells around the estimated row and column can be the closest.
+        let row = ((y / self.cell_height - 0.5) / 0.75).round() as isize;
+        let column = (x / self.cell_width - 0.5).round() as isize;
+        let distance = |coord: &Coord| {
+            let (center_x, center_y) = self.center(*coord);
+            // Scale the distances so that stretched hexagons still behave like regular ones
+            let dx = (x - center_x) / self.cell_width;
+            let dy = (y - center_y) / (self.cell_height * 3.0_f64.sqrt() / 2.0);
+            dx * dx + dy * dy
+        };
+        let closest = (row - 1..=row + 1)
+            .flat_map(|y| (column - 1..=column + 1).map(move |x| Coord::new(x, y)))
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))?;
+
+        // Positions outside of the grid are closest to the cells on the edges
+        let (grid_width, grid_height) = (
+            self.cell_width * (width as f64 + 0.5),
+            self.cell_height * (0.75 * (height as f64 - 1.0) + 1.0),
+        );
+        if x < 0.0 || y < 0.0 || x > grid_width || y > grid_height {
+            return None;
+        }
+        Some(closest).filter(in_bounds)
+    }
+}
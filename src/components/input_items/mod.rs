@@ -1,12 +1,16 @@
 mod button;
 mod checkbox;
 mod number_input;
+mod range_input;
 mod select_input;
+mod text_input;
 
 pub use button::Button;
 pub use checkbox::Checkbox;
 pub use number_input::{FloatInput, IntInput};
+pub use range_input::RangeInput;
 pub use select_input::SelectInput;
+pub use text_input::TextInput;
 
 use regex::Regex;
 
@@ -0,0 +1,57 @@
+use crate::components::input_items::input_title_to_id;
+
+use std::{fmt::Display, str::FromStr};
+
+use num_traits::Float;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct RangeInputProps<T: 'static + Clone + Copy + Display + PartialEq + Float + FromStr> {
+    pub title: String,
+    pub value: T,
+    pub min: T,
+    pub max: T,
+    #[prop_or(T::from(0.01).unwrap())]
+    pub step: T,
+    #[prop_or(false)]
+    pub disabled: bool,
+    pub oninput: Callback<T>,
+}
+
+#[function_component]
+pub fn RangeInput<T: 'static + Clone + Copy + Display + PartialEq + Float + FromStr>(
+    props: &RangeInputProps<T>,
+) -> Html {
+    let RangeInputProps {
+        title,
+        value,
+        min,
+        max,
+        step,
+        disabled,
+        oninput,
+    } = props.clone();
+    let id = input_title_to_id(&title);
+    let oninput = move |event: InputEvent| {
+        let el: HtmlInputElement = event.target_unchecked_into();
+        if let Ok(value) = el.value().parse::<T>() {
+            oninput.emit(value);
+        }
+    };
+
+    html! {
+        <div class="input range-input">
+            <label for={id.clone()}>{title.to_string()}</label>
+            <input {id}
+                type="range"
+                value={value.to_string()}
+                min={min.to_string()}
+                max={max.to_string()}
+                step={step.to_string()}
+                {disabled}
+                {oninput}
+            />
+        </div>
+    }
+}
@@ -0,0 +1,41 @@
+use crate::components::input_items::input_title_to_id;
+
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct TextInputProps {
+    pub title: String,
+    pub value: String,
+    #[prop_or(None)]
+    pub max_length: Option<usize>,
+    pub oninput: Callback<String>,
+}
+
+#[function_component]
+pub fn TextInput(props: &TextInputProps) -> Html {
+    let TextInputProps {
+        title,
+        value,
+        max_length,
+        oninput,
+    } = props.clone();
+    let id = input_title_to_id(&title);
+    let oninput = move |event: InputEvent| {
+        let el: HtmlInputElement = event.target_unchecked_into();
+        oninput.emit(el.value());
+    };
+
+    html! {
+        <div class="input text-input">
+            <label for={id.clone()}>{title.to_string()}</label>
+            <input {id}
+                type="text"
+                placeholder={title.to_string()}
+                {value}
+                maxlength={max_length.map(|length| length.to_string())}
+                {oninput}
+            />
+        </div>
+    }
+}
@@ -1,9 +1,14 @@
 pub mod algo_desc;
 pub mod collapsible;
+pub mod diagram;
+pub mod graph;
+pub mod graph_editor;
+pub mod grid_layout;
 pub mod input_items;
 pub mod raw_html;
 pub mod sidebar;
 pub mod step_slider;
 
+pub mod cellular_automata;
 pub mod pathfinding;
 pub mod sorting;
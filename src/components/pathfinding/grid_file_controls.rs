@@ -0,0 +1,182 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use pathfinding::waypoints::MAX_WAYPOINTS;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::HtmlInputElement;
+use yew::{platform::spawn_local, prelude::*};
+
+use super::Terrain;
+use crate::components::input_items::*;
+
+/// The largest width and height of a grid that can be imported.
+const MAX_SAVED_GRID_SIZE: usize = 200;
+
+/// A grid layout that can be saved to a JSON file and loaded later. Cells are stored as `[x, y]` pairs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedGrid {
+    pub width: usize,
+    pub height: usize,
+    #[serde(default)]
+    pub hex: bool,
+    pub start: (isize, isize),
+    pub end: (isize, isize),
+    #[serde(default)]
+    pub walls: Vec<(isize, isize)>,
+    /// The cells of each terrain type other than [`Terrain::Ground`].
+    #[serde(default)]
+    pub terrain: BTreeMap<Terrain, Vec<(isize, isize)>>,
+    #[serde(default)]
+    pub terrain_weights: BTreeMap<Terrain, f32>,
+    #[serde(default)]
+    pub waypoints: Vec<(isize, isize)>,
+}
+impl SavedGrid {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+    /// Parses a grid from JSON and checks that it can be shown. Cells outside of the grid are dropped, and so are
+    /// repeated waypoints and waypoints that can't be visited.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let mut grid: Self = serde_json::from_str(json)
+            .map_err(|err| format!("The file isn't a valid grid: {}", err))?;
+
+        let size_range = 2..=MAX_SAVED_GRID_SIZE;
+        if !size_range.contains(&grid.width) || !size_range.contains(&grid.height) {
+            return Err(format!(
+                "The width and height of the grid must be between 2 and {}",
+                MAX_SAVED_GRID_SIZE
+            ));
+        }
+        let (width, height) = (grid.width as isize, grid.height as isize);
+        let in_bounds =
+            move |&(x, y): &(isize, isize)| (0..width).contains(&x) && (0..height).contains(&y);
+
+        if !in_bounds(&grid.start) || !in_bounds(&grid.end) {
+            return Err("The start and end must be inside the grid".to_string());
+        }
+        if grid.start == grid.end {
+            return Err("The start and end must be in different cells".to_string());
+        }
+        if grid.terrain_weights.values().any(|weight| *weight <= 0.0) {
+            return Err("Terrain weights must be positive".to_string());
+        }
+
+        let (start, end) = (grid.start, grid.end);
+        grid.walls
+            .retain(|cell| in_bounds(cell) && *cell != start && *cell != end);
+        let walls = grid
+            .walls
+            .iter()
+            .copied()
+            .collect::<BTreeSet<(isize, isize)>>();
+        let mut seen = BTreeSet::new();
+        grid.waypoints.retain(|cell| {
+            in_bounds(cell)
+                && *cell != start
+                && *cell != end
+                && !walls.contains(cell)
+                && seen.insert(*cell)
+        });
+        if grid.waypoints.len() > MAX_WAYPOINTS {
+            return Err(format!(
+                "A grid can have at most {} waypoints, but this one has {}",
+                MAX_WAYPOINTS,
+                grid.waypoints.len()
+            ));
+        }
+        for cells in grid.terrain.values_mut() {
+            cells.retain(in_bounds);
+        }
+        grid.terrain.remove(&Terrain::Ground);
+
+        Ok(grid)
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct GridFileControlsProps {
+    pub on_export: Callback<()>,
+    pub on_import: Callback<SavedGrid>,
+}
+
+#[function_component]
+pub fn GridFileControls(props: &GridFileControlsProps) -> Html {
+    let GridFileControlsProps {
+        on_export,
+        on_import,
+    } = props.clone();
+
+    let file_input_ref = use_node_ref();
+    // The reason why the last imported file couldn't be loaded
+    let error = use_state_eq(|| None::<String>);
+
+    let on_export = Callback::from(move |_| {
+        on_export.emit(());
+    });
+
+    let on_click_import = {
+        let file_input_ref = file_input_ref.clone();
+
+        Callback::from(move |_| {
+            if let Some(input) = file_input_ref.cast::<HtmlInputElement>() {
+                input.click();
+            }
+        })
+    };
+
+    let on_choose_file = {
+        let error = error.clone();
+
+        Callback::from(move |event: Event| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+            let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                return;
+            };
+            // Allow choosing the same file again
+            input.set_value("");
+
+            let error = error.clone();
+            let on_import = on_import.clone();
+
+            spawn_local(async move {
+                let text = JsFuture::from(file.text())
+                    .await
+                    .ok()
+                    .and_then(|text| text.dyn_into::<js_sys::JsString>().ok())
+                    .map(String::from);
+                match text.map_or(Err("The file couldn't be read".to_string()), |text| {
+                    SavedGrid::from_json(&text)
+                }) {
+                    Ok(grid) => {
+                        error.set(None);
+                        on_import.emit(grid);
+                    }
+                    Err(err) => error.set(Some(err)),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="grid-file-controls">
+            <Button title="Export grid" onclick={on_export} />
+            <Button title="Import grid" onclick={on_click_import} />
+            <input
+                ref={file_input_ref}
+                type="file"
+                accept=".json,application/json"
+                hidden={true}
+                onchange={on_choose_file}
+            />
+            {
+                if let Some(error) = &*error {
+                    html! { <p class="error" role="alert">{ error }</p> }
+                } else {
+                    html! {}
+                }
+            }
+        </div>
+    }
+}
@@ -1,9 +1,23 @@
+mod grid_file_controls;
+mod node_graph;
+mod node_graph_controls;
 mod path_grid;
 mod pathfinding_controls;
+mod run_stats;
+mod state_legend;
+mod terrain_controls;
 mod wall_controls;
+mod waypoint_controls;
 
 pub mod toolbar;
 
+pub use grid_file_controls::{GridFileControls, SavedGrid};
+pub use node_graph::{NodeGraph, NodeGraphEditor};
+pub use node_graph_controls::NodeGraphControls;
 pub use path_grid::PathGrid;
 pub use pathfinding_controls::PathfindingControls;
+pub use run_stats::{push_run_stats, RunStats, RunStatsPanel};
+pub use state_legend::{state_class, StateLegend};
+pub use terrain_controls::{Terrain, TerrainControls};
 pub use wall_controls::WallControls;
+pub use waypoint_controls::WaypointControls;
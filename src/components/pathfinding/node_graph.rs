@@ -0,0 +1,146 @@
+use super::{state_class, toolbar::PathTool};
+use crate::components::graph_editor::{EditableGraph, GraphEditor, GraphTool};
+use pathfinding::{Coord, VertexState};
+use std::collections::BTreeMap;
+use yew::prelude::*;
+
+/// An undirected graph with a start node and an end node that a path is found between.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeGraph {
+    pub graph: EditableGraph,
+    pub start: usize,
+    pub end: usize,
+}
+impl NodeGraph {
+    /// Removes all edges and all nodes except the start and end nodes.
+    pub fn clear(&mut self) {
+        let (start, end) = (self.start, self.end);
+        self.graph.nodes.retain(|id, _| *id == start || *id == end);
+        self.graph.edges.clear();
+    }
+}
+impl Default for NodeGraph {
+    fn default() -> Self {
+        let mut graph = EditableGraph::new(false);
+        for (x, y) in [
+            (100, 300),
+            (260, 130),
+            (260, 470),
+            (460, 260),
+            (460, 480),
+            (660, 120),
+            (700, 380),
+            (900, 300),
+        ] {
+            graph.add_node(Coord::new(x, y));
+        }
+        for (a, b) in [
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (1, 5),
+            (2, 4),
+            (3, 4),
+            (3, 5),
+            (3, 6),
+            (4, 6),
+            (5, 7),
+            (6, 7),
+        ] {
+            graph.connect(a, b);
+        }
+        Self {
+            graph,
+            start: 0,
+            end: 7,
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct NodeGraphEditorProps {
+    pub graph: NodeGraph,
+    /// The states of the nodes at the current step.
+    pub states: BTreeMap<usize, VertexState>,
+    pub path: Vec<usize>,
+    /// The edges that were relaxed during the current step, from the expanded node to its neighbor.
+    pub relaxations: Vec<(usize, usize)>,
+    /// The index of the current step. Each step restarts the relaxation animations.
+    pub step: usize,
+    pub active_tool: PathTool,
+    /// Emitted with the new graph whenever it is edited. The start and end nodes can't be deleted.
+    pub on_change: Callback<NodeGraph>,
+    /// Emitted when a node is clicked with a tool other than [`PathTool::Graph`].
+    pub on_click_node: Callback<usize>,
+}
+
+/// A [`GraphEditor`] that shows the states of the nodes and the path found between the start and end nodes.
+#[function_component]
+pub fn NodeGraphEditor(props: &NodeGraphEditorProps) -> Html {
+    let NodeGraphEditorProps {
+        graph,
+        states,
+        path,
+        relaxations,
+        step,
+        active_tool,
+        on_change,
+        on_click_node,
+    } = props.clone();
+
+    let tool = match active_tool {
+        PathTool::Graph(tool) => tool,
+        _ => GraphTool::Pick,
+    };
+
+    let on_change = {
+        let graph = graph.clone();
+
+        Callback::from(move |new_graph: EditableGraph| {
+            if new_graph.nodes.contains_key(&graph.start)
+                && new_graph.nodes.contains_key(&graph.end)
+            {
+                on_change.emit(NodeGraph {
+                    graph: new_graph,
+                    ..graph.clone()
+                });
+            }
+        })
+    };
+
+    let node_classes = graph
+        .graph
+        .nodes
+        .keys()
+        .map(|id| {
+            let state = *states.get(id).unwrap_or(&VertexState::NotVisited);
+            (
+                *id,
+                classes!(
+                    state_class(state),
+                    (*id == graph.start).then_some("start"),
+                    (*id == graph.end).then_some("end"),
+                ),
+            )
+        })
+        .collect::<BTreeMap<usize, Classes>>();
+
+    let edge_classes = path
+        .windows(2)
+        .map(|pair| (graph.graph.edge_key(pair[0], pair[1]), classes!("path")))
+        .collect::<BTreeMap<(usize, usize), Classes>>();
+
+    html! {
+        <GraphEditor
+            graph={graph.graph}
+            {tool}
+            {on_change}
+            {on_click_node}
+            {node_classes}
+            {edge_classes}
+            animated_edges={relaxations}
+            animation_key={step}
+            aria_describedby="pathGridStatus"
+        />
+    }
+}
@@ -0,0 +1,32 @@
+use yew::prelude::*;
+
+use crate::components::input_items::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct NodeGraphControlsProps {
+    pub on_clear_graph: Callback<()>,
+    pub on_reset_graph: Callback<()>,
+}
+
+#[function_component]
+pub fn NodeGraphControls(props: &NodeGraphControlsProps) -> Html {
+    let NodeGraphControlsProps {
+        on_clear_graph,
+        on_reset_graph,
+    } = props.clone();
+
+    let on_clear_graph = Callback::from(move |_| {
+        on_clear_graph.emit(());
+    });
+
+    let on_reset_graph = Callback::from(move |_| {
+        on_reset_graph.emit(());
+    });
+
+    html! {
+        <div class="node-graph-controls">
+            <Button title="Clear graph" onclick={on_clear_graph} />
+            <Button title="Reset graph" onclick={on_reset_graph} />
+        </div>
+    }
+}
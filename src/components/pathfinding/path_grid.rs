@@ -1,4 +1,6 @@
-use pathfinding::{Coord, VertexState};
+use super::Terrain;
+use crate::components::grid_layout::GridLayout;
+use pathfinding::{Coord, VertexScores, VertexState};
 use std::{
     cell::RefCell,
     collections::{BTreeMap, BTreeSet},
@@ -24,23 +26,66 @@ pub struct PathGridConfig {
     color_start: String,
     color_end: String,
     color_wall: String,
+    color_frontier: String,
+    color_queued: String,
     color_new_visited: String,
     color_not_visited: String,
     color_visited: String,
     color_path: String,
+    color_queued_from_end: String,
+    color_new_visited_from_end: String,
+    color_visited_from_end: String,
+    color_meeting: String,
+    color_cursor: String,
+    color_grid: String,
+    color_waypoint: String,
 }
 
+/// The colors of the path's segments between waypoints. The first one is the normal path color.
+const SEGMENT_COLORS: [&str; 5] = ["#ffa500", "#ff4d4d", "#ffe14d", "#4dff88", "#e64dff"];
+
 #[derive(Properties, Clone, PartialEq)]
 pub struct PathGridProps {
     pub width: usize,
     pub height: usize,
+    /// Whether the cells are hexagons instead of squares.
+    #[prop_or_default]
+    pub hex: bool,
     pub graph: Rc<RefCell<BTreeMap<Coord, VertexState>>>,
     pub walls: Rc<RefCell<BTreeSet<Coord>>>,
     pub path: Option<Rc<RefCell<Vec<Coord>>>>,
+    /// The frontier of the maze generator at the current maze generation step.
+    #[prop_or_default]
+    pub frontier: Option<Rc<RefCell<BTreeSet<Coord>>>>,
+    /// The f, g and h scores that are shown when hovering over a visited cell.
+    #[prop_or_default]
+    pub scores: Rc<RefCell<BTreeMap<Coord, VertexScores<f32>>>>,
+    #[prop_or_default]
+    pub terrain: Rc<RefCell<BTreeMap<Coord, Terrain>>>,
+    /// The weights of terrain types, which determine how strongly the terrain is shaded.
+    #[prop_or_default]
+    pub terrain_weights: BTreeMap<Terrain, f32>,
+    /// The cells that the path visits between the start and the end, in the order they are visited.
+    #[prop_or_default]
+    pub waypoints: Vec<Coord>,
+    /// The paths between consecutive stops when there are waypoints. Each of them is drawn in its own color.
+    #[prop_or_default]
+    pub path_segments: Rc<RefCell<Vec<Vec<Coord>>>>,
     pub start: Coord,
     pub end: Coord,
     pub on_click_cell: Callback<Coord>,
     pub on_draw_end: Callback<()>,
+    /// Emitted when the start cell is dragged to a new cell.
+    pub on_move_start: Callback<Coord>,
+    /// Emitted when the end cell is dragged to a new cell.
+    pub on_move_end: Callback<Coord>,
+}
+
+/// The cells that can be dragged on the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DragTarget {
+    Start,
+    End,
 }
 
 #[function_component]
@@ -48,9 +93,16 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
     let PathGridProps {
         width,
         height,
+        hex,
         graph,
         walls,
         path,
+        frontier,
+        scores,
+        terrain,
+        terrain_weights,
+        waypoints,
+        path_segments,
         on_click_cell,
         on_draw_end,
         ..
@@ -59,10 +111,19 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
         color_start: "#00ff66".to_string(),
         color_end: "#ff4500".to_string(),
         color_wall: "#cccccc".to_string(),
+        color_frontier: "#66dd99".to_string(),
+        color_queued: "#99e6ff".to_string(),
         color_new_visited: "#00bbff".to_string(),
         color_not_visited: "".to_string(),
         color_visited: "#0066ff".to_string(),
         color_path: "#ffa500".to_string(),
+        color_queued_from_end: "#ffc2e0".to_string(),
+        color_new_visited_from_end: "#ff66b3".to_string(),
+        color_visited_from_end: "#cc0066".to_string(),
+        color_meeting: "#ffee00".to_string(),
+        color_cursor: "#aa44ff".to_string(),
+        color_grid: "hsla(0, 0%, 50%, 30%)".to_string(),
+        color_waypoint: "#9400d3".to_string(),
     };
     let (start, end) = (props.start, props.end);
 
@@ -79,17 +140,47 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
     let wall_canvas: UseStateHandle<Option<HtmlCanvasElement>> = use_state(|| None);
     let wall_ctx: UseStateHandle<Option<CanvasRenderingContext2d>> = use_state(|| None);
 
-    // Emit the coordinates of the hovered cell if the mouse button is down
-    let oncellclick = Callback::from(move |(e, x, y): (MouseEvent, isize, isize)| {
-        e.prevent_default();
-        if e.buttons() == 1 {
-            on_click_cell.emit(Coord::new(x, y));
-        }
-    });
+    // The cell selected with the keyboard
+    let cursor: UseStateHandle<Option<Coord>> = use_state_eq(|| None);
+    // The cell that the mouse is over
+    let hovered: UseStateHandle<Option<Coord>> = use_state_eq(|| None);
+
+    // The start or end cell that is being dragged
+    let drag_target = use_mut_ref(|| None::<DragTarget>);
+
+    // Move the cursor with the arrow keys and use the active tool with enter or space
+    let onkeydown = {
+        let cursor = cursor.clone();
+        let on_click_cell = on_click_cell.clone();
+        let on_draw_end = on_draw_end.clone();
+
+        Callback::from(move |e: KeyboardEvent| {
+            let Coord { x, y } = cursor.unwrap_or(start);
+            let new_cursor = match e.key().as_str() {
+                "ArrowUp" => Coord::new(x, (y - 1).max(0)),
+                "ArrowDown" => Coord::new(x, (y + 1).min(height as isize - 1)),
+                "ArrowLeft" => Coord::new((x - 1).max(0), y),
+                "ArrowRight" => Coord::new((x + 1).min(width as isize - 1), y),
+                "Enter" | " " => {
+                    on_click_cell.emit(Coord::new(x, y));
+                    on_draw_end.emit(());
+                    Coord::new(x, y)
+                }
+                "Escape" => {
+                    cursor.set(None);
+                    return;
+                }
+                _ => return,
+            };
+            e.prevent_default();
+            cursor.set(Some(new_cursor));
+        })
+    };
 
-    // Draw the current step's values on the canvas.
+    // Draw the current step's values and the terrain on the canvas.
     let draw_background = {
         let graph = graph.clone();
+        let terrain = terrain.clone();
         let canvas = background_canvas.clone();
         let ctx = background_ctx.clone();
         let config = config.clone();
@@ -99,42 +190,61 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
                 if let Some(ctx) = ctx.as_ref() {
                     let canvas_width = canvas.width() as f64;
                     let canvas_height = canvas.height() as f64;
-                    let cell_width = canvas_width / width as f64;
-                    let cell_height = canvas_height / height as f64;
+                    let layout = GridLayout::new(hex, width, height, canvas_width, canvas_height);
 
                     ctx.clear_rect(0.0, 0.0, canvas_width, canvas_height);
 
-                    ctx.begin_path();
-                    ctx.set_fill_style(&config.color_visited.as_str().into());
-                    for (vertex, _) in graph
-                        .borrow()
-                        .iter()
-                        .filter(|(_, state)| **state == VertexState::Visited)
-                    {
-                        ctx.rect(
-                            vertex.x as f64 * cell_width,
-                            vertex.y as f64 * cell_height,
-                            cell_width,
-                            cell_height,
-                        );
+                    for (state, color) in [
+                        (VertexState::Queued, &config.color_queued),
+                        (VertexState::Visited, &config.color_visited),
+                        (VertexState::NewVisited, &config.color_new_visited),
+                        (VertexState::QueuedFromEnd, &config.color_queued_from_end),
+                        (VertexState::VisitedFromEnd, &config.color_visited_from_end),
+                        (
+                            VertexState::NewVisitedFromEnd,
+                            &config.color_new_visited_from_end,
+                        ),
+                        (VertexState::Path, &config.color_path),
+                        (VertexState::Meeting, &config.color_meeting),
+                    ] {
+                        ctx.begin_path();
+                        ctx.set_fill_style(&color.as_str().into());
+                        for (vertex, _) in graph.borrow().iter().filter(|(_, s)| **s == state) {
+                            layout.add_cell(ctx, *vertex);
+                        }
+                        ctx.fill();
                     }
-                    ctx.fill();
 
-                    ctx.begin_path();
-                    ctx.set_fill_style(&config.color_new_visited.as_str().into());
-                    for (vertex, _) in graph
-                        .borrow()
-                        .iter()
-                        .filter(|(_, state)| **state == VertexState::NewVisited)
-                    {
-                        ctx.rect(
-                            vertex.x as f64 * cell_width,
-                            vertex.y as f64 * cell_height,
-                            cell_width,
-                            cell_height,
-                        );
+                    // Terrain is drawn on top of the states so that the weights stay visible during the search.
+                    // Heavier terrain is more opaque.
+                    let max_weight = terrain_weights.values().fold(1.0_f32, |a, b| a.max(*b));
+                    for terrain_type in Terrain::PAINTABLE {
+                        let (r, g, b) = terrain_type.color();
+                        let weight = *terrain_weights.get(&terrain_type).unwrap_or(&1.0);
+                        let alpha = 0.2 + 0.5 * weight / max_weight;
+
+                        ctx.begin_path();
+                        ctx.set_fill_style(&format!("rgba({}, {}, {}, {})", r, g, b, alpha).into());
+                        for (vertex, _) in
+                            terrain.borrow().iter().filter(|(_, t)| **t == terrain_type)
+                        {
+                            layout.add_cell(ctx, *vertex);
+                        }
+                        ctx.fill();
+                    }
+
+                    // The grid pattern only works for square grids, so the outlines of hexagons are drawn here
+                    if hex {
+                        ctx.begin_path();
+                        ctx.set_stroke_style(&config.color_grid.as_str().into());
+                        ctx.set_line_width(1.5);
+                        for y in 0..height as isize {
+                            for x in 0..width as isize {
+                                layout.add_cell(ctx, Coord::new(x, y));
+                            }
+                        }
+                        ctx.stroke();
                     }
-                    ctx.fill();
                 }
             }
         }
@@ -142,6 +252,9 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
 
     let draw_foreground = {
         let path = path.clone();
+        let waypoints = waypoints.clone();
+        let path_segments = path_segments.clone();
+        let cursor = cursor.clone();
         let canvas = foreground_canvas.clone();
         let ctx = foreground_ctx.clone();
         let config = config.clone();
@@ -151,51 +264,78 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
                 if let Some(ctx) = ctx.as_ref() {
                     let canvas_width = canvas.width() as f64;
                     let canvas_height = canvas.height() as f64;
-                    let cell_width = canvas_width / width as f64;
-                    let cell_height = canvas_height / height as f64;
+                    let layout = GridLayout::new(hex, width, height, canvas_width, canvas_height);
 
                     ctx.clear_rect(0.0, 0.0, canvas_width, canvas_height);
 
                     if let Some(path) = path {
                         if !path.borrow().is_empty() {
-                            ctx.set_stroke_style(&config.color_path.as_str().into());
                             ctx.set_line_cap("round");
                             ctx.set_line_join("round");
-                            ctx.set_line_width(cell_width * 0.4);
-
-                            ctx.begin_path();
-
-                            ctx.move_to(
-                                path.borrow()[0].x as f64 * cell_width + 0.5 * cell_width,
-                                path.borrow()[0].y as f64 * cell_height + 0.5 * cell_height,
-                            );
-
-                            for coord in path.borrow()[1..].iter() {
-                                ctx.line_to(
-                                    coord.x as f64 * cell_width + 0.5 * cell_width,
-                                    coord.y as f64 * cell_height + 0.5 * cell_height,
-                                );
+                            ctx.set_line_width(layout.cell_width * 0.4);
+
+                            // When there are waypoints, each part of the path is drawn in its own color
+                            let segments = if path_segments.borrow().len() > 1 {
+                                path_segments.borrow().clone()
+                            } else {
+                                vec![path.borrow().clone()]
+                            };
+
+                            for (i, segment) in segments.iter().enumerate() {
+                                let color = if segments.len() > 1 {
+                                    SEGMENT_COLORS[i % SEGMENT_COLORS.len()]
+                                } else {
+                                    config.color_path.as_str()
+                                };
+                                ctx.set_stroke_style(&color.into());
+
+                                ctx.begin_path();
+
+                                let (x, y) = layout.center(segment[0]);
+                                ctx.move_to(x, y);
+
+                                for coord in segment[1..].iter() {
+                                    let (x, y) = layout.center(*coord);
+                                    ctx.line_to(x, y);
+                                }
+
+                                ctx.stroke();
                             }
-
-                            ctx.stroke();
                         }
                     }
 
+                    // Waypoints are numbered in the order that they are visited
+                    ctx.set_font(&format!("bold {}px sans-serif", layout.cell_height * 0.6));
+                    ctx.set_text_align("center");
+                    ctx.set_text_baseline("middle");
+                    for (i, waypoint) in waypoints.iter().enumerate() {
+                        ctx.begin_path();
+                        ctx.set_fill_style(&config.color_waypoint.as_str().into());
+                        layout.add_cell(ctx, *waypoint);
+                        ctx.fill();
+
+                        let (x, y) = layout.center(*waypoint);
+                        ctx.set_fill_style(&"white".into());
+                        let _ = ctx.fill_text(&(i + 1).to_string(), x, y);
+                    }
+
+                    ctx.begin_path();
                     ctx.set_fill_style(&config.color_start.as_str().into());
-                    ctx.fill_rect(
-                        start.x as f64 * cell_width,
-                        start.y as f64 * cell_height,
-                        cell_width,
-                        cell_height,
-                    );
+                    layout.add_cell(ctx, start);
+                    ctx.fill();
 
+                    ctx.begin_path();
                     ctx.set_fill_style(&config.color_end.as_str().into());
-                    ctx.fill_rect(
-                        end.x as f64 * cell_width,
-                        end.y as f64 * cell_height,
-                        cell_width,
-                        cell_height,
-                    );
+                    layout.add_cell(ctx, end);
+                    ctx.fill();
+
+                    if let Some(cursor) = *cursor {
+                        ctx.begin_path();
+                        ctx.set_stroke_style(&config.color_cursor.as_str().into());
+                        ctx.set_line_width(layout.cell_width * 0.15);
+                        layout.add_cell(ctx, cursor);
+                        ctx.stroke();
+                    }
                 }
             }
         }
@@ -203,6 +343,7 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
 
     let draw_walls = {
         let walls = walls.clone();
+        let frontier = frontier.clone();
         let canvas = wall_canvas.clone();
         let ctx = wall_ctx.clone();
 
@@ -211,22 +352,25 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
                 if let Some(ctx) = ctx.as_ref() {
                     let canvas_width = canvas.width() as f64;
                     let canvas_height = canvas.height() as f64;
-                    let cell_width = canvas_width / width as f64;
-                    let cell_height = canvas_height / height as f64;
+                    let layout = GridLayout::new(hex, width, height, canvas_width, canvas_height);
 
                     ctx.clear_rect(0.0, 0.0, canvas_width, canvas_height);
 
                     ctx.begin_path();
                     ctx.set_fill_style(&config.color_wall.as_str().into());
                     for vertex in walls.borrow().iter() {
-                        ctx.rect(
-                            vertex.x as f64 * cell_width,
-                            vertex.y as f64 * cell_height,
-                            cell_width,
-                            cell_height,
-                        );
+                        layout.add_cell(ctx, *vertex);
                     }
                     ctx.fill();
+
+                    if let Some(frontier) = &frontier {
+                        ctx.begin_path();
+                        ctx.set_fill_style(&config.color_frontier.as_str().into());
+                        for vertex in frontier.borrow().iter() {
+                            layout.add_cell(ctx, *vertex);
+                        }
+                        ctx.fill();
+                    }
                 }
             }
         }
@@ -285,7 +429,11 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
                 draw_background();
                 || ()
             },
-            graph.borrow().clone(),
+            (
+                graph.borrow().clone(),
+                terrain.borrow().clone(),
+                props.terrain_weights.clone(),
+            ),
         );
     }
 
@@ -297,7 +445,14 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
                 draw_foreground();
                 || ()
             },
-            (path.map_or(vec![], |p| p.borrow().clone()), start, end),
+            (
+                path.map_or(vec![], |p| p.borrow().clone()),
+                path_segments.borrow().clone(),
+                waypoints,
+                start,
+                end,
+                *cursor,
+            ),
         );
     }
 
@@ -309,7 +464,10 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
                 draw_walls();
                 || ()
             },
-            walls.borrow().clone(),
+            (
+                walls.borrow().clone(),
+                frontier.map(|frontier| frontier.borrow().clone()),
+            ),
         );
     }
 
@@ -320,39 +478,143 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
             draw_walls();
             || ()
         },
-        (width, height, background_canvas_size),
+        (width, height, hex, background_canvas_size),
     );
 
-    let onmouseover = {
+    // Gets the cell under the pointer, or `None` if the pointer is outside of the grid
+    let cell_at = {
         let canvas_ref = wall_canvas_ref.clone();
 
-        move |e: MouseEvent| {
+        move |e: &PointerEvent| {
             let el = canvas_ref.get().unwrap().dyn_into::<Element>().unwrap();
-            let (x_px, y_px) = ((e.offset_x()) as f32, (e.offset_y()) as f32);
-            let (rect_width_px, rect_height_px) = (
-                el.client_width() as f32 / width as f32,
-                el.client_height() as f32 / height as f32,
-            );
-            let (x, y) = (
-                (x_px / rect_width_px).floor() as isize,
-                (y_px / rect_height_px).floor() as isize,
+            let layout = GridLayout::new(
+                hex,
+                width,
+                height,
+                el.client_width() as f64,
+                el.client_height() as f64,
             );
-            oncellclick.emit((e, x, y))
+            layout.cell_at(e.offset_x() as f64, e.offset_y() as f64, width, height)
         }
     };
 
+    // Pressing on the start or end cell starts dragging it, and pressing anywhere else uses the active tool
+    let onpointerdown = {
+        let cell_at = cell_at.clone();
+        let drag_target = drag_target.clone();
+        let on_click_cell = on_click_cell.clone();
+
+        move |e: PointerEvent| {
+            if e.button() != 0 {
+                return;
+            }
+            e.prevent_default();
+
+            // Keep getting the pointer's events while it is down, even if it leaves the grid
+            if let Some(el) = e.target_dyn_into::<Element>() {
+                let _ = el.set_pointer_capture(e.pointer_id());
+            }
+
+            if let Some(cell) = cell_at(&e) {
+                if cell == start {
+                    *drag_target.borrow_mut() = Some(DragTarget::Start);
+                } else if cell == end {
+                    *drag_target.borrow_mut() = Some(DragTarget::End);
+                } else {
+                    on_click_cell.emit(cell);
+                }
+            }
+        }
+    };
+
+    let onpointermove = {
+        let hovered = hovered.clone();
+        let drag_target = drag_target.clone();
+        let on_click_cell = on_click_cell.clone();
+        let on_move_start = props.on_move_start.clone();
+        let on_move_end = props.on_move_end.clone();
+
+        move |e: PointerEvent| {
+            let cell = cell_at(&e);
+            // Touch input has no hover, so only show the scores of touched cells
+            if e.pointer_type() == "mouse" || e.buttons() == 1 {
+                hovered.set(cell);
+            }
+
+            if let (Some(cell), 1) = (cell, e.buttons()) {
+                e.prevent_default();
+                match *drag_target.borrow() {
+                    Some(DragTarget::Start) if cell != start && cell != end => {
+                        on_move_start.emit(cell)
+                    }
+                    Some(DragTarget::End) if cell != start && cell != end => on_move_end.emit(cell),
+                    Some(_) => (),
+                    None => on_click_cell.emit(cell),
+                }
+            }
+        }
+    };
+
+    let onpointerup = move |_: PointerEvent| {
+        // Finishing a drag of the start or end cell isn't the end of drawing
+        if drag_target.borrow_mut().take().is_none() {
+            on_draw_end.emit(());
+        }
+    };
+
+    // Show the scores of the hovered cell, or the cell selected with the keyboard, if it has been reached at the current step
+    let score_tooltip = (*hovered)
+        .or(*cursor)
+        .filter(|vertex| {
+            graph
+                .borrow()
+                .get(vertex)
+                .is_some_and(|state| *state != VertexState::NotVisited)
+        })
+        .and_then(|vertex| scores.borrow().get(&vertex).map(|scores| (vertex, *scores)))
+        .map_or(html! {}, |(vertex, scores)| {
+            // A layout for a 100x100 canvas gives the position as percentages
+            let layout = GridLayout::new(hex, width, height, 100.0, 100.0);
+            let (x, y) = layout.center(vertex);
+            html! {
+                <div
+                    class="score-tooltip"
+                    style={format!(
+                        "left: {}%; top: {}%;",
+                        x + layout.cell_width / 2.0,
+                        y - layout.cell_height / 2.0
+                    )}
+                    aria-hidden="true"
+                >
+                    <span>{ format!("f: {:.2}", scores.f()) }</span>
+                    <span>{ format!("g: {:.2}", scores.g) }</span>
+                    <span>{ format!("h: {:.2}", scores.h) }</span>
+                </div>
+            }
+        });
+
+    let aspect_ratio = GridLayout::aspect_ratio(hex, width, height);
+
     html! {
-        <div class="path-grid" style={format!("aspect-ratio: {}/{}", width, height)}>
+        <div
+            class="path-grid"
+            style={format!("aspect-ratio: {}", aspect_ratio)}
+            tabindex="0"
+            role="application"
+            aria-label="Pathfinding grid. Use the arrow keys to move the cursor, enter or space to use the active tool and escape to hide the cursor."
+            aria-describedby="pathGridStatus"
+            {onkeydown}
+        >
             // Background (visited cells etc.)
             <canvas
                 ref={background_canvas_ref}
-                style={format!("z-index: 1; aspect-ratio: {} / {};", width, height)}
+                style={format!("z-index: 1; aspect-ratio: {};", aspect_ratio)}
                 width={background_canvas_size.0.to_string()}
                 height={background_canvas_size.1.to_string()}
             >
             </canvas>
 
-            // Grid pattern
+            // Grid pattern for square grids
             <svg
                 xmlns="http://www.w3.org/2000/svg"
                 style={"z-index: 2"}
@@ -376,7 +638,7 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
 
                 <rect
                     class="grid"
-                    fill="url(#gridPattern)"
+                    fill={if hex { "none" } else { "url(#gridPattern)" }}
                     // + 1.5 to make the right and bottom borders visible
                     width={(background_canvas_size.0 as f32 + 1.5).to_string()}
                     height={(background_canvas_size.1 as f32 + 1.5).to_string()}
@@ -385,21 +647,25 @@ pub fn PathGrid(props: &PathGridProps) -> Html {
 
             <canvas
                 ref={foreground_canvas_ref}
-                style={format!("z-index: 3; aspect-ratio: {} / {};", width, height, )}
+                style={format!("z-index: 3; aspect-ratio: {};", aspect_ratio)}
                 width={background_canvas_size.0.to_string()}
                 height={background_canvas_size.1.to_string()}
             >
             </canvas>
             <canvas
                 ref={wall_canvas_ref}
-                style={format!("z-index: 4; aspect-ratio: {} / {};", width, height, )}
+                style={format!("z-index: 4; aspect-ratio: {};", aspect_ratio)}
                 width={background_canvas_size.0.to_string()}
                 height={background_canvas_size.1.to_string()}
-                onmouseup={move |_| on_draw_end.emit(())}
-                onmousedown={onmouseover.clone()}
-                onmousemove={onmouseover}
+                onpointerdown={onpointerdown}
+                onpointermove={onpointermove}
+                onpointerup={onpointerup.clone()}
+                onpointercancel={onpointerup}
+                onpointerleave={move |_| hovered.set(None)}
             >
             </canvas>
+
+            { score_tooltip }
         </div>
     }
 }
@@ -1,13 +1,14 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::BTreeMap, iter::once, rc::Rc};
 
-use pathfinding::{Coord, Edge};
+use pathfinding::{Coord, CornerCutting, Edge, Heuristic};
 use yew::prelude::*;
 use yew_router::hooks::use_navigator;
 
 use crate::{
     components::input_items::*,
     pages::pathfinding::{
-        get_pathfinding_algorithms, PathfindingAlgorithm, PathfindingConfig, PathfindingRoute,
+        get_pathfinding_algorithms, GraphKind, PathfindingAlgorithm, PathfindingConfig,
+        PathfindingRoute,
     },
 };
 
@@ -33,6 +34,41 @@ pub fn PathfindingControls<E: 'static + Edge>(props: &PathfindingControlsProps<E
             .collect::<Vec<String>>()
     });
 
+    // `BTreeMap` so that the options are always shown in the same order.
+    let graph_kinds = use_state_eq(|| {
+        BTreeMap::from([("Grid", GraphKind::Grid), ("Node graph", GraphKind::Nodes)])
+    });
+
+    let heuristics = use_state_eq(|| {
+        BTreeMap::from([
+            ("Chebyshev", Heuristic::Chebyshev),
+            ("Euclidean", Heuristic::Euclidean),
+            ("Manhattan", Heuristic::Manhattan),
+            ("Octile", Heuristic::Octile),
+        ])
+    });
+
+    let corner_cutting_rules = use_state_eq(|| {
+        BTreeMap::from([
+            ("Always", CornerCutting::Always),
+            ("Never", CornerCutting::Never),
+            ("Past one wall", CornerCutting::OneWall),
+        ])
+    });
+
+    let change_graph_kind = {
+        let config = config.clone();
+        let on_update_config = on_update_config.clone();
+        let graph_kinds = graph_kinds.clone();
+
+        Callback::from(move |graph_kind: String| {
+            config.borrow_mut().graph_kind = *graph_kinds.get(graph_kind.as_str()).unwrap();
+            let heuristic = config.borrow().exact_heuristic();
+            config.borrow_mut().heuristic = heuristic;
+            on_update_config.emit(());
+        })
+    };
+
     let change_graph_width = {
         let config = config.clone();
         let on_update_config = on_update_config.clone();
@@ -59,10 +95,73 @@ pub fn PathfindingControls<E: 'static + Edge>(props: &PathfindingControlsProps<E
 
     let toggle_move_diagonally = {
         let config = config.clone();
+        let on_update_config = on_update_config.clone();
+
+        Callback::from(move |_| {
+            let move_diagonally = !config.borrow().move_diagonally;
+            config.borrow_mut().move_diagonally = move_diagonally;
+            // Switch to the heuristic that gives the exact cost on an empty grid with the new movement rules
+            let heuristic = config.borrow().exact_heuristic();
+            config.borrow_mut().heuristic = heuristic;
+            on_update_config.emit(());
+        })
+    };
+
+    let toggle_hex_grid = {
+        let config = config.clone();
+        let on_update_config = on_update_config.clone();
 
         Callback::from(move |_| {
-            let move_diagonally = config.borrow().move_diagonally;
-            config.borrow_mut().move_diagonally = !move_diagonally;
+            let hex_grid = !config.borrow().hex_grid;
+            config.borrow_mut().hex_grid = hex_grid;
+            // The other heuristics overestimate costs on hexagonal grids
+            let heuristic = config.borrow().exact_heuristic();
+            config.borrow_mut().heuristic = heuristic;
+            on_update_config.emit(());
+        })
+    };
+
+    let change_corner_cutting = {
+        let config = config.clone();
+        let on_update_config = on_update_config.clone();
+        let corner_cutting_rules = corner_cutting_rules.clone();
+
+        Callback::from(move |corner_cutting: String| {
+            config.borrow_mut().corner_cutting =
+                *corner_cutting_rules.get(corner_cutting.as_str()).unwrap();
+            on_update_config.emit(());
+        })
+    };
+
+    let change_heuristic = {
+        let config = config.clone();
+        let on_update_config = on_update_config.clone();
+        let heuristics = heuristics.clone();
+
+        Callback::from(move |heuristic: String| {
+            config.borrow_mut().heuristic = *heuristics.get(heuristic.as_str()).unwrap();
+            on_update_config.emit(());
+        })
+    };
+
+    let toggle_relaxation_steps = {
+        let config = config.clone();
+        let on_update_config = on_update_config.clone();
+
+        Callback::from(move |_| {
+            let relaxation_steps = !config.borrow().relaxation_steps;
+            config.borrow_mut().relaxation_steps = relaxation_steps;
+            on_update_config.emit(());
+        })
+    };
+
+    let toggle_incremental_replanning = {
+        let config = config.clone();
+        let on_update_config = on_update_config.clone();
+
+        Callback::from(move |_| {
+            let incremental_replanning = !config.borrow().incremental_replanning;
+            config.borrow_mut().incremental_replanning = incremental_replanning;
             on_update_config.emit(());
         })
     };
@@ -75,12 +174,91 @@ pub fn PathfindingControls<E: 'static + Edge>(props: &PathfindingControlsProps<E
         })
     };
 
+    // The first option turns the comparison off
+    let change_comparison = {
+        let config = config.clone();
+        let on_update_config = on_update_config.clone();
+
+        Callback::from(move |name: String| {
+            let comparison = get_pathfinding_algorithms()
+                .into_values()
+                .find(|algorithm: &PathfindingAlgorithm<Coord, E>| algorithm.name == name);
+            config.borrow_mut().comparison = comparison;
+            on_update_config.emit(());
+        })
+    };
+
     let change_algorithm = Callback::from(move |algorithm: String| {
         navigator.push(&PathfindingRoute::PathfindingAlgorithm {
-            algorithm: algorithm.replace(' ', "-").to_lowercase(),
+            algorithm: algorithm
+                .replace(' ', "-")
+                .replace('*', "-star")
+                .to_lowercase(),
         });
     });
 
+    let is_grid = config.borrow().graph_kind == GraphKind::Grid;
+    let is_hex_grid = is_grid && config.borrow().hex_grid;
+
+    let uses_heuristic = config.borrow().algorithm.uses_heuristic
+        || is_grid
+            && config
+                .borrow()
+                .comparison
+                .as_ref()
+                .is_some_and(|comparison| comparison.uses_heuristic);
+
+    let grid_controls = if is_grid {
+        html! {
+            <>
+                <SelectInput
+                    title="Compare with"
+                    options={once("None".to_string()).chain(algorithm_names.iter().cloned()).collect::<Vec<String>>()}
+                    selected_value={config.borrow().comparison.as_ref().map_or("None".to_string(), |comparison| comparison.name.clone())}
+                    onchange={change_comparison}
+                />
+                <IntInput<usize>
+                    title="Graph width"
+                    value={config.borrow().graph_width}
+                    oninput={change_graph_width}
+                    min={2}
+                />
+                <IntInput<usize>
+                    title="Graph height"
+                    value={config.borrow().graph_height}
+                    oninput={change_graph_height}
+                    min={2}
+                />
+                <Checkbox title="Hexagonal grid" value={config.borrow().hex_grid} oninput={toggle_hex_grid} />
+                {
+                    if is_hex_grid {
+                        html! {}
+                    } else {
+                        html! {
+                            <Checkbox title="Move diagonally" value={config.borrow().move_diagonally} oninput={toggle_move_diagonally} />
+                        }
+                    }
+                }
+                {
+                    if config.borrow().move_diagonally && !is_hex_grid {
+                        html! {
+                            <SelectInput
+                                title="Corner cutting"
+                                options={corner_cutting_rules.keys().map(|key| key.to_string()).collect::<Vec<String>>()}
+                                selected_value={corner_cutting_rules.iter().find(|(_, val)| **val == config.borrow().corner_cutting).unwrap().0.to_string()}
+                                onchange={change_corner_cutting}
+                            />
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+            </>
+        }
+    } else {
+        html! {}
+    };
+
     html! {
         <div class="pathfind-controls">
             <SelectInput
@@ -89,25 +267,43 @@ pub fn PathfindingControls<E: 'static + Edge>(props: &PathfindingControlsProps<E
                 selected_value={config.borrow().algorithm.name.to_string()}
                 onchange={change_algorithm}
             />
-            <IntInput<usize>
-                title="Graph width"
-                value={config.borrow().graph_width}
-                oninput={change_graph_width}
-                min={2}
-            />
-            <IntInput<usize>
-                title="Graph height"
-                value={config.borrow().graph_height}
-                oninput={change_graph_height}
-                min={2}
+            <SelectInput
+                title="Graph"
+                options={graph_kinds.keys().map(|key| key.to_string()).collect::<Vec<String>>()}
+                selected_value={graph_kinds.iter().find(|(_, val)| **val == config.borrow().graph_kind).unwrap().0.to_string()}
+                onchange={change_graph_kind}
             />
+            {
+                if uses_heuristic && !is_hex_grid {
+                    html! {
+                        <SelectInput
+                            title="Heuristic"
+                            options={heuristics.keys().map(|key| key.to_string()).collect::<Vec<String>>()}
+                            selected_value={heuristics.iter().find(|(_, val)| **val == config.borrow().heuristic).unwrap().0.to_string()}
+                            onchange={change_heuristic}
+                        />
+                    }
+                } else {
+                    html! {}
+                }
+            }
             <FloatInput<f32>
                 title="Playback time (seconds)"
                 value={config.borrow().playback_time}
                 oninput={change_playback_time}
                 min={0.0}
             />
-            <Checkbox title="Move diagonally" value={config.borrow().move_diagonally} oninput={toggle_move_diagonally} />
+            <Checkbox title="Show each relaxation as a step" value={config.borrow().relaxation_steps} oninput={toggle_relaxation_steps} />
+            {
+                if config.borrow().algorithm.replans_incrementally && is_grid {
+                    html! {
+                        <Checkbox title="Replan incrementally after changes" value={config.borrow().incremental_replanning} oninput={toggle_incremental_replanning} />
+                    }
+                } else {
+                    html! {}
+                }
+            }
+            { grid_controls }
         </div>
     }
 }
@@ -0,0 +1,135 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use pathfinding::{graph::AdjacencyList, PathfindingSteps, Vertex, VertexState};
+use yew::prelude::*;
+
+/// How many runs are kept in the history.
+const RUN_HISTORY_LENGTH: usize = 5;
+
+/// Statistics of a single run of a pathfinding algorithm.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunStats {
+    /// The name of the algorithm, along with the heuristic if it uses one.
+    pub label: String,
+    /// The number of moves in the path, or `None` if no path was found.
+    pub path_length: Option<usize>,
+    /// The sum of the weights of the edges along the path.
+    pub cost: Option<f32>,
+    /// The number of times a vertex was expanded.
+    pub expanded: usize,
+    /// The largest number of vertices that were in the open set at the same time.
+    pub peak_open_set: usize,
+    pub duration: instant::Duration,
+}
+impl RunStats {
+    /// Computes the statistics of a run from its steps and path. The steps shouldn't have their relaxations split.
+    pub fn new<V: Vertex>(
+        label: String,
+        graph: &AdjacencyList<V, f32>,
+        steps: &PathfindingSteps<V>,
+        path: &[V],
+        duration: instant::Duration,
+    ) -> Self {
+        let is_queued =
+            |state: &VertexState| matches!(state, VertexState::Queued | VertexState::QueuedFromEnd);
+
+        let mut states = BTreeMap::<V, VertexState>::new();
+        let mut open_set_size = 0_usize;
+        let mut peak_open_set = 0;
+        let mut expanded = 0;
+
+        for step in steps.steps.iter() {
+            for (vertex, state) in step.states.iter() {
+                if matches!(
+                    state,
+                    VertexState::NewVisited | VertexState::NewVisitedFromEnd
+                ) {
+                    expanded += 1;
+                }
+                let was_queued = states
+                    .insert(*vertex, *state)
+                    .is_some_and(|old| is_queued(&old));
+                match (was_queued, is_queued(state)) {
+                    (false, true) => open_set_size += 1,
+                    (true, false) => open_set_size -= 1,
+                    _ => (),
+                }
+            }
+            peak_open_set = peak_open_set.max(open_set_size);
+        }
+
+        let cost = path.windows(2).try_fold(0.0, |cost, pair| {
+            Some(cost + graph.get_neighbors(&pair[0])?.get(&pair[1])?)
+        });
+
+        Self {
+            label,
+            path_length: (!path.is_empty()).then(|| path.len() - 1),
+            cost: cost.filter(|_| !path.is_empty()),
+            expanded,
+            peak_open_set,
+            duration,
+        }
+    }
+}
+
+/// Adds a run to the start of the history. A run replaces the latest one if it has the same label,
+/// so that changing the grid doesn't push the other algorithms out of the history.
+pub fn push_run_stats(history: &mut VecDeque<RunStats>, stats: RunStats) {
+    if history
+        .front()
+        .is_some_and(|latest| latest.label == stats.label)
+    {
+        history.pop_front();
+    }
+    history.push_front(stats);
+    history.truncate(RUN_HISTORY_LENGTH);
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct RunStatsPanelProps {
+    /// The latest runs, newest first.
+    pub history: VecDeque<RunStats>,
+}
+
+#[function_component]
+pub fn RunStatsPanel(props: &RunStatsPanelProps) -> Html {
+    if props.history.is_empty() {
+        return html! {};
+    }
+
+    let rows = props
+        .history
+        .iter()
+        .enumerate()
+        .map(|(i, stats)| {
+            html! {
+                <tr class={classes!((i == 0).then_some("latest"))}>
+                    <th scope="row">{ &stats.label }</th>
+                    <td>{ stats.path_length.map_or("-".to_string(), |length| length.to_string()) }</td>
+                    <td>{ stats.cost.map_or("-".to_string(), |cost| format!("{:.1}", cost)) }</td>
+                    <td>{ stats.expanded }</td>
+                    <td>{ stats.peak_open_set }</td>
+                    <td>{ format!("{:.2} ms", stats.duration.as_secs_f64() * 1000.0) }</td>
+                </tr>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <table class="run-stats">
+            <caption>{ "Latest runs" }</caption>
+            <thead>
+                <tr>
+                    <th scope="col">{ "Algorithm" }</th>
+                    <th scope="col">{ "Path length" }</th>
+                    <th scope="col">{ "Cost" }</th>
+                    <th scope="col">{ "Expanded" }</th>
+                    <th scope="col">{ "Peak open set" }</th>
+                    <th scope="col">{ "Time" }</th>
+                </tr>
+            </thead>
+            <tbody>{ rows }</tbody>
+        </table>
+    }
+}
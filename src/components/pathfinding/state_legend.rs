@@ -0,0 +1,70 @@
+use pathfinding::VertexState;
+use yew::prelude::*;
+
+/// The states in the order that they are shown in the legend.
+const LEGEND_STATES: [VertexState; 8] = [
+    VertexState::Queued,
+    VertexState::NewVisited,
+    VertexState::Visited,
+    VertexState::QueuedFromEnd,
+    VertexState::NewVisitedFromEnd,
+    VertexState::VisitedFromEnd,
+    VertexState::Meeting,
+    VertexState::Path,
+];
+
+/// The CSS class that colors elements with the color of the state.
+pub fn state_class(state: VertexState) -> &'static str {
+    match state {
+        VertexState::NotVisited => "",
+        VertexState::Queued => "queued",
+        VertexState::NewVisited => "new-visited",
+        VertexState::Visited => "visited",
+        VertexState::Path => "path",
+        VertexState::QueuedFromEnd => "queued-from-end",
+        VertexState::NewVisitedFromEnd => "new-visited-from-end",
+        VertexState::VisitedFromEnd => "visited-from-end",
+        VertexState::Meeting => "meeting",
+    }
+}
+
+fn state_description(state: VertexState) -> &'static str {
+    match state {
+        VertexState::NotVisited => "Not visited",
+        VertexState::Queued => "Open set",
+        VertexState::NewVisited => "Expanded",
+        VertexState::Visited => "Closed set",
+        VertexState::Path => "Path",
+        VertexState::QueuedFromEnd => "Open set from end",
+        VertexState::NewVisitedFromEnd => "Expanded from end",
+        VertexState::VisitedFromEnd => "Closed set from end",
+        VertexState::Meeting => "Meeting point",
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct StateLegendProps {
+    /// The states that the algorithm uses. Only these are shown.
+    pub states: Vec<VertexState>,
+}
+
+/// Explains what the colors of the vertices mean.
+#[function_component]
+pub fn StateLegend(props: &StateLegendProps) -> Html {
+    html! {
+        <ul class="state-legend" aria-label="Legend">
+            {
+                LEGEND_STATES
+                    .into_iter()
+                    .filter(|state| props.states.contains(state))
+                    .map(|state| html! {
+                        <li>
+                            <span class={classes!("swatch", state_class(state))} aria-hidden="true" />
+                            { state_description(state) }
+                        </li>
+                    })
+                    .collect::<Html>()
+            }
+        </ul>
+    }
+}
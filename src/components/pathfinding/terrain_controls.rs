@@ -0,0 +1,96 @@
+use std::{cell::RefCell, rc::Rc};
+
+use pathfinding::Edge;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::{components::input_items::*, pages::pathfinding::PathfindingConfig};
+
+/// Types of terrain that can be painted on the grid. Moving through a cell costs its terrain's weight.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum Terrain {
+    /// Normal ground with a weight of 1. Painting with it removes other terrain.
+    #[default]
+    Ground,
+    Road,
+    Mud,
+    Water,
+}
+impl Terrain {
+    /// The terrain types that can be painted, excluding [`Terrain::Ground`].
+    pub const PAINTABLE: [Terrain; 3] = [Terrain::Road, Terrain::Mud, Terrain::Water];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Terrain::Ground => "Ground",
+            Terrain::Road => "Road",
+            Terrain::Mud => "Mud",
+            Terrain::Water => "Water",
+        }
+    }
+    /// The RGB color of the terrain. It is drawn with an opacity that depends on its weight.
+    pub fn color(&self) -> (u8, u8, u8) {
+        match self {
+            Terrain::Ground => (0, 0, 0),
+            Terrain::Road => (128, 128, 128),
+            Terrain::Mud => (139, 90, 43),
+            Terrain::Water => (30, 90, 220),
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct TerrainControlsProps<E: 'static + Edge> {
+    pub config: Rc<RefCell<PathfindingConfig<E>>>,
+    pub on_update_config: Callback<()>,
+    pub on_clear_terrain: Callback<()>,
+}
+
+#[function_component]
+pub fn TerrainControls<E: 'static + Edge>(props: &TerrainControlsProps<E>) -> Html {
+    let TerrainControlsProps {
+        config,
+        on_update_config,
+        on_clear_terrain,
+    } = props.clone();
+
+    let weight_inputs = Terrain::PAINTABLE
+        .iter()
+        .map(|terrain| {
+            let config = config.clone();
+            let on_update_config = on_update_config.clone();
+            let terrain = *terrain;
+
+            let change_weight = Callback::from(move |weight: f32| {
+                if let Some(weight) = E::from(weight) {
+                    if weight > E::zero() {
+                        config.borrow_mut().terrain_weights.insert(terrain, weight);
+                        on_update_config.emit(());
+                    }
+                }
+            });
+
+            html! {
+                <FloatInput<f32>
+                    title={format!("{} weight", terrain.name())}
+                    value={props.config.borrow().terrain_weight(terrain).to_f32().unwrap()}
+                    oninput={change_weight}
+                    min={0.1}
+                />
+            }
+        })
+        .collect::<Html>();
+
+    let on_clear_terrain = Callback::from(move |_| {
+        on_clear_terrain.emit(());
+    });
+
+    html! {
+        <div class="terrain-controls">
+            { weight_inputs }
+            <Button title="Clear terrain" onclick={on_clear_terrain} />
+        </div>
+    }
+}
@@ -1,11 +1,20 @@
 use yew::prelude::*;
 
+use super::Terrain;
+use crate::components::graph_editor::GraphTool;
+
 /// Different drawing modes
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PathTool {
     Start,
     End,
     Wall,
+    /// Paints the given terrain. [`Terrain::Ground`] erases terrain.
+    Terrain(Terrain),
+    /// Adds and removes cells that the path has to visit.
+    Waypoint,
+    /// Edits a node graph with the given tool.
+    Graph(GraphTool),
 }
 
 #[derive(Clone, PartialEq)]
@@ -19,33 +28,78 @@ struct PathToolButton {
 pub struct PathToolbarProps {
     #[prop_or(PathTool::Wall)]
     pub active_tool: PathTool,
+    /// Shows the tools for editing a node graph instead of a grid.
+    #[prop_or_default]
+    pub node_graph: bool,
     pub on_tool_change: Callback<PathTool>,
 }
 
 #[function_component]
 pub fn PathToolbar(props: &PathToolbarProps) -> Html {
+    let node_graph = props.node_graph;
     let tool_buttons = use_state_eq(|| {
-        vec![
-            PathToolButton {
+        let edit_buttons = if node_graph {
+            GraphTool::EDITING
+                .into_iter()
+                .map(|tool| PathToolButton {
+                    tool: PathTool::Graph(tool),
+                    description: tool.description().to_string(),
+                    icon: html! { { tool.icon() } },
+                })
+                .collect()
+        } else {
+            vec![PathToolButton {
                 tool: PathTool::Wall,
                 description: "Draw walls".to_string(),
                 icon: html! { { "▣" } },
-            },
-            PathToolButton {
-                tool: PathTool::Start,
-                description: "Set the path's starting point".to_string(),
-                icon: html! { <span style="color: var(--color-accent-1)">{ "▣" }</span> },
-            },
-            PathToolButton {
-                tool: PathTool::End,
-                description: "Set the path's end point".to_string(),
-                icon: html! { <span style="color: orangered">{ "▣" }</span> },
-            },
-        ]
+            }]
+        };
+        let terrain_buttons = if node_graph {
+            vec![]
+        } else {
+            [PathToolButton {
+                tool: PathTool::Waypoint,
+                description: "Add or remove waypoints".to_string(),
+                icon: html! { <span style="color: #9400d3">{ "◆" }</span> },
+            }]
+            .into_iter()
+            .chain(Terrain::PAINTABLE.into_iter()
+                .map(|terrain| {
+                    let (r, g, b) = terrain.color();
+                    PathToolButton {
+                        tool: PathTool::Terrain(terrain),
+                        description: format!("Paint {}", terrain.name().to_lowercase()),
+                        icon: html! { <span style={format!("color: rgb({}, {}, {})", r, g, b)}>{ "▩" }</span> },
+                    }
+                })
+                .chain([PathToolButton {
+                    tool: PathTool::Terrain(Terrain::Ground),
+                    description: "Erase terrain".to_string(),
+                    icon: html! { { "▢" } },
+                }]))
+                .collect()
+        };
+
+        edit_buttons
+            .into_iter()
+            .chain([
+                PathToolButton {
+                    tool: PathTool::Start,
+                    description: "Set the path's starting point".to_string(),
+                    icon: html! { <span style="color: var(--color-accent-1)">{ "▣" }</span> },
+                },
+                PathToolButton {
+                    tool: PathTool::End,
+                    description: "Set the path's end point".to_string(),
+                    icon: html! { <span style="color: orangered">{ "▣" }</span> },
+                },
+            ])
+            .chain(terrain_buttons)
+            .collect::<Vec<PathToolButton>>()
     });
 
     html! {
-        <div class="path-toolbar">
+        <div class="path-toolbar" role="toolbar" aria-label="Drawing tools">
             {
                 (*tool_buttons).clone().into_iter().map(|PathToolButton { tool, description, icon }| {
                     let on_tool_change = props.on_tool_change.clone();
@@ -58,6 +112,8 @@ pub fn PathToolbar(props: &PathToolbarProps) -> Html {
                                 if tool == active_tool { "active" } else { "" }
                             )}
                             onclick={move |_| on_tool_change.emit(tool)}
+                            aria-label={description.clone()}
+                            aria-pressed={(tool == active_tool).to_string()}
                             title={description}>
                             { icon }
                         </button>
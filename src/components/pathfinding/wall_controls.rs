@@ -1,20 +1,43 @@
+use std::{cell::RefCell, rc::Rc};
+
+use pathfinding::Edge;
 use yew::prelude::*;
 
-use crate::components::input_items::*;
+use crate::{
+    components::input_items::*,
+    pages::pathfinding::{get_maze_generators, PathfindingConfig},
+};
 
 #[derive(Properties, Clone, PartialEq)]
-pub struct WallControlsProps {
+pub struct WallControlsProps<E: 'static + Edge> {
+    pub config: Rc<RefCell<PathfindingConfig<E>>>,
     pub on_clear_walls: Callback<()>,
     pub on_generate_maze: Callback<()>,
 }
 
 #[function_component]
-pub fn WallControls(props: &WallControlsProps) -> Html {
+pub fn WallControls<E: 'static + Edge>(props: &WallControlsProps<E>) -> Html {
     let WallControlsProps {
+        config,
         on_clear_walls,
         on_generate_maze,
     } = props.clone();
 
+    let maze_generator_names = use_state_eq(|| {
+        get_maze_generators()
+            .keys()
+            .map(|name| name.to_string())
+            .collect::<Vec<String>>()
+    });
+
+    let change_maze_generator = {
+        let config = config.clone();
+
+        Callback::from(move |maze_generator: String| {
+            config.borrow_mut().maze_generator = maze_generator;
+        })
+    };
+
     let on_clear_walls = Callback::from(move |_| {
         on_clear_walls.emit(());
     });
@@ -26,6 +49,12 @@ pub fn WallControls(props: &WallControlsProps) -> Html {
     html! {
         <div class="wall-controls">
             <Button title="Clear walls" onclick={on_clear_walls} />
+            <SelectInput
+                title="Maze generator"
+                options={(*maze_generator_names).clone()}
+                selected_value={config.borrow().maze_generator.to_string()}
+                onchange={change_maze_generator}
+            />
             <Button title="Generate maze" onclick={on_generate_maze} />
         </div>
     }
@@ -0,0 +1,43 @@
+use std::{cell::RefCell, rc::Rc};
+
+use pathfinding::Edge;
+use yew::prelude::*;
+
+use crate::{components::input_items::*, pages::pathfinding::PathfindingConfig};
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct WaypointControlsProps<E: 'static + Edge> {
+    pub config: Rc<RefCell<PathfindingConfig<E>>>,
+    pub on_update_config: Callback<()>,
+    pub on_clear_waypoints: Callback<()>,
+}
+
+#[function_component]
+pub fn WaypointControls<E: 'static + Edge>(props: &WaypointControlsProps<E>) -> Html {
+    let WaypointControlsProps {
+        config,
+        on_update_config,
+        on_clear_waypoints,
+    } = props.clone();
+
+    let toggle_optimize_order = {
+        let config = config.clone();
+
+        Callback::from(move |_| {
+            let optimize_waypoint_order = !config.borrow().optimize_waypoint_order;
+            config.borrow_mut().optimize_waypoint_order = optimize_waypoint_order;
+            on_update_config.emit(());
+        })
+    };
+
+    let on_clear_waypoints = Callback::from(move |_| {
+        on_clear_waypoints.emit(());
+    });
+
+    html! {
+        <div class="waypoint-controls">
+            <Checkbox title="Visit in the best order" value={config.borrow().optimize_waypoint_order} oninput={toggle_optimize_order} />
+            <Button title="Clear waypoints" onclick={on_clear_waypoints} />
+        </div>
+    }
+}
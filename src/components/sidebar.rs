@@ -8,6 +8,8 @@ use crate::hooks::use_mouse_pos::use_mouse_pos;
 /// The screen width where the layout changes and the sidebar moves to the bottom
 const MOBILE_MODE_THRESHOLD: i32 = 600;
 const RESIZE_HANDLE_THICKNESS_PX: i32 = 4;
+/// How much the sidebar is resized per arrow key press
+const KEYBOARD_RESIZE_STEP_PX: i32 = 20;
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct SidebarProps {
@@ -61,6 +63,35 @@ pub fn Sidebar(props: &SidebarProps) -> Html {
         })
     };
 
+    // Allow resizing with the arrow keys when a resize handle is focused
+    let on_resize_width_keydown = {
+        let width_px = width_px.clone();
+
+        Callback::from(move |e: KeyboardEvent| {
+            let max_width = (window_width * 0.75) as i32;
+            let new_width = match e.key().as_str() {
+                "ArrowLeft" => *width_px - KEYBOARD_RESIZE_STEP_PX,
+                "ArrowRight" => *width_px + KEYBOARD_RESIZE_STEP_PX,
+                _ => return,
+            };
+            e.prevent_default();
+            width_px.set(new_width.clamp(RESIZE_HANDLE_THICKNESS_PX, max_width));
+        })
+    };
+    let on_resize_height_keydown = {
+        let height_px = height_px.clone();
+
+        Callback::from(move |e: KeyboardEvent| {
+            let new_height = match e.key().as_str() {
+                "ArrowUp" => *height_px + KEYBOARD_RESIZE_STEP_PX,
+                "ArrowDown" => *height_px - KEYBOARD_RESIZE_STEP_PX,
+                _ => return,
+            };
+            e.prevent_default();
+            height_px.set(new_height.clamp(RESIZE_HANDLE_THICKNESS_PX, window_height as i32));
+        })
+    };
+
     {
         let resizing_width = resizing_width.clone();
         let resizing_height = resizing_height.clone();
@@ -119,23 +150,35 @@ pub fn Sidebar(props: &SidebarProps) -> Html {
     }
 
     html! {
-        <div class="sidebar" style={format!("min-width: {}px; min-height: {}px", *width_px, *height_px)}
+        <aside class="sidebar" aria-label="Configuration" style={format!("min-width: {}px; min-height: {}px", *width_px, *height_px)}
         >
             <div
                 class="resize-handle resize-width"
+                role="separator"
+                aria-orientation="vertical"
+                aria-label="Resize sidebar width with the left and right arrow keys"
+                aria-valuenow={width_px.to_string()}
+                tabindex="0"
                 style={if *resizing_width { "opacity: 1" } else { "" }}
                 onmousedown={on_resize_width_mousedown}
-                ontouchstart={on_resize_width_touchstart}>
+                ontouchstart={on_resize_width_touchstart}
+                onkeydown={on_resize_width_keydown}>
             </div>
             <div
                 class="resize-handle resize-height"
+                role="separator"
+                aria-orientation="horizontal"
+                aria-label="Resize sidebar height with the up and down arrow keys"
+                aria-valuenow={height_px.to_string()}
+                tabindex="0"
                 style={if *resizing_height { "opacity: 1" } else { "" }}
                 onmousedown={on_resize_height_mousedown}
-                ontouchstart={on_resize_height_touchstart}>
+                ontouchstart={on_resize_height_touchstart}
+                onkeydown={on_resize_height_keydown}>
             </div>
             <div class="sidebar-content">
                 { for props.children.iter() }
             </div>
-        </div>
+        </aside>
     }
 }
@@ -1,7 +1,11 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-use crate::components::input_items::*;
+use crate::{
+    components::input_items::*,
+    utils::{audio::PitchMapping, prefers_reduced_motion},
+};
 
+use gloo_storage::{LocalStorage, Storage};
 use instant::Duration;
 use web_sys::OscillatorType;
 use yew::prelude::*;
@@ -9,18 +13,30 @@ use yew::prelude::*;
 #[derive(Clone, Debug, PartialEq)]
 pub struct AudioConfig {
     pub enabled: bool,
+    /// The volume from 0.0 to 1.0. Persisted in localStorage.
+    pub volume: f32,
+    /// Mutes the audio without changing the volume. Persisted in localStorage.
+    pub muted: bool,
     pub sound_type: OscillatorType,
     pub min_frequency: f32,
     pub max_frequency: f32,
+    pub pitch_mapping: PitchMapping,
+    /// Pan notes from left to right according to the index they were played for.
+    pub stereo_panning: bool,
     pub note_duration: Duration,
 }
 impl Default for AudioConfig {
     fn default() -> Self {
         Self {
-            enabled: true,
+            // Audio is disabled by default for users who prefer reduced motion
+            enabled: !prefers_reduced_motion(),
+            volume: LocalStorage::get("audio-volume").unwrap_or(0.5),
+            muted: LocalStorage::get("audio-muted").unwrap_or(false),
             sound_type: OscillatorType::Sine,
             min_frequency: 50.0,
             max_frequency: 800.0,
+            pitch_mapping: PitchMapping::default(),
+            stereo_panning: true,
             note_duration: Duration::from_millis(200),
         }
     }
@@ -38,8 +54,9 @@ pub fn AudioControls(props: &AudioControlsProps) -> Html {
         config,
         update_config,
     } = props.clone();
-    let sound_types: UseStateHandle<HashMap<&str, OscillatorType>> = use_state_eq(|| {
-        HashMap::from([
+    // `BTreeMap` so that the options are always shown in the same order.
+    let sound_types: UseStateHandle<BTreeMap<&str, OscillatorType>> = use_state_eq(|| {
+        BTreeMap::from([
             ("Sawtooth", OscillatorType::Sawtooth),
             ("Sine", OscillatorType::Sine),
             ("Square", OscillatorType::Square),
@@ -47,6 +64,14 @@ pub fn AudioControls(props: &AudioControlsProps) -> Html {
         ])
     });
 
+    let pitch_mappings = use_state_eq(|| {
+        BTreeMap::from([
+            ("Chromatic", PitchMapping::Chromatic),
+            ("Linear", PitchMapping::Linear),
+            ("Pentatonic", PitchMapping::Pentatonic),
+        ])
+    });
+
     let toggle_audio = {
         let config = config.clone();
         let update_config = update_config.clone();
@@ -62,6 +87,38 @@ pub fn AudioControls(props: &AudioControlsProps) -> Html {
         })
     };
 
+    let change_volume = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |volume: f32| {
+            LocalStorage::set("audio-volume", volume).unwrap();
+            update_config.emit((
+                AudioConfig {
+                    volume,
+                    ..config.clone()
+                },
+                false,
+            ));
+        })
+    };
+
+    let toggle_mute = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |_| {
+            LocalStorage::set("audio-muted", !config.muted).unwrap();
+            update_config.emit((
+                AudioConfig {
+                    muted: !config.muted,
+                    ..config.clone()
+                },
+                false,
+            ));
+        })
+    };
+
     let change_sound_type = {
         let config = config.clone();
         let update_config = update_config.clone();
@@ -78,6 +135,37 @@ pub fn AudioControls(props: &AudioControlsProps) -> Html {
         })
     };
 
+    let change_pitch_mapping = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+        let pitch_mappings = pitch_mappings.clone();
+
+        Callback::from(move |pitch_mapping: String| {
+            update_config.emit((
+                AudioConfig {
+                    pitch_mapping: *pitch_mappings.get(pitch_mapping.as_str()).unwrap(),
+                    ..config.clone()
+                },
+                false,
+            ))
+        })
+    };
+
+    let toggle_stereo_panning = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |_| {
+            update_config.emit((
+                AudioConfig {
+                    stereo_panning: !config.stereo_panning,
+                    ..config.clone()
+                },
+                false,
+            ))
+        })
+    };
+
     let change_min_frequency = {
         let config = config.clone();
         let update_config = update_config.clone();
@@ -110,12 +198,28 @@ pub fn AudioControls(props: &AudioControlsProps) -> Html {
     html! {
         <div class="audio-controls">
             <Checkbox title="Audio enabled" value={config.enabled} oninput={toggle_audio} />
+            <Checkbox title="Mute" value={config.muted} oninput={toggle_mute} />
+            <RangeInput<f32>
+                title={format!("Volume ({:.0}%)", config.volume * 100.0)}
+                value={config.volume}
+                min={0.0}
+                max={1.0}
+                disabled={config.muted}
+                oninput={change_volume}
+            />
             <SelectInput
-                title="Sound type"
-                options={sound_types.keys().into_iter().map(|key| key.to_string()).collect::<Vec<String>>()}
+                title="Waveform"
+                options={sound_types.keys().map(|key| key.to_string()).collect::<Vec<String>>()}
                 selected_value={sound_types.clone().iter().find(|(_, val)| **val == config.sound_type).unwrap().0.to_owned()}
                 onchange={change_sound_type}
             />
+            <SelectInput
+                title="Pitch mapping"
+                options={pitch_mappings.keys().map(|key| key.to_string()).collect::<Vec<String>>()}
+                selected_value={pitch_mappings.iter().find(|(_, val)| **val == config.pitch_mapping).unwrap().0.to_string()}
+                onchange={change_pitch_mapping}
+            />
+            <Checkbox title="Stereo panning" value={config.stereo_panning} oninput={toggle_stereo_panning} />
             <FloatInput<f32>
                 title="Minimum frequency"
                 value={props.config.min_frequency}
@@ -192,6 +192,8 @@ pub fn SortGraph(props: &SortGraphProps) -> Html {
             <canvas
                 ref={canvas_ref.clone()}
                 class="sort-graph"
+                role="img"
+                aria-label={format!("Bar graph of {} items at the current step", props.items.len())}
                 width={canvas_container_size.0.to_string()}
                 height={canvas_container_size.1.to_string()}
             ></canvas>
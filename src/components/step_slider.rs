@@ -2,6 +2,8 @@ use web_sys::HtmlInputElement;
 use yew::prelude::*;
 use yew_hooks::use_interval;
 
+use crate::components::input_items::input_title_to_id;
+
 #[derive(Clone, Debug, PartialEq, Properties)]
 pub struct StepSliderProps {
     #[prop_or_default]
@@ -13,6 +15,9 @@ pub struct StepSliderProps {
     pub playback_time: f32,
     #[prop_or(false)]
     pub disabled: bool,
+    /// Playback starts from the first step whenever this changes, e.g. when new steps have been made.
+    #[prop_or_default]
+    pub play_signal: usize,
     pub on_change: Callback<usize>,
 }
 
@@ -24,6 +29,7 @@ pub fn StepSlider(props: &StepSliderProps) -> Html {
         max,
         playback_time,
         disabled,
+        play_signal,
         on_change,
     } = props.clone();
 
@@ -52,6 +58,21 @@ pub fn StepSlider(props: &StepSliderProps) -> Html {
         );
     }
 
+    {
+        let interval_ms = interval_ms.clone();
+
+        use_effect_with_deps(
+            move |play_signal| {
+                if *play_signal != 0 && !disabled {
+                    let step_play_time_ms = playback_time / max as f32 * 1000.0;
+                    interval_ms.set(step_play_time_ms.max(max_refresh_rate_ms) as u32);
+                }
+                || ()
+            },
+            play_signal,
+        );
+    }
+
     {
         let on_change = on_change.clone();
         let interval_ms_value = *interval_ms;
@@ -112,12 +133,14 @@ pub fn StepSlider(props: &StepSliderProps) -> Html {
         }
     });
 
+    let id = format!("stepSlider{}", input_title_to_id(&label));
+
     html! {
         <>
             {
                 if !label.is_empty() {
                     html! {
-                        <label class="step-slider-label" for={format!("stepSlider{}", label.clone())}>{{ label.clone() }}</label>
+                        <label class="step-slider-label" for={id.clone()}>{{ label.clone() }}</label>
                     }
                 } else {
                     html! {}
@@ -127,7 +150,9 @@ pub fn StepSlider(props: &StepSliderProps) -> Html {
                 { playback_button(on_click_playback_button, playing) }
                 <input
                     type="range"
-                    id={format!("stepSlider{}", label.clone())}
+                    {id}
+                    aria-label={if label.is_empty() { "Step".to_string() } else { label.clone() }}
+                    aria-valuetext={format!("Step {} of {}", active_step_index, max)}
                     min="0"
                     max={max.to_string()}
                     value={active_step_index.to_string()}
@@ -141,11 +166,15 @@ pub fn StepSlider(props: &StepSliderProps) -> Html {
 
 fn playback_button(onclick: Callback<MouseEvent>, playing: bool) -> Html {
     html! {
-        <button aria-label="Step playback button" {onclick}>
+        <button
+            aria-label={if playing { "Pause step playback" } else { "Play steps" }}
+            aria-pressed={playing.to_string()}
+            {onclick}
+        >
             {
                 if playing {
                     html! {
-                        <svg width="500" height="600" viewBox="0 0 500 600" fill="none" xmlns="http://www.w3.org/2000/svg">
+                        <svg aria-hidden="true" width="500" height="600" viewBox="0 0 500 600" fill="none" xmlns="http://www.w3.org/2000/svg">
                             <g id="pause">
                                 <path
                                     d="M455 15H340.742C324.173 15 310.742 28.4314 310.742 45V555C310.742 571.569 324.173 585 340.742 585H455C471.569 585 485 571.569 485 555V45C485 28.4315 471.569 15 455 15Z"
@@ -159,7 +188,7 @@ fn playback_button(onclick: Callback<MouseEvent>, playing: bool) -> Html {
                     }
                 } else {
                     html! {
-                        <svg width="502" height="586" viewBox="0 0 502 586" fill="none" xmlns="http://www.w3.org/2000/svg">
+                        <svg aria-hidden="true" width="502" height="586" viewBox="0 0 502 586" fill="none" xmlns="http://www.w3.org/2000/svg">
                             <path id="play" d="M472.125 267.275L60.4349 20.2609C40.4391 8.26348 15 22.6669 15 45.9857V540.014C15 563.333 40.4391 577.737 60.4348 565.739L472.125 318.725C491.546 307.073 491.546 278.927 472.125 267.275Z" fill="var(--text-color)" stroke="var(--text-color)" stroke-width="30" stroke-linejoin="round"/>
                         </svg>
                     }
@@ -0,0 +1,3 @@
+//! This crate contains my implementations of data structures, like search trees.
+//! Each structure records a diagram of itself at every step of its operations so that they can be visualized.
+pub mod structures;
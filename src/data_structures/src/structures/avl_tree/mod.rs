@@ -0,0 +1,169 @@
+use diagram::*;
+
+use super::binary_tree::*;
+
+/// A binary search tree that keeps the heights of the subtrees of every node within one of each other
+/// by rotating the nodes after inserts and deletes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AvlTree {
+    tree: BinaryTree,
+}
+impl AvlTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Makes a tree by inserting the values in order without keeping the steps.
+    pub fn from_values(values: &[i64]) -> Self {
+        let mut tree = Self::new();
+        for value in values {
+            let _ = tree.insert(*value, &mut DiagramSteps::new());
+        }
+        tree
+    }
+    fn insert(&mut self, value: i64, steps: &mut DiagramSteps) -> Result<(), String> {
+        if let Some(id) = self.tree.insert_steps(value, steps, &draw)? {
+            let parent = self.tree.parent(id).map(|(parent, _)| parent);
+            self.rebalance(parent, steps);
+        }
+        Ok(())
+    }
+    fn delete(&mut self, value: i64, steps: &mut DiagramSteps) {
+        if let Some(parent) = self.tree.delete_steps(value, steps, &draw) {
+            self.rebalance(parent, steps);
+        }
+    }
+    /// Goes from the node up to the root, rotating the nodes whose balance factors are too large.
+    fn rebalance(&mut self, from: Option<usize>, steps: &mut DiagramSteps) {
+        let mut current = from;
+        while let Some(id) = current {
+            let value = self.tree.value(id);
+            let balance = balance_factor(&self.tree, id);
+
+            if balance.abs() <= 1 {
+                steps.push(
+                    format!(
+                        "{} has a balance factor of {}, so it is balanced",
+                        value,
+                        format_balance(balance)
+                    ),
+                    draw(
+                        &self.tree,
+                        &TreeHighlights::new().node(id, ElementState::Active),
+                    ),
+                );
+                current = self.tree.parent(id).map(|(parent, _)| parent);
+                continue;
+            }
+
+            // The side whose subtree is taller
+            let heavy = if balance > 1 { Side::Left } else { Side::Right };
+            let child = self.tree.child(id, heavy).unwrap();
+            let child_balance = balance_factor(&self.tree, child);
+            // If the child leans the other way, its taller subtree is in the middle and a single rotation
+            // would just move it to the other side.
+            let child_heavy = match child_balance.signum() {
+                1 => Side::Left,
+                -1 => Side::Right,
+                _ => heavy,
+            };
+            steps.push(
+                format!(
+                    "{} has a balance factor of {}, so its {} subtree is too tall. This is the {}-{} case",
+                    value,
+                    format_balance(balance),
+                    heavy.name(),
+                    heavy.name(),
+                    child_heavy.name()
+                ),
+                draw(
+                    &self.tree,
+                    &TreeHighlights::new()
+                        .node(id, ElementState::Conflict)
+                        .node(child, ElementState::Frontier)
+                        .edge((id, child), ElementState::Frontier),
+                ),
+            );
+
+            if child_heavy != heavy {
+                self.tree.rotate_steps(
+                    child,
+                    heavy,
+                    format!(
+                        "First make {} lean {} like {}",
+                        self.tree.value(child),
+                        heavy.name(),
+                        value
+                    ),
+                    steps,
+                    &draw,
+                );
+            }
+            let new_root = self.tree.rotate_steps(
+                id,
+                heavy.opposite(),
+                format!("Move the taller {} subtree of {} up", heavy.name(), value),
+                steps,
+                &draw,
+            );
+            current = self.tree.parent(new_root).map(|(parent, _)| parent);
+        }
+    }
+}
+impl Visualizer for AvlTree {
+    fn diagram(&self) -> Diagram {
+        draw(&self.tree, &TreeHighlights::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        search_tree_operations()
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Insert" => self.insert(value.number(), &mut steps)?,
+            "Search" => {
+                self.tree.search_steps(value.number(), &mut steps, &draw);
+            }
+            "Delete" => self.delete(value.number(), &mut steps),
+            "Traverse" => return self.tree.traverse(&value.text(), &draw),
+            "Clear" => {
+                self.tree = BinaryTree::new();
+                steps.push("Remove all values".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The number of nodes on the longest path from the node down to a leaf.
+fn height(tree: &BinaryTree, id: Option<usize>) -> i64 {
+    id.map_or(0, |id| {
+        let node = &tree.nodes[&id];
+        1 + height(tree, node.left).max(height(tree, node.right))
+    })
+}
+
+/// The height of the left subtree minus the height of the right subtree.
+fn balance_factor(tree: &BinaryTree, id: usize) -> i64 {
+    let node = &tree.nodes[&id];
+    height(tree, node.left) - height(tree, node.right)
+}
+
+fn format_balance(balance: i64) -> String {
+    match balance.signum() {
+        1 => format!("+{}", balance),
+        -1 => format!("−{}", -balance),
+        _ => "0".to_string(),
+    }
+}
+
+/// Draws the tree with the balance factors of the nodes below them.
+fn draw(tree: &BinaryTree, highlights: &TreeHighlights) -> Diagram {
+    tree.draw_with_labels(highlights, |id, node| {
+        (
+            node.value.to_string(),
+            Some(format_balance(balance_factor(tree, id))),
+        )
+    })
+}
@@ -0,0 +1,634 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use super::binary_tree::{node_key, search_tree_operations, Side, TREE_HEIGHT, TREE_WIDTH};
+
+/// The size of the cell of a key in the diagram.
+const KEY_SIZE: (f32, f32) = (44.0, 32.0);
+/// The space between the keys of a node and the outline of the node.
+const NODE_PADDING: f32 = 4.0;
+const MIN_ORDER: usize = 3;
+const MAX_ORDER: usize = 7;
+/// The largest number of keys in a tree, so that the leaves fit side by side in the diagram.
+const MAX_KEYS: usize = 30;
+
+/// Nodes on the way down from the root, with the indices of the children that were followed.
+type Path = Vec<(usize, usize)>;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct BTreeNode {
+    keys: Vec<i64>,
+    /// Empty for leaves, and one more than there are keys for the other nodes.
+    children: Vec<usize>,
+}
+impl BTreeNode {
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+/// The states of the nodes, the keys and the pointers of a B-tree at a step. Keys are unique, so they are keyed by
+/// their values, which also lets them glide from node to node when they move.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    nodes: BTreeMap<usize, ElementState>,
+    keys: BTreeMap<i64, ElementState>,
+    edges: BTreeMap<(usize, usize), ElementState>,
+}
+impl Highlights {
+    fn new() -> Self {
+        Self::default()
+    }
+    fn node(mut self, id: usize, state: ElementState) -> Self {
+        self.nodes.insert(id, state);
+        self
+    }
+    fn key(mut self, key: i64, state: ElementState) -> Self {
+        self.keys.insert(key, state);
+        self
+    }
+    /// Highlights the nodes of a path and the pointers between them.
+    fn path(mut self, path: &[usize], state: ElementState) -> Self {
+        for node in path {
+            self.nodes.insert(*node, state);
+        }
+        for pair in path.windows(2) {
+            self.edges.insert((pair[0], pair[1]), state);
+        }
+        self
+    }
+}
+
+/// A search tree whose nodes have many sorted keys and children. A B-tree of order *m* has at most *m* children
+/// per node, every node other than the root has at least *⌈m/2⌉* children, and all leaves are on the same level.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BTree {
+    order: usize,
+    nodes: BTreeMap<usize, BTreeNode>,
+    root: Option<usize>,
+    next_id: usize,
+}
+impl BTree {
+    pub fn new(order: usize) -> Self {
+        Self {
+            order: order.clamp(MIN_ORDER, MAX_ORDER),
+            nodes: BTreeMap::new(),
+            root: None,
+            next_id: 0,
+        }
+    }
+    /// Makes a tree by inserting the values in order without keeping the steps.
+    pub fn from_values(order: usize, values: &[i64]) -> Self {
+        let mut tree = Self::new(order);
+        for value in values {
+            let _ = tree.insert(*value, &mut DiagramSteps::new());
+        }
+        tree
+    }
+    fn max_keys(&self) -> usize {
+        self.order - 1
+    }
+    fn min_keys(&self) -> usize {
+        self.order.div_ceil(2) - 1
+    }
+    fn len(&self) -> usize {
+        self.nodes.values().map(|node| node.keys.len()).sum()
+    }
+    fn add_node(&mut self, node: BTreeNode) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.nodes.insert(id, node);
+        id
+    }
+    fn keys(&self, id: usize) -> &[i64] {
+        &self.nodes[&id].keys
+    }
+    fn push(&self, description: String, highlights: Highlights, steps: &mut DiagramSteps) {
+        steps.push(description, self.draw(&highlights));
+    }
+    /// Goes down from the root towards the value. Returns the path and the node and the index of the value
+    /// if it was found.
+    fn search_path(&self, value: i64, steps: &mut DiagramSteps) -> (Path, Option<(usize, usize)>) {
+        let mut path = vec![];
+        let mut nodes = vec![];
+        let mut id = self.root;
+
+        while let Some(node_id) = id {
+            nodes.push(node_id);
+            let node = &self.nodes[&node_id];
+            let highlights = Highlights::new()
+                .path(&nodes, ElementState::Frontier)
+                .node(node_id, ElementState::Active);
+
+            match node.keys.binary_search(&value) {
+                Ok(index) => {
+                    self.push(
+                        format!("Found {} in the node", value),
+                        highlights.key(value, ElementState::Selected),
+                        steps,
+                    );
+                    return (path, Some((node_id, index)));
+                }
+                Err(index) => {
+                    let comparison = describe_position(&node.keys, value, index);
+                    if node.is_leaf() {
+                        self.push(
+                            format!(
+                                "{}, but the node is a leaf, so {} isn't in the tree",
+                                comparison, value
+                            ),
+                            highlights.node(node_id, ElementState::Rejected),
+                            steps,
+                        );
+                        path.push((node_id, index));
+                        return (path, None);
+                    }
+                    self.push(
+                        format!(
+                            "{}, so go to the child {}",
+                            comparison,
+                            child_position(&node.keys, index)
+                        ),
+                        highlights,
+                        steps,
+                    );
+                    path.push((node_id, index));
+                    id = Some(node.children[index]);
+                }
+            }
+        }
+
+        (path, None)
+    }
+    fn search(&self, value: i64, steps: &mut DiagramSteps) {
+        if self.root.is_none() {
+            self.push(
+                format!("The tree is empty, so {} isn't in it", value),
+                Highlights::new(),
+                steps,
+            );
+            return;
+        }
+        self.search_path(value, steps);
+    }
+    fn insert(&mut self, value: i64, steps: &mut DiagramSteps) -> Result<(), String> {
+        if self.len() >= MAX_KEYS {
+            return Err(format!("The tree can have at most {} values", MAX_KEYS));
+        }
+        let Some(root) = self.root else {
+            let id = self.add_node(BTreeNode {
+                keys: vec![value],
+                children: vec![],
+            });
+            self.root = Some(id);
+            self.push(
+                format!("The tree is empty, so {} becomes the root", value),
+                Highlights::new().key(value, ElementState::Selected),
+                steps,
+            );
+            return Ok(());
+        };
+
+        let (mut path, found) = self.search_path(value, steps);
+        if let Some((node, _)) = found {
+            self.push(
+                format!("{} is already in the tree", value),
+                Highlights::new()
+                    .node(node, ElementState::Conflict)
+                    .key(value, ElementState::Conflict),
+                steps,
+            );
+            return Ok(());
+        }
+
+        let (leaf, index) = path.pop().unwrap_or((root, 0));
+        self.nodes.get_mut(&leaf).unwrap().keys.insert(index, value);
+        self.push(
+            format!("Insert {} into the leaf in sorted order", value),
+            Highlights::new()
+                .node(leaf, ElementState::Active)
+                .key(value, ElementState::Selected),
+            steps,
+        );
+
+        let mut id = leaf;
+        while self.keys(id).len() > self.max_keys() {
+            self.push(
+                format!(
+                    "The node has {} keys, but nodes of a B-tree of order {} can have at most {}, so it is split",
+                    self.keys(id).len(),
+                    self.order,
+                    self.max_keys()
+                ),
+                Highlights::new().node(id, ElementState::Conflict),
+                steps,
+            );
+
+            let node = self.nodes.get_mut(&id).unwrap();
+            let middle = node.keys.len() / 2;
+            let right = BTreeNode {
+                keys: node.keys.split_off(middle + 1),
+                children: if node.is_leaf() {
+                    vec![]
+                } else {
+                    node.children.split_off(middle + 1)
+                },
+            };
+            let median = node.keys.pop().unwrap();
+            let left = id;
+            let right = self.add_node(right);
+
+            let description = if let Some((parent, index)) = path.pop() {
+                let parent_node = self.nodes.get_mut(&parent).unwrap();
+                parent_node.keys.insert(index, median);
+                parent_node.children.insert(index + 1, right);
+                id = parent;
+                format!(
+                    "Move the middle key {} up to the parent, and split the keys on its sides into two nodes",
+                    median
+                )
+            } else {
+                id = self.add_node(BTreeNode {
+                    keys: vec![median],
+                    children: vec![left, right],
+                });
+                self.root = Some(id);
+                format!(
+                    "The root is split, so the middle key {} moves up into a new root and the tree grows a level",
+                    median
+                )
+            };
+            self.push(
+                description,
+                Highlights::new()
+                    .node(left, ElementState::Active)
+                    .node(right, ElementState::Active)
+                    .node(id, ElementState::Frontier)
+                    .key(median, ElementState::Selected),
+                steps,
+            );
+        }
+        Ok(())
+    }
+    fn delete(&mut self, value: i64, steps: &mut DiagramSteps) {
+        if self.root.is_none() {
+            self.push(
+                format!("The tree is empty, so {} isn't in it", value),
+                Highlights::new(),
+                steps,
+            );
+            return;
+        }
+        let (mut path, found) = self.search_path(value, steps);
+        let Some((node, index)) = found else {
+            return;
+        };
+
+        let leaf = if self.nodes[&node].is_leaf() {
+            self.push(
+                format!("Remove {} from the leaf", value),
+                Highlights::new()
+                    .node(node, ElementState::Active)
+                    .key(value, ElementState::Rejected),
+                steps,
+            );
+            self.nodes.get_mut(&node).unwrap().keys.remove(index);
+            node
+        } else {
+            // Replace the value with its predecessor, the largest key in the subtree on its left
+            path.push((node, index));
+            let mut nodes = vec![node];
+            let mut id = self.nodes[&node].children[index];
+            loop {
+                nodes.push(id);
+                let current = &self.nodes[&id];
+                let highlights = Highlights::new()
+                    .path(&nodes, ElementState::Frontier)
+                    .node(id, ElementState::Active)
+                    .key(value, ElementState::Rejected);
+                if current.is_leaf() {
+                    let predecessor = *current.keys.last().unwrap();
+                    self.push(
+                        format!(
+                            "{} is the largest key on the left of {}, so it takes the place of {}",
+                            predecessor, value, value
+                        ),
+                        highlights.key(predecessor, ElementState::Selected),
+                        steps,
+                    );
+                    break;
+                }
+                self.push(
+                    if nodes.len() == 2 {
+                        format!(
+                            "{} isn't in a leaf, so it is replaced by its predecessor. Go to the child on its left",
+                            value
+                        )
+                    } else {
+                        "Go to the last child".to_string()
+                    },
+                    highlights,
+                    steps,
+                );
+                path.push((id, current.children.len() - 1));
+                id = *current.children.last().unwrap();
+            }
+            let predecessor = self.nodes.get_mut(&id).unwrap().keys.pop().unwrap();
+            self.nodes.get_mut(&node).unwrap().keys[index] = predecessor;
+            self.push(
+                format!("Move {} up in place of {}", predecessor, value),
+                Highlights::new()
+                    .node(id, ElementState::Active)
+                    .key(predecessor, ElementState::Selected),
+                steps,
+            );
+            id
+        };
+
+        self.fix_underflow(leaf, path, steps);
+    }
+    /// Fills up or merges nodes that have too few keys, going up from `id` along the path from the root.
+    fn fix_underflow(&mut self, mut id: usize, mut path: Path, steps: &mut DiagramSteps) {
+        let min_keys = self.min_keys();
+        loop {
+            let key_count = self.keys(id).len();
+            let Some((parent, index)) = path.pop() else {
+                // The root can have any number of keys, but an empty root is removed
+                if key_count == 0 {
+                    let node = self.nodes.remove(&id).unwrap();
+                    self.root = node.children.first().copied();
+                    let description = match self.root {
+                        Some(_) => "The root has no keys left, so its only child becomes the root and the tree shrinks a level",
+                        None => "The tree is empty",
+                    };
+                    self.push(description.to_string(), Highlights::new(), steps);
+                }
+                return;
+            };
+            if key_count >= min_keys {
+                self.push(
+                    format!(
+                        "The node has {} key{}, which is enough",
+                        key_count,
+                        if key_count == 1 { "" } else { "s" }
+                    ),
+                    Highlights::new().node(id, ElementState::Selected),
+                    steps,
+                );
+                return;
+            }
+
+            let children = &self.nodes[&parent].children;
+            let left = (index > 0).then(|| children[index - 1]);
+            let right = children.get(index + 1).copied();
+            self.push(
+                format!(
+                    "The node has {} key{}, but nodes other than the root need at least {}",
+                    key_count,
+                    if key_count == 1 { "" } else { "s" },
+                    min_keys
+                ),
+                Highlights::new()
+                    .node(id, ElementState::Conflict)
+                    .node(parent, ElementState::Frontier),
+                steps,
+            );
+
+            if let Some(left) = left.filter(|left| self.keys(*left).len() > min_keys) {
+                let (separator, borrowed) = self.borrow(parent, index - 1, left, id, Side::Left);
+                self.push(
+                    format!(
+                        "The left sibling has a key to spare, so the separator {} moves down into the node and {} moves up from the sibling in its place",
+                        separator, borrowed
+                    ),
+                    Highlights::new()
+                        .node(id, ElementState::Active)
+                        .node(left, ElementState::Active)
+                        .key(separator, ElementState::Selected)
+                        .key(borrowed, ElementState::Selected),
+                    steps,
+                );
+                return;
+            }
+            if let Some(right) = right.filter(|right| self.keys(*right).len() > min_keys) {
+                let (separator, borrowed) = self.borrow(parent, index, right, id, Side::Right);
+                self.push(
+                    format!(
+                        "The right sibling has a key to spare, so the separator {} moves down into the node and {} moves up from the sibling in its place",
+                        separator, borrowed
+                    ),
+                    Highlights::new()
+                        .node(id, ElementState::Active)
+                        .node(right, ElementState::Active)
+                        .key(separator, ElementState::Selected)
+                        .key(borrowed, ElementState::Selected),
+                    steps,
+                );
+                return;
+            }
+
+            // Neither sibling has a key to spare, so the node is merged with one of them
+            let (merged, side, separator_index) = match left {
+                Some(left) => (left, Side::Left, index - 1),
+                None => (id, Side::Right, index),
+            };
+            let parent_node = self.nodes.get_mut(&parent).unwrap();
+            let separator = parent_node.keys.remove(separator_index);
+            let removed = parent_node.children.remove(separator_index + 1);
+            let removed = self.nodes.remove(&removed).unwrap();
+            let merged_node = self.nodes.get_mut(&merged).unwrap();
+            merged_node.keys.push(separator);
+            merged_node.keys.extend(removed.keys);
+            merged_node.children.extend(removed.children);
+            self.push(
+                format!(
+                    "Neither sibling has a key to spare, so the node is merged with its {} sibling and the separator {} between them",
+                    side.name(),
+                    separator
+                ),
+                Highlights::new()
+                    .node(merged, ElementState::Active)
+                    .key(separator, ElementState::Selected),
+                steps,
+            );
+            id = parent;
+        }
+    }
+    /// Moves the separator at `separator_index` of `parent` down into `node`, and the nearest key of the
+    /// sibling on the given side of the node up in its place. Returns the separator and the key that moved up.
+    fn borrow(
+        &mut self,
+        parent: usize,
+        separator_index: usize,
+        sibling: usize,
+        node: usize,
+        side: Side,
+    ) -> (i64, i64) {
+        let sibling_node = self.nodes.get_mut(&sibling).unwrap();
+        let (borrowed, child) = match side {
+            Side::Left => (
+                sibling_node.keys.pop().unwrap(),
+                sibling_node.children.pop(),
+            ),
+            Side::Right => (
+                sibling_node.keys.remove(0),
+                (!sibling_node.is_leaf()).then(|| sibling_node.children.remove(0)),
+            ),
+        };
+        let separator = std::mem::replace(
+            &mut self.nodes.get_mut(&parent).unwrap().keys[separator_index],
+            borrowed,
+        );
+        let node = self.nodes.get_mut(&node).unwrap();
+        match side {
+            Side::Left => {
+                node.keys.insert(0, separator);
+                node.children.splice(0..0, child);
+            }
+            Side::Right => {
+                node.keys.push(separator);
+                node.children.extend(child);
+            }
+        }
+        (separator, borrowed)
+    }
+    /// Inserts the values again into a tree of a new order.
+    fn set_order(&mut self, order: usize, steps: &mut DiagramSteps) {
+        let mut values = self
+            .nodes
+            .values()
+            .flat_map(|node| node.keys.iter().copied())
+            .collect::<Vec<i64>>();
+        values.sort_unstable();
+        *self = Self::from_values(order, &values);
+        self.push(
+            format!(
+                "Insert the values into a tree of order {}, where nodes have at most {} children and {} keys",
+                self.order,
+                self.order,
+                self.max_keys()
+            ),
+            Highlights::new(),
+            steps,
+        );
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let layout = tree_layout(&self.root.into_iter().collect::<Vec<usize>>(), |id| {
+            self.nodes[&id].children.clone()
+        });
+        // Nodes that have one key too many before they are split can overlap their neighbors for a step
+        let widest = KEY_SIZE.0 * self.max_keys() as f32 + NODE_PADDING * 2.0;
+        let spacing_x = widest + 16.0;
+        let max_x = layout.values().map(|(x, _)| *x).fold(0.0, f32::max);
+        let width = (max_x * spacing_x + widest + NODE_RADIUS * 4.0).max(TREE_WIDTH);
+        let positions = fit_layout(&layout, (width, TREE_HEIGHT), (spacing_x, 90.0));
+        let mut diagram = Diagram::new(width, TREE_HEIGHT);
+
+        for (id, node) in self.nodes.iter() {
+            let Some(position) = positions.get(id) else {
+                continue;
+            };
+            let size = (
+                KEY_SIZE.0 * node.keys.len() as f32 + NODE_PADDING * 2.0,
+                KEY_SIZE.1 + NODE_PADDING * 2.0,
+            );
+            let mut diagram_node = DiagramNode::rect(node_key(*id), "", *position, size);
+            diagram_node.state = highlights.nodes.get(id).copied();
+            diagram.add_node(diagram_node);
+
+            for child in node.children.iter() {
+                let mut edge = DiagramEdge::arrow(node_key(*id), node_key(*child));
+                edge.state = highlights.edges.get(&(*id, *child)).copied();
+                diagram.add_edge(edge);
+            }
+        }
+        // The keys are drawn on top of their nodes
+        for (id, node) in self.nodes.iter() {
+            let Some((x, y)) = positions.get(id) else {
+                continue;
+            };
+            let left = x - KEY_SIZE.0 * node.keys.len() as f32 / 2.0;
+            for (i, key) in node.keys.iter().enumerate() {
+                let position = (left + KEY_SIZE.0 * (i as f32 + 0.5), *y);
+                let mut key_node =
+                    DiagramNode::rect(format!("key-{}", key), key, position, KEY_SIZE);
+                key_node.state = highlights.keys.get(key).copied();
+                diagram.add_node(key_node);
+            }
+        }
+
+        diagram
+    }
+}
+impl Visualizer for BTree {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let mut operations = search_tree_operations();
+        operations.retain(|operation| operation.name != "Traverse");
+        operations.push(Operation::new(
+            "Change order",
+            vec![Input::Number {
+                label: "Order",
+                default: self.order as i64,
+                min: MIN_ORDER as i64,
+                max: MAX_ORDER as i64,
+            }],
+        ));
+        operations
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Insert" => self.insert(value.number(), &mut steps)?,
+            "Search" => self.search(value.number(), &mut steps),
+            "Delete" => self.delete(value.number(), &mut steps),
+            "Change order" => {
+                let order = value.number();
+                if !(MIN_ORDER as i64..=MAX_ORDER as i64).contains(&order) {
+                    return Err(format!(
+                        "The order must be between {} and {}",
+                        MIN_ORDER, MAX_ORDER
+                    ));
+                }
+                self.set_order(order as usize, &mut steps);
+            }
+            "Clear" => {
+                *self = Self::new(self.order);
+                steps.push("Remove all values".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// Describes where a value that isn't in a node is between its keys, like "45 is between 30 and 50".
+fn describe_position(keys: &[i64], value: i64, index: usize) -> String {
+    if index == 0 {
+        format!("{} < {}", value, keys[0])
+    } else if index == keys.len() {
+        format!("{} > {}", value, keys[index - 1])
+    } else {
+        format!(
+            "{} is between {} and {}",
+            value,
+            keys[index - 1],
+            keys[index]
+        )
+    }
+}
+
+/// Which child is followed for a value at `index` between the keys.
+fn child_position(keys: &[i64], index: usize) -> &'static str {
+    if index == 0 {
+        "on the left"
+    } else if index == keys.len() {
+        "on the right"
+    } else {
+        "between them"
+    }
+}
@@ -0,0 +1,417 @@
+use diagram::*;
+
+const HEAP_WIDTH: f32 = 800.0;
+const HEAP_HEIGHT: f32 = 600.0;
+/// The largest number of values in the heap, so that the tree has at most five levels.
+const MAX_HEAP_SIZE: usize = 31;
+const MAX_VALUE: i64 = 99;
+/// The space between the levels of the tree.
+const LEVEL_HEIGHT: f32 = 65.0;
+/// The y of the bottoms of the bars.
+const BAR_BASELINE: f32 = 560.0;
+const MAX_BAR_HEIGHT: f32 = 180.0;
+const HEAP_TYPES: [&str; 2] = ["Max-heap", "Min-heap"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Element {
+    /// Identifies the element in the diagram when it moves to another index.
+    id: usize,
+    value: i64,
+}
+
+/// A complete binary tree that is stored in an array, where the children of the element at index *i* are at
+/// *2i + 1* and *2i + 2*. In a max-heap, every element is at least as large as its children, so the largest
+/// element is at the root. In a min-heap, it's the other way around.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinaryHeap {
+    elements: Vec<Element>,
+    min_heap: bool,
+    next_id: usize,
+}
+impl BinaryHeap {
+    pub fn new(min_heap: bool) -> Self {
+        Self {
+            elements: vec![],
+            min_heap,
+            next_id: 0,
+        }
+    }
+    /// Makes a heap by inserting the values in order without keeping the steps.
+    pub fn from_values(min_heap: bool, values: &[i64]) -> Self {
+        let mut heap = Self::new(min_heap);
+        for value in values {
+            let _ = heap.insert(*value, &mut DiagramSteps::new());
+        }
+        heap
+    }
+    fn name(&self) -> &'static str {
+        if self.min_heap {
+            "min-heap"
+        } else {
+            "max-heap"
+        }
+    }
+    /// True if the element at `a` belongs above the element at `b`.
+    fn is_before(&self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.elements[a].value, self.elements[b].value);
+        if self.min_heap {
+            a < b
+        } else {
+            a > b
+        }
+    }
+    fn add_element(&mut self, value: i64) {
+        self.elements.push(Element {
+            id: self.next_id,
+            value,
+        });
+        self.next_id += 1;
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &[(usize, ElementState)],
+        steps: &mut DiagramSteps,
+    ) {
+        steps.push(description, self.draw(highlights));
+    }
+    fn swap(&mut self, a: usize, b: usize, steps: &mut DiagramSteps) {
+        self.elements.swap(a, b);
+        self.push(
+            format!(
+                "Swap {} and {}, so index {} now has {} and index {} has {}",
+                self.elements[b].value,
+                self.elements[a].value,
+                a,
+                self.elements[a].value,
+                b,
+                self.elements[b].value
+            ),
+            &[(a, ElementState::Selected), (b, ElementState::Selected)],
+            steps,
+        );
+    }
+    /// Moves the element at the index up until its parent belongs above it.
+    fn sift_up(&mut self, mut index: usize, steps: &mut DiagramSteps) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            let (value, parent_value) = (self.elements[index].value, self.elements[parent].value);
+            let highlights = [
+                (index, ElementState::Active),
+                (parent, ElementState::Active),
+            ];
+            if !self.is_before(index, parent) {
+                self.push(
+                    format!(
+                        "The parent {} at index {} belongs above {}, so sifting up stops",
+                        parent_value, parent, value
+                    ),
+                    &highlights,
+                    steps,
+                );
+                return;
+            }
+            self.push(
+                format!(
+                    "Compare {} with its parent {} at index ({} − 1) / 2 = {}. {} belongs above it, so they are swapped",
+                    value, parent_value, index, parent, value
+                ),
+                &highlights,
+                steps,
+            );
+            self.swap(index, parent, steps);
+            index = parent;
+        }
+        self.push(
+            format!(
+                "{} is at the root, so sifting up stops",
+                self.elements[0].value
+            ),
+            &[(0, ElementState::Active)],
+            steps,
+        );
+    }
+    /// Moves the element at the index down until it belongs above its children, swapping it with the child that
+    /// belongs higher. Only the first `len` elements are a part of the heap.
+    fn sift_down(&mut self, mut index: usize, len: usize, steps: &mut DiagramSteps) {
+        loop {
+            let value = self.elements[index].value;
+            let children = [2 * index + 1, 2 * index + 2]
+                .into_iter()
+                .filter(|child| *child < len)
+                .collect::<Vec<usize>>();
+            if children.is_empty() {
+                self.push(
+                    format!(
+                        "{} at index {} has no children, so sifting down stops",
+                        value, index
+                    ),
+                    &[(index, ElementState::Active)],
+                    steps,
+                );
+                return;
+            }
+            let child = children
+                .iter()
+                .copied()
+                .reduce(|a, b| if self.is_before(b, a) { b } else { a })
+                .unwrap();
+            let child_values = children
+                .iter()
+                .map(|child| self.elements[*child].value.to_string())
+                .collect::<Vec<String>>()
+                .join(" and ");
+            let mut highlights = vec![(index, ElementState::Active)];
+            highlights.extend(children.iter().map(|child| (*child, ElementState::Active)));
+
+            if !self.is_before(child, index) {
+                self.push(
+                    format!(
+                        "{} belongs above its children {}, so sifting down stops",
+                        value, child_values
+                    ),
+                    &highlights,
+                    steps,
+                );
+                return;
+            }
+            self.push(
+                format!(
+                    "Compare {} with its children {} at indices {}. {} belongs highest, so it is swapped with {}",
+                    value,
+                    child_values,
+                    children
+                        .iter()
+                        .map(|child| child.to_string())
+                        .collect::<Vec<String>>()
+                        .join(" and "),
+                    self.elements[child].value,
+                    value
+                ),
+                &highlights,
+                steps,
+            );
+            self.swap(index, child, steps);
+            index = child;
+        }
+    }
+    fn insert(&mut self, value: i64, steps: &mut DiagramSteps) -> Result<(), String> {
+        if self.elements.len() >= MAX_HEAP_SIZE {
+            return Err(format!(
+                "The heap can have at most {} values",
+                MAX_HEAP_SIZE
+            ));
+        }
+        if !(0..=MAX_VALUE).contains(&value) {
+            return Err(format!("The values must be between 0 and {}", MAX_VALUE));
+        }
+        self.add_element(value);
+        let index = self.elements.len() - 1;
+        self.push(
+            format!(
+                "Add {} to the end of the array at index {}, which is the next free place on the lowest level of the tree",
+                value, index
+            ),
+            &[(index, ElementState::Selected)],
+            steps,
+        );
+        self.sift_up(index, steps);
+        Ok(())
+    }
+    fn extract(&mut self, steps: &mut DiagramSteps) {
+        let Some(root) = self.elements.first().copied() else {
+            self.push("The heap is empty".to_string(), &[], steps);
+            return;
+        };
+        let last = self.elements.len() - 1;
+        self.push(
+            format!(
+                "The root {} is the {} value of the heap",
+                root.value,
+                if self.min_heap { "smallest" } else { "largest" }
+            ),
+            &[(0, ElementState::Selected)],
+            steps,
+        );
+        if last > 0 {
+            self.elements.swap(0, last);
+            self.push(
+                format!(
+                    "Swap it with the last value {}, which can be removed from the end of the array",
+                    self.elements[0].value
+                ),
+                &[(0, ElementState::Active), (last, ElementState::Rejected)],
+                steps,
+            );
+        }
+        self.elements.pop();
+        self.push(format!("Remove {}", root.value), &[], steps);
+        if !self.elements.is_empty() {
+            self.sift_down(0, self.elements.len(), steps);
+        }
+    }
+    /// Builds a heap from the values by sifting down every element that has children, from the last one to the root.
+    fn build(&mut self, values: &[i64], steps: &mut DiagramSteps) {
+        self.elements.clear();
+        for value in values {
+            self.add_element(*value);
+        }
+        self.push(
+            "Place the values in the array in their order. The leaves on the lowest level are already heaps of one value"
+                .to_string(),
+            &[],
+            steps,
+        );
+        let len = self.elements.len();
+        for index in (0..len / 2).rev() {
+            self.push(
+                format!(
+                    "Sift down {} at index {} to make its subtree a {}",
+                    self.elements[index].value,
+                    index,
+                    self.name()
+                ),
+                &[(index, ElementState::Frontier)],
+                steps,
+            );
+            self.sift_down(index, len, steps);
+        }
+        self.push(format!("The array is now a {}", self.name()), &[], steps);
+    }
+    fn draw(&self, highlights: &[(usize, ElementState)]) -> Diagram {
+        let mut diagram = Diagram::new(HEAP_WIDTH, HEAP_HEIGHT);
+        let margin = NODE_RADIUS * 2.0;
+        let state = |index: usize| {
+            highlights
+                .iter()
+                .rev()
+                .find(|(i, _)| *i == index)
+                .map(|(_, state)| *state)
+        };
+        // The tree is drawn by the indices of the elements, so its edges are figures that stay in place
+        let tree_position = |index: usize| {
+            let depth = (index + 1).ilog2();
+            let level_size = 2_usize.pow(depth);
+            let x = (index + 1 - level_size) as f32 + 0.5;
+            (
+                margin + x / level_size as f32 * (HEAP_WIDTH - margin * 2.0),
+                margin + depth as f32 * LEVEL_HEIGHT,
+            )
+        };
+        let len = self.elements.len();
+        let bar_spacing = ((HEAP_WIDTH - margin * 2.0) / len.max(1) as f32).min(50.0);
+        let bar_left = (HEAP_WIDTH - bar_spacing * len as f32) / 2.0;
+
+        for index in 1..len {
+            let parent = (index - 1) / 2;
+            let mut line = Figure::new(FigureKind::Line(
+                tree_position(parent),
+                tree_position(index),
+            ));
+            if let (Some(a), Some(b)) = (state(parent), state(index)) {
+                if a == b {
+                    line = line.with_state(a);
+                }
+            }
+            diagram.add_figure(line);
+        }
+        for (index, element) in self.elements.iter().enumerate() {
+            let mut node = DiagramNode::circle(
+                format!("node-{}", element.id),
+                element.value,
+                tree_position(index),
+            )
+            .with_note(index);
+            node.state = state(index);
+            diagram.add_node(node);
+
+            let height = 12.0 + element.value as f32 / MAX_VALUE as f32 * (MAX_BAR_HEIGHT - 12.0);
+            let mut bar = DiagramNode::rect(
+                format!("bar-{}", element.id),
+                element.value,
+                (
+                    bar_left + bar_spacing * (index as f32 + 0.5),
+                    BAR_BASELINE - height / 2.0,
+                ),
+                (bar_spacing - 4.0, height),
+            );
+            bar.note = Some(index.to_string());
+            bar.state = state(index);
+            diagram.add_node(bar);
+        }
+        diagram
+    }
+}
+impl Visualizer for BinaryHeap {
+    fn diagram(&self) -> Diagram {
+        self.draw(&[])
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Insert",
+                vec![Input::Number {
+                    label: "Value to insert",
+                    default: 42,
+                    min: 0,
+                    max: MAX_VALUE,
+                }],
+            ),
+            Operation::new(
+                if self.min_heap {
+                    "Extract min"
+                } else {
+                    "Extract max"
+                },
+                vec![],
+            ),
+            Operation::new(
+                "Build heap",
+                vec![Input::Text {
+                    label: "Values",
+                    default: "12, 35, 7, 58, 23, 91, 4, 66, 40".to_string(),
+                }],
+            ),
+            Operation::new(
+                "Change type",
+                vec![Input::Choice {
+                    label: "Heap type",
+                    choices: HEAP_TYPES.map(String::from).to_vec(),
+                }],
+            ),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Insert" => self.insert(value.number(), &mut steps)?,
+            "Extract min" | "Extract max" => self.extract(&mut steps),
+            "Build heap" => {
+                // An empty list builds an empty heap
+                let values = if value.text().trim().is_empty() {
+                    vec![]
+                } else {
+                    parse_values(&value.text(), 0, MAX_VALUE, MAX_HEAP_SIZE)?
+                };
+                self.build(&values, &mut steps);
+            }
+            "Change type" => {
+                self.min_heap = value.text() == HEAP_TYPES[1];
+                let values = self
+                    .elements
+                    .iter()
+                    .map(|element| element.value)
+                    .collect::<Vec<i64>>();
+                self.build(&values, &mut steps);
+            }
+            "Clear" => {
+                self.elements.clear();
+                steps.push("Remove all values".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
@@ -0,0 +1,59 @@
+use diagram::*;
+
+use super::binary_tree::*;
+
+/// A binary tree where the values in the left subtree of every node are smaller than the node's value
+/// and the values in the right subtree are larger.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BinarySearchTree {
+    tree: BinaryTree,
+}
+impl BinarySearchTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Makes a tree by inserting the values in order without keeping the steps.
+    pub fn from_values(values: &[i64]) -> Self {
+        let mut tree = Self::new();
+        for value in values {
+            let _ = tree
+                .tree
+                .insert_steps(*value, &mut DiagramSteps::new(), &draw);
+        }
+        tree
+    }
+}
+impl Visualizer for BinarySearchTree {
+    fn diagram(&self) -> Diagram {
+        self.tree.draw(&TreeHighlights::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        search_tree_operations()
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Insert" => {
+                self.tree.insert_steps(value.number(), &mut steps, &draw)?;
+            }
+            "Search" => {
+                self.tree.search_steps(value.number(), &mut steps, &draw);
+            }
+            "Delete" => {
+                self.tree.delete_steps(value.number(), &mut steps, &draw);
+            }
+            "Traverse" => return self.tree.traverse(&value.text(), &draw),
+            "Clear" => {
+                self.tree = BinaryTree::new();
+                steps.push("Remove all values".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn draw(tree: &BinaryTree, highlights: &TreeHighlights) -> Diagram {
+    tree.draw(highlights)
+}
@@ -0,0 +1,761 @@
+//! The nodes and pointers of binary trees, which the search trees build on.
+use std::collections::{BTreeMap, VecDeque};
+
+use diagram::*;
+
+pub const TREE_WIDTH: f32 = 800.0;
+pub const TREE_HEIGHT: f32 = 500.0;
+/// The largest number of values in a tree, so that the nodes fit side by side in the diagram.
+pub const MAX_TREE_SIZE: usize = 31;
+/// The deepest level that values can be inserted to, so that the levels fit in the diagram.
+pub const MAX_TREE_DEPTH: usize = 10;
+/// The orders that trees can be traversed in.
+pub const TRAVERSAL_ORDERS: [&str; 4] = ["In-order", "Pre-order", "Post-order", "Level order"];
+
+/// Draws a tree with the highlights, e.g. with the balance factors of the nodes.
+pub type DrawTree<'a> = &'a dyn Fn(&BinaryTree, &TreeHighlights) -> Diagram;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+impl Side {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Side::Left => "left",
+            Side::Right => "right",
+        }
+    }
+    pub fn opposite(&self) -> Self {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeNode {
+    pub value: i64,
+    pub left: Option<usize>,
+    pub right: Option<usize>,
+}
+
+/// The states of the nodes and the pointers of a tree at a step. Pointers are keyed by their parents and children.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreeHighlights {
+    pub nodes: BTreeMap<usize, ElementState>,
+    pub edges: BTreeMap<(usize, usize), ElementState>,
+}
+impl TreeHighlights {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn node(mut self, id: usize, state: ElementState) -> Self {
+        self.nodes.insert(id, state);
+        self
+    }
+    pub fn edge(mut self, edge: (usize, usize), state: ElementState) -> Self {
+        self.edges.insert(edge, state);
+        self
+    }
+    /// Highlights the nodes of a path and the pointers between them.
+    pub fn path(mut self, path: &[usize], state: ElementState) -> Self {
+        for node in path {
+            self.nodes.insert(*node, state);
+        }
+        for pair in path.windows(2) {
+            self.edges.insert((pair[0], pair[1]), state);
+        }
+        self
+    }
+}
+
+/// A binary tree whose nodes are stored by their ids, so that the nodes keep their keys in diagrams when the
+/// pointers between them change.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BinaryTree {
+    pub nodes: BTreeMap<usize, TreeNode>,
+    pub root: Option<usize>,
+    next_id: usize,
+}
+impl BinaryTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    pub fn value(&self, id: usize) -> i64 {
+        self.nodes[&id].value
+    }
+    pub fn child(&self, id: usize, side: Side) -> Option<usize> {
+        let node = &self.nodes[&id];
+        match side {
+            Side::Left => node.left,
+            Side::Right => node.right,
+        }
+    }
+    /// Adds a node without pointing anything to it.
+    pub fn add_node(&mut self, value: i64) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.nodes.insert(
+            id,
+            TreeNode {
+                value,
+                left: None,
+                right: None,
+            },
+        );
+        id
+    }
+    pub fn remove_node(&mut self, id: usize) {
+        self.nodes.remove(&id);
+    }
+    /// Points the given side of a node to a child, or the root if there is no parent.
+    pub fn set_child(&mut self, parent: Option<usize>, side: Side, child: Option<usize>) {
+        match parent {
+            Some(parent) => {
+                let node = self.nodes.get_mut(&parent).unwrap();
+                match side {
+                    Side::Left => node.left = child,
+                    Side::Right => node.right = child,
+                }
+            }
+            None => self.root = child,
+        }
+    }
+    /// Gets the parent of a node along with the side of the parent that the node is on.
+    pub fn parent(&self, id: usize) -> Option<(usize, Side)> {
+        self.nodes.iter().find_map(|(parent, node)| {
+            if node.left == Some(id) {
+                Some((*parent, Side::Left))
+            } else if node.right == Some(id) {
+                Some((*parent, Side::Right))
+            } else {
+                None
+            }
+        })
+    }
+    /// Searches for a value like in a binary search tree. Returns the visited nodes and the side of the last node
+    /// that the value would be on, or `None` if the last node has the value.
+    pub fn search_path(&self, value: i64) -> (Vec<usize>, Option<Side>) {
+        let mut path = vec![];
+        let mut current = self.root;
+        while let Some(id) = current {
+            path.push(id);
+            let node_value = self.value(id);
+            if value == node_value {
+                return (path, None);
+            }
+            let side = if value < node_value {
+                Side::Left
+            } else {
+                Side::Right
+            };
+            current = self.child(id, side);
+            if current.is_none() {
+                return (path, Some(side));
+            }
+        }
+        (path, None)
+    }
+    /// Records the comparisons of inserting a value like in a binary search tree.
+    /// Returns the id of the new node, or `None` if the value was already in the tree.
+    pub fn insert_steps(
+        &mut self,
+        value: i64,
+        steps: &mut DiagramSteps,
+        draw: DrawTree,
+    ) -> Result<Option<usize>, String> {
+        if self.len() >= MAX_TREE_SIZE {
+            return Err(format!(
+                "The tree can have at most {} values",
+                MAX_TREE_SIZE
+            ));
+        }
+        let Some(root) = self.root else {
+            let id = self.add_node(value);
+            self.root = Some(id);
+            steps.push(
+                format!("The tree is empty, so {} becomes the root", value),
+                draw(
+                    self,
+                    &TreeHighlights::new().node(id, ElementState::Selected),
+                ),
+            );
+            return Ok(Some(id));
+        };
+
+        let mut path = vec![];
+        let mut current = root;
+        loop {
+            path.push(current);
+            let node_value = self.value(current);
+            let highlights = TreeHighlights::new()
+                .path(&path, ElementState::Frontier)
+                .node(current, ElementState::Active);
+
+            if value == node_value {
+                steps.push(
+                    format!("{} is already in the tree", value),
+                    draw(self, &highlights.node(current, ElementState::Conflict)),
+                );
+                return Ok(None);
+            }
+            let side = if value < node_value {
+                Side::Left
+            } else {
+                Side::Right
+            };
+            steps.push(
+                format!(
+                    "{} {} {}, so go to the {} child",
+                    value,
+                    if side == Side::Left { "<" } else { ">" },
+                    node_value,
+                    side.name()
+                ),
+                draw(self, &highlights),
+            );
+
+            match self.child(current, side) {
+                Some(child) => current = child,
+                None => {
+                    if path.len() > MAX_TREE_DEPTH {
+                        return Err(format!(
+                            "{} would be deeper than {} levels, which don't fit in the view",
+                            value, MAX_TREE_DEPTH
+                        ));
+                    }
+                    let id = self.add_node(value);
+                    self.set_child(Some(current), side, Some(id));
+                    steps.push(
+                        format!(
+                            "The {} child of {} is empty, so point it to a new node with {}",
+                            side.name(),
+                            node_value,
+                            value
+                        ),
+                        draw(
+                            self,
+                            &TreeHighlights::new()
+                                .path(&path, ElementState::Frontier)
+                                .node(id, ElementState::Selected)
+                                .edge((current, id), ElementState::Active),
+                        ),
+                    );
+                    return Ok(Some(id));
+                }
+            }
+        }
+    }
+    /// Records the comparisons of a search for the value. Returns the path to the node with the value if it was found.
+    pub fn search_steps(
+        &self,
+        value: i64,
+        steps: &mut DiagramSteps,
+        draw: DrawTree,
+    ) -> Option<Vec<usize>> {
+        let (path, side) = self.search_path(value);
+        if path.is_empty() {
+            steps.push(
+                format!("The tree is empty, so {} isn't in it", value),
+                draw(self, &TreeHighlights::new()),
+            );
+            return None;
+        }
+
+        for (i, id) in path.iter().enumerate() {
+            let node_value = self.value(*id);
+            let highlights = TreeHighlights::new()
+                .path(&path[..=i], ElementState::Frontier)
+                .node(*id, ElementState::Active);
+            let description = if value == node_value {
+                format!(
+                    "Found {} after {} comparison{}",
+                    value,
+                    i + 1,
+                    if i == 0 { "" } else { "s" }
+                )
+            } else {
+                let side = if value < node_value {
+                    Side::Left
+                } else {
+                    Side::Right
+                };
+                let comparison = if side == Side::Left { "<" } else { ">" };
+                if i + 1 == path.len() {
+                    format!(
+                        "{} {} {}, but {} has no {} child, so {} isn't in the tree",
+                        value,
+                        comparison,
+                        node_value,
+                        node_value,
+                        side.name(),
+                        value
+                    )
+                } else {
+                    format!(
+                        "{} {} {}, so go to the {} child",
+                        value,
+                        comparison,
+                        node_value,
+                        side.name()
+                    )
+                }
+            };
+            let highlights = if value == node_value {
+                highlights.node(*id, ElementState::Selected)
+            } else if i + 1 == path.len() {
+                highlights.node(*id, ElementState::Rejected)
+            } else {
+                highlights
+            };
+            steps.push(description, draw(self, &highlights));
+        }
+
+        side.is_none().then_some(path)
+    }
+    /// Records the steps of deleting a value like in a binary search tree. If the value was deleted, returns the
+    /// parent of the node that was removed, which is where the tree may have to be rebalanced from.
+    pub fn delete_steps(
+        &mut self,
+        value: i64,
+        steps: &mut DiagramSteps,
+        draw: DrawTree,
+    ) -> Option<Option<usize>> {
+        let path = self.search_steps(value, steps, draw)?;
+        let target = *path.last().unwrap();
+        let parent = self.parent(target);
+        let mut removed_parent = parent.map(|(parent, _)| parent);
+        let node = self.nodes[&target].clone();
+        let ancestors = TreeHighlights::new().path(&path, ElementState::Frontier);
+
+        match (node.left, node.right) {
+            (None, None) => {
+                steps.push(
+                    format!("{} is a leaf, so it can be removed", value),
+                    draw(
+                        self,
+                        &ancestors.clone().node(target, ElementState::Rejected),
+                    ),
+                );
+                self.remove_with_child(target, None);
+                let description = match parent {
+                    Some((parent, side)) => format!(
+                        "Clear the {} pointer of {}",
+                        side.name(),
+                        self.value(parent)
+                    ),
+                    None => "The tree is now empty".to_string(),
+                };
+                let highlights = parent.map_or(TreeHighlights::new(), |(parent, _)| {
+                    TreeHighlights::new()
+                        .path(&path[..path.len() - 1], ElementState::Frontier)
+                        .node(parent, ElementState::Active)
+                });
+                steps.push(description, draw(self, &highlights));
+            }
+            (Some(child), None) | (None, Some(child)) => {
+                let child_value = self.value(child);
+                steps.push(
+                    format!(
+                        "{} has only one child, {}, which can take its place",
+                        value, child_value
+                    ),
+                    draw(
+                        self,
+                        &ancestors
+                            .node(target, ElementState::Rejected)
+                            .edge((target, child), ElementState::Active),
+                    ),
+                );
+                self.remove_with_child(target, Some(child));
+                let (description, highlights) = match parent {
+                    Some((parent, side)) => (
+                        format!(
+                            "Point the {} child of {} to {}",
+                            side.name(),
+                            self.value(parent),
+                            child_value
+                        ),
+                        TreeHighlights::new()
+                            .path(&path[..path.len() - 1], ElementState::Frontier)
+                            .edge((parent, child), ElementState::Active)
+                            .node(child, ElementState::Selected),
+                    ),
+                    None => (
+                        format!("{} becomes the root", child_value),
+                        TreeHighlights::new().node(child, ElementState::Selected),
+                    ),
+                };
+                steps.push(description, draw(self, &highlights));
+            }
+            (Some(_), Some(right)) => {
+                steps.push(
+                    format!(
+                        "{} has two children, so it is replaced by its in-order successor, the smallest value in its right subtree",
+                        value
+                    ),
+                    draw(self, &ancestors.clone().node(target, ElementState::Active)),
+                );
+
+                let mut successor_path = vec![target, right];
+                let mut successor = right;
+                loop {
+                    let left = self.nodes[&successor].left;
+                    let description = format!(
+                        "Go to the {} child, {}",
+                        if successor == right { "right" } else { "left" },
+                        self.value(successor)
+                    );
+                    steps.push(
+                        description,
+                        draw(
+                            self,
+                            &ancestors
+                                .clone()
+                                .path(&successor_path, ElementState::Frontier)
+                                .node(target, ElementState::Selected)
+                                .node(successor, ElementState::Active),
+                        ),
+                    );
+                    match left {
+                        Some(left) => {
+                            successor = left;
+                            successor_path.push(left);
+                        }
+                        None => break,
+                    }
+                }
+
+                let successor_value = self.value(successor);
+                self.nodes.get_mut(&target).unwrap().value = successor_value;
+                steps.push(
+                    format!(
+                        "{} has no left child, so it is the successor. Copy it over {}",
+                        successor_value, value
+                    ),
+                    draw(
+                        self,
+                        &TreeHighlights::new()
+                            .node(target, ElementState::Selected)
+                            .node(successor, ElementState::Rejected),
+                    ),
+                );
+
+                let successor_child = self.nodes[&successor].right;
+                let (successor_parent, side) = self.parent(successor).unwrap();
+                removed_parent = Some(successor_parent);
+                self.remove_with_child(successor, successor_child);
+                let description = match successor_child {
+                    Some(child) => format!(
+                        "Remove the old node of {}, pointing the {} child of {} to its right child {}",
+                        successor_value,
+                        side.name(),
+                        self.value(successor_parent),
+                        self.value(child)
+                    ),
+                    None => format!(
+                        "Remove the old node of {}, clearing the {} pointer of {}",
+                        successor_value,
+                        side.name(),
+                        self.value(successor_parent)
+                    ),
+                };
+                let mut highlights = TreeHighlights::new()
+                    .node(target, ElementState::Selected)
+                    .node(successor_parent, ElementState::Active);
+                if let Some(child) = successor_child {
+                    highlights = highlights.edge((successor_parent, child), ElementState::Active);
+                }
+                steps.push(description, draw(self, &highlights));
+            }
+        }
+
+        Some(removed_parent)
+    }
+    /// Removes a node that has at most one child, pointing its parent to the child.
+    pub fn remove_with_child(&mut self, id: usize, child: Option<usize>) {
+        match self.parent(id) {
+            Some((parent, side)) => self.set_child(Some(parent), side, child),
+            None => self.root = child,
+        }
+        self.remove_node(id);
+    }
+    /// Rotates the subtree of a node, so that the node moves down to the given side and its child on the other side
+    /// takes its place. Returns the child.
+    pub fn rotate(&mut self, id: usize, direction: Side) -> usize {
+        let parent = self.parent(id);
+        let child = self.child(id, direction.opposite()).unwrap();
+        let inner = self.child(child, direction);
+        self.set_child(Some(id), direction.opposite(), inner);
+        self.set_child(Some(child), direction, Some(id));
+        match parent {
+            Some((parent, side)) => self.set_child(Some(parent), side, Some(child)),
+            None => self.root = Some(child),
+        }
+        child
+    }
+    /// Records a rotation before and after the pointers change. `reason` explains why the rotation is done.
+    pub fn rotate_steps(
+        &mut self,
+        id: usize,
+        direction: Side,
+        reason: String,
+        steps: &mut DiagramSteps,
+        draw: DrawTree,
+    ) -> usize {
+        let child = self.child(id, direction.opposite()).unwrap();
+        let inner = self.child(child, direction);
+        let (value, child_value) = (self.value(id), self.value(child));
+
+        let mut highlights = TreeHighlights::new()
+            .node(id, ElementState::Active)
+            .node(child, ElementState::Active)
+            .edge((id, child), ElementState::Active);
+        if let Some(inner) = inner {
+            highlights = highlights.edge((child, inner), ElementState::Frontier);
+        }
+        steps.push(
+            format!(
+                "{}. Rotate {} {}, so that {} moves up",
+                reason,
+                value,
+                direction.name(),
+                child_value
+            ),
+            draw(self, &highlights),
+        );
+
+        self.rotate(id, direction);
+
+        let mut highlights = TreeHighlights::new()
+            .node(child, ElementState::Selected)
+            .edge((child, id), ElementState::Active);
+        if let Some((parent, _)) = self.parent(child) {
+            highlights = highlights.edge((parent, child), ElementState::Active);
+        }
+        let description = match inner {
+            Some(inner) => {
+                highlights = highlights.edge((id, inner), ElementState::Active);
+                format!(
+                    "{} is now the {} child of {}, and {} moved over to {}",
+                    value,
+                    direction.name(),
+                    child_value,
+                    self.value(inner),
+                    value
+                )
+            }
+            None => format!(
+                "{} is now the {} child of {}",
+                value,
+                direction.name(),
+                child_value
+            ),
+        };
+        steps.push(description, draw(self, &highlights));
+        child
+    }
+    /// Draws the tree with custom labels and notes for the nodes.
+    pub fn draw_with_labels(
+        &self,
+        highlights: &TreeHighlights,
+        label: impl Fn(usize, &TreeNode) -> (String, Option<String>),
+    ) -> Diagram {
+        let layout = binary_tree_layout(self.root, |id| {
+            let node = &self.nodes[&id];
+            (node.left, node.right)
+        });
+        let positions = fit_layout(&layout, (TREE_WIDTH, TREE_HEIGHT), (50.0, 70.0));
+        let mut diagram = Diagram::new(TREE_WIDTH, TREE_HEIGHT);
+
+        for (id, node) in self.nodes.iter() {
+            let Some(position) = positions.get(id) else {
+                continue;
+            };
+            let (text, note) = label(*id, node);
+            let mut diagram_node = DiagramNode::circle(node_key(*id), text, *position);
+            diagram_node.note = note;
+            diagram_node.state = highlights.nodes.get(id).copied();
+            diagram.add_node(diagram_node);
+
+            for child in [node.left, node.right].into_iter().flatten() {
+                let mut edge = DiagramEdge::arrow(node_key(*id), node_key(child));
+                edge.state = highlights.edges.get(&(*id, child)).copied();
+                diagram.add_edge(edge);
+            }
+        }
+
+        diagram
+    }
+    pub fn draw(&self, highlights: &TreeHighlights) -> Diagram {
+        self.draw_with_labels(highlights, |_, node| (node.value.to_string(), None))
+    }
+    /// Traverses the tree in one of the [`TRAVERSAL_ORDERS`], drawing it with `draw`.
+    pub fn traverse(&self, order: &str, draw: DrawTree) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        let Some(root) = self.root else {
+            steps.push(
+                "The tree is empty".to_string(),
+                draw(self, &TreeHighlights::new()),
+            );
+            return Ok(steps);
+        };
+        let mut visited = vec![];
+
+        let visited_list = |visited: &[usize]| {
+            StepList::new(
+                "Visited",
+                visited
+                    .iter()
+                    .map(|id| self.value(*id).to_string())
+                    .collect(),
+            )
+        };
+
+        if order == "Level order" {
+            let mut queue = VecDeque::from([root]);
+            while let Some(id) = queue.pop_front() {
+                visited.push(id);
+                let children = [self.nodes[&id].left, self.nodes[&id].right]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<usize>>();
+                queue.extend(children.iter());
+
+                let mut highlights = TreeHighlights::new();
+                for node in visited.iter() {
+                    highlights.nodes.insert(*node, ElementState::Selected);
+                }
+                for node in queue.iter() {
+                    highlights.nodes.insert(*node, ElementState::Frontier);
+                }
+                highlights.nodes.insert(id, ElementState::Active);
+                let description = if children.is_empty() {
+                    format!("Visit {}", self.value(id))
+                } else {
+                    format!("Visit {} and queue its children", self.value(id))
+                };
+                let step = steps.push(description, draw(self, &highlights));
+                step.lists = vec![
+                    visited_list(&visited),
+                    StepList::new(
+                        "Queue",
+                        queue.iter().map(|id| self.value(*id).to_string()).collect(),
+                    ),
+                ];
+            }
+            return Ok(steps);
+        }
+
+        if !TRAVERSAL_ORDERS.contains(&order) {
+            return Err(format!("Unknown traversal order \"{}\"", order));
+        }
+
+        // The nodes whose subtrees are being traversed, like the call stack of a recursive traversal
+        let mut stack = vec![];
+        self.traverse_depth_first(
+            root,
+            order,
+            &mut stack,
+            &mut visited,
+            &mut |stack, visited| {
+                let id = *visited.last().unwrap();
+                let mut highlights = TreeHighlights::new().path(stack, ElementState::Frontier);
+                for node in visited.iter() {
+                    highlights.nodes.insert(*node, ElementState::Selected);
+                }
+                highlights.nodes.insert(id, ElementState::Active);
+                let step = steps.push(format!("Visit {}", self.value(id)), draw(self, &highlights));
+                step.lists = vec![
+                    visited_list(visited),
+                    StepList::new(
+                        "Call stack",
+                        stack.iter().map(|id| self.value(*id).to_string()).collect(),
+                    ),
+                ];
+            },
+        );
+        Ok(steps)
+    }
+    fn traverse_depth_first(
+        &self,
+        id: usize,
+        order: &str,
+        stack: &mut Vec<usize>,
+        visited: &mut Vec<usize>,
+        on_visit: &mut impl FnMut(&[usize], &[usize]),
+    ) {
+        stack.push(id);
+        let node = &self.nodes[&id];
+        if order == "Pre-order" {
+            visited.push(id);
+            on_visit(stack, visited);
+        }
+        if let Some(left) = node.left {
+            self.traverse_depth_first(left, order, stack, visited, on_visit);
+        }
+        if order == "In-order" {
+            visited.push(id);
+            on_visit(stack, visited);
+        }
+        if let Some(right) = node.right {
+            self.traverse_depth_first(right, order, stack, visited, on_visit);
+        }
+        if order == "Post-order" {
+            visited.push(id);
+            on_visit(stack, visited);
+        }
+        stack.pop();
+    }
+}
+
+/// The operations of binary search trees, which only differ in how the trees are kept balanced.
+pub fn search_tree_operations() -> Vec<Operation> {
+    vec![
+        Operation::new(
+            "Insert",
+            vec![Input::Number {
+                label: "Value to insert",
+                default: 45,
+                min: -999,
+                max: 999,
+            }],
+        ),
+        Operation::new(
+            "Search",
+            vec![Input::Number {
+                label: "Value to search for",
+                default: 65,
+                min: -999,
+                max: 999,
+            }],
+        ),
+        Operation::new(
+            "Delete",
+            vec![Input::Number {
+                label: "Value to delete",
+                default: 30,
+                min: -999,
+                max: 999,
+            }],
+        ),
+        Operation::new(
+            "Traverse",
+            vec![Input::Choice {
+                label: "Traversal order",
+                choices: TRAVERSAL_ORDERS.map(String::from).to_vec(),
+            }],
+        ),
+        Operation::new("Clear", vec![]),
+    ]
+}
+
+pub fn node_key(id: usize) -> String {
+    format!("node-{}", id)
+}
@@ -0,0 +1,344 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+const FILTER_WIDTH: f32 = 800.0;
+const MIN_BITS: usize = 8;
+const MAX_BITS: usize = 64;
+const MAX_HASHES: usize = 6;
+const MAX_WORDS: usize = 20;
+const MAX_WORD_LENGTH: usize = 12;
+const BITS_PER_ROW: usize = 16;
+const BIT_SIZE: (f32, f32) = (38.0, 30.0);
+const BIT_SPACING: (f32, f32) = (44.0, 64.0);
+/// The y of the first row of bits.
+const BITS_Y: f32 = 170.0;
+
+/// The states of the bits and the word that is inserted or looked up at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    word: Option<(String, ElementState)>,
+    /// The states of the bits by their indices.
+    bits: BTreeMap<usize, ElementState>,
+    /// The bits that the word has been hashed to so far, with the numbers of the hash functions.
+    hashes: Vec<(usize, usize)>,
+}
+
+/// A set of words stored as an array of bits, where every word sets the bits that *k* hash functions map it to.
+/// A word is in the set only if all of its bits are set, which can also happen for words that weren't inserted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BloomFilter {
+    bits: Vec<bool>,
+    hash_count: usize,
+    words: Vec<String>,
+}
+impl BloomFilter {
+    pub fn new(size: usize, hash_count: usize) -> Self {
+        Self {
+            bits: vec![false; size.clamp(MIN_BITS, MAX_BITS)],
+            hash_count: hash_count.clamp(1, MAX_HASHES),
+            words: vec![],
+        }
+    }
+    /// Makes a filter by inserting the words in order without keeping the steps.
+    pub fn from_words(size: usize, hash_count: usize, words: &[&str]) -> Self {
+        let mut filter = Self::new(size, hash_count);
+        for word in words {
+            let _ = filter.insert(word, &mut DiagramSteps::new());
+        }
+        filter
+    }
+    /// The bits that the hash functions map the word to, computed with double hashing from two base hashes.
+    fn indices(&self, word: &str) -> Vec<usize> {
+        let (first, second) = base_hashes(word);
+        (0..self.hash_count as u64)
+            .map(|i| (first.wrapping_add(i.wrapping_mul(second)) % self.bits.len() as u64) as usize)
+            .collect()
+    }
+    /// The expected share of false positives, (1 − e^(−kn/m))^k.
+    fn false_positive_rate(&self) -> f64 {
+        let (k, n, m) = (
+            self.hash_count as f64,
+            self.words.len() as f64,
+            self.bits.len() as f64,
+        );
+        (1.0 - (-k * n / m).exp()).powf(k)
+    }
+    fn push(&self, description: String, highlights: &Highlights, steps: &mut DiagramSteps) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = vec![StepList::new("Inserted words", self.words.clone())];
+    }
+    fn insert(&mut self, word: &str, steps: &mut DiagramSteps) -> Result<(), String> {
+        let word = check_word(word)?;
+        if self.words.len() >= MAX_WORDS {
+            return Err(format!("The filter can have at most {} words", MAX_WORDS));
+        }
+        let mut highlights = Highlights {
+            word: Some((word.clone(), ElementState::Active)),
+            ..Default::default()
+        };
+        for (hash, index) in self.indices(&word).into_iter().enumerate() {
+            highlights.hashes.push((hash + 1, index));
+            let description = if self.bits[index] {
+                highlights.bits.insert(index, ElementState::Active);
+                format!(
+                    "h{}({}) = {}, and bit {} is already set",
+                    hash + 1,
+                    word,
+                    index,
+                    index
+                )
+            } else {
+                self.bits[index] = true;
+                highlights.bits.insert(index, ElementState::Selected);
+                format!("h{}({}) = {}, so set bit {}", hash + 1, word, index, index)
+            };
+            self.push(description, &highlights, steps);
+        }
+        if !self.words.contains(&word) {
+            self.words.push(word.clone());
+        }
+        highlights.word = Some((word.clone(), ElementState::Selected));
+        self.push(
+            format!(
+                "{} is inserted. With {} words, a word that was never inserted has all of its bits set with the probability of about {:.1} %",
+                word,
+                self.words.len(),
+                self.false_positive_rate() * 100.0
+            ),
+            &highlights,
+            steps,
+        );
+        Ok(())
+    }
+    fn query(&self, word: &str, steps: &mut DiagramSteps) -> Result<(), String> {
+        let word = check_word(word)?;
+        let mut highlights = Highlights {
+            word: Some((word.clone(), ElementState::Active)),
+            ..Default::default()
+        };
+        for (hash, index) in self.indices(&word).into_iter().enumerate() {
+            highlights.hashes.push((hash + 1, index));
+            if !self.bits[index] {
+                highlights.bits.insert(index, ElementState::Rejected);
+                highlights.word = Some((word.clone(), ElementState::Rejected));
+                self.push(
+                    format!(
+                        "h{}({}) = {}, but bit {} isn't set, so {} is definitely not in the set",
+                        hash + 1,
+                        word,
+                        index,
+                        index,
+                        word
+                    ),
+                    &highlights,
+                    steps,
+                );
+                return Ok(());
+            }
+            highlights.bits.insert(index, ElementState::Active);
+            self.push(
+                format!(
+                    "h{}({}) = {}, and bit {} is set",
+                    hash + 1,
+                    word,
+                    index,
+                    index
+                ),
+                &highlights,
+                steps,
+            );
+        }
+        if self.words.contains(&word) {
+            highlights.word = Some((word.clone(), ElementState::Selected));
+            for (_, index) in highlights.hashes.clone() {
+                highlights.bits.insert(index, ElementState::Selected);
+            }
+            self.push(
+                format!(
+                    "All bits of {} are set, so it is probably in the set, and it was inserted",
+                    word
+                ),
+                &highlights,
+                steps,
+            );
+        } else {
+            highlights.word = Some((word.clone(), ElementState::Conflict));
+            for (_, index) in highlights.hashes.clone() {
+                highlights.bits.insert(index, ElementState::Conflict);
+            }
+            self.push(
+                format!(
+                    "All bits of {} are set, but it was never inserted, so this is a false positive. Other words set its bits",
+                    word
+                ),
+                &highlights,
+                steps,
+            );
+        }
+        Ok(())
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let rows = self.bits.len().div_ceil(BITS_PER_ROW);
+        let height = BITS_Y + BIT_SPACING.1 * rows as f32 + 30.0;
+        let mut diagram = Diagram::new(FILTER_WIDTH, height);
+        let columns = self.bits.len().min(BITS_PER_ROW);
+        let position = |index: usize| {
+            let (row, column) = (index / BITS_PER_ROW, index % BITS_PER_ROW);
+            (
+                FILTER_WIDTH / 2.0 + BIT_SPACING.0 * (column as f32 - (columns as f32 - 1.0) / 2.0),
+                BITS_Y + BIT_SPACING.1 * row as f32,
+            )
+        };
+        for (index, bit) in self.bits.iter().enumerate() {
+            let mut node = DiagramNode::rect(
+                format!("bit-{}", index),
+                u8::from(*bit),
+                position(index),
+                BIT_SIZE,
+            )
+            .with_note(index);
+            if *bit {
+                node.fill = Fill::Group(0);
+            }
+            node.state = highlights.bits.get(&index).copied();
+            diagram.add_node(node);
+        }
+        if let Some((word, state)) = &highlights.word {
+            diagram.add_node(
+                DiagramNode::text("word", word, (FILTER_WIDTH / 2.0, 40.0)).with_state(*state),
+            );
+            // Hash functions that map the word to the same bit share an arrow
+            let mut hashes = BTreeMap::<usize, Vec<String>>::new();
+            for (hash, index) in highlights.hashes.iter() {
+                hashes.entry(*index).or_default().push(format!("h{}", hash));
+            }
+            for (index, names) in hashes {
+                let mut edge = DiagramEdge::arrow("word", format!("bit-{}", index))
+                    .with_label(names.join(", "));
+                edge.state = highlights.bits.get(&index).copied();
+                diagram.add_edge(edge);
+            }
+        }
+        let set_bits = self.bits.iter().filter(|bit| **bit).count();
+        diagram.add_node(DiagramNode::text(
+            "info",
+            format!(
+                "{} words, {} of {} bits set, {} hash functions, false positive rate ≈ {:.1} %",
+                self.words.len(),
+                set_bits,
+                self.bits.len(),
+                self.hash_count,
+                self.false_positive_rate() * 100.0
+            ),
+            (FILTER_WIDTH / 2.0, height - 12.0),
+        ));
+        diagram
+    }
+}
+impl Visualizer for BloomFilter {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Insert",
+                vec![Input::Text {
+                    label: "Word to insert",
+                    default: "cat".to_string(),
+                }],
+            ),
+            Operation::new(
+                "Query",
+                vec![Input::Text {
+                    label: "Word to look up",
+                    default: "emu".to_string(),
+                }],
+            ),
+            Operation::new(
+                "Change size",
+                vec![
+                    Input::Number {
+                        label: "Number of bits",
+                        default: self.bits.len() as i64,
+                        min: MIN_BITS as i64,
+                        max: MAX_BITS as i64,
+                    },
+                    Input::Number {
+                        label: "Number of hash functions",
+                        default: self.hash_count as i64,
+                        min: 1,
+                        max: MAX_HASHES as i64,
+                    },
+                ],
+            ),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Insert" => self.insert(&value.text(), &mut steps)?,
+            "Query" => self.query(&value.text(), &mut steps)?,
+            "Change size" => {
+                let size = value.number();
+                let hash_count = values.get(1).map(|value| value.number()).unwrap_or(1);
+                if !(MIN_BITS as i64..=MAX_BITS as i64).contains(&size) {
+                    return Err(format!(
+                        "The number of bits must be between {} and {}",
+                        MIN_BITS, MAX_BITS
+                    ));
+                }
+                if !(1..=MAX_HASHES as i64).contains(&hash_count) {
+                    return Err(format!(
+                        "The number of hash functions must be between 1 and {}",
+                        MAX_HASHES
+                    ));
+                }
+                // The bits can't be taken back, so the words are inserted again
+                let words = self.words.clone();
+                *self = Self::new(size as usize, hash_count as usize);
+                for word in words {
+                    self.insert(&word, &mut DiagramSteps::new())?;
+                }
+                self.push(
+                    format!(
+                        "Insert every word again into {} bits with {} hash functions",
+                        size, hash_count
+                    ),
+                    &Highlights::default(),
+                    &mut steps,
+                );
+            }
+            "Clear" => {
+                *self = Self::new(self.bits.len(), self.hash_count);
+                steps.push("Remove all words".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn check_word(word: &str) -> Result<String, String> {
+    let word = word.trim();
+    if word.is_empty() || word.chars().count() > MAX_WORD_LENGTH {
+        return Err(format!(
+            "Words must have 1 to {} characters",
+            MAX_WORD_LENGTH
+        ));
+    }
+    Ok(word.to_string())
+}
+
+/// The FNV-1a and djb2 hashes of the word, which the hash functions combine.
+fn base_hashes(word: &str) -> (u64, u64) {
+    let (mut fnv, mut djb) = (0xcbf29ce484222325u64, 5381u64);
+    for byte in word.bytes() {
+        fnv = (fnv ^ byte as u64).wrapping_mul(0x100000001b3);
+        djb = djb.wrapping_mul(33).wrapping_add(byte as u64);
+    }
+    (fnv, djb)
+}
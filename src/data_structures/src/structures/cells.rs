@@ -0,0 +1,152 @@
+//! The cells of stacks, queues and deques, which are drawn as a column or a row of boxes.
+use std::collections::{BTreeMap, VecDeque};
+
+use diagram::*;
+
+pub const CELLS_WIDTH: f32 = 800.0;
+/// The largest number of values in the cells, so that they fit in a row or a column.
+pub const MAX_CELLS: usize = 12;
+/// The space below a column or on both sides of a row.
+const MARGIN: f32 = 40.0;
+const CELL_SIZE: (f32, f32) = (56.0, 30.0);
+/// The distance between the centers of neighboring cells.
+const CELL_SPACING: (f32, f32) = (62.0, 34.0);
+
+/// The states of cells by their ids.
+pub type CellStates = BTreeMap<usize, ElementState>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cell {
+    pub id: usize,
+    pub label: String,
+}
+
+/// Values in order from the front to the back. Every value gets its own id, so that it keeps its key when the
+/// other values move.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Cells {
+    cells: VecDeque<Cell>,
+    next_id: usize,
+}
+impl Cells {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn from_labels(labels: &[impl ToString]) -> Self {
+        let mut cells = Self::new();
+        for label in labels.iter().take(MAX_CELLS) {
+            cells.push_back(label.to_string());
+        }
+        cells
+    }
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    pub fn is_full(&self) -> bool {
+        self.cells.len() >= MAX_CELLS
+    }
+    pub fn front(&self) -> Option<&Cell> {
+        self.cells.front()
+    }
+    pub fn back(&self) -> Option<&Cell> {
+        self.cells.back()
+    }
+    fn new_cell(&mut self, label: impl ToString) -> Cell {
+        self.next_id += 1;
+        Cell {
+            id: self.next_id - 1,
+            label: label.to_string(),
+        }
+    }
+    /// Adds a value to the front and returns its id.
+    pub fn push_front(&mut self, label: impl ToString) -> usize {
+        let cell = self.new_cell(label);
+        let id = cell.id;
+        self.cells.push_front(cell);
+        id
+    }
+    /// Adds a value to the back and returns its id.
+    pub fn push_back(&mut self, label: impl ToString) -> usize {
+        let cell = self.new_cell(label);
+        let id = cell.id;
+        self.cells.push_back(cell);
+        id
+    }
+    pub fn pop_front(&mut self) -> Option<Cell> {
+        self.cells.pop_front()
+    }
+    pub fn pop_back(&mut self) -> Option<Cell> {
+        self.cells.pop_back()
+    }
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+    /// The cells from the front to the back.
+    pub fn iter(&self) -> impl Iterator<Item = &Cell> {
+        self.cells.iter()
+    }
+    fn add_cell(
+        &self,
+        diagram: &mut Diagram,
+        cell: &Cell,
+        position: (f32, f32),
+        states: &CellStates,
+    ) {
+        let mut node = DiagramNode::rect(cell_key(cell.id), &cell.label, position, CELL_SIZE);
+        node.state = states.get(&cell.id).copied();
+        diagram.add_node(node);
+    }
+    /// Draws the cells as a column with the back on top, like a stack, below `top` units of free space.
+    pub fn draw_column(&self, states: &CellStates, top: f32) -> Diagram {
+        let height = top + CELL_SPACING.1 * MAX_CELLS as f32 + MARGIN;
+        let mut diagram = Diagram::new(CELLS_WIDTH, height);
+        let x = CELLS_WIDTH / 2.0;
+        let y = |index: usize| height - MARGIN - CELL_SPACING.1 * (index as f32 + 0.5);
+        diagram.add_figure(Figure::new(FigureKind::Line(
+            (x - CELL_SIZE.0, height - MARGIN + 2.0),
+            (x + CELL_SIZE.0, height - MARGIN + 2.0),
+        )));
+        for (index, cell) in self.cells.iter().enumerate() {
+            self.add_cell(&mut diagram, cell, (x, y(index)), states);
+        }
+        if let Some(back) = self.back() {
+            let key = "pointer-top";
+            let position = (x - CELL_SIZE.0 - 30.0, y(self.len() - 1));
+            diagram.add_node(DiagramNode::text(key, "top", position));
+            diagram.add_edge(DiagramEdge::arrow(key, cell_key(back.id)).dashed());
+        }
+        diagram
+    }
+    /// Draws the cells as a row from the front on the left to the back on the right, like a queue, below `top`
+    /// units of free space.
+    pub fn draw_row(&self, states: &CellStates, top: f32) -> Diagram {
+        let height = top + MARGIN * 4.0;
+        let mut diagram = Diagram::new(CELLS_WIDTH, height);
+        let y = top + MARGIN * 2.0;
+        let x = |index: usize| {
+            CELLS_WIDTH / 2.0 + CELL_SPACING.0 * (index as f32 - (self.len() as f32 - 1.0) / 2.0)
+        };
+        for (index, cell) in self.cells.iter().enumerate() {
+            self.add_cell(&mut diagram, cell, (x(index), y), states);
+        }
+        // The front is pointed to from above and the back from below, so that they don't overlap
+        for (name, cell, index, dy) in [
+            ("front", self.front(), 0, -1.0),
+            ("back", self.back(), self.len().max(1) - 1, 1.0),
+        ] {
+            if let Some(cell) = cell {
+                let key = format!("pointer-{}", name);
+                diagram.add_node(DiagramNode::text(&key, name, (x(index), y + dy * 50.0)));
+                diagram.add_edge(DiagramEdge::arrow(key, cell_key(cell.id)).dashed());
+            }
+        }
+        diagram
+    }
+}
+
+pub fn cell_key(id: usize) -> String {
+    format!("cell-{}", id)
+}
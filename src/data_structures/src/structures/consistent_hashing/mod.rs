@@ -0,0 +1,539 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+const RING_WIDTH: f32 = 800.0;
+const RING_HEIGHT: f32 = 700.0;
+const RING_CENTER: (f32, f32) = (400.0, 350.0);
+const RING_RADIUS: f32 = 200.0;
+/// The distances of the keys from the center, outside of the ring. Keys that are close together alternate between them.
+const KEY_RADII: [f32; 2] = [250.0, 292.0];
+/// How many degrees apart keys must be to fit next to each other at the same distance.
+const KEY_GAP: u32 = 14;
+const KEY_SIZE: (f32, f32) = (56.0, 24.0);
+const SERVER_NAMES: [char; 8] = ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H'];
+const VIRTUAL_NODES: usize = 3;
+const MAX_KEYS: usize = 16;
+const MAX_KEY_LENGTH: usize = 6;
+
+/// A place of a server on the ring. Without virtual nodes, every server has only its first point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Point {
+    /// The position on the ring in degrees clockwise from the top.
+    angle: u32,
+    server: char,
+    replica: usize,
+}
+impl Point {
+    fn new(server: char, replica: usize) -> Self {
+        Self {
+            angle: ring_angle(&format!("{}#{}", server, replica)),
+            server,
+            replica,
+        }
+    }
+    fn key(&self) -> String {
+        format!("point-{}-{}", self.server, self.replica)
+    }
+}
+
+/// The states of the points and the keys, along with the arc that is walked from a key to its server.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    points: BTreeMap<String, ElementState>,
+    keys: BTreeMap<String, ElementState>,
+    /// The angles that the walk starts and stops at.
+    arc: Option<(u32, u32)>,
+}
+
+/// Spreads keys over servers by hashing both onto a ring, where every key belongs to the first server point
+/// clockwise from it. Adding or removing a server only moves the keys next to its points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsistentHashing {
+    servers: Vec<char>,
+    keys: Vec<String>,
+    virtual_nodes: bool,
+}
+impl ConsistentHashing {
+    pub fn new(virtual_nodes: bool) -> Self {
+        Self {
+            servers: vec![],
+            keys: vec![],
+            virtual_nodes,
+        }
+    }
+    /// Makes a ring with the servers and the keys without keeping the steps.
+    pub fn from_keys(servers: &[char], keys: &[&str]) -> Self {
+        let mut ring = Self::new(false);
+        ring.servers = servers.to_vec();
+        ring.servers.sort_unstable();
+        for key in keys {
+            let _ = ring.add_key(key, &mut DiagramSteps::new());
+        }
+        ring
+    }
+    /// The points of all of the servers in clockwise order.
+    fn points(&self) -> Vec<Point> {
+        let replicas = if self.virtual_nodes { VIRTUAL_NODES } else { 1 };
+        let mut points = self
+            .servers
+            .iter()
+            .flat_map(|server| (1..=replicas).map(|replica| Point::new(*server, replica)))
+            .collect::<Vec<Point>>();
+        points.sort_unstable();
+        points
+    }
+    fn point_name(&self, point: &Point) -> String {
+        if self.virtual_nodes {
+            format!("{}{}", point.server, point.replica)
+        } else {
+            point.server.to_string()
+        }
+    }
+    /// The keys in clockwise order.
+    fn sorted_keys(&self) -> Vec<String> {
+        let mut keys = self.keys.clone();
+        keys.sort_by_key(|key| (ring_angle(key), key.clone()));
+        keys
+    }
+    /// Places the keys at their angles, moving a key outwards if it would overlap the previous key.
+    fn key_positions(&self) -> BTreeMap<String, (f32, f32)> {
+        let mut last_angles: [Option<u32>; 2] = [None; 2];
+        let mut positions = BTreeMap::new();
+        for key in self.sorted_keys() {
+            let angle = ring_angle(&key);
+            let lane = (0..KEY_RADII.len())
+                .find(|lane| last_angles[*lane].is_none_or(|last| angle - last >= KEY_GAP))
+                .unwrap_or(0);
+            last_angles[lane] = Some(angle);
+            positions.insert(key, ring_position(angle, KEY_RADII[lane]));
+        }
+        positions
+    }
+    fn owners(&self, points: &[Point]) -> BTreeMap<String, Point> {
+        self.keys
+            .iter()
+            .filter_map(|key| Some((key.clone(), owner(points, ring_angle(key))?)))
+            .collect()
+    }
+    fn push<'a>(
+        &self,
+        description: String,
+        points: &[Point],
+        owners: &BTreeMap<String, Point>,
+        highlights: &Highlights,
+        steps: &'a mut DiagramSteps,
+    ) -> &'a mut DiagramStep {
+        steps.push(description, self.draw(points, owners, highlights))
+    }
+    fn keys_per_server(&self, owners: &BTreeMap<String, Point>) -> Vec<String> {
+        self.servers
+            .iter()
+            .map(|server| {
+                let count = owners
+                    .values()
+                    .filter(|point| point.server == *server)
+                    .count();
+                format!("{}: {}", server, count)
+            })
+            .collect()
+    }
+    fn add_key(&mut self, key: &str, steps: &mut DiagramSteps) -> Result<(), String> {
+        let key = key.trim().to_string();
+        if key.is_empty() || key.chars().count() > MAX_KEY_LENGTH {
+            return Err(format!("Keys must have 1 to {} characters", MAX_KEY_LENGTH));
+        }
+        if self.servers.is_empty() {
+            return Err("Add a server before adding keys".to_string());
+        }
+        let points = self.points();
+        let angle = ring_angle(&key);
+        if self.keys.contains(&key) {
+            let owners = self.owners(&points);
+            let highlights = Highlights {
+                keys: BTreeMap::from([(key.clone(), ElementState::Conflict)]),
+                ..Default::default()
+            };
+            self.push(
+                format!("{} is already on the ring at {}°", key, angle),
+                &points,
+                &owners,
+                &highlights,
+                steps,
+            );
+            return Ok(());
+        }
+        if self.keys.len() >= MAX_KEYS {
+            return Err(format!("The ring can have at most {} keys", MAX_KEYS));
+        }
+        self.keys.push(key.clone());
+        let mut owners = self.owners(&points);
+        let point = owners.remove(&key).unwrap();
+        let mut highlights = Highlights {
+            keys: BTreeMap::from([(key.clone(), ElementState::Active)]),
+            ..Default::default()
+        };
+        self.push(
+            format!(
+                "hash({}) mod 360 = {}, so {} goes to {}°",
+                key, angle, key, angle
+            ),
+            &points,
+            &owners,
+            &highlights,
+            steps,
+        );
+        owners.insert(key.clone(), point);
+        highlights.keys.insert(key.clone(), ElementState::Selected);
+        highlights
+            .points
+            .insert(point.key(), ElementState::Selected);
+        highlights.arc = Some((angle, point.angle));
+        let description = if point.angle < angle {
+            format!(
+                "No point comes after {}°, so the walk wraps around past the top to {} at {}°. {} stores {}",
+                angle,
+                self.point_name(&point),
+                point.angle,
+                point.server,
+                key
+            )
+        } else {
+            format!(
+                "Walk clockwise from {}° to the first point, {} at {}°. {} stores {}",
+                angle,
+                self.point_name(&point),
+                point.angle,
+                point.server,
+                key
+            )
+        };
+        let step = self.push(description, &points, &owners, &highlights, steps);
+        step.lists = vec![StepList::new(
+            "Keys per server",
+            self.keys_per_server(&owners),
+        )];
+        Ok(())
+    }
+    /// Animates the keys that move to other servers after the points changed from `old_points` to the current ones.
+    fn reassign(&self, old_points: &[Point], description: String, steps: &mut DiagramSteps) {
+        let new_points = self.points();
+        let mut all_points = old_points.to_vec();
+        all_points.extend(
+            new_points
+                .iter()
+                .filter(|point| !old_points.contains(point)),
+        );
+        all_points.sort_unstable();
+        let mut highlights = Highlights::default();
+        for point in all_points.iter() {
+            if !old_points.contains(point) {
+                highlights
+                    .points
+                    .insert(point.key(), ElementState::Selected);
+            } else if !new_points.contains(point) {
+                highlights
+                    .points
+                    .insert(point.key(), ElementState::Rejected);
+            }
+        }
+        let mut owners = self.owners(old_points);
+        self.push(description, &all_points, &owners, &highlights, steps);
+
+        let new_owners = self.owners(&new_points);
+        let mut moved = vec![];
+        for key in self.sorted_keys() {
+            let (old, new) = (owners[&key], new_owners[&key]);
+            if old.server == new.server {
+                continue;
+            }
+            owners.insert(key.clone(), new);
+            highlights.keys.insert(key.clone(), ElementState::Selected);
+            highlights.arc = Some((ring_angle(&key), new.angle));
+            moved.push(format!("{}: {} → {}", key, old.server, new.server));
+            let step = self.push(
+                format!(
+                    "{} at {}° moves from {} to {}, since {} at {}° is now the first point clockwise from it",
+                    key,
+                    ring_angle(&key),
+                    old.server,
+                    new.server,
+                    self.point_name(&new),
+                    new.angle
+                ),
+                &all_points,
+                &owners,
+                &highlights,
+                steps,
+            );
+            step.lists = vec![StepList::new("Moved keys", moved.clone())];
+        }
+
+        highlights
+            .points
+            .retain(|_, state| *state != ElementState::Rejected);
+        highlights.arc = None;
+        let description = if self.keys.is_empty() {
+            "There are no keys to move".to_string()
+        } else {
+            format!(
+                "{} of {} keys moved, and the rest stayed on their servers",
+                moved.len(),
+                self.keys.len()
+            )
+        };
+        let step = self.push(description, &new_points, &new_owners, &highlights, steps);
+        step.lists = vec![
+            StepList::new("Moved keys", moved),
+            StepList::new("Keys per server", self.keys_per_server(&new_owners)),
+        ];
+    }
+    fn draw(
+        &self,
+        points: &[Point],
+        owners: &BTreeMap<String, Point>,
+        highlights: &Highlights,
+    ) -> Diagram {
+        let mut diagram = Diagram::new(RING_WIDTH, RING_HEIGHT);
+        let key_positions = self.key_positions();
+        diagram.add_figure(Figure::new(FigureKind::Circle {
+            center: RING_CENTER,
+            radius: RING_RADIUS,
+        }));
+        if let Some((from, to)) = highlights.arc {
+            // The walk goes clockwise, so it can wrap around past the top
+            let to = if to < from { to + 360 } else { to };
+            let arc = (from..=to)
+                .map(|angle| ring_position(angle, RING_RADIUS))
+                .collect();
+            diagram.add_figure(
+                Figure::new(FigureKind::Polyline(arc)).with_state(ElementState::Active),
+            );
+        }
+        diagram.add_node(DiagramNode::text(
+            "top",
+            "0°",
+            (RING_CENTER.0, RING_CENTER.1 - RING_RADIUS + 40.0),
+        ));
+        for point in points.iter() {
+            let mut node = DiagramNode::circle(
+                point.key(),
+                self.point_name(point),
+                ring_position(point.angle, RING_RADIUS),
+            )
+            .with_note(format!("{}°", point.angle))
+            .with_fill(server_fill(point.server));
+            node.state = highlights.points.get(&point.key()).copied();
+            diagram.add_node(node);
+        }
+        for (key, point) in owners.iter() {
+            let mut node = DiagramNode::rect(key_node(key), key, key_positions[key], KEY_SIZE)
+                .with_note(format!("{}°", ring_angle(key)))
+                .with_fill(server_fill(point.server));
+            node.state = highlights.keys.get(key).copied();
+            diagram.add_node(node);
+            let mut edge = DiagramEdge::arrow(key_node(key), point.key()).dashed();
+            edge.state = highlights.keys.get(key).copied();
+            diagram.add_edge(edge);
+        }
+        // A key that is being placed doesn't have a server yet
+        for (key, state) in highlights.keys.iter() {
+            if !owners.contains_key(key) {
+                diagram.add_node(
+                    DiagramNode::rect(key_node(key), key, key_positions[key], KEY_SIZE)
+                        .with_note(format!("{}°", ring_angle(key)))
+                        .with_state(*state),
+                );
+            }
+        }
+        diagram.add_node(DiagramNode::text(
+            "info",
+            format!("{} servers, {} keys", self.servers.len(), owners.len()),
+            RING_CENTER,
+        ));
+        if self.virtual_nodes {
+            diagram.add_node(DiagramNode::text(
+                "virtual",
+                format!("{} points per server", VIRTUAL_NODES),
+                (RING_CENTER.0, RING_CENTER.1 + 30.0),
+            ));
+        }
+        diagram
+    }
+}
+impl Visualizer for ConsistentHashing {
+    fn diagram(&self) -> Diagram {
+        let points = self.points();
+        self.draw(&points, &self.owners(&points), &Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let free = SERVER_NAMES
+            .iter()
+            .filter(|server| !self.servers.contains(server))
+            .map(|server| server.to_string())
+            .collect();
+        vec![
+            Operation::new(
+                "Add key",
+                vec![Input::Text {
+                    label: "Key to add",
+                    default: "kiwi".to_string(),
+                }],
+            ),
+            Operation::new(
+                "Add server",
+                vec![Input::Choice {
+                    label: "Server to add",
+                    choices: free,
+                }],
+            ),
+            Operation::new(
+                "Remove server",
+                vec![Input::Choice {
+                    label: "Server to remove",
+                    choices: self
+                        .servers
+                        .iter()
+                        .map(|server| server.to_string())
+                        .collect(),
+                }],
+            ),
+            Operation::new(
+                if self.virtual_nodes {
+                    "Turn off virtual nodes"
+                } else {
+                    "Turn on virtual nodes"
+                },
+                vec![],
+            ),
+            Operation::new("Clear keys", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Add key" => self.add_key(&value.text(), &mut steps)?,
+            "Add server" => {
+                if self.servers.len() >= SERVER_NAMES.len() {
+                    return Err(format!(
+                        "The ring can have at most {} servers",
+                        SERVER_NAMES.len()
+                    ));
+                }
+                let server = parse_server(&value.text())?;
+                if self.servers.contains(&server) {
+                    return Err(format!("{} is already on the ring", server));
+                }
+                let old_points = self.points();
+                self.servers.push(server);
+                self.servers.sort_unstable();
+                let description = if self.virtual_nodes {
+                    format!(
+                        "Hash {}1 to {}{} onto the ring. Only the keys right before them can move to {}",
+                        server, server, VIRTUAL_NODES, server
+                    )
+                } else {
+                    format!(
+                        "Hash {} onto the ring at {}°. Only the keys right before it can move to {}",
+                        server,
+                        Point::new(server, 1).angle,
+                        server
+                    )
+                };
+                self.reassign(&old_points, description, &mut steps);
+            }
+            "Remove server" => {
+                let server = parse_server(&value.text())?;
+                if !self.servers.contains(&server) {
+                    return Err(format!("{} isn't on the ring", server));
+                }
+                if self.servers.len() == 1 && !self.keys.is_empty() {
+                    return Err("The last server can't be removed while there are keys".to_string());
+                }
+                let old_points = self.points();
+                self.servers.retain(|s| *s != server);
+                self.reassign(
+                    &old_points,
+                    format!(
+                        "Remove the points of {}. Its keys move to the next points clockwise",
+                        server
+                    ),
+                    &mut steps,
+                );
+            }
+            "Turn on virtual nodes" | "Turn off virtual nodes" => {
+                let old_points = self.points();
+                self.virtual_nodes = !self.virtual_nodes;
+                let description = if self.virtual_nodes {
+                    format!(
+                        "Give every server {} points on the ring, so that the keys are spread more evenly",
+                        VIRTUAL_NODES
+                    )
+                } else {
+                    "Keep only the first point of every server".to_string()
+                };
+                self.reassign(&old_points, description, &mut steps);
+            }
+            "Clear keys" => {
+                self.keys.clear();
+                steps.push("Remove all keys".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The first point at or clockwise after the angle, wrapping around past the top.
+fn owner(points: &[Point], angle: u32) -> Option<Point> {
+    points
+        .iter()
+        .find(|point| point.angle >= angle)
+        .or_else(|| points.first())
+        .copied()
+}
+
+/// The FNV-1a hash of the text in whole degrees. The hash is mixed first, since short texts that only differ in
+/// their last characters would otherwise land close to each other.
+fn ring_angle(text: &str) -> u32 {
+    let mut hash = 0xcbf29ce484222325u64;
+    for byte in text.bytes() {
+        hash = (hash ^ byte as u64).wrapping_mul(0x100000001b3);
+    }
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+    hash ^= hash >> 33;
+    (hash % 360) as u32
+}
+
+fn ring_position(angle: u32, radius: f32) -> (f32, f32) {
+    let radians = (angle as f32).to_radians();
+    (
+        RING_CENTER.0 + radius * radians.sin(),
+        RING_CENTER.1 - radius * radians.cos(),
+    )
+}
+
+fn server_fill(server: char) -> Fill {
+    Fill::Group(SERVER_NAMES.iter().position(|s| *s == server).unwrap_or(0))
+}
+
+fn key_node(key: &str) -> String {
+    format!("key-{}", key)
+}
+
+fn parse_server(text: &str) -> Result<char, String> {
+    let text = text.trim().to_uppercase();
+    SERVER_NAMES
+        .iter()
+        .find(|server| server.to_string() == text)
+        .copied()
+        .ok_or_else(|| {
+            format!(
+                "Servers are named from {} to {}",
+                SERVER_NAMES[0],
+                SERVER_NAMES[SERVER_NAMES.len() - 1]
+            )
+        })
+}
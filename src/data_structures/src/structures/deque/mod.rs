@@ -0,0 +1,100 @@
+use diagram::*;
+
+use super::cells::*;
+
+/// A double-ended queue, where values can be added to and removed from both the front and the back.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Deque {
+    cells: Cells,
+}
+impl Deque {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn from_values(values: &[i64]) -> Self {
+        Self {
+            cells: Cells::from_labels(values),
+        }
+    }
+    fn push(&mut self, value: i64, front: bool, steps: &mut DiagramSteps) -> Result<(), String> {
+        if self.cells.is_full() {
+            return Err(format!("The deque can have at most {} values", MAX_CELLS));
+        }
+        let (id, end) = if front {
+            (self.cells.push_front(value), "front")
+        } else {
+            (self.cells.push_back(value), "back")
+        };
+        steps.push(
+            format!("Add {} to the {} of the deque", value, end),
+            self.cells
+                .draw_row(&CellStates::from([(id, ElementState::Selected)]), 0.0),
+        );
+        Ok(())
+    }
+    fn pop(&mut self, front: bool, steps: &mut DiagramSteps) -> Result<(), String> {
+        let (cell, end) = if front {
+            (self.cells.front(), "front")
+        } else {
+            (self.cells.back(), "back")
+        };
+        let cell = cell.ok_or("The deque is empty")?.clone();
+        steps.push(
+            format!("Remove {} from the {} of the deque", cell.label, end),
+            self.cells
+                .draw_row(&CellStates::from([(cell.id, ElementState::Rejected)]), 0.0),
+        );
+        let next = if front {
+            self.cells.pop_front();
+            self.cells.front()
+        } else {
+            self.cells.pop_back();
+            self.cells.back()
+        };
+        let description = match next {
+            Some(cell) => format!("{} is now at the {}", cell.label, end),
+            None => "The deque is now empty".to_string(),
+        };
+        steps.push(description, self.diagram());
+        Ok(())
+    }
+}
+impl Visualizer for Deque {
+    fn diagram(&self) -> Diagram {
+        self.cells.draw_row(&CellStates::new(), 0.0)
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let value_input = |label| Input::Number {
+            label,
+            default: 42,
+            min: 0,
+            max: 99,
+        };
+        vec![
+            Operation::new(
+                "Push front",
+                vec![value_input("Value to push to the front")],
+            ),
+            Operation::new("Push back", vec![value_input("Value to push to the back")]),
+            Operation::new("Pop front", vec![]),
+            Operation::new("Pop back", vec![]),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Push front" => self.push(value.number(), true, &mut steps)?,
+            "Push back" => self.push(value.number(), false, &mut steps)?,
+            "Pop front" => self.pop(true, &mut steps)?,
+            "Pop back" => self.pop(false, &mut steps)?,
+            "Clear" => {
+                self.cells.clear();
+                steps.push("Remove all values".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
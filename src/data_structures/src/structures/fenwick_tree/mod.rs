@@ -0,0 +1,396 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use super::ranges::*;
+
+const LEVEL_HEIGHT: f32 = 62.0;
+
+/// The states of the nodes and cells and the shaded ranges at a step. The keys are indices that start from 1.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    nodes: BTreeMap<usize, ElementState>,
+    cells: BTreeMap<usize, ElementState>,
+    /// The range of the current node.
+    range: Option<((usize, usize), ElementState)>,
+    /// The range of a query, which is outlined.
+    query: Option<(usize, usize)>,
+}
+impl Highlights {
+    fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// An array where the node at index *i* stores the sum of the *lowbit(i)* values that end at *i*, where *lowbit(i)*
+/// is the lowest set bit of *i*. Prefix sums and updates of single values follow *O(log n)* nodes by adding or
+/// subtracting the lowest set bit. Also known as a binary indexed tree. The indices start from 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FenwickTree {
+    values: Vec<i64>,
+    /// The values of the nodes, where the node at index 0 is unused.
+    nodes: Vec<i64>,
+}
+impl FenwickTree {
+    /// Builds a tree over the values, or over a single 0 if there are no values.
+    pub fn new(values: &[i64]) -> Self {
+        let values = if values.is_empty() { &[0] } else { values };
+        let mut tree = Self {
+            values: vec![],
+            nodes: vec![],
+        };
+        tree.build(values, &mut DiagramSteps::new());
+        tree
+    }
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+    /// The range of values that the node at the index covers.
+    fn node_range(index: usize) -> (usize, usize) {
+        (index - lowbit(index) + 1, index)
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &Highlights,
+        sums: Option<Vec<String>>,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(highlights));
+        if let Some(sums) = sums {
+            step.lists = vec![StepList::new("Added nodes", sums)];
+        }
+    }
+    fn build(&mut self, values: &[i64], steps: &mut DiagramSteps) {
+        self.values = values.to_vec();
+        self.nodes = [0].into_iter().chain(values.iter().copied()).collect();
+        self.push(
+            "Copy the values into the nodes, and then add every node to the next node that covers it".to_string(),
+            &Highlights::new(),
+            None,
+            steps,
+        );
+        for index in 1..=self.len() {
+            let parent = index + lowbit(index);
+            if parent > self.len() {
+                continue;
+            }
+            self.nodes[parent] += self.nodes[index];
+            self.push(
+                format!(
+                    "Add node {} to node {}, the next node that covers it, since {} + lowbit({}) = {}",
+                    index, parent, index, index, parent
+                ),
+                &Highlights {
+                    nodes: BTreeMap::from([(index, ElementState::Active), (parent, ElementState::Selected)]),
+                    range: Some((Self::node_range(parent), ElementState::Active)),
+                    ..Default::default()
+                },
+                None,
+                steps,
+            );
+        }
+        self.push(
+            "The tree is built, and every node has the sum of the range that ends at its index"
+                .to_string(),
+            &Highlights::new(),
+            None,
+            steps,
+        );
+    }
+    fn update(&mut self, index: usize, value: i64, steps: &mut DiagramSteps) {
+        let change = value - self.values[index - 1];
+        self.values[index - 1] = value;
+        let mut highlights = Highlights::new();
+        highlights.cells.insert(index, ElementState::Selected);
+        self.push(
+            format!(
+                "Set the value at index {} to {}, a change of {:+}",
+                index, value, change
+            ),
+            &highlights,
+            None,
+            steps,
+        );
+        let mut node = index;
+        while node <= self.len() {
+            self.nodes[node] += change;
+            highlights.nodes.insert(node, ElementState::Selected);
+            let next = node + lowbit(node);
+            self.push(
+                format!(
+                    "Node {} sums {}, which has index {}, so add {:+} to it. The next node is {} + lowbit({}) = {}",
+                    node,
+                    range_name(Self::node_range(node)),
+                    index,
+                    change,
+                    node,
+                    node,
+                    next
+                ),
+                &Highlights {
+                    range: Some((Self::node_range(node), ElementState::Active)),
+                    ..highlights.clone()
+                },
+                None,
+                steps,
+            );
+            node = next;
+        }
+        self.push(
+            format!(
+                "{} is past the end, so every node that covers index {} is updated",
+                node, index
+            ),
+            &highlights,
+            None,
+            steps,
+        );
+    }
+    /// Sums the values from 1 to `end`.
+    fn prefix_sum(
+        &self,
+        end: usize,
+        highlights: &mut Highlights,
+        sums: &mut Vec<String>,
+        steps: &mut DiagramSteps,
+    ) -> i64 {
+        let mut sum = 0;
+        let mut node = end;
+        while node > 0 {
+            sum += self.nodes[node];
+            highlights.nodes.insert(node, ElementState::Selected);
+            sums.push(format!(
+                "{} sums {}: {}",
+                node,
+                range_name(Self::node_range(node)),
+                self.nodes[node]
+            ));
+            let next = node - lowbit(node);
+            self.push(
+                format!(
+                    "Add node {}, the sum of {}, which makes the sum {}. The next node is {} − lowbit({}) = {}",
+                    node,
+                    range_name(Self::node_range(node)),
+                    sum,
+                    node,
+                    node,
+                    next
+                ),
+                &Highlights {
+                    range: Some((Self::node_range(node), ElementState::Active)),
+                    ..highlights.clone()
+                },
+                Some(sums.clone()),
+                steps,
+            );
+            node = next;
+        }
+        sum
+    }
+    fn range_sum(&self, (start, end): (usize, usize), steps: &mut DiagramSteps) {
+        let mut highlights = Highlights {
+            query: Some((start, end)),
+            ..Default::default()
+        };
+        let mut sums = vec![];
+        self.push(
+            format!(
+                "Sum the values from 1 to {} by following the nodes down from {}",
+                end, end
+            ),
+            &highlights,
+            Some(sums.clone()),
+            steps,
+        );
+        let sum = self.prefix_sum(end, &mut highlights, &mut sums, steps);
+        for index in start..=end {
+            highlights.cells.insert(index, ElementState::Selected);
+        }
+        if start == 1 {
+            self.push(
+                format!("The sum of {} is {}", range_name((start, end)), sum),
+                &highlights,
+                Some(sums),
+                steps,
+            );
+            return;
+        }
+
+        self.push(
+            format!(
+                "The sum from 1 to {} is {}. Subtract the sum from 1 to {}, which is before the range",
+                end,
+                sum,
+                start - 1
+            ),
+            &highlights,
+            Some(sums.clone()),
+            steps,
+        );
+        let mut before_highlights = Highlights {
+            query: Some((start, end)),
+            ..Default::default()
+        };
+        for index in 1..start {
+            before_highlights
+                .cells
+                .insert(index, ElementState::Rejected);
+        }
+        let mut before_sums = vec![];
+        let before = self.prefix_sum(start - 1, &mut before_highlights, &mut before_sums, steps);
+        for index in start..=end {
+            before_highlights
+                .cells
+                .insert(index, ElementState::Selected);
+        }
+        self.push(
+            format!(
+                "The sum of {} is {} − {} = {}",
+                range_name((start, end)),
+                sum,
+                before,
+                sum - before
+            ),
+            &before_highlights,
+            Some(before_sums),
+            steps,
+        );
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(RANGES_WIDTH, RANGES_HEIGHT);
+        let size = self.len();
+        // The ranges are drawn over cells whose indices start from 0
+        let cells = |(start, end): (usize, usize)| (start - 1, end - 1);
+        if let Some(query) = highlights.query {
+            shade_range(&mut diagram, cells(query), size, None, true);
+        }
+        if let Some((range, state)) = highlights.range {
+            shade_range(&mut diagram, cells(range), size, Some(state), false);
+        }
+        for index in 1..=size {
+            let (start, end) = cells(Self::node_range(index));
+            let position = (
+                (cell_x(start, size) + cell_x(end, size)) / 2.0,
+                ARRAY_Y - 80.0 - LEVEL_HEIGHT * index.trailing_zeros() as f32,
+            );
+            let mut node = DiagramNode::rect(
+                format!("node-{}", index),
+                self.nodes[index],
+                position,
+                (range_width(start, end), CELL_SIZE.1),
+            )
+            .with_note(index);
+            node.state = highlights.nodes.get(&index).copied();
+            diagram.add_node(node);
+        }
+        let cell_states = highlights
+            .cells
+            .iter()
+            .map(|(index, state)| (index - 1, *state))
+            .collect();
+        draw_array(&mut diagram, &self.values, 1, &cell_states);
+        diagram
+    }
+}
+impl Visualizer for FenwickTree {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let last = self.len() as i64;
+        vec![
+            Operation::new(
+                "Range sum",
+                vec![
+                    Input::Number {
+                        label: "Start index",
+                        default: 3.min(last),
+                        min: 1,
+                        max: last,
+                    },
+                    Input::Number {
+                        label: "End index",
+                        default: 7.min(last),
+                        min: 1,
+                        max: last,
+                    },
+                ],
+            ),
+            Operation::new(
+                "Update",
+                vec![
+                    Input::Number {
+                        label: "Index to update",
+                        default: 3.min(last),
+                        min: 1,
+                        max: last,
+                    },
+                    Input::Number {
+                        label: "New value",
+                        default: 10,
+                        min: 0,
+                        max: MAX_ARRAY_VALUE,
+                    },
+                ],
+            ),
+            Operation::new(
+                "Build",
+                vec![Input::Text {
+                    label: "Values",
+                    default: "5 8 6 3 2 7 2 6".to_string(),
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let number = |index: usize| {
+            values
+                .get(index)
+                .map(|value| value.number())
+                .unwrap_or_default()
+        };
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Range sum" => {
+                let (start, end) = check_range(number(0), number(1), self.len(), 1)?;
+                self.range_sum((start + 1, end + 1), &mut steps);
+            }
+            "Update" => {
+                let index = check_index(number(0), self.len(), 1)?;
+                let value = number(1);
+                if !(0..=MAX_ARRAY_VALUE).contains(&value) {
+                    return Err(format!(
+                        "The values must be between 0 and {}",
+                        MAX_ARRAY_VALUE
+                    ));
+                }
+                self.update(index + 1, value, &mut steps);
+            }
+            "Build" => {
+                let array = parse_values(
+                    &values.first().map(|value| value.text()).unwrap_or_default(),
+                    0,
+                    MAX_ARRAY_VALUE,
+                    MAX_ARRAY_SIZE,
+                )?;
+                self.build(&array, &mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The lowest set bit of the index, which is the number of values that the node at the index sums.
+fn lowbit(index: usize) -> usize {
+    index & index.wrapping_neg()
+}
+
+fn range_name((start, end): (usize, usize)) -> String {
+    if start == end {
+        format!("[{}]", start)
+    } else {
+        format!("[{}, {}]", start, end)
+    }
+}
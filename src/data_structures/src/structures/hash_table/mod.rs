@@ -0,0 +1,601 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use diagram::*;
+
+const TABLE_WIDTH: f32 = 800.0;
+const TABLE_HEIGHT: f32 = 500.0;
+/// The numbers of slots that the table grows through. They are primes, so that quadratic probing and double
+/// hashing can reach enough of the slots.
+const CAPACITIES: [usize; 3] = [5, 11, 23];
+const MAX_KEY: i64 = 99;
+/// The y of the keys that are waiting to be inserted again when the table grows.
+const PENDING_Y: f32 = 80.0;
+/// The y of the slots of the table.
+const SLOTS_Y: f32 = 170.0;
+const KEY_HEIGHT: f32 = 30.0;
+/// The space between the keys of a chain.
+const CHAIN_SPACING: f32 = 55.0;
+const COLLISION_STRATEGIES: [&str; 4] = [
+    "Separate chaining",
+    "Linear probing",
+    "Quadratic probing",
+    "Double hashing",
+];
+
+/// How keys that hash to the same slot are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Strategy {
+    /// Every slot has a list of its keys.
+    Chaining,
+    /// The next slots are tried one by one.
+    Linear,
+    /// The slots that are 1, 4, 9 and so on slots away are tried.
+    Quadratic,
+    /// The slots are tried with a step that is given by a second hash function.
+    Double,
+}
+impl Strategy {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "Linear probing" => Strategy::Linear,
+            "Quadratic probing" => Strategy::Quadratic,
+            "Double hashing" => Strategy::Double,
+            _ => Strategy::Chaining,
+        }
+    }
+    /// The largest ratio of keys to slots before the table grows. Quadratic probing is only guaranteed to find a
+    /// free slot when at most half of the slots are used.
+    fn max_load(&self) -> f32 {
+        match self {
+            Strategy::Chaining => 1.0,
+            Strategy::Linear | Strategy::Double => 0.7,
+            Strategy::Quadratic => 0.5,
+        }
+    }
+    /// The most keys that fit into the largest table.
+    fn max_keys(&self) -> usize {
+        (CAPACITIES[CAPACITIES.len() - 1] as f32 * self.max_load()) as usize
+    }
+}
+
+/// The states of the slots and the keys of a hash table at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    slots: BTreeMap<usize, ElementState>,
+    keys: BTreeMap<i64, ElementState>,
+}
+impl Highlights {
+    fn new() -> Self {
+        Self::default()
+    }
+    fn slot(mut self, slot: usize, state: ElementState) -> Self {
+        self.slots.insert(slot, state);
+        self
+    }
+    fn key(mut self, key: i64, state: ElementState) -> Self {
+        self.keys.insert(key, state);
+        self
+    }
+}
+
+/// Where the probes for a key ended in a table that uses open addressing.
+struct Probes {
+    /// The slots in the order that they were probed.
+    slots: Vec<usize>,
+    /// The slot of the key if it is in the table.
+    found: Option<usize>,
+    /// The first slot that the key could be placed in.
+    free: Option<usize>,
+}
+
+/// A table that stores keys in slots by their hashes, with one of the [`COLLISION_STRATEGIES`] for keys
+/// that hash to the same slot. The hash of a key *k* is *k mod m*, where *m* is the number of slots.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HashTable {
+    strategy: Strategy,
+    /// The keys of every slot. With open addressing, slots have at most one key.
+    slots: Vec<Vec<i64>>,
+    /// Empty slots that had a key that was deleted, which probes have to go past.
+    deleted: BTreeSet<usize>,
+}
+impl HashTable {
+    pub fn new(strategy: &str) -> Self {
+        Self {
+            strategy: Strategy::from_name(strategy),
+            slots: vec![vec![]; CAPACITIES[0]],
+            deleted: BTreeSet::new(),
+        }
+    }
+    /// Makes a table by inserting the keys in order without keeping the steps.
+    pub fn from_keys(strategy: &str, keys: &[i64]) -> Self {
+        let mut table = Self::new(strategy);
+        for key in keys {
+            let _ = table.insert(*key, &mut DiagramSteps::new());
+        }
+        table
+    }
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+    fn keys(&self) -> Vec<i64> {
+        self.slots.iter().flatten().copied().collect()
+    }
+    fn hash(&self, key: i64) -> usize {
+        key as usize % self.capacity()
+    }
+    /// The step of double hashing, which is never 0.
+    fn step_size(&self, key: i64) -> usize {
+        1 + key as usize % (self.capacity() - 1)
+    }
+    /// The slot of the probe with the given index.
+    fn probe(&self, key: i64, i: usize) -> usize {
+        let hash = self.hash(key);
+        let offset = match self.strategy {
+            Strategy::Chaining => 0,
+            Strategy::Linear => i,
+            Strategy::Quadratic => i * i,
+            Strategy::Double => i * self.step_size(key),
+        };
+        (hash + offset) % self.capacity()
+    }
+    /// Explains how the slot of a probe was calculated.
+    fn describe_probe(&self, key: i64, i: usize) -> String {
+        let (hash, capacity, slot) = (self.hash(key), self.capacity(), self.probe(key, i));
+        if i == 0 {
+            let description = format!("h({}) = {} mod {} = {}", key, key, capacity, hash);
+            return match self.strategy {
+                Strategy::Double => format!(
+                    "{}, and the step is h₂({}) = 1 + {} mod {} = {}",
+                    description,
+                    key,
+                    key,
+                    capacity - 1,
+                    self.step_size(key)
+                ),
+                _ => description,
+            };
+        }
+        let offset = match self.strategy {
+            Strategy::Chaining | Strategy::Linear => i.to_string(),
+            Strategy::Quadratic => format!("{}²", i),
+            Strategy::Double => format!("{} · {}", i, self.step_size(key)),
+        };
+        format!(
+            "Probe {}: ({} + {}) mod {} = {}",
+            i, hash, offset, capacity, slot
+        )
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: Highlights,
+        probes: &[usize],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(&highlights, &[]));
+        if !probes.is_empty() {
+            step.lists = vec![StepList::new(
+                "Probed slots",
+                probes.iter().map(|slot| slot.to_string()).collect(),
+            )];
+        }
+    }
+    /// Probes the slots for the key in a table that uses open addressing.
+    fn probe_steps(&self, key: i64, steps: &mut DiagramSteps) -> Probes {
+        let mut probes = Probes {
+            slots: vec![],
+            found: None,
+            free: None,
+        };
+        for i in 0..self.capacity() {
+            let slot = self.probe(key, i);
+            probes.slots.push(slot);
+            let probe = self.describe_probe(key, i);
+            let highlights = Highlights::new().slot(slot, ElementState::Active);
+
+            match self.slots[slot].first() {
+                Some(other) if *other == key => {
+                    self.push(
+                        format!("{}. Slot {} has {}", probe, slot, key),
+                        highlights.key(key, ElementState::Selected),
+                        &probes.slots,
+                        steps,
+                    );
+                    probes.found = Some(slot);
+                    return probes;
+                }
+                Some(other) => {
+                    self.push(
+                        format!(
+                            "{}. Slot {} already has {}, so the keys collide",
+                            probe, slot, other
+                        ),
+                        highlights.key(*other, ElementState::Conflict),
+                        &probes.slots,
+                        steps,
+                    );
+                }
+                None if self.deleted.contains(&slot) => {
+                    self.push(
+                        format!(
+                            "{}. The key of slot {} was deleted, so the probes continue past it",
+                            probe, slot
+                        ),
+                        highlights,
+                        &probes.slots,
+                        steps,
+                    );
+                    probes.free = probes.free.or(Some(slot));
+                }
+                None => {
+                    self.push(
+                        format!(
+                            "{}. Slot {} is empty, so {} can't be further along",
+                            probe, slot, key
+                        ),
+                        highlights,
+                        &probes.slots,
+                        steps,
+                    );
+                    probes.free = probes.free.or(Some(slot));
+                    return probes;
+                }
+            }
+        }
+        probes
+    }
+    /// Looks for the key in the chain of its slot in a table that uses separate chaining.
+    /// Returns the slot of the key if it is in the table.
+    fn chain_steps(&self, key: i64, steps: &mut DiagramSteps) -> Option<usize> {
+        let slot = self.hash(key);
+        let chain = &self.slots[slot];
+        self.push(
+            format!(
+                "{}, so {} is in the chain of slot {} if it is in the table",
+                self.describe_probe(key, 0),
+                key,
+                slot
+            ),
+            Highlights::new().slot(slot, ElementState::Active),
+            &[],
+            steps,
+        );
+        for other in chain {
+            let highlights = Highlights::new().slot(slot, ElementState::Frontier);
+            if *other == key {
+                self.push(
+                    format!("Found {} in the chain", key),
+                    highlights.key(key, ElementState::Selected),
+                    &[],
+                    steps,
+                );
+                return Some(slot);
+            }
+            self.push(
+                format!("{} ≠ {}, so go to the next key of the chain", other, key),
+                highlights.key(*other, ElementState::Active),
+                &[],
+                steps,
+            );
+        }
+        self.push(
+            format!("The chain doesn't have {}", key),
+            Highlights::new().slot(slot, ElementState::Frontier),
+            &[],
+            steps,
+        );
+        None
+    }
+    fn insert(&mut self, key: i64, steps: &mut DiagramSteps) -> Result<(), String> {
+        if !(0..=MAX_KEY).contains(&key) {
+            return Err(format!("The keys must be between 0 and {}", MAX_KEY));
+        }
+        let key_count = self.keys().len();
+        if key_count >= self.strategy.max_keys() && !self.keys().contains(&key) {
+            return Err(format!(
+                "The table can have at most {} keys with this collision strategy",
+                self.strategy.max_keys()
+            ));
+        }
+        let load = (key_count + 1) as f32 / self.capacity() as f32;
+        if load > self.strategy.max_load() && !self.keys().contains(&key) {
+            self.grow(load, steps);
+        }
+
+        if self.strategy == Strategy::Chaining {
+            if let Some(slot) = self.chain_steps(key, steps) {
+                self.push(
+                    format!("{} is already in the table", key),
+                    Highlights::new()
+                        .slot(slot, ElementState::Frontier)
+                        .key(key, ElementState::Conflict),
+                    &[],
+                    steps,
+                );
+                return Ok(());
+            }
+            let slot = self.hash(key);
+            self.slots[slot].push(key);
+            self.push(
+                format!("Add {} to the end of the chain of slot {}", key, slot),
+                Highlights::new()
+                    .slot(slot, ElementState::Frontier)
+                    .key(key, ElementState::Selected),
+                &[],
+                steps,
+            );
+            return Ok(());
+        }
+
+        let probes = self.probe_steps(key, steps);
+        if let Some(slot) = probes.found {
+            self.push(
+                format!("{} is already in the table", key),
+                Highlights::new()
+                    .slot(slot, ElementState::Frontier)
+                    .key(key, ElementState::Conflict),
+                &probes.slots,
+                steps,
+            );
+            return Ok(());
+        }
+        let Some(slot) = probes.free else {
+            return Err(format!("The probes for {} didn't find a free slot", key));
+        };
+        self.slots[slot].push(key);
+        self.deleted.remove(&slot);
+        self.push(
+            format!(
+                "Place {} in slot {} after {} probe{}",
+                key,
+                slot,
+                probes.slots.len(),
+                if probes.slots.len() == 1 { "" } else { "s" }
+            ),
+            Highlights::new()
+                .slot(slot, ElementState::Frontier)
+                .key(key, ElementState::Selected),
+            &probes.slots,
+            steps,
+        );
+        Ok(())
+    }
+    /// Moves the keys to a table with the next capacity one by one.
+    fn grow(&mut self, load: f32, steps: &mut DiagramSteps) {
+        let capacity = CAPACITIES
+            .into_iter()
+            .find(|capacity| *capacity > self.capacity())
+            .unwrap_or(self.capacity());
+        let mut pending = self.keys();
+        self.slots = vec![vec![]; capacity];
+        self.deleted.clear();
+        steps.push(
+            format!(
+                "The load factor would be {:.2}, which is over {}, so the keys are moved to a new table with {} slots",
+                load,
+                self.strategy.max_load(),
+                capacity
+            ),
+            self.draw(&Highlights::new(), &pending),
+        );
+
+        while !pending.is_empty() {
+            let key = pending.remove(0);
+            let slot = (0..capacity)
+                .map(|i| self.probe(key, i))
+                .find(|slot| self.strategy == Strategy::Chaining || self.slots[*slot].is_empty())
+                .unwrap_or_else(|| self.hash(key));
+            self.slots[slot].push(key);
+            steps.push(
+                format!(
+                    "Rehash {}: h({}) = {} mod {} = {}{}",
+                    key,
+                    key,
+                    key,
+                    capacity,
+                    self.hash(key),
+                    if slot == self.hash(key) {
+                        String::new()
+                    } else {
+                        format!(", and it goes to slot {} after a collision", slot)
+                    }
+                ),
+                self.draw(
+                    &Highlights::new()
+                        .slot(slot, ElementState::Frontier)
+                        .key(key, ElementState::Selected),
+                    &pending,
+                ),
+            );
+        }
+    }
+    fn search(&self, key: i64, steps: &mut DiagramSteps) {
+        let found = if self.strategy == Strategy::Chaining {
+            self.chain_steps(key, steps).is_some()
+        } else {
+            self.probe_steps(key, steps).found.is_some()
+        };
+        if !found {
+            self.push(
+                format!("{} isn't in the table", key),
+                Highlights::new(),
+                &[],
+                steps,
+            );
+        }
+    }
+    fn delete(&mut self, key: i64, steps: &mut DiagramSteps) {
+        let (slot, probes) = if self.strategy == Strategy::Chaining {
+            (self.chain_steps(key, steps), vec![])
+        } else {
+            let probes = self.probe_steps(key, steps);
+            (probes.found, probes.slots)
+        };
+        let Some(slot) = slot else {
+            self.push(
+                format!("{} isn't in the table", key),
+                Highlights::new(),
+                &probes,
+                steps,
+            );
+            return;
+        };
+
+        self.slots[slot].retain(|other| *other != key);
+        if self.strategy == Strategy::Chaining {
+            self.push(
+                format!("Remove {} from the chain of slot {}", key, slot),
+                Highlights::new().slot(slot, ElementState::Rejected),
+                &[],
+                steps,
+            );
+        } else {
+            self.deleted.insert(slot);
+            self.push(
+                format!(
+                    "Remove {} and mark slot {} as deleted, so that the probes for keys that collided with it continue past the slot",
+                    key, slot
+                ),
+                Highlights::new().slot(slot, ElementState::Rejected),
+                &probes,
+                steps,
+            );
+        }
+    }
+    /// Draws the table and a row of keys that are waiting to be inserted again.
+    fn draw(&self, highlights: &Highlights, pending: &[i64]) -> Diagram {
+        // Long chains make the diagram taller
+        let longest_chain = self.slots.iter().map(|keys| keys.len()).max().unwrap_or(0);
+        let height = TABLE_HEIGHT.max(SLOTS_Y + CHAIN_SPACING * longest_chain as f32 + 40.0);
+        let mut diagram = Diagram::new(TABLE_WIDTH, height);
+        let margin = NODE_RADIUS * 2.0;
+        let capacity = self.capacity();
+        let key_count = self.keys().len() + pending.len();
+        diagram.add_node(DiagramNode::text(
+            "info",
+            format!(
+                "h(k) = k mod {}. {} key{} in {} slots, load factor {:.2}",
+                capacity,
+                key_count,
+                if key_count == 1 { "" } else { "s" },
+                capacity,
+                key_count as f32 / capacity as f32
+            ),
+            (TABLE_WIDTH / 2.0, 24.0),
+        ));
+
+        let pending_width = 44.0;
+        let pending_left = (TABLE_WIDTH - pending_width * pending.len() as f32) / 2.0;
+        for (i, key) in pending.iter().enumerate() {
+            let position = (pending_left + pending_width * (i as f32 + 0.5), PENDING_Y);
+            let mut node = DiagramNode::rect(
+                format!("key-{}", key),
+                key,
+                position,
+                (pending_width - 6.0, KEY_HEIGHT),
+            );
+            node.state = highlights.keys.get(key).copied();
+            diagram.add_node(node);
+        }
+
+        let slot_width = ((TABLE_WIDTH - margin * 2.0) / capacity as f32).min(60.0);
+        let left = (TABLE_WIDTH - slot_width * capacity as f32) / 2.0;
+        for (slot, keys) in self.slots.iter().enumerate() {
+            let x = left + slot_width * (slot as f32 + 0.5);
+            let label = if self.deleted.contains(&slot) && keys.is_empty() {
+                "×"
+            } else {
+                ""
+            };
+            let mut slot_node = DiagramNode::rect(
+                format!("slot-{}", slot),
+                label,
+                (x, SLOTS_Y),
+                (slot_width - 2.0, KEY_HEIGHT + 10.0),
+            )
+            .with_note(slot);
+            slot_node.state = highlights.slots.get(&slot).copied();
+            diagram.add_node(slot_node);
+
+            let mut previous = format!("slot-{}", slot);
+            for (i, key) in keys.iter().enumerate() {
+                let key_key = format!("key-{}", key);
+                // With open addressing the key is drawn inside its slot, and chains hang below their slots
+                let y = match self.strategy {
+                    Strategy::Chaining => SLOTS_Y + CHAIN_SPACING * (i + 1) as f32,
+                    _ => SLOTS_Y,
+                };
+                let mut key_node =
+                    DiagramNode::rect(key_key.clone(), key, (x, y), (slot_width - 8.0, KEY_HEIGHT));
+                key_node.state = highlights.keys.get(key).copied();
+                diagram.add_node(key_node);
+                if self.strategy == Strategy::Chaining {
+                    diagram.add_edge(DiagramEdge::arrow(previous, key_key.clone()));
+                }
+                previous = key_key;
+            }
+        }
+        diagram
+    }
+}
+impl Visualizer for HashTable {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::new(), &[])
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let key_input = |label| Input::Number {
+            label,
+            default: 42,
+            min: 0,
+            max: MAX_KEY,
+        };
+        vec![
+            Operation::new("Insert", vec![key_input("Key to insert")]),
+            Operation::new("Search", vec![key_input("Key to search for")]),
+            Operation::new("Delete", vec![key_input("Key to delete")]),
+            Operation::new(
+                "Change strategy",
+                vec![Input::Choice {
+                    label: "Collision strategy",
+                    choices: COLLISION_STRATEGIES.map(String::from).to_vec(),
+                }],
+            ),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Insert" => self.insert(value.number(), &mut steps)?,
+            "Search" => self.search(value.number(), &mut steps),
+            "Delete" => self.delete(value.number(), &mut steps),
+            "Change strategy" => {
+                let strategy = value.text();
+                let keys = self.keys();
+                let max_keys = Strategy::from_name(&strategy).max_keys();
+                if keys.len() > max_keys {
+                    return Err(format!(
+                        "{} can only be used with at most {} keys",
+                        strategy, max_keys
+                    ));
+                }
+                *self = Self::from_keys(&strategy, &keys);
+                steps.push(
+                    format!(
+                        "Insert the keys into a new table that uses {}",
+                        strategy.to_lowercase()
+                    ),
+                    self.diagram(),
+                );
+            }
+            "Clear" => {
+                self.slots = vec![vec![]; CAPACITIES[0]];
+                self.deleted.clear();
+                steps.push("Remove all keys".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
@@ -0,0 +1,628 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use super::binary_tree::node_key;
+
+const LIST_WIDTH: f32 = 800.0;
+const LIST_HEIGHT: f32 = 400.0;
+const MAX_LENGTH: usize = 10;
+const NODE_SIZE: (f32, f32) = (50.0, 34.0);
+/// The y of the row of nodes.
+const ROW_Y: f32 = 180.0;
+/// How far below the row a new node is drawn before it is linked in.
+const NEW_NODE_OFFSET: f32 = 100.0;
+const LIST_TYPES: [&str; 2] = ["Singly linked", "Doubly linked"];
+
+#[derive(Clone, Debug, PartialEq)]
+struct ListNode {
+    value: i64,
+    next: Option<usize>,
+    prev: Option<usize>,
+    /// The horizontal place of the node, which stays the same while the pointers are rewired.
+    slot: f32,
+    /// True for a new node that isn't linked into the list yet.
+    lowered: bool,
+}
+
+/// The states of the nodes and pointers and the pointer variables of an operation at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    nodes: BTreeMap<usize, ElementState>,
+    /// The next pointers by the nodes that they start from.
+    next: BTreeMap<usize, ElementState>,
+    /// The previous pointers by the nodes that they start from.
+    prev: BTreeMap<usize, ElementState>,
+    /// The names and the nodes of the pointer variables, where `None` is NIL.
+    variables: Vec<(&'static str, Option<usize>)>,
+}
+impl Highlights {
+    fn new() -> Self {
+        Self::default()
+    }
+    fn node(mut self, id: usize, state: ElementState) -> Self {
+        self.nodes.insert(id, state);
+        self
+    }
+    fn next(mut self, id: usize, state: ElementState) -> Self {
+        self.next.insert(id, state);
+        self
+    }
+    fn prev(mut self, id: usize, state: ElementState) -> Self {
+        self.prev.insert(id, state);
+        self
+    }
+    fn variable(mut self, name: &'static str, id: Option<usize>) -> Self {
+        self.variables.push((name, id));
+        self
+    }
+}
+
+/// A list of nodes where every node points to the next one, and in a doubly linked list also to the previous one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkedList {
+    doubly: bool,
+    nodes: BTreeMap<usize, ListNode>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    next_id: usize,
+}
+impl LinkedList {
+    pub fn new(doubly: bool) -> Self {
+        Self {
+            doubly,
+            nodes: BTreeMap::new(),
+            head: None,
+            tail: None,
+            next_id: 0,
+        }
+    }
+    /// Makes a list of the values in order.
+    pub fn from_values(doubly: bool, values: &[i64]) -> Self {
+        let mut list = Self::new(doubly);
+        for value in values.iter().take(MAX_LENGTH) {
+            let id = list.next_id;
+            list.next_id += 1;
+            list.nodes.insert(
+                id,
+                ListNode {
+                    value: *value,
+                    next: None,
+                    prev: list.tail,
+                    slot: 0.0,
+                    lowered: false,
+                },
+            );
+            match list.tail {
+                Some(tail) => list.nodes.get_mut(&tail).unwrap().next = Some(id),
+                None => list.head = Some(id),
+            }
+            list.tail = Some(id);
+        }
+        list.update_slots();
+        list
+    }
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    fn value(&self, id: usize) -> i64 {
+        self.nodes[&id].value
+    }
+    fn name(&self, id: Option<usize>) -> String {
+        id.map_or("NIL".to_string(), |id| self.value(id).to_string())
+    }
+    fn node_mut(&mut self, id: usize) -> &mut ListNode {
+        self.nodes.get_mut(&id).unwrap()
+    }
+    /// Places the nodes in the order of the list.
+    fn update_slots(&mut self) {
+        let mut current = self.head;
+        let mut slot = 0.0;
+        while let Some(id) = current {
+            let node = self.node_mut(id);
+            node.slot = slot;
+            node.lowered = false;
+            slot += 1.0;
+            current = node.next;
+        }
+    }
+    fn push(&self, description: String, highlights: Highlights, steps: &mut DiagramSteps) {
+        steps.push(description, self.draw(&highlights));
+    }
+    fn check_index(&self, index: i64, max: usize) -> Result<usize, String> {
+        if index < 0 || index as usize > max {
+            return Err(format!("The index must be between 0 and {}", max));
+        }
+        Ok(index as usize)
+    }
+    /// Walks from the head to the node before the index. Returns `None` if the index is 0.
+    fn walk_to(&self, index: usize, steps: &mut DiagramSteps) -> Option<usize> {
+        if index == 0 {
+            return None;
+        }
+        let mut current = self.head.unwrap();
+        self.push(
+            format!(
+                "Start from the head {}, which is at index 0",
+                self.value(current)
+            ),
+            Highlights::new()
+                .node(current, ElementState::Active)
+                .variable("curr", Some(current)),
+            steps,
+        );
+        for i in 1..index {
+            let previous = current;
+            current = self.nodes[&current].next.unwrap();
+            self.push(
+                format!(
+                    "Follow the next pointer to {} at index {}",
+                    self.value(current),
+                    i
+                ),
+                Highlights::new()
+                    .node(current, ElementState::Active)
+                    .next(previous, ElementState::Frontier)
+                    .variable("curr", Some(current)),
+                steps,
+            );
+        }
+        Some(current)
+    }
+    fn insert(&mut self, value: i64, index: usize, steps: &mut DiagramSteps) -> Result<(), String> {
+        if self.len() >= MAX_LENGTH {
+            return Err(format!("The list can have at most {} nodes", MAX_LENGTH));
+        }
+        let before = self.walk_to(index, steps);
+        let after = before.map_or(self.head, |before| self.nodes[&before].next);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.nodes.insert(
+            id,
+            ListNode {
+                value,
+                next: None,
+                prev: None,
+                slot: index as f32 - 0.5,
+                lowered: true,
+            },
+        );
+        let base = Highlights::new()
+            .variable("curr", before)
+            .variable("new", Some(id));
+        self.push(
+            format!("Create a new node with {}", value),
+            base.clone().node(id, ElementState::Selected),
+            steps,
+        );
+
+        self.node_mut(id).next = after;
+        self.push(
+            format!(
+                "Point the next pointer of the new node to {}",
+                self.name(after)
+            ),
+            base.clone()
+                .node(id, ElementState::Selected)
+                .next(id, ElementState::Active),
+            steps,
+        );
+        if self.doubly {
+            self.node_mut(id).prev = before;
+            if before.is_some() {
+                self.push(
+                    format!(
+                        "Point the previous pointer of the new node to {}",
+                        self.name(before)
+                    ),
+                    base.clone()
+                        .node(id, ElementState::Selected)
+                        .prev(id, ElementState::Active),
+                    steps,
+                );
+            }
+            match after {
+                Some(after) => {
+                    self.node_mut(after).prev = Some(id);
+                    self.push(
+                        format!(
+                            "Point the previous pointer of {} back to the new node",
+                            self.value(after)
+                        ),
+                        base.clone()
+                            .node(id, ElementState::Selected)
+                            .prev(after, ElementState::Active),
+                        steps,
+                    );
+                }
+                None => {
+                    self.tail = Some(id);
+                    self.push(
+                        "Point the tail to the new node, since it is the last one".to_string(),
+                        base.clone().node(id, ElementState::Selected),
+                        steps,
+                    );
+                }
+            }
+        } else if after.is_none() {
+            self.tail = Some(id);
+        }
+        let description = match before {
+            Some(before) => {
+                self.node_mut(before).next = Some(id);
+                format!(
+                    "Point the next pointer of {} to the new node, which links it into the list",
+                    self.value(before)
+                )
+            }
+            None => {
+                self.head = Some(id);
+                "Point the head to the new node, which links it into the list".to_string()
+            }
+        };
+        let mut highlights = base.node(id, ElementState::Selected);
+        if let Some(before) = before {
+            highlights = highlights.next(before, ElementState::Active);
+        }
+        self.push(description, highlights, steps);
+
+        self.update_slots();
+        self.push(
+            format!("{} is now at index {}", value, index),
+            Highlights::new().node(id, ElementState::Selected),
+            steps,
+        );
+        Ok(())
+    }
+    fn delete(&mut self, index: usize, steps: &mut DiagramSteps) {
+        let before = self.walk_to(index, steps);
+        let id = before
+            .map_or(self.head, |before| self.nodes[&before].next)
+            .unwrap();
+        let after = self.nodes[&id].next;
+        let base = Highlights::new()
+            .node(id, ElementState::Rejected)
+            .variable("curr", before)
+            .variable("old", Some(id));
+        self.push(
+            format!(
+                "The node to remove is {} at index {}",
+                self.value(id),
+                index
+            ),
+            base.clone(),
+            steps,
+        );
+
+        let description = match before {
+            Some(before) => {
+                self.node_mut(before).next = after;
+                format!(
+                    "Point the next pointer of {} past the node to {}",
+                    self.value(before),
+                    self.name(after)
+                )
+            }
+            None => {
+                self.head = after;
+                format!("Point the head past the node to {}", self.name(after))
+            }
+        };
+        let mut highlights = base.clone();
+        if let Some(before) = before {
+            highlights = highlights.next(before, ElementState::Active);
+        }
+        self.push(description, highlights, steps);
+
+        match after {
+            Some(after) if self.doubly => {
+                self.node_mut(after).prev = before;
+                let description = match before {
+                    Some(before) => format!(
+                        "Point the previous pointer of {} back past the node to {}",
+                        self.value(after),
+                        self.value(before)
+                    ),
+                    None => format!(
+                        "{} is the new head, so its previous pointer is cleared",
+                        self.value(after)
+                    ),
+                };
+                self.push(description, base.prev(after, ElementState::Active), steps);
+            }
+            Some(_) => {}
+            None => {
+                self.tail = before;
+                if self.doubly {
+                    self.push(
+                        format!("Point the tail back to {}", self.name(before)),
+                        base,
+                        steps,
+                    );
+                }
+            }
+        }
+
+        let value = self.value(id);
+        self.nodes.remove(&id);
+        self.update_slots();
+        self.push(
+            format!("Nothing points to {} anymore, so it is removed", value),
+            Highlights::new(),
+            steps,
+        );
+    }
+    /// Reverses the list in place by turning its pointers around one node at a time.
+    fn reverse(&mut self, steps: &mut DiagramSteps) {
+        if self.doubly {
+            self.reverse_doubly(steps);
+        } else {
+            self.reverse_singly(steps);
+        }
+        std::mem::swap(&mut self.head, &mut self.tail);
+        let description = if self.doubly {
+            "Swap the head and the tail, so the list is reversed".to_string()
+        } else {
+            format!(
+                "Point the head to prev, {}, so the list is reversed",
+                self.name(self.head)
+            )
+        };
+        self.push(description, Highlights::new(), steps);
+        self.update_slots();
+        self.push(
+            "The nodes are now in the reversed order".to_string(),
+            Highlights::new(),
+            steps,
+        );
+    }
+    fn reverse_singly(&mut self, steps: &mut DiagramSteps) {
+        let mut prev = None;
+        let mut current = self.head;
+        self.push(
+            "Start with prev as NIL and curr at the head".to_string(),
+            Highlights::new()
+                .variable("prev", prev)
+                .variable("curr", current),
+            steps,
+        );
+        while let Some(id) = current {
+            let next = self.nodes[&id].next;
+            let variables = |highlights: Highlights, prev, current, next| {
+                highlights
+                    .variable("prev", prev)
+                    .variable("curr", current)
+                    .variable("next", next)
+            };
+            self.push(
+                format!("Remember the node after {} as next", self.value(id)),
+                variables(
+                    Highlights::new().node(id, ElementState::Active),
+                    prev,
+                    current,
+                    next,
+                ),
+                steps,
+            );
+            self.node_mut(id).next = prev;
+            self.push(
+                format!(
+                    "Turn the next pointer of {} around to point to prev, {}",
+                    self.value(id),
+                    self.name(prev)
+                ),
+                variables(
+                    Highlights::new()
+                        .node(id, ElementState::Active)
+                        .next(id, ElementState::Selected),
+                    prev,
+                    current,
+                    next,
+                ),
+                steps,
+            );
+            prev = current;
+            current = next;
+            self.push(
+                "Move prev to curr and curr to next".to_string(),
+                variables(Highlights::new(), prev, current, next),
+                steps,
+            );
+        }
+    }
+    fn reverse_doubly(&mut self, steps: &mut DiagramSteps) {
+        let mut current = self.head;
+        while let Some(id) = current {
+            let node = self.node_mut(id);
+            let next = node.next;
+            std::mem::swap(&mut node.next, &mut node.prev);
+            self.push(
+                format!("Swap the next and previous pointers of {}", self.value(id)),
+                Highlights::new()
+                    .node(id, ElementState::Active)
+                    .next(id, ElementState::Selected)
+                    .prev(id, ElementState::Selected)
+                    .variable("curr", current),
+                steps,
+            );
+            current = next;
+        }
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(LIST_WIDTH, LIST_HEIGHT);
+        let slots = self.len() as f32 + 2.0;
+        let spacing = ((LIST_WIDTH - NODE_RADIUS * 2.0) / slots).min(90.0);
+        let x = |slot: f32| LIST_WIDTH / 2.0 + (slot - (self.len() as f32 - 1.0) / 2.0) * spacing;
+        // NIL is drawn on both sides, since the pointers turn around when the list is reversed
+        let (nil_left, nil_right) = ("nil-left", "nil-right");
+        diagram.add_node(DiagramNode::text(nil_left, "NIL", (x(-1.0), ROW_Y)));
+        diagram.add_node(DiagramNode::text(
+            nil_right,
+            "NIL",
+            (x(self.len() as f32), ROW_Y),
+        ));
+        let nil = |slot: f32| {
+            if slot * 2.0 < self.len() as f32 - 1.0 {
+                nil_left
+            } else {
+                nil_right
+            }
+        };
+
+        for (id, node) in self.nodes.iter() {
+            let y = if node.lowered {
+                ROW_Y + NEW_NODE_OFFSET
+            } else {
+                ROW_Y
+            };
+            let mut diagram_node =
+                DiagramNode::rect(node_key(*id), node.value, (x(node.slot), y), NODE_SIZE);
+            diagram_node.state = highlights.nodes.get(id).copied();
+            diagram.add_node(diagram_node);
+
+            // Next pointers bend above the row and previous pointers below it
+            let to = node.next.map_or(nil(node.slot).to_string(), node_key);
+            let mut edge = DiagramEdge::arrow(node_key(*id), to);
+            if self.doubly {
+                edge = edge.with_bend(0.15);
+            }
+            edge.state = highlights.next.get(id).copied();
+            diagram.add_edge(edge);
+            if let Some(prev) = node.prev.filter(|_| self.doubly) {
+                let mut edge = DiagramEdge::arrow(node_key(*id), node_key(prev)).with_bend(0.15);
+                edge.state = highlights.prev.get(id).copied();
+                diagram.add_edge(edge);
+            }
+        }
+
+        // The head and the tail are below the row and the variables of the operation above it,
+        // stacked if they point to the same node. A new node that isn't linked yet has its pointers below it.
+        let mut pointers = vec![("head", self.head, true)];
+        if self.doubly {
+            pointers.push(("tail", self.tail, true));
+        }
+        pointers.extend(
+            highlights
+                .variables
+                .iter()
+                .map(|(name, id)| (*name, *id, false)),
+        );
+        let mut counts = BTreeMap::<(String, bool), usize>::new();
+        for (name, id, below) in pointers {
+            let (target, slot) = match id {
+                Some(id) => (node_key(id), self.nodes[&id].slot),
+                None if name == "prev" => (nil_left.to_string(), -1.0),
+                None => (nil_right.to_string(), self.len() as f32),
+            };
+            let lowered = id.is_some_and(|id| self.nodes[&id].lowered);
+            let count = counts
+                .entry((target.clone(), below || lowered))
+                .or_default();
+            let offset = 70.0 + 22.0 * *count as f32;
+            *count += 1;
+            let y = if lowered {
+                ROW_Y + NEW_NODE_OFFSET + offset - 15.0
+            } else if below {
+                ROW_Y + offset
+            } else {
+                ROW_Y - offset
+            };
+            let key = format!("pointer-{}", name);
+            diagram.add_node(DiagramNode::text(key.clone(), name, (x(slot), y)));
+            diagram.add_edge(DiagramEdge::arrow(key, target).dashed());
+        }
+        diagram
+    }
+}
+impl Visualizer for LinkedList {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let len = self.len() as i64;
+        vec![
+            Operation::new(
+                "Insert",
+                vec![
+                    Input::Number {
+                        label: "Value to insert",
+                        default: 42,
+                        min: -999,
+                        max: 999,
+                    },
+                    Input::Number {
+                        label: "Index to insert at",
+                        default: 2.min(len),
+                        min: 0,
+                        max: len,
+                    },
+                ],
+            ),
+            Operation::new(
+                "Delete",
+                vec![Input::Number {
+                    label: "Index to delete",
+                    default: 1.min(len - 1).max(0),
+                    min: 0,
+                    max: (len - 1).max(0),
+                }],
+            ),
+            Operation::new("Reverse", vec![]),
+            Operation::new(
+                "Change type",
+                vec![Input::Choice {
+                    label: "List type",
+                    choices: LIST_TYPES.map(String::from).to_vec(),
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let number = |index: usize| {
+            values
+                .get(index)
+                .map(|value| value.number())
+                .unwrap_or_default()
+        };
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Insert" => {
+                let index = self.check_index(number(1), self.len())?;
+                self.insert(number(0), index, &mut steps)?;
+            }
+            "Delete" => {
+                if self.len() == 0 {
+                    return Err("The list is empty".to_string());
+                }
+                let index = self.check_index(number(0), self.len() - 1)?;
+                self.delete(index, &mut steps);
+            }
+            "Reverse" => self.reverse(&mut steps),
+            "Change type" => {
+                let doubly = values
+                    .first()
+                    .is_some_and(|value| value.text() == LIST_TYPES[1]);
+                let mut values = vec![];
+                let mut current = self.head;
+                while let Some(id) = current {
+                    values.push(self.value(id));
+                    current = self.nodes[&id].next;
+                }
+                *self = Self::from_values(doubly, &values);
+                steps.push(
+                    format!(
+                        "Link the nodes {}",
+                        if doubly {
+                            "in both directions"
+                        } else {
+                            "only forward"
+                        }
+                    ),
+                    self.diagram(),
+                );
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+const CACHE_WIDTH: f32 = 800.0;
+const CACHE_HEIGHT: f32 = 380.0;
+const MAX_CAPACITY: usize = 8;
+const MAX_KEY: i64 = 99;
+/// The largest number of keys in an access sequence, so that the steps stay short enough to follow.
+const MAX_ACCESSES: usize = 20;
+const MAP_Y: f32 = 70.0;
+const LIST_Y: f32 = 250.0;
+const CELL_SIZE: (f32, f32) = (44.0, 30.0);
+const NODE_SIZE: (f32, f32) = (50.0, 34.0);
+
+/// The states of the entries of the hash map and the nodes of the list by their keys.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    map: BTreeMap<i64, ElementState>,
+    list: BTreeMap<i64, ElementState>,
+}
+impl Highlights {
+    fn new() -> Self {
+        Self::default()
+    }
+    fn both(key: i64, state: ElementState) -> Self {
+        Self {
+            map: BTreeMap::from([(key, state)]),
+            list: BTreeMap::from([(key, state)]),
+        }
+    }
+}
+
+/// A cache with a fixed capacity that evicts the least recently used key when it is full. A hash map finds the node
+/// of a key in a doubly linked list, which is kept in the order of the latest accesses.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LruCache {
+    capacity: usize,
+    /// The keys from the most recently used to the least recently used.
+    keys: Vec<i64>,
+}
+impl LruCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.clamp(1, MAX_CAPACITY),
+            keys: vec![],
+        }
+    }
+    /// Makes a cache by accessing the keys in order without keeping the steps.
+    pub fn from_accesses(capacity: usize, keys: &[i64]) -> Self {
+        let mut cache = Self::new(capacity);
+        for key in keys {
+            cache.access(*key, &mut vec![], &mut DiagramSteps::new());
+        }
+        cache
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: Highlights,
+        history: &[String],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(&highlights));
+        step.lists = vec![StepList::new("Accesses", history.to_vec())];
+    }
+    /// Accesses a key, which is a hit if it is in the cache and a miss otherwise. Returns true on a hit.
+    fn access(&mut self, key: i64, history: &mut Vec<String>, steps: &mut DiagramSteps) -> bool {
+        if let Some(index) = self.keys.iter().position(|k| *k == key) {
+            history.push(format!("{}: hit", key));
+            self.push(
+                format!("{} is in the hash map, so it's a hit", key),
+                Highlights::both(key, ElementState::Active),
+                history,
+                steps,
+            );
+            if index == 0 {
+                self.push(
+                    format!("{} is already the most recently used key at the head", key),
+                    Highlights::both(key, ElementState::Selected),
+                    history,
+                    steps,
+                );
+            } else {
+                self.keys.remove(index);
+                self.keys.insert(0, key);
+                self.push(
+                    format!(
+                        "Unlink the node of {} and move it to the head, since it's now the most recently used",
+                        key
+                    ),
+                    Highlights::both(key, ElementState::Selected),
+                    history,
+                    steps,
+                );
+            }
+            return true;
+        }
+
+        history.push(format!("{}: miss", key));
+        self.push(
+            format!("{} isn't in the hash map, so it's a miss", key),
+            Highlights::new(),
+            history,
+            steps,
+        );
+        if self.keys.len() >= self.capacity {
+            let evicted = *self.keys.last().unwrap();
+            self.push(
+                format!(
+                    "The cache is full, so evict the least recently used key {} from the tail",
+                    evicted
+                ),
+                Highlights::both(evicted, ElementState::Rejected),
+                history,
+                steps,
+            );
+            self.keys.pop();
+        }
+        self.keys.insert(0, key);
+        self.push(
+            format!(
+                "Add {} to the head of the list and its node to the hash map",
+                key
+            ),
+            Highlights::both(key, ElementState::Selected),
+            history,
+            steps,
+        );
+        false
+    }
+    fn play(&mut self, keys: &[i64], steps: &mut DiagramSteps) {
+        let mut history = vec![];
+        let hits = keys
+            .iter()
+            .filter(|key| self.access(**key, &mut history, steps))
+            .count();
+        self.push(
+            format!(
+                "The {} accesses had {} hits and {} misses, a hit rate of {:.0} %",
+                keys.len(),
+                hits,
+                keys.len() - hits,
+                hits as f32 / keys.len() as f32 * 100.0
+            ),
+            Highlights::new(),
+            &history,
+            steps,
+        );
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(CACHE_WIDTH, CACHE_HEIGHT);
+        let x = |index: usize, count: usize, spacing: f32| {
+            CACHE_WIDTH / 2.0 + spacing * (index as f32 - (count as f32 - 1.0) / 2.0)
+        };
+        diagram.add_node(DiagramNode::text(
+            "map-title",
+            "Hash map",
+            (x(0, 1, 0.0), MAP_Y - 45.0),
+        ));
+        diagram.add_node(DiagramNode::text(
+            "list-title",
+            "Recency list",
+            (x(0, 1, 0.0), LIST_Y - 75.0),
+        ));
+
+        // The hash map is in the order of the keys and the list in the order of recency
+        let mut sorted = self.keys.clone();
+        sorted.sort();
+        for (index, key) in sorted.iter().enumerate() {
+            let mut node = DiagramNode::rect(
+                map_key(*key),
+                key,
+                (x(index, sorted.len(), 60.0), MAP_Y),
+                CELL_SIZE,
+            );
+            node.state = highlights.map.get(key).copied();
+            diagram.add_node(node);
+            diagram.add_edge(DiagramEdge::arrow(map_key(*key), list_key(*key)).dashed());
+        }
+        for (index, key) in self.keys.iter().enumerate() {
+            let mut node = DiagramNode::rect(
+                list_key(*key),
+                key,
+                (x(index, self.keys.len(), 84.0), LIST_Y),
+                NODE_SIZE,
+            );
+            node.state = highlights.list.get(key).copied();
+            diagram.add_node(node);
+            if let Some(next) = self.keys.get(index + 1) {
+                diagram
+                    .add_edge(DiagramEdge::arrow(list_key(*key), list_key(*next)).with_bend(0.15));
+                diagram
+                    .add_edge(DiagramEdge::arrow(list_key(*next), list_key(*key)).with_bend(0.15));
+            }
+        }
+        for (name, index) in [("head", 0), ("tail", self.keys.len().max(1) - 1)] {
+            if index < self.keys.len() {
+                let key = format!("pointer-{}", name);
+                let y = LIST_Y + if name == "head" { 70.0 } else { 100.0 };
+                diagram.add_node(DiagramNode::text(
+                    &key,
+                    name,
+                    (x(index, self.keys.len(), 84.0), y),
+                ));
+                diagram.add_edge(DiagramEdge::arrow(key, list_key(self.keys[index])).dashed());
+            }
+        }
+        diagram.add_node(DiagramNode::text(
+            "capacity",
+            format!("{} of {} keys", self.keys.len(), self.capacity),
+            (CACHE_WIDTH - 70.0, MAP_Y - 45.0),
+        ));
+        diagram
+    }
+}
+impl Visualizer for LruCache {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Access",
+                vec![Input::Number {
+                    label: "Key to access",
+                    default: 4,
+                    min: 0,
+                    max: MAX_KEY,
+                }],
+            ),
+            Operation::new(
+                "Play accesses",
+                vec![Input::Text {
+                    label: "Access sequence",
+                    default: "1 2 3 1 4 5 2 1 6 3".to_string(),
+                }],
+            ),
+            Operation::new(
+                "Change capacity",
+                vec![Input::Number {
+                    label: "Capacity",
+                    default: self.capacity as i64,
+                    min: 1,
+                    max: MAX_CAPACITY as i64,
+                }],
+            ),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Access" => {
+                self.access(check_key(value.number())?, &mut vec![], &mut steps);
+            }
+            "Play accesses" => {
+                let keys = parse_keys(&value.text())?;
+                self.play(&keys, &mut steps);
+            }
+            "Change capacity" => {
+                let capacity = value.number();
+                if !(1..=MAX_CAPACITY as i64).contains(&capacity) {
+                    return Err(format!(
+                        "The capacity must be between 1 and {}",
+                        MAX_CAPACITY
+                    ));
+                }
+                *self = Self::new(capacity as usize);
+                steps.push(
+                    format!("Start with an empty cache that can hold {} keys", capacity),
+                    self.diagram(),
+                );
+            }
+            "Clear" => {
+                self.keys.clear();
+                steps.push("Remove all keys".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn map_key(key: i64) -> String {
+    format!("map-{}", key)
+}
+
+fn list_key(key: i64) -> String {
+    format!("node-{}", key)
+}
+
+fn check_key(key: i64) -> Result<i64, String> {
+    if !(0..=MAX_KEY).contains(&key) {
+        return Err(format!("The keys must be between 0 and {}", MAX_KEY));
+    }
+    Ok(key)
+}
+
+fn parse_keys(text: &str) -> Result<Vec<i64>, String> {
+    let keys = text
+        .split([',', ' '])
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| {
+            part.trim()
+                .parse::<i64>()
+                .ok()
+                .filter(|key| (0..=MAX_KEY).contains(key))
+                .ok_or_else(|| {
+                    format!(
+                        "\"{}\" isn't a whole number between 0 and {}",
+                        part.trim(),
+                        MAX_KEY
+                    )
+                })
+        })
+        .collect::<Result<Vec<i64>, String>>()?;
+    if keys.is_empty() || keys.len() > MAX_ACCESSES {
+        return Err(format!("Enter 1 to {} keys to access", MAX_ACCESSES));
+    }
+    Ok(keys)
+}
@@ -0,0 +1,45 @@
+//! A collection of data structures that can be visualized.
+mod avl_tree;
+mod b_tree;
+mod binary_heap;
+mod binary_search_tree;
+mod binary_tree;
+mod bloom_filter;
+mod cells;
+mod consistent_hashing;
+mod deque;
+mod fenwick_tree;
+mod hash_table;
+mod linked_list;
+mod lru_cache;
+mod queue;
+mod ranges;
+mod red_black_tree;
+mod segment_tree;
+mod skip_list;
+mod splay_tree;
+mod stack;
+mod treap;
+mod trie;
+mod union_find;
+
+pub use avl_tree::AvlTree;
+pub use b_tree::BTree;
+pub use binary_heap::BinaryHeap;
+pub use binary_search_tree::BinarySearchTree;
+pub use bloom_filter::BloomFilter;
+pub use consistent_hashing::ConsistentHashing;
+pub use deque::Deque;
+pub use fenwick_tree::FenwickTree;
+pub use hash_table::HashTable;
+pub use linked_list::LinkedList;
+pub use lru_cache::LruCache;
+pub use queue::Queue;
+pub use red_black_tree::RedBlackTree;
+pub use segment_tree::SegmentTree;
+pub use skip_list::SkipList;
+pub use splay_tree::SplayTree;
+pub use stack::Stack;
+pub use treap::Treap;
+pub use trie::Trie;
+pub use union_find::UnionFind;
@@ -0,0 +1,259 @@
+use diagram::*;
+
+use super::cells::*;
+
+/// The vertices of the graph that breadth-first search is shown on.
+const GRAPH_VERTICES: [(f32, f32); 8] = [
+    (160.0, 50.0),
+    (320.0, 50.0),
+    (480.0, 50.0),
+    (640.0, 50.0),
+    (160.0, 170.0),
+    (320.0, 170.0),
+    (480.0, 170.0),
+    (640.0, 170.0),
+];
+const GRAPH_EDGES: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 4),
+    (1, 2),
+    (1, 5),
+    (4, 5),
+    (2, 6),
+    (5, 6),
+    (2, 3),
+    (3, 7),
+    (6, 7),
+];
+/// The space above the queue for the graph.
+const GRAPH_HEIGHT: f32 = 220.0;
+
+/// The states of the vertices and edges of the graph during breadth-first search.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Search {
+    vertices: Vec<Option<ElementState>>,
+    /// The edges that vertices were discovered through.
+    tree_edges: Vec<(usize, usize)>,
+    visited: Vec<usize>,
+}
+
+/// A line of values where the first value that was enqueued is the first one that is dequeued.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Queue {
+    cells: Cells,
+}
+impl Queue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn from_values(values: &[i64]) -> Self {
+        Self {
+            cells: Cells::from_labels(values),
+        }
+    }
+    fn enqueue(&mut self, label: impl ToString, steps: &mut DiagramSteps) -> Result<(), String> {
+        if self.cells.is_full() {
+            return Err(format!("The queue can have at most {} values", MAX_CELLS));
+        }
+        let label = label.to_string();
+        let id = self.cells.push_back(&label);
+        steps.push(
+            format!("Enqueue {} at the back of the queue", label),
+            self.cells
+                .draw_row(&CellStates::from([(id, ElementState::Selected)]), 0.0),
+        );
+        Ok(())
+    }
+    fn dequeue(&mut self, steps: &mut DiagramSteps) -> Result<(), String> {
+        let front = self.cells.front().ok_or("The queue is empty")?.clone();
+        steps.push(
+            format!("Dequeue {} from the front of the queue", front.label),
+            self.cells
+                .draw_row(&CellStates::from([(front.id, ElementState::Rejected)]), 0.0),
+        );
+        self.cells.pop_front();
+        let description = match self.cells.front() {
+            Some(cell) => format!("{} is now at the front", cell.label),
+            None => "The queue is now empty".to_string(),
+        };
+        steps.push(description, self.diagram());
+        Ok(())
+    }
+    fn peek(&self, steps: &mut DiagramSteps) -> Result<(), String> {
+        let front = self.cells.front().ok_or("The queue is empty")?;
+        steps.push(
+            format!("{} is at the front of the queue", front.label),
+            self.cells
+                .draw_row(&CellStates::from([(front.id, ElementState::Active)]), 0.0),
+        );
+        Ok(())
+    }
+    fn draw_search(&self, search: &Search, states: &CellStates) -> Diagram {
+        let mut diagram = self.cells.draw_row(states, GRAPH_HEIGHT);
+        let key = |vertex: usize| format!("vertex-{}", vertex);
+        for (a, b) in GRAPH_EDGES {
+            let mut edge = DiagramEdge::new(key(a), key(b));
+            if search.tree_edges.contains(&(a, b)) || search.tree_edges.contains(&(b, a)) {
+                edge.state = Some(ElementState::Selected);
+            }
+            diagram.add_edge(edge);
+        }
+        for (vertex, position) in GRAPH_VERTICES.iter().enumerate() {
+            let mut node = DiagramNode::circle(key(vertex), vertex, *position);
+            node.state = search.vertices[vertex];
+            diagram.add_node(node);
+        }
+        diagram
+    }
+    fn push_search(
+        &self,
+        description: String,
+        search: &Search,
+        states: &CellStates,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw_search(search, states));
+        step.lists = vec![
+            StepList::new(
+                "Queue",
+                self.cells.iter().map(|cell| cell.label.clone()).collect(),
+            ),
+            StepList::new(
+                "Visited order",
+                search
+                    .visited
+                    .iter()
+                    .map(|vertex| vertex.to_string())
+                    .collect(),
+            ),
+        ];
+    }
+    /// Visits the vertices of the graph in the order of their distance from the start, using the queue to remember
+    /// the vertices that were discovered but not visited yet.
+    fn breadth_first_search(
+        &mut self,
+        start: usize,
+        steps: &mut DiagramSteps,
+    ) -> Result<(), String> {
+        if start >= GRAPH_VERTICES.len() {
+            return Err(format!(
+                "The start vertex must be between 0 and {}",
+                GRAPH_VERTICES.len() - 1
+            ));
+        }
+        let neighbors = |vertex: usize| {
+            let mut neighbors = GRAPH_EDGES
+                .iter()
+                .filter_map(|(a, b)| match vertex {
+                    _ if *a == vertex => Some(*b),
+                    _ if *b == vertex => Some(*a),
+                    _ => None,
+                })
+                .collect::<Vec<usize>>();
+            neighbors.sort();
+            neighbors
+        };
+        let mut search = Search {
+            vertices: vec![None; GRAPH_VERTICES.len()],
+            ..Default::default()
+        };
+
+        self.cells.clear();
+        let id = self.cells.push_back(start);
+        search.vertices[start] = Some(ElementState::Frontier);
+        self.push_search(
+            format!(
+                "Enqueue the start vertex {} and mark it as discovered",
+                start
+            ),
+            &search,
+            &CellStates::from([(id, ElementState::Selected)]),
+            steps,
+        );
+        while let Some(cell) = self.cells.pop_front() {
+            let vertex = cell.label.parse::<usize>().unwrap();
+            search.vertices[vertex] = Some(ElementState::Active);
+            search.visited.push(vertex);
+            self.push_search(
+                format!("Dequeue {} from the front and visit it", vertex),
+                &search,
+                &CellStates::new(),
+                steps,
+            );
+            for neighbor in neighbors(vertex) {
+                if search.vertices[neighbor].is_some() {
+                    continue;
+                }
+                let id = self.cells.push_back(neighbor);
+                search.vertices[neighbor] = Some(ElementState::Frontier);
+                search.tree_edges.push((vertex, neighbor));
+                self.push_search(
+                    format!(
+                        "{} is a neighbor of {} that wasn't discovered yet, so enqueue it at the back",
+                        neighbor, vertex
+                    ),
+                    &search,
+                    &CellStates::from([(id, ElementState::Selected)]),
+                    steps,
+                );
+            }
+            search.vertices[vertex] = Some(ElementState::Selected);
+        }
+        self.push_search(
+            "The queue is empty, so every vertex that can be reached has been visited".to_string(),
+            &search,
+            &CellStates::new(),
+            steps,
+        );
+        Ok(())
+    }
+}
+impl Visualizer for Queue {
+    fn diagram(&self) -> Diagram {
+        self.cells.draw_row(&CellStates::new(), 0.0)
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Enqueue",
+                vec![Input::Number {
+                    label: "Value to enqueue",
+                    default: 42,
+                    min: 0,
+                    max: 99,
+                }],
+            ),
+            Operation::new("Dequeue", vec![]),
+            Operation::new("Peek", vec![]),
+            Operation::new(
+                "Breadth-first search",
+                vec![Input::Number {
+                    label: "Start vertex",
+                    default: 0,
+                    min: 0,
+                    max: GRAPH_VERTICES.len() as i64 - 1,
+                }],
+            ),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Enqueue" => self.enqueue(value.number(), &mut steps)?,
+            "Dequeue" => self.dequeue(&mut steps)?,
+            "Peek" => self.peek(&mut steps)?,
+            "Breadth-first search" => {
+                let start = usize::try_from(value.number()).unwrap_or(usize::MAX);
+                self.breadth_first_search(start, &mut steps)?;
+            }
+            "Clear" => {
+                self.cells.clear();
+                steps.push("Remove all values".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
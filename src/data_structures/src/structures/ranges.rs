@@ -0,0 +1,102 @@
+//! The arrays of range query trees, which are drawn as a row of cells below the tree where the range of a tree node
+//! can be shaded.
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+pub const RANGES_WIDTH: f32 = 800.0;
+pub const RANGES_HEIGHT: f32 = 420.0;
+/// The largest number of values in the array, so that the cells fit side by side.
+pub const MAX_ARRAY_SIZE: usize = 16;
+pub const MAX_ARRAY_VALUE: i64 = 99;
+/// The y of the row of cells.
+pub const ARRAY_Y: f32 = 370.0;
+/// The size of the cells and the tree nodes.
+pub const CELL_SIZE: (f32, f32) = (42.0, 30.0);
+const CELL_SPACING: f32 = 46.0;
+
+/// The x of the center of a cell in an array of `size` cells.
+pub fn cell_x(index: usize, size: usize) -> f32 {
+    RANGES_WIDTH / 2.0 + CELL_SPACING * (index as f32 - (size as f32 - 1.0) / 2.0)
+}
+
+/// The width of the cells from `start` to `end`, inclusive.
+pub fn range_width(start: usize, end: usize) -> f32 {
+    CELL_SPACING * (end - start) as f32 + CELL_SIZE.0
+}
+
+/// Draws the values as cells with their indices, which start from `first_index`.
+pub fn draw_array(
+    diagram: &mut Diagram,
+    values: &[i64],
+    first_index: usize,
+    states: &BTreeMap<usize, ElementState>,
+) {
+    for (index, value) in values.iter().enumerate() {
+        let mut node = DiagramNode::rect(
+            format!("cell-{}", index),
+            value,
+            (cell_x(index, values.len()), ARRAY_Y),
+            CELL_SIZE,
+        )
+        .with_note(index + first_index);
+        node.state = states.get(&index).copied();
+        diagram.add_node(node);
+    }
+}
+
+/// Shades the cells from `start` to `end`, inclusive. A dashed outline is drawn a bit further out, so that it stays
+/// visible around a shaded range.
+pub fn shade_range(
+    diagram: &mut Diagram,
+    (start, end): (usize, usize),
+    size: usize,
+    state: Option<ElementState>,
+    dashed: bool,
+) {
+    let padding = if dashed { 8.0 } else { 4.0 };
+    let mut figure = Figure::new(FigureKind::Rect {
+        position: (
+            cell_x(start, size) - CELL_SIZE.0 / 2.0 - padding,
+            ARRAY_Y - CELL_SIZE.1 / 2.0 - padding,
+        ),
+        size: (
+            range_width(start, end) + padding * 2.0,
+            CELL_SIZE.1 + padding * 2.0 + if dashed { 14.0 } else { 0.0 },
+        ),
+    });
+    figure.state = state;
+    if dashed {
+        figure = figure.dashed();
+    }
+    diagram.add_figure(figure);
+}
+
+/// Checks that a range of indices that start from `first_index` is in an array of `size` values.
+pub fn check_range(
+    start: i64,
+    end: i64,
+    size: usize,
+    first_index: usize,
+) -> Result<(usize, usize), String> {
+    let last = (size + first_index - 1) as i64;
+    if start < first_index as i64 || end > last || start > end {
+        return Err(format!(
+            "The range must be between {} and {} with the start before the end",
+            first_index, last
+        ));
+    }
+    Ok((start as usize - first_index, end as usize - first_index))
+}
+
+/// Checks that an index that starts from `first_index` is in an array of `size` values.
+pub fn check_index(index: i64, size: usize, first_index: usize) -> Result<usize, String> {
+    let last = (size + first_index - 1) as i64;
+    if index < first_index as i64 || index > last {
+        return Err(format!(
+            "The index must be between {} and {}",
+            first_index, last
+        ));
+    }
+    Ok(index as usize - first_index)
+}
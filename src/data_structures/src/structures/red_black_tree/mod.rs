@@ -0,0 +1,440 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use super::binary_tree::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Color {
+    Red,
+    Black,
+}
+
+/// A binary search tree whose nodes are red or black. The root is black, red nodes don't have red children and
+/// every path from a node down to an empty child goes through the same number of black nodes, which keeps the
+/// longest path at most twice as long as the shortest one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RedBlackTree {
+    tree: BinaryTree,
+    /// The colors of the nodes. Nodes without a color are red, since new nodes start out red.
+    colors: BTreeMap<usize, Color>,
+}
+impl RedBlackTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Makes a tree by inserting the values in order without keeping the steps.
+    pub fn from_values(values: &[i64]) -> Self {
+        let mut tree = Self::new();
+        for value in values {
+            let _ = tree.insert(*value, &mut DiagramSteps::new());
+        }
+        tree
+    }
+    /// The color of a node, where empty children are black.
+    fn color(&self, id: Option<usize>) -> Color {
+        id.map_or(Color::Black, |id| {
+            self.colors.get(&id).copied().unwrap_or(Color::Red)
+        })
+    }
+    fn is_red(&self, id: Option<usize>) -> bool {
+        self.color(id) == Color::Red
+    }
+    fn parent(&self, id: usize) -> Option<usize> {
+        self.tree.parent(id).map(|(parent, _)| parent)
+    }
+    fn name(&self, id: Option<usize>) -> String {
+        id.map_or("the empty child".to_string(), |id| {
+            self.tree.value(id).to_string()
+        })
+    }
+    fn push(&self, description: String, highlights: TreeHighlights, steps: &mut DiagramSteps) {
+        steps.push(description, draw(&self.tree, &self.colors, &highlights));
+    }
+    fn rotate(&mut self, id: usize, direction: Side, reason: String, steps: &mut DiagramSteps) {
+        let colors = &self.colors;
+        self.tree
+            .rotate_steps(id, direction, reason, steps, &|tree, highlights| {
+                draw(tree, colors, highlights)
+            });
+    }
+    fn insert(&mut self, value: i64, steps: &mut DiagramSteps) -> Result<(), String> {
+        let colors = &self.colors;
+        let Some(mut node) = self.tree.insert_steps(value, steps, &|tree, highlights| {
+            draw(tree, colors, highlights)
+        })?
+        else {
+            return Ok(());
+        };
+        self.colors.insert(node, Color::Red);
+
+        loop {
+            let Some(parent) = self.parent(node) else {
+                if self.color(Some(node)) == Color::Red {
+                    self.colors.insert(node, Color::Black);
+                    self.push(
+                        format!(
+                            "{} is the root, so it is colored black",
+                            self.name(Some(node))
+                        ),
+                        TreeHighlights::new().node(node, ElementState::Active),
+                        steps,
+                    );
+                }
+                return Ok(());
+            };
+            if !self.is_red(Some(parent)) {
+                self.push(
+                    format!(
+                        "The parent of {} is black, so there are no red nodes in a row",
+                        self.name(Some(node))
+                    ),
+                    TreeHighlights::new()
+                        .node(node, ElementState::Selected)
+                        .node(parent, ElementState::Active),
+                    steps,
+                );
+                return Ok(());
+            }
+
+            // The parent is red, so it isn't the root and there is a grandparent
+            let (grandparent, parent_side) = self.tree.parent(parent).unwrap();
+            let uncle = self.tree.child(grandparent, parent_side.opposite());
+            let violation = TreeHighlights::new()
+                .node(node, ElementState::Conflict)
+                .node(parent, ElementState::Conflict)
+                .edge((parent, node), ElementState::Conflict);
+
+            if self.is_red(uncle) {
+                let uncle = uncle.unwrap();
+                self.push(
+                    format!(
+                        "{} and its parent {} are both red, and so is the uncle {}",
+                        self.name(Some(node)),
+                        self.name(Some(parent)),
+                        self.name(Some(uncle))
+                    ),
+                    violation.node(uncle, ElementState::Frontier),
+                    steps,
+                );
+                self.colors.insert(parent, Color::Black);
+                self.colors.insert(uncle, Color::Black);
+                self.colors.insert(grandparent, Color::Red);
+                self.push(
+                    format!(
+                        "Recolor {} and {} black and the grandparent {} red. {} may now break the rules with its own parent",
+                        self.name(Some(parent)),
+                        self.name(Some(uncle)),
+                        self.name(Some(grandparent)),
+                        self.name(Some(grandparent))
+                    ),
+                    TreeHighlights::new()
+                        .node(parent, ElementState::Active)
+                        .node(uncle, ElementState::Active)
+                        .node(grandparent, ElementState::Active),
+                    steps,
+                );
+                node = grandparent;
+                continue;
+            }
+
+            let uncle = uncle.map_or(
+                "the uncle is empty, which counts as black".to_string(),
+                |uncle| format!("the uncle {} is black", self.name(Some(uncle))),
+            );
+            self.push(
+                format!(
+                    "{} and its parent {} are both red, but {}, so the nodes are rotated",
+                    self.name(Some(node)),
+                    self.name(Some(parent)),
+                    uncle
+                ),
+                violation,
+                steps,
+            );
+            let mut parent = parent;
+            let node_side = self.tree.parent(node).unwrap().1;
+            if node_side != parent_side {
+                self.rotate(
+                    parent,
+                    parent_side,
+                    format!(
+                        "{} is an inner grandchild of {}, so it is moved to the outside first",
+                        self.name(Some(node)),
+                        self.name(Some(grandparent))
+                    ),
+                    steps,
+                );
+                // The old parent is now the child of the node
+                std::mem::swap(&mut node, &mut parent);
+            }
+            self.colors.insert(parent, Color::Black);
+            self.colors.insert(grandparent, Color::Red);
+            self.push(
+                format!(
+                    "Recolor {} black and {} red",
+                    self.name(Some(parent)),
+                    self.name(Some(grandparent))
+                ),
+                TreeHighlights::new()
+                    .node(parent, ElementState::Active)
+                    .node(grandparent, ElementState::Active),
+                steps,
+            );
+            self.rotate(
+                grandparent,
+                parent_side.opposite(),
+                format!(
+                    "{} is now black and can take the place of {}",
+                    self.name(Some(parent)),
+                    self.name(Some(grandparent))
+                ),
+                steps,
+            );
+            return Ok(());
+        }
+    }
+    fn delete(&mut self, value: i64, steps: &mut DiagramSteps) {
+        // Find the node that will be removed, which is the successor if the node with the value has two children
+        let (path, side) = self.tree.search_path(value);
+        let removed = match (path.last(), side) {
+            (Some(target), None) => {
+                let node = &self.tree.nodes[target];
+                match (node.left, node.right) {
+                    (Some(_), Some(right)) => {
+                        let mut successor = right;
+                        while let Some(left) = self.tree.nodes[&successor].left {
+                            successor = left;
+                        }
+                        Some(successor)
+                    }
+                    _ => Some(*target),
+                }
+            }
+            _ => None,
+        };
+        let removed_info = removed.map(|removed| {
+            let node = &self.tree.nodes[&removed];
+            (
+                self.color(Some(removed)),
+                node.left.or(node.right),
+                self.tree.parent(removed).map(|(_, side)| side),
+            )
+        });
+
+        let colors = &self.colors;
+        let Some(parent) = self.tree.delete_steps(value, steps, &|tree, highlights| {
+            draw(tree, colors, highlights)
+        }) else {
+            return;
+        };
+        let (removed_color, child, side) = removed_info.unwrap();
+        self.colors.remove(&removed.unwrap());
+
+        if removed_color == Color::Red {
+            self.push(
+                "The removed node was red, so the numbers of black nodes on the paths didn't change".to_string(),
+                TreeHighlights::new(),
+                steps,
+            );
+            return;
+        }
+        if self.is_red(child) {
+            let child = child.unwrap();
+            self.colors.insert(child, Color::Black);
+            self.push(
+                format!(
+                    "The removed node was black, so its red child {} is colored black to make up for it",
+                    self.name(Some(child))
+                ),
+                TreeHighlights::new().node(child, ElementState::Active),
+                steps,
+            );
+            return;
+        }
+        self.fix_double_black(child, parent, side, steps);
+    }
+    /// Fixes a path that has one black node too few after removing a black node. The missing black is thought of as
+    /// an extra black on `node`, which is then "double black". `side` is the side of the parent that the node is on.
+    fn fix_double_black(
+        &mut self,
+        mut node: Option<usize>,
+        mut parent: Option<usize>,
+        mut side: Option<Side>,
+        steps: &mut DiagramSteps,
+    ) {
+        let double_black = |node: Option<usize>| {
+            node.map_or(TreeHighlights::new(), |node| {
+                TreeHighlights::new().node(node, ElementState::Conflict)
+            })
+        };
+
+        while let (Some(parent_id), Some(node_side)) = (parent, side) {
+            if self.is_red(node) {
+                break;
+            }
+            let mut sibling = self.tree.child(parent_id, node_side.opposite()).unwrap();
+            self.push(
+                format!(
+                    "The paths through {} have one black node too few, so it is double black",
+                    self.name(node)
+                ),
+                double_black(node)
+                    .node(parent_id, ElementState::Frontier)
+                    .node(sibling, ElementState::Frontier),
+                steps,
+            );
+
+            if self.is_red(Some(sibling)) {
+                self.colors.insert(sibling, Color::Black);
+                self.colors.insert(parent_id, Color::Red);
+                self.rotate(
+                    parent_id,
+                    node_side,
+                    format!(
+                        "The sibling {} is red, so it is recolored black and {} red",
+                        self.name(Some(sibling)),
+                        self.name(Some(parent_id))
+                    ),
+                    steps,
+                );
+                sibling = self.tree.child(parent_id, node_side.opposite()).unwrap();
+            }
+
+            let near = self.tree.child(sibling, node_side);
+            let far = self.tree.child(sibling, node_side.opposite());
+
+            if !self.is_red(near) && !self.is_red(far) {
+                self.colors.insert(sibling, Color::Red);
+                self.push(
+                    format!(
+                        "The sibling {} and its children are black, so {} is recolored red and the extra black moves up to {}",
+                        self.name(Some(sibling)),
+                        self.name(Some(sibling)),
+                        self.name(Some(parent_id))
+                    ),
+                    TreeHighlights::new()
+                        .node(sibling, ElementState::Active)
+                        .node(parent_id, ElementState::Conflict),
+                    steps,
+                );
+                node = Some(parent_id);
+                let grandparent = self.tree.parent(parent_id);
+                parent = grandparent.map(|(grandparent, _)| grandparent);
+                side = grandparent.map(|(_, side)| side);
+                continue;
+            }
+
+            if !self.is_red(far) {
+                let near = near.unwrap();
+                self.colors.insert(near, Color::Black);
+                self.colors.insert(sibling, Color::Red);
+                self.rotate(
+                    sibling,
+                    node_side.opposite(),
+                    format!(
+                        "The near child {} of the sibling is red but the far child is black, so {} is recolored black and {} red",
+                        self.name(Some(near)),
+                        self.name(Some(near)),
+                        self.name(Some(sibling))
+                    ),
+                    steps,
+                );
+                sibling = near;
+            }
+
+            let far = self.tree.child(sibling, node_side.opposite()).unwrap();
+            let parent_color = self.color(Some(parent_id));
+            self.colors.insert(sibling, parent_color);
+            self.colors.insert(parent_id, Color::Black);
+            self.colors.insert(far, Color::Black);
+            self.rotate(
+                parent_id,
+                node_side,
+                format!(
+                    "The far child {} of the sibling is red. {} takes the color of {}, and {} and {} are recolored black",
+                    self.name(Some(far)),
+                    self.name(Some(sibling)),
+                    self.name(Some(parent_id)),
+                    self.name(Some(parent_id)),
+                    self.name(Some(far))
+                ),
+                steps,
+            );
+            self.push(
+                "The extra black went to the new black node above it, so the tree is balanced"
+                    .to_string(),
+                TreeHighlights::new().node(sibling, ElementState::Selected),
+                steps,
+            );
+            return;
+        }
+
+        match node {
+            Some(node) if self.is_red(Some(node)) || parent.is_none() => {
+                let recolor = self.is_red(Some(node));
+                self.colors.insert(node, Color::Black);
+                let description = if recolor {
+                    format!(
+                        "{} is red, so it is colored black to make up for the missing black node",
+                        self.name(Some(node))
+                    )
+                } else {
+                    format!(
+                        "The extra black reached the root {}, where it can be dropped",
+                        self.name(Some(node))
+                    )
+                };
+                self.push(
+                    description,
+                    TreeHighlights::new().node(node, ElementState::Active),
+                    steps,
+                );
+            }
+            _ => {}
+        }
+    }
+}
+impl Visualizer for RedBlackTree {
+    fn diagram(&self) -> Diagram {
+        draw(&self.tree, &self.colors, &TreeHighlights::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        search_tree_operations()
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        let colors = &self.colors;
+        let draw = |tree: &BinaryTree, highlights: &TreeHighlights| draw(tree, colors, highlights);
+        match operation {
+            "Insert" => self.insert(value.number(), &mut steps)?,
+            "Search" => {
+                self.tree.search_steps(value.number(), &mut steps, &draw);
+            }
+            "Delete" => self.delete(value.number(), &mut steps),
+            "Traverse" => return self.tree.traverse(&value.text(), &draw),
+            "Clear" => {
+                *self = Self::new();
+                steps.push("Remove all values".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn draw(
+    tree: &BinaryTree,
+    colors: &BTreeMap<usize, Color>,
+    highlights: &TreeHighlights,
+) -> Diagram {
+    let mut diagram = tree.draw(highlights);
+    for (id, node) in tree.nodes.keys().zip(diagram.nodes.iter_mut()) {
+        node.fill = match colors.get(id) {
+            Some(Color::Black) => Fill::Black,
+            _ => Fill::Red,
+        };
+    }
+    diagram
+}
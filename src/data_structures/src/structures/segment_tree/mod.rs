@@ -0,0 +1,489 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use diagram::*;
+
+use super::ranges::*;
+
+const QUERY_TYPES: [&str; 2] = ["Sum", "Minimum"];
+const LEVEL_HEIGHT: f32 = 65.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum QueryType {
+    Sum,
+    Minimum,
+}
+impl QueryType {
+    fn from_name(name: &str) -> Self {
+        if name == QUERY_TYPES[1] {
+            QueryType::Minimum
+        } else {
+            QueryType::Sum
+        }
+    }
+    fn name(&self) -> &'static str {
+        match self {
+            QueryType::Sum => "sum",
+            QueryType::Minimum => "minimum",
+        }
+    }
+    fn combine(&self, a: i64, b: i64) -> i64 {
+        match self {
+            QueryType::Sum => a + b,
+            QueryType::Minimum => a.min(b),
+        }
+    }
+    /// How the value of a node is computed from the values of its children.
+    fn describe(&self, a: i64, b: i64) -> String {
+        match self {
+            QueryType::Sum => format!("{} + {} = {}", a, b, a + b),
+            QueryType::Minimum => format!("min({}, {}) = {}", a, b, a.min(b)),
+        }
+    }
+}
+
+/// The states of the nodes and cells and the shaded ranges at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    nodes: BTreeMap<usize, ElementState>,
+    cells: BTreeMap<usize, ElementState>,
+    /// The range of the current node.
+    range: Option<((usize, usize), ElementState)>,
+    /// The range of a query, which is outlined.
+    query: Option<(usize, usize)>,
+    /// Nodes whose values aren't computed yet.
+    pending: BTreeSet<usize>,
+}
+impl Highlights {
+    fn new() -> Self {
+        Self::default()
+    }
+    fn with_range(&self, range: (usize, usize), state: ElementState) -> Self {
+        Self {
+            range: Some((range, state)),
+            ..self.clone()
+        }
+    }
+}
+
+/// A binary tree over an array where every node stores the sum or the minimum of a range of the array, and the
+/// children of a node split its range in half. Any range is covered by *O(log n)* nodes, so range queries and
+/// updates of single values take *O(log n)* time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SegmentTree {
+    values: Vec<i64>,
+    /// The values of the nodes, where the root is 1 and the children of node *i* are *2i* and *2i + 1*.
+    nodes: Vec<i64>,
+    query_type: QueryType,
+}
+impl SegmentTree {
+    /// Builds a tree for sums over the values, or over a single 0 if there are no values.
+    pub fn new(values: &[i64]) -> Self {
+        let values = if values.is_empty() { &[0] } else { values };
+        let mut tree = Self {
+            values: vec![],
+            nodes: vec![],
+            query_type: QueryType::Sum,
+        };
+        tree.build(values, &mut DiagramSteps::new());
+        tree
+    }
+    /// All nodes of the tree with their ranges and depths.
+    fn ranges(&self) -> Vec<(usize, (usize, usize), usize)> {
+        let mut ranges = vec![];
+        let mut stack = vec![(1, (0, self.values.len() - 1), 0)];
+        while let Some((id, (start, end), depth)) = stack.pop() {
+            ranges.push((id, (start, end), depth));
+            if start < end {
+                let middle = (start + end) / 2;
+                stack.push((id * 2, (start, middle), depth + 1));
+                stack.push((id * 2 + 1, (middle + 1, end), depth + 1));
+            }
+        }
+        ranges
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &Highlights,
+        lists: Vec<StepList>,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = lists;
+    }
+    fn build(&mut self, values: &[i64], steps: &mut DiagramSteps) {
+        self.values = values.to_vec();
+        self.nodes = vec![0; self.values.len() * 4];
+        let mut highlights = Highlights {
+            pending: self.ranges().into_iter().map(|(id, _, _)| id).collect(),
+            ..Default::default()
+        };
+        self.push(
+            format!(
+                "Every node stores the {} of its range, computed from the leaves up",
+                self.query_type.name()
+            ),
+            &highlights,
+            vec![],
+            steps,
+        );
+        self.build_node(1, (0, self.values.len() - 1), &mut highlights, steps);
+        self.push(
+            format!(
+                "The tree is built, and the root has the {} of the whole array",
+                self.query_type.name()
+            ),
+            &Highlights::new(),
+            vec![],
+            steps,
+        );
+    }
+    fn build_node(
+        &mut self,
+        id: usize,
+        (start, end): (usize, usize),
+        highlights: &mut Highlights,
+        steps: &mut DiagramSteps,
+    ) {
+        let description = if start == end {
+            self.nodes[id] = self.values[start];
+            format!(
+                "The leaf of index {} is the value {}",
+                start, self.values[start]
+            )
+        } else {
+            let middle = (start + end) / 2;
+            self.build_node(id * 2, (start, middle), highlights, steps);
+            self.build_node(id * 2 + 1, (middle + 1, end), highlights, steps);
+            let (left, right) = (self.nodes[id * 2], self.nodes[id * 2 + 1]);
+            self.nodes[id] = self.query_type.combine(left, right);
+            format!(
+                "{} is the {} of its children, {}",
+                range_name(start, end),
+                self.query_type.name(),
+                self.query_type.describe(left, right)
+            )
+        };
+        highlights.pending.remove(&id);
+        let mut step_highlights = highlights.with_range((start, end), ElementState::Active);
+        step_highlights.nodes.insert(id, ElementState::Selected);
+        self.push(description, &step_highlights, vec![], steps);
+    }
+    fn update(&mut self, index: usize, value: i64, steps: &mut DiagramSteps) {
+        let mut highlights = Highlights::new();
+        highlights.cells.insert(index, ElementState::Selected);
+        let mut path = vec![];
+        let (mut id, mut start, mut end) = (1, 0, self.values.len() - 1);
+        while start < end {
+            path.push((id, (start, end)));
+            highlights.nodes.insert(id, ElementState::Frontier);
+            let middle = (start + end) / 2;
+            let side = if index <= middle {
+                id *= 2;
+                end = middle;
+                "left"
+            } else {
+                id = id * 2 + 1;
+                start = middle + 1;
+                "right"
+            };
+            self.push(
+                format!(
+                    "Index {} is in the {} half of {}, so go down to {}",
+                    index,
+                    side,
+                    range_name(path.last().unwrap().1 .0, path.last().unwrap().1 .1),
+                    range_name(start, end)
+                ),
+                &highlights.with_range((start, end), ElementState::Active),
+                vec![],
+                steps,
+            );
+        }
+
+        self.values[index] = value;
+        self.nodes[id] = value;
+        highlights.nodes.insert(id, ElementState::Selected);
+        self.push(
+            format!("Set the leaf of index {} to {}", index, value),
+            &highlights.with_range((index, index), ElementState::Selected),
+            vec![],
+            steps,
+        );
+        for (id, (start, end)) in path.into_iter().rev() {
+            let (left, right) = (self.nodes[id * 2], self.nodes[id * 2 + 1]);
+            self.nodes[id] = self.query_type.combine(left, right);
+            highlights.nodes.insert(id, ElementState::Selected);
+            self.push(
+                format!(
+                    "Recompute {} from its children, {}",
+                    range_name(start, end),
+                    self.query_type.describe(left, right)
+                ),
+                &highlights.with_range((start, end), ElementState::Active),
+                vec![],
+                steps,
+            );
+        }
+    }
+    fn query(&self, (start, end): (usize, usize), steps: &mut DiagramSteps) {
+        let mut highlights = Highlights {
+            query: Some((start, end)),
+            ..Default::default()
+        };
+        let mut used = vec![];
+        self.push(
+            format!(
+                "Find the {} of the range {} by starting from the root",
+                self.query_type.name(),
+                range_name(start, end)
+            ),
+            &highlights,
+            vec![],
+            steps,
+        );
+        let result = self
+            .query_node(
+                1,
+                (0, self.values.len() - 1),
+                (start, end),
+                &mut highlights,
+                &mut used,
+                steps,
+            )
+            .unwrap();
+        for index in start..=end {
+            highlights.cells.insert(index, ElementState::Selected);
+        }
+        self.push(
+            format!(
+                "The {} of the range {} is {}, combined from {} {}",
+                self.query_type.name(),
+                range_name(start, end),
+                result,
+                used.len(),
+                if used.len() == 1 { "node" } else { "nodes" }
+            ),
+            &highlights,
+            vec![used_list(&used)],
+            steps,
+        );
+    }
+    /// Returns the value of the part of the query range that is in the range of the node.
+    fn query_node(
+        &self,
+        id: usize,
+        (start, end): (usize, usize),
+        query: (usize, usize),
+        highlights: &mut Highlights,
+        used: &mut Vec<String>,
+        steps: &mut DiagramSteps,
+    ) -> Option<i64> {
+        let name = range_name(start, end);
+        if end < query.0 || start > query.1 {
+            highlights.nodes.insert(id, ElementState::Rejected);
+            self.push(
+                format!("{} is outside of the query range, so skip it", name),
+                &highlights.with_range((start, end), ElementState::Rejected),
+                vec![used_list(used)],
+                steps,
+            );
+            return None;
+        }
+        if query.0 <= start && end <= query.1 {
+            highlights.nodes.insert(id, ElementState::Selected);
+            used.push(format!("{}: {}", name, self.nodes[id]));
+            self.push(
+                format!(
+                    "{} is inside of the query range, so use its {} {} without going deeper",
+                    name,
+                    self.query_type.name(),
+                    self.nodes[id]
+                ),
+                &highlights.with_range((start, end), ElementState::Selected),
+                vec![used_list(used)],
+                steps,
+            );
+            return Some(self.nodes[id]);
+        }
+        highlights.nodes.insert(id, ElementState::Frontier);
+        self.push(
+            format!(
+                "{} is partly in the query range, so check both of its children",
+                name
+            ),
+            &highlights.with_range((start, end), ElementState::Active),
+            vec![used_list(used)],
+            steps,
+        );
+        let middle = (start + end) / 2;
+        let left = self.query_node(id * 2, (start, middle), query, highlights, used, steps);
+        let right = self.query_node(
+            id * 2 + 1,
+            (middle + 1, end),
+            query,
+            highlights,
+            used,
+            steps,
+        );
+        match (left, right) {
+            (Some(left), Some(right)) => Some(self.query_type.combine(left, right)),
+            (value, None) | (None, value) => value,
+        }
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(RANGES_WIDTH, RANGES_HEIGHT);
+        let size = self.values.len();
+        if let Some(query) = highlights.query {
+            shade_range(&mut diagram, query, size, None, true);
+        }
+        if let Some((range, state)) = highlights.range {
+            shade_range(&mut diagram, range, size, Some(state), false);
+        }
+        let ranges = self.ranges();
+        let position = |(start, end): (usize, usize), depth: usize| {
+            (
+                (cell_x(start, size) + cell_x(end, size)) / 2.0,
+                40.0 + LEVEL_HEIGHT * depth as f32,
+            )
+        };
+        for (id, range, depth) in ranges.iter() {
+            let label = if highlights.pending.contains(id) {
+                String::new()
+            } else {
+                self.nodes[*id].to_string()
+            };
+            let mut node = DiagramNode::rect(
+                format!("node-{}", id),
+                label,
+                position(*range, *depth),
+                CELL_SIZE,
+            )
+            .with_note(range_name(range.0, range.1));
+            node.state = highlights.nodes.get(id).copied();
+            diagram.add_node(node);
+            if *id > 1 {
+                diagram.add_edge(DiagramEdge::new(
+                    format!("node-{}", id / 2),
+                    format!("node-{}", id),
+                ));
+            }
+        }
+        draw_array(&mut diagram, &self.values, 0, &highlights.cells);
+        diagram
+    }
+}
+impl Visualizer for SegmentTree {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let last = self.values.len() as i64 - 1;
+        vec![
+            Operation::new(
+                "Query",
+                vec![
+                    Input::Number {
+                        label: "Start index",
+                        default: 2.min(last),
+                        min: 0,
+                        max: last,
+                    },
+                    Input::Number {
+                        label: "End index",
+                        default: 6.min(last),
+                        min: 0,
+                        max: last,
+                    },
+                ],
+            ),
+            Operation::new(
+                "Update",
+                vec![
+                    Input::Number {
+                        label: "Index to update",
+                        default: 3.min(last),
+                        min: 0,
+                        max: last,
+                    },
+                    Input::Number {
+                        label: "New value",
+                        default: 10,
+                        min: 0,
+                        max: MAX_ARRAY_VALUE,
+                    },
+                ],
+            ),
+            Operation::new(
+                "Build",
+                vec![Input::Text {
+                    label: "Values",
+                    default: "5 8 6 3 2 7 2 6".to_string(),
+                }],
+            ),
+            Operation::new(
+                "Change query type",
+                vec![Input::Choice {
+                    label: "Query type",
+                    choices: QUERY_TYPES.map(String::from).to_vec(),
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let number = |index: usize| {
+            values
+                .get(index)
+                .map(|value| value.number())
+                .unwrap_or_default()
+        };
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Query" => {
+                let range = check_range(number(0), number(1), self.values.len(), 0)?;
+                self.query(range, &mut steps);
+            }
+            "Update" => {
+                let index = check_index(number(0), self.values.len(), 0)?;
+                let value = number(1);
+                if !(0..=MAX_ARRAY_VALUE).contains(&value) {
+                    return Err(format!(
+                        "The values must be between 0 and {}",
+                        MAX_ARRAY_VALUE
+                    ));
+                }
+                self.update(index, value, &mut steps);
+            }
+            "Build" => {
+                let array = parse_values(
+                    &values.first().map(|value| value.text()).unwrap_or_default(),
+                    0,
+                    MAX_ARRAY_VALUE,
+                    MAX_ARRAY_SIZE,
+                )?;
+                self.build(&array, &mut steps);
+            }
+            "Change query type" => {
+                self.query_type = QueryType::from_name(
+                    &values.first().map(|value| value.text()).unwrap_or_default(),
+                );
+                let array = self.values.clone();
+                self.build(&array, &mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn range_name(start: usize, end: usize) -> String {
+    if start == end {
+        format!("[{}]", start)
+    } else {
+        format!("[{}, {}]", start, end)
+    }
+}
+
+fn used_list(used: &[String]) -> StepList {
+    StepList::new("Used nodes", used.to_vec())
+}
@@ -0,0 +1,384 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+const LIST_WIDTH: f32 = 800.0;
+const LIST_HEIGHT: f32 = 400.0;
+/// The number of levels, so that the towers fit in the diagram.
+const MAX_LEVELS: usize = 5;
+const MAX_SIZE: usize = 16;
+const CELL_SIZE: (f32, f32) = (40.0, 30.0);
+const LEVEL_HEIGHT: f32 = 60.0;
+/// The y of the lowest level.
+const BOTTOM_Y: f32 = 340.0;
+
+/// A cell of a tower, by the value of the tower and the level of the cell. The towers of the head have no value.
+type Cell = (Option<i64>, usize);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    cells: BTreeMap<Cell, ElementState>,
+    /// The pointers by the cells that they start from.
+    pointers: BTreeMap<Cell, ElementState>,
+}
+impl Highlights {
+    fn new() -> Self {
+        Self::default()
+    }
+    fn cell(mut self, cell: Cell, state: ElementState) -> Self {
+        self.cells.insert(cell, state);
+        self
+    }
+    fn pointer(mut self, cell: Cell, state: ElementState) -> Self {
+        self.pointers.insert(cell, state);
+        self
+    }
+    fn tower(mut self, value: i64, height: usize, state: ElementState) -> Self {
+        for level in 0..height {
+            self.cells.insert((Some(value), level), state);
+        }
+        self
+    }
+}
+
+/// A sorted linked list with extra levels of pointers that skip over elements. Every element gets a tower of
+/// levels by flipping coins, so each level has about half of the elements of the level below it, and searches
+/// can skip ahead on the higher levels.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SkipList {
+    /// The values and the heights of their towers in order.
+    elements: Vec<(i64, usize)>,
+}
+impl SkipList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Makes a list with the values and the heights of their towers.
+    pub fn from_towers(towers: &[(i64, usize)]) -> Self {
+        let mut elements = towers
+            .iter()
+            .map(|(value, height)| (*value, (*height).clamp(1, MAX_LEVELS)))
+            .collect::<Vec<(i64, usize)>>();
+        elements.sort_unstable();
+        elements.dedup_by_key(|(value, _)| *value);
+        Self { elements }
+    }
+    /// The number of levels that are in use.
+    fn top_level(&self) -> usize {
+        self.elements
+            .iter()
+            .map(|(_, height)| *height)
+            .max()
+            .unwrap_or(1)
+    }
+    /// The index of the first element after `index` whose tower reaches `level`, where `None` is the head.
+    fn next(&self, index: Option<usize>, level: usize) -> Option<usize> {
+        let start = index.map_or(0, |index| index + 1);
+        (start..self.elements.len()).find(|i| self.elements[*i].1 > level)
+    }
+    fn cell(&self, index: Option<usize>, level: usize) -> Cell {
+        (index.map(|index| self.elements[index].0), level)
+    }
+    fn name(&self, index: Option<usize>) -> String {
+        index.map_or("the head".to_string(), |index| {
+            self.elements[index].0.to_string()
+        })
+    }
+    fn push<'a>(
+        &self,
+        description: String,
+        highlights: Highlights,
+        steps: &'a mut DiagramSteps,
+    ) -> &'a mut DiagramStep {
+        steps.push(description, self.draw(&highlights))
+    }
+    /// Goes right on each level while the next value is smaller than the value, and down otherwise. Returns the last
+    /// element before the value on every level, where `None` is the head.
+    fn search_path(&self, value: i64, steps: &mut DiagramSteps) -> [Option<usize>; MAX_LEVELS] {
+        let mut before = [None; MAX_LEVELS];
+        let mut current = None;
+        let mut highlights = Highlights::new();
+        let top = self.top_level();
+        self.push(
+            format!("Start from the head on the top level {}", top - 1),
+            Highlights::new().cell((None, top - 1), ElementState::Active),
+            steps,
+        );
+        for level in (0..top).rev() {
+            loop {
+                let next = self.next(current, level);
+                highlights = highlights.cell(self.cell(current, level), ElementState::Frontier);
+                let step_highlights = highlights
+                    .clone()
+                    .cell(self.cell(current, level), ElementState::Active)
+                    .pointer(self.cell(current, level), ElementState::Active);
+                match next {
+                    Some(next) if self.elements[next].0 < value => {
+                        self.push(
+                            format!(
+                                "Level {}: the next value {} < {}, so move right",
+                                level, self.elements[next].0, value
+                            ),
+                            step_highlights,
+                            steps,
+                        );
+                        highlights =
+                            highlights.pointer(self.cell(current, level), ElementState::Frontier);
+                        current = Some(next);
+                    }
+                    _ => {
+                        let reason = match next {
+                            Some(next) => {
+                                format!("the next value {} ≥ {}", self.elements[next].0, value)
+                            }
+                            None => "the next pointer is NIL".to_string(),
+                        };
+                        let action = if level == 0 {
+                            format!("so {} belongs right after {}", value, self.name(current))
+                        } else {
+                            "so move down a level".to_string()
+                        };
+                        self.push(
+                            format!("Level {}: {}, {}", level, reason, action),
+                            step_highlights,
+                            steps,
+                        );
+                        before[level] = current;
+                        break;
+                    }
+                }
+            }
+        }
+        before
+    }
+    /// The index of the value if it is right after `before` on the lowest level.
+    fn found(&self, before: &[Option<usize>; MAX_LEVELS], value: i64) -> Option<usize> {
+        self.next(before[0], 0)
+            .filter(|index| self.elements[*index].0 == value)
+    }
+    fn search(&self, value: i64, steps: &mut DiagramSteps) {
+        let before = self.search_path(value, steps);
+        match self.found(&before, value) {
+            Some(index) => {
+                let height = self.elements[index].1;
+                self.push(
+                    format!("The next value is {}, so it was found", value),
+                    Highlights::new().tower(value, height, ElementState::Selected),
+                    steps,
+                );
+            }
+            None => {
+                self.push(
+                    format!("The next value isn't {}, so it isn't in the list", value),
+                    Highlights::new().cell(self.cell(before[0], 0), ElementState::Rejected),
+                    steps,
+                );
+            }
+        }
+    }
+    fn insert(&mut self, value: i64, steps: &mut DiagramSteps) -> Result<(), String> {
+        if !(0..=999).contains(&value) {
+            return Err("The values must be between 0 and 999".to_string());
+        }
+        let before = self.search_path(value, steps);
+        if let Some(index) = self.found(&before, value) {
+            let height = self.elements[index].1;
+            self.push(
+                format!("{} is already in the list", value),
+                Highlights::new().tower(value, height, ElementState::Conflict),
+                steps,
+            );
+            return Ok(());
+        }
+        if self.elements.len() >= MAX_SIZE {
+            return Err(format!("The list can have at most {} values", MAX_SIZE));
+        }
+
+        let index = before[0].map_or(0, |index| index + 1);
+        // The indices of the elements before the value have to be updated after inserting it
+        let before = before.map(|before| before.map(|i| if i >= index { i + 1 } else { i }));
+        self.elements.insert(index, (value, 1));
+        let mut flips = vec![];
+        self.push(
+            format!("Link {} into level 0 after {}", value, self.name(before[0])),
+            Highlights::new()
+                .cell((Some(value), 0), ElementState::Selected)
+                .pointer(self.cell(before[0], 0), ElementState::Selected)
+                .pointer((Some(value), 0), ElementState::Selected),
+            steps,
+        );
+
+        while self.elements[index].1 < MAX_LEVELS {
+            let level = self.elements[index].1;
+            if !rand::random::<bool>() {
+                flips.push("Tails".to_string());
+                self.push(
+                    format!(
+                        "Flip a coin: tails, so the tower of {} stops at level {}",
+                        value,
+                        level - 1
+                    ),
+                    Highlights::new().tower(value, level, ElementState::Active),
+                    steps,
+                )
+                .lists = vec![StepList::new("Coin flips", flips.clone())];
+                break;
+            }
+            flips.push("Heads".to_string());
+            self.elements[index].1 += 1;
+            // The element before on a level that wasn't in use is the head
+            let previous = before[level];
+            self.push(
+                format!(
+                    "Flip a coin: heads, so the tower grows to level {} and is linked after {}",
+                    level,
+                    self.name(previous)
+                ),
+                Highlights::new()
+                    .tower(value, level, ElementState::Active)
+                    .cell((Some(value), level), ElementState::Selected)
+                    .pointer(self.cell(previous, level), ElementState::Selected)
+                    .pointer((Some(value), level), ElementState::Selected),
+                steps,
+            )
+            .lists = vec![StepList::new("Coin flips", flips.clone())];
+        }
+        if self.elements[index].1 == MAX_LEVELS {
+            self.push(
+                format!("The tower of {} reached the highest level", value),
+                Highlights::new().tower(value, MAX_LEVELS, ElementState::Selected),
+                steps,
+            )
+            .lists = vec![StepList::new("Coin flips", flips)];
+        }
+        Ok(())
+    }
+    fn delete(&mut self, value: i64, steps: &mut DiagramSteps) {
+        let before = self.search_path(value, steps);
+        let Some(index) = self.found(&before, value) else {
+            self.push(
+                format!("The next value isn't {}, so it isn't in the list", value),
+                Highlights::new().cell(self.cell(before[0], 0), ElementState::Rejected),
+                steps,
+            );
+            return;
+        };
+        let height = self.elements[index].1;
+        let mut highlights = Highlights::new().tower(value, height, ElementState::Rejected);
+        for (level, previous) in before.iter().enumerate().take(height) {
+            highlights = highlights.pointer(self.cell(*previous, level), ElementState::Active);
+        }
+        self.push(
+            format!(
+                "Found {}. Point the elements before it on its {} level{} to the elements after it",
+                value,
+                height,
+                if height == 1 { "" } else { "s" }
+            ),
+            highlights,
+            steps,
+        );
+        self.elements.remove(index);
+        let mut highlights = Highlights::new();
+        for (level, previous) in before.iter().enumerate().take(height) {
+            highlights = highlights.pointer(self.cell(*previous, level), ElementState::Selected);
+        }
+        self.push(format!("Remove {}", value), highlights, steps);
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(LIST_WIDTH, LIST_HEIGHT);
+        let columns = self.elements.len() + 2;
+        let spacing = ((LIST_WIDTH - NODE_RADIUS * 2.0) / columns as f32).min(70.0);
+        let left = (LIST_WIDTH - spacing * columns as f32) / 2.0 + spacing / 2.0;
+        let key = |cell: Cell| match cell {
+            (Some(value), level) => format!("cell-{}-{}", value, level),
+            (None, level) => format!("head-{}", level),
+        };
+        let y = |level: usize| BOTTOM_Y - LEVEL_HEIGHT * level as f32;
+        let tail_x = left + spacing * (columns - 1) as f32;
+
+        for level in 0..MAX_LEVELS {
+            let head = (None, level);
+            let mut node = DiagramNode::rect(
+                key(head),
+                "head",
+                (left, y(level)),
+                (CELL_SIZE.0 + 10.0, CELL_SIZE.1),
+            );
+            node.state = highlights.cells.get(&head).copied();
+            diagram.add_node(node);
+            diagram.add_node(DiagramNode::rect(
+                format!("tail-{}", level),
+                "NIL",
+                (tail_x, y(level)),
+                (CELL_SIZE.0 + 10.0, CELL_SIZE.1),
+            ));
+        }
+        for (i, (value, height)) in self.elements.iter().enumerate() {
+            let x = left + spacing * (i + 1) as f32;
+            for level in 0..*height {
+                let cell = (Some(*value), level);
+                let mut node = DiagramNode::rect(key(cell), value, (x, y(level)), CELL_SIZE);
+                node.state = highlights.cells.get(&cell).copied();
+                diagram.add_node(node);
+            }
+        }
+        // Every cell points to the next cell on its level or to NIL
+        let mut cells = (0..MAX_LEVELS)
+            .map(|level| (None, level))
+            .collect::<Vec<Cell>>();
+        for (value, height) in self.elements.iter() {
+            cells.extend((0..*height).map(|level| (Some(*value), level)));
+        }
+        for cell in cells {
+            let index = cell
+                .0
+                .and_then(|value| self.elements.iter().position(|(other, _)| *other == value));
+            let to = match self.next(index, cell.1) {
+                Some(next) => key((Some(self.elements[next].0), cell.1)),
+                None => format!("tail-{}", cell.1),
+            };
+            let mut edge = DiagramEdge::arrow(key(cell), to);
+            edge.state = highlights.pointers.get(&cell).copied();
+            diagram.add_edge(edge);
+        }
+        diagram
+    }
+}
+impl Visualizer for SkipList {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let value_input = |label, default| Input::Number {
+            label,
+            default,
+            min: 0,
+            max: 999,
+        };
+        vec![
+            Operation::new("Insert", vec![value_input("Value to insert", 42)]),
+            Operation::new("Search", vec![value_input("Value to search for", 61)]),
+            Operation::new("Delete", vec![value_input("Value to delete", 25)]),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values
+            .first()
+            .map(|value| value.number())
+            .unwrap_or_default();
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Insert" => self.insert(value, &mut steps)?,
+            "Search" => self.search(value, &mut steps),
+            "Delete" => self.delete(value, &mut steps),
+            "Clear" => {
+                self.elements.clear();
+                steps.push("Remove all values".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
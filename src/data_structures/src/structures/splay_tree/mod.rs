@@ -0,0 +1,340 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use super::binary_tree::*;
+
+/// The largest number of values in an access sequence, so that the steps stay short enough to follow.
+const MAX_ACCESSES: usize = 10;
+
+/// A binary search tree that moves every node that is accessed to the root with rotations, which is called
+/// splaying. Frequently accessed values stay near the root, and any sequence of operations takes *O(log n)* time
+/// per operation on average, even though the tree isn't kept balanced.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SplayTree {
+    tree: BinaryTree,
+    /// How many times the nodes have been accessed.
+    accesses: BTreeMap<usize, usize>,
+}
+impl SplayTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Makes a tree by inserting the values in order without keeping the steps.
+    pub fn from_values(values: &[i64]) -> Self {
+        let mut tree = Self::new();
+        for value in values {
+            let _ = tree.insert(*value, &mut DiagramSteps::new());
+        }
+        tree.accesses.clear();
+        tree
+    }
+    fn push(&self, description: String, highlights: TreeHighlights, steps: &mut DiagramSteps) {
+        steps.push(description, draw(&self.tree, &self.accesses, &highlights));
+    }
+    fn access(&mut self, id: usize) {
+        *self.accesses.entry(id).or_default() += 1;
+    }
+    /// Rotates the node up until its parent is `top`, or until it is the root if `top` is `None`.
+    fn splay(&mut self, id: usize, top: Option<usize>, steps: &mut DiagramSteps) {
+        let value = self.tree.value(id);
+        while let Some((parent, side)) = self
+            .tree
+            .parent(id)
+            .filter(|(parent, _)| Some(*parent) != top)
+        {
+            let parent_value = self.tree.value(parent);
+            let accesses = &self.accesses;
+            let draw =
+                |tree: &BinaryTree, highlights: &TreeHighlights| draw(tree, accesses, highlights);
+            match self
+                .tree
+                .parent(parent)
+                .filter(|(grandparent, _)| Some(*grandparent) != top)
+            {
+                None => {
+                    self.tree.rotate_steps(
+                        parent,
+                        side.opposite(),
+                        format!(
+                            "Zig: {} is the {} child of {}, which is {}",
+                            value,
+                            side.name(),
+                            parent_value,
+                            if top.is_some() {
+                                "as high as it can go"
+                            } else {
+                                "the root"
+                            }
+                        ),
+                        steps,
+                        &draw,
+                    );
+                }
+                Some((grandparent, parent_side)) if parent_side == side => {
+                    self.tree.rotate_steps(
+                        grandparent,
+                        side.opposite(),
+                        format!(
+                            "Zig-zig: {} and its parent {} are both {} children, so first rotate the grandparent {}",
+                            value,
+                            parent_value,
+                            side.name(),
+                            self.tree.value(grandparent)
+                        ),
+                        steps,
+                        &draw,
+                    );
+                    self.tree.rotate_steps(
+                        parent,
+                        side.opposite(),
+                        format!("Then rotate the parent {}", parent_value),
+                        steps,
+                        &draw,
+                    );
+                }
+                Some((grandparent, parent_side)) => {
+                    self.tree.rotate_steps(
+                        parent,
+                        side.opposite(),
+                        format!(
+                            "Zig-zag: {} is a {} child, but its parent {} is a {} child, so first rotate the parent",
+                            value,
+                            side.name(),
+                            parent_value,
+                            parent_side.name()
+                        ),
+                        steps,
+                        &draw,
+                    );
+                    self.tree.rotate_steps(
+                        grandparent,
+                        parent_side.opposite(),
+                        format!(
+                            "Then rotate the old grandparent {}",
+                            self.tree.value(grandparent)
+                        ),
+                        steps,
+                        &draw,
+                    );
+                }
+            }
+        }
+        if top.is_none() {
+            self.push(
+                format!("{} is now the root", value),
+                TreeHighlights::new().node(id, ElementState::Selected),
+                steps,
+            );
+        }
+    }
+    /// Splays the node to the root, explaining why with `reason`.
+    fn splay_to_root(&mut self, id: usize, reason: String, steps: &mut DiagramSteps) {
+        if self.tree.parent(id).is_none() {
+            self.push(
+                format!("{} is already the root", self.tree.value(id)),
+                TreeHighlights::new().node(id, ElementState::Selected),
+                steps,
+            );
+            return;
+        }
+        self.push(
+            reason,
+            TreeHighlights::new().node(id, ElementState::Active),
+            steps,
+        );
+        self.splay(id, None, steps);
+    }
+    /// Splays the last node on the search path of a value that isn't in the tree.
+    fn splay_last_visited(&mut self, value: i64, steps: &mut DiagramSteps) {
+        if let Some(last) = self.tree.search_path(value).0.last().copied() {
+            let reason = format!(
+                "Splay {}, the last node that was visited, to the root",
+                self.tree.value(last)
+            );
+            self.splay_to_root(last, reason, steps);
+        }
+    }
+    fn insert(&mut self, value: i64, steps: &mut DiagramSteps) -> Result<(), String> {
+        let accesses = &self.accesses;
+        let inserted = self.tree.insert_steps(value, steps, &|tree, highlights| {
+            draw(tree, accesses, highlights)
+        })?;
+        // A value that is already in the tree is splayed like on a search
+        let id = inserted.unwrap_or_else(|| *self.tree.search_path(value).0.last().unwrap());
+        self.access(id);
+        self.splay_to_root(id, format!("Splay {} to the root", value), steps);
+        Ok(())
+    }
+    fn search(&mut self, value: i64, steps: &mut DiagramSteps) {
+        let accesses = &self.accesses;
+        let found = self.tree.search_steps(value, steps, &|tree, highlights| {
+            draw(tree, accesses, highlights)
+        });
+        match found {
+            Some(path) => {
+                let id = *path.last().unwrap();
+                self.access(id);
+                self.splay_to_root(id, format!("Splay {} to the root", value), steps);
+            }
+            None => self.splay_last_visited(value, steps),
+        }
+    }
+    /// Splays the node to the root and joins its subtrees by splaying the largest value of the left subtree up
+    /// below it, so that the right subtree can be attached to that value.
+    fn delete(&mut self, value: i64, steps: &mut DiagramSteps) {
+        let accesses = &self.accesses;
+        let found = self.tree.search_steps(value, steps, &|tree, highlights| {
+            draw(tree, accesses, highlights)
+        });
+        let Some(path) = found else {
+            self.splay_last_visited(value, steps);
+            return;
+        };
+        let id = *path.last().unwrap();
+        self.splay_to_root(
+            id,
+            format!("Splay {} to the root before removing it", value),
+            steps,
+        );
+
+        let (left, right) = (
+            self.tree.child(id, Side::Left),
+            self.tree.child(id, Side::Right),
+        );
+        let Some(left) = left else {
+            self.push(
+                format!(
+                    "{} has no left subtree, so its right subtree takes its place",
+                    value
+                ),
+                TreeHighlights::new().node(id, ElementState::Rejected),
+                steps,
+            );
+            self.tree.remove_with_child(id, right);
+            self.accesses.remove(&id);
+            let description = match right {
+                Some(right) => format!("{} is the new root", self.tree.value(right)),
+                None => "The tree is now empty".to_string(),
+            };
+            self.push(description, TreeHighlights::new(), steps);
+            return;
+        };
+
+        let mut largest_path = vec![left];
+        while let Some(child) = self.tree.child(*largest_path.last().unwrap(), Side::Right) {
+            largest_path.push(child);
+        }
+        let largest = *largest_path.last().unwrap();
+        let largest_value = self.tree.value(largest);
+        self.push(
+            format!(
+                "The largest value of the left subtree is {}, found by going right from {}. Splay it up to be the left child of {}",
+                largest_value,
+                self.tree.value(left),
+                value
+            ),
+            TreeHighlights::new()
+                .path(&largest_path, ElementState::Frontier)
+                .node(id, ElementState::Rejected)
+                .node(largest, ElementState::Active),
+            steps,
+        );
+        self.splay(largest, Some(id), steps);
+
+        self.tree.set_child(Some(id), Side::Right, None);
+        self.tree.set_child(Some(largest), Side::Right, right);
+        self.tree.remove_with_child(id, Some(largest));
+        self.accesses.remove(&id);
+        let mut highlights = TreeHighlights::new().node(largest, ElementState::Selected);
+        let description = match right {
+            Some(right) => {
+                highlights = highlights.edge((largest, right), ElementState::Active);
+                format!(
+                    "{} has no right child, so attach the right subtree of {} to it and make it the root",
+                    largest_value, value
+                )
+            }
+            None => format!(
+                "Remove {}, so that {} becomes the root",
+                value, largest_value
+            ),
+        };
+        self.push(description, highlights, steps);
+    }
+}
+impl Visualizer for SplayTree {
+    fn diagram(&self) -> Diagram {
+        draw(&self.tree, &self.accesses, &TreeHighlights::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let mut operations = search_tree_operations();
+        operations.insert(
+            3,
+            Operation::new(
+                "Access sequence",
+                vec![Input::Text {
+                    label: "Values to access",
+                    default: "20 70 20 60 20".to_string(),
+                }],
+            ),
+        );
+        operations
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Insert" => self.insert(value.number(), &mut steps)?,
+            "Search" => self.search(value.number(), &mut steps),
+            "Delete" => self.delete(value.number(), &mut steps),
+            "Access sequence" => {
+                for value in parse_accesses(&value.text())? {
+                    self.search(value, &mut steps);
+                }
+            }
+            "Traverse" => {
+                let accesses = &self.accesses;
+                return self.tree.traverse(&value.text(), &|tree, highlights| {
+                    draw(tree, accesses, highlights)
+                });
+            }
+            "Clear" => {
+                *self = Self::new();
+                steps.push("Remove all values".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// Draws the tree with the number of accesses of every node below it.
+fn draw(
+    tree: &BinaryTree,
+    accesses: &BTreeMap<usize, usize>,
+    highlights: &TreeHighlights,
+) -> Diagram {
+    tree.draw_with_labels(highlights, |id, node| {
+        (
+            node.value.to_string(),
+            accesses.get(&id).map(|count| format!("{}×", count)),
+        )
+    })
+}
+
+fn parse_accesses(text: &str) -> Result<Vec<i64>, String> {
+    let values = text
+        .split([',', ' '])
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| {
+            part.trim()
+                .parse::<i64>()
+                .map_err(|_| format!("\"{}\" isn't a whole number", part.trim()))
+        })
+        .collect::<Result<Vec<i64>, String>>()?;
+    if values.is_empty() || values.len() > MAX_ACCESSES {
+        return Err(format!("Enter 1 to {} values to access", MAX_ACCESSES));
+    }
+    Ok(values)
+}
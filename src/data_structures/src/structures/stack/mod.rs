@@ -0,0 +1,231 @@
+use diagram::*;
+
+use super::cells::*;
+
+const BRACKET_PAIRS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+/// The space above the stack for the brackets that are checked.
+const BRACKETS_HEIGHT: f32 = 60.0;
+const BRACKET_SPACING: f32 = 28.0;
+
+/// A pile of values where the last value that was pushed is the first one that is popped.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stack {
+    cells: Cells,
+}
+impl Stack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn from_values(values: &[i64]) -> Self {
+        Self {
+            cells: Cells::from_labels(values),
+        }
+    }
+    fn push(&mut self, label: impl ToString, steps: &mut DiagramSteps) -> Result<(), String> {
+        if self.cells.is_full() {
+            return Err(format!("The stack can have at most {} values", MAX_CELLS));
+        }
+        let label = label.to_string();
+        let id = self.cells.push_back(&label);
+        steps.push(
+            format!("Push {} on top of the stack", label),
+            self.cells
+                .draw_column(&CellStates::from([(id, ElementState::Selected)]), 0.0),
+        );
+        Ok(())
+    }
+    fn pop(&mut self, steps: &mut DiagramSteps) -> Result<(), String> {
+        let top = self.cells.back().ok_or("The stack is empty")?.clone();
+        steps.push(
+            format!("Pop {} from the top of the stack", top.label),
+            self.cells
+                .draw_column(&CellStates::from([(top.id, ElementState::Rejected)]), 0.0),
+        );
+        self.cells.pop_back();
+        let description = match self.cells.back() {
+            Some(cell) => format!("{} is now on top", cell.label),
+            None => "The stack is now empty".to_string(),
+        };
+        steps.push(description, self.diagram());
+        Ok(())
+    }
+    fn peek(&self, steps: &mut DiagramSteps) -> Result<(), String> {
+        let top = self.cells.back().ok_or("The stack is empty")?;
+        steps.push(
+            format!("{} is on top of the stack", top.label),
+            self.cells
+                .draw_column(&CellStates::from([(top.id, ElementState::Active)]), 0.0),
+        );
+        Ok(())
+    }
+    /// Draws the stack with the brackets above it, where the bracket at `current` has the state.
+    fn draw_brackets(
+        &self,
+        brackets: &[char],
+        current: Option<(usize, ElementState)>,
+        states: &CellStates,
+    ) -> Diagram {
+        let mut diagram = self.cells.draw_column(states, BRACKETS_HEIGHT);
+        for (index, bracket) in brackets.iter().enumerate() {
+            let x = CELLS_WIDTH / 2.0
+                + BRACKET_SPACING * (index as f32 - (brackets.len() as f32 - 1.0) / 2.0);
+            let mut node = DiagramNode::text(format!("bracket-{}", index), bracket, (x, 30.0));
+            node.state = current
+                .filter(|(current, _)| *current == index)
+                .map(|(_, state)| state);
+            diagram.add_node(node);
+        }
+        diagram
+    }
+    /// Checks if every opening bracket is closed by a matching bracket in the right order. Opening brackets are
+    /// pushed, and every closing bracket pops the latest opening bracket, which has to match it.
+    fn check_brackets(&mut self, text: &str, steps: &mut DiagramSteps) -> Result<(), String> {
+        let brackets = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<Vec<char>>();
+        if brackets.is_empty() || brackets.len() > MAX_CELLS {
+            return Err(format!("Enter 1 to {} brackets", MAX_CELLS));
+        }
+        if let Some(c) = brackets.iter().find(|c| {
+            !BRACKET_PAIRS
+                .iter()
+                .any(|(open, close)| *c == open || *c == close)
+        }) {
+            return Err(format!("\"{}\" is not one of the brackets ()[]{{}}", c));
+        }
+
+        self.cells.clear();
+        steps.push(
+            "Start with an empty stack".to_string(),
+            self.draw_brackets(&brackets, None, &CellStates::new()),
+        );
+        for (index, bracket) in brackets.iter().enumerate() {
+            if let Some((_, close)) = BRACKET_PAIRS.iter().find(|(open, _)| open == bracket) {
+                let id = self.cells.push_back(bracket);
+                steps.push(
+                    format!(
+                        "{} opens a pair, so push it and wait for {}",
+                        bracket, close
+                    ),
+                    self.draw_brackets(
+                        &brackets,
+                        Some((index, ElementState::Active)),
+                        &CellStates::from([(id, ElementState::Selected)]),
+                    ),
+                );
+                continue;
+            }
+            let (open, _) = BRACKET_PAIRS
+                .iter()
+                .find(|(_, close)| close == bracket)
+                .unwrap();
+            let Some(top) = self.cells.back().cloned() else {
+                steps.push(
+                    format!(
+                        "{} closes a pair, but the stack is empty, so the brackets are unbalanced",
+                        bracket
+                    ),
+                    self.draw_brackets(
+                        &brackets,
+                        Some((index, ElementState::Conflict)),
+                        &CellStates::new(),
+                    ),
+                );
+                return Ok(());
+            };
+            if top.label != open.to_string() {
+                steps.push(
+                    format!(
+                        "{} doesn't close {} on top of the stack, so the brackets are unbalanced",
+                        bracket, top.label
+                    ),
+                    self.draw_brackets(
+                        &brackets,
+                        Some((index, ElementState::Conflict)),
+                        &CellStates::from([(top.id, ElementState::Conflict)]),
+                    ),
+                );
+                return Ok(());
+            }
+            steps.push(
+                format!("{} closes {} on top of the stack, so pop it", bracket, open),
+                self.draw_brackets(
+                    &brackets,
+                    Some((index, ElementState::Active)),
+                    &CellStates::from([(top.id, ElementState::Rejected)]),
+                ),
+            );
+            self.cells.pop_back();
+        }
+        if self.cells.is_empty() {
+            steps.push(
+                "Every bracket was closed and the stack is empty, so the brackets are balanced"
+                    .to_string(),
+                self.draw_brackets(&brackets, None, &CellStates::new()),
+            );
+        } else {
+            let states = self
+                .cells
+                .iter()
+                .map(|cell| (cell.id, ElementState::Conflict))
+                .collect();
+            steps.push(
+                format!(
+                    "{} {} never closed, so the brackets are unbalanced",
+                    self.cells
+                        .iter()
+                        .map(|cell| cell.label.as_str())
+                        .collect::<String>(),
+                    if self.cells.len() == 1 { "was" } else { "were" }
+                ),
+                self.draw_brackets(&brackets, None, &states),
+            );
+        }
+        Ok(())
+    }
+}
+impl Visualizer for Stack {
+    fn diagram(&self) -> Diagram {
+        self.cells.draw_column(&CellStates::new(), 0.0)
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Push",
+                vec![Input::Number {
+                    label: "Value to push",
+                    default: 42,
+                    min: 0,
+                    max: 99,
+                }],
+            ),
+            Operation::new("Pop", vec![]),
+            Operation::new("Peek", vec![]),
+            Operation::new(
+                "Check brackets",
+                vec![Input::Text {
+                    label: "Brackets",
+                    default: "{[()()]}".to_string(),
+                }],
+            ),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Push" => self.push(value.number(), &mut steps)?,
+            "Pop" => self.pop(&mut steps)?,
+            "Peek" => self.peek(&mut steps)?,
+            "Check brackets" => self.check_brackets(&value.text(), &mut steps)?,
+            "Clear" => {
+                self.cells.clear();
+                steps.push("Remove all values".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
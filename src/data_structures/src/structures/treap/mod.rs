@@ -0,0 +1,370 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+use rand::Rng;
+
+use super::binary_tree::*;
+
+/// The priorities of the nodes, which are drawn below them.
+type Priorities = BTreeMap<usize, i64>;
+
+/// A binary search tree by the values and a max-heap by random priorities at the same time. The random priorities
+/// make the shape of the tree the same as if the values were inserted in a random order, so the tree is balanced
+/// on average.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Treap {
+    tree: BinaryTree,
+    priorities: Priorities,
+    /// The key that the treap was split at, along with the roots of the two treaps.
+    split: Option<(i64, Option<usize>, Option<usize>)>,
+}
+impl Treap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Makes a treap by inserting the values with the given priorities in order without keeping the steps.
+    pub fn from_pairs(pairs: &[(i64, i64)]) -> Self {
+        let mut treap = Self::new();
+        for (value, priority) in pairs {
+            if let Ok(Some(id)) =
+                treap
+                    .tree
+                    .insert_steps(*value, &mut DiagramSteps::new(), &draw_plain)
+            {
+                treap.priorities.insert(id, *priority);
+                treap.rotate_up(id, &mut DiagramSteps::new());
+            }
+        }
+        treap
+    }
+    fn priority(&self, id: usize) -> i64 {
+        self.priorities[&id]
+    }
+    fn push(&self, description: String, highlights: TreeHighlights, steps: &mut DiagramSteps) {
+        steps.push(
+            description,
+            draw(&self.tree, &self.priorities, self.split, &highlights),
+        );
+    }
+    fn check_split(&self) -> Result<(), String> {
+        if self.split.is_some() {
+            return Err("The treap is split, so merge it first".to_string());
+        }
+        Ok(())
+    }
+    /// Rotates the node up while its priority is higher than that of its parent.
+    fn rotate_up(&mut self, id: usize, steps: &mut DiagramSteps) {
+        let value = self.tree.value(id);
+        while let Some((parent, side)) = self.tree.parent(id) {
+            if self.priority(parent) >= self.priority(id) {
+                self.push(
+                    format!(
+                        "The priority of {} is at most that of its parent {}, so the heap order holds",
+                        value,
+                        self.tree.value(parent)
+                    ),
+                    TreeHighlights::new()
+                        .node(id, ElementState::Selected)
+                        .node(parent, ElementState::Active),
+                    steps,
+                );
+                return;
+            }
+            let reason = format!(
+                "{} has the priority {}, which is higher than the priority {} of its parent {}",
+                value,
+                priority_name(self.priority(id)),
+                priority_name(self.priority(parent)),
+                self.tree.value(parent)
+            );
+            let priorities = &self.priorities;
+            self.tree.rotate_steps(
+                parent,
+                side.opposite(),
+                reason,
+                steps,
+                &|tree, highlights| draw(tree, priorities, None, highlights),
+            );
+        }
+        self.push(
+            format!("{} has the highest priority, so it is the root", value),
+            TreeHighlights::new().node(id, ElementState::Selected),
+            steps,
+        );
+    }
+    /// Rotates the node down below the child with the higher priority until it is a leaf.
+    fn rotate_down(&mut self, id: usize, steps: &mut DiagramSteps) {
+        let value = self.tree.value(id);
+        loop {
+            let children = [Side::Left, Side::Right]
+                .into_iter()
+                .filter_map(|side| Some((self.tree.child(id, side)?, side)))
+                .collect::<Vec<(usize, Side)>>();
+            let Some((child, side)) = children
+                .iter()
+                .copied()
+                .max_by_key(|(child, _)| self.priority(*child))
+            else {
+                return;
+            };
+            let reason = if children.len() == 1 {
+                format!("{} is the only child of {}", self.tree.value(child), value)
+            } else {
+                format!(
+                    "{} is the child of {} with the higher priority, {}",
+                    self.tree.value(child),
+                    value,
+                    priority_name(self.priority(child))
+                )
+            };
+            let priorities = &self.priorities;
+            self.tree
+                .rotate_steps(id, side.opposite(), reason, steps, &|tree, highlights| {
+                    draw(tree, priorities, None, highlights)
+                });
+        }
+    }
+    fn insert(&mut self, value: i64, steps: &mut DiagramSteps) -> Result<(), String> {
+        self.check_split()?;
+        let priorities = &self.priorities;
+        let inserted = self.tree.insert_steps(value, steps, &|tree, highlights| {
+            draw(tree, priorities, None, highlights)
+        })?;
+        let Some(id) = inserted else {
+            return Ok(());
+        };
+        let priority = rand::thread_rng().gen_range(0..100);
+        self.priorities.insert(id, priority);
+        self.push(
+            format!("Give {} the random priority {}", value, priority),
+            TreeHighlights::new().node(id, ElementState::Active),
+            steps,
+        );
+        self.rotate_up(id, steps);
+        Ok(())
+    }
+    fn delete(&mut self, value: i64, steps: &mut DiagramSteps) -> Result<(), String> {
+        self.check_split()?;
+        let priorities = &self.priorities;
+        let found = self.tree.search_steps(value, steps, &|tree, highlights| {
+            draw(tree, priorities, None, highlights)
+        });
+        let Some(path) = found else {
+            return Ok(());
+        };
+        let id = *path.last().unwrap();
+        self.push(
+            format!(
+                "Rotate {} down until it is a leaf, keeping the heap order of its children",
+                value
+            ),
+            TreeHighlights::new().node(id, ElementState::Rejected),
+            steps,
+        );
+        self.rotate_down(id, steps);
+        self.remove_leaf(id, steps);
+        Ok(())
+    }
+    fn remove_leaf(&mut self, id: usize, steps: &mut DiagramSteps) {
+        let value = self.tree.value(id);
+        self.push(
+            format!("{} is now a leaf, so it can be removed", value),
+            TreeHighlights::new().node(id, ElementState::Rejected),
+            steps,
+        );
+        self.tree.remove_with_child(id, None);
+        self.priorities.remove(&id);
+        self.push(format!("Remove {}", value), TreeHighlights::new(), steps);
+    }
+    /// Splits the treap into one with the values that are smaller than the key and one with the larger values,
+    /// by inserting the key with the highest possible priority, which rotates it up to the root.
+    fn split(&mut self, key: i64, steps: &mut DiagramSteps) -> Result<(), String> {
+        self.check_split()?;
+        let (path, side) = self.tree.search_path(key);
+        if !path.is_empty() && side.is_none() {
+            return Err(format!(
+                "{} is in the treap, so split at a key between its values",
+                key
+            ));
+        }
+        let priorities = &self.priorities;
+        let id = self
+            .tree
+            .insert_steps(key, steps, &|tree, highlights| {
+                draw(tree, priorities, None, highlights)
+            })?
+            .unwrap();
+        self.priorities.insert(id, i64::MAX);
+        self.push(
+            format!(
+                "Give {} the priority ∞, so that it rotates all the way up to the root",
+                key
+            ),
+            TreeHighlights::new().node(id, ElementState::Active),
+            steps,
+        );
+        self.rotate_up(id, steps);
+
+        let (left, right) = (
+            self.tree.child(id, Side::Left),
+            self.tree.child(id, Side::Right),
+        );
+        let mut highlights = TreeHighlights::new().node(id, ElementState::Rejected);
+        for (child, _) in [(left, Side::Left), (right, Side::Right)] {
+            if let Some(child) = child {
+                highlights = highlights.edge((id, child), ElementState::Active);
+            }
+        }
+        self.push(
+            format!(
+                "The left subtree of {} has the values that are smaller than it and the right subtree has the larger values",
+                key
+            ),
+            highlights,
+            steps,
+        );
+        self.tree.remove_node(id);
+        self.tree.root = None;
+        self.priorities.remove(&id);
+        self.split = Some((key, left, right));
+        self.push(
+            format!("Remove {}, which leaves two treaps", key),
+            TreeHighlights::new(),
+            steps,
+        );
+        Ok(())
+    }
+    /// Merges the two treaps of a split under a temporary root with the lowest possible priority, which is then
+    /// rotated down and removed.
+    fn merge(&mut self, steps: &mut DiagramSteps) -> Result<(), String> {
+        let (key, left, right) = self
+            .split
+            .ok_or("The treap isn't split, so split it first")?;
+        self.split = None;
+        let id = self.tree.add_node(key);
+        self.tree.set_child(Some(id), Side::Left, left);
+        self.tree.set_child(Some(id), Side::Right, right);
+        self.tree.root = Some(id);
+        self.priorities.insert(id, i64::MIN);
+        self.push(
+            format!(
+                "Join the treaps under a temporary root {} with the priority −∞, since every value on the left is smaller than every value on the right",
+                key
+            ),
+            TreeHighlights::new().node(id, ElementState::Active),
+            steps,
+        );
+        self.rotate_down(id, steps);
+        self.remove_leaf(id, steps);
+        Ok(())
+    }
+}
+impl Visualizer for Treap {
+    fn diagram(&self) -> Diagram {
+        draw(
+            &self.tree,
+            &self.priorities,
+            self.split,
+            &TreeHighlights::new(),
+        )
+    }
+    fn operations(&self) -> Vec<Operation> {
+        if self.split.is_some() {
+            return vec![
+                Operation::new("Merge", vec![]),
+                Operation::new("Clear", vec![]),
+            ];
+        }
+        let mut operations = search_tree_operations();
+        operations.insert(
+            3,
+            Operation::new(
+                "Split",
+                vec![Input::Number {
+                    label: "Key to split at",
+                    default: 45,
+                    min: -999,
+                    max: 999,
+                }],
+            ),
+        );
+        operations
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Insert" => self.insert(value.number(), &mut steps)?,
+            "Search" => {
+                self.check_split()?;
+                let priorities = &self.priorities;
+                self.tree
+                    .search_steps(value.number(), &mut steps, &|tree, highlights| {
+                        draw(tree, priorities, None, highlights)
+                    });
+            }
+            "Delete" => self.delete(value.number(), &mut steps)?,
+            "Split" => self.split(value.number(), &mut steps)?,
+            "Merge" => self.merge(&mut steps)?,
+            "Traverse" => {
+                self.check_split()?;
+                let priorities = &self.priorities;
+                return self.tree.traverse(&value.text(), &|tree, highlights| {
+                    draw(tree, priorities, None, highlights)
+                });
+            }
+            "Clear" => {
+                *self = Self::new();
+                steps.push("Remove all values".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn priority_name(priority: i64) -> String {
+    match priority {
+        i64::MAX => "∞".to_string(),
+        i64::MIN => "−∞".to_string(),
+        _ => priority.to_string(),
+    }
+}
+
+fn draw_plain(tree: &BinaryTree, highlights: &TreeHighlights) -> Diagram {
+    tree.draw(highlights)
+}
+
+/// Draws the tree with the priorities below the nodes. A split treap is drawn as two trees side by side.
+fn draw(
+    tree: &BinaryTree,
+    priorities: &Priorities,
+    split: Option<(i64, Option<usize>, Option<usize>)>,
+    highlights: &TreeHighlights,
+) -> Diagram {
+    let draw_tree = |tree: &BinaryTree| {
+        tree.draw_with_labels(highlights, |id, node| {
+            (
+                node.value.to_string(),
+                priorities.get(&id).map(|priority| priority_name(*priority)),
+            )
+        })
+    };
+    let Some((_, left, right)) = split else {
+        return draw_tree(tree);
+    };
+    let mut diagram = Diagram::new(TREE_WIDTH, TREE_HEIGHT);
+    for (root, offset) in [(left, 0.0), (right, TREE_WIDTH / 2.0)] {
+        let mut half = tree.clone();
+        half.root = root;
+        let half_diagram = draw_tree(&half);
+        for mut node in half_diagram.nodes {
+            node.position.0 = offset + node.position.0 / 2.0;
+            diagram.add_node(node);
+        }
+        for edge in half_diagram.edges {
+            diagram.add_edge(edge);
+        }
+    }
+    diagram
+}
@@ -0,0 +1,396 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use super::binary_tree::{node_key, TREE_HEIGHT, TREE_WIDTH};
+
+/// The largest number of words, so that the leaves fit side by side in the diagram.
+const MAX_WORDS: usize = 20;
+const MAX_WORD_LENGTH: usize = 12;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct TrieNode {
+    letter: Option<char>,
+    children: BTreeMap<char, usize>,
+    /// True if a word ends at the node.
+    is_word: bool,
+}
+
+/// A tree of letters where every word is a path from the root, so words with the same prefix share the nodes of the
+/// prefix. The nodes where words end are marked, since words can also be prefixes of other words.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trie {
+    nodes: BTreeMap<usize, TrieNode>,
+    next_id: usize,
+}
+impl Default for Trie {
+    fn default() -> Self {
+        Self {
+            nodes: BTreeMap::from([(0, TrieNode::default())]),
+            next_id: 1,
+        }
+    }
+}
+impl Trie {
+    const ROOT: usize = 0;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Makes a trie by inserting the words in order without keeping the steps.
+    pub fn from_words(words: &[&str]) -> Self {
+        let mut trie = Self::new();
+        for word in words {
+            let _ = trie.insert(word, &mut DiagramSteps::new());
+        }
+        trie
+    }
+    fn word_count(&self) -> usize {
+        self.nodes.values().filter(|node| node.is_word).count()
+    }
+    /// The highlights of the path of nodes from the root, with the last node highlighted with `state`.
+    fn path_highlights(path: &[usize], state: ElementState) -> BTreeMap<usize, ElementState> {
+        let mut highlights = path
+            .iter()
+            .map(|id| (*id, ElementState::Frontier))
+            .collect::<BTreeMap<usize, ElementState>>();
+        if let Some(last) = path.last() {
+            highlights.insert(*last, state);
+        }
+        highlights
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &BTreeMap<usize, ElementState>,
+        steps: &mut DiagramSteps,
+    ) {
+        steps.push(description, self.draw(highlights));
+    }
+    /// Follows the letters of the word from the root. Returns the path of nodes that were found, which is
+    /// shorter than the word if a letter is missing.
+    fn follow(&self, word: &str, steps: &mut DiagramSteps) -> Vec<usize> {
+        let mut path = vec![Self::ROOT];
+        self.push(
+            "Start from the root".to_string(),
+            &Self::path_highlights(&path, ElementState::Active),
+            steps,
+        );
+        for (i, letter) in word.char_indices() {
+            let id = *path.last().unwrap();
+            let Some(child) = self.nodes[&id].children.get(&letter) else {
+                self.push(
+                    format!(
+                        "The node has no child for \"{}\", so no word starts with \"{}\"",
+                        letter,
+                        &word[..i + letter.len_utf8()]
+                    ),
+                    &Self::path_highlights(&path, ElementState::Rejected),
+                    steps,
+                );
+                return path;
+            };
+            path.push(*child);
+            self.push(
+                format!("Follow the child for \"{}\"", letter),
+                &Self::path_highlights(&path, ElementState::Active),
+                steps,
+            );
+        }
+        path
+    }
+    fn insert(&mut self, word: &str, steps: &mut DiagramSteps) -> Result<(), String> {
+        validate(word)?;
+        let mut path = vec![Self::ROOT];
+        self.push(
+            "Start from the root".to_string(),
+            &Self::path_highlights(&path, ElementState::Active),
+            steps,
+        );
+        for letter in word.chars() {
+            let id = *path.last().unwrap();
+            if let Some(child) = self.nodes[&id].children.get(&letter) {
+                path.push(*child);
+                self.push(
+                    format!("There is already a child for \"{}\", so follow it", letter),
+                    &Self::path_highlights(&path, ElementState::Active),
+                    steps,
+                );
+                continue;
+            }
+            if self.word_count() >= MAX_WORDS {
+                return Err(format!("The trie can have at most {} words", MAX_WORDS));
+            }
+            let child = self.next_id;
+            self.next_id += 1;
+            self.nodes.insert(
+                child,
+                TrieNode {
+                    letter: Some(letter),
+                    ..Default::default()
+                },
+            );
+            self.nodes
+                .get_mut(&id)
+                .unwrap()
+                .children
+                .insert(letter, child);
+            path.push(child);
+            self.push(
+                format!("There is no child for \"{}\", so add a new node", letter),
+                &Self::path_highlights(&path, ElementState::Selected),
+                steps,
+            );
+        }
+
+        let last = *path.last().unwrap();
+        if self.nodes[&last].is_word {
+            self.push(
+                format!("\"{}\" is already in the trie", word),
+                &Self::path_highlights(&path, ElementState::Conflict),
+                steps,
+            );
+        } else {
+            if self.word_count() >= MAX_WORDS {
+                return Err(format!("The trie can have at most {} words", MAX_WORDS));
+            }
+            self.nodes.get_mut(&last).unwrap().is_word = true;
+            self.push(
+                format!(
+                    "Mark the node of the last letter as the end of \"{}\"",
+                    word
+                ),
+                &Self::path_highlights(&path, ElementState::Selected),
+                steps,
+            );
+        }
+        Ok(())
+    }
+    fn search(&self, word: &str, steps: &mut DiagramSteps) -> Result<(), String> {
+        validate(word)?;
+        let path = self.follow(word, steps);
+        if path.len() <= word.len() {
+            return Ok(());
+        }
+        let last = *path.last().unwrap();
+        if self.nodes[&last].is_word {
+            self.push(
+                format!("The node ends a word, so \"{}\" is in the trie", word),
+                &Self::path_highlights(&path, ElementState::Selected),
+                steps,
+            );
+        } else {
+            self.push(
+                format!(
+                    "The node doesn't end a word, so \"{}\" is only a prefix of the words in the trie",
+                    word
+                ),
+                &Self::path_highlights(&path, ElementState::Rejected),
+                steps,
+            );
+        }
+        Ok(())
+    }
+    fn delete(&mut self, word: &str, steps: &mut DiagramSteps) -> Result<(), String> {
+        validate(word)?;
+        let mut path = self.follow(word, steps);
+        if path.len() <= word.len() {
+            return Ok(());
+        }
+        let last = *path.last().unwrap();
+        if !self.nodes[&last].is_word {
+            self.push(
+                format!(
+                    "The node doesn't end a word, so \"{}\" isn't in the trie",
+                    word
+                ),
+                &Self::path_highlights(&path, ElementState::Rejected),
+                steps,
+            );
+            return Ok(());
+        }
+        self.nodes.get_mut(&last).unwrap().is_word = false;
+        self.push(
+            format!("Unmark the node as the end of \"{}\"", word),
+            &Self::path_highlights(&path, ElementState::Active),
+            steps,
+        );
+
+        // Remove the nodes that are no longer on the path of any word, going back up
+        while path.len() > 1 {
+            let id = path.pop().unwrap();
+            let node = &self.nodes[&id];
+            if node.is_word || !node.children.is_empty() {
+                let reason = if node.is_word {
+                    "ends another word"
+                } else {
+                    "has other children"
+                };
+                path.push(id);
+                self.push(
+                    format!(
+                        "The node for \"{}\" {}, so it stays",
+                        node.letter.unwrap_or_default(),
+                        reason
+                    ),
+                    &Self::path_highlights(&path, ElementState::Selected),
+                    steps,
+                );
+                return Ok(());
+            }
+            let mut highlights = Self::path_highlights(&path, ElementState::Frontier);
+            highlights.insert(id, ElementState::Rejected);
+            self.push(
+                format!(
+                    "The node for \"{}\" has no children and doesn't end a word, so it is removed",
+                    node.letter.unwrap_or_default()
+                ),
+                &highlights,
+                steps,
+            );
+            let letter = node.letter.unwrap_or_default();
+            self.nodes.remove(&id);
+            let parent = *path.last().unwrap();
+            self.nodes
+                .get_mut(&parent)
+                .unwrap()
+                .children
+                .remove(&letter);
+        }
+        self.push("Only the root is left".to_string(), &BTreeMap::new(), steps);
+        Ok(())
+    }
+    /// Lists the words that start with the prefix by going through the subtree of the prefix in alphabetical order.
+    fn find_prefix(&self, prefix: &str, steps: &mut DiagramSteps) -> Result<(), String> {
+        if !prefix.is_empty() {
+            validate(prefix)?;
+        }
+        let path = self.follow(prefix, steps);
+        if path.len() <= prefix.len() {
+            return Ok(());
+        }
+
+        let mut words = vec![];
+        let mut highlights = Self::path_highlights(&path, ElementState::Frontier);
+        // Depth-first search with the words of the nodes
+        let mut stack = vec![(*path.last().unwrap(), prefix.to_string())];
+        while let Some((id, word)) = stack.pop() {
+            let node = &self.nodes[&id];
+            highlights.insert(id, ElementState::Active);
+            let description = if node.is_word {
+                words.push(word.clone());
+                format!("The node ends \"{}\", so it is added to the results", word)
+            } else if word.is_empty() {
+                "Go through all of the words from the root".to_string()
+            } else {
+                format!("Go through the words that start with \"{}\"", word)
+            };
+            let step = steps.push(description, self.draw(&highlights));
+            step.lists = vec![StepList::new("Words with the prefix", words.clone())];
+            highlights.insert(
+                id,
+                if node.is_word {
+                    ElementState::Selected
+                } else {
+                    ElementState::Frontier
+                },
+            );
+            for (letter, child) in node.children.iter().rev() {
+                stack.push((*child, format!("{}{}", word, letter)));
+            }
+        }
+
+        let step = steps.push(
+            format!(
+                "Found {} word{} that start{} with \"{}\"",
+                words.len(),
+                if words.len() == 1 { "" } else { "s" },
+                if words.len() == 1 { "s" } else { "" },
+                prefix
+            ),
+            self.draw(&highlights),
+        );
+        step.lists = vec![StepList::new("Words with the prefix", words)];
+        Ok(())
+    }
+    fn draw(&self, highlights: &BTreeMap<usize, ElementState>) -> Diagram {
+        let layout = tree_layout(&[Self::ROOT], |id| {
+            self.nodes[&id].children.values().copied().collect()
+        });
+        let spacing_x = 50.0;
+        let max_x = layout.values().map(|(x, _)| *x).fold(0.0, f32::max);
+        let width = (max_x * spacing_x + NODE_RADIUS * 4.0).max(TREE_WIDTH);
+        let max_y = layout.values().map(|(_, y)| *y).fold(0.0, f32::max);
+        let height = (max_y * 60.0 + NODE_RADIUS * 4.0).max(TREE_HEIGHT);
+        let positions = fit_layout(&layout, (width, height), (spacing_x, 70.0));
+        let mut diagram = Diagram::new(width, height);
+
+        for (id, node) in self.nodes.iter() {
+            let Some(position) = positions.get(id) else {
+                continue;
+            };
+            let label = node.letter.map(String::from).unwrap_or_default();
+            let mut diagram_node = DiagramNode::circle(node_key(*id), label, *position);
+            if node.is_word {
+                diagram_node.fill = Fill::Group(0);
+            }
+            diagram_node.state = highlights.get(id).copied();
+            diagram.add_node(diagram_node);
+
+            for child in node.children.values() {
+                diagram.add_edge(DiagramEdge::arrow(node_key(*id), node_key(*child)));
+            }
+        }
+        diagram
+    }
+}
+impl Visualizer for Trie {
+    fn diagram(&self) -> Diagram {
+        self.draw(&BTreeMap::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let word_input = |label, default: &str| Input::Text {
+            label,
+            default: default.to_string(),
+        };
+        vec![
+            Operation::new("Insert", vec![word_input("Word to insert", "tent")]),
+            Operation::new("Search", vec![word_input("Word to search for", "ten")]),
+            Operation::new("Delete", vec![word_input("Word to delete", "tea")]),
+            Operation::new("Find words with prefix", vec![word_input("Prefix", "te")]),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let word = values
+            .first()
+            .map(|value| value.text().trim().to_lowercase())
+            .unwrap_or_default();
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Insert" => self.insert(&word, &mut steps)?,
+            "Search" => self.search(&word, &mut steps)?,
+            "Delete" => self.delete(&word, &mut steps)?,
+            "Find words with prefix" => self.find_prefix(&word, &mut steps)?,
+            "Clear" => {
+                *self = Self::new();
+                steps.push("Remove all words".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn validate(word: &str) -> Result<(), String> {
+    if word.is_empty()
+        || word.len() > MAX_WORD_LENGTH
+        || !word.chars().all(|c| c.is_ascii_lowercase())
+    {
+        return Err(format!(
+            "Words must have 1 to {} letters from a to z",
+            MAX_WORD_LENGTH
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,311 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use super::binary_tree::{node_key, TREE_HEIGHT, TREE_WIDTH};
+
+const MAX_ELEMENTS: usize = 16;
+
+/// Disjoint sets of the elements 0 to *n − 1*, stored as a forest where every element points to its parent and the
+/// root of a tree represents its set. Unions attach the root with the lower rank under the other root, and finds
+/// point every element on the way directly to the root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    /// Upper bounds for the heights of the trees of the roots.
+    ranks: Vec<usize>,
+}
+impl UnionFind {
+    pub fn new(size: usize) -> Self {
+        let size = size.clamp(1, MAX_ELEMENTS);
+        Self {
+            parents: (0..size).collect(),
+            ranks: vec![0; size],
+        }
+    }
+    /// Makes sets by joining the pairs in order without keeping the steps.
+    pub fn from_unions(size: usize, unions: &[(usize, usize)]) -> Self {
+        let mut sets = Self::new(size);
+        for (a, b) in unions {
+            let _ = sets.union(*a, *b, &mut DiagramSteps::new());
+        }
+        sets
+    }
+    fn is_root(&self, element: usize) -> bool {
+        self.parents[element] == element
+    }
+    fn roots(&self) -> Vec<usize> {
+        (0..self.parents.len())
+            .filter(|element| self.is_root(*element))
+            .collect()
+    }
+    fn children(&self, element: usize) -> Vec<usize> {
+        (0..self.parents.len())
+            .filter(|child| *child != element && self.parents[*child] == element)
+            .collect()
+    }
+    fn height(&self, element: usize) -> usize {
+        self.children(element)
+            .into_iter()
+            .map(|child| self.height(child) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+    fn push(
+        &self,
+        description: String,
+        nodes: &[(usize, ElementState)],
+        edges: &[(usize, ElementState)],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(nodes, edges));
+        step.lists = vec![StepList::new(
+            "Tree heights",
+            self.roots()
+                .into_iter()
+                .filter(|root| !self.children(*root).is_empty())
+                .map(|root| {
+                    format!(
+                        "{}: height {}, rank {}",
+                        root,
+                        self.height(root),
+                        self.ranks[root]
+                    )
+                })
+                .collect(),
+        )];
+    }
+    fn check(&self, element: usize) -> Result<(), String> {
+        if element >= self.parents.len() {
+            return Err(format!(
+                "The elements are from 0 to {}",
+                self.parents.len() - 1
+            ));
+        }
+        Ok(())
+    }
+    /// Finds the root of the element, and then points the elements on the way directly to it.
+    fn find(&mut self, element: usize, steps: &mut DiagramSteps) -> usize {
+        let mut path = vec![element];
+        let mut current = element;
+        let path_states = |path: &[usize]| {
+            path.iter()
+                .map(|element| (*element, ElementState::Frontier))
+                .collect::<Vec<(usize, ElementState)>>()
+        };
+        self.push(
+            format!("Start the find from {}", element),
+            &[(element, ElementState::Active)],
+            &[],
+            steps,
+        );
+        while !self.is_root(current) {
+            current = self.parents[current];
+            path.push(current);
+            let mut nodes = path_states(&path);
+            nodes.push((current, ElementState::Active));
+            self.push(
+                format!(
+                    "Follow the pointer of {} to {}",
+                    path[path.len() - 2],
+                    current
+                ),
+                &nodes,
+                &path_states(&path[..path.len() - 1]),
+                steps,
+            );
+        }
+        let root = current;
+        let mut nodes = path_states(&path);
+        nodes.push((root, ElementState::Selected));
+        self.push(
+            format!("{} points to itself, so it is the root of the set", root),
+            &nodes,
+            &path_states(&path[..path.len() - 1]),
+            steps,
+        );
+
+        // Path compression
+        let compressed = path
+            .iter()
+            .copied()
+            .filter(|element| self.parents[*element] != root && *element != root)
+            .collect::<Vec<usize>>();
+        for element in compressed.iter() {
+            let old_parent = self.parents[*element];
+            self.parents[*element] = root;
+            self.push(
+                format!(
+                    "Path compression: point {} directly to the root {} instead of {}",
+                    element, root, old_parent
+                ),
+                &[
+                    (*element, ElementState::Active),
+                    (root, ElementState::Selected),
+                ],
+                &[(*element, ElementState::Active)],
+                steps,
+            );
+        }
+        if !compressed.is_empty() {
+            self.push(
+                format!(
+                    "Every element on the path now points to {}, so later finds from them take one step",
+                    root
+                ),
+                &[(root, ElementState::Selected)],
+                &[],
+                steps,
+            );
+        }
+        root
+    }
+    fn union(&mut self, a: usize, b: usize, steps: &mut DiagramSteps) -> Result<(), String> {
+        self.check(a)?;
+        self.check(b)?;
+        let root_a = self.find(a, steps);
+        let root_b = self.find(b, steps);
+        if root_a == root_b {
+            self.push(
+                format!(
+                    "{} and {} have the same root, so they are already in the same set",
+                    a, b
+                ),
+                &[(root_a, ElementState::Conflict)],
+                &[],
+                steps,
+            );
+            return Ok(());
+        }
+
+        let (rank_a, rank_b) = (self.ranks[root_a], self.ranks[root_b]);
+        let (child, parent) = if rank_a < rank_b {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+        self.push(
+            if rank_a == rank_b {
+                format!(
+                    "The roots {} and {} have the same rank {}, so {} goes under {}, and the rank of {} grows by one",
+                    root_a, root_b, rank_a, child, parent, parent
+                )
+            } else {
+                format!(
+                    "The root {} has rank {} and {} has rank {}, so the shorter tree of {} goes under {}",
+                    root_a, rank_a, root_b, rank_b, child, parent
+                )
+            },
+            &[(root_a, ElementState::Active), (root_b, ElementState::Active)],
+            &[],
+            steps,
+        );
+        self.parents[child] = parent;
+        if rank_a == rank_b {
+            self.ranks[parent] += 1;
+        }
+        self.push(
+            format!("Point {} to {}, which joins the sets", child, parent),
+            &[
+                (child, ElementState::Selected),
+                (parent, ElementState::Selected),
+            ],
+            &[(child, ElementState::Selected)],
+            steps,
+        );
+        Ok(())
+    }
+    /// Draws the forest with the states of the elements and the pointers, which are keyed by the elements
+    /// that they start from.
+    fn draw(&self, nodes: &[(usize, ElementState)], edges: &[(usize, ElementState)]) -> Diagram {
+        let nodes = nodes
+            .iter()
+            .copied()
+            .collect::<BTreeMap<usize, ElementState>>();
+        let edges = edges
+            .iter()
+            .copied()
+            .collect::<BTreeMap<usize, ElementState>>();
+        let layout = tree_layout(&self.roots(), |element| self.children(element));
+        let positions = fit_layout(&layout, (TREE_WIDTH, TREE_HEIGHT), (50.0, 80.0));
+        let mut diagram = Diagram::new(TREE_WIDTH, TREE_HEIGHT);
+
+        for element in 0..self.parents.len() {
+            let mut node = DiagramNode::circle(node_key(element), element, positions[&element]);
+            if self.is_root(element) {
+                node.note = Some(format!("rank {}", self.ranks[element]));
+            }
+            node.state = nodes.get(&element).copied();
+            diagram.add_node(node);
+
+            if !self.is_root(element) {
+                let mut edge =
+                    DiagramEdge::arrow(node_key(element), node_key(self.parents[element]));
+                edge.state = edges.get(&element).copied();
+                diagram.add_edge(edge);
+            }
+        }
+        diagram
+    }
+}
+impl Visualizer for UnionFind {
+    fn diagram(&self) -> Diagram {
+        self.draw(&[], &[])
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let max = self.parents.len() as i64 - 1;
+        let element_input = |label, default| Input::Number {
+            label,
+            default,
+            min: 0,
+            max,
+        };
+        vec![
+            Operation::new(
+                "Union",
+                vec![
+                    element_input("First element", 4.min(max)),
+                    element_input("Second element", 7.min(max)),
+                ],
+            ),
+            Operation::new("Find", vec![element_input("Element to find", 3.min(max))]),
+            Operation::new(
+                "Reset",
+                vec![Input::Number {
+                    label: "Number of elements",
+                    default: self.parents.len() as i64,
+                    min: 1,
+                    max: MAX_ELEMENTS as i64,
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let number = |index: usize| {
+            values
+                .get(index)
+                .map(|value| value.number())
+                .unwrap_or_default()
+                .max(0) as usize
+        };
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Union" => self.union(number(0), number(1), &mut steps)?,
+            "Find" => {
+                self.check(number(0))?;
+                self.find(number(0), &mut steps);
+            }
+            "Reset" => {
+                *self = Self::new(number(0));
+                self.push(
+                    format!("Start with {} sets of one element each", self.parents.len()),
+                    &[],
+                    &[],
+                    &mut steps,
+                );
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
@@ -0,0 +1,113 @@
+//! Charts of values that are drawn with the figures and nodes of a diagram, like plots of functions and bar charts.
+use crate::{Diagram, DiagramNode, Figure, FigureKind};
+
+/// A chart in a part of a diagram. Values are placed from the smallest ones at the bottom left corner to the largest
+/// ones at the top right corner, so the y axis points up, unlike on the screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Chart {
+    /// The top left corner of the chart in the diagram.
+    pub position: (f32, f32),
+    pub size: (f32, f32),
+    /// The smallest values on the x and y axes, which are zero unless they are set with [`Chart::with_min`].
+    pub min: (f32, f32),
+    /// The largest values on the x and y axes.
+    pub max: (f32, f32),
+}
+impl Chart {
+    pub fn new(position: (f32, f32), size: (f32, f32), max: (f32, f32)) -> Self {
+        Self {
+            position,
+            size,
+            min: (0.0, 0.0),
+            max,
+        }
+    }
+    /// Sets the smallest values on the axes, e.g. to show negative values.
+    pub fn with_min(mut self, min: (f32, f32)) -> Self {
+        self.min = min;
+        self
+    }
+    /// The position of a value in the diagram. Values outside of the chart are kept at its edges.
+    pub fn point(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        (
+            self.position.0
+                + ((x - self.min.0) / (self.max.0 - self.min.0)).clamp(0.0, 1.0) * self.size.0,
+            self.position.1 + self.size.1
+                - ((y - self.min.1) / (self.max.1 - self.min.1)).clamp(0.0, 1.0) * self.size.1,
+        )
+    }
+    /// The value at a position in the diagram, or `None` if the position is outside of the chart.
+    pub fn value(&self, (x, y): (f32, f32)) -> Option<(f32, f32)> {
+        let (fx, fy) = (
+            (x - self.position.0) / self.size.0,
+            (self.position.1 + self.size.1 - y) / self.size.1,
+        );
+        ((0.0..=1.0).contains(&fx) && (0.0..=1.0).contains(&fy)).then_some((
+            self.min.0 + fx * (self.max.0 - self.min.0),
+            self.min.1 + fy * (self.max.1 - self.min.1),
+        ))
+    }
+    /// Draws the axes with a title above them and labels at their ends. The corner is labeled with the smallest value
+    /// on the y axis, and if it is negative, the zero of the y axis is drawn as a dashed line. The keys of the labels
+    /// start with the key.
+    pub fn draw_axes(
+        &self,
+        diagram: &mut Diagram,
+        key: &str,
+        title: impl ToString,
+        labels: (impl ToString, impl ToString),
+    ) {
+        let (left, top) = self.position;
+        let (right, bottom) = (left + self.size.0, top + self.size.1);
+        diagram.add_figure(Figure::new(FigureKind::Polyline(vec![
+            (left, top),
+            (left, bottom),
+            (right, bottom),
+        ])));
+        diagram.add_node(DiagramNode::text(
+            format!("{}-title", key),
+            title,
+            (left + self.size.0 / 2.0, top - 20.0),
+        ));
+        diagram.add_node(DiagramNode::text(
+            format!("{}-min", key),
+            // Rounded to two decimals, since the smallest value is often computed
+            (self.min.1 * 100.0).round() / 100.0,
+            (left - 14.0, bottom + 14.0),
+        ));
+        if self.min.1 < 0.0 {
+            let (_, zero) = self.point((0.0, 0.0));
+            diagram.add_figure(Figure::new(FigureKind::Line((left, zero), (right, zero))).dashed());
+        }
+        diagram.add_node(DiagramNode::text(
+            format!("{}-x", key),
+            labels.0,
+            (right, bottom + 16.0),
+        ));
+        diagram.add_node(DiagramNode::text(
+            format!("{}-y", key),
+            labels.1,
+            (left - 24.0, top),
+        ));
+    }
+    /// Draws a line through the values in order.
+    pub fn draw_line<'a>(&self, diagram: &'a mut Diagram, values: &[(f32, f32)]) -> &'a mut Figure {
+        diagram.add_figure(Figure::new(FigureKind::Polyline(
+            values.iter().map(|value| self.point(*value)).collect(),
+        )))
+    }
+    /// Draws a bar from zero to the value, centered on its x and as wide as `width` on the x axis.
+    pub fn draw_bar<'a>(
+        &self,
+        diagram: &'a mut Diagram,
+        (x, y): (f32, f32),
+        width: f32,
+    ) -> &'a mut Figure {
+        let (left, end) = self.point((x - width / 2.0, y));
+        let (right, zero) = self.point((x + width / 2.0, 0.0));
+        diagram.add_figure(Figure::new(FigureKind::Rect {
+            position: (left, end.min(zero)),
+            size: (right - left, (end - zero).abs()),
+        }))
+    }
+}
@@ -0,0 +1,91 @@
+//! Ways to place the nodes of trees. The layouts are in units of one horizontal slot and one level,
+//! and [`fit_layout`] scales them to a diagram.
+use std::collections::BTreeMap;
+
+use crate::NODE_RADIUS;
+
+/// Places the nodes of a binary tree in the order of an in-order traversal, so that the values of a search tree
+/// are sorted from left to right. `children` gives the left and right children of a node.
+pub fn binary_tree_layout(
+    root: Option<usize>,
+    children: impl Fn(usize) -> (Option<usize>, Option<usize>),
+) -> BTreeMap<usize, (f32, f32)> {
+    fn place(
+        node: usize,
+        depth: usize,
+        children: &impl Fn(usize) -> (Option<usize>, Option<usize>),
+        layout: &mut BTreeMap<usize, (f32, f32)>,
+    ) {
+        let (left, right) = children(node);
+        if let Some(left) = left {
+            place(left, depth + 1, children, layout);
+        }
+        layout.insert(node, (layout.len() as f32, depth as f32));
+        if let Some(right) = right {
+            place(right, depth + 1, children, layout);
+        }
+    }
+
+    let mut layout = BTreeMap::new();
+    if let Some(root) = root {
+        place(root, 0, &children, &mut layout);
+    }
+    layout
+}
+
+/// Places the trees of a forest next to each other so that their leaves are evenly spaced and
+/// every parent is centered above its children. `children` gives the children of a node in order.
+pub fn tree_layout(
+    roots: &[usize],
+    children: impl Fn(usize) -> Vec<usize>,
+) -> BTreeMap<usize, (f32, f32)> {
+    // Returns the x of the node
+    fn place(
+        node: usize,
+        depth: usize,
+        next_leaf_x: &mut f32,
+        children: &impl Fn(usize) -> Vec<usize>,
+        layout: &mut BTreeMap<usize, (f32, f32)>,
+    ) -> f32 {
+        let child_xs = children(node)
+            .into_iter()
+            .map(|child| place(child, depth + 1, next_leaf_x, children, layout))
+            .collect::<Vec<f32>>();
+        let x = match (child_xs.first(), child_xs.last()) {
+            (Some(first), Some(last)) => (first + last) / 2.0,
+            _ => {
+                *next_leaf_x += 1.0;
+                *next_leaf_x - 1.0
+            }
+        };
+        layout.insert(node, (x, depth as f32));
+        x
+    }
+
+    let mut layout = BTreeMap::new();
+    let mut next_leaf_x = 0.0;
+    for root in roots {
+        place(*root, 0, &mut next_leaf_x, &children, &mut layout);
+    }
+    layout
+}
+
+/// Scales a layout to a diagram of the given size, with at most `max_spacing` between neighboring slots and levels.
+/// The layout is centered horizontally and starts from the top of the diagram.
+pub fn fit_layout(
+    layout: &BTreeMap<usize, (f32, f32)>,
+    (width, height): (f32, f32),
+    (max_spacing_x, max_spacing_y): (f32, f32),
+) -> BTreeMap<usize, (f32, f32)> {
+    let margin = NODE_RADIUS * 2.0;
+    let max_x = layout.values().map(|(x, _)| *x).fold(0.0, f32::max);
+    let max_y = layout.values().map(|(_, y)| *y).fold(0.0, f32::max);
+    let spacing_x = max_spacing_x.min((width - margin * 2.0) / max_x.max(1.0));
+    let spacing_y = max_spacing_y.min((height - margin * 2.0) / max_y.max(1.0));
+    let left = (width - max_x * spacing_x) / 2.0;
+
+    layout
+        .iter()
+        .map(|(node, (x, y))| (*node, (left + x * spacing_x, margin + y * spacing_y)))
+        .collect()
+}
@@ -0,0 +1,468 @@
+//! This crate contains the drawings that visualizers of data structures and other algorithms record at every step,
+//! along with the operations that the visualizers can run. A drawing is made of nodes, the edges between them and
+//! free-standing figures, and the nodes keep their keys between steps so that their movements can be animated.
+mod chart;
+mod layout;
+mod table;
+
+use std::{fmt::Display, str::FromStr};
+
+pub use chart::Chart;
+pub use graph::{ElementState, StepList};
+pub use layout::{binary_tree_layout, fit_layout, tree_layout};
+pub use table::Table;
+
+/// The radius of the circles of nodes that are made with [`DiagramNode::circle`].
+pub const NODE_RADIUS: f32 = 20.0;
+
+/// How a node is drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shape {
+    Circle {
+        radius: f32,
+    },
+    Rect {
+        width: f32,
+        height: f32,
+    },
+    /// Only the label is drawn, e.g. the name of a pointer.
+    Text,
+}
+
+/// The color that a node is filled with when it doesn't have a state.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Fill {
+    #[default]
+    Default,
+    Red,
+    Black,
+    /// The color of a group, like the colors of node groups in graphs.
+    Group(usize),
+    /// A value between 0 and 1 that is shown from cold blue to hot red.
+    Heat(f32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagramNode {
+    /// Identifies the node between steps. Nodes with the same key are animated from their old positions to the new ones.
+    pub key: String,
+    pub label: String,
+    /// Small text below the node, like its index or balance factor.
+    pub note: Option<String>,
+    /// The center of the node.
+    pub position: (f32, f32),
+    pub shape: Shape,
+    pub fill: Fill,
+    pub state: Option<ElementState>,
+}
+impl DiagramNode {
+    pub fn new(
+        key: impl Into<String>,
+        label: impl ToString,
+        position: (f32, f32),
+        shape: Shape,
+    ) -> Self {
+        Self {
+            key: key.into(),
+            label: label.to_string(),
+            note: None,
+            position,
+            shape,
+            fill: Fill::Default,
+            state: None,
+        }
+    }
+    pub fn circle(key: impl Into<String>, label: impl ToString, position: (f32, f32)) -> Self {
+        Self::new(
+            key,
+            label,
+            position,
+            Shape::Circle {
+                radius: NODE_RADIUS,
+            },
+        )
+    }
+    pub fn rect(
+        key: impl Into<String>,
+        label: impl ToString,
+        position: (f32, f32),
+        (width, height): (f32, f32),
+    ) -> Self {
+        Self::new(key, label, position, Shape::Rect { width, height })
+    }
+    pub fn text(key: impl Into<String>, label: impl ToString, position: (f32, f32)) -> Self {
+        Self::new(key, label, position, Shape::Text)
+    }
+    pub fn with_note(mut self, note: impl ToString) -> Self {
+        self.note = Some(note.to_string());
+        self
+    }
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.fill = fill;
+        self
+    }
+    pub fn with_state(mut self, state: ElementState) -> Self {
+        self.state = Some(state);
+        self
+    }
+}
+
+/// A line from one node to another, like a pointer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagramEdge {
+    pub from: String,
+    pub to: String,
+    pub label: Option<String>,
+    /// True if the edge is an arrow that points to the second node.
+    pub directed: bool,
+    pub dashed: bool,
+    /// How far the middle of the edge bends to its left, relative to its length, so that edges in both
+    /// directions between the same nodes don't overlap.
+    pub bend: f32,
+    pub state: Option<ElementState>,
+}
+impl DiagramEdge {
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+            label: None,
+            directed: false,
+            dashed: false,
+            bend: 0.0,
+            state: None,
+        }
+    }
+    /// An arrow from a node to another.
+    pub fn arrow(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            directed: true,
+            ..Self::new(from, to)
+        }
+    }
+    pub fn with_label(mut self, label: impl ToString) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+    pub fn dashed(mut self) -> Self {
+        self.dashed = true;
+        self
+    }
+    pub fn with_bend(mut self, bend: f32) -> Self {
+        self.bend = bend;
+        self
+    }
+    pub fn with_state(mut self, state: ElementState) -> Self {
+        self.state = Some(state);
+        self
+    }
+}
+
+/// The geometry of a [`Figure`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum FigureKind {
+    Line((f32, f32), (f32, f32)),
+    Polyline(Vec<(f32, f32)>),
+    Polygon(Vec<(f32, f32)>),
+    Circle {
+        center: (f32, f32),
+        radius: f32,
+    },
+    /// A rectangle by its top left corner and its size.
+    Rect {
+        position: (f32, f32),
+        size: (f32, f32),
+    },
+}
+
+/// A shape that isn't connected to nodes, like a segment, a region or the plot of a function.
+/// Figures are drawn below the edges and the nodes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Figure {
+    pub kind: FigureKind,
+    pub dashed: bool,
+    pub state: Option<ElementState>,
+}
+impl Figure {
+    pub fn new(kind: FigureKind) -> Self {
+        Self {
+            kind,
+            dashed: false,
+            state: None,
+        }
+    }
+    pub fn dashed(mut self) -> Self {
+        self.dashed = true;
+        self
+    }
+    pub fn with_state(mut self, state: ElementState) -> Self {
+        self.state = Some(state);
+        self
+    }
+}
+
+/// A drawing of the state of a data structure or an algorithm. The coordinates are in the units of `width` and `height`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Diagram {
+    pub width: f32,
+    pub height: f32,
+    pub figures: Vec<Figure>,
+    pub edges: Vec<DiagramEdge>,
+    pub nodes: Vec<DiagramNode>,
+}
+impl Diagram {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+    pub fn add_node(&mut self, node: DiagramNode) -> &mut DiagramNode {
+        self.nodes.push(node);
+        self.nodes.last_mut().unwrap()
+    }
+    pub fn add_edge(&mut self, edge: DiagramEdge) -> &mut DiagramEdge {
+        self.edges.push(edge);
+        self.edges.last_mut().unwrap()
+    }
+    pub fn add_figure(&mut self, figure: Figure) -> &mut Figure {
+        self.figures.push(figure);
+        self.figures.last_mut().unwrap()
+    }
+    pub fn node_mut(&mut self, key: &str) -> Option<&mut DiagramNode> {
+        self.nodes.iter_mut().find(|node| node.key == key)
+    }
+    pub fn edge_mut(&mut self, from: &str, to: &str) -> Option<&mut DiagramEdge> {
+        self.edges
+            .iter_mut()
+            .find(|edge| edge.from == from && edge.to == to)
+    }
+    /// Sets the state of a node if it is in the diagram.
+    pub fn set_node_state(&mut self, key: &str, state: ElementState) {
+        if let Some(node) = self.node_mut(key) {
+            node.state = Some(state);
+        }
+    }
+    /// Sets the state of an edge if it is in the diagram.
+    pub fn set_edge_state(&mut self, from: &str, to: &str, state: ElementState) {
+        if let Some(edge) = self.edge_mut(from, to) {
+            edge.state = Some(state);
+        }
+    }
+}
+
+/// The state of the diagram at a step of an operation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiagramStep {
+    /// What happened during the step.
+    pub description: String,
+    pub diagram: Diagram,
+    pub lists: Vec<StepList>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiagramSteps {
+    pub steps: Vec<DiagramStep>,
+}
+impl DiagramSteps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds a step that shows the given diagram.
+    pub fn push(&mut self, description: String, diagram: Diagram) -> &mut DiagramStep {
+        self.steps.push(DiagramStep {
+            description,
+            diagram,
+            lists: vec![],
+        });
+        self.steps.last_mut().unwrap()
+    }
+    /// Starts a new step that is a copy of the last one without its [`ElementState::Active`] elements.
+    pub fn init_step(&mut self, description: String) -> &mut DiagramStep {
+        let mut step = self.steps.last().cloned().unwrap_or_default();
+        step.description = description;
+        let diagram = &mut step.diagram;
+        for node in diagram.nodes.iter_mut() {
+            node.state = node.state.filter(|state| *state != ElementState::Active);
+        }
+        for edge in diagram.edges.iter_mut() {
+            edge.state = edge.state.filter(|state| *state != ElementState::Active);
+        }
+        for figure in diagram.figures.iter_mut() {
+            figure.state = figure.state.filter(|state| *state != ElementState::Active);
+        }
+        self.steps.push(step);
+        self.steps.last_mut().unwrap()
+    }
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// A value that an operation needs, which can be chosen before running it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    Number {
+        label: &'static str,
+        default: i64,
+        min: i64,
+        max: i64,
+    },
+    Text {
+        label: &'static str,
+        default: String,
+    },
+    Choice {
+        label: &'static str,
+        choices: Vec<String>,
+    },
+    /// A decimal number that is chosen with a slider, like a learning rate.
+    Slider {
+        label: &'static str,
+        default: f64,
+        min: f64,
+        max: f64,
+        step: f64,
+    },
+}
+impl Input {
+    /// The label of the input, which should be unique among all of the inputs of a visualizer.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Input::Number { label, .. }
+            | Input::Text { label, .. }
+            | Input::Choice { label, .. }
+            | Input::Slider { label, .. } => label,
+        }
+    }
+    /// The value that the input has before it is changed.
+    pub fn default_value(&self) -> Value {
+        match self {
+            Input::Number { default, .. } => Value::Number(*default),
+            Input::Text { default, .. } => Value::Text(default.clone()),
+            Input::Choice { choices, .. } => {
+                Value::Text(choices.first().cloned().unwrap_or_default())
+            }
+            Input::Slider { default, .. } => Value::Decimal(*default),
+        }
+    }
+}
+
+/// The chosen value of an [`Input`]. Choices are given as the texts of the chosen options.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Number(i64),
+    Text(String),
+    Decimal(f64),
+}
+impl Value {
+    pub fn number(&self) -> i64 {
+        match self {
+            Value::Number(number) => *number,
+            Value::Text(text) => text.trim().parse().unwrap_or_default(),
+            Value::Decimal(decimal) => decimal.round() as i64,
+        }
+    }
+    pub fn text(&self) -> String {
+        match self {
+            Value::Number(number) => number.to_string(),
+            Value::Text(text) => text.clone(),
+            Value::Decimal(decimal) => decimal.to_string(),
+        }
+    }
+    pub fn decimal(&self) -> f64 {
+        match self {
+            Value::Number(number) => *number as f64,
+            Value::Text(text) => text.trim().parse().unwrap_or_default(),
+            Value::Decimal(decimal) => *decimal,
+        }
+    }
+}
+
+/// Reads a number input and checks that it is between the bounds.
+pub fn parse_number(value: Option<&Value>, name: &str, min: u64, max: u64) -> Result<u64, String> {
+    let number = value.map(|value| value.number()).unwrap_or(0);
+    if number < min as i64 || number > max as i64 {
+        return Err(format!("The {} must be between {} and {}", name, min, max));
+    }
+    Ok(number as u64)
+}
+
+/// Reads a list of numbers that are separated by commas or spaces, like "3, 1, 4", and checks that every number
+/// is between the bounds and that there are 1 to `max_len` of them.
+pub fn parse_values<T: FromStr + PartialOrd + Display>(
+    text: &str,
+    min: T,
+    max: T,
+    max_len: usize,
+) -> Result<Vec<T>, String> {
+    let values = text
+        .split([',', ' '])
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| {
+            part.trim()
+                .parse::<T>()
+                .ok()
+                .filter(|value| *value >= min && *value <= max)
+                .ok_or_else(|| {
+                    format!(
+                        "\"{}\" isn't a whole number between {} and {}",
+                        part.trim(),
+                        min,
+                        max
+                    )
+                })
+        })
+        .collect::<Result<Vec<T>, String>>()?;
+    if values.is_empty() || values.len() > max_len {
+        return Err(format!("There must be 1 to {} values", max_len));
+    }
+    Ok(values)
+}
+
+/// The count followed by the word, which gets an "s" unless the count is one.
+pub fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        format!("1 {}", word)
+    } else {
+        format!("{} {}s", count, word)
+    }
+}
+
+/// Something that can be run on a visualizer, like inserting a value into a tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Operation {
+    pub name: &'static str,
+    pub inputs: Vec<Input>,
+}
+impl Operation {
+    pub fn new(name: &'static str, inputs: Vec<Input>) -> Self {
+        Self { name, inputs }
+    }
+}
+
+/// A data structure or an algorithm whose operations are visualized step by step. It keeps its state between
+/// operations, so e.g. the values that are inserted into a tree stay there.
+pub trait Visualizer {
+    /// Draws the current state without any highlights.
+    fn diagram(&self) -> Diagram;
+    /// The operations that can be run. They can change after running operations.
+    fn operations(&self) -> Vec<Operation>;
+    /// Runs the operation with the given name with values for each of its inputs in order.
+    /// Returns an explanation if the operation can't be run with the values.
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String>;
+    /// True if the diagram can be clicked, like a canvas where points are placed.
+    fn clickable(&self) -> bool {
+        false
+    }
+    /// Handles a click at a position in the coordinates of the diagram. Returns `None` if the state didn't change, or
+    /// the steps of the change, which are played like the steps of an operation. They are empty if there is nothing
+    /// to animate, like when a point is placed.
+    fn click(&mut self, _position: (f32, f32)) -> Option<DiagramSteps> {
+        None
+    }
+}
@@ -0,0 +1,61 @@
+//! Tables of values that are drawn as rows of cells in a diagram, with a header above every column.
+use crate::{Diagram, DiagramNode, ElementState};
+
+/// A table of numbers in a part of a diagram, with a header above every column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Table {
+    /// The top left corner of the table in the diagram.
+    pub position: (f32, f32),
+    pub column_width: f32,
+    pub headers: Vec<&'static str>,
+}
+impl Table {
+    pub const ROW_HEIGHT: f32 = 30.0;
+
+    pub fn new(position: (f32, f32), column_width: f32, headers: &[&'static str]) -> Self {
+        Self {
+            position,
+            column_width,
+            headers: headers.to_vec(),
+        }
+    }
+    /// How tall the table is with the headers and the number of rows.
+    pub fn height(&self, rows: usize) -> f32 {
+        (rows + 1) as f32 * Self::ROW_HEIGHT
+    }
+    /// Draws the headers and the rows of cells with their states. The keys of the cells start with the key, and empty
+    /// cells are drawn without a label.
+    pub fn draw(
+        &self,
+        diagram: &mut Diagram,
+        key: &str,
+        rows: &[Vec<(String, Option<ElementState>)>],
+    ) {
+        let (left, top) = self.position;
+        let center = |row: usize, column: usize| {
+            (
+                left + (column as f32 + 0.5) * self.column_width,
+                top + (row as f32 + 0.5) * Self::ROW_HEIGHT,
+            )
+        };
+        for (column, header) in self.headers.iter().enumerate() {
+            diagram.add_node(DiagramNode::text(
+                format!("{}-header-{}", key, column),
+                header,
+                center(0, column),
+            ));
+        }
+        for (row, cells) in rows.iter().enumerate() {
+            for (column, (label, state)) in cells.iter().enumerate() {
+                let mut node = DiagramNode::rect(
+                    format!("{}-{}-{}", key, row, column),
+                    label,
+                    center(row + 1, column),
+                    (self.column_width - 4.0, Self::ROW_HEIGHT - 4.0),
+                );
+                node.state = *state;
+                diagram.add_node(node);
+            }
+        }
+    }
+}
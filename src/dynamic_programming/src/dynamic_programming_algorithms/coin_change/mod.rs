@@ -0,0 +1,517 @@
+use diagram::*;
+
+use crate::*;
+
+const MAX_AMOUNT: u64 = 20;
+const MAX_COINS: usize = 6;
+const CANVAS_HEIGHT: f32 = 360.0;
+const ROW_POSITION: (f32, f32) = (150.0, 240.0);
+const CELL_HEIGHT: f32 = 36.0;
+const MAX_CELL_WIDTH: f32 = 56.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Variant {
+    FewestCoins,
+    CountWays,
+}
+
+/// The highlights of the current step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    cells: Vec<(usize, ElementState)>,
+    /// Arrows from the amount that is left after a coin to the amount, and whether the arrow was used.
+    arrows: Vec<(usize, usize, bool)>,
+}
+
+/// The coin change problem, where an amount is paid with coins of given values, and there is an unlimited number of
+/// coins of every value. The table has a cell for every amount from zero up, and every cell combines the cells of
+/// the amounts that are left after paying one of the coins, either to find the fewest coins or to count the ways to
+/// pay the amount.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoinChange {
+    /// The values of the coins from the smallest to the largest.
+    coins: Vec<usize>,
+    amount: usize,
+}
+impl CoinChange {
+    pub fn new(coins: &[usize], amount: usize) -> Self {
+        Self {
+            coins: coins.to_vec(),
+            amount,
+        }
+    }
+    fn cell_width(&self) -> f32 {
+        ((CANVAS_WIDTH - ROW_POSITION.0 - 20.0) / (self.amount + 1) as f32).min(MAX_CELL_WIDTH)
+    }
+    /// Draws the cells with their values, and the last coins of the fewest coins below them. The amounts up to
+    /// `looked_at` that have no value can't be paid.
+    fn draw(
+        &self,
+        variant: Variant,
+        looked_at: usize,
+        values: &[Option<u64>],
+        last_coins: &[Option<usize>],
+        highlights: &Highlights,
+    ) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let (left, y) = ROW_POSITION;
+        let width = self.cell_width();
+        let x = |amount: usize| left + (amount as f32 + 0.5) * width;
+        diagram.add_node(DiagramNode::text(
+            "row-title",
+            match variant {
+                Variant::FewestCoins => "Fewest coins",
+                Variant::CountWays => "Ways",
+            },
+            (left - 70.0, y),
+        ));
+        for (amount, value) in values.iter().enumerate() {
+            let mut node = DiagramNode::rect(
+                format!("cell-{}", amount),
+                match value {
+                    Some(value) => value.to_string(),
+                    None if amount <= looked_at => "∞".to_string(),
+                    None => String::new(),
+                },
+                (x(amount), y),
+                (width - 4.0, CELL_HEIGHT - 8.0),
+            )
+            .with_note(amount);
+            node.state = highlights
+                .cells
+                .iter()
+                .find(|(cell, _)| *cell == amount)
+                .map(|(_, state)| *state);
+            diagram.add_node(node);
+        }
+        if variant == Variant::FewestCoins {
+            diagram.add_node(DiagramNode::text(
+                "last-coin-title",
+                "Last coin",
+                (left - 70.0, y + 60.0),
+            ));
+            for (amount, coin) in last_coins.iter().enumerate() {
+                if let Some(coin) = coin {
+                    diagram.add_node(DiagramNode::text(
+                        format!("last-coin-{}", amount),
+                        coin,
+                        (x(amount), y + 60.0),
+                    ));
+                }
+            }
+        }
+        for (from, to, used) in &highlights.arrows {
+            let coin = to - from;
+            let edge = DiagramEdge::arrow(format!("cell-{}", from), format!("cell-{}", to))
+                .with_label(format!("+{}", coin))
+                // Arrows of larger coins are drawn higher, so that their labels don't overlap
+                .with_bend((40.0 + 8.0 * coin as f32) / (coin as f32 * width));
+            diagram.add_edge(if *used {
+                edge.with_state(ElementState::Selected)
+            } else {
+                edge.dashed()
+            });
+        }
+        diagram
+    }
+    fn push(
+        &self,
+        description: String,
+        diagram: Diagram,
+        combination: Option<&[usize]>,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, diagram);
+        step.lists = vec![StepList::new(
+            "Coins",
+            self.coins.iter().map(|coin| coin.to_string()).collect(),
+        )];
+        if let Some(combination) = combination {
+            step.lists.push(StepList::new(
+                "Combination",
+                combination.iter().map(|coin| coin.to_string()).collect(),
+            ));
+        }
+    }
+    fn fewest_coins(&self, steps: &mut DiagramSteps) {
+        let variant = Variant::FewestCoins;
+        let mut values = vec![None; self.amount + 1];
+        let mut last_coins: Vec<Option<usize>> = vec![None; self.amount + 1];
+        values[0] = Some(0);
+        self.push(
+            format!(
+                "Find the fewest coins that pay every amount from 0 to {}. Paying 0 takes no coins, and every other amount is paid by a last coin after paying the amount that is left before it",
+                self.amount
+            ),
+            self.draw(
+                variant,
+                0,
+                &values,
+                &last_coins,
+                &Highlights {
+                    cells: vec![(0, ElementState::Active)],
+                    ..Default::default()
+                },
+            ),
+            None,
+            steps,
+        );
+        let mut checks = 0;
+        for amount in 1..=self.amount {
+            let candidates = self
+                .coins
+                .iter()
+                .filter(|coin| **coin <= amount)
+                .map(|coin| (*coin, values[amount - coin].map(|count| count + 1)))
+                .collect::<Vec<(usize, Option<u64>)>>();
+            checks += candidates.len();
+            let best = candidates
+                .iter()
+                .filter_map(|(coin, count)| count.map(|count| (count, *coin)))
+                .min();
+            if let Some((count, coin)) = best {
+                values[amount] = Some(count);
+                last_coins[amount] = Some(coin);
+            }
+            let mut highlights = Highlights {
+                cells: vec![(amount, ElementState::Active)],
+                arrows: vec![],
+            };
+            for (coin, _) in &candidates {
+                highlights
+                    .cells
+                    .push((amount - coin, ElementState::Frontier));
+                highlights.arrows.push((
+                    amount - coin,
+                    amount,
+                    best.is_some_and(|(_, best)| best == *coin),
+                ));
+            }
+            let options = candidates
+                .iter()
+                .map(|(coin, count)| match count {
+                    Some(count) => format!(
+                        "a {} after {} takes {} + 1 = {}",
+                        coin,
+                        amount - coin,
+                        count - 1,
+                        count
+                    ),
+                    None => format!("{} − {} = {} can't be paid", amount, coin, amount - coin),
+                })
+                .collect::<Vec<String>>();
+            let description = match best {
+                _ if candidates.is_empty() => {
+                    format!("Every coin is larger than {}, so it can't be paid", amount)
+                }
+                None => format!(
+                    "For {}, {}. So {} can't be paid either",
+                    amount,
+                    join(&options),
+                    amount
+                ),
+                Some((count, coin)) => format!(
+                    "For {}, {}. The fewest is {} with a last coin of {}",
+                    amount,
+                    join(&options),
+                    plural(count as usize, "coin"),
+                    coin
+                ),
+            };
+            let diagram = self.draw(variant, amount, &values, &last_coins, &highlights);
+            self.push(description, diagram, None, steps);
+        }
+        let Some(total) = values[self.amount] else {
+            self.push(
+                format!(
+                    "{} can't be paid with the coins {}",
+                    self.amount,
+                    coin_list(&self.coins)
+                ),
+                self.draw(
+                    variant,
+                    self.amount,
+                    &values,
+                    &last_coins,
+                    &Highlights {
+                        cells: vec![(self.amount, ElementState::Conflict)],
+                        ..Default::default()
+                    },
+                ),
+                Some(&[]),
+                steps,
+            );
+            return;
+        };
+        // Reconstruct the coins by following the last coins back to zero.
+        let mut combination = vec![];
+        let mut path = vec![self.amount];
+        let mut arrows = vec![];
+        let mut amount = self.amount;
+        while amount > 0 {
+            let coin = last_coins[amount].unwrap();
+            combination.push(coin);
+            arrows.push((amount - coin, amount, true));
+            let description = format!(
+                "The last coin of {} is {}, so pay a {} and continue from {} − {} = {}",
+                amount,
+                coin,
+                coin,
+                amount,
+                coin,
+                amount - coin
+            );
+            amount -= coin;
+            path.push(amount);
+            let mut cells = path
+                .iter()
+                .map(|cell| (*cell, ElementState::Selected))
+                .collect::<Vec<(usize, ElementState)>>();
+            cells.insert(0, (amount, ElementState::Active));
+            self.push(
+                description,
+                self.draw(
+                    variant,
+                    self.amount,
+                    &values,
+                    &last_coins,
+                    &Highlights {
+                        cells,
+                        arrows: arrows.clone(),
+                    },
+                ),
+                Some(&combination),
+                steps,
+            );
+        }
+        self.push(
+            format!(
+                "{} is paid with {}: {}. Filling the table took {}, one for every coin that fits in an amount",
+                self.amount,
+                plural(total as usize, "coin"),
+                combination
+                    .iter()
+                    .map(|coin| coin.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" + "),
+                plural(checks, "check")
+            ),
+            self.draw(
+                variant,
+                self.amount,
+                &values,
+                &last_coins,
+                &Highlights {
+                    cells: path
+                        .iter()
+                        .map(|cell| (*cell, ElementState::Selected))
+                        .collect(),
+                    arrows,
+                },
+            ),
+            Some(&combination),
+            steps,
+        );
+    }
+    fn count_ways(&self, steps: &mut DiagramSteps) {
+        let variant = Variant::CountWays;
+        let mut values = vec![Some(0); self.amount + 1];
+        values[0] = Some(1);
+        let last_coins = vec![None; self.amount + 1];
+        self.push(
+            format!(
+                "Count the ways to pay every amount from 0 to {}, without caring about the order of the coins. Before any coins are used, only 0 can be paid, in one way with no coins",
+                self.amount
+            ),
+            self.draw(
+                variant,
+                self.amount,
+                &values,
+                &last_coins,
+                &Highlights {
+                    cells: vec![(0, ElementState::Active)],
+                    ..Default::default()
+                },
+            ),
+            None,
+            steps,
+        );
+        // Going through the coins in the outer loop counts every combination once, in the order of the coins.
+        for (i, coin) in self.coins.iter().enumerate() {
+            for amount in *coin..=self.amount {
+                let before = values[amount].unwrap();
+                let added = values[amount - coin].unwrap();
+                values[amount] = Some(before + added);
+                let allowed = match i {
+                    _ if amount != *coin => String::new(),
+                    0 => format!("Allow the coin {}. ", coin),
+                    1 => format!(
+                        "Allow the coin {} along with the smaller coin {}. ",
+                        coin, self.coins[0]
+                    ),
+                    _ => format!(
+                        "Allow the coin {} along with the smaller coins {}. ",
+                        coin,
+                        coin_list(&self.coins[..i])
+                    ),
+                };
+                let description = format!(
+                    "{}{} can {}be paid with a last coin of {} after {} to pay {} − {} = {}, so it has {} + {} = {}",
+                    allowed,
+                    amount,
+                    if before > 0 { "also " } else { "" },
+                    coin,
+                    match added {
+                        1 => "the one way".to_string(),
+                        _ => format!("any of the {} ways", added),
+                    },
+                    amount,
+                    coin,
+                    amount - coin,
+                    before,
+                    added,
+                    plural((before + added) as usize, "way")
+                );
+                self.push(
+                    description,
+                    self.draw(
+                        variant,
+                        self.amount,
+                        &values,
+                        &last_coins,
+                        &Highlights {
+                            cells: vec![
+                                (amount, ElementState::Active),
+                                (amount - coin, ElementState::Frontier),
+                            ],
+                            arrows: vec![(amount - coin, amount, true)],
+                        },
+                    ),
+                    None,
+                    steps,
+                );
+            }
+        }
+        let ways = values[self.amount].unwrap();
+        self.push(
+            format!(
+                "There {} {} to pay {} with the coins {}. Filling the table took {} updates",
+                if ways == 1 { "is" } else { "are" },
+                plural(ways as usize, "way"),
+                self.amount,
+                coin_list(&self.coins),
+                self.coins
+                    .iter()
+                    .map(|coin| (self.amount + 1).saturating_sub(*coin))
+                    .sum::<usize>()
+            ),
+            self.draw(
+                variant,
+                self.amount,
+                &values,
+                &last_coins,
+                &Highlights {
+                    cells: vec![(self.amount, ElementState::Selected)],
+                    ..Default::default()
+                },
+            ),
+            None,
+            steps,
+        );
+    }
+}
+impl Visualizer for CoinChange {
+    fn diagram(&self) -> Diagram {
+        let mut values = vec![None; self.amount + 1];
+        values[0] = Some(0);
+        self.draw(
+            Variant::FewestCoins,
+            0,
+            &values,
+            &vec![None; self.amount + 1],
+            &Highlights::default(),
+        )
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let inputs = vec![
+            Input::Text {
+                label: "Coins",
+                default: self
+                    .coins
+                    .iter()
+                    .map(|coin| coin.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            },
+            Input::Number {
+                label: "Amount",
+                default: self.amount as i64,
+                min: 1,
+                max: MAX_AMOUNT as i64,
+            },
+        ];
+        vec![
+            Operation::new("Fewest coins", inputs.clone()),
+            Operation::new("Count ways", inputs),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        let text = values.first().map(|value| value.text()).unwrap_or_default();
+        match operation {
+            "Fewest coins" | "Count ways" => {
+                let coins = parse_coins(&text)?;
+                let amount = parse_number(values.get(1), "amount", 1, MAX_AMOUNT)?;
+                *self = Self::new(&coins, amount as usize);
+                if operation == "Fewest coins" {
+                    self.fewest_coins(&mut steps);
+                } else {
+                    self.count_ways(&mut steps);
+                }
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// Joins the items with commas and an "and" before the last one.
+fn join(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+/// The values of the coins, like "1, 2 and 5".
+fn coin_list(coins: &[usize]) -> String {
+    join(
+        &coins
+            .iter()
+            .map(|coin| coin.to_string())
+            .collect::<Vec<String>>(),
+    )
+}
+
+/// Parses the values of the coins, which are separated by commas or spaces, from the smallest to the largest.
+fn parse_coins(text: &str) -> Result<Vec<usize>, String> {
+    let mut coins = text
+        .split([',', ' '])
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| part.trim().parse::<usize>())
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|_| "The coins must be numbers separated by spaces, like \"1 2 5\"".to_string())?;
+    coins.sort_unstable();
+    coins.dedup();
+    if coins.is_empty() || coins.len() > MAX_COINS {
+        return Err(format!("There must be 1 to {} different coins", MAX_COINS));
+    }
+    if coins
+        .iter()
+        .any(|coin| *coin == 0 || *coin as u64 > MAX_AMOUNT)
+    {
+        return Err(format!("The coins must be between 1 and {}", MAX_AMOUNT));
+    }
+    Ok(coins)
+}
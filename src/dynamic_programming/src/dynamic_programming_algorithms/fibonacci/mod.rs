@@ -0,0 +1,499 @@
+use diagram::*;
+
+use crate::*;
+
+/// The largest number whose call tree fits in the diagram, with 41 calls.
+const MAX_N: u64 = 7;
+const TREE_TOP: f32 = 50.0;
+const TREE_HEIGHT: f32 = 300.0;
+const CALL_RADIUS: f32 = 14.0;
+const TABLE_POSITION: (f32, f32) = (170.0, 400.0);
+const CELL_SIZE: (f32, f32) = (60.0, 36.0);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Method {
+    Naive,
+    Memoization,
+    BottomUp,
+}
+impl Method {
+    fn name(&self) -> &'static str {
+        match self {
+            Method::Naive => "Naive recursion",
+            Method::Memoization => "Memoization",
+            Method::BottomUp => "Bottom-up",
+        }
+    }
+}
+
+/// A call of the recursive function in the call tree.
+#[derive(Clone, Debug, PartialEq)]
+struct Call {
+    /// The call computes F(k).
+    k: usize,
+    children: Vec<usize>,
+    value: u64,
+    /// True if F(k) had already been computed by an earlier call when this call started.
+    repeated: bool,
+}
+
+/// What happens at a step of the recursion. Calls without children return in the same step that they start.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Event {
+    Start(usize),
+    Return(usize),
+}
+
+/// The calls of a recursion in the order that they start, which is the order of a preorder traversal of the call
+/// tree, and the starts and returns of the calls in the order that they happen.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Recursion {
+    calls: Vec<Call>,
+    events: Vec<Event>,
+}
+impl Recursion {
+    fn new(n: usize, memoize: bool) -> Self {
+        let mut recursion = Self::default();
+        recursion.call(n, memoize, &mut vec![false; n + 1]);
+        recursion
+    }
+    /// Records a call of F(k) and returns its ID. `computed` tells which values have been computed, which is the memo
+    /// when the values are memoized.
+    fn call(&mut self, k: usize, memoize: bool, computed: &mut Vec<bool>) -> usize {
+        let id = self.calls.len();
+        self.calls.push(Call {
+            k,
+            children: vec![],
+            value: fibonacci(k),
+            repeated: computed[k],
+        });
+        self.events.push(Event::Start(id));
+        if k >= 2 && !(memoize && computed[k]) {
+            let first = self.call(k - 1, memoize, computed);
+            let second = self.call(k - 2, memoize, computed);
+            self.calls[id].children = vec![first, second];
+            self.events.push(Event::Return(id));
+        }
+        computed[k] = true;
+        id
+    }
+}
+
+/// The Fibonacci numbers F(0) = 0, F(1) = 1 and F(k) = F(k − 1) + F(k − 2) computed in three ways. The naive recursion
+/// calls itself for both of the previous numbers, so it computes the same numbers again and again, and its calls
+/// grow exponentially. Memoization stores every computed number in a memo and looks it up on later calls, and the
+/// bottom-up method fills the same table from F(0) up without any recursion.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fibonacci {
+    n: usize,
+}
+impl Fibonacci {
+    pub fn new(n: usize) -> Self {
+        Self { n }
+    }
+    /// Draws the calls that have started by the given event, and a table of values or call counts below them.
+    fn draw(
+        &self,
+        method: Method,
+        recursion: &Recursion,
+        event: Option<Event>,
+        table: &[Option<u64>],
+        table_states: &[(usize, ElementState)],
+    ) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, TABLE_POSITION.1 + CELL_SIZE.1 * 2.0 + 20.0);
+        // The positions come from the whole tree, so that the calls don't move when new ones start.
+        let roots: &[usize] = if recursion.calls.is_empty() {
+            &[]
+        } else {
+            &[0]
+        };
+        let layout = tree_layout(roots, |id| recursion.calls[id].children.clone());
+        let positions = fit_layout(&layout, (CANVAS_WIDTH, TREE_HEIGHT), (80.0, 60.0));
+        let (started, returned) = progress(recursion, event);
+        let current = event.map(|event| match event {
+            Event::Start(id) | Event::Return(id) => id,
+        });
+        diagram.add_node(DiagramNode::text(
+            "tree-title",
+            match method {
+                Method::BottomUp => "Bottom-up: no recursive calls".to_string(),
+                _ => format!(
+                    "{}: {} of F(k), labeled with k",
+                    method.name(),
+                    plural(started, "call")
+                ),
+            },
+            (CANVAS_WIDTH / 2.0, TREE_TOP - 20.0),
+        ));
+        for (id, call) in recursion.calls.iter().enumerate().take(started) {
+            let (x, y) = positions[&id];
+            let mut node = DiagramNode::new(
+                format!("call-{}", id),
+                call.k,
+                (x, TREE_TOP + y),
+                Shape::Circle {
+                    radius: CALL_RADIUS,
+                },
+            );
+            node.state = Some(if current == Some(id) {
+                ElementState::Active
+            } else if !returned[id] {
+                ElementState::Frontier
+            } else if call.repeated && method == Method::Naive {
+                ElementState::Rejected
+            } else {
+                ElementState::Selected
+            });
+            diagram.add_node(node);
+            for child in call.children.iter().filter(|child| **child < started) {
+                let edge = DiagramEdge::new(format!("call-{}", id), format!("call-{}", child));
+                // Calls that only look up the memo are connected with dashed edges
+                diagram.add_edge(
+                    if method == Method::Memoization && recursion.calls[*child].repeated {
+                        edge.dashed()
+                    } else {
+                        edge
+                    },
+                );
+            }
+        }
+        let (left, top) = TABLE_POSITION;
+        let (width, height) = CELL_SIZE;
+        diagram.add_node(DiagramNode::text(
+            "table-title",
+            match method {
+                Method::Naive => "Calls of F(k)",
+                Method::Memoization => "Memo",
+                Method::BottomUp => "Table",
+            },
+            (left - 80.0, top + height * 1.5),
+        ));
+        for (k, value) in table.iter().enumerate() {
+            let x = left + (k as f32 + 0.5) * width;
+            diagram.add_node(DiagramNode::text(
+                format!("header-{}", k),
+                format!("F({})", k),
+                (x, top + height / 2.0),
+            ));
+            let mut node = DiagramNode::rect(
+                format!("cell-{}", k),
+                value.map(|value| value.to_string()).unwrap_or_default(),
+                (x, top + height * 1.5),
+                (width - 6.0, height - 6.0),
+            );
+            node.state = table_states
+                .iter()
+                .find(|(cell, _)| *cell == k)
+                .map(|(_, state)| *state);
+            diagram.add_node(node);
+        }
+        diagram
+    }
+    fn push(
+        &self,
+        description: String,
+        diagram: Diagram,
+        method: Method,
+        count: usize,
+        steps: &mut DiagramSteps,
+    ) {
+        let totals = [
+            (
+                Method::Naive,
+                2 * fibonacci(self.n + 1) as usize - 1,
+                "call",
+            ),
+            (Method::Memoization, (2 * self.n).max(2) - 1, "call"),
+            (Method::BottomUp, self.n.saturating_sub(1), "addition"),
+        ];
+        let step = steps.push(description, diagram);
+        step.lists = vec![StepList::new(
+            "Work",
+            totals
+                .iter()
+                .map(|(other, total, word)| {
+                    if *other == method {
+                        format!("{}: {} of {}", other.name(), count, plural(*total, word))
+                    } else {
+                        format!("{}: {}", other.name(), plural(*total, word))
+                    }
+                })
+                .collect(),
+        )];
+    }
+    fn recurse(&self, method: Method, steps: &mut DiagramSteps) {
+        let memoize = method == Method::Memoization;
+        let recursion = Recursion::new(self.n, memoize);
+        let mut table = vec![None; self.n + 1];
+        if method == Method::Naive {
+            table = vec![Some(0); self.n + 1];
+        }
+        let mut started = 0;
+        for event in recursion.events.iter() {
+            let (description, table_state) = match *event {
+                Event::Start(id) => {
+                    started += 1;
+                    let call = &recursion.calls[id];
+                    let description = if memoize && call.repeated {
+                        format!(
+                            "Call F({}). It is already in the memo, so the call returns {} without calling anything",
+                            call.k, call.value
+                        )
+                    } else if call.k < 2 {
+                        format!(
+                            "Call F({}), which is a base case and returns {} right away{}",
+                            call.k,
+                            call.value,
+                            if memoize {
+                                ". The value is stored in the memo"
+                            } else if call.repeated {
+                                ". It has been called before"
+                            } else {
+                                ""
+                            }
+                        )
+                    } else {
+                        format!(
+                            "Call F({}), which calls F({}) and F({}) and adds them up{}",
+                            call.k,
+                            call.k - 1,
+                            call.k - 2,
+                            if call.repeated {
+                                format!(
+                                    ". F({}) has already been computed, but the naive recursion doesn't remember it, so the whole subtree is computed again",
+                                    call.k
+                                )
+                            } else {
+                                String::new()
+                            }
+                        )
+                    };
+                    if !memoize {
+                        table[call.k] = table[call.k].map(|count| count + 1);
+                        (description, ElementState::Active)
+                    } else if call.k < 2 && !call.repeated {
+                        table[call.k] = Some(call.value);
+                        (description, ElementState::Selected)
+                    } else {
+                        // Looking up the memo, whether the value is there or not
+                        (description, ElementState::Active)
+                    }
+                }
+                Event::Return(id) => {
+                    let call = &recursion.calls[id];
+                    let (first, second) = (call.k - 1, call.k - 2);
+                    let description = format!(
+                        "F({}) = F({}) + F({}) = {} + {} = {}{}",
+                        call.k,
+                        first,
+                        second,
+                        fibonacci(first),
+                        fibonacci(second),
+                        call.value,
+                        if memoize {
+                            ", which is stored in the memo"
+                        } else {
+                            ""
+                        }
+                    );
+                    if memoize {
+                        table[call.k] = Some(call.value);
+                    }
+                    (
+                        description,
+                        if memoize {
+                            ElementState::Selected
+                        } else {
+                            ElementState::Active
+                        },
+                    )
+                }
+            };
+            let k = match *event {
+                Event::Start(id) | Event::Return(id) => recursion.calls[id].k,
+            };
+            let diagram = self.draw(
+                method,
+                &recursion,
+                Some(*event),
+                &table,
+                &[(k, table_state)],
+            );
+            self.push(description, diagram, method, started, steps);
+        }
+        let calls = recursion.calls.len();
+        let description = match method {
+            _ if self.n < 2 => format!(
+                "F({}) = {} is a base case, so it only took 1 call",
+                self.n, self.n
+            ),
+            Method::Naive => {
+                let most = (0..=self.n)
+                    .max_by_key(|k| table[*k].unwrap_or_default())
+                    .unwrap_or_default();
+                format!(
+                    "F({}) = {} took {}, and F({}) alone was called {}. The number of calls grows like the Fibonacci numbers themselves, about 1.6ⁿ",
+                    self.n,
+                    fibonacci(self.n),
+                    plural(calls, "call"),
+                    most,
+                    plural(table[most].unwrap_or_default() as usize, "time")
+                )
+            }
+            _ => format!(
+                "F({}) = {} took {}, since every number is computed once and every later call only looks up the memo. The naive recursion takes {}",
+                self.n,
+                fibonacci(self.n),
+                plural(calls, "call"),
+                plural(2 * fibonacci(self.n + 1) as usize - 1, "call")
+            ),
+        };
+        let diagram = self.draw(method, &recursion, None, &table, &[]);
+        self.push(description, diagram, method, started, steps);
+    }
+    fn bottom_up(&self, steps: &mut DiagramSteps) {
+        let recursion = Recursion::default();
+        let mut table = vec![None; self.n + 1];
+        table[0] = Some(0);
+        let mut base = vec![(0, ElementState::Selected)];
+        if self.n >= 1 {
+            table[1] = Some(1);
+            base.push((1, ElementState::Selected));
+        }
+        let diagram = self.draw(Method::BottomUp, &recursion, None, &table, &base);
+        self.push(
+            "Start the table with the base cases F(0) = 0 and F(1) = 1. Every later number only needs the two before it, so the table can be filled from left to right without any recursion".to_string(),
+            diagram,
+            Method::BottomUp,
+            0,
+            steps,
+        );
+        for k in 2..=self.n {
+            table[k] = Some(fibonacci(k));
+            let mut diagram = self.draw(
+                Method::BottomUp,
+                &recursion,
+                None,
+                &table,
+                &[
+                    (k - 2, ElementState::Frontier),
+                    (k - 1, ElementState::Frontier),
+                    (k, ElementState::Active),
+                ],
+            );
+            for from in [k - 2, k - 1] {
+                diagram.add_edge(
+                    DiagramEdge::arrow(format!("cell-{}", from), format!("cell-{}", k))
+                        .with_bend(0.4)
+                        .with_state(ElementState::Selected),
+                );
+            }
+            self.push(
+                format!(
+                    "F({}) = F({}) + F({}) = {} + {} = {}",
+                    k,
+                    k - 1,
+                    k - 2,
+                    fibonacci(k - 1),
+                    fibonacci(k - 2),
+                    fibonacci(k)
+                ),
+                diagram,
+                Method::BottomUp,
+                k - 1,
+                steps,
+            );
+        }
+        let diagram = self.draw(Method::BottomUp, &recursion, None, &table, &[]);
+        let description = if self.n < 2 {
+            format!(
+                "F({}) = {} is a base case, so it didn't take any additions",
+                self.n, self.n
+            )
+        } else {
+            format!(
+                "F({}) = {} took {}. Only the last two numbers are needed at a time, so the table could also be replaced by two variables",
+                self.n,
+                fibonacci(self.n),
+                plural(self.n - 1, "addition")
+            )
+        };
+        self.push(
+            description,
+            diagram,
+            Method::BottomUp,
+            self.n.saturating_sub(1),
+            steps,
+        );
+    }
+}
+impl Visualizer for Fibonacci {
+    fn diagram(&self) -> Diagram {
+        self.draw(
+            Method::BottomUp,
+            &Recursion::default(),
+            None,
+            &vec![None; self.n + 1],
+            &[],
+        )
+    }
+    fn operations(&self) -> Vec<Operation> {
+        [Method::Naive, Method::Memoization, Method::BottomUp]
+            .iter()
+            .map(|method| {
+                Operation::new(
+                    method.name(),
+                    vec![Input::Number {
+                        label: "n",
+                        default: self.n as i64,
+                        min: 0,
+                        max: MAX_N as i64,
+                    }],
+                )
+            })
+            .collect()
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        let method = match operation {
+            "Naive recursion" => Method::Naive,
+            "Memoization" => Method::Memoization,
+            "Bottom-up" => Method::BottomUp,
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        };
+        self.n = parse_number(values.first(), "number", 0, MAX_N)? as usize;
+        match method {
+            Method::BottomUp => self.bottom_up(&mut steps),
+            _ => self.recurse(method, &mut steps),
+        }
+        Ok(steps)
+    }
+}
+
+/// How many calls have started by the event, and which of them have returned.
+fn progress(recursion: &Recursion, event: Option<Event>) -> (usize, Vec<bool>) {
+    let mut started = 0;
+    let mut returned = vec![false; recursion.calls.len()];
+    let until = event.and_then(|event| recursion.events.iter().position(|e| *e == event));
+    for (i, event) in recursion.events.iter().enumerate() {
+        if until.is_some_and(|until| i > until) {
+            break;
+        }
+        match *event {
+            Event::Start(id) => {
+                started += 1;
+                returned[id] = recursion.calls[id].children.is_empty();
+            }
+            Event::Return(id) => returned[id] = true,
+        }
+    }
+    (started, returned)
+}
+
+fn fibonacci(k: usize) -> u64 {
+    let (mut a, mut b) = (0, 1);
+    for _ in 0..k {
+        (a, b) = (b, a + b);
+    }
+    a
+}
@@ -0,0 +1,444 @@
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const MAX_ITEMS: usize = 6;
+const MAX_CAPACITY: u64 = 12;
+const MAX_VALUE: u64 = 99;
+const ITEM_COUNTS: [&str; 4] = ["4", "3", "5", "6"];
+const TABLE_POSITION: (f32, f32) = (170.0, 70.0);
+const ROW_HEIGHT: f32 = 40.0;
+const MAX_COLUMN_WIDTH: f32 = 56.0;
+
+/// An item that can be put into the knapsack.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Item {
+    weight: usize,
+    value: u64,
+}
+
+/// The cells of the table by the number of items that can be used and the capacity. Cells that haven't been filled
+/// yet are `None`, and the flag tells if the best value of a cell includes the last of its items.
+type Cells = Vec<Vec<Option<(u64, bool)>>>;
+
+/// The row and the column of a cell.
+type Position = (usize, usize);
+
+/// The highlights of the current step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    /// The states of cells by their rows and columns.
+    cells: Vec<(usize, usize, ElementState)>,
+    /// Arrows from a cell to a cell that was computed from it, and whether the value came from the arrow.
+    arrows: Vec<(Position, Position, bool)>,
+}
+
+/// The 0/1 knapsack problem, where items with weights and values are packed into a knapsack so that their total
+/// weight fits in its capacity and their total value is as large as possible. Every item can be packed at most once.
+/// The table has a row for each number of the first items that can be packed and a column for each capacity, and
+/// every cell either excludes the last item of its row or includes it on top of the best packing of the rest.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Knapsack {
+    items: Vec<Item>,
+    capacity: usize,
+}
+impl Knapsack {
+    /// Creates a problem from the weights and values of the items and the capacity of the knapsack.
+    pub fn new(items: &[(usize, u64)], capacity: usize) -> Self {
+        Self {
+            items: items
+                .iter()
+                .map(|(weight, value)| Item {
+                    weight: *weight,
+                    value: *value,
+                })
+                .collect(),
+            capacity,
+        }
+    }
+    fn column_width(&self) -> f32 {
+        ((CANVAS_WIDTH - TABLE_POSITION.0 - 20.0) / (self.capacity + 1) as f32)
+            .min(MAX_COLUMN_WIDTH)
+    }
+    fn cell_key(row: usize, column: usize) -> String {
+        format!("cell-{}-{}", row, column)
+    }
+    fn draw(&self, cells: &Cells, highlights: &Highlights) -> Diagram {
+        let (left, top) = TABLE_POSITION;
+        let width = self.column_width();
+        let mut diagram = Diagram::new(
+            CANVAS_WIDTH,
+            top + (self.items.len() + 1) as f32 * ROW_HEIGHT + 30.0,
+        );
+        diagram.add_node(DiagramNode::text(
+            "capacity-title",
+            "Capacity",
+            (left + (self.capacity + 1) as f32 * width / 2.0, top - 50.0),
+        ));
+        for column in 0..=self.capacity {
+            diagram.add_node(DiagramNode::text(
+                format!("capacity-{}", column),
+                column,
+                (left + (column as f32 + 0.5) * width, top - 20.0),
+            ));
+        }
+        for (row, cells) in cells.iter().enumerate() {
+            let y = top + (row as f32 + 0.5) * ROW_HEIGHT;
+            diagram.add_node(DiagramNode::text(
+                format!("item-{}", row),
+                match row {
+                    0 => "No items".to_string(),
+                    _ => item_name(row, &self.items[row - 1]),
+                },
+                (left - 80.0, y),
+            ));
+            for (column, cell) in cells.iter().enumerate() {
+                let mut node = DiagramNode::rect(
+                    Self::cell_key(row, column),
+                    cell.map(|(value, _)| value.to_string()).unwrap_or_default(),
+                    (left + (column as f32 + 0.5) * width, y),
+                    (width - 6.0, ROW_HEIGHT - 8.0),
+                );
+                if let Some((_, true)) = cell {
+                    node.fill = Fill::Group(0);
+                }
+                node.state = highlights
+                    .cells
+                    .iter()
+                    .find(|(r, c, _)| *r == row && *c == column)
+                    .map(|(_, _, state)| *state);
+                diagram.add_node(node);
+            }
+        }
+        for ((from_row, from_column), (to_row, to_column), used) in &highlights.arrows {
+            let edge = DiagramEdge::arrow(
+                Self::cell_key(*from_row, *from_column),
+                Self::cell_key(*to_row, *to_column),
+            );
+            diagram.add_edge(if *used {
+                edge.with_state(ElementState::Selected)
+            } else {
+                edge.dashed()
+            });
+        }
+        diagram
+    }
+    fn push(
+        &self,
+        description: String,
+        cells: &Cells,
+        highlights: Highlights,
+        packed: &[usize],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(cells, &highlights));
+        step.lists = vec![
+            StepList::new(
+                "Items",
+                self.items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| item_name(i + 1, item))
+                    .collect(),
+            ),
+            StepList::new(
+                "Knapsack",
+                packed
+                    .iter()
+                    .rev()
+                    .map(|row| format!("Item {}", row))
+                    .collect(),
+            ),
+        ];
+    }
+    fn solve(&self, steps: &mut DiagramSteps) {
+        let n = self.items.len();
+        let mut cells: Cells = vec![vec![None; self.capacity + 1]; n + 1];
+        cells[0] = vec![Some((0, false)); self.capacity + 1];
+        self.push(
+            format!(
+                "Fill a table with a row for each number of the first items that can be packed and a column for each capacity from 0 to {}. Without any items the best value is 0 at every capacity",
+                self.capacity
+            ),
+            &cells,
+            Highlights {
+                cells: (0..=self.capacity)
+                    .map(|column| (0, column, ElementState::Active))
+                    .collect(),
+                ..Default::default()
+            },
+            &[],
+            steps,
+        );
+        for row in 1..=n {
+            let item = self.items[row - 1];
+            for capacity in 0..=self.capacity {
+                let (excluded, _) = cells[row - 1][capacity].unwrap();
+                let mut highlights = Highlights {
+                    cells: vec![
+                        (row, capacity, ElementState::Active),
+                        (row - 1, capacity, ElementState::Frontier),
+                    ],
+                    arrows: vec![],
+                };
+                let description = if item.weight > capacity {
+                    cells[row][capacity] = Some((excluded, false));
+                    highlights
+                        .arrows
+                        .push(((row - 1, capacity), (row, capacity), true));
+                    format!(
+                        "Item {} weighs {}, so it doesn't fit in a capacity of {}. It is excluded, and the best value stays {} from the row above",
+                        row, item.weight, capacity, excluded
+                    )
+                } else {
+                    let rest = capacity - item.weight;
+                    let (rest_value, _) = cells[row - 1][rest].unwrap();
+                    let included = item.value + rest_value;
+                    let include = included > excluded;
+                    cells[row][capacity] = Some((excluded.max(included), include));
+                    highlights
+                        .cells
+                        .push((row - 1, rest, ElementState::Frontier));
+                    highlights
+                        .arrows
+                        .push(((row - 1, capacity), (row, capacity), !include));
+                    highlights
+                        .arrows
+                        .push(((row - 1, rest), (row, capacity), include));
+                    format!(
+                        "Excluding item {} keeps the value {} from the row above. Including it adds its value {} to the best value {} of the remaining capacity {} − {} = {}, which gives {}. {}",
+                        row,
+                        excluded,
+                        item.value,
+                        rest_value,
+                        capacity,
+                        item.weight,
+                        rest,
+                        included,
+                        if include {
+                            format!("So item {} is included, and the best value is {}", row, included)
+                        } else if included == excluded {
+                            format!("They are equal, so item {} is excluded", row)
+                        } else {
+                            format!("So item {} is excluded, and the best value is {}", row, excluded)
+                        }
+                    )
+                };
+                self.push(description, &cells, highlights, &[], steps);
+            }
+        }
+        // Trace the decisions back from the last cell to find the items that were packed.
+        let best = cells[n][self.capacity].unwrap().0;
+        let mut packed = vec![];
+        let mut path = vec![];
+        let mut capacity = self.capacity;
+        for row in (1..=n).rev() {
+            let item = self.items[row - 1];
+            let (value, include) = cells[row][capacity].unwrap();
+            path.push((row, capacity));
+            let from = if include {
+                capacity - item.weight
+            } else {
+                capacity
+            };
+            let mut highlights = Highlights {
+                cells: path
+                    .iter()
+                    .map(|(r, c)| (*r, *c, ElementState::Selected))
+                    .collect(),
+                arrows: vec![((row - 1, from), (row, capacity), true)],
+            };
+            highlights.cells.push((row, capacity, ElementState::Active));
+            let description = if include {
+                packed.push(row);
+                format!(
+                    "The best value {} at capacity {} with {} includes item {}, so the item is packed. The rest of the knapsack is the best packing of capacity {} − {} = {} in the row above",
+                    value,
+                    capacity,
+                    first_items(row),
+                    row,
+                    capacity,
+                    item.weight,
+                    from
+                )
+            } else {
+                format!(
+                    "The best value {} at capacity {} with {} excludes item {}, so the trace continues from the same capacity in the row above",
+                    value,
+                    capacity,
+                    first_items(row),
+                    row
+                )
+            };
+            self.push(description, &cells, highlights, &packed, steps);
+            capacity = from;
+        }
+        path.push((0, capacity));
+        let weight = packed
+            .iter()
+            .map(|row| self.items[row - 1].weight)
+            .sum::<usize>();
+        self.push(
+            format!(
+                "The trace reached the row without items. The best value is {} with {} of total weight {} out of {}. Filling the table took {} × {} = {} cells, each in constant time",
+                best,
+                match packed.len() {
+                    0 => "no items".to_string(),
+                    _ => format!(
+                        "{} {}",
+                        if packed.len() == 1 { "item" } else { "items" },
+                        packed
+                            .iter()
+                            .rev()
+                            .map(|row| row.to_string())
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                },
+                weight,
+                self.capacity,
+                n + 1,
+                self.capacity + 1,
+                (n + 1) * (self.capacity + 1)
+            ),
+            &cells,
+            Highlights {
+                cells: path
+                    .iter()
+                    .map(|(r, c)| (*r, *c, ElementState::Selected))
+                    .collect(),
+                ..Default::default()
+            },
+            &packed,
+            steps,
+        );
+    }
+}
+impl Visualizer for Knapsack {
+    fn diagram(&self) -> Diagram {
+        let mut cells: Cells = vec![vec![None; self.capacity + 1]; self.items.len() + 1];
+        cells[0] = vec![Some((0, false)); self.capacity + 1];
+        self.draw(&cells, &Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let capacity = Input::Number {
+            label: "Capacity",
+            default: self.capacity as i64,
+            min: 1,
+            max: MAX_CAPACITY as i64,
+        };
+        vec![
+            Operation::new(
+                "Fill table",
+                vec![
+                    Input::Text {
+                        label: "Items",
+                        default: self
+                            .items
+                            .iter()
+                            .map(|item| format!("{}:{}", item.weight, item.value))
+                            .collect::<Vec<String>>()
+                            .join(" "),
+                    },
+                    capacity.clone(),
+                ],
+            ),
+            Operation::new(
+                "Random items",
+                vec![
+                    Input::Choice {
+                        label: "Number of items",
+                        choices: ITEM_COUNTS.iter().map(|count| count.to_string()).collect(),
+                    },
+                    capacity,
+                ],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Fill table" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                let capacity = parse_number(values.get(1), "capacity", 1, MAX_CAPACITY)?;
+                self.items = parse_items(&text)?;
+                self.capacity = capacity as usize;
+                self.solve(&mut steps);
+            }
+            "Random items" => {
+                let count = values.first().map(|value| value.number()).unwrap_or(4);
+                let capacity = parse_number(values.get(1), "capacity", 1, MAX_CAPACITY)?;
+                self.items = random_items(count.clamp(1, MAX_ITEMS as i64) as usize, capacity);
+                self.capacity = capacity as usize;
+                self.solve(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The first items of the rows up to the given one, like "the first 3 items".
+fn first_items(count: usize) -> String {
+    match count {
+        1 => "the first item".to_string(),
+        _ => format!("the first {} items", count),
+    }
+}
+
+/// The number of an item with its weight and value, like "2: weight 3, value 4".
+fn item_name(number: usize, item: &Item) -> String {
+    format!("{}: weight {}, value {}", number, item.weight, item.value)
+}
+
+/// Parses items that are given as weights and values separated by colons, like "2:3 4:5".
+fn parse_items(text: &str) -> Result<Vec<Item>, String> {
+    let items = text
+        .split([',', ' ', ';'])
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| {
+            let (weight, value) = part.split_once(':')?;
+            Some(Item {
+                weight: weight.trim().parse().ok()?,
+                value: value.trim().parse().ok()?,
+            })
+        })
+        .collect::<Option<Vec<Item>>>()
+        .ok_or_else(|| {
+            "The items must be weights and values separated by colons, like \"2:3 4:5\"".to_string()
+        })?;
+    if items.is_empty() || items.len() > MAX_ITEMS {
+        return Err(format!("There must be 1 to {} items", MAX_ITEMS));
+    }
+    if items
+        .iter()
+        .any(|item| item.weight == 0 || item.weight as u64 > MAX_CAPACITY)
+    {
+        return Err(format!(
+            "The weights must be between 1 and {}",
+            MAX_CAPACITY
+        ));
+    }
+    if items
+        .iter()
+        .any(|item| item.value == 0 || item.value > MAX_VALUE)
+    {
+        return Err(format!("The values must be between 1 and {}", MAX_VALUE));
+    }
+    Ok(items)
+}
+
+/// Items that are around half of the capacity, so that some of them fit together and others don't.
+fn random_items(count: usize, capacity: u64) -> Vec<Item> {
+    let mut rng = rand::thread_rng();
+    (0..count)
+        .map(|_| {
+            let weight = rng.gen_range(1..=capacity.div_ceil(2).max(2).min(capacity));
+            Item {
+                weight: weight as usize,
+                value: rng.gen_range(weight..=weight * 3).min(MAX_VALUE),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,444 @@
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const MAX_VALUES: usize = 12;
+const MAX_VALUE: u64 = 99;
+const VALUE_COUNTS: [&str; 4] = ["10", "6", "8", "12"];
+const CANVAS_HEIGHT: f32 = 420.0;
+/// The bottom of the bars, which are as tall as `BAR_HEIGHT` at the largest value like the bars of sorting.
+const BASELINE: f32 = 300.0;
+const BAR_HEIGHT: f32 = 200.0;
+const ROW_Y: f32 = 370.0;
+const ROW_LEFT: f32 = 100.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Method {
+    Quadratic,
+    Patience,
+}
+
+/// The highlights of the current step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    bars: Vec<(usize, ElementState)>,
+    cells: Vec<(usize, ElementState)>,
+    /// The bars whose arrows to their predecessors are highlighted.
+    arrows: Vec<usize>,
+}
+
+/// The longest increasing subsequence of a sequence, which is the longest selection of its values that increase
+/// from left to right when the rest of the values are removed. The quadratic dynamic programming compares every
+/// value with every earlier one, while patience sorting keeps the smallest last value of an increasing subsequence
+/// of every length and finds the place of every value with a binary search.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LongestIncreasingSubsequence {
+    values: Vec<u64>,
+}
+impl LongestIncreasingSubsequence {
+    pub fn new(values: Vec<u64>) -> Self {
+        Self { values }
+    }
+    fn bar_pitch(&self) -> f32 {
+        (CANVAS_WIDTH - 40.0) / self.values.len().max(1) as f32
+    }
+    fn bar_x(&self, index: usize) -> f32 {
+        20.0 + (index as f32 + 0.5) * self.bar_pitch()
+    }
+    /// Draws the values as bars with arrows to their predecessors, and a row of cells below them, which are the
+    /// lengths of the subsequences that end at the bars or the last values of the subsequences of every length.
+    fn draw(
+        &self,
+        method: Method,
+        predecessors: &[Option<Option<usize>>],
+        row: &[String],
+        highlights: &Highlights,
+    ) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let max = self.values.iter().copied().max().unwrap_or(1).max(1) as f32;
+        let width = self.bar_pitch() * 0.8;
+        for (index, value) in self.values.iter().enumerate() {
+            let height = (*value as f32 / max * BAR_HEIGHT).max(8.0);
+            let mut node = DiagramNode::rect(
+                format!("bar-{}", index),
+                value,
+                (self.bar_x(index), BASELINE - height / 2.0),
+                (width, height),
+            )
+            .with_note(index);
+            node.state = highlights
+                .bars
+                .iter()
+                .find(|(bar, _)| *bar == index)
+                .map(|(_, state)| *state);
+            diagram.add_node(node);
+        }
+        for (index, predecessor) in predecessors.iter().enumerate() {
+            if let Some(Some(predecessor)) = predecessor {
+                let edge =
+                    DiagramEdge::arrow(format!("bar-{}", predecessor), format!("bar-{}", index))
+                        .with_bend(0.3);
+                diagram.add_edge(if highlights.arrows.contains(&index) {
+                    edge.with_state(ElementState::Selected)
+                } else {
+                    edge.dashed()
+                });
+            }
+        }
+        diagram.add_node(DiagramNode::text(
+            "row-title",
+            match method {
+                Method::Quadratic => "Length",
+                Method::Patience => "Tails",
+            },
+            (ROW_LEFT / 2.0, ROW_Y),
+        ));
+        // The lengths are below their bars, while the tails have a cell for every length
+        let (left, pitch) = match method {
+            Method::Quadratic => (20.0, self.bar_pitch()),
+            Method::Patience => (
+                ROW_LEFT,
+                ((CANVAS_WIDTH - ROW_LEFT - 20.0) / self.values.len().max(1) as f32).min(60.0),
+            ),
+        };
+        for (i, label) in row.iter().enumerate() {
+            let mut node = DiagramNode::rect(
+                format!("cell-{}", i),
+                label,
+                (left + (i as f32 + 0.5) * pitch, ROW_Y),
+                (pitch.min(60.0) - 6.0, 30.0),
+            );
+            if method == Method::Patience {
+                node.note = Some(format!("length {}", i + 1));
+            }
+            node.state = highlights
+                .cells
+                .iter()
+                .find(|(cell, _)| *cell == i)
+                .map(|(_, state)| *state);
+            diagram.add_node(node);
+        }
+        diagram
+    }
+    fn push(
+        &self,
+        description: String,
+        diagram: Diagram,
+        comparisons: usize,
+        subsequence: &[usize],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, diagram);
+        step.lists = vec![
+            StepList::new("Comparisons", vec![comparisons.to_string()]),
+            StepList::new(
+                "Subsequence",
+                subsequence
+                    .iter()
+                    .map(|index| self.values[*index].to_string())
+                    .collect(),
+            ),
+        ];
+    }
+    /// Follows the predecessors back from the last value of the longest subsequence, and returns the indices of the
+    /// subsequence in order.
+    fn trace_back(
+        &self,
+        method: Method,
+        last: usize,
+        predecessors: &[Option<Option<usize>>],
+        row: &[String],
+        comparisons: usize,
+        steps: &mut DiagramSteps,
+    ) {
+        let mut subsequence = vec![last];
+        while let Some(Some(predecessor)) = predecessors[*subsequence.last().unwrap()] {
+            subsequence.push(predecessor);
+        }
+        subsequence.reverse();
+        self.push(
+            if subsequence.len() == 1 {
+                format!(
+                    "No value is larger than an earlier one, so the longest increasing subsequence is just {}. The search took {}",
+                    self.values[last],
+                    plural(comparisons, "comparison")
+                )
+            } else {
+                format!(
+                    "Follow the arrows back from {} to get the increasing subsequence {}, which has {}. The search took {}",
+                    self.values[last],
+                    subsequence
+                        .iter()
+                        .map(|index| self.values[*index].to_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    plural(subsequence.len(), "value"),
+                    plural(comparisons, "comparison")
+                )
+            },
+            self.draw(
+                method,
+                predecessors,
+                row,
+                &Highlights {
+                    bars: subsequence
+                        .iter()
+                        .map(|index| (*index, ElementState::Selected))
+                        .collect(),
+                    cells: match method {
+                        Method::Quadratic => vec![(last, ElementState::Selected)],
+                        Method::Patience => vec![(subsequence.len() - 1, ElementState::Selected)],
+                    },
+                    arrows: subsequence.clone(),
+                },
+            ),
+            comparisons,
+            &subsequence,
+            steps,
+        );
+    }
+    fn quadratic(&self, steps: &mut DiagramSteps) {
+        let method = Method::Quadratic;
+        let n = self.values.len();
+        let mut lengths: Vec<usize> = vec![];
+        let mut predecessors = vec![None; n];
+        let mut comparisons = 0;
+        self.push(
+            "Find the length of the longest increasing subsequence that ends at every value. A subsequence that ends at a value continues the longest one that ends at a smaller value before it".to_string(),
+            self.draw(method, &predecessors, &[], &Highlights::default()),
+            0,
+            &[],
+            steps,
+        );
+        for (i, value) in self.values.iter().enumerate() {
+            comparisons += i;
+            let smaller = (0..i)
+                .filter(|j| self.values[*j] < *value)
+                .collect::<Vec<usize>>();
+            // The first of the longest ones
+            let best = smaller.iter().copied().rev().max_by_key(|j| lengths[*j]);
+            predecessors[i] = Some(best);
+            lengths.push(best.map_or(1, |j| lengths[j] + 1));
+            let description = match best {
+                None if i == 0 => format!(
+                    "{} is the first value, so the subsequence that ends at it is just 1 long",
+                    value
+                ),
+                None => format!(
+                    "None of the {} before {} is smaller, so the subsequence that ends at it is just 1 long",
+                    plural(i, "value"),
+                    value
+                ),
+                Some(j) if smaller.len() == 1 => format!(
+                    "The only value before {} that is smaller than it is {}, so the subsequence that ends at {} continues the one of length {} and is {} long",
+                    value, self.values[j], value, lengths[j], lengths[i]
+                ),
+                Some(j) => format!(
+                    "The values before {} that are smaller than it are {}. The longest subsequence among them ends at {} with a length of {}, so the one that ends at {} is {} long",
+                    value,
+                    smaller
+                        .iter()
+                        .map(|j| self.values[*j].to_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    self.values[j],
+                    lengths[j],
+                    value,
+                    lengths[i]
+                ),
+            };
+            let mut bars = smaller
+                .iter()
+                .map(|j| (*j, ElementState::Frontier))
+                .collect::<Vec<(usize, ElementState)>>();
+            bars.insert(0, (i, ElementState::Active));
+            let row = lengths
+                .iter()
+                .map(|length| length.to_string())
+                .collect::<Vec<String>>();
+            self.push(
+                description,
+                self.draw(
+                    method,
+                    &predecessors,
+                    &row,
+                    &Highlights {
+                        bars,
+                        cells: vec![(i, ElementState::Active)],
+                        arrows: vec![i],
+                    },
+                ),
+                comparisons,
+                &[],
+                steps,
+            );
+        }
+        let Some(last) = (0..n).rev().max_by_key(|i| lengths[*i]) else {
+            return;
+        };
+        let row = lengths
+            .iter()
+            .map(|length| length.to_string())
+            .collect::<Vec<String>>();
+        self.trace_back(method, last, &predecessors, &row, comparisons, steps);
+    }
+    fn patience(&self, steps: &mut DiagramSteps) {
+        let method = Method::Patience;
+        let n = self.values.len();
+        // The indices of the smallest last values of the increasing subsequences of every length
+        let mut tails: Vec<usize> = vec![];
+        let mut predecessors = vec![None; n];
+        let mut comparisons = 0;
+        let tail_row = |tails: &[usize]| {
+            tails
+                .iter()
+                .map(|index| self.values[*index].to_string())
+                .collect::<Vec<String>>()
+        };
+        self.push(
+            "Keep the smallest last value, or tail, of an increasing subsequence of every length. The tails increase from left to right, so the place of every value can be found with a binary search".to_string(),
+            self.draw(method, &predecessors, &[], &Highlights::default()),
+            0,
+            &[],
+            steps,
+        );
+        for (i, value) in self.values.iter().enumerate() {
+            // Binary search for the first tail that isn't smaller than the value
+            let (mut low, mut high) = (0, tails.len());
+            while low < high {
+                let middle = (low + high) / 2;
+                comparisons += 1;
+                if self.values[tails[middle]] < *value {
+                    low = middle + 1;
+                } else {
+                    high = middle;
+                }
+            }
+            let predecessor = low.checked_sub(1).map(|length| tails[length]);
+            predecessors[i] = Some(predecessor);
+            let previous = tails.get(low).copied();
+            if low == tails.len() {
+                tails.push(i);
+            } else {
+                tails[low] = i;
+            }
+            let continues = match predecessor {
+                Some(predecessor) => format!(
+                    ", continuing the subsequence of length {} that ends at {}",
+                    low, self.values[predecessor]
+                ),
+                None => String::new(),
+            };
+            let description = match previous {
+                None if low == 0 => format!(
+                    "{} is the first value, so it is the tail of a subsequence of length 1",
+                    value
+                ),
+                None => format!(
+                    "{} is larger than every tail, so it makes a longer subsequence of length {}{}",
+                    value,
+                    low + 1,
+                    continues
+                ),
+                Some(previous) => format!(
+                    "The first tail that isn't smaller than {} is {}, so {} replaces it as the tail of length {}{}. A smaller tail is easier to continue",
+                    value,
+                    self.values[previous],
+                    value,
+                    low + 1,
+                    continues
+                ),
+            };
+            let mut bars = vec![(i, ElementState::Active)];
+            if let Some(predecessor) = predecessor {
+                bars.push((predecessor, ElementState::Frontier));
+            }
+            if let Some(previous) = previous {
+                bars.push((previous, ElementState::Rejected));
+            }
+            self.push(
+                description,
+                self.draw(
+                    method,
+                    &predecessors,
+                    &tail_row(&tails),
+                    &Highlights {
+                        bars,
+                        cells: vec![(low, ElementState::Active)],
+                        arrows: vec![i],
+                    },
+                ),
+                comparisons,
+                &[],
+                steps,
+            );
+        }
+        let Some(last) = tails.last().copied() else {
+            return;
+        };
+        self.trace_back(
+            method,
+            last,
+            &predecessors,
+            &tail_row(&tails),
+            comparisons,
+            steps,
+        );
+    }
+}
+impl Visualizer for LongestIncreasingSubsequence {
+    fn diagram(&self) -> Diagram {
+        self.draw(
+            Method::Quadratic,
+            &vec![None; self.values.len()],
+            &[],
+            &Highlights::default(),
+        )
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let values = Input::Text {
+            label: "Values",
+            default: self
+                .values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<String>>()
+                .join(" "),
+        };
+        vec![
+            Operation::new("Quadratic DP", vec![values.clone()]),
+            Operation::new("Patience sorting", vec![values]),
+            Operation::new(
+                "Random values",
+                vec![Input::Choice {
+                    label: "Number of values",
+                    choices: VALUE_COUNTS.iter().map(|count| count.to_string()).collect(),
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Quadratic DP" | "Patience sorting" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                self.values = parse_values(&text, 0, MAX_VALUE, MAX_VALUES)?;
+                if operation == "Quadratic DP" {
+                    self.quadratic(&mut steps);
+                } else {
+                    self.patience(&mut steps);
+                }
+            }
+            "Random values" => {
+                let count = values.first().map(|value| value.number()).unwrap_or(10);
+                let mut rng = rand::thread_rng();
+                self.values = (0..count.clamp(1, MAX_VALUES as i64))
+                    .map(|_| rng.gen_range(1..=MAX_VALUE))
+                    .collect();
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
@@ -0,0 +1,441 @@
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const MAX_MATRICES: usize = 6;
+const MAX_DIMENSION: u64 = 100;
+const MATRIX_COUNTS: [&str; 4] = ["4", "3", "5", "6"];
+const TABLE_POSITION: (f32, f32) = (120.0, 70.0);
+const CELL_PITCH: (f32, f32) = (90.0, 50.0);
+const TREE_HEIGHT: f32 = 260.0;
+
+/// A cell of the table, which has the fewest multiplications of the matrices from `i` to `j` and the matrix after
+/// which the chain is split to get them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Cell {
+    cost: u64,
+    split: Option<usize>,
+}
+
+/// The highlights of the current step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    /// The states of cells by the first and the last matrix of their chains.
+    cells: Vec<(usize, usize, ElementState)>,
+    /// The chains of the tree that have been split, from the root down.
+    tree: Vec<(usize, usize)>,
+    /// The chain that was split last.
+    current: Option<(usize, usize)>,
+}
+
+/// Matrix chain multiplication, which finds the order of multiplying a chain of matrices that takes the fewest
+/// multiplications of numbers. Multiplying a p × q matrix by a q × r matrix takes pqr of them, and the product is
+/// the same in every order, but the costs of the orders can be very different. The table has a cell for every
+/// chain of consecutive matrices, and the cells are filled from the shortest chains to the longest one, since every
+/// chain is split into two shorter ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatrixChain {
+    /// The matrix `i` has `dimensions[i]` rows and `dimensions[i + 1]` columns.
+    dimensions: Vec<u64>,
+}
+impl MatrixChain {
+    pub fn new(dimensions: Vec<u64>) -> Self {
+        Self { dimensions }
+    }
+    fn count(&self) -> usize {
+        self.dimensions.len() - 1
+    }
+    fn cell_key(i: usize, j: usize) -> String {
+        format!("cell-{}-{}", i, j)
+    }
+    fn draw(&self, table: &[Vec<Option<Cell>>], highlights: &Highlights) -> Diagram {
+        let n = self.count();
+        let (left, top) = TABLE_POSITION;
+        let (width, height) = CELL_PITCH;
+        let tree_top = top + (n as f32 + 0.5) * height;
+        let mut diagram = Diagram::new(CANVAS_WIDTH, tree_top + TREE_HEIGHT);
+        for i in 0..n {
+            diagram.add_node(DiagramNode::text(
+                format!("column-{}", i),
+                matrix_name(i),
+                (left + (i as f32 + 0.5) * width, top - 20.0),
+            ));
+            diagram.add_node(DiagramNode::text(
+                format!("row-{}", i),
+                format!("{} {}", matrix_name(i), self.shape(i)),
+                (left - 60.0, top + (i as f32 + 0.5) * height),
+            ));
+        }
+        for (i, row) in table.iter().enumerate() {
+            for (j, cell) in row.iter().enumerate().skip(i) {
+                let mut node = DiagramNode::rect(
+                    Self::cell_key(i, j),
+                    cell.map(|cell| cell.cost.to_string()).unwrap_or_default(),
+                    (
+                        left + (j as f32 + 0.5) * width,
+                        top + (i as f32 + 0.5) * height,
+                    ),
+                    (width - 6.0, height - 20.0),
+                );
+                if let Some(Cell {
+                    split: Some(split), ..
+                }) = cell
+                {
+                    node.note = Some(format!("split after {}", matrix_name(*split)));
+                }
+                node.state = highlights
+                    .cells
+                    .iter()
+                    .find(|(r, c, _)| *r == i && *c == j)
+                    .map(|(_, _, state)| *state);
+                diagram.add_node(node);
+            }
+        }
+        if !highlights.tree.is_empty() {
+            self.draw_tree(&mut diagram, table, highlights, tree_top);
+        }
+        diagram
+    }
+    /// Draws the splits that have been traced back as a tree, where the leaves are the matrices and every other node
+    /// multiplies the products of its children.
+    fn draw_tree(
+        &self,
+        diagram: &mut Diagram,
+        table: &[Vec<Option<Cell>>],
+        highlights: &Highlights,
+        tree_top: f32,
+    ) {
+        // The nodes are the chains, numbered by i * n + j
+        let n = self.count();
+        let split = |(i, j): (usize, usize)| {
+            if highlights.tree.contains(&(i, j)) {
+                table[i][j].and_then(|cell| cell.split)
+            } else {
+                None
+            }
+        };
+        let children = |id: usize| match split((id / n, id % n)) {
+            Some(k) => vec![id / n * n + k, (k + 1) * n + id % n],
+            None => vec![],
+        };
+        let layout = tree_layout(&[n - 1], children);
+        let positions = fit_layout(&layout, (CANVAS_WIDTH, TREE_HEIGHT - 20.0), (90.0, 70.0));
+        for (id, (x, y)) in positions.iter() {
+            let (i, j) = (id / n, id % n);
+            let node = match split((i, j)) {
+                Some(k) => {
+                    DiagramNode::circle(format!("tree-{}-{}", i, j), "×", (*x, tree_top + y))
+                        .with_note(
+                            self.dimensions[i] * self.dimensions[k + 1] * self.dimensions[j + 1],
+                        )
+                }
+                None if i == j => DiagramNode::circle(
+                    format!("tree-{}-{}", i, j),
+                    matrix_name(i),
+                    (*x, tree_top + y),
+                )
+                .with_note(self.shape(i)),
+                None => DiagramNode::circle(
+                    format!("tree-{}-{}", i, j),
+                    format!("{}–{}", i + 1, j + 1),
+                    (*x, tree_top + y),
+                ),
+            };
+            let state = if highlights.current == Some((i, j)) {
+                ElementState::Active
+            } else if split((i, j)).is_some() || i == j {
+                ElementState::Selected
+            } else {
+                ElementState::Frontier
+            };
+            diagram.add_node(node.with_state(state));
+            for child in children(*id) {
+                diagram.add_edge(DiagramEdge::new(
+                    format!("tree-{}-{}", i, j),
+                    format!("tree-{}-{}", child / n, child % n),
+                ));
+            }
+        }
+    }
+    /// The shape of a matrix, like "10 × 30".
+    fn shape(&self, i: usize) -> String {
+        format!("{} × {}", self.dimensions[i], self.dimensions[i + 1])
+    }
+    fn push(
+        &self,
+        description: String,
+        table: &[Vec<Option<Cell>>],
+        highlights: Highlights,
+        order: Option<String>,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(table, &highlights));
+        step.lists = vec![StepList::new(
+            "Matrices",
+            (0..self.count())
+                .map(|i| format!("{}: {}", matrix_name(i), self.shape(i)))
+                .collect(),
+        )];
+        if let Some(order) = order {
+            step.lists.push(StepList::new("Order", vec![order]));
+        }
+    }
+    fn solve(&self, steps: &mut DiagramSteps) {
+        let n = self.count();
+        let p = &self.dimensions;
+        let mut table = vec![vec![None; n]; n];
+        for (i, row) in table.iter_mut().enumerate() {
+            row[i] = Some(Cell {
+                cost: 0,
+                split: None,
+            });
+        }
+        self.push(
+            format!(
+                "Fill a table with a cell for every chain of consecutive matrices, from {} to {}. A single matrix doesn't need any multiplications, so the cells of the chains of length 1 are 0",
+                matrix_name(0),
+                matrix_name(n - 1)
+            ),
+            &table,
+            Highlights {
+                cells: (0..n).map(|i| (i, i, ElementState::Active)).collect(),
+                ..Default::default()
+            },
+            None,
+            steps,
+        );
+        for length in 2..=n {
+            for i in 0..=n - length {
+                let j = i + length - 1;
+                let costs = (i..j)
+                    .map(|k| {
+                        let (left, right) =
+                            (table[i][k].unwrap().cost, table[k + 1][j].unwrap().cost);
+                        (k, left, right, p[i] * p[k + 1] * p[j + 1])
+                    })
+                    .collect::<Vec<(usize, u64, u64, u64)>>();
+                // The first split with the smallest cost
+                let (best, left, right, product) = *costs
+                    .iter()
+                    .min_by_key(|(_, left, right, product)| left + right + product)
+                    .unwrap();
+                table[i][j] = Some(Cell {
+                    cost: left + right + product,
+                    split: Some(best),
+                });
+                let options = costs
+                    .iter()
+                    .map(|(k, left, right, product)| {
+                        format!(
+                            "after {}: {} + {} + {} × {} × {} = {}",
+                            matrix_name(*k),
+                            left,
+                            right,
+                            p[i],
+                            p[k + 1],
+                            p[j + 1],
+                            left + right + product
+                        )
+                    })
+                    .collect::<Vec<String>>();
+                self.push(
+                    format!(
+                        "Split the chain {} into two shorter chains, whose costs are in the table, and multiply their products. Splitting {}. The best split is after {}, with {}",
+                        chain_name(i, j),
+                        options.join(", "),
+                        matrix_name(best),
+                        plural((left + right + product) as usize, "multiplication")
+                    ),
+                    &table,
+                    Highlights {
+                        cells: vec![
+                            (i, j, ElementState::Active),
+                            (i, best, ElementState::Frontier),
+                            (best + 1, j, ElementState::Frontier),
+                        ],
+                        ..Default::default()
+                    },
+                    None,
+                    steps,
+                );
+            }
+        }
+        // Build the tree from the root down by splitting the chains in the order of a preorder traversal.
+        let mut tree = vec![];
+        let mut stack = vec![(0, n - 1)];
+        while let Some((i, j)) = stack.pop() {
+            if i == j {
+                continue;
+            }
+            let cell = table[i][j].unwrap();
+            let k = cell.split.unwrap();
+            tree.push((i, j));
+            stack.push((k + 1, j));
+            stack.push((i, k));
+            self.push(
+                format!(
+                    "The cell of {} says that it is split after {}, so it is the product of {} and {}. The multiplication of the two products takes {} × {} × {} = {}",
+                    chain_name(i, j),
+                    matrix_name(k),
+                    chain_name(i, k),
+                    chain_name(k + 1, j),
+                    p[i],
+                    p[k + 1],
+                    p[j + 1],
+                    p[i] * p[k + 1] * p[j + 1]
+                ),
+                &table,
+                Highlights {
+                    cells: tree
+                        .iter()
+                        .map(|(i, j)| (*i, *j, ElementState::Selected))
+                        .chain([(i, j, ElementState::Active)])
+                        .collect(),
+                    tree: tree.clone(),
+                    current: Some((i, j)),
+                },
+                None,
+                steps,
+            );
+        }
+        let cost = table[0][n - 1].unwrap().cost;
+        // Multiplying from left to right for comparison
+        let naive = (1..n).map(|j| p[0] * p[j] * p[j + 1]).sum::<u64>();
+        let order = parenthesize(&table, 0, n - 1);
+        self.push(
+            format!(
+                "The best order is {}, with {}. Multiplying from left to right would take {}. Filling the table took {} cells, each trying every split",
+                order,
+                plural(cost as usize, "multiplication"),
+                plural(naive as usize, "multiplication"),
+                n * (n + 1) / 2
+            ),
+            &table,
+            Highlights {
+                cells: tree
+                    .iter()
+                    .map(|(i, j)| (*i, *j, ElementState::Selected))
+                    .collect(),
+                tree,
+                current: None,
+            },
+            Some(order),
+            steps,
+        );
+    }
+}
+impl Visualizer for MatrixChain {
+    fn diagram(&self) -> Diagram {
+        let n = self.count();
+        let mut table = vec![vec![None; n]; n];
+        for (i, row) in table.iter_mut().enumerate() {
+            row[i] = Some(Cell {
+                cost: 0,
+                split: None,
+            });
+        }
+        self.draw(&table, &Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Find order",
+                vec![Input::Text {
+                    label: "Dimensions",
+                    default: self
+                        .dimensions
+                        .iter()
+                        .map(|dimension| dimension.to_string())
+                        .collect::<Vec<String>>()
+                        .join(" "),
+                }],
+            ),
+            Operation::new(
+                "Random chain",
+                vec![Input::Choice {
+                    label: "Number of matrices",
+                    choices: MATRIX_COUNTS
+                        .iter()
+                        .map(|count| count.to_string())
+                        .collect(),
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Find order" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                self.dimensions = parse_dimensions(&text)?;
+                self.solve(&mut steps);
+            }
+            "Random chain" => {
+                let count = values.first().map(|value| value.number()).unwrap_or(4);
+                let mut rng = rand::thread_rng();
+                self.dimensions = (0..=count.clamp(2, MAX_MATRICES as i64))
+                    .map(|_| rng.gen_range(1..=12) * 5)
+                    .collect();
+                self.solve(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn matrix_name(i: usize) -> String {
+    format!("A{}", i + 1)
+}
+
+/// The chain of matrices from `i` to `j`, like "A2…A4".
+fn chain_name(i: usize, j: usize) -> String {
+    if i == j {
+        matrix_name(i)
+    } else {
+        format!("{}…{}", matrix_name(i), matrix_name(j))
+    }
+}
+
+/// The best order of multiplying the chain from `i` to `j` with parentheses, like "(A1(A2A3))".
+fn parenthesize(table: &[Vec<Option<Cell>>], i: usize, j: usize) -> String {
+    match table[i][j].and_then(|cell| cell.split) {
+        Some(k) => format!(
+            "({}{})",
+            parenthesize(table, i, k),
+            parenthesize(table, k + 1, j)
+        ),
+        None => matrix_name(i),
+    }
+}
+
+/// Parses the dimensions of the matrices, which are separated by commas or spaces, so that the matrix `i` has the
+/// dimensions `i` and `i + 1`.
+fn parse_dimensions(text: &str) -> Result<Vec<u64>, String> {
+    let dimensions = text
+        .split([',', ' ', '×', 'x'])
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| part.trim().parse::<u64>())
+        .collect::<Result<Vec<u64>, _>>()
+        .map_err(|_| {
+            "The dimensions must be numbers separated by spaces, like \"10 30 5 60\"".to_string()
+        })?;
+    if !(3..=MAX_MATRICES + 1).contains(&dimensions.len()) {
+        return Err(format!(
+            "There must be 3 to {} dimensions for 2 to {} matrices",
+            MAX_MATRICES + 1,
+            MAX_MATRICES
+        ));
+    }
+    if dimensions
+        .iter()
+        .any(|dimension| *dimension == 0 || *dimension > MAX_DIMENSION)
+    {
+        return Err(format!(
+            "The dimensions must be between 1 and {}",
+            MAX_DIMENSION
+        ));
+    }
+    Ok(dimensions)
+}
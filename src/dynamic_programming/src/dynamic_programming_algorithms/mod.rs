@@ -0,0 +1,13 @@
+//! A collection of algorithms that solve problems by combining the solutions of smaller subproblems, which are
+//! stored in a table so that each of them is only solved once.
+mod coin_change;
+mod fibonacci;
+mod knapsack;
+mod longest_increasing_subsequence;
+mod matrix_chain;
+
+pub use coin_change::CoinChange;
+pub use fibonacci::Fibonacci;
+pub use knapsack::Knapsack;
+pub use longest_increasing_subsequence::LongestIncreasingSubsequence;
+pub use matrix_chain::MatrixChain;
@@ -0,0 +1,5 @@
+//! This crate contains my implementations of dynamic programming algorithms, like the 0/1 knapsack problem.
+//! Each algorithm records a diagram of the table of subproblems that it fills at every step.
+pub mod dynamic_programming_algorithms;
+
+pub const CANVAS_WIDTH: f32 = 800.0;
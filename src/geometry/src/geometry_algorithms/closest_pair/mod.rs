@@ -0,0 +1,384 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use crate::*;
+
+/// The states of the points and the lines that split them at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    points: BTreeMap<usize, ElementState>,
+    /// The left and right edges of the part of the canvas that is being solved.
+    part: Option<(f32, f32)>,
+    /// The lines that split the parts that contain the current one.
+    lines: Vec<f32>,
+    line: Option<f32>,
+    /// The left and right edges of the strip around the line.
+    strip: Option<(f32, f32)>,
+    best: Option<(usize, usize)>,
+    compared: Option<(usize, usize, ElementState)>,
+}
+
+/// The closest pair of points, found by dividing the points into halves with a vertical line, solving both of them
+/// and checking the pairs that cross the line. Only the points near the line can be in such a pair, and each of them
+/// only has to be compared with a few of its neighbors above it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClosestPair {
+    points: Vec<Point>,
+}
+impl ClosestPair {
+    pub fn new(points: &[Point]) -> Self {
+        Self {
+            points: points.to_vec(),
+        }
+    }
+    fn push(&self, description: String, highlights: &Highlights, steps: &mut DiagramSteps) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = vec![StepList::new(
+            "Closest pair",
+            highlights
+                .best
+                .map(|pair| vec![self.pair_text(pair)])
+                .unwrap_or_default(),
+        )];
+    }
+    fn distance(&self, (a, b): (usize, usize)) -> f32 {
+        distance(self.points[a], self.points[b])
+    }
+    fn pair_text(&self, pair: (usize, usize)) -> String {
+        format!(
+            "Points {} and {} at distance {:.1}",
+            pair.0,
+            pair.1,
+            self.distance(pair)
+        )
+    }
+    fn find_closest_pair(&self, steps: &mut DiagramSteps) {
+        let points = &self.points;
+        let mut order = (0..points.len()).collect::<Vec<usize>>();
+        order.sort_by(|a, b| points[*a].0.total_cmp(&points[*b].0));
+        let mut highlights = Highlights::default();
+        self.push(
+            "Sort the points by x, so that they can be split into a left and a right half"
+                .to_string(),
+            &highlights,
+            steps,
+        );
+        let mut comparisons = 0;
+        let best = self.divide(
+            &order,
+            (0.0, CANVAS_WIDTH),
+            &mut highlights,
+            &mut comparisons,
+            steps,
+        );
+        let n = points.len();
+        highlights = Highlights {
+            points: BTreeMap::from([
+                (best.0, ElementState::Selected),
+                (best.1, ElementState::Selected),
+            ]),
+            best: Some(best),
+            ..Default::default()
+        };
+        self.push(
+            format!(
+                "The closest pair is {} and {} at distance {:.1}. Finding it compared {}, while comparing every pair would take {}",
+                best.0,
+                best.1,
+                self.distance(best),
+                plural(comparisons, "pair"),
+                n * (n - 1) / 2
+            ),
+            &highlights,
+            steps,
+        );
+    }
+    /// Finds the closest pair of the points in the part, which are sorted by x.
+    fn divide(
+        &self,
+        order: &[usize],
+        part: (f32, f32),
+        highlights: &mut Highlights,
+        comparisons: &mut usize,
+        steps: &mut DiagramSteps,
+    ) -> (usize, usize) {
+        let points = &self.points;
+        highlights.part = Some(part);
+        highlights.points = order
+            .iter()
+            .map(|index| (*index, ElementState::Frontier))
+            .collect();
+        highlights.line = None;
+        highlights.strip = None;
+        highlights.compared = None;
+        if order.len() <= 3 {
+            let mut best = ordered_pair(order[0], order[1]);
+            for (i, a) in order.iter().enumerate() {
+                for b in &order[i + 1..] {
+                    *comparisons += 1;
+                    if self.distance((*a, *b)) < self.distance(best) {
+                        best = ordered_pair(*a, *b);
+                    }
+                }
+            }
+            highlights.best = Some(best);
+            for index in [best.0, best.1] {
+                highlights.points.insert(index, ElementState::Selected);
+            }
+            let description = if order.len() == 2 {
+                format!(
+                    "There are only 2 points, {} and {}, so they are the closest pair of this part at distance {:.1}",
+                    best.0,
+                    best.1,
+                    self.distance(best)
+                )
+            } else {
+                format!(
+                    "There are only 3 points, so compare all of their pairs. The closest are {} and {} at distance {:.1}",
+                    best.0,
+                    best.1,
+                    self.distance(best)
+                )
+            };
+            self.push(description, highlights, steps);
+            return best;
+        }
+        let middle = order.len() / 2;
+        let line = (points[order[middle - 1]].0 + points[order[middle]].0) / 2.0;
+        highlights.line = Some(line);
+        highlights.best = None;
+        self.push(
+            format!(
+                "Split the {} points of this part in half with a line at x = {:.0}",
+                order.len(),
+                line
+            ),
+            highlights,
+            steps,
+        );
+        highlights.lines.push(line);
+        let left = self.divide(
+            &order[..middle],
+            (part.0, line),
+            highlights,
+            comparisons,
+            steps,
+        );
+        let right = self.divide(
+            &order[middle..],
+            (line, part.1),
+            highlights,
+            comparisons,
+            steps,
+        );
+        highlights.lines.pop();
+        let mut best = if self.distance(left) <= self.distance(right) {
+            left
+        } else {
+            right
+        };
+        let mut d = self.distance(best);
+        let mut strip = order
+            .iter()
+            .copied()
+            .filter(|index| (points[*index].0 - line).abs() < d)
+            .collect::<Vec<usize>>();
+        strip.sort_by(|a, b| points[*a].1.total_cmp(&points[*b].1));
+        highlights.part = Some(part);
+        highlights.line = Some(line);
+        highlights.strip = Some((line - d, line + d));
+        highlights.best = Some(best);
+        highlights.compared = None;
+        highlights.points = order
+            .iter()
+            .map(|index| (*index, ElementState::Frontier))
+            .collect();
+        for index in &strip {
+            highlights.points.insert(*index, ElementState::Active);
+        }
+        for index in [best.0, best.1] {
+            highlights.points.insert(index, ElementState::Selected);
+        }
+        if strip.len() < 2 {
+            self.push(
+                format!(
+                    "The closest pair of the halves is {} and {} at distance d = {:.1}. A closer pair across the line has to be within d of it, but the strip only has {}, so they are the closest pair of this part",
+                    best.0,
+                    best.1,
+                    d,
+                    plural(strip.len(), "point")
+                ),
+                highlights,
+                steps,
+            );
+            return best;
+        }
+        self.push(
+            format!(
+                "The closest pair of the halves is {} and {} at distance d = {:.1}. A closer pair across the line has to be within d of it, so only the {} points in the strip are checked, from top to bottom",
+                best.0,
+                best.1,
+                d,
+                strip.len()
+            ),
+            highlights,
+            steps,
+        );
+        for (i, a) in strip.iter().enumerate() {
+            for b in &strip[i + 1..] {
+                if points[*b].1 - points[*a].1 >= d {
+                    break;
+                }
+                *comparisons += 1;
+                let distance = self.distance((*a, *b));
+                let closer = distance < d;
+                let (a, b) = ordered_pair(*a, *b);
+                highlights.compared = Some((
+                    a,
+                    b,
+                    if closer {
+                        ElementState::Selected
+                    } else {
+                        ElementState::Rejected
+                    },
+                ));
+                let description = if closer {
+                    for index in [best.0, best.1] {
+                        highlights.points.insert(
+                            index,
+                            if strip.contains(&index) {
+                                ElementState::Active
+                            } else {
+                                ElementState::Frontier
+                            },
+                        );
+                    }
+                    best = (a, b);
+                    d = distance;
+                    highlights.strip = Some((line - d, line + d));
+                    highlights.best = Some(best);
+                    for index in [best.0, best.1] {
+                        highlights.points.insert(index, ElementState::Selected);
+                    }
+                    format!(
+                        "Points {} and {} are {:.1} apart, which is closer, so they are the new closest pair and the strip narrows",
+                        a, b, distance
+                    )
+                } else {
+                    format!(
+                        "Points {} and {} are {:.1} apart, which isn't closer than d = {:.1}",
+                        a, b, distance, d
+                    )
+                };
+                self.push(description, highlights, steps);
+            }
+        }
+        highlights.compared = None;
+        highlights.strip = None;
+        self.push(
+            format!(
+                "The strip has been checked without comparing points that are more than d apart vertically. The closest pair of this part is {} and {} at distance {:.1}",
+                best.0, best.1, d
+            ),
+            highlights,
+            steps,
+        );
+        best
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = draw_canvas(&self.points, &highlights.points);
+        if let Some((left, right)) = highlights.part {
+            diagram.add_figure(
+                Figure::new(FigureKind::Rect {
+                    position: (left, 0.0),
+                    size: (right - left, CANVAS_HEIGHT),
+                })
+                .with_state(ElementState::Frontier),
+            );
+            if let Some((strip_left, strip_right)) = highlights.strip {
+                let (strip_left, strip_right) = (strip_left.max(left), strip_right.min(right));
+                diagram.add_figure(
+                    Figure::new(FigureKind::Rect {
+                        position: (strip_left, 0.0),
+                        size: (strip_right - strip_left, CANVAS_HEIGHT),
+                    })
+                    .with_state(ElementState::Active),
+                );
+            }
+        }
+        for x in &highlights.lines {
+            diagram
+                .add_figure(Figure::new(FigureKind::Line((*x, 0.0), (*x, CANVAS_HEIGHT))).dashed());
+        }
+        if let Some(x) = highlights.line {
+            diagram.add_figure(
+                Figure::new(FigureKind::Line((x, 0.0), (x, CANVAS_HEIGHT)))
+                    .dashed()
+                    .with_state(ElementState::Active),
+            );
+        }
+        if let Some((a, b)) = highlights.best {
+            diagram.add_edge(
+                DiagramEdge::new(point_key(a), point_key(b))
+                    .with_label(format!("{:.1}", self.distance((a, b))))
+                    .with_state(ElementState::Selected),
+            );
+        }
+        if let Some((a, b, state)) = highlights.compared {
+            if highlights.best != Some((a, b)) {
+                diagram.add_edge(
+                    DiagramEdge::new(point_key(a), point_key(b))
+                        .dashed()
+                        .with_state(state),
+                );
+            }
+        }
+        diagram
+    }
+}
+impl Visualizer for ClosestPair {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new("Find closest pair", vec![]),
+            random_points_operation(16),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Find closest pair" => {
+                check_points(&self.points, 2)?;
+                self.find_closest_pair(&mut steps);
+            }
+            "Random points" => {
+                self.points = random_points(parse_point_count(values)?);
+                steps.push(
+                    format!("Place {} at random", plural(self.points.len(), "point")),
+                    self.diagram(),
+                );
+            }
+            "Clear" => {
+                self.points.clear();
+                steps.push("Remove all points".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+    fn clickable(&self) -> bool {
+        true
+    }
+    fn click(&mut self, position: (f32, f32)) -> Option<DiagramSteps> {
+        toggle_point(&mut self.points, position).then(DiagramSteps::new)
+    }
+}
+
+/// The pair with the smaller index first, so that pairs are always shown the same way.
+fn ordered_pair(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
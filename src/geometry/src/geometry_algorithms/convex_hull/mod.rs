@@ -0,0 +1,370 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use crate::*;
+
+/// The states of the points and the edges between them at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    points: BTreeMap<usize, ElementState>,
+    edges: Vec<(usize, usize, ElementState)>,
+    /// The edges that are only being tried, which are dashed.
+    tried: Vec<(usize, usize, ElementState)>,
+    /// The finished hull, which is filled.
+    hull: Vec<usize>,
+}
+
+/// The convex hull of a set of points, which is the smallest convex polygon that contains all of them. It can be
+/// found with Graham scan, which sorts the points by angle and keeps a stack of the turns that go left, or with Jarvis
+/// march, which wraps around the points like a gift by always choosing the point furthest to the right.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvexHull {
+    points: Vec<Point>,
+}
+impl ConvexHull {
+    pub fn new(points: &[Point]) -> Self {
+        Self {
+            points: points.to_vec(),
+        }
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = draw_canvas(&self.points, &highlights.points);
+        if highlights.hull.len() >= 3 {
+            diagram.add_figure(
+                Figure::new(FigureKind::Polygon(
+                    highlights
+                        .hull
+                        .iter()
+                        .map(|index| self.points[*index])
+                        .collect(),
+                ))
+                .with_state(ElementState::Selected),
+            );
+        }
+        for (from, to, state) in &highlights.edges {
+            diagram.add_edge(DiagramEdge::new(point_key(*from), point_key(*to)).with_state(*state));
+        }
+        for (from, to, state) in &highlights.tried {
+            diagram.add_edge(
+                DiagramEdge::new(point_key(*from), point_key(*to))
+                    .dashed()
+                    .with_state(*state),
+            );
+        }
+        diagram
+    }
+    /// The steps end with the hull as a closed polygon.
+    fn push_hull(
+        &self,
+        description: String,
+        hull: &[usize],
+        title: &str,
+        steps: &mut DiagramSteps,
+    ) {
+        let highlights = Highlights {
+            points: hull
+                .iter()
+                .map(|index| (*index, ElementState::Selected))
+                .collect(),
+            edges: (0..hull.len())
+                .map(|i| (hull[i], hull[(i + 1) % hull.len()], ElementState::Selected))
+                .collect(),
+            hull: hull.to_vec(),
+            ..Default::default()
+        };
+        let step = steps.push(description, self.draw(&highlights));
+        step.lists = vec![points_list(title, hull)];
+    }
+    fn graham_scan(&self, steps: &mut DiagramSteps) {
+        let points = &self.points;
+        // The lowest point on the screen, or the leftmost one of them, which is always on the hull
+        let pivot = (0..points.len())
+            .max_by(|a, b| {
+                points[*a]
+                    .1
+                    .total_cmp(&points[*b].1)
+                    .then(points[*b].0.total_cmp(&points[*a].0))
+            })
+            .unwrap();
+        let angle = |index: usize| {
+            let (x, y) = points[index];
+            (points[pivot].1 - y).atan2(x - points[pivot].0)
+        };
+        let mut order = (0..points.len())
+            .filter(|index| *index != pivot)
+            .collect::<Vec<usize>>();
+        order.sort_by(|a, b| {
+            angle(*a).total_cmp(&angle(*b)).then(
+                distance(points[pivot], points[*a]).total_cmp(&distance(points[pivot], points[*b])),
+            )
+        });
+        let step = steps.push(
+            format!(
+                "Point {} is the lowest, so it is on the hull. Sort the other points by the angle of the line from it to them, counterclockwise",
+                pivot
+            ),
+            self.draw(&Highlights {
+                points: BTreeMap::from([(pivot, ElementState::Active)]),
+                tried: order
+                    .iter()
+                    .map(|index| (pivot, *index, ElementState::Frontier))
+                    .collect(),
+                ..Default::default()
+            }),
+        );
+        step.lists = vec![points_list("Sorted points", &order)];
+        let mut stack = vec![pivot];
+        for next in order {
+            while stack.len() >= 2 {
+                let (a, b) = (stack[stack.len() - 2], stack[stack.len() - 1]);
+                let turn = cross(points[a], points[b], points[next]);
+                if turn > 0.0 {
+                    break;
+                }
+                let mut highlights = stack_highlights(&stack);
+                highlights.points.insert(b, ElementState::Rejected);
+                highlights.points.insert(next, ElementState::Frontier);
+                highlights.edges.pop();
+                highlights.edges.push((a, b, ElementState::Rejected));
+                highlights.tried = vec![(b, next, ElementState::Rejected)];
+                let step = steps.push(
+                    format!(
+                        "Points {}, {} and {} {}, so {} can't be on the hull. Pop it from the stack",
+                        a,
+                        b,
+                        next,
+                        if turn < 0.0 {
+                            "make a right turn"
+                        } else {
+                            "are on a line"
+                        },
+                        b
+                    ),
+                    self.draw(&highlights),
+                );
+                step.lists = vec![points_list("Stack", &stack)];
+                stack.pop();
+            }
+            let description = match stack[..] {
+                [.., a, b] => format!(
+                    "Points {}, {} and {} make a left turn, so push {} to the stack",
+                    a, b, next, next
+                ),
+                _ => format!(
+                    "Point {} is the first point by angle, so push it to the stack",
+                    next
+                ),
+            };
+            stack.push(next);
+            let mut highlights = stack_highlights(&stack);
+            highlights.points.insert(next, ElementState::Frontier);
+            let step = steps.push(description, self.draw(&highlights));
+            step.lists = vec![points_list("Stack", &stack)];
+        }
+        self.push_hull(
+            format!(
+                "Every point has been pushed once, and the {} left on the stack are the convex hull",
+                plural(stack.len(), "point")
+            ),
+            &stack,
+            "Stack",
+            steps,
+        );
+    }
+    fn jarvis_march(&self, steps: &mut DiagramSteps) {
+        let points = &self.points;
+        let n = points.len();
+        // The leftmost point, or the lowest one of them, which is always on the hull
+        let start = (0..n)
+            .min_by(|a, b| {
+                points[*a]
+                    .0
+                    .total_cmp(&points[*b].0)
+                    .then(points[*b].1.total_cmp(&points[*a].1))
+            })
+            .unwrap();
+        let mut hull = vec![start];
+        let step = steps.push(
+            format!(
+                "Point {} is the leftmost, so it is on the hull. Wrap around the points counterclockwise from it",
+                start
+            ),
+            self.draw(&Highlights {
+                points: BTreeMap::from([(start, ElementState::Active)]),
+                ..Default::default()
+            }),
+        );
+        step.lists = vec![points_list("Hull", &hull)];
+        let mut current = start;
+        loop {
+            let first = (current + 1) % n;
+            let mut candidate = first;
+            for other in (0..n).filter(|other| *other != current && *other != first) {
+                let turn = cross(points[current], points[candidate], points[other]);
+                let farther = distance(points[current], points[other])
+                    > distance(points[current], points[candidate]);
+                let (replaced, reason) = if turn < 0.0 {
+                    (true, "to the right of")
+                } else if turn > 0.0 {
+                    (false, "to the left of")
+                } else if farther {
+                    (true, "on the line but farther than")
+                } else {
+                    (false, "on the line but closer than")
+                };
+                let mut highlights = march_highlights(&hull, current);
+                highlights.points.insert(candidate, ElementState::Frontier);
+                highlights.points.insert(
+                    other,
+                    if replaced {
+                        ElementState::Frontier
+                    } else {
+                        ElementState::Rejected
+                    },
+                );
+                highlights
+                    .edges
+                    .push((current, candidate, ElementState::Frontier));
+                highlights.tried = vec![(
+                    current,
+                    other,
+                    if replaced {
+                        ElementState::Frontier
+                    } else {
+                        ElementState::Rejected
+                    },
+                )];
+                let description = format!(
+                    "Point {} is {} the line from {} to {}, so {}",
+                    other,
+                    reason,
+                    current,
+                    candidate,
+                    if replaced {
+                        format!("{} becomes the candidate", other)
+                    } else {
+                        format!("{} stays the candidate", candidate)
+                    }
+                );
+                let step = steps.push(description, self.draw(&highlights));
+                step.lists = vec![points_list("Hull", &hull)];
+                if replaced {
+                    candidate = other;
+                }
+            }
+            if candidate == start {
+                break;
+            }
+            hull.push(candidate);
+            let highlights = march_highlights(&hull, candidate);
+            let step = steps.push(
+                format!(
+                    "No point is to the right of the line from {} to {}, so {} is the next point on the hull",
+                    current, candidate, candidate
+                ),
+                self.draw(&highlights),
+            );
+            step.lists = vec![points_list("Hull", &hull)];
+            current = candidate;
+        }
+        self.push_hull(
+            format!(
+                "No point is to the right of the line from {} back to {}, so the wrap is closed. The convex hull has {}",
+                current,
+                start,
+                plural(hull.len(), "point")
+            ),
+            &hull,
+            "Hull",
+            steps,
+        );
+    }
+}
+impl Visualizer for ConvexHull {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new("Graham scan", vec![]),
+            Operation::new("Jarvis march", vec![]),
+            random_points_operation(12),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Graham scan" => {
+                check_points(&self.points, 3)?;
+                self.graham_scan(&mut steps);
+            }
+            "Jarvis march" => {
+                check_points(&self.points, 3)?;
+                self.jarvis_march(&mut steps);
+            }
+            "Random points" => {
+                self.points = random_points(parse_point_count(values)?);
+                steps.push(
+                    format!("Place {} at random", plural(self.points.len(), "point")),
+                    self.diagram(),
+                );
+            }
+            "Clear" => {
+                self.points.clear();
+                steps.push("Remove all points".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+    fn clickable(&self) -> bool {
+        true
+    }
+    fn click(&mut self, position: (f32, f32)) -> Option<DiagramSteps> {
+        toggle_point(&mut self.points, position).then(DiagramSteps::new)
+    }
+}
+
+/// The stack of Graham scan as a chain of edges.
+fn stack_highlights(stack: &[usize]) -> Highlights {
+    Highlights {
+        points: stack
+            .iter()
+            .map(|index| (*index, ElementState::Active))
+            .collect(),
+        edges: stack
+            .windows(2)
+            .map(|edge| (edge[0], edge[1], ElementState::Active))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// The part of the hull that Jarvis march has wrapped so far, which ends at the current point.
+fn march_highlights(hull: &[usize], current: usize) -> Highlights {
+    let mut points = hull
+        .iter()
+        .map(|index| (*index, ElementState::Selected))
+        .collect::<BTreeMap<usize, ElementState>>();
+    points.insert(current, ElementState::Active);
+    Highlights {
+        points,
+        edges: hull
+            .windows(2)
+            .map(|edge| (edge[0], edge[1], ElementState::Selected))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn points_list(title: &str, points: &[usize]) -> StepList {
+    StepList::new(
+        title,
+        points
+            .iter()
+            .map(|index| format!("Point {}", index))
+            .collect(),
+    )
+}
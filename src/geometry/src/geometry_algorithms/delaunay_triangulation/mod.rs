@@ -0,0 +1,688 @@
+use std::collections::BTreeSet;
+
+use diagram::*;
+
+use crate::*;
+
+/// How far the corners of the large triangle that contains the canvas are from it. They are far enough that they are
+/// like points at infinity for the circumcircle tests, but close enough that the tests can be computed exactly.
+const SUPER_DISTANCE: f32 = 500_000_000.0;
+/// How far the corners of the large triangle are drawn from the canvas. Lines to them only need to go off the canvas.
+const SUPER_DRAW_DISTANCE: f32 = 3000.0;
+const VORONOI_VERTEX_RADIUS: f32 = 4.0;
+/// How long the rays of the Voronoi edges that go to infinity are drawn.
+const RAY_LENGTH: f32 = 2000.0;
+
+/// The corners of a triangle, which are always in counterclockwise order on the screen.
+type Triangle = [usize; 3];
+
+/// The orientation of the points like [`cross`], but computed exactly, since the corners of the large triangle are
+/// far away. Points on the canvas always have whole coordinates, so integers can be used.
+fn orientation(o: Point, a: Point, b: Point) -> i128 {
+    let (o, a, b) = (exact(o), exact(a), exact(b));
+    (a.0 - o.0) * (o.1 - b.1) - (o.1 - a.1) * (b.0 - o.0)
+}
+
+fn exact(point: Point) -> (i128, i128) {
+    (point.0.round() as i128, point.1.round() as i128)
+}
+
+fn wide(point: Point) -> (f64, f64) {
+    (point.0 as f64, point.1 as f64)
+}
+
+/// Whether `d` is inside the circle that goes through the corners of the counterclockwise triangle `a`, `b`, `c`.
+/// It is computed exactly like [`orientation`].
+fn in_circumcircle(a: Point, b: Point, c: Point, d: Point) -> bool {
+    // The y axis is flipped so that the triangle is counterclockwise in the usual coordinates
+    let relative = |point: Point| {
+        let (point, d) = (exact(point), exact(d));
+        (point.0 - d.0, d.1 - point.1)
+    };
+    let ((ax, ay), (bx, by), (cx, cy)) = (relative(a), relative(b), relative(c));
+    let determinant = (ax * ax + ay * ay) * (bx * cy - cx * by)
+        - (bx * bx + by * by) * (ax * cy - cx * ay)
+        + (cx * cx + cy * cy) * (ax * by - bx * ay);
+    determinant > 0
+}
+
+/// The center of the circle that goes through the points.
+fn circumcenter(a: Point, b: Point, c: Point) -> Point {
+    let ((ax, ay), (bx, by), (cx, cy)) = (wide(a), wide(b), wide(c));
+    let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+    let (a2, b2, c2) = (ax * ax + ay * ay, bx * bx + by * by, cx * cx + cy * cy);
+    (
+        ((a2 * (by - cy) + b2 * (cy - ay) + c2 * (ay - by)) / d) as f32,
+        ((a2 * (cx - bx) + b2 * (ax - cx) + c2 * (bx - ax)) / d) as f32,
+    )
+}
+
+/// The corners of the large triangle at the distance from the canvas, in counterclockwise order.
+fn super_corners(distance: f32) -> [Point; 3] {
+    let (center_x, center_y) = (CANVAS_WIDTH / 2.0, CANVAS_HEIGHT / 2.0);
+    [
+        (center_x - distance, center_y + distance),
+        (center_x + distance, center_y + distance),
+        (center_x, center_y - distance),
+    ]
+}
+
+fn edges(triangle: &Triangle) -> [(usize, usize); 3] {
+    let [a, b, c] = *triangle;
+    [(a, b), (b, c), (c, a)]
+}
+
+fn undirected((a, b): (usize, usize)) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+/// A triangulation of the points that have been inserted so far, inside a large triangle that contains all of them.
+#[derive(Clone, Debug, PartialEq)]
+struct Triangulation {
+    /// The points followed by the three corners of the large triangle.
+    vertices: Vec<Point>,
+    triangles: Vec<Triangle>,
+}
+impl Triangulation {
+    fn new(points: &[Point]) -> Self {
+        let mut vertices = points.to_vec();
+        vertices.extend(super_corners(SUPER_DISTANCE));
+        let n = points.len();
+        Self {
+            vertices,
+            triangles: vec![[n, n + 1, n + 2]],
+        }
+    }
+    fn is_super(&self, vertex: usize) -> bool {
+        vertex >= self.vertices.len() - 3
+    }
+    /// The index of the triangle that has the edge from `a` to `b`, in that direction.
+    fn triangle_with_edge(&self, a: usize, b: usize) -> Option<usize> {
+        self.triangles
+            .iter()
+            .position(|triangle| edges(triangle).contains(&(a, b)))
+    }
+    /// The corner of the triangle that isn't on the edge.
+    fn opposite(&self, triangle: usize, (a, b): (usize, usize)) -> usize {
+        *self.triangles[triangle]
+            .iter()
+            .find(|vertex| **vertex != a && **vertex != b)
+            .unwrap()
+    }
+    fn remove(&mut self, triangles: &[usize]) {
+        let mut triangles = triangles.to_vec();
+        triangles.sort_unstable();
+        for index in triangles.into_iter().rev() {
+            self.triangles.remove(index);
+        }
+    }
+    /// The position of the vertex in the diagram, where the corners of the large triangle are closer to the canvas.
+    fn drawn(&self, vertex: usize) -> Point {
+        if self.is_super(vertex) {
+            super_corners(SUPER_DRAW_DISTANCE)[vertex + 3 - self.vertices.len()]
+        } else {
+            self.vertices[vertex]
+        }
+    }
+    /// The triangles that don't touch the corners of the large triangle.
+    fn real_triangles(&self) -> Vec<Triangle> {
+        self.triangles
+            .iter()
+            .filter(|triangle| triangle.iter().all(|vertex| !self.is_super(*vertex)))
+            .copied()
+            .collect()
+    }
+}
+
+/// The states of the triangulation and the Voronoi diagram at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    points: Vec<(usize, ElementState)>,
+    triangles: Vec<(Triangle, ElementState)>,
+    edges: Vec<((usize, usize), ElementState)>,
+    /// An edge that was just flipped away.
+    removed: Option<(usize, usize)>,
+    circle: Option<(Point, f32, ElementState)>,
+    /// The edges to the corners of the large triangle are only shown while the points are being inserted.
+    show_super: bool,
+    /// The edges that are left to be checked, which are listed.
+    check: Option<Vec<(usize, usize)>>,
+    voronoi_vertices: Vec<Point>,
+    voronoi_edges: Vec<(Point, Point)>,
+    /// The triangulation is dashed under the finished Voronoi diagram.
+    dashed: bool,
+}
+
+/// The Delaunay triangulation of a set of points, where the circumcircle of every triangle is empty. The points are
+/// inserted one by one by splitting the triangle that they are in, and the edges around them are flipped until every
+/// triangle is empty again. Its dual is the Voronoi diagram, which splits the plane into the areas closest to each
+/// point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DelaunayTriangulation {
+    points: Vec<Point>,
+}
+impl DelaunayTriangulation {
+    pub fn new(points: &[Point]) -> Self {
+        Self {
+            points: points.to_vec(),
+        }
+    }
+    /// The triangulation of all the points, which is built without any steps.
+    fn triangulation(&self) -> Triangulation {
+        let mut triangulation = Triangulation::new(&self.points);
+        for index in 0..self.points.len() {
+            self.insert(&mut triangulation, index, &mut DiagramSteps::new());
+        }
+        triangulation
+    }
+    fn vertex_name(&self, vertex: usize) -> String {
+        if vertex >= self.points.len() {
+            ["A", "B", "C"][vertex - self.points.len()].to_string()
+        } else {
+            vertex.to_string()
+        }
+    }
+    fn edge_name(&self, (a, b): (usize, usize)) -> String {
+        format!("{}–{}", self.vertex_name(a), self.vertex_name(b))
+    }
+    fn point_name(&self, vertex: usize) -> String {
+        if vertex >= self.points.len() {
+            format!("Corner {}", self.vertex_name(vertex))
+        } else {
+            format!("Point {}", vertex)
+        }
+    }
+    fn push(
+        &self,
+        description: String,
+        triangulation: &Triangulation,
+        highlights: &Highlights,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(triangulation, highlights));
+        if let Some(check) = &highlights.check {
+            step.lists = vec![StepList::new(
+                "Edges to check",
+                check
+                    .iter()
+                    .rev()
+                    .map(|edge| self.edge_name(*edge))
+                    .collect(),
+            )];
+        }
+    }
+    /// Inserts the point into the triangulation and flips the edges around it until the triangulation is Delaunay
+    /// again. Returns the number of flips.
+    fn insert(
+        &self,
+        triangulation: &mut Triangulation,
+        point: usize,
+        steps: &mut DiagramSteps,
+    ) -> usize {
+        let position = self.points[point];
+        let vertices = triangulation.vertices.clone();
+        let name = |vertex: usize| self.vertex_name(vertex);
+        let (container, on_edge) = triangulation
+            .triangles
+            .iter()
+            .enumerate()
+            .find_map(|(index, triangle)| {
+                let orientations =
+                    edges(triangle).map(|(a, b)| orientation(vertices[a], vertices[b], position));
+                orientations
+                    .iter()
+                    .all(|orientation| *orientation >= 0)
+                    .then(|| {
+                        (
+                            index,
+                            orientations
+                                .iter()
+                                .position(|orientation| *orientation == 0)
+                                .map(|edge| edges(triangle)[edge]),
+                        )
+                    })
+            })
+            .unwrap();
+        let [a, b, c] = triangulation.triangles[container];
+        let (description, old, mut check) = match on_edge.and_then(|(a, b)| {
+            triangulation
+                .triangle_with_edge(b, a)
+                .map(|other| (a, b, other))
+        }) {
+            Some((a, b, other)) => {
+                let c = triangulation.opposite(container, (a, b));
+                let d = triangulation.opposite(other, (b, a));
+                let old = vec![[a, b, c], [b, a, d]];
+                triangulation.remove(&[container, other]);
+                triangulation.triangles.extend([
+                    [b, c, point],
+                    [c, a, point],
+                    [a, d, point],
+                    [d, b, point],
+                ]);
+                (
+                    format!(
+                        "Point {} is on the edge {}, so connect it to the corners of the triangles on both sides, which splits them into 4",
+                        point,
+                        self.edge_name((a, b))
+                    ),
+                    old,
+                    vec![(b, c), (c, a), (a, d), (d, b)],
+                )
+            }
+            None => {
+                triangulation.remove(&[container]);
+                triangulation
+                    .triangles
+                    .extend([[a, b, point], [b, c, point], [c, a, point]]);
+                (
+                    format!(
+                        "Point {} is inside the triangle {}, {} and {}, so connect it to the corners, which splits the triangle into 3",
+                        point,
+                        name(a),
+                        name(b),
+                        name(c)
+                    ),
+                    vec![[a, b, c]],
+                    vec![(a, b), (b, c), (c, a)],
+                )
+            }
+        };
+        let mut highlights = Highlights {
+            points: vec![(point, ElementState::Active)],
+            triangles: old
+                .into_iter()
+                .map(|triangle| (triangle, ElementState::Frontier))
+                .collect(),
+            edges: check
+                .iter()
+                .flat_map(|(a, b)| [(*a, point), (*b, point)])
+                .map(|edge| (undirected(edge), ElementState::Active))
+                .collect(),
+            show_super: true,
+            check: Some(check.clone()),
+            ..Default::default()
+        };
+        self.push(description, triangulation, &highlights, steps);
+        let mut flips = 0;
+        while let Some((a, b)) = check.pop() {
+            // The edges of the large triangle have nothing on their other side
+            let Some(other) = triangulation.triangle_with_edge(b, a) else {
+                continue;
+            };
+            let d = triangulation.opposite(other, (b, a));
+            let illegal = in_circumcircle(vertices[a], vertices[b], position, vertices[d]);
+            // The circumcircles of triangles with a corner of the large triangle are too large to be drawn
+            let circle = (!triangulation.is_super(a) && !triangulation.is_super(b)).then(|| {
+                let center = circumcenter(vertices[a], vertices[b], position);
+                (center, distance(center, position))
+            });
+            highlights.points = vec![
+                (point, ElementState::Active),
+                (
+                    d,
+                    if illegal {
+                        ElementState::Rejected
+                    } else {
+                        ElementState::Frontier
+                    },
+                ),
+            ];
+            highlights.triangles = vec![([a, b, point], ElementState::Frontier)];
+            highlights.removed = None;
+            let description = if illegal {
+                let own = triangulation.triangle_with_edge(a, b).unwrap();
+                triangulation.remove(&[own, other]);
+                triangulation
+                    .triangles
+                    .extend([[a, d, point], [d, b, point]]);
+                check.extend([(a, d), (d, b)]);
+                flips += 1;
+                highlights.edges = vec![(undirected((point, d)), ElementState::Selected)];
+                highlights.removed = Some((a, b));
+                highlights.circle =
+                    circle.map(|(center, radius)| (center, radius, ElementState::Rejected));
+                format!(
+                    "{} is inside the circumcircle of {}, {} and {}, so the edge {} is illegal. Flip it to {}, and check the edges on the other side",
+                    self.point_name(d),
+                    name(point),
+                    name(a),
+                    name(b),
+                    self.edge_name((a, b)),
+                    self.edge_name((point, d))
+                )
+            } else {
+                highlights.edges = vec![(undirected((a, b)), ElementState::Frontier)];
+                highlights.circle =
+                    circle.map(|(center, radius)| (center, radius, ElementState::Frontier));
+                format!(
+                    "{} is outside the circumcircle of {}, {} and {}, so the edge {} is legal",
+                    self.point_name(d),
+                    name(point),
+                    name(a),
+                    name(b),
+                    self.edge_name((a, b))
+                )
+            };
+            highlights.check = Some(check.clone());
+            self.push(description, triangulation, &highlights, steps);
+        }
+        flips
+    }
+    fn triangulate(&self, steps: &mut DiagramSteps) {
+        let mut triangulation = Triangulation::new(&self.points);
+        self.push(
+            "Start with a large triangle with the corners A, B and C far outside the canvas, so that it contains every point".to_string(),
+            &triangulation,
+            &Highlights {
+                show_super: true,
+                ..Default::default()
+            },
+            steps,
+        );
+        for point in 0..self.points.len() {
+            let flips = self.insert(&mut triangulation, point, steps);
+            self.push(
+                format!(
+                    "Every edge around point {} is legal, so it is inserted after {}",
+                    point,
+                    plural(flips, "flip")
+                ),
+                &triangulation,
+                &Highlights {
+                    points: vec![(point, ElementState::Selected)],
+                    show_super: true,
+                    check: Some(vec![]),
+                    ..Default::default()
+                },
+                steps,
+            );
+        }
+        let triangles = triangulation.real_triangles();
+        self.push(
+            format!(
+                "Every point is inserted. Remove the triangles that touch the corners of the large triangle, and what is left is the Delaunay triangulation with {}",
+                plural(triangles.len(), "triangle")
+            ),
+            &triangulation,
+            &Highlights {
+                triangles: triangles
+                    .into_iter()
+                    .map(|triangle| (triangle, ElementState::Selected))
+                    .collect(),
+                ..Default::default()
+            },
+            steps,
+        );
+    }
+    fn voronoi_diagram(&self, steps: &mut DiagramSteps) -> Result<(), String> {
+        let triangulation = self.triangulation();
+        let triangles = triangulation.real_triangles();
+        if triangles.is_empty() {
+            return Err(
+                "The points are on a line, so they don't have a Voronoi diagram with vertices"
+                    .to_string(),
+            );
+        }
+        let points = &self.points;
+        let centers = triangles
+            .iter()
+            .map(|[a, b, c]| circumcenter(points[*a], points[*b], points[*c]))
+            .collect::<Vec<Point>>();
+        let mut highlights = Highlights::default();
+        self.push(
+            format!(
+                "Start from the Delaunay triangulation. Each of its {} becomes a vertex of the Voronoi diagram, and each of its edges becomes a Voronoi edge",
+                plural(triangles.len(), "triangle")
+            ),
+            &triangulation,
+            &highlights,
+            steps,
+        );
+        for (index, triangle) in triangles.iter().enumerate() {
+            let [a, b, c] = *triangle;
+            let center = centers[index];
+            let mut connected = 0;
+            let mut rays = 0;
+            for (from, to) in edges(triangle) {
+                match triangles
+                    .iter()
+                    .position(|other| edges(other).contains(&(to, from)))
+                {
+                    Some(other) if other < index => {
+                        highlights.voronoi_edges.push((center, centers[other]));
+                        connected += 1;
+                    }
+                    Some(_) => {}
+                    None => {
+                        // The edge is on the hull, so the Voronoi edge goes out to infinity away from the triangle
+                        let (p, q) = (points[from], points[to]);
+                        let middle = ((p.0 + q.0) / 2.0, (p.1 + q.1) / 2.0);
+                        let mut normal = (q.1 - p.1, p.0 - q.0);
+                        let corner = points[triangulation.opposite(
+                            triangulation.triangle_with_edge(from, to).unwrap(),
+                            (from, to),
+                        )];
+                        if normal.0 * (corner.0 - middle.0) + normal.1 * (corner.1 - middle.1) > 0.0
+                        {
+                            normal = (-normal.0, -normal.1);
+                        }
+                        let length = (normal.0 * normal.0 + normal.1 * normal.1).sqrt();
+                        highlights.voronoi_edges.push((
+                            center,
+                            (
+                                center.0 + normal.0 / length * RAY_LENGTH,
+                                center.1 + normal.1 / length * RAY_LENGTH,
+                            ),
+                        ));
+                        rays += 1;
+                    }
+                }
+            }
+            highlights.voronoi_vertices.push(center);
+            highlights.triangles = vec![(*triangle, ElementState::Frontier)];
+            highlights.points = vec![
+                (a, ElementState::Frontier),
+                (b, ElementState::Frontier),
+                (c, ElementState::Frontier),
+            ];
+            highlights.circle = Some((center, distance(center, points[a]), ElementState::Frontier));
+            let mut description = format!(
+                "The circumcircle of {}, {} and {} has no points inside it, so its center is a Voronoi vertex",
+                a, b, c
+            );
+            if connected == 1 {
+                description +=
+                    ". Connect it to the center of the neighboring triangle that is already done";
+            } else if connected > 1 {
+                description += &format!(
+                    ". Connect it to the centers of the {} neighboring triangles that are already done",
+                    connected
+                );
+            }
+            if rays > 0 {
+                description += &format!(
+                    ". {} on the hull, so draw {} out from the center",
+                    if rays == 1 {
+                        "An edge of the triangle is"
+                    } else {
+                        "Edges of the triangle are"
+                    },
+                    if rays == 1 { "a ray" } else { "rays" }
+                );
+            }
+            self.push(description, &triangulation, &highlights, steps);
+        }
+        highlights.triangles.clear();
+        highlights.points.clear();
+        highlights.circle = None;
+        highlights.dashed = true;
+        self.push(
+            "Every Voronoi vertex is connected. Each cell of the Voronoi diagram is the area that is closer to its point than to any other point, and the Voronoi edges cross the Delaunay edges at right angles".to_string(),
+            &triangulation,
+            &highlights,
+            steps,
+        );
+        Ok(())
+    }
+    fn draw(&self, triangulation: &Triangulation, highlights: &Highlights) -> Diagram {
+        let mut diagram = draw_canvas(
+            &self.points,
+            &highlights
+                .points
+                .iter()
+                .filter(|(vertex, _)| !triangulation.is_super(*vertex))
+                .copied()
+                .collect(),
+        );
+        for (triangle, state) in &highlights.triangles {
+            diagram.add_figure(
+                Figure::new(FigureKind::Polygon(
+                    triangle
+                        .iter()
+                        .map(|vertex| triangulation.drawn(*vertex))
+                        .collect(),
+                ))
+                .with_state(*state),
+            );
+        }
+        let mut lines = BTreeSet::new();
+        for triangle in &triangulation.triangles {
+            for edge in edges(triangle) {
+                lines.insert(undirected(edge));
+            }
+        }
+        for (a, b) in lines {
+            let to_super = triangulation.is_super(a) || triangulation.is_super(b);
+            if to_super && !highlights.show_super {
+                continue;
+            }
+            let mut figure = Figure::new(FigureKind::Line(
+                triangulation.drawn(a),
+                triangulation.drawn(b),
+            ));
+            if to_super || highlights.dashed {
+                figure = figure.dashed();
+            }
+            if let Some((_, state)) = highlights.edges.iter().find(|(edge, _)| *edge == (a, b)) {
+                figure = figure.with_state(*state);
+            }
+            diagram.add_figure(figure);
+        }
+        if let Some((a, b)) = highlights.removed {
+            diagram.add_figure(
+                Figure::new(FigureKind::Line(
+                    triangulation.drawn(a),
+                    triangulation.drawn(b),
+                ))
+                .dashed()
+                .with_state(ElementState::Rejected),
+            );
+        }
+        if let Some((center, radius, state)) = highlights.circle {
+            diagram.add_figure(
+                Figure::new(FigureKind::Circle { center, radius })
+                    .dashed()
+                    .with_state(state),
+            );
+        }
+        for (from, to) in &highlights.voronoi_edges {
+            diagram.add_figure(
+                Figure::new(FigureKind::Line(*from, *to)).with_state(ElementState::Selected),
+            );
+        }
+        for center in &highlights.voronoi_vertices {
+            diagram.add_figure(
+                Figure::new(FigureKind::Circle {
+                    center: *center,
+                    radius: VORONOI_VERTEX_RADIUS,
+                })
+                .with_state(ElementState::Selected),
+            );
+        }
+        diagram
+    }
+}
+impl Visualizer for DelaunayTriangulation {
+    fn diagram(&self) -> Diagram {
+        self.draw(&self.triangulation(), &Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new("Triangulate", vec![]),
+            Operation::new("Voronoi diagram", vec![]),
+            random_points_operation(12),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Triangulate" => {
+                check_points(&self.points, 3)?;
+                self.triangulate(&mut steps);
+            }
+            "Voronoi diagram" => {
+                check_points(&self.points, 3)?;
+                self.voronoi_diagram(&mut steps)?;
+            }
+            "Random points" => {
+                self.points = random_points(parse_point_count(values)?);
+                steps.push(
+                    format!("Place {} at random", plural(self.points.len(), "point")),
+                    self.diagram(),
+                );
+            }
+            "Clear" => {
+                self.points.clear();
+                steps.push("Remove all points".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+    fn clickable(&self) -> bool {
+        true
+    }
+    /// Clicking a point removes it, and other clicks insert a new point into the triangulation step by step.
+    fn click(&mut self, position: (f32, f32)) -> Option<DiagramSteps> {
+        if let Some(index) = clicked_point(&self.points, position) {
+            self.points.remove(index);
+            return Some(DiagramSteps::new());
+        }
+        if self.points.len() >= MAX_POINTS {
+            return None;
+        }
+        let mut triangulation = self.triangulation();
+        self.points.push(canvas_point(position));
+        let point = self.points.len() - 1;
+        triangulation = Triangulation {
+            vertices: Triangulation::new(&self.points).vertices,
+            triangles: triangulation
+                .triangles
+                .into_iter()
+                .map(|triangle| {
+                    triangle.map(|vertex| if vertex >= point { vertex + 1 } else { vertex })
+                })
+                .collect(),
+        };
+        let mut steps = DiagramSteps::new();
+        let flips = self.insert(&mut triangulation, point, &mut steps);
+        let highlights = Highlights {
+            points: vec![(point, ElementState::Selected)],
+            ..Default::default()
+        };
+        self.push(
+            format!(
+                "Every edge around point {} is legal, so it is inserted after {}. The triangulation of the {} is Delaunay again",
+                point,
+                plural(flips, "flip"),
+                plural(self.points.len(), "point")
+            ),
+            &triangulation,
+            &highlights,
+            &mut steps,
+        );
+        Some(steps)
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::VecDeque;
+
+use diagram::*;
+
+use crate::*;
+
+const PIXEL_SIZE: f32 = 26.0;
+const GRID_TOP: f32 = 24.0;
+const CHART_TOP: f32 = 390.0;
+const CHART_HEIGHT: f32 = 70.0;
+const METHODS: [&str; 3] = ["Recursive DFS", "BFS", "Scanline"];
+const CLICK_MODES: [&str; 2] = ["Fill from the pixel", "Draw pixels"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Method {
+    Dfs,
+    Bfs,
+    Scanline,
+}
+impl Method {
+    /// What the depth of the method measures.
+    fn depth_name(&self) -> &'static str {
+        match self {
+            Method::Dfs => "Recursion depth",
+            Method::Bfs => "Queue length",
+            Method::Scanline => "Stack size",
+        }
+    }
+}
+
+/// The state of a fill at one step, which is drawn after the fill is done, when the largest depth is known.
+struct Record {
+    description: String,
+    filled: Vec<bool>,
+    active: Vec<usize>,
+    /// The pixels on the call stack, in the queue or on the stack of seeds.
+    frontier: Vec<usize>,
+    depth: usize,
+}
+
+/// A paint bucket on an image of black and white pixels, which fills the region of pixels with the same color that
+/// are connected to the start pixel through their sides. Recursive depth-first search calls itself for the four
+/// neighbors of every filled pixel, breadth-first search fills the pixels in the order of their distance from the
+/// start with a queue, and scanline fill fills whole horizontal spans at once with a stack of seeds above and below
+/// them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FloodFill {
+    width: usize,
+    height: usize,
+    /// Whether every pixel is black, row by row.
+    black: Vec<bool>,
+    start: usize,
+    method: Method,
+    drawing: bool,
+}
+impl FloodFill {
+    /// The rows of the image have "#" for black pixels and any other character for white ones. The start is given as
+    /// a column and a row.
+    pub fn new(image: &[&str], start: (usize, usize)) -> Self {
+        let width = image
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        let height = image.len();
+        let mut black = vec![false; width * height];
+        for (y, row) in image.iter().enumerate() {
+            for (x, character) in row.chars().enumerate() {
+                black[y * width + x] = character == '#';
+            }
+        }
+        Self {
+            width,
+            height,
+            black,
+            start: (start.1 * width + start.0).min(width * height - 1),
+            method: Method::Dfs,
+            drawing: false,
+        }
+    }
+    fn pixel_position(&self, pixel: usize) -> (f32, f32) {
+        let left = (CANVAS_WIDTH - self.width as f32 * PIXEL_SIZE) / 2.0;
+        (
+            left + ((pixel % self.width) as f32 + 0.5) * PIXEL_SIZE,
+            GRID_TOP + ((pixel / self.width) as f32 + 0.5) * PIXEL_SIZE,
+        )
+    }
+    fn pixel_name(&self, pixel: usize) -> String {
+        format!("({}, {})", pixel % self.width, pixel / self.width)
+    }
+    /// The pixels next to the sides of the pixel.
+    fn neighbors(&self, pixel: usize) -> Vec<usize> {
+        let (x, y) = (pixel % self.width, pixel / self.width);
+        let mut neighbors = vec![];
+        if x + 1 < self.width {
+            neighbors.push(pixel + 1);
+        }
+        if x > 0 {
+            neighbors.push(pixel - 1);
+        }
+        if y + 1 < self.height {
+            neighbors.push(pixel + self.width);
+        }
+        if y > 0 {
+            neighbors.push(pixel - self.width);
+        }
+        neighbors
+    }
+    /// Draws the pixels, and a chart of the depth of every step so far below them.
+    fn draw(&self, records: &[Record], index: Option<usize>) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let record = index.map(|index| &records[index]);
+        for pixel in 0..self.width * self.height {
+            let mut node = DiagramNode::rect(
+                format!("pixel-{}", pixel),
+                "",
+                self.pixel_position(pixel),
+                (PIXEL_SIZE - 2.0, PIXEL_SIZE - 2.0),
+            );
+            if self.black[pixel] {
+                node = node.with_fill(Fill::Black);
+            }
+            if let Some(record) = record {
+                node.state = if record.active.contains(&pixel) {
+                    Some(ElementState::Active)
+                } else if record.frontier.contains(&pixel) {
+                    Some(ElementState::Frontier)
+                } else if record.filled[pixel] {
+                    Some(ElementState::Selected)
+                } else {
+                    None
+                };
+            } else if pixel == self.start {
+                node.state = Some(ElementState::Active);
+            }
+            diagram.add_node(node);
+        }
+        if let Some(index) = index {
+            let max_depth = records.iter().map(|record| record.depth).max().unwrap_or(0);
+            let chart = Chart::new(
+                (120.0, CHART_TOP),
+                (CANVAS_WIDTH - 240.0, CHART_HEIGHT),
+                (records.len().max(2) as f32 - 1.0, max_depth.max(1) as f32),
+            );
+            chart.draw_axes(
+                &mut diagram,
+                "depth",
+                self.method.depth_name(),
+                ("Step", max_depth),
+            );
+            let depths = records[..=index]
+                .iter()
+                .enumerate()
+                .map(|(step, record)| (step as f32, record.depth as f32))
+                .collect::<Vec<(f32, f32)>>();
+            chart.draw_line(&mut diagram, &depths).state = Some(ElementState::Frontier);
+        }
+        diagram
+    }
+    fn fill(&self, steps: &mut DiagramSteps) {
+        let color = if self.black[self.start] {
+            "black"
+        } else {
+            "white"
+        };
+        let mut records = vec![Record {
+            description: format!(
+                "Fill the {} pixels that are connected to {} through their sides. {}",
+                color,
+                self.pixel_name(self.start),
+                match self.method {
+                    Method::Dfs => "Every filled pixel calls the fill for its four neighbors, which return at once if they are filled already or have another color",
+                    Method::Bfs => "The start is added to a queue, and every pixel that is taken from the queue is filled, and adds its unfilled neighbors of the same color to the queue",
+                    Method::Scanline => "The start is pushed on a stack of seeds, and every seed that is taken from the stack is extended to a whole horizontal span, which pushes a seed for every span of unfilled pixels above and below it",
+                }
+            ),
+            filled: vec![false; self.black.len()],
+            active: vec![self.start],
+            frontier: vec![],
+            depth: 0,
+        }];
+        let mut filled = vec![false; self.black.len()];
+        match self.method {
+            Method::Dfs => self.dfs(self.start, &mut filled, &mut vec![], &mut records),
+            Method::Bfs => self.bfs(&mut filled, &mut records),
+            Method::Scanline => self.scanline(&mut filled, &mut records),
+        }
+        let count = filled.iter().filter(|filled| **filled).count();
+        let max_depth = records.iter().map(|record| record.depth).max().unwrap_or(0);
+        records.push(Record {
+            description: format!(
+                "The region is filled with {}. The {} was at most {}",
+                plural(count, "pixel"),
+                self.method.depth_name().to_lowercase(),
+                max_depth
+            ),
+            filled,
+            active: vec![],
+            frontier: vec![],
+            depth: 0,
+        });
+        for index in 0..records.len() {
+            let count = records[index]
+                .filled
+                .iter()
+                .filter(|filled| **filled)
+                .count();
+            let step = steps.push(
+                records[index].description.clone(),
+                self.draw(&records, Some(index)),
+            );
+            step.lists = vec![
+                StepList::new("Filled pixels", vec![count.to_string()]),
+                StepList::new(
+                    self.method.depth_name(),
+                    vec![records[index].depth.to_string()],
+                ),
+            ];
+        }
+    }
+    /// Fills the pixel and calls itself for its neighbors. The calls that return at once aren't shown.
+    fn dfs(
+        &self,
+        pixel: usize,
+        filled: &mut Vec<bool>,
+        calls: &mut Vec<usize>,
+        records: &mut Vec<Record>,
+    ) {
+        filled[pixel] = true;
+        calls.push(pixel);
+        records.push(Record {
+            description: format!(
+                "Fill {} at a recursion depth of {}, and call the fill for its neighbors",
+                self.pixel_name(pixel),
+                calls.len()
+            ),
+            filled: filled.clone(),
+            active: vec![pixel],
+            frontier: calls.clone(),
+            depth: calls.len(),
+        });
+        for neighbor in self.neighbors(pixel) {
+            if !filled[neighbor] && self.black[neighbor] == self.black[self.start] {
+                self.dfs(neighbor, filled, calls, records);
+            }
+        }
+        calls.pop();
+    }
+    fn bfs(&self, filled: &mut [bool], records: &mut Vec<Record>) {
+        let mut queued = vec![false; self.black.len()];
+        let mut queue = VecDeque::from([self.start]);
+        queued[self.start] = true;
+        while let Some(pixel) = queue.pop_front() {
+            filled[pixel] = true;
+            let mut added = 0;
+            for neighbor in self.neighbors(pixel) {
+                if !queued[neighbor] && self.black[neighbor] == self.black[self.start] {
+                    queued[neighbor] = true;
+                    queue.push_back(neighbor);
+                    added += 1;
+                }
+            }
+            records.push(Record {
+                description: if added == 0 {
+                    format!(
+                        "Take {} from the queue and fill it. None of its neighbors of the same color are left to add to the queue",
+                        self.pixel_name(pixel)
+                    )
+                } else {
+                    format!(
+                        "Take {} from the queue and fill it, and add its {} of the same color to the queue",
+                        self.pixel_name(pixel),
+                        plural(added, "new neighbor")
+                    )
+                },
+                filled: filled.to_vec(),
+                active: vec![pixel],
+                frontier: queue.iter().copied().collect(),
+                depth: queue.len(),
+            });
+        }
+    }
+    fn scanline(&self, filled: &mut [bool], records: &mut Vec<Record>) {
+        let fits = |pixel: usize, filled: &[bool]| {
+            !filled[pixel] && self.black[pixel] == self.black[self.start]
+        };
+        let mut seeds = vec![self.start];
+        while let Some(seed) = seeds.pop() {
+            if !fits(seed, filled) {
+                // A seed can be filled by another span after it was pushed
+                continue;
+            }
+            let (y, row) = (seed / self.width, seed / self.width * self.width);
+            let (mut left, mut right) = (seed % self.width, seed % self.width);
+            while left > 0 && fits(row + left - 1, filled) {
+                left -= 1;
+            }
+            while right + 1 < self.width && fits(row + right + 1, filled) {
+                right += 1;
+            }
+            for x in left..=right {
+                filled[row + x] = true;
+            }
+            let mut pushed = 0;
+            for neighbor_row in [y.checked_sub(1), Some(y + 1).filter(|y| *y < self.height)]
+                .into_iter()
+                .flatten()
+            {
+                // One seed at the start of every run of pixels that fit
+                let mut in_run = false;
+                for x in left..=right {
+                    let pixel = neighbor_row * self.width + x;
+                    if fits(pixel, filled) {
+                        if !in_run {
+                            seeds.push(pixel);
+                            pushed += 1;
+                        }
+                        in_run = true;
+                    } else {
+                        in_run = false;
+                    }
+                }
+            }
+            records.push(Record {
+                description: format!(
+                    "Pop {} and extend it to the span from column {} to {} of row {}, and {}",
+                    self.pixel_name(seed),
+                    left,
+                    right,
+                    y,
+                    if pushed == 0 {
+                        "there are no unfilled spans above or below it to push seeds for"
+                            .to_string()
+                    } else {
+                        format!(
+                            "push {} for the spans above and below it",
+                            plural(pushed, "seed")
+                        )
+                    }
+                ),
+                filled: filled.to_vec(),
+                active: (left..=right).map(|x| row + x).collect(),
+                frontier: seeds.clone(),
+                depth: seeds.len(),
+            });
+        }
+    }
+}
+impl Visualizer for FloodFill {
+    fn diagram(&self) -> Diagram {
+        self.draw(&[], None)
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Fill",
+                vec![Input::Choice {
+                    label: "Method",
+                    choices: METHODS.iter().map(|method| method.to_string()).collect(),
+                }],
+            ),
+            Operation::new(
+                "Click mode",
+                vec![Input::Choice {
+                    label: "Clicks",
+                    choices: CLICK_MODES.iter().map(|mode| mode.to_string()).collect(),
+                }],
+            ),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Fill" => {
+                let method = values.first().map(|value| value.text()).unwrap_or_default();
+                self.method = match METHODS.iter().position(|name| *name == method) {
+                    Some(0) => Method::Dfs,
+                    Some(1) => Method::Bfs,
+                    Some(_) => Method::Scanline,
+                    None => return Err(format!("Unknown method \"{}\"", method)),
+                };
+                self.fill(&mut steps);
+            }
+            "Click mode" => {
+                let mode = values.first().map(|value| value.text()).unwrap_or_default();
+                if !CLICK_MODES.contains(&mode.as_str()) {
+                    return Err(format!("Unknown click mode \"{}\"", mode));
+                }
+                self.drawing = mode == CLICK_MODES[1];
+                steps.push(
+                    if self.drawing {
+                        "Clicks now turn pixels black or white".to_string()
+                    } else {
+                        "Clicks now fill the region of the clicked pixel with the chosen method"
+                            .to_string()
+                    },
+                    self.diagram(),
+                );
+            }
+            "Clear" => {
+                self.black.fill(false);
+                steps.push("Turn every pixel white".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+    fn clickable(&self) -> bool {
+        true
+    }
+    /// Depending on the click mode, clicks fill the region of the clicked pixel with the latest method, or flip the
+    /// color of the pixel.
+    fn click(&mut self, position: (f32, f32)) -> Option<DiagramSteps> {
+        let pixel = (0..self.width * self.height).find(|pixel| {
+            let (x, y) = self.pixel_position(*pixel);
+            (position.0 - x).abs() <= PIXEL_SIZE / 2.0 && (position.1 - y).abs() <= PIXEL_SIZE / 2.0
+        })?;
+        let mut steps = DiagramSteps::new();
+        if self.drawing {
+            self.black[pixel] = !self.black[pixel];
+        } else {
+            self.start = pixel;
+            self.fill(&mut steps);
+        }
+        Some(steps)
+    }
+}
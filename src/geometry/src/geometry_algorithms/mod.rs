@@ -0,0 +1,14 @@
+//! A collection of algorithms on points in the plane and on images of pixels.
+mod closest_pair;
+mod convex_hull;
+mod delaunay_triangulation;
+mod flood_fill;
+mod polygon;
+mod segment_intersection;
+
+pub use closest_pair::ClosestPair;
+pub use convex_hull::ConvexHull;
+pub use delaunay_triangulation::DelaunayTriangulation;
+pub use flood_fill::FloodFill;
+pub use polygon::Polygon;
+pub use segment_intersection::{segment, Segment, SegmentIntersection};
@@ -0,0 +1,508 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const QUERY_KEY: &str = "query";
+const CROSSING_RADIUS: f32 = 5.0;
+const CLICK_MODES: [&str; 2] = ["Draw the polygon", "Place the point"];
+const MAX_RANDOM_VERTICES: usize = 20;
+
+/// Twice the area of the polygon, which is positive if its vertices go counterclockwise on the screen.
+fn signed_area(vertices: &[Point]) -> f32 {
+    (0..vertices.len())
+        .map(|i| {
+            let (a, b) = (vertices[i], vertices[(i + 1) % vertices.len()]);
+            b.0 * a.1 - a.0 * b.1
+        })
+        .sum()
+}
+
+/// Whether the segments meet, including at their endpoints.
+fn segments_meet(p1: Point, p2: Point, p3: Point, p4: Point) -> bool {
+    let on_segment = |p: Point, q: Point, r: Point| {
+        r.0 >= p.0.min(q.0) && r.0 <= p.0.max(q.0) && r.1 >= p.1.min(q.1) && r.1 <= p.1.max(q.1)
+    };
+    let (d1, d2) = (cross(p3, p4, p1), cross(p3, p4, p2));
+    let (d3, d4) = (cross(p1, p2, p3), cross(p1, p2, p4));
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return true;
+    }
+    d1 == 0.0 && on_segment(p3, p4, p1)
+        || d2 == 0.0 && on_segment(p3, p4, p2)
+        || d3 == 0.0 && on_segment(p1, p2, p3)
+        || d4 == 0.0 && on_segment(p1, p2, p4)
+}
+
+/// The states of the polygon and the query point at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    points: BTreeMap<usize, ElementState>,
+    /// The states of the edges from a vertex to the next one.
+    edges: BTreeMap<usize, ElementState>,
+    polygon: Option<ElementState>,
+    query: Option<ElementState>,
+    ray: bool,
+    crossings: Vec<Point>,
+    triangles: Vec<([usize; 3], ElementState)>,
+    /// The edges between vertices that aren't next to each other, which split the polygon into triangles.
+    diagonals: Vec<(usize, usize)>,
+}
+
+/// A polygon that is drawn by clicking its vertices in order. Ray casting finds out whether a point is inside it by
+/// counting how many times a ray from the point crosses its boundary, and ear clipping splits it into triangles by
+/// cutting off one corner at a time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+    query: Option<Point>,
+    /// Whether clicks place the query point instead of the vertices.
+    placing_query: bool,
+}
+impl Polygon {
+    pub fn new(vertices: &[Point], query: Option<Point>) -> Self {
+        Self {
+            vertices: vertices.to_vec(),
+            query,
+            placing_query: false,
+        }
+    }
+    fn edge_name(&self, from: usize) -> String {
+        format!("{}–{}", from, (from + 1) % self.vertices.len())
+    }
+    fn point_in_polygon(&self, query: Point, steps: &mut DiagramSteps) {
+        let n = self.vertices.len();
+        let mut highlights = Highlights {
+            query: Some(ElementState::Active),
+            ray: true,
+            ..Default::default()
+        };
+        let push = |description: String, highlights: &Highlights, steps: &mut DiagramSteps| {
+            let step = steps.push(description, self.draw(highlights));
+            step.lists = vec![StepList::new(
+                "Crossings",
+                highlights
+                    .crossings
+                    .iter()
+                    .map(|(x, _)| format!("x = {:.0}", x))
+                    .collect(),
+            )];
+        };
+        push(
+            format!(
+                "Cast a ray from the point at ({:.0}, {:.0}) to the right, and count how many times it crosses the edges of the polygon",
+                query.0, query.1
+            ),
+            &highlights,
+            steps,
+        );
+        for i in 0..n {
+            let (a, b) = (self.vertices[i], self.vertices[(i + 1) % n]);
+            let edge = self.edge_name(i);
+            // An edge is counted if one of its ends is below the ray and the other isn't, so that a vertex on the
+            // ray is only counted once
+            let description = if (a.1 > query.1) == (b.1 > query.1) {
+                highlights.edges = BTreeMap::from([(i, ElementState::Rejected)]);
+                format!(
+                    "Both ends of the edge {} are on the same side of the ray, so it can't cross it",
+                    edge
+                )
+            } else {
+                let x = a.0 + (query.1 - a.1) * (b.0 - a.0) / (b.1 - a.1);
+                if x > query.0 {
+                    highlights.edges = BTreeMap::from([(i, ElementState::Selected)]);
+                    highlights.crossings.push((x, query.1));
+                    format!(
+                        "The edge {} crosses the ray at x = {:.0}, so the count is {}",
+                        edge,
+                        x,
+                        highlights.crossings.len()
+                    )
+                } else {
+                    highlights.edges = BTreeMap::from([(i, ElementState::Rejected)]);
+                    format!(
+                        "The edge {} crosses the line of the ray at x = {:.0}, which is behind the point, so it doesn't count",
+                        edge, x
+                    )
+                }
+            };
+            push(description, &highlights, steps);
+        }
+        let count = highlights.crossings.len();
+        let inside = count % 2 == 1;
+        highlights.edges.clear();
+        highlights.polygon = Some(if inside {
+            ElementState::Selected
+        } else {
+            ElementState::Rejected
+        });
+        highlights.query = highlights.polygon;
+        push(
+            format!(
+                "The ray crosses the boundary {}, which is {}, so the point is {} the polygon",
+                if count == 1 {
+                    "once".to_string()
+                } else {
+                    format!("{} times", count)
+                },
+                if inside { "odd" } else { "even" },
+                if inside { "inside" } else { "outside" }
+            ),
+            &highlights,
+            steps,
+        );
+    }
+    fn ear_clipping(&self, steps: &mut DiagramSteps) -> Result<(), String> {
+        let n = self.vertices.len();
+        for i in 0..n {
+            for j in i + 1..n {
+                // Edges that share a vertex always meet there
+                if j == i + 1 || (j + 1) % n == i {
+                    continue;
+                }
+                let (a, b) = (self.vertices[i], self.vertices[(i + 1) % n]);
+                let (c, d) = (self.vertices[j], self.vertices[(j + 1) % n]);
+                if segments_meet(a, b, c, d) {
+                    return Err(format!(
+                        "The edges {} and {} cross, so the polygon can't be triangulated",
+                        self.edge_name(i),
+                        self.edge_name(j)
+                    ));
+                }
+            }
+        }
+        let mut remaining = (0..n).collect::<Vec<usize>>();
+        let clockwise = signed_area(&self.vertices) < 0.0;
+        if clockwise {
+            remaining.reverse();
+        }
+        let mut highlights = Highlights::default();
+        let mut triangles: Vec<[usize; 3]> = vec![];
+        let push = |description: String,
+                    highlights: &Highlights,
+                    remaining: &[usize],
+                    triangles: &[[usize; 3]],
+                    steps: &mut DiagramSteps| {
+            let step = steps.push(description, self.draw(highlights));
+            step.lists = vec![
+                StepList::new(
+                    "Remaining vertices",
+                    remaining.iter().map(|vertex| vertex.to_string()).collect(),
+                ),
+                StepList::new(
+                    "Triangles",
+                    triangles
+                        .iter()
+                        .map(|[a, b, c]| format!("{}, {}, {}", a, b, c))
+                        .collect(),
+                ),
+            ];
+        };
+        push(
+            format!(
+                "Go through the vertices counterclockwise{}, and clip off an ear wherever there is one. An ear is a convex corner whose triangle has no other vertex inside it",
+                if clockwise {
+                    ", which is the reverse of the order they were drawn in"
+                } else {
+                    ""
+                }
+            ),
+            &highlights,
+            &remaining,
+            &triangles,
+            steps,
+        );
+        let mut index = 0;
+        let mut misses = 0;
+        while remaining.len() > 3 {
+            let m = remaining.len();
+            let (prev, vertex, next) = (
+                remaining[(index + m - 1) % m],
+                remaining[index % m],
+                remaining[(index + 1) % m],
+            );
+            let (a, b, c) = (
+                self.vertices[prev],
+                self.vertices[vertex],
+                self.vertices[next],
+            );
+            let turn = cross(a, b, c);
+            let inside = remaining.iter().copied().find(|other| {
+                ![prev, vertex, next].contains(other) && {
+                    let p = self.vertices[*other];
+                    cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+                }
+            });
+            highlights.points = BTreeMap::from([
+                (prev, ElementState::Frontier),
+                (vertex, ElementState::Active),
+                (next, ElementState::Frontier),
+            ]);
+            let ear = [prev, vertex, next];
+            let description = if turn < 0.0 || turn == 0.0 && misses < m {
+                highlights.triangles.push((ear, ElementState::Rejected));
+                format!(
+                    "The corner at vertex {} is {}, so it isn't an ear",
+                    vertex,
+                    if turn < 0.0 {
+                        "reflex"
+                    } else {
+                        "on a line with its neighbors"
+                    }
+                )
+            } else if let Some(other) = inside.filter(|_| turn > 0.0) {
+                highlights.points.insert(other, ElementState::Rejected);
+                highlights.triangles.push((ear, ElementState::Rejected));
+                format!(
+                    "The triangle {}, {}, {} contains vertex {}, so vertex {} isn't an ear",
+                    prev, vertex, next, other, vertex
+                )
+            } else {
+                remaining.remove(index % m);
+                misses = 0;
+                if turn == 0.0 {
+                    // Every vertex has been tried, so the rest are on a line, and this one can be removed
+                    highlights.diagonals.push((prev, next));
+                    highlights.points.insert(vertex, ElementState::Rejected);
+                    format!(
+                        "Vertex {} is on a line with its neighbors, so it is removed without a triangle",
+                        vertex
+                    )
+                } else {
+                    triangles.push(ear);
+                    highlights.diagonals.push((prev, next));
+                    highlights.triangles.push((ear, ElementState::Selected));
+                    highlights.points.insert(vertex, ElementState::Selected);
+                    format!(
+                        "Vertex {} is an ear, so clip off the triangle {}, {}, {}. The edge {}–{} becomes a part of the boundary",
+                        vertex, prev, vertex, next, prev, next
+                    )
+                }
+            };
+            push(description, &highlights, &remaining, &triangles, steps);
+            highlights
+                .triangles
+                .retain(|(_, state)| *state == ElementState::Selected);
+            if remaining.len() < m {
+                index %= remaining.len();
+            } else {
+                index += 1;
+                misses += 1;
+            }
+        }
+        let last = [remaining[0], remaining[1], remaining[2]];
+        triangles.push(last);
+        highlights.points.clear();
+        highlights.triangles.push((last, ElementState::Selected));
+        push(
+            format!(
+                "Only the triangle {}, {}, {} is left, so the polygon is split into {}",
+                last[0],
+                last[1],
+                last[2],
+                plural(triangles.len(), "triangle")
+            ),
+            &highlights,
+            &remaining,
+            &triangles,
+            steps,
+        );
+        Ok(())
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = draw_canvas(&self.vertices, &highlights.points);
+        let n = self.vertices.len();
+        if n >= 3 {
+            let mut figure = Figure::new(FigureKind::Polygon(self.vertices.clone()));
+            figure.state = highlights.polygon;
+            diagram.add_figure(figure);
+        }
+        for (triangle, state) in &highlights.triangles {
+            diagram.add_figure(
+                Figure::new(FigureKind::Polygon(
+                    triangle
+                        .iter()
+                        .map(|vertex| self.vertices[*vertex])
+                        .collect(),
+                ))
+                .with_state(*state),
+            );
+        }
+        let edge_count = match n {
+            0 | 1 => 0,
+            2 => 1,
+            _ => n,
+        };
+        for from in 0..edge_count {
+            let mut edge = DiagramEdge::new(point_key(from), point_key((from + 1) % n));
+            edge.state = highlights.edges.get(&from).copied();
+            diagram.add_edge(edge);
+        }
+        for (from, to) in &highlights.diagonals {
+            diagram.add_edge(
+                DiagramEdge::new(point_key(*from), point_key(*to))
+                    .dashed()
+                    .with_state(ElementState::Selected),
+            );
+        }
+        if let Some(query) = self.query {
+            if highlights.ray {
+                diagram.add_figure(
+                    Figure::new(FigureKind::Line(query, (CANVAS_WIDTH, query.1)))
+                        .dashed()
+                        .with_state(ElementState::Active),
+                );
+            }
+            for crossing in &highlights.crossings {
+                diagram.add_figure(
+                    Figure::new(FigureKind::Circle {
+                        center: *crossing,
+                        radius: CROSSING_RADIUS,
+                    })
+                    .with_state(ElementState::Selected),
+                );
+            }
+            let mut node = DiagramNode::new(
+                QUERY_KEY,
+                "",
+                query,
+                Shape::Rect {
+                    width: POINT_RADIUS * 2.0,
+                    height: POINT_RADIUS * 2.0,
+                },
+            )
+            .with_note("P");
+            node.state = highlights.query;
+            diagram.add_node(node);
+        }
+        diagram
+    }
+}
+impl Visualizer for Polygon {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new("Point in polygon", vec![]),
+            Operation::new("Ear clipping", vec![]),
+            Operation::new(
+                "Click mode",
+                vec![Input::Choice {
+                    label: "Clicks",
+                    choices: CLICK_MODES.iter().map(|mode| mode.to_string()).collect(),
+                }],
+            ),
+            Operation::new(
+                "Random polygon",
+                vec![Input::Number {
+                    label: "Number of vertices",
+                    default: 10,
+                    min: 3,
+                    max: MAX_RANDOM_VERTICES as i64,
+                }],
+            ),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Point in polygon" => {
+                check_points(&self.vertices, 3)?;
+                let Some(query) = self.query else {
+                    return Err(
+                        "Choose the click mode that places the point, and click the canvas"
+                            .to_string(),
+                    );
+                };
+                self.point_in_polygon(query, &mut steps);
+            }
+            "Ear clipping" => {
+                check_points(&self.vertices, 3)?;
+                self.ear_clipping(&mut steps)?;
+            }
+            "Click mode" => {
+                let mode = values.first().map(|value| value.text()).unwrap_or_default();
+                if !CLICK_MODES.contains(&mode.as_str()) {
+                    return Err(format!("Unknown click mode \"{}\"", mode));
+                }
+                self.placing_query = mode == CLICK_MODES[1];
+                steps.push(
+                    if self.placing_query {
+                        "Clicks now place the point and check whether it is inside the polygon"
+                            .to_string()
+                    } else {
+                        "Clicks now add vertices to the end of the polygon, or remove them"
+                            .to_string()
+                    },
+                    self.diagram(),
+                );
+            }
+            "Random polygon" => {
+                let count = values.first().map(|value| value.number()).unwrap_or(0);
+                if !(3..=MAX_RANDOM_VERTICES as i64).contains(&count) {
+                    return Err(format!(
+                        "The number of vertices must be between 3 and {}",
+                        MAX_RANDOM_VERTICES
+                    ));
+                }
+                self.vertices = random_polygon(count as usize);
+                steps.push(
+                    format!(
+                        "Draw a random polygon with {} vertices",
+                        self.vertices.len()
+                    ),
+                    self.diagram(),
+                );
+            }
+            "Clear" => {
+                self.vertices.clear();
+                steps.push("Remove all vertices".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+    fn clickable(&self) -> bool {
+        true
+    }
+    /// Depending on the click mode, clicks toggle the vertices of the polygon, or place the point and check it.
+    fn click(&mut self, position: (f32, f32)) -> Option<DiagramSteps> {
+        if !self.placing_query {
+            return toggle_point(&mut self.vertices, position).then(DiagramSteps::new);
+        }
+        let query = canvas_point(position);
+        self.query = Some(query);
+        let mut steps = DiagramSteps::new();
+        if self.vertices.len() >= 3 {
+            self.point_in_polygon(query, &mut steps);
+        }
+        Some(steps)
+    }
+}
+
+/// A polygon with its vertices around the center of the canvas in counterclockwise order, at random distances. Every
+/// vertex can be seen from the center, so the edges never cross.
+fn random_polygon(count: usize) -> Vec<Point> {
+    let mut rng = rand::thread_rng();
+    let mut angles = (0..count)
+        .map(|i| {
+            let sector = std::f32::consts::TAU / count as f32;
+            sector * (i as f32 + rng.gen_range(0.1..0.9))
+        })
+        .collect::<Vec<f32>>();
+    angles.sort_by(f32::total_cmp);
+    angles
+        .into_iter()
+        .map(|angle| {
+            let radius = rng.gen_range(0.35..1.0);
+            canvas_point((
+                CANVAS_WIDTH / 2.0 + angle.cos() * radius * (CANVAS_WIDTH / 2.0 - CANVAS_MARGIN),
+                CANVAS_HEIGHT / 2.0 - angle.sin() * radius * (CANVAS_HEIGHT / 2.0 - CANVAS_MARGIN),
+            ))
+        })
+        .collect()
+}
@@ -0,0 +1,581 @@
+use std::{cmp::Ordering, collections::BTreeSet};
+
+use diagram::*;
+
+use crate::*;
+
+const MAX_SEGMENTS: usize = 12;
+const ENDPOINT_RADIUS: f32 = 5.0;
+const INTERSECTION_RADIUS: f32 = 6.0;
+
+/// A segment from its left endpoint to its right endpoint.
+pub type Segment = (Point, Point);
+
+/// The segment between the points with its endpoints from left to right. A vertical segment is tilted by a unit, so
+/// that the sweep line never meets a whole segment at once.
+pub fn segment(a: Point, b: Point) -> Segment {
+    let (left, mut right) = if (a.0, a.1) <= (b.0, b.1) {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    if left.0 == right.0 {
+        right.0 += 1.0;
+    }
+    (left, right)
+}
+
+/// The point where the segments cross or touch, if they aren't on the same line.
+fn intersection((p1, p2): Segment, (p3, p4): Segment) -> Option<Point> {
+    let denominator = (p2.0 - p1.0) * (p4.1 - p3.1) - (p2.1 - p1.1) * (p4.0 - p3.0);
+    if denominator == 0.0 {
+        return None;
+    }
+    let t = ((p3.0 - p1.0) * (p4.1 - p3.1) - (p3.1 - p1.1) * (p4.0 - p3.0)) / denominator;
+    let u = ((p3.0 - p1.0) * (p2.1 - p1.1) - (p3.1 - p1.1) * (p2.0 - p1.0)) / denominator;
+    if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+        Some((p1.0 + t * (p2.0 - p1.0), p1.1 + t * (p2.1 - p1.1)))
+    } else {
+        None
+    }
+}
+
+/// Whether the segments cross each other instead of only touching at an endpoint.
+fn crosses((p1, p2): Segment, (p3, p4): Segment) -> bool {
+    cross(p1, p2, p3) * cross(p1, p2, p4) < 0.0 && cross(p3, p4, p1) * cross(p3, p4, p2) < 0.0
+}
+
+/// The y coordinate of the segment where the sweep line at x crosses it.
+fn y_at((p, q): Segment, x: f32) -> f32 {
+    p.1 + (q.1 - p.1) * (x - p.0) / (q.0 - p.0)
+}
+
+fn slope((p, q): Segment) -> f32 {
+    (q.1 - p.1) / (q.0 - p.0)
+}
+
+/// Orders points like the sweep line meets them: from left to right, and from top to bottom on the same x.
+fn compare_points(a: Point, b: Point) -> Ordering {
+    a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum EventKind {
+    Start,
+    Intersection(usize),
+    End,
+}
+impl EventKind {
+    /// Segments that start at a point are inserted before they are swapped, and removed after.
+    fn order(&self) -> usize {
+        match self {
+            EventKind::Start => 0,
+            EventKind::Intersection(_) => 1,
+            EventKind::End => 2,
+        }
+    }
+}
+
+/// A point where the sweep line stops, because a segment starts or ends there or two segments cross there.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Event {
+    point: Point,
+    segment: usize,
+    kind: EventKind,
+}
+impl Event {
+    fn compare(&self, other: &Event) -> Ordering {
+        compare_points(self.point, other.point).then(self.kind.order().cmp(&other.kind.order()))
+    }
+}
+
+/// The state of the sweep that is drawn and listed at every step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Sweep {
+    x: Option<f32>,
+    queue: Vec<Event>,
+    /// The segments that the sweep line crosses, from top to bottom.
+    status: Vec<usize>,
+    /// The pairs of segments whose crossing has been added to the queue.
+    scheduled: BTreeSet<(usize, usize)>,
+    found: Vec<(usize, usize, Point)>,
+}
+impl Sweep {
+    fn schedule(&mut self, event: Event) {
+        let index = self
+            .queue
+            .partition_point(|other| other.compare(&event) != Ordering::Greater);
+        self.queue.insert(index, event);
+    }
+}
+
+/// The intersections of a set of segments, found with the Bentley-Ottmann algorithm. A vertical line sweeps over the
+/// segments from left to right, and only segments that are next to each other on the line are checked for crossings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SegmentIntersection {
+    segments: Vec<Segment>,
+    /// The first endpoint of a segment that is being drawn by clicking.
+    pending: Option<Point>,
+}
+impl SegmentIntersection {
+    pub fn new(segments: &[Segment]) -> Self {
+        Self {
+            segments: segments.to_vec(),
+            pending: None,
+        }
+    }
+    fn push(
+        &self,
+        description: String,
+        sweep: &Sweep,
+        event: Option<Point>,
+        tested: Option<(usize, usize, ElementState)>,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(sweep, event, tested));
+        step.lists = vec![
+            StepList::new(
+                "Event queue",
+                sweep
+                    .queue
+                    .iter()
+                    .map(|event| self.event_text(event))
+                    .collect(),
+            ),
+            StepList::new(
+                "Status",
+                sweep
+                    .status
+                    .iter()
+                    .map(|segment| format!("Segment {}", segment))
+                    .collect(),
+            ),
+            StepList::new(
+                "Intersections",
+                sweep
+                    .found
+                    .iter()
+                    .map(|(a, b, point)| {
+                        format!(
+                            "Segments {} and {} at ({:.0}, {:.0})",
+                            a, b, point.0, point.1
+                        )
+                    })
+                    .collect(),
+            ),
+        ];
+    }
+    fn event_text(&self, event: &Event) -> String {
+        match event.kind {
+            EventKind::Start => format!("Start of {} at x = {:.0}", event.segment, event.point.0),
+            EventKind::Intersection(other) => format!(
+                "Crossing of {} and {} at x = {:.0}",
+                event.segment, other, event.point.0
+            ),
+            EventKind::End => format!("End of {} at x = {:.0}", event.segment, event.point.0),
+        }
+    }
+    fn find_intersections(&self, steps: &mut DiagramSteps) {
+        let mut sweep = Sweep::default();
+        for (index, (start, end)) in self.segments.iter().enumerate() {
+            sweep.schedule(Event {
+                point: *start,
+                segment: index,
+                kind: EventKind::Start,
+            });
+            sweep.schedule(Event {
+                point: *end,
+                segment: index,
+                kind: EventKind::End,
+            });
+        }
+        self.push(
+            format!(
+                "Add the {} endpoints of the segments to the event queue, sorted by x. The sweep line will stop at each of them from left to right",
+                sweep.queue.len()
+            ),
+            &sweep,
+            None,
+            None,
+            steps,
+        );
+        while !sweep.queue.is_empty() {
+            let event = sweep.queue.remove(0);
+            sweep.x = Some(event.point.0);
+            let segment = event.segment;
+            match event.kind {
+                EventKind::Start => {
+                    let (x, y) = event.point;
+                    let index = sweep.status.partition_point(|other| {
+                        let other_y = y_at(self.segments[*other], x);
+                        other_y < y
+                            || other_y == y
+                                && slope(self.segments[*other]) < slope(self.segments[segment])
+                    });
+                    sweep.status.insert(index, segment);
+                    let description = match (
+                        index.checked_sub(1).map(|above| sweep.status[above]),
+                        sweep.status.get(index + 1),
+                    ) {
+                        (None, None) => format!(
+                            "Segment {} starts, so insert it into the empty status",
+                            segment
+                        ),
+                        (Some(above), None) => format!(
+                            "Segment {} starts, so insert it into the status below {}",
+                            segment, above
+                        ),
+                        (None, Some(below)) => format!(
+                            "Segment {} starts, so insert it into the status above {}",
+                            segment, below
+                        ),
+                        (Some(above), Some(below)) => format!(
+                            "Segment {} starts, so insert it into the status between {} and {}",
+                            segment, above, below
+                        ),
+                    };
+                    self.push(description, &sweep, Some(event.point), None, steps);
+                    self.check_neighbors(index, &event, &mut sweep, steps);
+                }
+                EventKind::End => {
+                    let Some(index) = sweep.status.iter().position(|other| *other == segment)
+                    else {
+                        continue;
+                    };
+                    sweep.status.remove(index);
+                    self.push(
+                        format!("Segment {} ends, so remove it from the status", segment),
+                        &sweep,
+                        Some(event.point),
+                        None,
+                        steps,
+                    );
+                    if index > 0 && index < sweep.status.len() {
+                        self.check(
+                            sweep.status[index - 1],
+                            sweep.status[index],
+                            &event,
+                            &mut sweep,
+                            steps,
+                        );
+                    }
+                }
+                EventKind::Intersection(other) => {
+                    sweep.found.push((segment, other, event.point));
+                    if !crosses(self.segments[segment], self.segments[other]) {
+                        self.push(
+                            format!(
+                                "Segments {} and {} touch at the end of one of them, so report it. They don't change order there",
+                                segment, other
+                            ),
+                            &sweep,
+                            Some(event.point),
+                            Some((segment, other, ElementState::Selected)),
+                            steps,
+                        );
+                        continue;
+                    }
+                    let positions = (
+                        sweep.status.iter().position(|s| *s == segment),
+                        sweep.status.iter().position(|s| *s == other),
+                    );
+                    let (Some(a), Some(b)) = positions else {
+                        self.push(
+                            format!(
+                                "Segments {} and {} meet at the end of one of them, so report it",
+                                segment, other
+                            ),
+                            &sweep,
+                            Some(event.point),
+                            Some((segment, other, ElementState::Selected)),
+                            steps,
+                        );
+                        continue;
+                    };
+                    sweep.status.swap(a, b);
+                    self.push(
+                        format!(
+                            "The sweep line reaches the crossing of {} and {}, so report it. The segments change order there, so swap them in the status",
+                            segment, other
+                        ),
+                        &sweep,
+                        Some(event.point),
+                        Some((segment, other, ElementState::Selected)),
+                        steps,
+                    );
+                    let (upper, lower) = (a.min(b), a.max(b));
+                    if upper > 0 {
+                        self.check(
+                            sweep.status[upper - 1],
+                            sweep.status[upper],
+                            &event,
+                            &mut sweep,
+                            steps,
+                        );
+                    }
+                    if lower + 1 < sweep.status.len() {
+                        self.check(
+                            sweep.status[lower],
+                            sweep.status[lower + 1],
+                            &event,
+                            &mut sweep,
+                            steps,
+                        );
+                    }
+                }
+            }
+        }
+        sweep.x = None;
+        self.push(
+            format!(
+                "The event queue is empty, so the sweep is done. The {} have {}",
+                plural(self.segments.len(), "segment"),
+                plural(sweep.found.len(), "intersection")
+            ),
+            &sweep,
+            None,
+            None,
+            steps,
+        );
+    }
+    /// Checks the segment at the index with the segments above and below it on the sweep line.
+    fn check_neighbors(
+        &self,
+        index: usize,
+        event: &Event,
+        sweep: &mut Sweep,
+        steps: &mut DiagramSteps,
+    ) {
+        let segment = sweep.status[index];
+        if index > 0 {
+            self.check(sweep.status[index - 1], segment, event, sweep, steps);
+        }
+        if index + 1 < sweep.status.len() {
+            self.check(segment, sweep.status[index + 1], event, sweep, steps);
+        }
+    }
+    /// Adds the crossing of the neighboring segments to the event queue if they meet. They can only have met behind the
+    /// sweep line if they were neighbors before, so the crossing has already been added then.
+    fn check(
+        &self,
+        a: usize,
+        b: usize,
+        event: &Event,
+        sweep: &mut Sweep,
+        steps: &mut DiagramSteps,
+    ) {
+        let pair = (a.min(b), a.max(b));
+        let crossing = intersection(self.segments[a], self.segments[b]);
+        let (description, state) = match crossing {
+            Some(_) if sweep.scheduled.contains(&pair) => (
+                format!(
+                    "Segments {} and {} are now neighbors, but their crossing is already in the event queue",
+                    a, b
+                ),
+                ElementState::Frontier,
+            ),
+            Some(point) => {
+                sweep.scheduled.insert(pair);
+                sweep.schedule(Event {
+                    point,
+                    segment: pair.0,
+                    kind: EventKind::Intersection(pair.1),
+                });
+                (
+                    format!(
+                        "Segments {} and {} are now neighbors, and they meet at ({:.0}, {:.0}), so add the crossing to the event queue",
+                        a, b, point.0, point.1
+                    ),
+                    ElementState::Frontier,
+                )
+            }
+            None => (
+                format!(
+                    "Segments {} and {} are now neighbors, but they don't meet",
+                    a, b
+                ),
+                ElementState::Rejected,
+            ),
+        };
+        self.push(
+            description,
+            sweep,
+            Some(event.point),
+            Some((a, b, state)),
+            steps,
+        );
+    }
+    fn draw(
+        &self,
+        sweep: &Sweep,
+        event: Option<Point>,
+        tested: Option<(usize, usize, ElementState)>,
+    ) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        if self.segments.is_empty() && self.pending.is_none() {
+            diagram.add_node(DiagramNode::text(
+                "hint",
+                "Click the canvas twice to draw a segment",
+                (CANVAS_WIDTH / 2.0, CANVAS_HEIGHT / 2.0),
+            ));
+        }
+        if let Some(x) = sweep.x {
+            diagram.add_figure(
+                Figure::new(FigureKind::Line((x, 0.0), (x, CANVAS_HEIGHT)))
+                    .dashed()
+                    .with_state(ElementState::Active),
+            );
+        }
+        for (index, (start, end)) in self.segments.iter().enumerate() {
+            let state = match tested {
+                Some((a, b, state)) if a == index || b == index => Some(state),
+                _ if sweep.status.contains(&index) => Some(ElementState::Active),
+                _ => None,
+            };
+            let mut from = DiagramNode::new(
+                format!("segment-{}-start", index),
+                "",
+                *start,
+                Shape::Circle {
+                    radius: ENDPOINT_RADIUS,
+                },
+            )
+            .with_note(index);
+            let mut to = DiagramNode::new(
+                format!("segment-{}-end", index),
+                "",
+                *end,
+                Shape::Circle {
+                    radius: ENDPOINT_RADIUS,
+                },
+            );
+            from.state = state;
+            to.state = state;
+            let mut edge = DiagramEdge::new(&from.key, &to.key);
+            edge.state = state;
+            diagram.add_node(from);
+            diagram.add_node(to);
+            diagram.add_edge(edge);
+        }
+        for (index, (_, _, point)) in sweep.found.iter().enumerate() {
+            diagram.add_node(
+                DiagramNode::new(
+                    format!("intersection-{}", index),
+                    "",
+                    *point,
+                    Shape::Circle {
+                        radius: INTERSECTION_RADIUS,
+                    },
+                )
+                .with_state(ElementState::Selected),
+            );
+        }
+        if let Some(point) = event {
+            diagram.add_figure(
+                Figure::new(FigureKind::Circle {
+                    center: point,
+                    radius: INTERSECTION_RADIUS * 2.0,
+                })
+                .with_state(ElementState::Frontier),
+            );
+        }
+        if let Some(point) = self.pending {
+            diagram.add_node(
+                DiagramNode::new(
+                    "pending",
+                    "",
+                    point,
+                    Shape::Circle {
+                        radius: ENDPOINT_RADIUS,
+                    },
+                )
+                .with_note("Click again to finish the segment")
+                .with_state(ElementState::Frontier),
+            );
+        }
+        diagram
+    }
+}
+impl Visualizer for SegmentIntersection {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Sweep::default(), None, None)
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new("Find intersections", vec![]),
+            Operation::new(
+                "Random segments",
+                vec![Input::Number {
+                    label: "Number of segments",
+                    default: 6,
+                    min: 1,
+                    max: MAX_SEGMENTS as i64,
+                }],
+            ),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Find intersections" => {
+                if self.segments.len() < 2 {
+                    return Err("Draw at least 2 segments by clicking the canvas".to_string());
+                }
+                self.pending = None;
+                self.find_intersections(&mut steps);
+            }
+            "Random segments" => {
+                let count = values.first().map(|value| value.number()).unwrap_or(0);
+                if !(1..=MAX_SEGMENTS as i64).contains(&count) {
+                    return Err(format!(
+                        "The number of segments must be between 1 and {}",
+                        MAX_SEGMENTS
+                    ));
+                }
+                self.segments = random_points(count as usize * 2)
+                    .chunks_exact(2)
+                    .map(|points| segment(points[0], points[1]))
+                    .collect();
+                self.pending = None;
+                steps.push(
+                    format!("Draw {} at random", plural(self.segments.len(), "segment")),
+                    self.diagram(),
+                );
+            }
+            "Clear" => {
+                self.segments.clear();
+                self.pending = None;
+                steps.push("Remove all segments".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+    fn clickable(&self) -> bool {
+        true
+    }
+    /// Clicking an endpoint removes its segment, and other clicks draw a segment between every two of them.
+    fn click(&mut self, position: (f32, f32)) -> Option<DiagramSteps> {
+        let endpoints = self
+            .segments
+            .iter()
+            .flat_map(|(start, end)| [*start, *end])
+            .chain(self.pending)
+            .collect::<Vec<Point>>();
+        match clicked_point(&endpoints, position) {
+            Some(index) if index / 2 < self.segments.len() => {
+                self.segments.remove(index / 2);
+            }
+            Some(_) => self.pending = None,
+            None => match self.pending.take() {
+                Some(start) => self.segments.push(segment(start, canvas_point(position))),
+                None if self.segments.len() < MAX_SEGMENTS => {
+                    self.pending = Some(canvas_point(position))
+                }
+                None => return None,
+            },
+        }
+        Some(DiagramSteps::new())
+    }
+}
@@ -0,0 +1,161 @@
+//! This crate contains my implementations of computational geometry algorithms, like convex hulls.
+//! The algorithms work on points that are placed on a canvas or on pixels, and each records a diagram of them at every step.
+pub mod geometry_algorithms;
+
+use std::collections::BTreeMap;
+
+use diagram::*;
+use rand::Rng;
+
+pub const CANVAS_WIDTH: f32 = 800.0;
+pub const CANVAS_HEIGHT: f32 = 500.0;
+/// How far points are kept from the edges of the canvas, so that they and their notes are fully shown.
+pub const CANVAS_MARGIN: f32 = 24.0;
+pub const POINT_RADIUS: f32 = 7.0;
+pub const MAX_POINTS: usize = 40;
+/// How close a click has to be to a point to remove it instead of placing a new one.
+const CLICK_DISTANCE: f32 = 14.0;
+/// How close random points can be to each other, so that they don't overlap.
+const MIN_RANDOM_DISTANCE: f32 = 30.0;
+
+/// A point on the canvas. The y axis points down, like on the screen.
+pub type Point = (f32, f32);
+
+/// The cross product of the vectors from `o` to `a` and from `o` to `b` with the y axis flipped to point up. It is
+/// positive if the points make a left turn as they are seen on the screen, negative for a right turn and zero if they
+/// are on a line.
+pub fn cross(o: Point, a: Point, b: Point) -> f32 {
+    (a.0 - o.0) * (o.1 - b.1) - (o.1 - a.1) * (b.0 - o.0)
+}
+
+pub fn distance(a: Point, b: Point) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+/// The key of the node of the point at the index, which edges between points are drawn with.
+pub fn point_key(index: usize) -> String {
+    format!("point-{}", index)
+}
+
+/// Draws the points as small circles with their indices below them on an empty canvas.
+pub fn draw_canvas(points: &[Point], states: &BTreeMap<usize, ElementState>) -> Diagram {
+    let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+    if points.is_empty() {
+        diagram.add_node(DiagramNode::text(
+            "hint",
+            "Click the canvas to place points",
+            (CANVAS_WIDTH / 2.0, CANVAS_HEIGHT / 2.0),
+        ));
+    }
+    for (index, point) in points.iter().enumerate() {
+        let mut node = DiagramNode::new(
+            point_key(index),
+            "",
+            *point,
+            Shape::Circle {
+                radius: POINT_RADIUS,
+            },
+        )
+        .with_note(index);
+        node.state = states.get(&index).copied();
+        diagram.add_node(node);
+    }
+    diagram
+}
+
+/// The index of the point that a click at the position hit, if any.
+pub fn clicked_point(points: &[Point], position: Point) -> Option<usize> {
+    points
+        .iter()
+        .position(|point| distance(*point, position) <= CLICK_DISTANCE)
+}
+
+/// The position of a click as a point that is kept inside the margins. It is rounded to whole units, so that the
+/// coordinates are readable in the steps.
+pub fn canvas_point(position: Point) -> Point {
+    (
+        position
+            .0
+            .clamp(CANVAS_MARGIN, CANVAS_WIDTH - CANVAS_MARGIN)
+            .round(),
+        position
+            .1
+            .clamp(CANVAS_MARGIN, CANVAS_HEIGHT - CANVAS_MARGIN)
+            .round(),
+    )
+}
+
+/// Removes the point that was clicked, or places a new point if no point was clicked and there is room for it.
+/// Returns true if the points changed.
+pub fn toggle_point(points: &mut Vec<Point>, position: Point) -> bool {
+    if let Some(index) = clicked_point(points, position) {
+        points.remove(index);
+        return true;
+    }
+    if points.len() >= MAX_POINTS {
+        return false;
+    }
+    points.push(canvas_point(position));
+    true
+}
+
+/// Places points at random positions that aren't too close to each other. There can be fewer of them than asked for
+/// if there is no room for more.
+pub fn random_points(count: usize) -> Vec<Point> {
+    let mut rng = rand::thread_rng();
+    let mut points: Vec<Point> = vec![];
+    for _ in 0..count * 20 {
+        if points.len() == count {
+            break;
+        }
+        let point = (
+            rng.gen_range(CANVAS_MARGIN..=CANVAS_WIDTH - CANVAS_MARGIN)
+                .round(),
+            rng.gen_range(CANVAS_MARGIN..=CANVAS_HEIGHT - CANVAS_MARGIN)
+                .round(),
+        );
+        if points
+            .iter()
+            .all(|other| distance(*other, point) >= MIN_RANDOM_DISTANCE)
+        {
+            points.push(point);
+        }
+    }
+    points
+}
+
+/// The operation that replaces the points with random ones.
+pub fn random_points_operation(count: usize) -> Operation {
+    Operation::new(
+        "Random points",
+        vec![Input::Number {
+            label: "Number of points",
+            default: count as i64,
+            min: 1,
+            max: MAX_POINTS as i64,
+        }],
+    )
+}
+
+/// Reads the number of points of a [`random_points_operation`].
+pub fn parse_point_count(values: &[Value]) -> Result<usize, String> {
+    let count = values.first().map(|value| value.number()).unwrap_or(0);
+    if !(1..=MAX_POINTS as i64).contains(&count) {
+        return Err(format!(
+            "The number of points must be between 1 and {}",
+            MAX_POINTS
+        ));
+    }
+    Ok(count as usize)
+}
+
+/// Checks that there are enough points for an algorithm to be run.
+pub fn check_points(points: &[Point], min: usize) -> Result<(), String> {
+    if points.len() < min {
+        return Err(format!(
+            "Place at least {} points by clicking the canvas",
+            min
+        ));
+    }
+    Ok(())
+}
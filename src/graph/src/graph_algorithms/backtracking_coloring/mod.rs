@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use super::greedy_coloring::{by_degree, order_list, show_colors};
+use crate::*;
+
+/// The largest number of steps that are shown. Backtracking can take exponential time,
+/// so the search is stopped if it takes longer than this.
+const MAX_STEPS: usize = 5000;
+
+/// Tries to color the nodes with the chosen number of colors so that no neighbors have the same color.
+/// The nodes are colored one at a time with the first color that doesn't conflict with their neighbors,
+/// and when a node has no such color left, the search goes back to the previous node and tries its next color.
+pub fn backtracking_coloring(graph: &Graph, options: &AlgorithmOptions) -> GraphSteps {
+    let AlgorithmOptions { color_count, .. } = *options;
+    let mut steps = GraphSteps::new();
+    let order = by_degree(graph);
+    let mut colors = BTreeMap::<usize, usize>::new();
+    // The next color to try for each position in the order
+    let mut next_colors = vec![0; order.len()];
+    let mut position = 0;
+
+    steps
+        .init_step(format!(
+            "Try to color the nodes with {} {}, starting from the nodes with the highest degrees",
+            color_count,
+            if color_count == 1 { "color" } else { "colors" }
+        ))
+        .lists = vec![order_list(&order, &colors)];
+
+    while position < order.len() {
+        if steps.len() >= MAX_STEPS {
+            steps.init_step(format!(
+                "The search was stopped after {} steps, since backtracking can take exponential time",
+                MAX_STEPS
+            ));
+            return steps;
+        }
+
+        let node = order[position];
+        let color = next_colors[position];
+
+        if color == color_count {
+            next_colors[position] = 0;
+            if position == 0 {
+                let step = steps.init_step(format!(
+                    "Every color has been tried for node {}, so the graph can't be colored with {} {}, and its chromatic number is greater than {}",
+                    node,
+                    color_count,
+                    if color_count == 1 { "color" } else { "colors" },
+                    color_count
+                ));
+                step.node_states = BTreeMap::from([(node, ElementState::Conflict)]);
+                return steps;
+            }
+
+            position -= 1;
+            let previous = order[position];
+            colors.remove(&previous);
+
+            let step = steps.init_step(format!(
+                "Every color conflicts for node {}, so backtrack to node {} and try its next color",
+                node, previous
+            ));
+            show_colors(&colors, step);
+            step.edge_states.clear();
+            step.node_states.insert(node, ElementState::Rejected);
+            step.node_states.insert(previous, ElementState::Active);
+            step.lists = vec![order_list(&order, &colors)];
+            continue;
+        }
+
+        next_colors[position] = color + 1;
+        let conflict = graph
+            .neighbors(node)
+            .into_iter()
+            .find(|(neighbor, _)| colors.get(neighbor) == Some(&color));
+
+        if let Some((neighbor, _)) = conflict {
+            let step = steps.init_step(format!(
+                "Color {} for node {} conflicts with its neighbor {}",
+                color + 1,
+                node,
+                neighbor
+            ));
+            show_colors(&colors, step);
+            step.node_states.clear();
+            step.edge_states.clear();
+            step.node_states.insert(node, ElementState::Active);
+            step.edge_states
+                .insert(graph.edge_key(node, neighbor), ElementState::Conflict);
+            continue;
+        }
+
+        colors.insert(node, color);
+        position += 1;
+
+        let step = steps.init_step(format!(
+            "Give node {} color {}, which none of its neighbors have",
+            node,
+            color + 1
+        ));
+        show_colors(&colors, step);
+        step.node_states.clear();
+        step.edge_states.clear();
+        step.node_states.insert(node, ElementState::Active);
+        step.lists = vec![order_list(&order, &colors)];
+    }
+
+    let step = steps.init_step(format!(
+        "Every node has a color, so the graph can be colored with {} {}",
+        color_count,
+        if color_count == 1 { "color" } else { "colors" }
+    ));
+    step.node_states.clear();
+    step.edge_states.clear();
+
+    steps
+}
@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+
+use crate::*;
+
+/// Finds the shortest paths from the start node to every other node by relaxing every edge in passes.
+/// A shortest path has at most V − 1 edges, so the distances are final after V − 1 passes,
+/// unless an edge can still be relaxed, which means that the graph has a negative cycle.
+pub fn bellman_ford(graph: &Graph, options: &AlgorithmOptions) -> GraphSteps {
+    let AlgorithmOptions { start, .. } = *options;
+    let mut steps = GraphSteps::new();
+    if !graph.nodes.contains(&start) {
+        return steps;
+    }
+
+    let mut distances = BTreeMap::from([(start, 0.0)]);
+    // The node before each reached node on the cheapest path found to it so far
+    let mut came_from = BTreeMap::<usize, usize>::new();
+    let pass_count = graph.nodes.len() - 1;
+
+    let step = steps.init_step(format!(
+        "Start with a distance of 0 at node {} and an infinite distance at every other node",
+        start
+    ));
+    show(graph, &distances, &came_from, step);
+
+    for pass in 1..=pass_count {
+        let mut updated = Vec::<usize>::new();
+
+        let step = steps.init_step(format!(
+            "Pass {} of at most {}: relax every edge once",
+            pass, pass_count
+        ));
+        show(graph, &distances, &came_from, step);
+        step.lists = vec![updated_list(pass, &updated)];
+
+        for (&(from, to), &weight) in graph.edges.iter() {
+            let Some(&from_distance) = distances.get(&from) else {
+                let step = steps.init_step(format!(
+                    "Skip edge {} because node {} hasn't been reached yet",
+                    edge_name(graph, (from, to)),
+                    from
+                ));
+                show(graph, &distances, &came_from, step);
+                step.edge_states.insert((from, to), ElementState::Active);
+                continue;
+            };
+            let new_distance = from_distance + weight;
+
+            if distances
+                .get(&to)
+                .is_some_and(|distance| new_distance >= *distance)
+            {
+                let step = steps.init_step(format!(
+                    "Edge {} gives node {} a distance of {} = {}, which isn't shorter than its distance {}",
+                    edge_name(graph, (from, to)),
+                    to,
+                    sum_name(from_distance, weight),
+                    format_weight(new_distance),
+                    format_weight(distances[&to])
+                ));
+                show(graph, &distances, &came_from, step);
+                step.edge_states.insert((from, to), ElementState::Active);
+                continue;
+            }
+
+            distances.insert(to, new_distance);
+            came_from.insert(to, from);
+            if !updated.contains(&to) {
+                updated.push(to);
+            }
+
+            let step = steps.init_step(format!(
+                "Relax edge {}: {} = {} is shorter than the distance of node {}, so it becomes its new distance",
+                edge_name(graph, (from, to)),
+                sum_name(from_distance, weight),
+                format_weight(new_distance),
+                to
+            ));
+            show(graph, &distances, &came_from, step);
+            step.node_states.insert(to, ElementState::Active);
+            step.lists = vec![updated_list(pass, &updated)];
+        }
+
+        if updated.is_empty() {
+            let step = steps.init_step(format!(
+                "No distance changed during pass {}, so the distances are final",
+                pass
+            ));
+            show(graph, &distances, &came_from, step);
+            step.lists.clear();
+            return steps;
+        }
+    }
+
+    // After V − 1 passes, an edge can only be relaxed if a cycle with a negative total weight leads to it
+    let relaxable = graph.edges.iter().find(|((from, to), weight)| {
+        distances.get(from).is_some_and(|from_distance| {
+            distances
+                .get(to)
+                .is_none_or(|distance| from_distance + **weight < *distance)
+        })
+    });
+
+    if let Some((&(from, to), _)) = relaxable {
+        came_from.insert(to, from);
+        let cycle = negative_cycle(&came_from, to);
+        let cycle_weight = cycle
+            .windows(2)
+            .map(|pair| graph.edges[&(pair[0], pair[1])])
+            .sum::<f32>();
+
+        let step = steps.init_step(format!(
+            "Edge {} can still be relaxed after {} passes, so the graph has a negative cycle {} with a total weight of {}. The distances of the nodes that it reaches have no lower bound",
+            edge_name(graph, (from, to)),
+            pass_count,
+            path_name(graph, &cycle),
+            format_weight(cycle_weight)
+        ));
+        show(graph, &distances, &came_from, step);
+        for pair in cycle.windows(2) {
+            step.node_states.insert(pair[0], ElementState::Conflict);
+            step.edge_states
+                .insert((pair[0], pair[1]), ElementState::Conflict);
+        }
+        step.lists.clear();
+    } else {
+        let step = steps.init_step(format!(
+            "Every edge has been relaxed {} times and none of them can be relaxed anymore, so the distances are final",
+            pass_count
+        ));
+        show(graph, &distances, &came_from, step);
+        step.lists.clear();
+    }
+
+    steps
+}
+
+/// Follows the predecessors backwards from a node whose distance can still be decreased until they go around a cycle,
+/// and returns the cycle in the direction of the edges, starting and ending at the same node.
+fn negative_cycle(came_from: &BTreeMap<usize, usize>, node: usize) -> Vec<usize> {
+    // The node can be on a path that leads from the cycle, so go back far enough to be on the cycle
+    let mut on_cycle = node;
+    for _ in 0..came_from.len() {
+        on_cycle = came_from[&on_cycle];
+    }
+
+    let mut cycle = vec![on_cycle];
+    let mut curr = came_from[&on_cycle];
+    while curr != on_cycle {
+        cycle.push(curr);
+        curr = came_from[&curr];
+    }
+    cycle.push(on_cycle);
+    cycle.reverse();
+    cycle
+}
+
+/// Labels the nodes with their distances from the start node and highlights the reached nodes
+/// and the edges of the cheapest paths found so far.
+fn show(
+    graph: &Graph,
+    distances: &BTreeMap<usize, f32>,
+    came_from: &BTreeMap<usize, usize>,
+    step: &mut GraphStep,
+) {
+    step.node_states = distances
+        .keys()
+        .map(|node| (*node, ElementState::Selected))
+        .collect();
+    step.edge_states = came_from
+        .iter()
+        .map(|(to, from)| ((*from, *to), ElementState::Selected))
+        .collect();
+    step.node_labels = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            (
+                *node,
+                distances
+                    .get(node)
+                    .map_or("∞".to_string(), |distance| format_weight(*distance)),
+            )
+        })
+        .collect();
+}
+
+fn updated_list(pass: usize, updated: &[usize]) -> StepList {
+    StepList::new(
+        &format!("Updated in pass {}", pass),
+        updated.iter().map(|node| node.to_string()).collect(),
+    )
+}
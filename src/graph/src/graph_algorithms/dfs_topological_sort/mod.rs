@@ -0,0 +1,123 @@
+use std::collections::BTreeSet;
+
+use crate::*;
+
+/// Sorts the nodes of a directed acyclic graph so that every edge goes from an earlier node to a later one.
+/// Depth-first searches are run from unvisited nodes, and each node is added to the front of the order
+/// when all of the nodes after it are finished. Reaching a node that is still on the stack means that the graph has a cycle.
+pub fn dfs_topological_sort(graph: &Graph, _options: &AlgorithmOptions) -> GraphSteps {
+    let mut steps = GraphSteps::new();
+    let mut visited = BTreeSet::new();
+    // The nodes of the current search along with the indices of the next neighbors to look at
+    let mut stack = Vec::<(usize, usize)>::new();
+    let mut order = Vec::<usize>::new();
+
+    steps
+        .init_step("Start with every node unvisited".to_string())
+        .lists = lists(&stack, &order);
+
+    for root in graph.nodes.iter() {
+        if visited.contains(root) {
+            continue;
+        }
+        visited.insert(*root);
+        stack.push((*root, 0));
+
+        let step = steps.init_step(format!("Start a depth-first search from node {}", root));
+        step.node_states.insert(*root, ElementState::Frontier);
+        step.lists = lists(&stack, &order);
+
+        while let Some((node, neighbor_i)) = stack.last().copied() {
+            let neighbors = graph.neighbors(node);
+
+            let Some((neighbor, _)) = neighbors.get(neighbor_i).copied() else {
+                // All of the nodes after this one are finished, so it can be put in front of them
+                stack.pop();
+                order.insert(0, node);
+
+                let step = steps.init_step(format!(
+                    "Every node after {} is finished, so it is popped from the stack and added to the front of the order",
+                    node
+                ));
+                step.node_states.insert(node, ElementState::Selected);
+                show_order(step, &order);
+                step.lists = lists(&stack, &order);
+                continue;
+            };
+            stack.last_mut().unwrap().1 += 1;
+
+            let edge = (node, neighbor);
+            if stack.iter().any(|(stack_node, _)| *stack_node == neighbor) {
+                let cycle_start = stack
+                    .iter()
+                    .position(|(stack_node, _)| *stack_node == neighbor)
+                    .unwrap();
+                let cycle = stack[cycle_start..]
+                    .iter()
+                    .map(|(stack_node, _)| *stack_node)
+                    .chain([neighbor])
+                    .collect::<Vec<usize>>();
+
+                let step = steps.init_step(format!(
+                    "Edge {} leads back to a node on the stack, so the graph has the cycle {}. The graph isn't acyclic, so it has no topological order",
+                    edge_name(graph, edge),
+                    path_name(graph, &cycle)
+                ));
+                for pair in cycle.windows(2) {
+                    step.node_states.insert(pair[0], ElementState::Conflict);
+                    step.edge_states
+                        .insert((pair[0], pair[1]), ElementState::Conflict);
+                }
+                return steps;
+            }
+
+            if visited.contains(&neighbor) {
+                let step = steps.init_step(format!(
+                    "Follow edge {}. Node {} is already finished",
+                    edge_name(graph, edge),
+                    neighbor
+                ));
+                step.edge_states.insert(edge, ElementState::Rejected);
+                continue;
+            }
+
+            visited.insert(neighbor);
+            stack.push((neighbor, 0));
+
+            let step = steps.init_step(format!(
+                "Follow edge {} and push node {} to the stack",
+                edge_name(graph, edge),
+                neighbor
+            ));
+            step.edge_states.insert(edge, ElementState::Selected);
+            step.node_states.insert(neighbor, ElementState::Frontier);
+            step.lists = lists(&stack, &order);
+        }
+    }
+
+    steps.init_step(format!(
+        "Every node has been finished, so the topological order is {}",
+        path_name(graph, &order)
+    ));
+
+    steps
+}
+
+/// Labels the nodes with their positions in the order.
+fn show_order(step: &mut GraphStep, order: &[usize]) {
+    step.node_labels = order
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (*node, format!("#{}", i + 1)))
+        .collect();
+}
+
+fn lists(stack: &[(usize, usize)], order: &[usize]) -> Vec<StepList> {
+    vec![
+        StepList::new(
+            "Stack",
+            stack.iter().map(|(node, _)| node.to_string()).collect(),
+        ),
+        StepList::new("Order", order.iter().map(|node| node.to_string()).collect()),
+    ]
+}
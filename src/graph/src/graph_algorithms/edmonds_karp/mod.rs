@@ -0,0 +1,258 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::*;
+
+/// Residual capacities smaller than this are treated as zero so that rounding errors don't create new augmenting paths.
+const EPSILON: f32 = 1e-4;
+
+/// Finds the maximum flow from the start node to the end node, using the weights of the edges as their capacities.
+/// The flow is increased along the shortest augmenting path in the residual graph until no such path is left,
+/// and the nodes that can still be reached from the start node then form the minimum cut.
+pub fn edmonds_karp(graph: &Graph, options: &AlgorithmOptions) -> GraphSteps {
+    let AlgorithmOptions { start, end, .. } = *options;
+    let mut steps = GraphSteps::new();
+    if !graph.nodes.contains(&start) || !graph.nodes.contains(&end) {
+        return steps;
+    }
+    if start == end {
+        steps.init_step(
+            "The source and the sink are the same node, so there is no flow".to_string(),
+        );
+        return steps;
+    }
+    if graph.edges.values().any(|capacity| *capacity < 0.0) {
+        steps.init_step("The capacities of the edges can't be negative".to_string());
+        return steps;
+    }
+
+    let mut flows = graph
+        .edges
+        .keys()
+        .map(|edge| (*edge, 0.0))
+        .collect::<BTreeMap<(usize, usize), f32>>();
+    let mut total_flow = 0.0;
+    let mut paths = Vec::<String>::new();
+
+    let step = steps.init_step(format!(
+        "Start with no flow from the source {} to the sink {}. The capacities of the edges are their weights",
+        start, end
+    ));
+    show_flows(graph, &flows, step);
+    step.lists = vec![StepList::new("Augmenting paths", vec![])];
+
+    while let Some(arcs) = shortest_augmenting_path(graph, &flows, start, end) {
+        let bottleneck = arcs
+            .iter()
+            .map(|arc| arc.residual(graph, &flows))
+            .fold(f32::INFINITY, f32::min);
+        let mut nodes = vec![start];
+        nodes.extend(arcs.iter().map(|arc| arc.to()));
+        let backward_edges = arcs
+            .iter()
+            .filter(|arc| !arc.forward)
+            .map(|arc| edge_name(graph, arc.edge))
+            .collect::<Vec<String>>();
+
+        let step = steps.init_step(format!(
+            "Breadth-first search finds the shortest augmenting path {}{}. The smallest residual capacity on it is {}",
+            path_name(graph, &nodes),
+            match backward_edges.len() {
+                0 => String::new(),
+                1 => format!(", which follows edge {} backwards to cancel its flow", backward_edges[0]),
+                _ => format!(", which follows edges {} backwards to cancel their flow", backward_edges.join(", ")),
+            },
+            format_weight(bottleneck)
+        ));
+        for node in nodes.iter() {
+            step.node_states.insert(*node, ElementState::Active);
+        }
+        for arc in arcs.iter() {
+            step.edge_states.insert(arc.edge, ElementState::Active);
+        }
+
+        for arc in arcs.iter() {
+            let capacity = graph.edges[&arc.edge];
+            let flow = flows.get_mut(&arc.edge).unwrap();
+            *flow = if arc.forward {
+                *flow + bottleneck
+            } else {
+                *flow - bottleneck
+            };
+            if (capacity - *flow).abs() < EPSILON {
+                *flow = capacity;
+            } else if flow.abs() < EPSILON {
+                *flow = 0.0;
+            }
+        }
+        total_flow += bottleneck;
+        paths.push(format!(
+            "{}: {}",
+            path_name(graph, &nodes),
+            format_weight(bottleneck)
+        ));
+
+        let step = steps.init_step(format!(
+            "Increase the flow along the path by {} and update the residual capacities. The total flow is now {}",
+            format_weight(bottleneck),
+            format_weight(total_flow)
+        ));
+        show_flows(graph, &flows, step);
+        step.lists = vec![StepList::new("Augmenting paths", paths.clone())];
+    }
+
+    // The edges from the reachable nodes to the rest are all saturated, so their capacities add up to the flow
+    let source_side = reachable(graph, &flows, start);
+    let cut = graph
+        .edges
+        .keys()
+        .filter(|(a, b)| source_side.contains(a) && !source_side.contains(b))
+        .copied()
+        .collect::<Vec<(usize, usize)>>();
+
+    let step = steps.init_step(if cut.is_empty() {
+        "No path leads from the source to the sink, so the maximum flow is 0".to_string()
+    } else {
+        format!(
+            "No augmenting path is left, so the maximum flow is {}. The nodes that can still be reached from the source form the minimum cut, whose {} {} add up to the same amount",
+            format_weight(total_flow),
+            if cut.len() == 1 { "edge" } else { "edges" },
+            cut.iter()
+                .map(|edge| edge_name(graph, *edge))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    });
+    step.node_groups = graph
+        .nodes
+        .iter()
+        .map(|node| (*node, usize::from(!source_side.contains(node))))
+        .collect();
+    for edge in cut {
+        step.edge_states.insert(edge, ElementState::Conflict);
+    }
+
+    steps
+}
+
+/// An edge of the residual graph. A forward arc can add flow to an edge that isn't saturated,
+/// and a backward arc goes against the direction of an edge with flow and cancels some of it.
+#[derive(Clone, Copy, Debug)]
+struct Arc {
+    edge: (usize, usize),
+    forward: bool,
+}
+impl Arc {
+    fn from(&self) -> usize {
+        if self.forward {
+            self.edge.0
+        } else {
+            self.edge.1
+        }
+    }
+    fn to(&self) -> usize {
+        if self.forward {
+            self.edge.1
+        } else {
+            self.edge.0
+        }
+    }
+    fn residual(&self, graph: &Graph, flows: &BTreeMap<(usize, usize), f32>) -> f32 {
+        if self.forward {
+            graph.edges[&self.edge] - flows[&self.edge]
+        } else {
+            flows[&self.edge]
+        }
+    }
+}
+
+/// Gets the arcs of the residual graph that leave the given node.
+fn residual_arcs(graph: &Graph, flows: &BTreeMap<(usize, usize), f32>, node: usize) -> Vec<Arc> {
+    graph
+        .edges
+        .keys()
+        .flat_map(|edge| {
+            [
+                Arc {
+                    edge: *edge,
+                    forward: true,
+                },
+                Arc {
+                    edge: *edge,
+                    forward: false,
+                },
+            ]
+        })
+        .filter(|arc| arc.from() == node && arc.residual(graph, flows) > EPSILON)
+        .collect()
+}
+
+/// Finds the path from the start node to the end node with the fewest arcs in the residual graph.
+fn shortest_augmenting_path(
+    graph: &Graph,
+    flows: &BTreeMap<(usize, usize), f32>,
+    start: usize,
+    end: usize,
+) -> Option<Vec<Arc>> {
+    let mut came_from = BTreeMap::<usize, Arc>::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        for arc in residual_arcs(graph, flows, node) {
+            let to = arc.to();
+            if to == start || came_from.contains_key(&to) {
+                continue;
+            }
+            came_from.insert(to, arc);
+            queue.push_back(to);
+        }
+        if came_from.contains_key(&end) {
+            break;
+        }
+    }
+
+    let mut path = vec![];
+    let mut curr = end;
+    while curr != start {
+        let arc = *came_from.get(&curr)?;
+        path.push(arc);
+        curr = arc.from();
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Gets the nodes that can be reached from the start node in the residual graph.
+fn reachable(
+    graph: &Graph,
+    flows: &BTreeMap<(usize, usize), f32>,
+    start: usize,
+) -> BTreeSet<usize> {
+    let mut visited = BTreeSet::from([start]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for arc in residual_arcs(graph, flows, node) {
+            if visited.insert(arc.to()) {
+                stack.push(arc.to());
+            }
+        }
+    }
+    visited
+}
+
+/// Labels the edges with their flows and capacities and highlights the edges that carry flow.
+fn show_flows(graph: &Graph, flows: &BTreeMap<(usize, usize), f32>, step: &mut GraphStep) {
+    step.edge_labels.clear();
+    step.edge_states.clear();
+    for (edge, flow) in flows.iter() {
+        let capacity = graph.edges[edge];
+        step.edge_labels.insert(
+            *edge,
+            format!("{}/{}", format_weight(*flow), format_weight(capacity)),
+        );
+        if *flow >= capacity {
+            step.edge_states.insert(*edge, ElementState::Rejected);
+        } else if *flow > 0.0 {
+            step.edge_states.insert(*edge, ElementState::Selected);
+        }
+    }
+}
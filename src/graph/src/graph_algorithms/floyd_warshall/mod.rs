@@ -0,0 +1,183 @@
+use std::collections::BTreeSet;
+
+use crate::*;
+
+/// Finds the shortest distances between every pair of nodes. The nodes are allowed as intermediate nodes of the paths
+/// one at a time, and the distance from `i` to `j` is updated whenever going through the new node `k` is shorter.
+pub fn floyd_warshall(graph: &Graph, _options: &AlgorithmOptions) -> GraphSteps {
+    let mut steps = GraphSteps::new();
+    let nodes = graph.nodes.iter().copied().collect::<Vec<usize>>();
+    let n = nodes.len();
+
+    let mut distances = vec![vec![None; n]; n];
+    // The intermediate node of the shortest path from `i` to `j`, for highlighting the paths
+    let mut via = vec![vec![None; n]; n];
+    for i in 0..n {
+        distances[i][i] = Some(0.0);
+        for j in 0..n {
+            if let Some(weight) = graph.weight(nodes[i], nodes[j]).filter(|_| i != j) {
+                distances[i][j] = Some(weight);
+            }
+        }
+    }
+
+    let step = steps.init_step(
+        "Start with the weights of the edges as the distances. Nodes without an edge between them are infinitely far apart"
+            .to_string(),
+    );
+    step.matrix = Some(StepMatrix {
+        title: "Distances".to_string(),
+        nodes: nodes.clone(),
+        values: distances.clone(),
+        ..Default::default()
+    });
+
+    for k in 0..n {
+        let mut updated = BTreeSet::<(usize, usize)>::new();
+
+        let step = steps.init_step(format!(
+            "Allow the paths to go through node {}. Its row and column hold the distances to and from it",
+            nodes[k]
+        ));
+        show(step, &nodes, &distances, k, &updated);
+
+        for i in 0..n {
+            for j in 0..n {
+                if i == k || j == k {
+                    continue;
+                }
+                let (Some(to_k), Some(from_k)) = (distances[i][k], distances[k][j]) else {
+                    continue;
+                };
+                let through_k = to_k + from_k;
+                let improves = distances[i][j].is_none_or(|distance| through_k < distance);
+                // A node only gets a shorter distance to itself if it is on a negative cycle
+                if i == j && !improves {
+                    continue;
+                }
+
+                let description = format!(
+                    "From {} to {} through {}: {} = {}",
+                    nodes[i],
+                    nodes[j],
+                    nodes[k],
+                    sum_name(to_k, from_k),
+                    format_weight(through_k)
+                );
+                let old_distance = distances[i][j].map_or("∞".to_string(), format_weight);
+
+                if improves {
+                    distances[i][j] = Some(through_k);
+                    via[i][j] = Some(k);
+                    updated.insert((i, j));
+                }
+
+                let step = steps.init_step(if improves {
+                    format!(
+                        "{}, which is shorter than {}, so it becomes the new distance",
+                        description, old_distance
+                    )
+                } else {
+                    format!("{}, which isn't shorter than {}", description, old_distance)
+                });
+                show(step, &nodes, &distances, k, &updated);
+
+                let matrix = step.matrix.as_mut().unwrap();
+                matrix.cell_states.insert((i, k), ElementState::Active);
+                matrix.cell_states.insert((k, j), ElementState::Active);
+                if !improves {
+                    matrix.cell_states.insert((i, j), ElementState::Active);
+                }
+                step.node_states.insert(nodes[i], ElementState::Active);
+                step.node_states.insert(nodes[j], ElementState::Active);
+                if improves && i != j {
+                    for pair in path(&via, i, j, n).windows(2) {
+                        step.edge_states.insert(
+                            graph.edge_key(nodes[pair[0]], nodes[pair[1]]),
+                            ElementState::Active,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    let on_negative_cycle = (0..n)
+        .filter(|i| distances[*i][*i].is_some_and(|distance| distance < 0.0))
+        .collect::<Vec<usize>>();
+
+    let step = steps.init_step(if on_negative_cycle.is_empty() {
+        "Every node has been allowed on the paths, so the matrix has the shortest distances between every pair of nodes"
+            .to_string()
+    } else {
+        format!(
+            "Every node has been allowed on the paths, but {}, so the graph has a negative cycle and the distances through it have no lower bound",
+            if on_negative_cycle.len() == 1 {
+                format!("node {} has a negative distance to itself", nodes[on_negative_cycle[0]])
+            } else {
+                format!(
+                    "the nodes {} have negative distances to themselves",
+                    set_name(
+                        &on_negative_cycle
+                            .iter()
+                            .map(|i| nodes[*i])
+                            .collect::<Vec<usize>>()
+                    )
+                )
+            }
+        )
+    });
+    step.node_states.clear();
+    step.edge_states.clear();
+    let matrix = step.matrix.as_mut().unwrap();
+    matrix.cell_states.clear();
+    for i in on_negative_cycle {
+        step.node_states.insert(nodes[i], ElementState::Conflict);
+        matrix.cell_states.insert((i, i), ElementState::Conflict);
+    }
+
+    steps
+}
+
+/// Gets the indices of the nodes on the shortest path from `i` to `j` by splitting it at its intermediate nodes.
+/// The paths can go around negative cycles forever, so they are only split `depth` times.
+fn path(via: &[Vec<Option<usize>>], i: usize, j: usize, depth: usize) -> Vec<usize> {
+    match via[i][j] {
+        Some(k) if depth > 0 => {
+            let mut nodes = path(via, i, k, depth - 1);
+            nodes.extend(&path(via, k, j, depth - 1)[1..]);
+            nodes
+        }
+        _ => vec![i, j],
+    }
+}
+
+/// Shows the distance matrix with the row and column of the intermediate node and the distances updated through it.
+fn show(
+    step: &mut GraphStep,
+    nodes: &[usize],
+    distances: &[Vec<Option<f32>>],
+    k: usize,
+    updated: &BTreeSet<(usize, usize)>,
+) {
+    let n = nodes.len();
+    let mut matrix = StepMatrix {
+        title: "Distances".to_string(),
+        nodes: nodes.to_vec(),
+        values: distances.to_vec(),
+        ..Default::default()
+    };
+    for other in 0..n {
+        matrix
+            .cell_states
+            .insert((k, other), ElementState::Frontier);
+        matrix
+            .cell_states
+            .insert((other, k), ElementState::Frontier);
+    }
+    for cell in updated {
+        matrix.cell_states.insert(*cell, ElementState::Selected);
+    }
+    step.matrix = Some(matrix);
+    step.node_states = [(nodes[k], ElementState::Frontier)].into();
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use crate::*;
+
+/// The number of iterations that the temperature cools down over.
+const ITERATIONS: usize = 50;
+/// How far from the sides of the area the nodes are kept.
+const MARGIN: f32 = 40.0;
+/// Scales the ideal distance between nodes, which is computed from the area that each node gets.
+const IDEAL_DISTANCE_SCALE: f32 = 0.6;
+/// How far each node can move on average during an iteration for the layout to be considered converged.
+const CONVERGED_MOVEMENT: f32 = 0.5;
+
+/// Lays the graph out with the force-directed algorithm of Fruchterman and Reingold, starting from the current positions of the nodes.
+/// Every pair of nodes pushes the nodes apart and every edge pulls its nodes together, and the nodes move along the sum of the forces.
+/// How far the nodes can move is limited by a temperature, which cools down after every iteration so that the layout settles.
+pub fn fruchterman_reingold(graph: &Graph, options: &AlgorithmOptions) -> GraphSteps {
+    let (width, height) = options.layout_size;
+    let mut steps = GraphSteps::new();
+    let mut positions = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            let position = options
+                .positions
+                .get(node)
+                .copied()
+                .unwrap_or((width / 2.0, height / 2.0));
+            (*node, position)
+        })
+        .collect::<BTreeMap<usize, (f32, f32)>>();
+
+    if positions.len() < 2 {
+        steps.init_step("A graph with fewer than two nodes has nothing to lay out".to_string());
+        return steps;
+    }
+
+    let ideal_distance = IDEAL_DISTANCE_SCALE * (width * height / positions.len() as f32).sqrt();
+    let start_temperature = width / 10.0;
+    let mut temperatures = Vec::<f32>::new();
+
+    let step = steps.init_step(format!(
+        "Start from the current layout. Nodes push each other away and edges pull their nodes together, so that the nodes of an edge settle about {:.0} units apart",
+        ideal_distance
+    ));
+    step.node_positions = positions.clone();
+
+    for iteration in 1..=ITERATIONS {
+        let temperature = start_temperature * (1.0 - (iteration - 1) as f32 / ITERATIONS as f32);
+        temperatures.push(temperature);
+
+        let mut displacements = positions
+            .keys()
+            .map(|node| (*node, (0.0, 0.0)))
+            .collect::<BTreeMap<usize, (f32, f32)>>();
+
+        for (a, pos_a) in positions.iter() {
+            for (b, pos_b) in positions.range(a + 1..) {
+                let (dx, dy, distance) = difference(*a, *pos_a, *b, *pos_b);
+                let force = ideal_distance.powi(2) / distance;
+                push(&mut displacements, *a, dx, dy, force);
+                push(&mut displacements, *b, dx, dy, -force);
+            }
+        }
+        for (a, b) in graph.edges.keys().filter(|(a, b)| a != b) {
+            let (dx, dy, distance) = difference(*a, positions[a], *b, positions[b]);
+            let force = distance.powi(2) / ideal_distance;
+            push(&mut displacements, *a, dx, dy, -force);
+            push(&mut displacements, *b, dx, dy, force);
+        }
+
+        let mut total_movement = 0.0;
+        for (node, (dx, dy)) in displacements {
+            let length = (dx * dx + dy * dy).sqrt();
+            if length < f32::EPSILON {
+                continue;
+            }
+            let movement = length.min(temperature);
+            let (x, y) = positions[&node];
+            let new_position = (
+                (x + dx / length * movement).clamp(MARGIN, width - MARGIN),
+                (y + dy / length * movement).clamp(MARGIN, height - MARGIN),
+            );
+            total_movement += ((new_position.0 - x).powi(2) + (new_position.1 - y).powi(2)).sqrt();
+            positions.insert(node, new_position);
+        }
+
+        let step = steps.init_step(format!(
+            "Iteration {}: the temperature is {:.1}, so no node moves further than that. The nodes moved {:.1} units in total",
+            iteration, temperature, total_movement
+        ));
+        step.node_positions = positions.clone();
+        step.lists = vec![StepList::new(
+            "Temperature",
+            temperatures
+                .iter()
+                .map(|temperature| format!("{:.0}", temperature))
+                .collect(),
+        )];
+
+        if total_movement < CONVERGED_MOVEMENT * positions.len() as f32 {
+            steps.init_step(
+                "The nodes barely moved, so the layout has converged before the temperature cooled down"
+                    .to_string(),
+            );
+            return steps;
+        }
+    }
+
+    steps.init_step("The temperature has cooled down, so the layout is finished".to_string());
+
+    steps
+}
+
+/// Gets the direction and distance from node `b` to node `a`.
+/// Nodes at the same position are moved apart in a direction that depends on their ids.
+fn difference(a: usize, (ax, ay): (f32, f32), b: usize, (bx, by): (f32, f32)) -> (f32, f32, f32) {
+    let (dx, dy) = (ax - bx, ay - by);
+    let distance = (dx * dx + dy * dy).sqrt();
+    if distance < 0.01 {
+        let angle = (a * 7 + b * 13) as f32;
+        return (angle.cos(), angle.sin(), 0.01);
+    }
+    (dx / distance, dy / distance, distance)
+}
+
+/// Adds a force along a direction to the displacement of a node.
+fn push(
+    displacements: &mut BTreeMap<usize, (f32, f32)>,
+    node: usize,
+    dx: f32,
+    dy: f32,
+    force: f32,
+) {
+    let displacement = displacements.get_mut(&node).unwrap();
+    displacement.0 += dx * force;
+    displacement.1 += dy * force;
+}
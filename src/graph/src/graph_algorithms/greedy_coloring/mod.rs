@@ -0,0 +1,100 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::*;
+
+/// Colors the nodes one at a time from the highest degree to the lowest, always giving a node
+/// the smallest color that none of its neighbors has. This is also known as the Welsh-Powell algorithm.
+pub fn greedy_coloring(graph: &Graph, _options: &AlgorithmOptions) -> GraphSteps {
+    let mut steps = GraphSteps::new();
+    let order = by_degree(graph);
+    let mut colors = BTreeMap::<usize, usize>::new();
+
+    steps
+        .init_step("Order the nodes by their degrees from the highest to the lowest".to_string())
+        .lists = vec![order_list(&order, &colors)];
+
+    for node in order.iter() {
+        let neighbor_colors = graph
+            .neighbors(*node)
+            .iter()
+            .filter_map(|(neighbor, _)| colors.get(neighbor).copied())
+            .collect::<BTreeSet<usize>>();
+        let color = (0..)
+            .find(|color| !neighbor_colors.contains(color))
+            .unwrap();
+        colors.insert(*node, color);
+
+        let step = steps.init_step(if neighbor_colors.is_empty() {
+            format!(
+                "None of the neighbors of node {} have a color yet, so it gets color {}",
+                node,
+                color + 1
+            )
+        } else {
+            format!(
+                "The neighbors of node {} have the {} {}, so it gets the smallest free color {}",
+                node,
+                if neighbor_colors.len() == 1 {
+                    "color"
+                } else {
+                    "colors"
+                },
+                neighbor_colors
+                    .iter()
+                    .map(|color| (color + 1).to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                color + 1
+            )
+        });
+        show_colors(&colors, step);
+        step.node_states.insert(*node, ElementState::Active);
+        for (neighbor, _) in graph.neighbors(*node) {
+            if colors.contains_key(&neighbor) && neighbor != *node {
+                step.edge_states
+                    .insert(graph.edge_key(*node, neighbor), ElementState::Active);
+            }
+        }
+        step.lists = vec![order_list(&order, &colors)];
+    }
+
+    let color_count = colors.values().max().map_or(0, |color| color + 1);
+    steps.init_step(format!(
+        "Every node has a color. The greedy coloring uses {} {}, so the chromatic number of the graph is at most {}, but another order could need fewer",
+        color_count,
+        if color_count == 1 { "color" } else { "colors" },
+        color_count
+    ));
+
+    steps
+}
+
+/// Gets the nodes from the highest degree to the lowest. Nodes with the same degree are ordered by their ids.
+pub(crate) fn by_degree(graph: &Graph) -> Vec<usize> {
+    let mut order = graph.nodes.iter().copied().collect::<Vec<usize>>();
+    order.sort_by_key(|node| std::cmp::Reverse(graph.neighbors(*node).len()));
+    order
+}
+
+/// Shows the colors of the nodes as their groups, and as labels so that they can be told apart without seeing the colors.
+pub(crate) fn show_colors(colors: &BTreeMap<usize, usize>, step: &mut GraphStep) {
+    step.node_groups = colors.clone();
+    step.node_labels = colors
+        .iter()
+        .map(|(node, color)| (*node, format!("color {}", color + 1)))
+        .collect();
+}
+
+/// Lists the nodes in the order that they are colored in, along with their colors.
+pub(crate) fn order_list(order: &[usize], colors: &BTreeMap<usize, usize>) -> StepList {
+    StepList::new(
+        "Order",
+        order
+            .iter()
+            .map(|node| match colors.get(node) {
+                Some(color) => format!("{}: {}", node, color + 1),
+                None => node.to_string(),
+            })
+            .collect(),
+    )
+}
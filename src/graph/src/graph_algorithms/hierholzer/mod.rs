@@ -0,0 +1,217 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::*;
+
+/// Finds an Eulerian circuit of an undirected multigraph, which uses every edge exactly once and ends where it starts.
+/// The weight of an edge is the number of edges between its nodes.
+///
+/// The walk follows unused edges from the start node until it gets stuck, which can only happen at the node where it started.
+/// Nodes are popped from the stack until one with unused edges is found, and a new cycle is walked from there.
+/// The popped nodes form the circuit, which splices the cycles together.
+pub fn hierholzer(graph: &Graph, options: &AlgorithmOptions) -> GraphSteps {
+    let AlgorithmOptions { start, .. } = *options;
+    let mut steps = GraphSteps::new();
+    let counts = graph
+        .edges
+        .iter()
+        .map(|(edge, weight)| (*edge, weight.round().max(1.0) as usize))
+        .collect::<BTreeMap<(usize, usize), usize>>();
+    let degrees = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            let degree = counts
+                .iter()
+                .filter(|((a, b), _)| *a == *node || *b == *node)
+                .map(|(_, count)| count)
+                .sum::<usize>();
+            (*node, degree)
+        })
+        .collect::<BTreeMap<usize, usize>>();
+    let degree_labels = degrees
+        .iter()
+        .map(|(node, degree)| (*node, format!("degree {}", degree)))
+        .collect::<BTreeMap<usize, String>>();
+
+    if counts.is_empty() {
+        steps.init_step("The graph has no edges, so there is no circuit to find".to_string());
+        return steps;
+    }
+
+    let odd_nodes = degrees
+        .iter()
+        .filter(|(_, degree)| *degree % 2 == 1)
+        .map(|(node, _)| *node)
+        .collect::<Vec<usize>>();
+    if !odd_nodes.is_empty() {
+        let step = steps.init_step(format!(
+            "The nodes {} have an odd degree. A circuit leaves a node every time it enters it, so the graph has no Eulerian circuit",
+            set_name(&odd_nodes)
+        ));
+        step.node_labels = degree_labels;
+        for node in odd_nodes {
+            step.node_states.insert(node, ElementState::Conflict);
+        }
+        return steps;
+    }
+
+    let reachable = reachable(graph, start);
+    let unreachable = degrees
+        .iter()
+        .filter(|(node, degree)| **degree > 0 && !reachable.contains(node))
+        .map(|(node, _)| *node)
+        .collect::<Vec<usize>>();
+    if !unreachable.is_empty() {
+        let step = steps.init_step(format!(
+            "The edges of the nodes {} can't be reached from node {}, so no circuit from it can use every edge",
+            set_name(&unreachable),
+            start
+        ));
+        step.node_labels = degree_labels;
+        step.node_states.insert(start, ElementState::Active);
+        for node in unreachable {
+            step.node_states.insert(node, ElementState::Conflict);
+        }
+        return steps;
+    }
+
+    let mut remaining = counts.clone();
+    let mut stack = vec![start];
+    let mut circuit = Vec::<usize>::new();
+
+    let step = steps.init_step(format!(
+        "Every node has an even degree and every edge can be reached from node {}, so the graph has an Eulerian circuit. Push node {} to the stack",
+        start, start
+    ));
+    step.node_labels = degree_labels;
+    show(&counts, &remaining, &stack, &circuit, step);
+
+    while let Some(node) = stack.last().copied() {
+        let next = graph
+            .neighbors(node)
+            .into_iter()
+            .map(|(neighbor, _)| neighbor)
+            .find(|neighbor| remaining[&graph.edge_key(node, *neighbor)] > 0);
+
+        if let Some(neighbor) = next {
+            let edge = graph.edge_key(node, neighbor);
+            *remaining.get_mut(&edge).unwrap() -= 1;
+            stack.push(neighbor);
+
+            let step = steps.init_step(format!(
+                "Walk along an unused edge {} and push node {} to the stack",
+                edge_name(graph, (node, neighbor)),
+                neighbor
+            ));
+            show(&counts, &remaining, &stack, &circuit, step);
+            step.edge_states.insert(edge, ElementState::Active);
+            continue;
+        }
+
+        stack.pop();
+        circuit.insert(0, node);
+
+        let mut description = format!(
+            "Node {} has no unused edges, so it is popped from the stack and added to the circuit",
+            node
+        );
+        let splice_node = stack
+            .last()
+            .copied()
+            .filter(|top| unused_degree(&remaining, *top) > 0);
+        if let Some(top) = splice_node {
+            description.push_str(&format!(
+                ". Node {} still has unused edges, so a new cycle is walked from it and spliced into the circuit there",
+                top
+            ));
+        }
+        let step = steps.init_step(description);
+        show(&counts, &remaining, &stack, &circuit, step);
+        step.node_states.insert(node, ElementState::Active);
+    }
+
+    // The edges are numbered in the order that the circuit uses them
+    let mut numbers = BTreeMap::<(usize, usize), Vec<String>>::new();
+    for (i, pair) in circuit.windows(2).enumerate() {
+        numbers
+            .entry(graph.edge_key(pair[0], pair[1]))
+            .or_default()
+            .push(format!("#{}", i + 1));
+    }
+
+    let step = steps.init_step(format!(
+        "The stack is empty, so the circuit {} uses all {} edges",
+        path_name(graph, &circuit),
+        circuit.len() - 1
+    ));
+    step.node_states.clear();
+    step.edge_states = counts
+        .keys()
+        .map(|edge| (*edge, ElementState::Selected))
+        .collect();
+    step.edge_labels = numbers
+        .into_iter()
+        .map(|(edge, numbers)| (edge, numbers.join(", ")))
+        .collect();
+
+    steps
+}
+
+/// Gets the number of unused edges of a node.
+fn unused_degree(remaining: &BTreeMap<(usize, usize), usize>, node: usize) -> usize {
+    remaining
+        .iter()
+        .filter(|((a, b), _)| *a == node || *b == node)
+        .map(|(_, count)| count)
+        .sum()
+}
+
+/// Gets the nodes that can be reached from `start`.
+fn reachable(graph: &Graph, start: usize) -> BTreeSet<usize> {
+    let mut reached = BTreeSet::from([start]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for (neighbor, _) in graph.neighbors(node) {
+            if reached.insert(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+    reached
+}
+
+/// Shows how many of the edges between each pair of nodes have been used, fading out the ones that have all been used.
+fn show(
+    counts: &BTreeMap<(usize, usize), usize>,
+    remaining: &BTreeMap<(usize, usize), usize>,
+    stack: &[usize],
+    circuit: &[usize],
+    step: &mut GraphStep,
+) {
+    step.node_states = circuit
+        .iter()
+        .map(|node| (*node, ElementState::Selected))
+        .chain(stack.iter().map(|node| (*node, ElementState::Frontier)))
+        .collect();
+    step.edge_states.clear();
+    step.edge_labels.clear();
+    for (edge, count) in counts.iter() {
+        let unused = remaining[edge];
+        if unused == 0 {
+            step.edge_states.insert(*edge, ElementState::Rejected);
+        } else if unused < *count {
+            step.edge_states.insert(*edge, ElementState::Frontier);
+        }
+        if *count > 1 {
+            step.edge_labels
+                .insert(*edge, format!("{}/{} used", count - unused, count));
+        }
+    }
+    step.lists = vec![
+        StepList::new("Stack", stack.iter().map(|node| node.to_string()).collect()),
+        StepList::new(
+            "Circuit",
+            circuit.iter().map(|node| node.to_string()).collect(),
+        ),
+    ];
+}
@@ -0,0 +1,157 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::*;
+
+/// Sorts the nodes of a directed acyclic graph so that every edge goes from an earlier node to a later one.
+/// Nodes without incoming edges are taken one at a time, and their outgoing edges are removed from the graph.
+/// If nodes are left over when there are no such nodes, the graph has a cycle Undirected graphs can't be sorted at all.
+pub fn kahn(graph: &Graph, _options: &AlgorithmOptions) -> GraphSteps {
+    let mut steps = GraphSteps::new();
+    if !graph.directed {
+        steps.init_step(
+            "A topological order needs a directed graph, since the edges of undirected graphs go both ways"
+                .to_string(),
+        );
+        return steps;
+    }
+
+    let mut in_degrees = graph
+        .nodes
+        .iter()
+        .map(|node| (*node, 0))
+        .collect::<BTreeMap<usize, usize>>();
+    for (_, to) in graph.edges.keys() {
+        *in_degrees.get_mut(to).unwrap() += 1;
+    }
+
+    let mut queue = in_degrees
+        .iter()
+        .filter(|(_, in_degree)| **in_degree == 0)
+        .map(|(node, _)| *node)
+        .collect::<VecDeque<usize>>();
+    let mut order = vec![];
+
+    let step = steps.init_step(
+        "Count the incoming edges of each node and queue the nodes that have none".to_string(),
+    );
+    for node in queue.iter() {
+        step.node_states.insert(*node, ElementState::Frontier);
+    }
+    show_in_degrees(step, &in_degrees, &order);
+    step.lists = lists(&queue, &order);
+
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+
+        let step = steps.init_step(format!(
+            "Take node {} from the queue and add it to the order",
+            node
+        ));
+        step.node_states.insert(node, ElementState::Selected);
+        show_in_degrees(step, &in_degrees, &order);
+        step.lists = lists(&queue, &order);
+
+        for (neighbor, _) in graph.neighbors(node) {
+            let in_degree = in_degrees.get_mut(&neighbor).unwrap();
+            *in_degree -= 1;
+
+            let step = if *in_degree == 0 {
+                queue.push_back(neighbor);
+                let step = steps.init_step(format!(
+                    "Remove edge {}. Node {} has no incoming edges left, so it is queued",
+                    edge_name(graph, (node, neighbor)),
+                    neighbor
+                ));
+                step.node_states.insert(neighbor, ElementState::Frontier);
+                step
+            } else {
+                steps.init_step(format!(
+                    "Remove edge {}. Node {} still has {} incoming {}",
+                    edge_name(graph, (node, neighbor)),
+                    neighbor,
+                    in_degree,
+                    if *in_degree == 1 { "edge" } else { "edges" }
+                ))
+            };
+            step.edge_states
+                .insert((node, neighbor), ElementState::Rejected);
+            show_in_degrees(step, &in_degrees, &order);
+            step.lists = lists(&queue, &order);
+        }
+    }
+
+    if order.len() == graph.nodes.len() {
+        steps.init_step(format!(
+            "Every node has been ordered, so the topological order is {}",
+            path_name(graph, &order)
+        ));
+    } else {
+        let ordered = order.iter().copied().collect::<BTreeSet<usize>>();
+        let cycle = find_cycle(graph, &ordered);
+        let step = steps.init_step(format!(
+            "The queue is empty, but {} of the nodes couldn't be ordered because they are on or after a cycle, like {}. The graph isn't acyclic, so it has no topological order",
+            graph.nodes.len() - order.len(),
+            path_name(graph, &cycle)
+        ));
+        for pair in cycle.windows(2) {
+            step.node_states.insert(pair[0], ElementState::Conflict);
+            step.edge_states
+                .insert((pair[0], pair[1]), ElementState::Conflict);
+        }
+    }
+
+    steps
+}
+
+/// Labels the nodes with the number of incoming edges that they have left, or their positions in the order.
+fn show_in_degrees(step: &mut GraphStep, in_degrees: &BTreeMap<usize, usize>, order: &[usize]) {
+    step.node_labels = in_degrees
+        .iter()
+        .map(|(node, in_degree)| (*node, format!("in: {}", in_degree)))
+        .collect();
+    for (i, node) in order.iter().enumerate() {
+        step.node_labels.insert(*node, format!("#{}", i + 1));
+    }
+}
+
+fn lists(queue: &VecDeque<usize>, order: &[usize]) -> Vec<StepList> {
+    vec![
+        StepList::new("Queue", queue.iter().map(|node| node.to_string()).collect()),
+        StepList::new("Order", order.iter().map(|node| node.to_string()).collect()),
+    ]
+}
+
+/// Finds a cycle among the nodes that couldn't be ordered. Each of them has an incoming edge from another one of them,
+/// so following the incoming edges backwards has to come back to a node that was already seen.
+/// The cycle is returned in the direction of the edges, with the first node repeated at the end.
+fn find_cycle(graph: &Graph, ordered: &BTreeSet<usize>) -> Vec<usize> {
+    let predecessor = |node: usize| {
+        graph
+            .edges
+            .keys()
+            .find(|(from, to)| *to == node && !ordered.contains(from))
+            .map(|(from, _)| *from)
+            .unwrap()
+    };
+
+    let mut node = *graph
+        .nodes
+        .iter()
+        .find(|node| !ordered.contains(node))
+        .unwrap();
+    let mut seen = vec![];
+    while !seen.contains(&node) {
+        seen.push(node);
+        node = predecessor(node);
+    }
+
+    // The nodes before the first occurrence of the repeated node lead to the cycle, but aren't on it
+    let cycle_start = seen
+        .iter()
+        .position(|seen_node| *seen_node == node)
+        .unwrap();
+    let mut cycle = seen[cycle_start..].to_vec();
+    cycle.push(node);
+    cycle.reverse();
+    cycle
+}
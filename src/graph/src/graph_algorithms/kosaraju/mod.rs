@@ -0,0 +1,158 @@
+use std::collections::BTreeSet;
+
+use crate::*;
+
+/// Finds the strongly connected components of a directed graph with two depth-first searches.
+/// The first search records the order that the nodes are finished in. The second one goes through the nodes
+/// from the last finished one to the first and follows the edges backwards, and each search finds one component.
+pub fn kosaraju(graph: &Graph, _options: &AlgorithmOptions) -> GraphSteps {
+    let mut steps = GraphSteps::new();
+    let mut visited = BTreeSet::new();
+    let mut finished = Vec::<usize>::new();
+    // The nodes whose edges are being followed along with the indices of the next neighbors to look at
+    let mut calls = Vec::<(usize, usize)>::new();
+    let mut components = Vec::<Vec<usize>>::new();
+
+    let lists = |calls: &[(usize, usize)], finished: &[usize], components: &[Vec<usize>]| {
+        vec![
+            StepList::new(
+                "Depth-first search",
+                calls.iter().map(|(node, _)| node.to_string()).collect(),
+            ),
+            StepList::new(
+                "Finished",
+                finished.iter().map(|node| node.to_string()).collect(),
+            ),
+            StepList::new(
+                "Components",
+                components
+                    .iter()
+                    .map(|component| set_name(component))
+                    .collect(),
+            ),
+        ]
+    };
+
+    steps
+        .init_step(
+            "First, search the graph and record the order that the nodes are finished in"
+                .to_string(),
+        )
+        .lists = lists(&calls, &finished, &components);
+
+    for root in graph.nodes.iter() {
+        if !visited.insert(*root) {
+            continue;
+        }
+        calls.push((*root, 0));
+
+        let step = steps.init_step(format!("Start a depth-first search from node {}", root));
+        step.node_states.insert(*root, ElementState::Frontier);
+        step.lists = lists(&calls, &finished, &components);
+
+        while let Some((node, neighbor_i)) = calls.last().copied() {
+            let neighbors = graph.neighbors(node);
+
+            if let Some((neighbor, _)) = neighbors.get(neighbor_i).copied() {
+                calls.last_mut().unwrap().1 += 1;
+                if visited.insert(neighbor) {
+                    calls.push((neighbor, 0));
+
+                    let step = steps.init_step(format!(
+                        "Follow edge {} to node {}",
+                        edge_name(graph, (node, neighbor)),
+                        neighbor
+                    ));
+                    step.edge_states
+                        .insert((node, neighbor), ElementState::Selected);
+                    step.node_states.insert(neighbor, ElementState::Frontier);
+                    step.lists = lists(&calls, &finished, &components);
+                }
+                continue;
+            }
+
+            calls.pop();
+            finished.push(node);
+
+            let step = steps.init_step(format!(
+                "Every node that can be reached from node {} has been visited, so it is finished as number {}",
+                node,
+                finished.len()
+            ));
+            step.node_states.insert(node, ElementState::Selected);
+            step.node_labels
+                .insert(node, format!("#{}", finished.len()));
+            step.lists = lists(&calls, &finished, &components);
+        }
+    }
+
+    let step = steps.init_step(
+        "Next, search the graph with its edges reversed, starting from the node that was finished last. Each search finds one component".to_string(),
+    );
+    step.node_states.clear();
+    step.edge_states.clear();
+
+    let mut assigned = BTreeSet::new();
+
+    for root in finished.iter().rev() {
+        if !assigned.insert(*root) {
+            continue;
+        }
+        let component_i = components.len();
+        let mut component = vec![*root];
+        let mut stack = vec![*root];
+
+        let step = steps.init_step(format!(
+            "Node {} was finished last of the nodes that aren't in a component yet, so it starts a new component",
+            root
+        ));
+        step.node_groups.insert(*root, component_i);
+        step.node_states.insert(*root, ElementState::Active);
+
+        while let Some(node) = stack.pop() {
+            // The edges are followed backwards, so the predecessors of the node are searched
+            for predecessor in graph
+                .nodes
+                .iter()
+                .filter(|predecessor| graph.edges.contains_key(&(**predecessor, node)))
+            {
+                if !assigned.insert(*predecessor) {
+                    continue;
+                }
+                component.push(*predecessor);
+                stack.push(*predecessor);
+
+                let step = steps.init_step(format!(
+                    "Follow edge {} backwards, so node {} is in the same component as node {}",
+                    edge_name(graph, (*predecessor, node)),
+                    predecessor,
+                    root
+                ));
+                step.edge_states
+                    .insert((*predecessor, node), ElementState::Selected);
+                step.node_groups.insert(*predecessor, component_i);
+                step.node_states.insert(*predecessor, ElementState::Active);
+            }
+        }
+
+        component.sort();
+        components.push(component);
+        let step = steps.init_step(format!(
+            "No more nodes can be reached backwards, so the component {} is complete",
+            set_name(components.last().unwrap())
+        ));
+        step.lists = lists(&calls, &finished, &components);
+    }
+
+    steps.init_step(format!(
+        "Every node is in a component, and the graph has {} strongly connected {}",
+        components.len(),
+        if components.len() == 1 {
+            "component"
+        } else {
+            "components"
+        }
+    ));
+
+    steps
+}
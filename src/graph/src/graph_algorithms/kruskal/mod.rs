@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+
+use crate::*;
+
+/// Finds a minimum spanning forest by going through the edges from the cheapest to the most expensive
+/// and keeping the edges that connect two different sets of nodes.
+pub fn kruskal(graph: &Graph, _options: &AlgorithmOptions) -> GraphSteps {
+    let mut steps = GraphSteps::new();
+    let mut sets = DisjointSet::new(graph);
+
+    let mut edges = graph
+        .edges
+        .iter()
+        .map(|(edge, weight)| (*edge, *weight))
+        .collect::<Vec<((usize, usize), f32)>>();
+    edges.sort_by(|(edge_a, a), (edge_b, b)| a.partial_cmp(b).unwrap().then(edge_a.cmp(edge_b)));
+
+    let step = steps.init_step("Every node starts in a set of its own".to_string());
+    sets.show(step);
+    step.lists.push(edges_list(graph, &edges));
+
+    let mut tree_weight = 0.0;
+    let mut tree_edges = 0;
+
+    for (i, (edge, weight)) in edges.iter().enumerate() {
+        if tree_edges + 1 >= graph.nodes.len() {
+            let step = steps.init_step(
+                "The tree spans all of the nodes, so the rest of the edges are skipped".to_string(),
+            );
+            for (edge, _) in &edges[i..] {
+                step.edge_states.insert(*edge, ElementState::Rejected);
+            }
+            step.lists[1] = edges_list(graph, &[]);
+            break;
+        }
+
+        let step = steps.init_step(format!(
+            "Take the cheapest remaining edge {} with a weight of {}",
+            edge_name(graph, *edge),
+            format_weight(*weight)
+        ));
+        step.edge_states.insert(*edge, ElementState::Active);
+        step.lists[1] = edges_list(graph, &edges[i + 1..]);
+
+        let (a, b) = *edge;
+        let (root_a, root_b) = (sets.find(a), sets.find(b));
+        if root_a == root_b {
+            let step = steps.init_step(format!(
+                "Skip {} because {} and {} are already in the same set, so the edge would make a cycle",
+                edge_name(graph, *edge),
+                a,
+                b
+            ));
+            step.edge_states.insert(*edge, ElementState::Rejected);
+        } else {
+            sets.union(root_a, root_b);
+            tree_weight += weight;
+            tree_edges += 1;
+
+            let step = steps.init_step(format!(
+                "Add {} to the tree and merge the sets of {} and {}",
+                edge_name(graph, *edge),
+                a,
+                b
+            ));
+            step.edge_states.insert(*edge, ElementState::Selected);
+            sets.show(step);
+        }
+    }
+
+    let set_count = sets.sets().len();
+    let step = steps.init_step(if set_count > 1 {
+        format!(
+            "The graph isn't connected, so the result is a forest of {} trees with a total weight of {}",
+            set_count,
+            format_weight(tree_weight)
+        )
+    } else {
+        format!(
+            "The minimum spanning tree has a total weight of {}",
+            format_weight(tree_weight)
+        )
+    });
+    for edge in graph.edges.keys() {
+        step.edge_states
+            .entry(*edge)
+            .or_insert(ElementState::Rejected);
+    }
+
+    steps
+}
+
+/// Lists edges along with their weights in the order that they are taken.
+fn edges_list(graph: &Graph, edges: &[((usize, usize), f32)]) -> StepList {
+    StepList::new(
+        "Remaining edges",
+        edges
+            .iter()
+            .map(|(edge, weight)| {
+                format!("{} ({})", edge_name(graph, *edge), format_weight(*weight))
+            })
+            .collect(),
+    )
+}
+
+/// A union-find data structure for keeping track of which nodes are connected.
+struct DisjointSet {
+    parents: BTreeMap<usize, usize>,
+    /// The upper bounds for the heights of the trees, used to keep the trees shallow
+    ranks: BTreeMap<usize, usize>,
+}
+impl DisjointSet {
+    fn new(graph: &Graph) -> Self {
+        Self {
+            parents: graph.nodes.iter().map(|node| (*node, *node)).collect(),
+            ranks: graph.nodes.iter().map(|node| (*node, 0)).collect(),
+        }
+    }
+    /// Finds the root of the set that the node is in, making the nodes on the way point to the root directly.
+    fn find(&mut self, node: usize) -> usize {
+        let parent = self.parents[&node];
+        if parent == node {
+            return node;
+        }
+        let root = self.find(parent);
+        self.parents.insert(node, root);
+        root
+    }
+    /// Joins the sets with the given roots. The root of the shorter tree is made to point to the other root.
+    fn union(&mut self, root_a: usize, root_b: usize) {
+        let (rank_a, rank_b) = (self.ranks[&root_a], self.ranks[&root_b]);
+        if rank_a < rank_b {
+            self.parents.insert(root_a, root_b);
+        } else {
+            self.parents.insert(root_b, root_a);
+            if rank_a == rank_b {
+                self.ranks.insert(root_a, rank_a + 1);
+            }
+        }
+    }
+    /// Finds the root of the set that the node is in without changing the parents.
+    fn root(&self, node: usize) -> usize {
+        let mut root = node;
+        while self.parents[&root] != root {
+            root = self.parents[&root];
+        }
+        root
+    }
+    /// Gets the nodes of each set, ordered by their smallest nodes.
+    fn sets(&self) -> Vec<Vec<usize>> {
+        let mut sets = BTreeMap::<usize, Vec<usize>>::new();
+        for node in self.parents.keys() {
+            sets.entry(self.root(*node)).or_default().push(*node);
+        }
+        let mut sets = sets.into_values().collect::<Vec<Vec<usize>>>();
+        sets.sort();
+        sets
+    }
+    /// Colors the nodes by their sets and labels them with their parents, replacing the list of sets in the step.
+    fn show(&self, step: &mut GraphStep) {
+        step.node_groups.clear();
+        step.node_labels.clear();
+        for (node, parent) in self.parents.iter() {
+            step.node_groups.insert(*node, self.root(*node));
+            if parent != node {
+                step.node_labels.insert(*node, format!("↑{}", parent));
+            }
+        }
+
+        let sets = StepList::new(
+            "Sets",
+            self.sets().iter().map(|set| set_name(set)).collect(),
+        );
+        if step.lists.is_empty() {
+            step.lists.push(sets);
+        } else {
+            step.lists[0] = sets;
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! A collection of algorithms on general graphs.
+mod backtracking_coloring;
+mod bellman_ford;
+mod dfs_topological_sort;
+mod edmonds_karp;
+mod floyd_warshall;
+mod fruchterman_reingold;
+mod greedy_coloring;
+mod hierholzer;
+mod kahn;
+mod kosaraju;
+mod kruskal;
+mod pagerank;
+mod prim;
+mod tarjan;
+
+pub use backtracking_coloring::backtracking_coloring;
+pub use bellman_ford::bellman_ford;
+pub use dfs_topological_sort::dfs_topological_sort;
+pub use edmonds_karp::edmonds_karp;
+pub use floyd_warshall::floyd_warshall;
+pub use fruchterman_reingold::fruchterman_reingold;
+pub use greedy_coloring::greedy_coloring;
+pub use hierholzer::hierholzer;
+pub use kahn::kahn;
+pub use kosaraju::kosaraju;
+pub use kruskal::kruskal;
+pub use pagerank::pagerank;
+pub use prim::prim;
+pub use tarjan::tarjan;
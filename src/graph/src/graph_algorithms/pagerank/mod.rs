@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use crate::*;
+
+/// The most iterations that are run if the ranks don't converge before.
+const MAX_ITERATIONS: usize = 100;
+/// The ranks have converged when they change less than this in total during an iteration.
+const TOLERANCE: f32 = 1e-5;
+
+/// Ranks the nodes of a directed graph by how likely a random surfer is to be at them.
+/// The surfer follows a random outgoing edge with the probability of the damping factor and otherwise jumps to a random node.
+/// Power iteration starts from equal ranks and recomputes them from the ranks of the predecessors until they stop changing.
+pub fn pagerank(graph: &Graph, options: &AlgorithmOptions) -> GraphSteps {
+    let damping = options.damping;
+    let mut steps = GraphSteps::new();
+    let node_count = graph.nodes.len();
+
+    if node_count == 0 {
+        steps.init_step("The graph has no nodes to rank".to_string());
+        return steps;
+    }
+
+    let out_degrees = graph
+        .nodes
+        .iter()
+        .map(|node| (*node, graph.neighbors(*node).len()))
+        .collect::<BTreeMap<usize, usize>>();
+    let dangling = out_degrees
+        .iter()
+        .filter(|(_, degree)| **degree == 0)
+        .map(|(node, _)| *node)
+        .collect::<Vec<usize>>();
+    let mut ranks = graph
+        .nodes
+        .iter()
+        .map(|node| (*node, 1.0 / node_count as f32))
+        .collect::<BTreeMap<usize, f32>>();
+    let mut residuals = Vec::<f32>::new();
+
+    let mut description = format!(
+        "Give every node the same rank of 1/{}. The surfer follows an edge with a probability of {} and otherwise jumps to a random node",
+        node_count,
+        format_weight(damping)
+    );
+    if !dangling.is_empty() {
+        description.push_str(&format!(
+            ". The nodes {} have no outgoing edges, so the surfer always jumps from them",
+            set_name(&dangling)
+        ));
+    }
+    let step = steps.init_step(description);
+    show(&ranks, &residuals, &dangling, step);
+
+    for iteration in 1..=MAX_ITERATIONS {
+        let dangling_rank = dangling.iter().map(|node| ranks[node]).sum::<f32>();
+        // The rank that every node gets from jumps, including every jump from a node without outgoing edges
+        let base =
+            (1.0 - damping) / node_count as f32 + damping * dangling_rank / node_count as f32;
+        let mut new_ranks = graph
+            .nodes
+            .iter()
+            .map(|node| (*node, base))
+            .collect::<BTreeMap<usize, f32>>();
+        for (node, rank) in ranks.iter() {
+            for (neighbor, _) in graph.neighbors(*node) {
+                *new_ranks.get_mut(&neighbor).unwrap() += damping * rank / out_degrees[node] as f32;
+            }
+        }
+
+        let residual = ranks
+            .iter()
+            .map(|(node, rank)| (new_ranks[node] - rank).abs())
+            .sum::<f32>();
+        residuals.push(residual);
+        // The node whose rank changed the most
+        let most_changed = ranks
+            .iter()
+            .max_by(|(a, rank_a), (b, rank_b)| {
+                (new_ranks[*a] - **rank_a)
+                    .abs()
+                    .partial_cmp(&(new_ranks[*b] - **rank_b).abs())
+                    .unwrap()
+            })
+            .map(|(node, _)| *node)
+            .unwrap();
+        ranks = new_ranks;
+
+        let step = steps.init_step(format!(
+            "Iteration {}: every node shares its rank along its outgoing edges. The ranks changed by {:.6} in total, most at node {}",
+            iteration, residual, most_changed
+        ));
+        show(&ranks, &residuals, &dangling, step);
+        step.node_states.insert(most_changed, ElementState::Active);
+
+        if residual < TOLERANCE {
+            let mut order = ranks.keys().copied().collect::<Vec<usize>>();
+            order.sort_by(|a, b| ranks[b].partial_cmp(&ranks[a]).unwrap());
+            steps.init_step(format!(
+                "The ranks changed by less than {} in total, so they have converged. Node {} has the highest rank",
+                TOLERANCE, order[0]
+            ));
+            return steps;
+        }
+    }
+
+    steps.init_step(format!(
+        "The ranks didn't converge in {} iterations",
+        MAX_ITERATIONS
+    ));
+
+    steps
+}
+
+/// Shows the ranks as labels and node values relative to the highest rank, along with the ranking and the residuals.
+fn show(ranks: &BTreeMap<usize, f32>, residuals: &[f32], dangling: &[usize], step: &mut GraphStep) {
+    step.node_states = dangling
+        .iter()
+        .map(|node| (*node, ElementState::Rejected))
+        .collect();
+    let max_rank = ranks.values().copied().fold(0.0, f32::max);
+    step.node_labels = ranks
+        .iter()
+        .map(|(node, rank)| (*node, format!("{:.3}", rank)))
+        .collect();
+    step.node_values = ranks
+        .iter()
+        .map(|(node, rank)| (*node, rank / max_rank))
+        .collect();
+
+    let mut order = ranks.keys().copied().collect::<Vec<usize>>();
+    order.sort_by(|a, b| ranks[b].partial_cmp(&ranks[a]).unwrap());
+    step.lists = vec![StepList::new(
+        "Ranking",
+        order
+            .iter()
+            .map(|node| format!("{}: {:.3}", node, ranks[node]))
+            .collect(),
+    )];
+    step.chart = Some(StepChart {
+        title: "Change in the ranks".to_string(),
+        values: residuals.to_vec(),
+    });
+}
@@ -0,0 +1,136 @@
+use std::collections::BTreeSet;
+
+use crate::*;
+
+/// Grows a minimum spanning tree from the start node by always adding the cheapest edge
+/// that leads from the tree to a node outside of it.
+pub fn prim(graph: &Graph, options: &AlgorithmOptions) -> GraphSteps {
+    let AlgorithmOptions { start, .. } = *options;
+    let mut steps = GraphSteps::new();
+    if !graph.nodes.contains(&start) {
+        return steps;
+    }
+
+    let mut tree = BTreeSet::from([start]);
+    // The edges that lead out of the tree, from the node in the tree to the other node
+    let mut frontier = Vec::<(usize, usize, f32)>::new();
+    let mut tree_weight = 0.0;
+
+    let step = steps.init_step(format!("Start the tree from node {}", start));
+    step.node_states.insert(start, ElementState::Selected);
+    add_to_frontier(graph, &tree, &mut frontier, start, step);
+
+    // The frontier can contain edges whose both ends have been added to the tree since they were added,
+    // since they are only removed when they are the cheapest edge
+    while !frontier.is_empty() {
+        let cheapest = (0..frontier.len())
+            .min_by(|a, b| {
+                let (from_a, to_a, weight_a) = frontier[*a];
+                let (from_b, to_b, weight_b) = frontier[*b];
+                weight_a
+                    .partial_cmp(&weight_b)
+                    .unwrap()
+                    .then((from_a, to_a).cmp(&(from_b, to_b)))
+            })
+            .unwrap();
+        let (from, to, weight) = frontier.remove(cheapest);
+        let edge = graph.edge_key(from, to);
+
+        let step = steps.init_step(format!(
+            "Take the cheapest frontier edge {} with a weight of {}",
+            edge_name(graph, (from, to)),
+            format_weight(weight)
+        ));
+        step.edge_states.insert(edge, ElementState::Active);
+        step.lists = vec![frontier_list(graph, &frontier)];
+
+        if tree.contains(&to) {
+            let step = steps.init_step(format!(
+                "Skip {} because {} is already in the tree",
+                edge_name(graph, (from, to)),
+                to
+            ));
+            step.edge_states.insert(edge, ElementState::Rejected);
+            continue;
+        }
+
+        tree.insert(to);
+        tree_weight += weight;
+
+        let step = steps.init_step(format!(
+            "Add node {} and edge {} to the tree",
+            to,
+            edge_name(graph, (from, to))
+        ));
+        step.node_states.insert(to, ElementState::Selected);
+        step.edge_states.insert(edge, ElementState::Selected);
+        add_to_frontier(graph, &tree, &mut frontier, to, step);
+    }
+
+    let unreachable = graph.nodes.len() - tree.len();
+    let step = steps.init_step(if unreachable > 0 {
+        format!(
+            "The frontier is empty, but {} can't be reached from node {}, so the tree only spans its component. It has a total weight of {}",
+            if unreachable == 1 {
+                "1 node".to_string()
+            } else {
+                format!("{} nodes", unreachable)
+            },
+            start,
+            format_weight(tree_weight)
+        )
+    } else {
+        format!(
+            "The minimum spanning tree has a total weight of {}",
+            format_weight(tree_weight)
+        )
+    });
+    for edge in graph.edges.keys() {
+        step.edge_states
+            .entry(*edge)
+            .or_insert(ElementState::Rejected);
+    }
+
+    steps
+}
+
+/// Adds the edges from a node that was added to the tree to the nodes outside of the tree to the frontier.
+fn add_to_frontier(
+    graph: &Graph,
+    tree: &BTreeSet<usize>,
+    frontier: &mut Vec<(usize, usize, f32)>,
+    node: usize,
+    step: &mut GraphStep,
+) {
+    for (neighbor, weight) in graph.neighbors(node) {
+        if !tree.contains(&neighbor) {
+            frontier.push((node, neighbor, weight));
+            step.edge_states
+                .insert(graph.edge_key(node, neighbor), ElementState::Frontier);
+        }
+    }
+    step.lists = vec![frontier_list(graph, frontier)];
+}
+
+/// Lists the frontier edges from the cheapest to the most expensive.
+fn frontier_list(graph: &Graph, frontier: &[(usize, usize, f32)]) -> StepList {
+    let mut edges = frontier.to_vec();
+    edges.sort_by(|(from_a, to_a, a), (from_b, to_b, b)| {
+        a.partial_cmp(b)
+            .unwrap()
+            .then((from_a, to_a).cmp(&(from_b, to_b)))
+    });
+    StepList::new(
+        "Frontier",
+        edges
+            .iter()
+            .map(|(from, to, weight)| {
+                format!(
+                    "{} ({})",
+                    edge_name(graph, (*from, *to)),
+                    format_weight(*weight)
+                )
+            })
+            .collect(),
+    )
+}
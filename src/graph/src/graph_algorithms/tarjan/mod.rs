@@ -0,0 +1,189 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::*;
+
+/// Finds the strongly connected components of a directed graph with a single depth-first search.
+/// Every node gets an index in the order that it is visited and a low-link value, which is the smallest index
+/// of a node on the stack that can be reached from it. A node whose low-link value is its own index is the root of a component.
+pub fn tarjan(graph: &Graph, _options: &AlgorithmOptions) -> GraphSteps {
+    let mut steps = GraphSteps::new();
+    let mut search = Search::default();
+
+    steps
+        .init_step("Start with every node unvisited".to_string())
+        .lists = search.lists();
+
+    for root in graph.nodes.iter() {
+        if search.indices.contains_key(root) {
+            continue;
+        }
+        search.visit(
+            *root,
+            &mut steps,
+            format!("Start a depth-first search from node {}", root),
+        );
+
+        while let Some((node, neighbor_i)) = search.calls.last().copied() {
+            let neighbors = graph.neighbors(node);
+
+            if let Some((neighbor, _)) = neighbors.get(neighbor_i).copied() {
+                search.calls.last_mut().unwrap().1 += 1;
+                let edge = (node, neighbor);
+
+                if !search.indices.contains_key(&neighbor) {
+                    let description = format!(
+                        "Follow edge {} to node {}",
+                        edge_name(graph, edge),
+                        neighbor
+                    );
+                    search.visit(neighbor, &mut steps, description);
+                    let step = steps.steps.last_mut().unwrap();
+                    step.edge_states.insert(edge, ElementState::Selected);
+                } else if search.on_stack.contains(&neighbor) {
+                    let low_link = search.low_links[&node].min(search.indices[&neighbor]);
+                    search.low_links.insert(node, low_link);
+
+                    let step = steps.init_step(format!(
+                        "Edge {} leads to node {}, which is on the stack, so the low-link value of {} is the smaller of its own and the index of {}: {}",
+                        edge_name(graph, edge),
+                        neighbor,
+                        node,
+                        neighbor,
+                        low_link
+                    ));
+                    step.edge_states.insert(edge, ElementState::Active);
+                    search.show(step);
+                } else {
+                    let step = steps.init_step(format!(
+                        "Edge {} leads to node {}, which is already in a component, so it is ignored",
+                        edge_name(graph, edge),
+                        neighbor
+                    ));
+                    step.edge_states.insert(edge, ElementState::Rejected);
+                    search.show(step);
+                }
+                continue;
+            }
+
+            search.calls.pop();
+
+            if search.low_links[&node] == search.indices[&node] {
+                // Every node above this one on the stack can reach it and be reached from it
+                let position = search
+                    .stack
+                    .iter()
+                    .position(|stack_node| *stack_node == node)
+                    .unwrap();
+                let mut component = search.stack.split_off(position);
+                component.sort();
+                for component_node in component.iter() {
+                    search.on_stack.remove(component_node);
+                }
+                search.components.push(component.clone());
+
+                let step = steps.init_step(format!(
+                    "Every edge of node {} has been followed, and its low-link value is its own index, so it is the root of a component. The nodes down to it are popped from the stack: {}",
+                    node,
+                    set_name(&component)
+                ));
+                search.show(step);
+            }
+
+            if let Some((parent, _)) = search.calls.last().copied() {
+                let low_link = search.low_links[&parent].min(search.low_links[&node]);
+                search.low_links.insert(parent, low_link);
+
+                let step = steps.init_step(format!(
+                    "Return from node {} to node {}, whose low-link value is now {}",
+                    node, parent, low_link
+                ));
+                search.show(step);
+            }
+        }
+    }
+
+    let step = steps.init_step(format!(
+        "Every node has been visited, and the graph has {} strongly connected {}",
+        search.components.len(),
+        if search.components.len() == 1 {
+            "component"
+        } else {
+            "components"
+        }
+    ));
+    search.show(step);
+
+    steps
+}
+
+/// The state of the depth-first search.
+#[derive(Default)]
+struct Search {
+    /// The order that the nodes were visited in
+    indices: BTreeMap<usize, usize>,
+    low_links: BTreeMap<usize, usize>,
+    /// The nodes that have been visited but aren't in a component yet
+    stack: Vec<usize>,
+    on_stack: BTreeSet<usize>,
+    /// The nodes whose edges are being followed along with the indices of the next neighbors to look at
+    calls: Vec<(usize, usize)>,
+    components: Vec<Vec<usize>>,
+}
+impl Search {
+    fn visit(&mut self, node: usize, steps: &mut GraphSteps, description: String) {
+        let index = self.indices.len();
+        self.indices.insert(node, index);
+        self.low_links.insert(node, index);
+        self.stack.push(node);
+        self.on_stack.insert(node);
+        self.calls.push((node, 0));
+
+        let step = steps.init_step(format!(
+            "{}. It gets the index {} and is pushed to the stack",
+            description, index
+        ));
+        self.show(step);
+        step.node_states.insert(node, ElementState::Active);
+    }
+    /// Labels the nodes with their indices and low-link values and colors them by their components.
+    fn show(&self, step: &mut GraphStep) {
+        step.node_labels = self
+            .indices
+            .iter()
+            .map(|(node, index)| (*node, format!("{} / {}", index, self.low_links[node])))
+            .collect();
+        step.node_states = self
+            .on_stack
+            .iter()
+            .map(|node| (*node, ElementState::Frontier))
+            .collect();
+        for (i, component) in self.components.iter().enumerate() {
+            for node in component {
+                step.node_groups.insert(*node, i);
+            }
+        }
+        step.lists = self.lists();
+    }
+    fn lists(&self) -> Vec<StepList> {
+        vec![
+            StepList::new(
+                "Depth-first search",
+                self.calls
+                    .iter()
+                    .map(|(node, _)| node.to_string())
+                    .collect(),
+            ),
+            StepList::new(
+                "Stack",
+                self.stack.iter().map(|node| node.to_string()).collect(),
+            ),
+            StepList::new(
+                "Components",
+                self.components
+                    .iter()
+                    .map(|component| set_name(component))
+                    .collect(),
+            ),
+        ]
+    }
+}
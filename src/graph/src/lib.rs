@@ -0,0 +1,214 @@
+//! This crate contains my implementations of algorithms on general graphs, like minimum spanning trees.
+//! Each algorithm records the state of the graph at every step so that it can be visualized.
+pub mod graph_algorithms;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A graph of nodes connected by weighted edges.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Graph {
+    pub nodes: BTreeSet<usize>,
+    /// The weights of the edges. In undirected graphs the smaller id of the two nodes is always first.
+    pub edges: BTreeMap<(usize, usize), f32>,
+    pub directed: bool,
+}
+impl Graph {
+    pub fn new(
+        nodes: BTreeSet<usize>,
+        edges: BTreeMap<(usize, usize), f32>,
+        directed: bool,
+    ) -> Self {
+        Self {
+            nodes,
+            edges,
+            directed,
+        }
+    }
+    /// Gets the key of the edge between two nodes in `edges`.
+    pub fn edge_key(&self, a: usize, b: usize) -> (usize, usize) {
+        if self.directed {
+            (a, b)
+        } else {
+            (a.min(b), a.max(b))
+        }
+    }
+    pub fn weight(&self, a: usize, b: usize) -> Option<f32> {
+        self.edges.get(&self.edge_key(a, b)).copied()
+    }
+    /// Gets the nodes that can be reached from `id` along a single edge, along with the weights of the edges.
+    pub fn neighbors(&self, id: usize) -> Vec<(usize, f32)> {
+        self.edges
+            .iter()
+            .filter_map(|((a, b), weight)| {
+                if *a == id {
+                    Some((*b, *weight))
+                } else if *b == id && !self.directed {
+                    Some((*a, *weight))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// How a node or an edge is highlighted at a step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ElementState {
+    /// The element that the algorithm is looking at. This only lasts for one step.
+    Active,
+    /// The element is a candidate that the algorithm can choose later.
+    Frontier,
+    /// The element is a part of the result.
+    Selected,
+    /// The element was looked at and left out of the result.
+    Rejected,
+    /// The element breaks a requirement of the algorithm, like a cycle in a graph that should be acyclic.
+    Conflict,
+}
+
+/// A list of items that is shown next to the graph, like a queue or a stack.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepList {
+    pub title: String,
+    pub items: Vec<String>,
+}
+impl StepList {
+    pub fn new(title: &str, items: Vec<String>) -> Self {
+        Self {
+            title: title.to_string(),
+            items,
+        }
+    }
+}
+
+/// A matrix of values between every pair of nodes that is shown next to the graph, like a distance matrix.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StepMatrix {
+    pub title: String,
+    /// The nodes of the rows and the columns in order.
+    pub nodes: Vec<usize>,
+    /// The values of the cells by their row and column indices. Missing values are shown as infinity.
+    pub values: Vec<Vec<Option<f32>>>,
+    /// The states of the cells by their row and column indices.
+    pub cell_states: BTreeMap<(usize, usize), ElementState>,
+}
+
+/// A chart of a value over the iterations of an algorithm, like how much the results changed.
+/// The values are plotted on a logarithmic scale, since they often shrink exponentially.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StepChart {
+    pub title: String,
+    /// The values of the iterations so far.
+    pub values: Vec<f32>,
+}
+
+/// The state of the whole graph at a step of an algorithm.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GraphStep {
+    /// What happened during the step.
+    pub description: String,
+    pub node_states: BTreeMap<usize, ElementState>,
+    /// The states of the edges by their keys, see [`Graph::edge_key`].
+    pub edge_states: BTreeMap<(usize, usize), ElementState>,
+    /// Nodes in the same group are shown with the same color, e.g. the nodes of a component.
+    pub node_groups: BTreeMap<usize, usize>,
+    /// Text that is shown next to nodes, like their current distances.
+    pub node_labels: BTreeMap<usize, String>,
+    /// Text that is shown on edges instead of their weights.
+    pub edge_labels: BTreeMap<(usize, usize), String>,
+    pub lists: Vec<StepList>,
+    pub matrix: Option<StepMatrix>,
+    /// Where the nodes are drawn at the step, if the algorithm moves them. Other nodes stay where they are.
+    pub node_positions: BTreeMap<usize, (f32, f32)>,
+    /// Values between 0 and 1 that are shown by the sizes and colors of the nodes, like their shares of a ranking.
+    pub node_values: BTreeMap<usize, f32>,
+    pub chart: Option<StepChart>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GraphSteps {
+    pub steps: Vec<GraphStep>,
+}
+impl GraphSteps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Starts a new step that is a copy of the last one without its [`ElementState::Active`] elements.
+    pub fn init_step(&mut self, description: String) -> &mut GraphStep {
+        let mut step = self.steps.last().cloned().unwrap_or_default();
+        step.description = description;
+        step.node_states
+            .retain(|_, state| *state != ElementState::Active);
+        step.edge_states
+            .retain(|_, state| *state != ElementState::Active);
+        if let Some(matrix) = &mut step.matrix {
+            matrix
+                .cell_states
+                .retain(|_, state| *state != ElementState::Active);
+        }
+        self.steps.push(step);
+        self.steps.last_mut().unwrap()
+    }
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// The choices that can be made when running an algorithm. Algorithms ignore the ones that they don't need.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlgorithmOptions {
+    pub start: usize,
+    pub end: usize,
+    /// How many colors a coloring algorithm can use.
+    pub color_count: usize,
+    /// How likely a random surfer is to follow an edge instead of jumping to a random node in PageRank.
+    pub damping: f32,
+    /// The current positions of the nodes, which layout algorithms start from.
+    pub positions: BTreeMap<usize, (f32, f32)>,
+    /// The width and height of the area that layout algorithms keep the nodes in.
+    pub layout_size: (f32, f32),
+}
+
+pub type GraphAlgorithmFunc = fn(&Graph, &AlgorithmOptions) -> GraphSteps;
+
+/// Formats an edge for step descriptions, e.g. "1 – 3" or "1 → 3" in directed graphs.
+pub fn edge_name(graph: &Graph, (a, b): (usize, usize)) -> String {
+    format!("{} {} {}", a, if graph.directed { "→" } else { "–" }, b)
+}
+
+/// Formats a path through the given nodes for step descriptions, e.g. "2 → 5 → 3".
+pub fn path_name(graph: &Graph, nodes: &[usize]) -> String {
+    let separator = if graph.directed { " → " } else { " – " };
+    nodes
+        .iter()
+        .map(|node| node.to_string())
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+/// Formats a set of nodes for step descriptions and lists, e.g. "{1, 4, 5}".
+pub fn set_name(nodes: &[usize]) -> String {
+    let nodes = nodes
+        .iter()
+        .map(|node| node.to_string())
+        .collect::<Vec<String>>();
+    format!("{{{}}}", nodes.join(", "))
+}
+
+/// Formats a weight or a sum of weights without the rounding errors of floats, e.g. 0.30000001 as "0.3".
+pub fn format_weight(weight: f32) -> String {
+    ((weight * 1000.0).round() / 1000.0).to_string()
+}
+
+/// Formats a sum of two weights for step descriptions, e.g. "2 + 1.5" or "2 − 3".
+pub fn sum_name(a: f32, b: f32) -> String {
+    if b < 0.0 {
+        format!("{} − {}", format_weight(a), format_weight(-b))
+    } else {
+        format!("{} + {}", format_weight(a), format_weight(b))
+    }
+}
@@ -0,0 +1,403 @@
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const MAX_INTERVALS: usize = 12;
+const MAX_TIME: u32 = 24;
+const INTERVAL_COUNTS: [&str; 4] = ["8", "5", "10", "12"];
+const ORDERS: [&str; 3] = ["Finish time", "Start time", "Length"];
+const CANVAS_HEIGHT: f32 = 440.0;
+const TIMELINE_LEFT: f32 = 60.0;
+const TIMELINE_RIGHT: f32 = CANVAS_WIDTH - 40.0;
+const ROWS_TOP: f32 = 50.0;
+const ROW_HEIGHT: f32 = 28.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Interval {
+    start: u32,
+    end: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Order {
+    Finish,
+    Start,
+    Length,
+}
+
+/// Activity selection, where the most intervals that don't overlap are chosen from a set of intervals on a timeline,
+/// like meetings that need the same room. The greedy algorithm sorts the intervals by the time they finish, and
+/// chooses every interval that starts after the latest chosen one has finished. The intervals can also be sorted by
+/// their starts or lengths to see why those orders don't always work.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntervalScheduling {
+    intervals: Vec<Interval>,
+}
+impl IntervalScheduling {
+    /// The intervals are given as their starts and ends.
+    pub fn new(intervals: &[(u32, u32)]) -> Self {
+        Self {
+            intervals: intervals
+                .iter()
+                .map(|(start, end)| Interval {
+                    start: *start,
+                    end: *end,
+                })
+                .collect(),
+        }
+    }
+    fn time_x(&self, time: f32) -> f32 {
+        let max = self
+            .intervals
+            .iter()
+            .map(|interval| interval.end)
+            .max()
+            .unwrap_or(1);
+        TIMELINE_LEFT + time / max as f32 * (TIMELINE_RIGHT - TIMELINE_LEFT)
+    }
+    /// Draws the intervals on their rows in the order, with a dashed line at the time that the chosen intervals
+    /// finish.
+    fn draw(
+        &self,
+        order: &[usize],
+        states: &[(usize, ElementState)],
+        free_from: Option<u32>,
+    ) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let bottom = ROWS_TOP + order.len() as f32 * ROW_HEIGHT;
+        for (row, index) in order.iter().enumerate() {
+            let Interval { start, end } = self.intervals[*index];
+            let (left, right) = (self.time_x(start as f32), self.time_x(end as f32));
+            let mut node = DiagramNode::rect(
+                format!("interval-{}", index),
+                format!("{} {}–{}", interval_name(*index), start, end),
+                (
+                    (left + right) / 2.0,
+                    ROWS_TOP + (row as f32 + 0.5) * ROW_HEIGHT,
+                ),
+                (right - left, ROW_HEIGHT - 6.0),
+            );
+            node.state = states
+                .iter()
+                .find(|(state_index, _)| state_index == index)
+                .map(|(_, state)| *state);
+            diagram.add_node(node);
+        }
+        // The timeline with a tick at every time that an interval starts or ends
+        diagram.add_figure(Figure::new(FigureKind::Line(
+            (TIMELINE_LEFT, bottom + 10.0),
+            (TIMELINE_RIGHT, bottom + 10.0),
+        )));
+        let mut times = self
+            .intervals
+            .iter()
+            .flat_map(|interval| [interval.start, interval.end])
+            .chain([0])
+            .collect::<Vec<u32>>();
+        times.sort_unstable();
+        times.dedup();
+        for time in times {
+            let x = self.time_x(time as f32);
+            diagram.add_figure(Figure::new(FigureKind::Line(
+                (x, bottom + 6.0),
+                (x, bottom + 14.0),
+            )));
+            diagram.add_node(DiagramNode::text(
+                format!("time-{}", time),
+                time,
+                (x, bottom + 28.0),
+            ));
+        }
+        if let Some(time) = free_from {
+            let x = self.time_x(time as f32);
+            diagram.add_figure(
+                Figure::new(FigureKind::Line((x, ROWS_TOP - 10.0), (x, bottom + 10.0)))
+                    .dashed()
+                    .with_state(ElementState::Selected),
+            );
+            diagram.add_node(DiagramNode::text(
+                "free-from",
+                format!("Free from {}", time),
+                (x, ROWS_TOP - 24.0),
+            ));
+        }
+        diagram
+    }
+    fn sorted(&self, order: Order) -> Vec<usize> {
+        let mut indices = (0..self.intervals.len()).collect::<Vec<usize>>();
+        // Ties are broken by the order of the intervals, since the sort is stable
+        indices.sort_by_key(|index| {
+            let Interval { start, end } = self.intervals[*index];
+            match order {
+                Order::Finish => end,
+                Order::Start => start,
+                Order::Length => end - start,
+            }
+        });
+        indices
+    }
+    /// The intervals that the greedy algorithm chooses in the order.
+    fn choose(&self, order: &[usize]) -> Vec<usize> {
+        let mut chosen: Vec<usize> = vec![];
+        for index in order {
+            if chosen
+                .iter()
+                .all(|other| !overlap(self.intervals[*index], self.intervals[*other]))
+            {
+                chosen.push(*index);
+            }
+        }
+        chosen
+    }
+    fn push(
+        &self,
+        description: String,
+        diagram: Diagram,
+        chosen: &[usize],
+        skipped: &[usize],
+        steps: &mut DiagramSteps,
+    ) {
+        let names = |indices: &[usize]| indices.iter().map(|index| interval_name(*index)).collect();
+        let step = steps.push(description, diagram);
+        step.lists = vec![
+            StepList::new("Chosen", names(chosen)),
+            StepList::new("Skipped", names(skipped)),
+        ];
+    }
+    fn schedule(&self, order: Order, steps: &mut DiagramSteps) {
+        let original = (0..self.intervals.len()).collect::<Vec<usize>>();
+        let sorted = self.sorted(order);
+        self.push(
+            format!(
+                "Choose the most intervals that don't overlap. An interval can start at the same time as another one ends. There are {} to choose from",
+                plural(self.intervals.len(), "interval")
+            ),
+            self.draw(&original, &[], None),
+            &[],
+            &[],
+            steps,
+        );
+        self.push(
+            match order {
+                Order::Finish => "Sort the intervals by the time they finish. The interval that finishes first leaves the most time for the rest",
+                Order::Start => "Sort the intervals by the time they start. This looks reasonable, but a long interval that starts early can block many short ones",
+                Order::Length => "Sort the intervals from the shortest to the longest. This looks reasonable, but a short interval can overlap two longer ones that don't overlap each other",
+            }
+            .to_string(),
+            self.draw(&sorted, &[], None),
+            &[],
+            &[],
+            steps,
+        );
+
+        let mut chosen: Vec<usize> = vec![];
+        let mut skipped = vec![];
+        for index in sorted.iter().copied() {
+            let interval = self.intervals[index];
+            let name = interval_name(index);
+            let conflict = chosen
+                .iter()
+                .copied()
+                .find(|other| overlap(interval, self.intervals[*other]));
+            let mut states = chosen
+                .iter()
+                .map(|other| (*other, ElementState::Selected))
+                .chain(skipped.iter().map(|other| (*other, ElementState::Rejected)))
+                .collect::<Vec<(usize, ElementState)>>();
+            let description = match conflict {
+                Some(other) => {
+                    skipped.push(index);
+                    states.retain(|(state_index, _)| *state_index != other);
+                    states.extend([
+                        (index, ElementState::Rejected),
+                        (other, ElementState::Conflict),
+                    ]);
+                    format!(
+                        "{} overlaps {}, which was chosen already, so skip it",
+                        name,
+                        interval_name(other)
+                    )
+                }
+                None => {
+                    let description = match order {
+                        // With the finish times in order, the latest chosen interval is the only one that can overlap
+                        Order::Finish => match chosen.last() {
+                            Some(last) => format!(
+                                "{} starts at {}, after {} finishes at {}, so choose it",
+                                name,
+                                interval.start,
+                                interval_name(*last),
+                                self.intervals[*last].end
+                            ),
+                            None => format!("{} finishes first, so choose it", name),
+                        },
+                        _ => format!(
+                            "{} doesn't overlap any of the chosen intervals, so choose it",
+                            name
+                        ),
+                    };
+                    chosen.push(index);
+                    states.push((index, ElementState::Active));
+                    description
+                }
+            };
+            let free_from = match order {
+                Order::Finish => chosen.last().map(|last| self.intervals[*last].end),
+                _ => None,
+            };
+            self.push(
+                description,
+                self.draw(&sorted, &states, free_from),
+                &chosen,
+                &skipped,
+                steps,
+            );
+        }
+
+        let names = chosen
+            .iter()
+            .map(|index| interval_name(*index))
+            .collect::<Vec<String>>();
+        let best = self.choose(&self.sorted(Order::Finish)).len();
+        let description = match order {
+            Order::Finish => format!(
+                "Chose {}: {}. No set of intervals that don't overlap is larger, since the interval that finishes first can always replace the first interval of any other set",
+                plural(chosen.len(), "interval"),
+                join_names(&names)
+            ),
+            _ if chosen.len() < best => format!(
+                "Chose {}: {}. Sorting by the finish time would have chosen {}, so this order isn't always the best",
+                plural(chosen.len(), "interval"),
+                join_names(&names),
+                best
+            ),
+            _ => format!(
+                "Chose {}: {}. That is as many as sorting by the finish time chooses here, but this order doesn't always find the most intervals",
+                plural(chosen.len(), "interval"),
+                join_names(&names)
+            ),
+        };
+        let states = chosen
+            .iter()
+            .map(|index| (*index, ElementState::Selected))
+            .chain(skipped.iter().map(|index| (*index, ElementState::Rejected)))
+            .collect::<Vec<(usize, ElementState)>>();
+        self.push(
+            description,
+            self.draw(&sorted, &states, None),
+            &chosen,
+            &skipped,
+            steps,
+        );
+    }
+}
+impl Visualizer for IntervalScheduling {
+    fn diagram(&self) -> Diagram {
+        self.draw(
+            &(0..self.intervals.len()).collect::<Vec<usize>>(),
+            &[],
+            None,
+        )
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let intervals = Input::Text {
+            label: "Intervals",
+            default: self
+                .intervals
+                .iter()
+                .map(|interval| format!("{}-{}", interval.start, interval.end))
+                .collect::<Vec<String>>()
+                .join(" "),
+        };
+        let order = Input::Choice {
+            label: "Sort by",
+            choices: ORDERS.iter().map(|order| order.to_string()).collect(),
+        };
+        vec![
+            Operation::new("Schedule", vec![intervals, order.clone()]),
+            Operation::new(
+                "Random intervals",
+                vec![
+                    Input::Choice {
+                        label: "Number of intervals",
+                        choices: INTERVAL_COUNTS
+                            .iter()
+                            .map(|count| count.to_string())
+                            .collect(),
+                    },
+                    order,
+                ],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        let order = match values.get(1).map(|value| value.text()).as_deref() {
+            Some("Start time") => Order::Start,
+            Some("Length") => Order::Length,
+            _ => Order::Finish,
+        };
+        match operation {
+            "Schedule" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                self.intervals = parse_intervals(&text)?;
+                self.schedule(order, &mut steps);
+            }
+            "Random intervals" => {
+                let count = values.first().map(|value| value.number()).unwrap_or(8);
+                let mut rng = rand::thread_rng();
+                self.intervals = (0..count.clamp(1, MAX_INTERVALS as i64))
+                    .map(|_| {
+                        let start = rng.gen_range(0..MAX_TIME - 2);
+                        Interval {
+                            start,
+                            end: (start + rng.gen_range(1..=6)).min(MAX_TIME),
+                        }
+                    })
+                    .collect();
+                self.schedule(order, &mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// Whether the intervals overlap for some time. Intervals that only touch at their ends don't overlap.
+fn overlap(a: Interval, b: Interval) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+fn interval_name(index: usize) -> String {
+    ((b'A' + index as u8) as char).to_string()
+}
+
+/// Parses intervals like "1-4", which are separated by commas or spaces.
+fn parse_intervals(text: &str) -> Result<Vec<Interval>, String> {
+    let intervals = text
+        .split([',', ' '])
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| {
+            let (start, end) = part.trim().split_once(['-', '–'])?;
+            Some(Interval {
+                start: start.parse().ok()?,
+                end: end.parse().ok()?,
+            })
+        })
+        .collect::<Option<Vec<Interval>>>()
+        .ok_or("The intervals must be starts and ends separated by spaces, like \"1-4 3-5\"")?;
+    if intervals.is_empty() || intervals.len() > MAX_INTERVALS {
+        return Err(format!("There must be 1 to {} intervals", MAX_INTERVALS));
+    }
+    if intervals
+        .iter()
+        .any(|interval| interval.start >= interval.end || interval.end > MAX_TIME)
+    {
+        return Err(format!(
+            "Every interval must end after it starts, and no later than {}",
+            MAX_TIME
+        ));
+    }
+    Ok(intervals)
+}
@@ -0,0 +1,5 @@
+//! A collection of algorithms that build a solution by always making the choice that looks best right now, and
+//! never take a choice back.
+mod interval_scheduling;
+
+pub use interval_scheduling::IntervalScheduling;
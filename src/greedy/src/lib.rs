@@ -0,0 +1,14 @@
+//! This crate contains my implementations of greedy algorithms, like interval scheduling.
+//! Each algorithm records a diagram of the choices that it has made so far at every step.
+pub mod greedy_algorithms;
+
+pub const CANVAS_WIDTH: f32 = 800.0;
+
+/// The names in a list like "A, B and C".
+pub fn join_names(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
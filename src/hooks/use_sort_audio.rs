@@ -1,5 +1,3 @@
-use std::rc::Rc;
-
 use crate::{
     components::sorting::audio_controls::AudioConfig,
     utils::audio::{Note, Synth},
@@ -16,24 +14,47 @@ pub fn use_sort_audio(
 ) {
     let synth = use_mut_ref(Synth::new);
 
+    {
+        let synth = synth.clone();
+
+        use_effect_with_deps(
+            move |(volume, muted)| {
+                synth
+                    .borrow()
+                    .set_volume(if *muted { 0.0 } else { *volume });
+                || ()
+            },
+            (config.volume, config.muted),
+        );
+    }
+
     use_effect(move || {
         if config.enabled {
             synth.borrow_mut().stop_all();
 
             let mut notes: Vec<Note> = vec![];
 
-            let ctx = Rc::clone(&synth.borrow().ctx);
-
             for command in step.iter() {
-                let val = match command {
-                    SortCommand::Swap(_, to) => items[*to],
-                    SortCommand::Set(index, _) => items[*index],
-                } as f32;
+                let index = match command {
+                    SortCommand::Swap(_, to) => *to,
+                    SortCommand::Set(index, _) => *index,
+                };
+                let val = items[index] as f32;
                 let ratio = val / *items.iter().max().unwrap() as f32;
-                let frequency =
-                    config.min_frequency + (config.max_frequency - config.min_frequency) * ratio;
+                let frequency = config.pitch_mapping.frequency(
+                    ratio,
+                    config.min_frequency,
+                    config.max_frequency,
+                );
+
+                // Index 0 is hard left and the last index is hard right
+                let pan = if config.stereo_panning && items.len() > 1 {
+                    index as f32 / (items.len() - 1) as f32 * 2.0 - 1.0
+                } else {
+                    0.0
+                };
 
-                notes.push(Note::new(&ctx, frequency, config.sound_type));
+                notes.push(Note::new(frequency, config.sound_type, pan));
             }
 
             synth.borrow_mut().play(notes, config.note_duration);
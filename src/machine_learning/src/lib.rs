@@ -0,0 +1,182 @@
+//! This crate contains my implementations of machine learning algorithms, like gradient descent.
+//! The models learn from points that are placed on a chart, and each records a diagram of the model after every epoch.
+pub mod machine_learning_algorithms;
+
+use diagram::*;
+
+pub const CANVAS_WIDTH: f32 = 800.0;
+pub const CANVAS_HEIGHT: f32 = 600.0;
+pub const POINT_RADIUS: f32 = 9.0;
+pub const MAX_POINTS: usize = 40;
+pub const MAX_EPOCHS: usize = 200;
+/// How close a click has to be to a point to change it instead of placing a new one.
+const CLICK_DISTANCE: f32 = 14.0;
+
+/// A point of the data. Both of its coordinates are between 0 and 1.
+pub type Point = (f32, f32);
+
+/// The chart of the points, which takes the top of the canvas.
+pub fn data_chart() -> Chart {
+    Chart::new((70.0, 50.0), (CANVAS_WIDTH - 130.0, 330.0), (1.0, 1.0))
+}
+
+/// The chart of the loss after every epoch, below the points.
+pub fn loss_chart(epochs: usize, max_loss: f32) -> Chart {
+    Chart::new(
+        (70.0, 450.0),
+        (CANVAS_WIDTH - 130.0, 100.0),
+        (epochs.max(1) as f32, max_loss.max(f32::EPSILON)),
+    )
+}
+
+/// Draws the axes of the chart of the points, with a hint for placing them if there are none.
+pub fn draw_data_axes(diagram: &mut Diagram, title: &str, is_empty: bool) {
+    let chart = data_chart();
+    chart.draw_axes(diagram, "data", title, ("x", "1"));
+    if is_empty {
+        diagram.add_node(DiagramNode::text(
+            "hint",
+            "Click the chart to place points",
+            chart.point((0.5, 0.5)),
+        ));
+    }
+}
+
+/// The part of the line ax + by + c = 0 that is inside the chart of the points, or `None` if the line misses it.
+pub fn clip_line(a: f32, b: f32, c: f32) -> Option<(Point, Point)> {
+    let inside = |value: f32| (-1e-6..=1.0 + 1e-6).contains(&value);
+    let mut ends: Vec<Point> = vec![];
+    let mut add_end = |end: Point| {
+        let is_new = ends
+            .iter()
+            .all(|other| (other.0 - end.0).abs() + (other.1 - end.1).abs() > 1e-6);
+        if inside(end.0) && inside(end.1) && is_new {
+            ends.push(end);
+        }
+    };
+    if b != 0.0 {
+        for x in [0.0, 1.0] {
+            add_end((x, -(a * x + c) / b));
+        }
+    }
+    if a != 0.0 {
+        for y in [0.0, 1.0] {
+            add_end((-(b * y + c) / a, y));
+        }
+    }
+    (ends.len() >= 2).then(|| (ends[0], ends[1]))
+}
+
+/// Draws the line ax + by + c = 0 across the chart of the points, if it crosses the chart.
+pub fn draw_model_line(diagram: &mut Diagram, (a, b, c): (f32, f32, f32)) -> Option<&mut Figure> {
+    let chart = data_chart();
+    clip_line(a, b, c).map(|(start, end)| chart.draw_line(diagram, &[start, end]))
+}
+
+/// The key of the node of the point at the index.
+pub fn point_key(index: usize) -> String {
+    format!("point-{}", index)
+}
+
+/// The index of the point that a click at the position in the diagram hit, if any.
+pub fn clicked_point(points: &[Point], position: (f32, f32)) -> Option<usize> {
+    let chart = data_chart();
+    points.iter().position(|point| {
+        let (x, y) = chart.point(*point);
+        ((x - position.0).powi(2) + (y - position.1).powi(2)).sqrt() <= CLICK_DISTANCE
+    })
+}
+
+/// The point at a position in the diagram, rounded to two decimals so that it is readable in the steps. `None` if
+/// the position is outside of the chart of the points.
+pub fn clicked_value(position: (f32, f32)) -> Option<Point> {
+    data_chart()
+        .value(position)
+        .map(|(x, y)| ((x * 100.0).round() / 100.0, (y * 100.0).round() / 100.0))
+}
+
+pub fn point_count_input(default: usize) -> Input {
+    Input::Number {
+        label: "Number of points",
+        default: default as i64,
+        min: 2,
+        max: MAX_POINTS as i64,
+    }
+}
+
+pub fn learning_rate_input(default: f64, max: f64) -> Input {
+    Input::Slider {
+        label: "Learning rate",
+        default,
+        min: max / 100.0,
+        max,
+        step: max / 100.0,
+    }
+}
+
+pub fn epochs_input(default: usize) -> Input {
+    Input::Number {
+        label: "Epochs",
+        default: default as i64,
+        min: 1,
+        max: MAX_EPOCHS as i64,
+    }
+}
+
+/// Reads a [`point_count_input`].
+pub fn parse_point_count(value: Option<&Value>) -> Result<usize, String> {
+    let count = value.map(|value| value.number()).unwrap_or(0);
+    if !(2..=MAX_POINTS as i64).contains(&count) {
+        return Err(format!(
+            "The number of points must be between 2 and {}",
+            MAX_POINTS
+        ));
+    }
+    Ok(count as usize)
+}
+
+/// Reads a [`learning_rate_input`] with the same largest value.
+pub fn parse_learning_rate(value: Option<&Value>, max: f64) -> Result<f32, String> {
+    let learning_rate = value.map(|value| value.decimal()).unwrap_or(0.0);
+    if learning_rate <= 0.0 || learning_rate > max {
+        return Err(format!(
+            "The learning rate must be more than 0 and at most {}",
+            max
+        ));
+    }
+    Ok(learning_rate as f32)
+}
+
+/// Reads an [`epochs_input`].
+pub fn parse_epochs(value: Option<&Value>) -> Result<usize, String> {
+    let epochs = value.map(|value| value.number()).unwrap_or(0);
+    if !(1..=MAX_EPOCHS as i64).contains(&epochs) {
+        return Err(format!(
+            "The number of epochs must be between 1 and {}",
+            MAX_EPOCHS
+        ));
+    }
+    Ok(epochs as usize)
+}
+
+/// Checks that there are enough points for a model to learn from.
+pub fn check_points(count: usize, min: usize) -> Result<(), String> {
+    if count < min {
+        return Err(format!(
+            "Place at least {} points by clicking the chart",
+            min
+        ));
+    }
+    Ok(())
+}
+
+/// The number rounded to three decimals, without trailing zeros.
+pub fn format_number(number: f32) -> String {
+    let rounded = (number * 1000.0).round() / 1000.0;
+    // Negative numbers that round to zero would otherwise be shown as "-0"
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        rounded.to_string().replace('-', "−")
+    }
+}
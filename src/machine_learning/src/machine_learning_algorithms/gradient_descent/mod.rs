@@ -0,0 +1,309 @@
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+/// The largest learning rate on the slider, which is large enough for the steps to overshoot on most points.
+const MAX_LEARNING_RATE: f64 = 2.0;
+/// How far the random points are scattered above and below their line.
+const NOISE: f32 = 0.1;
+/// How small the gradient has to get for the line to be considered fitted.
+const TOLERANCE: f32 = 1e-3;
+/// How large the loss can grow before gradient descent is considered to diverge.
+const MAX_LOSS: f32 = 1e6;
+
+/// The slope and the intercept of a line y = wx + b.
+type Line = (f32, f32);
+
+/// What is drawn on top of the points at an epoch.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    line: Option<Line>,
+    /// The least squares line, which is only shown at the end.
+    best: Option<Line>,
+    /// The loss after every epoch so far, starting from the first line.
+    losses: Vec<f32>,
+    epochs: usize,
+}
+
+/// Linear regression with gradient descent. A line y = wx + b is fitted to the points by moving its slope and
+/// intercept a little against the gradient of the mean squared error on every epoch, which uses all of the points
+/// once. The loss after every epoch is charted below the points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradientDescent {
+    points: Vec<Point>,
+}
+impl GradientDescent {
+    pub fn new(points: &[Point]) -> Self {
+        Self {
+            points: points.to_vec(),
+        }
+    }
+    /// The mean squared error of the line on the points.
+    fn loss(&self, (w, b): Line) -> f32 {
+        self.points
+            .iter()
+            .map(|(x, y)| (w * x + b - y).powi(2))
+            .sum::<f32>()
+            / self.points.len().max(1) as f32
+    }
+    /// The derivatives of the loss by the slope and by the intercept.
+    fn gradient(&self, (w, b): Line) -> (f32, f32) {
+        let n = self.points.len().max(1) as f32;
+        self.points.iter().fold((0.0, 0.0), |(dw, db), (x, y)| {
+            let error = w * x + b - y;
+            (dw + 2.0 * error * x / n, db + 2.0 * error / n)
+        })
+    }
+    /// The line with the smallest loss, which is solved directly from the points. `None` if every point has the same
+    /// x, since then the line would be vertical.
+    fn least_squares(&self) -> Option<Line> {
+        let n = self.points.len() as f32;
+        let mean_x = self.points.iter().map(|(x, _)| x).sum::<f32>() / n;
+        let mean_y = self.points.iter().map(|(_, y)| y).sum::<f32>() / n;
+        let (covariance, variance) =
+            self.points
+                .iter()
+                .fold((0.0, 0.0), |(covariance, variance), (x, y)| {
+                    (
+                        covariance + (x - mean_x) * (y - mean_y),
+                        variance + (x - mean_x).powi(2),
+                    )
+                });
+        (variance > 1e-9).then(|| {
+            let w = covariance / variance;
+            (w, mean_y - w * mean_x)
+        })
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let chart = data_chart();
+        draw_data_axes(
+            &mut diagram,
+            "Points and the line y = wx + b",
+            self.points.is_empty(),
+        );
+        if let Some(best) = highlights.best {
+            if let Some(figure) = draw_model_line(&mut diagram, (best.0, -1.0, best.1)) {
+                figure.dashed = true;
+                figure.state = Some(ElementState::Frontier);
+            }
+        }
+        if let Some((w, b)) = highlights.line {
+            // The errors of the points, whose squares are averaged into the loss
+            for (x, y) in self.points.iter() {
+                chart
+                    .draw_line(&mut diagram, &[(*x, *y), (*x, w * x + b)])
+                    .dashed = true;
+            }
+            if let Some(figure) = draw_model_line(&mut diagram, (w, -1.0, b)) {
+                figure.state = Some(ElementState::Selected);
+            }
+        }
+        for (index, point) in self.points.iter().enumerate() {
+            diagram.add_node(DiagramNode::new(
+                point_key(index),
+                "",
+                chart.point(*point),
+                Shape::Circle {
+                    radius: POINT_RADIUS * 0.7,
+                },
+            ));
+        }
+
+        let max_loss = highlights.losses.first().copied().unwrap_or(0.0) * 1.1;
+        let loss_chart = loss_chart(highlights.epochs, max_loss);
+        loss_chart.draw_axes(
+            &mut diagram,
+            "loss",
+            "Mean squared error after every epoch",
+            ("epoch", format_number(max_loss)),
+        );
+        loss_chart
+            .draw_line(
+                &mut diagram,
+                &highlights
+                    .losses
+                    .iter()
+                    .enumerate()
+                    .map(|(epoch, loss)| (epoch as f32, *loss))
+                    .collect::<Vec<(f32, f32)>>(),
+            )
+            .state = Some(ElementState::Selected);
+        diagram
+    }
+    fn push(&self, description: String, highlights: &Highlights, steps: &mut DiagramSteps) {
+        let step = steps.push(description, self.draw(highlights));
+        if let Some(line) = highlights.line {
+            step.lists = vec![StepList::new(
+                "Line",
+                vec![
+                    line_text(line),
+                    format!("Error {}", format_number(self.loss(line))),
+                ],
+            )];
+        }
+    }
+    fn descend(&self, learning_rate: f32, epochs: usize, steps: &mut DiagramSteps) {
+        let mut line = (0.0, 0.0);
+        let mut highlights = Highlights {
+            line: Some(line),
+            best: None,
+            losses: vec![self.loss(line)],
+            epochs,
+        };
+        self.push(
+            format!(
+                "Start from the line {}, whose mean squared error on the points is {}. On every epoch, the slope and the intercept move against the gradient of the error, by the learning rate {} times the gradient",
+                line_text(line),
+                format_number(self.loss(line)),
+                learning_rate
+            ),
+            &highlights,
+            steps,
+        );
+        let mut converged = false;
+        let mut diverged = false;
+        for epoch in 1..=epochs {
+            let (dw, db) = self.gradient(line);
+            line = (line.0 - learning_rate * dw, line.1 - learning_rate * db);
+            let loss = self.loss(line);
+            diverged = !loss.is_finite() || loss > MAX_LOSS;
+            if diverged {
+                break;
+            }
+            highlights.line = Some(line);
+            highlights.losses.push(loss);
+            self.push(
+                format!(
+                    "Epoch {}: the gradient is ({}, {}), so the line moves to {} and the error is {}",
+                    epoch,
+                    format_number(dw),
+                    format_number(db),
+                    line_text(line),
+                    format_number(loss)
+                ),
+                &highlights,
+                steps,
+            );
+            let (dw, db) = self.gradient(line);
+            if (dw * dw + db * db).sqrt() < TOLERANCE {
+                converged = true;
+                break;
+            }
+        }
+
+        highlights.best = self.least_squares();
+        let first_loss = highlights.losses[0];
+        let last_loss = *highlights.losses.last().unwrap();
+        let description = match highlights.best {
+            _ if diverged || last_loss > first_loss => format!(
+                "The error grew from {} to {}, since the steps overshoot the bottom of the error and land higher up on the other side. Gradient descent diverges, so a learning rate smaller than {} is needed",
+                format_number(first_loss),
+                if diverged { "more than a million".to_string() } else { format_number(last_loss) },
+                learning_rate
+            ),
+            Some(best) if converged => format!(
+                "The gradient is about zero, so the line {} is at the bottom of the error. It is almost the least squares line {}, which can also be solved directly from the points",
+                line_text(highlights.line.unwrap()),
+                line_text(best)
+            ),
+            Some(best) => format!(
+                "After {}, the error is {}, while the least squares line {} has the smallest error {}. More epochs or a larger learning rate would get closer to it",
+                plural(epochs, "epoch"),
+                format_number(last_loss),
+                line_text(best),
+                format_number(self.loss(best))
+            ),
+            None => format!(
+                "After {}, the error is {}. Every point has the same x, so there are many lines with the smallest error",
+                plural(highlights.losses.len() - 1, "epoch"),
+                format_number(last_loss)
+            ),
+        };
+        self.push(description, &highlights, steps);
+    }
+    fn random_points(count: usize) -> Vec<Point> {
+        let mut rng = rand::thread_rng();
+        let slope = rng.gen_range(-0.8..=0.8);
+        let intercept = 0.5 - slope * 0.5 + rng.gen_range(-0.1..=0.1);
+        (0..count)
+            .map(|_| {
+                let x: f32 = rng.gen_range(0.05..=0.95);
+                // The sum of two uniform values is more likely to be close to the line than far from it
+                let noise = (rng.gen_range(-1.0..=1.0) + rng.gen_range(-1.0..=1.0)) * NOISE / 2.0;
+                let y: f32 = (slope * x + intercept + noise).clamp(0.02, 0.98);
+                ((x * 100.0).round() / 100.0, (y * 100.0).round() / 100.0)
+            })
+            .collect()
+    }
+}
+impl Visualizer for GradientDescent {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Run gradient descent",
+                vec![
+                    learning_rate_input(0.5, MAX_LEARNING_RATE),
+                    epochs_input(40),
+                ],
+            ),
+            Operation::new("Random points", vec![point_count_input(20)]),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Run gradient descent" => {
+                check_points(self.points.len(), 2)?;
+                let learning_rate = parse_learning_rate(values.first(), MAX_LEARNING_RATE)?;
+                let epochs = parse_epochs(values.get(1))?;
+                self.descend(learning_rate, epochs, &mut steps);
+            }
+            "Random points" => {
+                self.points = Self::random_points(parse_point_count(values.first())?);
+                steps.push(
+                    format!(
+                        "Scatter {} around a random line",
+                        plural(self.points.len(), "point")
+                    ),
+                    self.diagram(),
+                );
+            }
+            "Clear" => {
+                self.points.clear();
+                steps.push("Remove all points".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+    fn clickable(&self) -> bool {
+        true
+    }
+    fn click(&mut self, position: (f32, f32)) -> Option<DiagramSteps> {
+        if let Some(index) = clicked_point(&self.points, position) {
+            self.points.remove(index);
+        } else if self.points.len() < MAX_POINTS {
+            self.points.push(clicked_value(position)?);
+        } else {
+            return None;
+        }
+        Some(DiagramSteps::new())
+    }
+}
+
+/// The line as an equation like "y = 0.5x − 0.25".
+fn line_text((w, b): Line) -> String {
+    let sign = if b < 0.0 { "−" } else { "+" };
+    format!(
+        "y = {}x {} {}",
+        format_number(w),
+        sign,
+        format_number(b.abs())
+    )
+}
@@ -0,0 +1,6 @@
+//! A collection of models that learn from points, trained one epoch at a time.
+mod gradient_descent;
+mod perceptron;
+
+pub use gradient_descent::GradientDescent;
+pub use perceptron::Perceptron;
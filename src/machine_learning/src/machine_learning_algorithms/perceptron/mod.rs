@@ -0,0 +1,322 @@
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const MAX_LEARNING_RATE: f64 = 1.0;
+/// The weights that the perceptron starts from, which make the boundary the horizontal line through the middle of
+/// the chart with the positive class above it.
+const START_WEIGHTS: Weights = (0.0, 1.0, -0.5);
+/// How far random points of separable classes are kept from the line that separates them.
+const RANDOM_MARGIN: f32 = 0.05;
+/// The share of random points of overlapping classes that are put into the wrong class.
+const RANDOM_FLIP_PROBABILITY: f64 = 0.15;
+const CLASSES: [&str; 2] = ["Separable", "Overlapping"];
+
+/// The weights of x and y and the bias, which classify a point as positive if w₁x + w₂y + b > 0.
+type Weights = (f32, f32, f32);
+
+/// What is drawn on top of the points at an epoch.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    weights: Option<Weights>,
+    /// The boundary before the epoch.
+    previous: Option<Weights>,
+    /// The points that were misclassified during the epoch.
+    mistakes: Vec<usize>,
+    /// The number of mistakes on every epoch so far.
+    mistake_counts: Vec<usize>,
+    epochs: usize,
+}
+
+/// The perceptron, which learns a line that separates two classes of points. It goes through the points one at a
+/// time, and whenever it classifies a point wrong, it moves its boundary towards the point by adding the point to its
+/// weights, scaled by the learning rate. If the classes can be separated by a line, it always finds one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Perceptron {
+    /// The points and whether they are in the positive class.
+    points: Vec<(Point, bool)>,
+}
+impl Perceptron {
+    pub fn new(points: &[(Point, bool)]) -> Self {
+        Self {
+            points: points.to_vec(),
+        }
+    }
+    fn activation((w1, w2, b): Weights, (x, y): Point) -> f32 {
+        w1 * x + w2 * y + b
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let chart = data_chart();
+        draw_data_axes(
+            &mut diagram,
+            "Points of the positive and negative class, and the boundary w₁x + w₂y + b = 0",
+            self.points.is_empty(),
+        );
+        if let Some(previous) = highlights.previous {
+            if let Some(figure) = draw_model_line(&mut diagram, previous) {
+                figure.dashed = true;
+                figure.state = Some(ElementState::Frontier);
+            }
+        }
+        if let Some(weights) = highlights.weights {
+            if let Some(figure) = draw_model_line(&mut diagram, weights) {
+                figure.state = Some(ElementState::Selected);
+            }
+            // Shows which side of the boundary is positive
+            if let Some((start, end)) = clip_line(weights.0, weights.1, weights.2) {
+                let length = (weights.0.powi(2) + weights.1.powi(2)).sqrt();
+                let middle = ((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0);
+                diagram.add_node(DiagramNode::text(
+                    "positive-side",
+                    "+",
+                    chart.point((
+                        middle.0 + weights.0 / length * 0.06,
+                        middle.1 + weights.1 / length * 0.06,
+                    )),
+                ));
+            }
+        }
+        for (index, (point, positive)) in self.points.iter().enumerate() {
+            let mut node = DiagramNode::new(
+                point_key(index),
+                if *positive { "+" } else { "−" },
+                chart.point(*point),
+                Shape::Circle {
+                    radius: POINT_RADIUS,
+                },
+            )
+            .with_fill(Fill::Group(if *positive { 0 } else { 1 }));
+            if highlights.mistakes.contains(&index) {
+                node.state = Some(ElementState::Rejected);
+            }
+            diagram.add_node(node);
+        }
+
+        let max_mistakes = self.points.len().max(1) as f32;
+        let mistake_chart = loss_chart(highlights.epochs, max_mistakes);
+        mistake_chart.draw_axes(
+            &mut diagram,
+            "loss",
+            "Misclassified points during every epoch",
+            ("epoch", max_mistakes),
+        );
+        mistake_chart
+            .draw_line(
+                &mut diagram,
+                &highlights
+                    .mistake_counts
+                    .iter()
+                    .enumerate()
+                    .map(|(epoch, count)| (epoch as f32 + 1.0, *count as f32))
+                    .collect::<Vec<(f32, f32)>>(),
+            )
+            .state = Some(ElementState::Selected);
+        diagram
+    }
+    fn push(&self, description: String, highlights: &Highlights, steps: &mut DiagramSteps) {
+        let step = steps.push(description, self.draw(highlights));
+        if let Some((w1, w2, b)) = highlights.weights {
+            step.lists = vec![StepList::new(
+                "Weights",
+                vec![
+                    format!("w₁ = {}", format_number(w1)),
+                    format!("w₂ = {}", format_number(w2)),
+                    format!("b = {}", format_number(b)),
+                ],
+            )];
+        }
+    }
+    fn train(&self, learning_rate: f32, epochs: usize, steps: &mut DiagramSteps) {
+        let mut weights = START_WEIGHTS;
+        let mut highlights = Highlights {
+            weights: Some(weights),
+            epochs,
+            ..Default::default()
+        };
+        self.push(
+            format!(
+                "Start from the horizontal boundary through the middle, with the positive class above it. On every epoch, go through the points in order, and add every misclassified point to the weights, scaled by the learning rate {} and negated for the negative class",
+                learning_rate
+            ),
+            &highlights,
+            steps,
+        );
+        for epoch in 1..=epochs {
+            let previous = weights;
+            let mut mistakes = vec![];
+            for (index, (point, positive)) in self.points.iter().enumerate() {
+                let sign = if *positive { 1.0 } else { -1.0 };
+                if sign * Self::activation(weights, *point) <= 0.0 {
+                    mistakes.push(index);
+                    weights = (
+                        weights.0 + learning_rate * sign * point.0,
+                        weights.1 + learning_rate * sign * point.1,
+                        weights.2 + learning_rate * sign,
+                    );
+                }
+            }
+            highlights.previous = (!mistakes.is_empty()).then_some(previous);
+            highlights.weights = Some(weights);
+            highlights.mistake_counts.push(mistakes.len());
+            highlights.mistakes = mistakes;
+            let description = if highlights.mistakes.is_empty() {
+                format!(
+                    "Epoch {}: every point is on its side of the boundary, so the weights don't change and the perceptron has converged",
+                    epoch
+                )
+            } else {
+                format!(
+                    "Epoch {}: {} moved the boundary towards {}",
+                    epoch,
+                    if highlights.mistakes.len() == 1 {
+                        "1 misclassified point".to_string()
+                    } else {
+                        format!("{} misclassified points", highlights.mistakes.len())
+                    },
+                    if highlights.mistakes.len() == 1 {
+                        "itself"
+                    } else {
+                        "themselves"
+                    }
+                )
+            };
+            self.push(description, &highlights, steps);
+            if highlights.mistakes.is_empty() {
+                return;
+            }
+        }
+
+        let wrong = self
+            .points
+            .iter()
+            .filter(|(point, positive)| (Self::activation(weights, *point) > 0.0) != *positive)
+            .count();
+        highlights.previous = None;
+        highlights.mistakes.clear();
+        self.push(
+            format!(
+                "After {}, {} still on the wrong side. If the classes can't be separated by a line, the perceptron never stops moving its boundary, but more epochs would find a separating line if there is one",
+                plural(epochs, "epoch"),
+                if wrong == 1 {
+                    "1 point is".to_string()
+                } else {
+                    format!("{} points are", wrong)
+                }
+            ),
+            &highlights,
+            steps,
+        );
+    }
+    /// Points on both sides of a random line through the middle of the chart.
+    fn random_points(count: usize, overlapping: bool) -> Vec<(Point, bool)> {
+        let mut rng = rand::thread_rng();
+        let angle = rng.gen_range(0.0..std::f32::consts::PI);
+        let (nx, ny) = (angle.cos(), angle.sin());
+        let center = (rng.gen_range(0.4..=0.6), rng.gen_range(0.4..=0.6));
+        let mut points = vec![];
+        for _ in 0..count * 20 {
+            if points.len() == count {
+                break;
+            }
+            let point: Point = (
+                (rng.gen_range(0.05..=0.95_f32) * 100.0).round() / 100.0,
+                (rng.gen_range(0.05..=0.95_f32) * 100.0).round() / 100.0,
+            );
+            let distance = (point.0 - center.0) * nx + (point.1 - center.1) * ny;
+            if !overlapping && distance.abs() < RANDOM_MARGIN {
+                continue;
+            }
+            let flipped = overlapping && rng.gen_bool(RANDOM_FLIP_PROBABILITY);
+            points.push((point, (distance > 0.0) != flipped));
+        }
+        points
+    }
+}
+impl Visualizer for Perceptron {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Train perceptron",
+                vec![
+                    learning_rate_input(0.1, MAX_LEARNING_RATE),
+                    epochs_input(20),
+                ],
+            ),
+            Operation::new(
+                "Random points",
+                vec![
+                    point_count_input(20),
+                    Input::Choice {
+                        label: "Classes",
+                        choices: CLASSES.iter().map(|classes| classes.to_string()).collect(),
+                    },
+                ],
+            ),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Train perceptron" => {
+                check_points(self.points.len(), 1)?;
+                let learning_rate = parse_learning_rate(values.first(), MAX_LEARNING_RATE)?;
+                let epochs = parse_epochs(values.get(1))?;
+                self.train(learning_rate, epochs, &mut steps);
+            }
+            "Random points" => {
+                let count = parse_point_count(values.first())?;
+                let overlapping =
+                    values.get(1).map(|value| value.text()).as_deref() == Some(CLASSES[1]);
+                self.points = Self::random_points(count, overlapping);
+                steps.push(
+                    format!(
+                        "Place {} on both sides of a random line{}",
+                        plural(self.points.len(), "point"),
+                        if overlapping {
+                            ", and put some of them into the wrong class so that no line separates the classes"
+                        } else {
+                            ""
+                        }
+                    ),
+                    self.diagram(),
+                );
+            }
+            "Clear" => {
+                self.points.clear();
+                steps.push("Remove all points".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+    fn clickable(&self) -> bool {
+        true
+    }
+    /// Clicking places a positive point. Clicking a positive point makes it negative, and clicking a negative point
+    /// removes it.
+    fn click(&mut self, position: (f32, f32)) -> Option<DiagramSteps> {
+        let points = self
+            .points
+            .iter()
+            .map(|(point, _)| *point)
+            .collect::<Vec<Point>>();
+        if let Some(index) = clicked_point(&points, position) {
+            if self.points[index].1 {
+                self.points[index].1 = false;
+            } else {
+                self.points.remove(index);
+            }
+        } else if self.points.len() < MAX_POINTS {
+            self.points.push((clicked_value(position)?, true));
+        } else {
+            return None;
+        }
+        Some(DiagramSteps::new())
+    }
+}
@@ -1,5 +1,20 @@
+extern crate backtracking;
+extern crate cellular_automata;
+extern crate data_structures;
+extern crate diagram;
+extern crate dynamic_programming;
+extern crate geometry;
+extern crate graph;
+extern crate greedy;
+extern crate machine_learning;
+extern crate number_theory;
+extern crate numerical;
+extern crate optimization;
 extern crate pathfinding;
+extern crate randomized;
+extern crate searching;
 extern crate sorting;
+extern crate strings;
 
 mod components;
 mod hooks;
@@ -27,6 +42,62 @@ enum Route {
     Pathfinding,
     #[at("/pathfinding/:algorithm")]
     PathfindingAlgorithm,
+    #[at("/graph")]
+    Graph,
+    #[at("/graph/:algorithm")]
+    GraphAlgorithm,
+    #[at("/data-structures")]
+    DataStructures,
+    #[at("/data-structures/:structure")]
+    DataStructure,
+    #[at("/searching")]
+    Searching,
+    #[at("/searching/:algorithm")]
+    SearchingAlgorithm,
+    #[at("/strings")]
+    Strings,
+    #[at("/strings/:algorithm")]
+    StringAlgorithm,
+    #[at("/geometry")]
+    Geometry,
+    #[at("/geometry/:algorithm")]
+    GeometryAlgorithm,
+    #[at("/number-theory")]
+    NumberTheory,
+    #[at("/number-theory/:algorithm")]
+    NumberTheoryAlgorithm,
+    #[at("/numerical")]
+    Numerical,
+    #[at("/numerical/:algorithm")]
+    NumericalAlgorithm,
+    #[at("/dynamic-programming")]
+    DynamicProgramming,
+    #[at("/dynamic-programming/:algorithm")]
+    DynamicProgrammingAlgorithm,
+    #[at("/backtracking")]
+    Backtracking,
+    #[at("/backtracking/:algorithm")]
+    BacktrackingAlgorithm,
+    #[at("/greedy")]
+    Greedy,
+    #[at("/greedy/:algorithm")]
+    GreedyAlgorithm,
+    #[at("/cellular-automata")]
+    CellularAutomata,
+    #[at("/cellular-automata/:automaton")]
+    CellularAutomaton,
+    #[at("/machine-learning")]
+    MachineLearning,
+    #[at("/machine-learning/:algorithm")]
+    MachineLearningAlgorithm,
+    #[at("/optimization")]
+    Optimization,
+    #[at("/optimization/:algorithm")]
+    OptimizationAlgorithm,
+    #[at("/randomized")]
+    Randomized,
+    #[at("/randomized/:algorithm")]
+    RandomizedAlgorithm,
 }
 
 fn switch(route: Route) -> Html {
@@ -46,6 +117,90 @@ fn switch(route: Route) -> Html {
         Route::PathfindingAlgorithm => html! {
             <Switch<pages::pathfinding::PathfindingRoute> render={pages::pathfinding::switch_pathfinding} />
         },
+        Route::Graph => html! {
+            <Switch<pages::graph::GraphRoute> render={pages::graph::switch_graph} />
+        },
+        Route::GraphAlgorithm => html! {
+            <Switch<pages::graph::GraphRoute> render={pages::graph::switch_graph} />
+        },
+        Route::DataStructures => html! {
+            <Switch<pages::data_structures::DataStructuresRoute> render={pages::data_structures::switch_data_structures} />
+        },
+        Route::DataStructure => html! {
+            <Switch<pages::data_structures::DataStructuresRoute> render={pages::data_structures::switch_data_structures} />
+        },
+        Route::Searching => html! {
+            <Switch<pages::searching::SearchingRoute> render={pages::searching::switch_searching} />
+        },
+        Route::SearchingAlgorithm => html! {
+            <Switch<pages::searching::SearchingRoute> render={pages::searching::switch_searching} />
+        },
+        Route::Strings => html! {
+            <Switch<pages::strings::StringsRoute> render={pages::strings::switch_strings} />
+        },
+        Route::StringAlgorithm => html! {
+            <Switch<pages::strings::StringsRoute> render={pages::strings::switch_strings} />
+        },
+        Route::Geometry => html! {
+            <Switch<pages::geometry::GeometryRoute> render={pages::geometry::switch_geometry} />
+        },
+        Route::GeometryAlgorithm => html! {
+            <Switch<pages::geometry::GeometryRoute> render={pages::geometry::switch_geometry} />
+        },
+        Route::NumberTheory => html! {
+            <Switch<pages::number_theory::NumberTheoryRoute> render={pages::number_theory::switch_number_theory} />
+        },
+        Route::NumberTheoryAlgorithm => html! {
+            <Switch<pages::number_theory::NumberTheoryRoute> render={pages::number_theory::switch_number_theory} />
+        },
+        Route::Numerical => html! {
+            <Switch<pages::numerical::NumericalRoute> render={pages::numerical::switch_numerical} />
+        },
+        Route::NumericalAlgorithm => html! {
+            <Switch<pages::numerical::NumericalRoute> render={pages::numerical::switch_numerical} />
+        },
+        Route::DynamicProgramming => html! {
+            <Switch<pages::dynamic_programming::DynamicProgrammingRoute> render={pages::dynamic_programming::switch_dynamic_programming} />
+        },
+        Route::DynamicProgrammingAlgorithm => html! {
+            <Switch<pages::dynamic_programming::DynamicProgrammingRoute> render={pages::dynamic_programming::switch_dynamic_programming} />
+        },
+        Route::Backtracking => html! {
+            <Switch<pages::backtracking::BacktrackingRoute> render={pages::backtracking::switch_backtracking} />
+        },
+        Route::BacktrackingAlgorithm => html! {
+            <Switch<pages::backtracking::BacktrackingRoute> render={pages::backtracking::switch_backtracking} />
+        },
+        Route::Greedy => html! {
+            <Switch<pages::greedy::GreedyRoute> render={pages::greedy::switch_greedy} />
+        },
+        Route::GreedyAlgorithm => html! {
+            <Switch<pages::greedy::GreedyRoute> render={pages::greedy::switch_greedy} />
+        },
+        Route::CellularAutomata => html! {
+            <Switch<pages::cellular_automata::CellularAutomataRoute> render={pages::cellular_automata::switch_cellular_automata} />
+        },
+        Route::CellularAutomaton => html! {
+            <Switch<pages::cellular_automata::CellularAutomataRoute> render={pages::cellular_automata::switch_cellular_automata} />
+        },
+        Route::MachineLearning => html! {
+            <Switch<pages::machine_learning::MachineLearningRoute> render={pages::machine_learning::switch_machine_learning} />
+        },
+        Route::MachineLearningAlgorithm => html! {
+            <Switch<pages::machine_learning::MachineLearningRoute> render={pages::machine_learning::switch_machine_learning} />
+        },
+        Route::Optimization => html! {
+            <Switch<pages::optimization::OptimizationRoute> render={pages::optimization::switch_optimization} />
+        },
+        Route::OptimizationAlgorithm => html! {
+            <Switch<pages::optimization::OptimizationRoute> render={pages::optimization::switch_optimization} />
+        },
+        Route::Randomized => html! {
+            <Switch<pages::randomized::RandomizedRoute> render={pages::randomized::switch_randomized} />
+        },
+        Route::RandomizedAlgorithm => html! {
+            <Switch<pages::randomized::RandomizedRoute> render={pages::randomized::switch_randomized} />
+        },
     }
 }
 
@@ -102,13 +257,35 @@ fn App() -> Html {
         <BrowserRouter>
             <ContextProvider<ColorScheme> context={color_scheme}>
                 <div class="top-bar">
-                    <div class="page-links">
+                    <nav class="page-links" aria-label="Pages">
                         <Link<Route> to={Route::Home}>{ "Home" }</Link<Route>>
                         <Link<Route> to={Route::Sorting}>{ "Sorting" }</Link<Route>>
                         <Link<Route> to={Route::Pathfinding}>{ "Pathfinding" }</Link<Route>>
-                    </div>
+                        <Link<Route> to={Route::Graph}>{ "Graphs" }</Link<Route>>
+                        <Link<Route> to={Route::DataStructures}>{ "Data structures" }</Link<Route>>
+                        <Link<Route> to={Route::Searching}>{ "Searching" }</Link<Route>>
+                        <Link<Route> to={Route::Strings}>{ "Strings" }</Link<Route>>
+                        <Link<Route> to={Route::Geometry}>{ "Geometry" }</Link<Route>>
+                        <Link<Route> to={Route::NumberTheory}>{ "Number theory" }</Link<Route>>
+                        <Link<Route> to={Route::Numerical}>{ "Numerical" }</Link<Route>>
+                        <Link<Route> to={Route::DynamicProgramming}>{ "Dynamic programming" }</Link<Route>>
+                        <Link<Route> to={Route::Backtracking}>{ "Backtracking" }</Link<Route>>
+                        <Link<Route> to={Route::Greedy}>{ "Greedy" }</Link<Route>>
+                        <Link<Route> to={Route::CellularAutomata}>{ "Cellular automata" }</Link<Route>>
+                        <Link<Route> to={Route::MachineLearning}>{ "Machine learning" }</Link<Route>>
+                        <Link<Route> to={Route::Optimization}>{ "Optimization" }</Link<Route>>
+                        <Link<Route> to={Route::Randomized}>{ "Randomized" }</Link<Route>>
+                    </nav>
                     <div class="other-links">
-                        <button onclick={toggle_theme}>{
+                        <button
+                            onclick={toggle_theme}
+                            aria-label={
+                                match color_scheme {
+                                    ColorScheme::Light => "Switch to dark theme",
+                                    ColorScheme::Dark => "Switch to light theme",
+                                }
+                            }
+                        >{
                             match color_scheme {
                                 ColorScheme::Light => "☀️",
                                 ColorScheme::Dark => "🌙"
@@ -0,0 +1,5 @@
+//! This crate contains my implementations of number theory algorithms, like the sieve of Eratosthenes.
+//! Each algorithm records a diagram of the numbers it works on at every step, and charts of what it finds.
+pub mod number_theory_algorithms;
+
+pub const CANVAS_WIDTH: f32 = 800.0;
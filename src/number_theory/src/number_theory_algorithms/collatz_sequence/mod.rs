@@ -0,0 +1,257 @@
+use diagram::*;
+
+use crate::*;
+
+const CANVAS_HEIGHT: f32 = 500.0;
+const MAX_START: u64 = 1_000_000;
+const MAX_RANGE: u64 = 200;
+const TERM_RADIUS: f32 = 5.0;
+
+/// The Collatz sequence, where an even number is halved and an odd number `n` is replaced with `3n + 1`. It is
+/// conjectured to reach 1 from every start, and the terms rise and fall on the way like hailstones in a cloud. The
+/// sequence of one start is plotted term by term, and the steps that a range of starts take are charted as bars.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollatzSequence {
+    start: u64,
+    range: u64,
+}
+impl CollatzSequence {
+    pub fn new(start: u64, range: u64) -> Self {
+        Self { start, range }
+    }
+    fn chart(max: (f32, f32)) -> Chart {
+        Chart::new(
+            (80.0, 50.0),
+            (CANVAS_WIDTH - 140.0, CANVAS_HEIGHT - 110.0),
+            max,
+        )
+    }
+    /// Plots the first terms of the sequence with the current one highlighted, on axes that fit all of it.
+    fn draw_sequence(&self, sequence: &[u64], terms: usize, peak: bool) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let highest = sequence.iter().max().copied().unwrap_or(1);
+        let chart = Self::chart(((sequence.len() - 1).max(1) as f32, highest as f32));
+        chart.draw_axes(
+            &mut diagram,
+            "chart",
+            format!("Hailstone sequence of {}", self.start),
+            (sequence.len() - 1, highest),
+        );
+        let points = sequence[..terms]
+            .iter()
+            .enumerate()
+            .map(|(i, term)| (i as f32, *term as f32))
+            .collect::<Vec<(f32, f32)>>();
+        chart.draw_line(&mut diagram, &points);
+        if let Some(i) = terms.checked_sub(1) {
+            let mut node = DiagramNode::new(
+                "term",
+                "",
+                chart.point(points[i]),
+                Shape::Circle {
+                    radius: TERM_RADIUS,
+                },
+            )
+            .with_note(sequence[i])
+            .with_state(ElementState::Active);
+            if peak {
+                node.state = Some(ElementState::Selected);
+            }
+            diagram.add_node(node);
+        }
+        if peak {
+            let i = sequence.iter().position(|term| *term == highest).unwrap();
+            diagram.add_node(
+                DiagramNode::new(
+                    "peak",
+                    "",
+                    chart.point((i as f32, highest as f32)),
+                    Shape::Circle {
+                        radius: TERM_RADIUS,
+                    },
+                )
+                .with_note(format!("peak {}", highest))
+                .with_state(ElementState::Selected),
+            );
+        }
+        diagram
+    }
+    /// Charts the steps that the starts from 1 up take, with the longest ones so far highlighted.
+    fn draw_steps(&self, steps: &[usize], current: Option<usize>) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let longest = steps.iter().max().copied().unwrap_or(1).max(1);
+        let chart = Self::chart((self.range as f32 + 1.0, longest as f32));
+        chart.draw_axes(
+            &mut diagram,
+            "chart",
+            format!("Steps to reach 1 from 1 to {}", self.range),
+            (self.range, longest),
+        );
+        let mut record = None;
+        for (i, count) in steps.iter().enumerate() {
+            let state = if Some(i) == current {
+                Some(ElementState::Active)
+            } else if record.is_none_or(|record| *count > record) {
+                Some(ElementState::Selected)
+            } else {
+                None
+            };
+            record = record.max(Some(*count));
+            chart
+                .draw_bar(&mut diagram, ((i + 1) as f32, *count as f32), 0.7)
+                .state = state;
+        }
+        if let Some(i) = current {
+            diagram.add_node(DiagramNode::text(
+                "count",
+                steps[i],
+                chart.point(((i + 1) as f32, steps[i] as f32 + longest as f32 * 0.05)),
+            ));
+        }
+        diagram
+    }
+    fn hailstone_sequence(&self, steps: &mut DiagramSteps) {
+        let sequence = collatz_sequence(self.start);
+        let list = |terms: usize| {
+            vec![StepList::new(
+                "Sequence",
+                sequence[..terms]
+                    .iter()
+                    .map(|term| term.to_string())
+                    .collect(),
+            )]
+        };
+        let step = steps.push(
+            format!("Start the sequence from {}", self.start),
+            self.draw_sequence(&sequence, 1, false),
+        );
+        step.lists = list(1);
+        for i in 1..sequence.len() {
+            let n = sequence[i - 1];
+            let description = if n.is_multiple_of(2) {
+                format!("{} is even, so halve it: {} / 2 = {}", n, n, sequence[i])
+            } else {
+                format!(
+                    "{} is odd, so triple it and add one: 3 × {} + 1 = {}",
+                    n, n, sequence[i]
+                )
+            };
+            let step = steps.push(description, self.draw_sequence(&sequence, i + 1, false));
+            step.lists = list(i + 1);
+        }
+        let step = steps.push(
+            format!(
+                "The sequence reached 1 after {}, and its highest term was {}. From 1 it would only loop through 4, 2 and 1",
+                plural(sequence.len() - 1, "step"),
+                sequence.iter().max().unwrap()
+            ),
+            self.draw_sequence(&sequence, sequence.len(), true),
+        );
+        step.lists = list(sequence.len());
+    }
+    fn stopping_times(&self, steps: &mut DiagramSteps) {
+        let mut counts = vec![];
+        let mut records = vec![];
+        for start in 1..=self.range {
+            let count = collatz_sequence(start).len() - 1;
+            counts.push(count);
+            let record = records.last().is_none_or(|(_, longest)| count > *longest);
+            if record {
+                records.push((start, count));
+            }
+            let step = steps.push(
+                format!(
+                    "{} reaches 1 after {}{}",
+                    start,
+                    plural(count, "step"),
+                    if record && start > 1 {
+                        ", which is the longest so far"
+                    } else {
+                        ""
+                    }
+                ),
+                self.draw_steps(&counts, Some(counts.len() - 1)),
+            );
+            step.lists = vec![records_list(&records)];
+        }
+        let (start, longest) = *records.last().unwrap();
+        let step = steps.push(
+            format!(
+                "Every start from 1 to {} reaches 1. {} takes the longest, {}, and the steps jump around without a clear pattern",
+                self.range,
+                start,
+                plural(longest, "step")
+            ),
+            self.draw_steps(&counts, None),
+        );
+        step.lists = vec![records_list(&records)];
+    }
+}
+impl Visualizer for CollatzSequence {
+    fn diagram(&self) -> Diagram {
+        let sequence = collatz_sequence(self.start);
+        self.draw_sequence(&sequence, sequence.len(), false)
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Hailstone sequence",
+                vec![Input::Number {
+                    label: "Start",
+                    default: self.start as i64,
+                    min: 1,
+                    max: MAX_START as i64,
+                }],
+            ),
+            Operation::new(
+                "Steps to reach 1",
+                vec![Input::Number {
+                    label: "Last start",
+                    default: self.range as i64,
+                    min: 1,
+                    max: MAX_RANGE as i64,
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Hailstone sequence" => {
+                self.start = parse_number(values.first(), "start", 1, MAX_START)?;
+                self.hailstone_sequence(&mut steps);
+            }
+            "Steps to reach 1" => {
+                self.range = parse_number(values.first(), "last start", 1, MAX_RANGE)?;
+                self.stopping_times(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The terms of the sequence from the start until it reaches 1.
+fn collatz_sequence(start: u64) -> Vec<u64> {
+    let mut sequence = vec![start];
+    let mut n = start;
+    while n != 1 {
+        n = if n.is_multiple_of(2) {
+            n / 2
+        } else {
+            3 * n + 1
+        };
+        sequence.push(n);
+    }
+    sequence
+}
+
+fn records_list(records: &[(u64, usize)]) -> StepList {
+    StepList::new(
+        "Longest so far",
+        records
+            .iter()
+            .map(|(start, count)| format!("{}: {}", start, plural(*count, "step")))
+            .collect(),
+    )
+}
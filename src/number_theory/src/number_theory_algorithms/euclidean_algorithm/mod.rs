@@ -0,0 +1,438 @@
+use diagram::*;
+
+use crate::*;
+
+const MAX_NUMBER: u64 = 1000;
+/// The part of the diagram that the rectangle is drawn in, by its top left corner and its size.
+const RECTANGLE_AREA: ((f32, f32), (f32, f32)) = ((20.0, 40.0), (420.0, 380.0));
+/// How big a square has to be on the screen for its side to be written on it.
+const MIN_LABELED_SIDE: f32 = 26.0;
+const HEADERS: [&str; 6] = ["a", "b", "q", "r", "x", "y"];
+
+/// A square by its top left corner and its side.
+type Square = (f32, f32, f32);
+/// A rectangle by its top left corner, its width and its height.
+type Rectangle = (f32, f32, f32, f32);
+
+/// A division `a = q × b + r` of the algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Division {
+    a: u64,
+    b: u64,
+    q: u64,
+    r: u64,
+}
+
+/// The states of the rectangle and the table at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    /// How many of the divisions have cut squares off the rectangle and are in the table.
+    divisions: usize,
+    /// True if the last row of the table, where `b` is zero, is shown.
+    last_row: bool,
+    /// The Bézout coefficients of the rows from the bottom up.
+    coefficients: Vec<(i64, i64)>,
+    /// The row that is being filled in and the row that it is computed from.
+    active_row: Option<usize>,
+    frontier_row: Option<usize>,
+    /// True if the algorithm has finished, so the squares that tile the rest of the rectangle are the answer.
+    done: bool,
+}
+
+/// The greatest common divisor of two numbers, found by replacing the larger of them with the remainder of dividing
+/// it by the smaller until the remainder is zero. A rectangle with the numbers as its sides shows it geometrically:
+/// every division cuts squares off it until squares with the gcd as their side tile the rest of it exactly. The
+/// extended version also finds the Bézout coefficients `x` and `y` for which `ax + by = gcd(a, b)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EuclideanAlgorithm {
+    a: u64,
+    b: u64,
+    divisions: Vec<Division>,
+}
+impl EuclideanAlgorithm {
+    pub fn new(a: u64, b: u64) -> Self {
+        Self {
+            a,
+            b,
+            divisions: divisions(a, b),
+        }
+    }
+    fn set_numbers(&mut self, values: &[Value]) -> Result<(), String> {
+        let a = parse_number(values.first(), "number a", 1, MAX_NUMBER)?;
+        let b = parse_number(values.get(1), "number b", 1, MAX_NUMBER)?;
+        *self = Self::new(a, b);
+        Ok(())
+    }
+    fn gcd(&self) -> u64 {
+        self.divisions.last().map(|division| division.b).unwrap()
+    }
+    /// Cuts the squares of the divisions off the rectangle and returns them with the rectangle that is left, in the
+    /// units of the numbers.
+    fn cut_squares(&self, divisions: usize) -> (Vec<Vec<Square>>, Rectangle) {
+        let mut rectangle = (0.0, 0.0, self.a as f32, self.b as f32);
+        let mut squares = vec![];
+        for division in &self.divisions[..divisions] {
+            let (x, y, width, height) = rectangle;
+            let side = division.b as f32;
+            let cut = division.q as f32 * side;
+            // The squares are cut along the longer side, which is `a` after the first division
+            squares.push(
+                (0..division.q)
+                    .map(|i| {
+                        if width >= height {
+                            (x + i as f32 * side, y, side)
+                        } else {
+                            (x, y + i as f32 * side, side)
+                        }
+                    })
+                    .collect(),
+            );
+            if division.q > 0 {
+                rectangle = if width >= height {
+                    (x + cut, y, width - cut, height)
+                } else {
+                    (x, y + cut, width, height - cut)
+                };
+            }
+        }
+        (squares, rectangle)
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let ((left, top), (area_width, area_height)) = RECTANGLE_AREA;
+        let scale = (area_width / self.a as f32).min(area_height / self.b as f32);
+        let rows = self.divisions.len() + 1;
+        let table = Table::new((left + area_width + 30.0, top - 10.0), 52.0, &HEADERS);
+        let mut diagram = Diagram::new(
+            CANVAS_WIDTH,
+            (top + area_height + 20.0).max(table.position.1 + table.height(rows) + 20.0),
+        );
+        diagram.add_node(DiagramNode::text(
+            "rectangle-title",
+            format!("{} × {} rectangle", self.a, self.b),
+            (left + area_width / 2.0, top - 20.0),
+        ));
+        let (squares, rest) = self.cut_squares(highlights.divisions);
+        let (x, y, width, height) = rest;
+        if width > 0.0 && height > 0.0 {
+            let mut figure = Figure::new(FigureKind::Rect {
+                position: (left + x * scale, top + y * scale),
+                size: (width * scale, height * scale),
+            });
+            if highlights.divisions > 0 && !highlights.done {
+                figure.state = Some(ElementState::Frontier);
+            }
+            diagram.add_figure(figure);
+        }
+        for (division, squares) in squares.iter().enumerate() {
+            let state = if division + 1 < highlights.divisions {
+                None
+            } else if highlights.done {
+                Some(ElementState::Selected)
+            } else {
+                Some(ElementState::Active)
+            };
+            for (i, (x, y, side)) in squares.iter().enumerate() {
+                let mut figure = Figure::new(FigureKind::Rect {
+                    position: (left + x * scale, top + y * scale),
+                    size: (side * scale, side * scale),
+                });
+                figure.state = state;
+                diagram.add_figure(figure);
+                if side * scale >= MIN_LABELED_SIDE {
+                    diagram.add_node(DiagramNode::text(
+                        format!("square-{}-{}", division, i),
+                        side,
+                        (
+                            left + (x + side / 2.0) * scale,
+                            top + (y + side / 2.0) * scale,
+                        ),
+                    ));
+                }
+            }
+        }
+        let mut cells = self.divisions[..highlights.divisions]
+            .iter()
+            .map(|division| {
+                [division.a, division.b, division.q, division.r]
+                    .iter()
+                    .map(|number| number.to_string())
+                    .collect::<Vec<String>>()
+            })
+            .collect::<Vec<Vec<String>>>();
+        if highlights.last_row {
+            cells.push(vec![
+                self.gcd().to_string(),
+                0.to_string(),
+                String::new(),
+                String::new(),
+            ]);
+        }
+        let row_count = cells.len();
+        let rows = cells
+            .into_iter()
+            .enumerate()
+            .map(|(row, mut cells)| {
+                // The coefficients are filled in from the bottom up
+                match highlights.coefficients.get(row_count - 1 - row) {
+                    Some((x, y)) => cells.extend([x.to_string(), y.to_string()]),
+                    None => cells.extend([String::new(), String::new()]),
+                }
+                let state = if Some(row) == highlights.active_row {
+                    Some(ElementState::Active)
+                } else if Some(row) == highlights.frontier_row {
+                    Some(ElementState::Frontier)
+                } else if highlights.done
+                    && (row == row_count - 1
+                        || row == 0 && highlights.coefficients.len() == row_count)
+                {
+                    // The gcd and the Bézout coefficients
+                    Some(ElementState::Selected)
+                } else {
+                    None
+                };
+                cells.into_iter().map(|cell| (cell, state)).collect()
+            })
+            .collect::<Vec<Vec<(String, Option<ElementState>)>>>();
+        table.draw(&mut diagram, "table", &rows);
+        diagram
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &Highlights,
+        lists: Vec<StepList>,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = lists;
+    }
+    fn division_list(&self, divisions: usize) -> StepList {
+        StepList::new(
+            "Divisions",
+            self.divisions[..divisions]
+                .iter()
+                .map(|division| {
+                    format!(
+                        "{} = {} × {} + {}",
+                        division.a, division.q, division.b, division.r
+                    )
+                })
+                .collect(),
+        )
+    }
+    /// Records the divisions, which both versions of the algorithm start with.
+    fn divide(&self, highlights: &mut Highlights, steps: &mut DiagramSteps) {
+        self.push(
+            format!(
+                "Draw a {} × {} rectangle. The gcd of {} and {} is the side of the largest squares that tile it exactly, since it has to divide both of the sides",
+                self.a, self.b, self.a, self.b
+            ),
+            highlights,
+            vec![self.division_list(0)],
+            steps,
+        );
+        for (i, division) in self.divisions.iter().enumerate() {
+            highlights.divisions = i + 1;
+            highlights.active_row = Some(i);
+            let Division { a, b, q, r } = *division;
+            let description = if q == 0 {
+                format!(
+                    "{} = 0 × {} + {}. {} is smaller than {}, so no squares of side {} fit and the numbers swap places",
+                    a, b, r, a, b, b
+                )
+            } else if a == b {
+                format!(
+                    "{} = 1 × {} + 0. The rectangle is a square of side {}, so it is tiled by itself",
+                    a, b, b
+                )
+            } else if r == 0 {
+                format!(
+                    "{} = {} × {} + 0. {} of side {} tile the rest of the rectangle exactly, so they are the largest squares that tile all of it",
+                    a,
+                    q,
+                    b,
+                    plural(q as usize, "square"),
+                    b
+                )
+            } else {
+                format!(
+                    "{} = {} × {} + {}. Cut {} of side {} off the rectangle, which leaves a {} × {} rectangle. Every square that tiles both of the parts tiles the whole rectangle, so the gcd doesn't change",
+                    a,
+                    q,
+                    b,
+                    r,
+                    plural(q as usize, "square"),
+                    b,
+                    b,
+                    r
+                )
+            };
+            self.push(
+                description,
+                highlights,
+                vec![self.division_list(i + 1)],
+                steps,
+            );
+        }
+        highlights.active_row = None;
+    }
+    fn euclidean_algorithm(&self, steps: &mut DiagramSteps) {
+        let mut highlights = Highlights::default();
+        self.divide(&mut highlights, steps);
+        highlights.done = true;
+        self.push(
+            format!(
+                "The remainder is zero, so gcd({}, {}) = {}. It took {}",
+                self.a,
+                self.b,
+                self.gcd(),
+                plural(self.divisions.len(), "division")
+            ),
+            &highlights,
+            vec![self.division_list(self.divisions.len())],
+            steps,
+        );
+    }
+    fn extended_euclidean_algorithm(&self, steps: &mut DiagramSteps) {
+        let mut highlights = Highlights::default();
+        self.divide(&mut highlights, steps);
+        let gcd = self.gcd();
+        let n = self.divisions.len();
+        highlights.last_row = true;
+        highlights.active_row = Some(n);
+        highlights.coefficients = vec![(1, 0)];
+        let lists = |coefficients: &[(i64, i64)]| {
+            vec![
+                self.division_list(n),
+                StepList::new(
+                    "Back substitution",
+                    coefficients
+                        .iter()
+                        .enumerate()
+                        .map(|(i, (x, y))| {
+                            let (a, b) = if i == 0 {
+                                (gcd, 0)
+                            } else {
+                                let division = self.divisions[n - i];
+                                (division.a, division.b)
+                            };
+                            format!("{} × {} + {} × {} = {}", a, x, b, y, gcd)
+                        })
+                        .collect(),
+                ),
+            ]
+        };
+        self.push(
+            format!(
+                "Add a row for gcd({}, 0) = {}, where {} × 1 + 0 × 0 = {}. Going back up the table, the coefficients of each row are found from the ones below it",
+                gcd, gcd, gcd, gcd
+            ),
+            &highlights,
+            lists(&highlights.coefficients),
+            steps,
+        );
+        for row in (0..n).rev() {
+            let Division { a, b, q, r } = self.divisions[row];
+            let (x, y) = *highlights.coefficients.last().unwrap();
+            let (new_x, new_y) = (y, x - q as i64 * y);
+            highlights.coefficients.push((new_x, new_y));
+            highlights.active_row = Some(row);
+            highlights.frontier_row = Some(row + 1);
+            self.push(
+                format!(
+                    "The row below has {} × {} + {} × {} = {}. Substituting {} = {} − {} × {} gives {} × {} + {} × ({} − {} × {}) = {}, so x = {} and y = {}",
+                    b,
+                    x,
+                    r,
+                    y,
+                    gcd,
+                    r,
+                    a,
+                    q,
+                    b,
+                    a,
+                    y,
+                    b,
+                    x,
+                    q,
+                    y,
+                    gcd,
+                    new_x,
+                    new_y
+                ),
+                &highlights,
+                lists(&highlights.coefficients),
+                steps,
+            );
+        }
+        let (x, y) = *highlights.coefficients.last().unwrap();
+        highlights.active_row = None;
+        highlights.frontier_row = None;
+        highlights.done = true;
+        self.push(
+            format!(
+                "The first row has the Bézout coefficients x = {} and y = {}, for which {} × {} + {} × {} = {} = gcd({}, {})",
+                x, y, self.a, x, self.b, y, gcd, self.a, self.b
+            ),
+            &highlights,
+            lists(&highlights.coefficients),
+            steps,
+        );
+    }
+}
+impl Visualizer for EuclideanAlgorithm {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let inputs = vec![
+            Input::Number {
+                label: "a",
+                default: self.a as i64,
+                min: 1,
+                max: MAX_NUMBER as i64,
+            },
+            Input::Number {
+                label: "b",
+                default: self.b as i64,
+                min: 1,
+                max: MAX_NUMBER as i64,
+            },
+        ];
+        vec![
+            Operation::new("Euclidean algorithm", inputs.clone()),
+            Operation::new("Extended Euclidean algorithm", inputs),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Euclidean algorithm" => {
+                self.set_numbers(values)?;
+                self.euclidean_algorithm(&mut steps);
+            }
+            "Extended Euclidean algorithm" => {
+                self.set_numbers(values)?;
+                self.extended_euclidean_algorithm(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The divisions of the Euclidean algorithm until the remainder is zero.
+fn divisions(mut a: u64, mut b: u64) -> Vec<Division> {
+    let mut divisions = vec![];
+    while b > 0 {
+        let division = Division {
+            a,
+            b,
+            q: a / b,
+            r: a % b,
+        };
+        divisions.push(division);
+        (a, b) = (b, division.r);
+    }
+    divisions
+}
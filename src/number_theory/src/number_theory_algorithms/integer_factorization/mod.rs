@@ -0,0 +1,517 @@
+use diagram::*;
+
+use crate::*;
+
+const MAX_NUMBER: u64 = 1_000_000;
+/// How many of the last rows of the table of Pollard's rho are shown.
+const MAX_ROWS: usize = 12;
+const HEADERS: [&str; 4] = ["i", "Tortoise x", "Hare y", "gcd(|x − y|, n)"];
+const FACTORS_TOP: f32 = 60.0;
+const CONTENT_TOP: f32 = 120.0;
+
+/// The states of the factors and the work of the algorithm at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    /// The prime factors that have been found.
+    factors: Vec<u64>,
+    /// The numbers that are left to factor, of which the first one is being factored.
+    pending: Vec<u64>,
+    /// The divisor that trial division is trying, and the remainder.
+    division: Option<(u64, u64)>,
+    /// The iterations of Pollard's rho on the current number by `i`, `x`, `y` and the gcd.
+    rows: Vec<[u64; 4]>,
+    /// How many divisions, gcd checks or rounds of the Miller–Rabin test have been done.
+    count: usize,
+}
+
+/// Splitting a number into its prime factors, either by trial division, which tries every divisor up to the square
+/// root, or by Pollard's rho, which walks a pseudorandom sequence with a tortoise and a hare until their difference
+/// shares a factor with the number.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntegerFactorization {
+    number: u64,
+}
+impl IntegerFactorization {
+    pub fn new(number: u64) -> Self {
+        Self { number }
+    }
+    /// Draws the factors and the work of the algorithm along with a chart of how many steps both algorithms take,
+    /// where `counts` are the final counts of trial division and Pollard's rho.
+    fn draw(&self, highlights: &Highlights, counts: (usize, usize), rho: bool) -> Diagram {
+        let table = Table::new((20.0, CONTENT_TOP), 120.0, &HEADERS);
+        let chart = Chart::new(
+            (560.0, CONTENT_TOP + 30.0),
+            (200.0, 200.0),
+            (3.0, counts.0.max(counts.1).max(1) as f32),
+        );
+        let mut diagram = Diagram::new(
+            CANVAS_WIDTH,
+            (table.position.1 + table.height(MAX_ROWS)).max(chart.position.1 + chart.size.1 + 40.0)
+                + 20.0,
+        );
+        diagram.add_node(DiagramNode::text(
+            "title",
+            format!("Factors of {}", self.number),
+            (CANVAS_WIDTH / 2.0, 20.0),
+        ));
+        let boxes = highlights.factors.len() + highlights.pending.len();
+        let pitch = 76.0_f32.min((CANVAS_WIDTH - 40.0) / boxes as f32);
+        let numbers = highlights
+            .factors
+            .iter()
+            .map(|factor| (*factor, ElementState::Selected))
+            .chain(highlights.pending.iter().enumerate().map(|(i, number)| {
+                (
+                    *number,
+                    if i == 0 {
+                        ElementState::Active
+                    } else {
+                        ElementState::Frontier
+                    },
+                )
+            }));
+        for (i, (number, state)) in numbers.enumerate() {
+            diagram.add_node(
+                DiagramNode::rect(
+                    format!("number-{}", i),
+                    number,
+                    (20.0 + (i as f32 + 0.5) * pitch, FACTORS_TOP),
+                    (pitch - 6.0, 32.0),
+                )
+                .with_state(state),
+            );
+        }
+        if let (Some((divisor, remainder)), Some(number)) =
+            (highlights.division, highlights.pending.first())
+        {
+            let state = if remainder == 0 {
+                ElementState::Selected
+            } else {
+                ElementState::Rejected
+            };
+            for (i, (label, state)) in [
+                (format!("n = {}", number), ElementState::Frontier),
+                (format!("d = {}", divisor), ElementState::Active),
+                (format!("n mod d = {}", remainder), state),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                diagram.add_node(
+                    DiagramNode::rect(
+                        format!("division-{}", i),
+                        label,
+                        (100.0 + i as f32 * 160.0, CONTENT_TOP + 40.0),
+                        (150.0, 36.0),
+                    )
+                    .with_state(state),
+                );
+            }
+        }
+        if rho {
+            let first = highlights.rows.len().saturating_sub(MAX_ROWS);
+            let number = highlights.pending.first().copied().unwrap_or(1);
+            let rows = highlights.rows[first..]
+                .iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    let gcd = row[3];
+                    let state = if gcd == number {
+                        Some(ElementState::Rejected)
+                    } else if gcd > 1 {
+                        Some(ElementState::Selected)
+                    } else if first + i + 1 == highlights.rows.len() {
+                        Some(ElementState::Active)
+                    } else {
+                        None
+                    };
+                    row.iter().map(|cell| (cell.to_string(), state)).collect()
+                })
+                .collect::<Vec<Vec<(String, Option<ElementState>)>>>();
+            table.draw(&mut diagram, "table", &rows);
+        }
+        chart.draw_axes(&mut diagram, "chart", "Steps", ("", ""));
+        for (x, count, label, current) in
+            [(1.0, counts.0, "Trial", !rho), (2.0, counts.1, "Rho", rho)]
+        {
+            let count = if current { highlights.count } else { count };
+            let bar = chart.draw_bar(&mut diagram, (x, count as f32), 0.6);
+            if current {
+                bar.state = Some(ElementState::Selected);
+            }
+            let (bar_x, bar_top) = chart.point((x, count as f32));
+            diagram.add_node(DiagramNode::text(
+                format!("chart-count-{}", x),
+                count,
+                (bar_x, bar_top - 12.0),
+            ));
+            diagram.add_node(DiagramNode::text(
+                format!("chart-label-{}", x),
+                label,
+                (bar_x, chart.position.1 + chart.size.1 + 16.0),
+            ));
+        }
+        diagram
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &Highlights,
+        counts: (usize, usize),
+        rho: bool,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(highlights, counts, rho));
+        step.lists = vec![StepList::new(
+            "Prime factors",
+            highlights
+                .factors
+                .iter()
+                .map(|factor| factor.to_string())
+                .collect(),
+        )];
+    }
+    /// Finishes with the factors in order and how many steps both algorithms took.
+    fn push_factors(
+        &self,
+        highlights: &mut Highlights,
+        counts: (usize, usize),
+        rho: bool,
+        steps: &mut DiagramSteps,
+    ) {
+        highlights.factors.sort_unstable();
+        highlights.pending.clear();
+        highlights.division = None;
+        let factors = highlights
+            .factors
+            .iter()
+            .map(|factor| factor.to_string())
+            .collect::<Vec<String>>()
+            .join(" × ");
+        let (count, other, name) = if rho {
+            (counts.1, counts.0, "trial division")
+        } else {
+            (counts.0, counts.1, "Pollard's rho")
+        };
+        self.push(
+            format!(
+                "{} = {}. It took {}, while {} takes {}",
+                self.number,
+                factors,
+                plural(count, "step"),
+                name,
+                plural(other, "step")
+            ),
+            highlights,
+            counts,
+            rho,
+            steps,
+        );
+    }
+    fn trial_division(&self, counts: (usize, usize), steps: &mut DiagramSteps) -> usize {
+        let mut highlights = Highlights {
+            pending: vec![self.number],
+            ..Default::default()
+        };
+        self.push(
+            format!(
+                "Try to divide {} by 2 and then by every odd number. A divisor only has to be tried while its square is at most the number that is left, since a larger factor would need a smaller one",
+                self.number
+            ),
+            &highlights,
+            counts,
+            false,
+            steps,
+        );
+        let mut n = self.number;
+        let mut d = 2;
+        while d * d <= n {
+            let remainder = n % d;
+            highlights.count += 1;
+            highlights.division = Some((d, remainder));
+            if remainder == 0 {
+                self.push(
+                    format!(
+                        "{} mod {} = 0, so {} is a factor. Divide it out: {} / {} = {}",
+                        n,
+                        d,
+                        d,
+                        n,
+                        d,
+                        n / d
+                    ),
+                    &highlights,
+                    counts,
+                    false,
+                    steps,
+                );
+                n /= d;
+                highlights.factors.push(d);
+                highlights.pending = vec![n];
+            } else {
+                self.push(
+                    format!("{} mod {} = {}, so {} isn't a factor", n, d, remainder, d),
+                    &highlights,
+                    counts,
+                    false,
+                    steps,
+                );
+                d += if d == 2 { 1 } else { 2 };
+            }
+        }
+        highlights.division = None;
+        if n > 1 {
+            self.push(
+                format!(
+                    "{}² = {} is larger than {}, so {} doesn't have a smaller factor and is prime",
+                    d,
+                    d * d,
+                    n,
+                    n
+                ),
+                &highlights,
+                counts,
+                false,
+                steps,
+            );
+            highlights.factors.push(n);
+        }
+        self.push_factors(&mut highlights, counts, false, steps);
+        highlights.count
+    }
+    fn pollard_rho(&self, counts: (usize, usize), steps: &mut DiagramSteps) -> usize {
+        let mut highlights = Highlights {
+            pending: vec![self.number],
+            ..Default::default()
+        };
+        self.push(
+            format!(
+                "Factor {} with Pollard's rho. It walks the sequence x → x² + c mod n from 2 with a tortoise that takes one step at a time and a hare that takes two. Modulo an unknown factor p, the sequence has to repeat, and when the tortoise and the hare meet modulo p, p divides |x − y|",
+                self.number
+            ),
+            &highlights,
+            counts,
+            true,
+            steps,
+        );
+        while let Some(n) = highlights.pending.first().copied() {
+            highlights.rows.clear();
+            if n == 1 {
+                highlights.pending.remove(0);
+                continue;
+            }
+            if n % 2 == 0 {
+                highlights.count += 1;
+                highlights.pending[0] = n / 2;
+                highlights.factors.push(2);
+                self.push(
+                    format!(
+                        "{} is even, so divide out the factor 2 first, which is quicker than finding it with the sequence: {} / 2 = {}",
+                        n,
+                        n,
+                        n / 2
+                    ),
+                    &highlights,
+                    counts,
+                    true,
+                    steps,
+                );
+                continue;
+            }
+            let (prime, rounds) = miller_rabin(n);
+            highlights.count += rounds;
+            if prime {
+                highlights.pending.remove(0);
+                highlights.factors.push(n);
+                self.push(
+                    format!(
+                        "{} passes {} of the Miller–Rabin test, which shows that it is prime much faster than factoring it would",
+                        n,
+                        plural(rounds, "round")
+                    ),
+                    &highlights,
+                    counts,
+                    true,
+                    steps,
+                );
+                continue;
+            }
+            let mut c = 1;
+            let (mut x, mut y) = (2, 2);
+            loop {
+                let f = |x: u64| (x * x + c) % n;
+                x = f(x);
+                y = f(f(y));
+                let d = gcd(x.abs_diff(y), n);
+                highlights.count += 1;
+                highlights
+                    .rows
+                    .push([highlights.rows.len() as u64 + 1, x, y, d]);
+                if d == 1 {
+                    self.push(
+                        format!(
+                            "The tortoise moves to {} and the hare to {}. gcd(|{} − {}|, {}) = 1, so they haven't met modulo a factor yet",
+                            x, y, x, y, n
+                        ),
+                        &highlights,
+                        counts,
+                        true,
+                        steps,
+                    );
+                } else if d == n {
+                    self.push(
+                        format!(
+                            "The tortoise moves to {} and the hare to {}. gcd(|{} − {}|, {}) = {}, so they met modulo every factor at once. Start over with c = {}",
+                            x,
+                            y,
+                            x,
+                            y,
+                            n,
+                            n,
+                            c + 1
+                        ),
+                        &highlights,
+                        counts,
+                        true,
+                        steps,
+                    );
+                    c += 1;
+                    (x, y) = (2, 2);
+                    highlights.rows.clear();
+                } else {
+                    highlights.pending[0] = d;
+                    highlights.pending.insert(1, n / d);
+                    self.push(
+                        format!(
+                            "The tortoise moves to {} and the hare to {}. gcd(|{} − {}|, {}) = {}, which is a factor: {} = {} × {}. Both of them are factored in turn",
+                            x,
+                            y,
+                            x,
+                            y,
+                            n,
+                            d,
+                            n,
+                            d,
+                            n / d
+                        ),
+                        &highlights,
+                        counts,
+                        true,
+                        steps,
+                    );
+                    break;
+                }
+            }
+        }
+        highlights.rows.clear();
+        self.push_factors(&mut highlights, counts, true, steps);
+        highlights.count
+    }
+}
+impl Visualizer for IntegerFactorization {
+    fn diagram(&self) -> Diagram {
+        self.draw(
+            &Highlights {
+                pending: vec![self.number],
+                ..Default::default()
+            },
+            (0, 0),
+            false,
+        )
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let inputs = vec![Input::Number {
+            label: "Number",
+            default: self.number as i64,
+            min: 2,
+            max: MAX_NUMBER as i64,
+        }];
+        vec![
+            Operation::new("Trial division", inputs.clone()),
+            Operation::new("Pollard's rho", inputs),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Trial division" | "Pollard's rho" => {
+                self.number = parse_number(values.first(), "number", 2, MAX_NUMBER)?;
+                // Both algorithms are run once without showing them to know the step counts to compare
+                let counts = (
+                    self.trial_division((0, 0), &mut DiagramSteps::new()),
+                    self.pollard_rho((0, 0), &mut DiagramSteps::new()),
+                );
+                if operation == "Trial division" {
+                    self.trial_division(counts, &mut steps);
+                } else {
+                    self.pollard_rho(counts, &mut steps);
+                }
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The bases that are enough for the Miller–Rabin test to be exact for every `u64`.
+const MILLER_RABIN_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Checks if the number is prime with the deterministic Miller–Rabin test, and returns how many rounds it took.
+///
+/// The odd number n − 1 is written as 2^s × d, and each base a is a witness of n being composite
+/// unless a^d ≡ 1 or a^(2^r × d) ≡ −1 mod n for some r < s, which is always the case for primes.
+fn miller_rabin(n: u64) -> (bool, usize) {
+    if n < 2 || n.is_multiple_of(2) {
+        return (n == 2, 0);
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mut rounds = 0;
+    for a in MILLER_RABIN_BASES {
+        if a % n == 0 {
+            break;
+        }
+        rounds += 1;
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut witness = true;
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                witness = false;
+                break;
+            }
+        }
+        if witness {
+            return (false, rounds);
+        }
+    }
+    (true, rounds)
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, m: u64) -> u64 {
+    let mut result = 1;
+    base %= m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
@@ -0,0 +1,12 @@
+//! A collection of algorithms on whole numbers, like finding primes.
+mod collatz_sequence;
+mod euclidean_algorithm;
+mod integer_factorization;
+mod modular_exponentiation;
+mod sieve_of_eratosthenes;
+
+pub use collatz_sequence::CollatzSequence;
+pub use euclidean_algorithm::EuclideanAlgorithm;
+pub use integer_factorization::IntegerFactorization;
+pub use modular_exponentiation::ModularExponentiation;
+pub use sieve_of_eratosthenes::SieveOfEratosthenes;
@@ -0,0 +1,288 @@
+use diagram::*;
+
+use crate::*;
+
+const MAX_NUMBER: u64 = 1_000_000_000;
+const BITS_TOP: f32 = 50.0;
+const MAX_BIT_PITCH: f32 = 44.0;
+const HEADERS: [&str; 4] = ["Bit", "Exponent", "Square", "Multiply"];
+
+/// A row of the table, which is filled in when a bit of the exponent is consumed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Row {
+    /// The part of the exponent that has been consumed, as a number.
+    exponent: u64,
+    /// The accumulator after it was squared, which the first bit doesn't need.
+    square: Option<u64>,
+    /// The accumulator after it was multiplied by the base, which is only done for ones.
+    multiply: Option<u64>,
+}
+
+/// Raising a number to a power modulo another number by consuming the binary digits of the exponent from the most
+/// significant one. Every digit squares the accumulator, which doubles the exponent that it has been raised to, and
+/// the ones also multiply it by the base, which adds one to the exponent. This takes about two multiplications per
+/// digit instead of one per unit of the exponent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModularExponentiation {
+    base: u64,
+    exponent: u64,
+    modulus: u64,
+}
+impl ModularExponentiation {
+    pub fn new(base: u64, exponent: u64, modulus: u64) -> Self {
+        Self {
+            base,
+            exponent,
+            modulus,
+        }
+    }
+    /// The binary digits of the exponent from the most significant one.
+    fn bits(&self) -> Vec<bool> {
+        let count = u64::BITS - self.exponent.leading_zeros();
+        (0..count)
+            .rev()
+            .map(|bit| self.exponent >> bit & 1 == 1)
+            .collect()
+    }
+    fn draw(&self, rows: &[Row], current: Option<usize>, multiplications: usize) -> Diagram {
+        let bits = self.bits();
+        let pitch = MAX_BIT_PITCH.min((CANVAS_WIDTH - 40.0) / bits.len() as f32);
+        let left = (CANVAS_WIDTH - bits.len() as f32 * pitch) / 2.0;
+        let table = Table::new((20.0, BITS_TOP + 60.0), 112.0, &HEADERS);
+        let chart_top = table.position.1 + 40.0;
+        let mut diagram = Diagram::new(
+            CANVAS_WIDTH,
+            (table.position.1 + table.height(bits.len())).max(chart_top + 260.0) + 20.0,
+        );
+        diagram.add_node(DiagramNode::text(
+            "bits-title",
+            format!("{} in binary", self.exponent),
+            (CANVAS_WIDTH / 2.0, BITS_TOP - 36.0),
+        ));
+        for (i, bit) in bits.iter().enumerate() {
+            let mut node = DiagramNode::rect(
+                format!("bit-{}", i),
+                u8::from(*bit),
+                (left + (i as f32 + 0.5) * pitch, BITS_TOP),
+                (pitch - 4.0, 32.0),
+            );
+            node.state = if Some(i) == current {
+                Some(ElementState::Active)
+            } else if i < rows.len() {
+                Some(ElementState::Selected)
+            } else {
+                None
+            };
+            diagram.add_node(node);
+        }
+        table.draw(
+            &mut diagram,
+            "table",
+            &rows
+                .iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    let state = (Some(i) == current).then_some(ElementState::Active);
+                    [
+                        Some(u8::from(bits[i]) as u64),
+                        Some(row.exponent),
+                        row.square,
+                        row.multiply,
+                    ]
+                    .iter()
+                    .map(|cell| (cell.map(|cell| cell.to_string()).unwrap_or_default(), state))
+                    .collect()
+                })
+                .collect::<Vec<Vec<(String, Option<ElementState>)>>>(),
+        );
+        let naive = self.exponent as usize - 1;
+        let chart = Chart::new(
+            (
+                table.position.0 + table.column_width * 4.0 + 80.0,
+                chart_top,
+            ),
+            (200.0, 200.0),
+            (3.0, naive.max(multiplications).max(1) as f32),
+        );
+        chart.draw_axes(&mut diagram, "chart", "Multiplications", ("", ""));
+        for (x, count, label, state) in [
+            (1.0, naive, "Naive", None),
+            (
+                2.0,
+                multiplications,
+                "Squaring",
+                Some(ElementState::Selected),
+            ),
+        ] {
+            chart.draw_bar(&mut diagram, (x, count as f32), 0.6).state = state;
+            let (bar_x, bar_top) = chart.point((x, count as f32));
+            diagram.add_node(DiagramNode::text(
+                format!("chart-count-{}", x),
+                count,
+                (bar_x, bar_top - 12.0),
+            ));
+            diagram.add_node(DiagramNode::text(
+                format!("chart-label-{}", x),
+                label,
+                (bar_x, chart.position.1 + chart.size.1 + 16.0),
+            ));
+        }
+        diagram
+    }
+    fn push(
+        &self,
+        description: String,
+        rows: &[Row],
+        current: Option<usize>,
+        multiplications: usize,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(rows, current, multiplications));
+        step.lists = vec![StepList::new(
+            "Accumulator",
+            rows.iter()
+                .flat_map(|row| [row.square, row.multiply])
+                .flatten()
+                .map(|value| format!("x = {}", value))
+                .collect(),
+        )];
+    }
+    fn square_and_multiply(&self, steps: &mut DiagramSteps) {
+        let (b, e, m) = (self.base % self.modulus, self.exponent, self.modulus);
+        let bits = self.bits();
+        let mut rows = vec![Row {
+            exponent: 1,
+            square: None,
+            multiply: Some(b),
+        }];
+        let binary = bits
+            .iter()
+            .map(|bit| if *bit { '1' } else { '0' })
+            .collect::<String>();
+        self.push(
+            format!(
+                "Write the exponent {} in binary as {}. The first digit is always 1, so the accumulator starts as x = {}{} = {}^1 mod {}",
+                e,
+                binary,
+                self.base,
+                if self.base >= m {
+                    format!(" mod {} = {}", m, b)
+                } else {
+                    String::new()
+                },
+                self.base,
+                m
+            ),
+            &rows,
+            Some(0),
+            0,
+            steps,
+        );
+        let mut x = b;
+        let mut multiplications = 0;
+        for (i, bit) in bits.iter().enumerate().skip(1) {
+            let exponent = rows[i - 1].exponent;
+            let squared = (x as u128 * x as u128 % m as u128) as u64;
+            multiplications += 1;
+            rows.push(Row {
+                exponent: exponent * 2,
+                square: Some(squared),
+                multiply: None,
+            });
+            self.push(
+                format!(
+                    "The next digit is {}. Square the accumulator, which doubles its exponent from {} to {}: x = {}² mod {} = {}",
+                    u8::from(*bit),
+                    exponent,
+                    exponent * 2,
+                    x,
+                    m,
+                    squared
+                ),
+                &rows,
+                Some(i),
+                multiplications,
+                steps,
+            );
+            x = squared;
+            if *bit {
+                let multiplied = (x as u128 * b as u128 % m as u128) as u64;
+                multiplications += 1;
+                rows[i].exponent += 1;
+                rows[i].multiply = Some(multiplied);
+                self.push(
+                    format!(
+                        "The digit is 1, so also multiply by the base, which adds one to the exponent: x = {} × {} mod {} = {}",
+                        x, b, m, multiplied
+                    ),
+                    &rows,
+                    Some(i),
+                    multiplications,
+                    steps,
+                );
+                x = multiplied;
+            }
+        }
+        self.push(
+            format!(
+                "Every digit has been consumed, so {}^{} mod {} = {}. It took {} for {}, while multiplying by the base over and over would take {}",
+                self.base,
+                e,
+                m,
+                x,
+                plural(multiplications, "multiplication"),
+                plural(bits.len(), "binary digit"),
+                plural(e as usize - 1, "multiplication")
+            ),
+            &rows,
+            None,
+            multiplications,
+            steps,
+        );
+    }
+}
+impl Visualizer for ModularExponentiation {
+    fn diagram(&self) -> Diagram {
+        self.draw(&[], None, 0)
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![Operation::new(
+            "Square and multiply",
+            vec![
+                Input::Number {
+                    label: "Base",
+                    default: self.base as i64,
+                    min: 0,
+                    max: MAX_NUMBER as i64,
+                },
+                Input::Number {
+                    label: "Exponent",
+                    default: self.exponent as i64,
+                    min: 1,
+                    max: MAX_NUMBER as i64,
+                },
+                Input::Number {
+                    label: "Modulus",
+                    default: self.modulus as i64,
+                    min: 2,
+                    max: MAX_NUMBER as i64,
+                },
+            ],
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Square and multiply" => {
+                *self = Self::new(
+                    parse_number(values.first(), "base", 0, MAX_NUMBER)?,
+                    parse_number(values.get(1), "exponent", 1, MAX_NUMBER)?,
+                    parse_number(values.get(2), "modulus", 2, MAX_NUMBER)?,
+                );
+                self.square_and_multiply(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
@@ -0,0 +1,226 @@
+use diagram::*;
+
+use crate::*;
+
+const MAX_BOUND: u64 = 400;
+/// How far the centers of the cells of the grid are from each other.
+const CELL_PITCH: f32 = 38.0;
+const CELL_SIZE: f32 = 34.0;
+const CHART_HEIGHT: f32 = 180.0;
+
+/// The numbers from 1 to an upper bound in a grid, where the multiples of every prime are crossed out in turn. The
+/// numbers that are never crossed out are the primes, and the density of them is charted below the grid.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SieveOfEratosthenes {
+    bound: usize,
+}
+impl SieveOfEratosthenes {
+    pub fn new(bound: usize) -> Self {
+        Self { bound }
+    }
+    fn columns(&self) -> usize {
+        if self.bound <= 100 {
+            10
+        } else {
+            20
+        }
+    }
+    fn cell_position(&self, number: usize) -> (f32, f32) {
+        let columns = self.columns();
+        let left = (CANVAS_WIDTH - columns as f32 * CELL_PITCH) / 2.0;
+        (
+            left + ((number - 1) % columns) as f32 * CELL_PITCH + CELL_PITCH / 2.0,
+            ((number - 1) / columns) as f32 * CELL_PITCH + CELL_PITCH / 2.0,
+        )
+    }
+    /// Draws the grid with the states of the numbers from 1 up, and the density of the primes up to `known`. The
+    /// numbers up to it have been decided, so the ones that aren't crossed out are prime.
+    fn draw(&self, states: &[Option<ElementState>], known: usize) -> Diagram {
+        let rows = self.bound.div_ceil(self.columns());
+        let grid_height = rows as f32 * CELL_PITCH;
+        let chart = Chart::new(
+            (80.0, grid_height + 60.0),
+            (CANVAS_WIDTH - 160.0, CHART_HEIGHT),
+            (self.bound as f32, 1.0),
+        );
+        let mut diagram = Diagram::new(CANVAS_WIDTH, chart.position.1 + CHART_HEIGHT + 40.0);
+        for number in 1..=self.bound {
+            let mut node = DiagramNode::rect(
+                format!("number-{}", number),
+                number,
+                self.cell_position(number),
+                (CELL_SIZE, CELL_SIZE),
+            );
+            node.state = states.get(number).copied().flatten();
+            diagram.add_node(node);
+        }
+        chart.draw_axes(
+            &mut diagram,
+            "chart",
+            "Density of primes π(x) / x, and 1 / ln x dashed",
+            (self.bound, 1),
+        );
+        if self.bound >= 3 {
+            chart
+                .draw_line(
+                    &mut diagram,
+                    &(3..=self.bound)
+                        .map(|x| (x as f32, 1.0 / (x as f32).ln()))
+                        .collect::<Vec<(f32, f32)>>(),
+                )
+                .dashed = true;
+        }
+        if known >= 2 {
+            let mut primes = 0;
+            let mut density = vec![];
+            for (x, state) in states.iter().enumerate().take(known + 1).skip(1) {
+                if x >= 2 && !matches!(state, Some(ElementState::Rejected | ElementState::Frontier))
+                {
+                    primes += 1;
+                }
+                density.push((x as f32, primes as f32 / x as f32));
+            }
+            chart.draw_line(&mut diagram, &density).state = Some(ElementState::Selected);
+        }
+        diagram
+    }
+    fn push(
+        &self,
+        description: String,
+        states: &[Option<ElementState>],
+        known: usize,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(states, known));
+        step.lists = vec![StepList::new(
+            "Primes",
+            (2..states.len())
+                .filter(|number| {
+                    matches!(
+                        states[*number],
+                        Some(ElementState::Selected | ElementState::Active)
+                    )
+                })
+                .map(|number| number.to_string())
+                .collect(),
+        )];
+    }
+    fn sieve(&self, steps: &mut DiagramSteps) {
+        let n = self.bound;
+        let mut states = vec![None; n + 1];
+        states[1] = Some(ElementState::Rejected);
+        self.push(
+            format!(
+                "Write down the numbers from 1 to {}. 1 isn't prime, so cross it out",
+                n
+            ),
+            &states,
+            1,
+            steps,
+        );
+        let mut crossings = 0;
+        let mut p = 2;
+        while p * p <= n {
+            if states[p].is_some() {
+                p += 1;
+                continue;
+            }
+            states[p] = Some(ElementState::Active);
+            let mut crossed = 0;
+            let mut already = 0;
+            for multiple in (p * p..=n).step_by(p) {
+                crossings += 1;
+                if states[multiple].is_some() {
+                    already += 1;
+                } else {
+                    states[multiple] = Some(ElementState::Frontier);
+                    crossed += 1;
+                }
+            }
+            self.push(
+                format!(
+                    "{} isn't crossed out, so it is prime. Cross out its multiples from {}² = {} up, since the smaller ones have a smaller prime factor and are already crossed out. That crosses out {}{}",
+                    p,
+                    p,
+                    p * p,
+                    plural(crossed, "number"),
+                    if already > 0 {
+                        format!(
+                            ". {} of its multiples were already crossed out by smaller primes",
+                            already
+                        )
+                    } else {
+                        String::new()
+                    }
+                ),
+                &states,
+                (p * p - 1).min(n),
+                steps,
+            );
+            for state in states.iter_mut() {
+                *state = match state {
+                    Some(ElementState::Active) => Some(ElementState::Selected),
+                    Some(ElementState::Frontier) => Some(ElementState::Rejected),
+                    _ => *state,
+                };
+            }
+            p += 1;
+        }
+        // The next number that isn't crossed out, which is the next prime
+        while states[p].is_some() {
+            p += 1;
+        }
+        let mut primes = 0;
+        for state in states.iter_mut().skip(2) {
+            if state.is_none() || *state == Some(ElementState::Selected) {
+                *state = Some(ElementState::Selected);
+                primes += 1;
+            }
+        }
+        self.push(
+            format!(
+                "The next number that isn't crossed out is {}, and {}² = {} is larger than {}. Every composite number up to {} has a prime factor smaller than {}, so it has been crossed out, and the numbers that are left are prime. π({}) = {} of the numbers up to it are prime, which is {:.1}%. Multiples were crossed out {} in total",
+                p,
+                p,
+                p * p,
+                n,
+                n,
+                p,
+                n,
+                primes,
+                primes as f32 / n as f32 * 100.0,
+                plural(crossings, "time")
+            ),
+            &states,
+            n,
+            steps,
+        );
+    }
+}
+impl Visualizer for SieveOfEratosthenes {
+    fn diagram(&self) -> Diagram {
+        self.draw(&[], 0)
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![Operation::new(
+            "Sieve",
+            vec![Input::Number {
+                label: "Upper bound",
+                default: self.bound as i64,
+                min: 2,
+                max: MAX_BOUND as i64,
+            }],
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Sieve" => {
+                self.bound = parse_number(values.first(), "upper bound", 2, MAX_BOUND)? as usize;
+                self.sieve(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
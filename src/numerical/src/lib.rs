@@ -0,0 +1,16 @@
+//! This crate contains my implementations of numerical algorithms, like the fast Fourier transform.
+//! Each algorithm records a diagram of the numbers it computes at every step, and charts of what they show.
+pub mod numerical_algorithms;
+
+pub const CANVAS_WIDTH: f32 = 800.0;
+
+/// The number rounded to two decimals, without trailing zeros.
+pub fn format_number(number: f64) -> String {
+    let rounded = (number * 100.0).round() / 100.0;
+    // Negative numbers that round to zero would otherwise be shown as "-0"
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        rounded.to_string().replace('-', "−")
+    }
+}
@@ -0,0 +1,577 @@
+use std::f64::consts::PI;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const MAX_SAMPLES: usize = 16;
+const NETWORK_TOP: f32 = 60.0;
+/// How far the network is from the sides of the canvas, which leaves room for the labels of the rows.
+const NETWORK_MARGIN: f32 = 100.0;
+const CELL_WIDTH: f32 = 96.0;
+const CHART_SIZE: (f32, f32) = (300.0, 150.0);
+const LENGTHS: [&str; 3] = ["8", "16", "4"];
+
+/// A complex number, which the samples become when they are transformed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+impl Complex {
+    fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+    /// The number with the magnitude 1 at the angle in radians.
+    fn from_angle(angle: f64) -> Self {
+        Self::new(angle.cos(), angle.sin())
+    }
+    fn magnitude(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+    /// The number in parentheses if it has both a real and an imaginary part, so that it can be multiplied.
+    fn factor(&self) -> String {
+        let text = self.to_string();
+        if text.chars().skip(1).any(|c| c == '+' || c == '−') {
+            format!("({})", text)
+        } else {
+            text
+        }
+    }
+}
+impl Add for Complex {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+impl Sub for Complex {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+impl Mul for Complex {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+impl fmt::Display for Complex {
+    /// Rounds the parts to two decimals and leaves out the parts that are zero, like `1.5−0.5i`, `2` or `−i`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (re, im) = (format_number(self.re), format_number(self.im));
+        let imaginary = match im.as_str() {
+            "1" => "i".to_string(),
+            "−1" => "−i".to_string(),
+            _ => format!("{}i", im),
+        };
+        match (re.as_str(), im.as_str()) {
+            (_, "0") => write!(f, "{}", re),
+            ("0", _) => write!(f, "{}", imaginary),
+            _ if im.starts_with('−') => write!(f, "{}{}", re, imaginary),
+            _ => write!(f, "{}+{}", re, imaginary),
+        }
+    }
+}
+
+/// What the current step is doing, as the columns and rows of the cells of the network.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    /// The cells that are read.
+    inputs: Vec<(usize, usize)>,
+    /// The cells that are written.
+    outputs: Vec<(usize, usize)>,
+}
+
+/// The radix-2 Cooley–Tukey fast Fourier transform of a signal whose length is a power of two. The samples are first
+/// put in bit-reversed order, and then every stage of butterflies combines pairs of transforms into transforms that
+/// are twice as long, until the last column is the transform of the whole signal. The signal and its spectrum are
+/// charted below the network.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FastFourierTransform {
+    samples: Vec<f64>,
+}
+impl FastFourierTransform {
+    pub fn new(samples: Vec<f64>) -> Self {
+        Self { samples }
+    }
+    /// The number of stages of butterflies, which is the base 2 logarithm of the number of samples.
+    fn stages(&self) -> usize {
+        self.samples.len().trailing_zeros() as usize
+    }
+    fn row_pitch(&self) -> f32 {
+        if self.samples.len() <= 8 {
+            40.0
+        } else {
+            30.0
+        }
+    }
+    /// The center of a cell, where column 0 is the input, column 1 is the input in bit-reversed order and the next
+    /// columns are the outputs of the stages.
+    fn cell_position(&self, column: usize, row: usize) -> (f32, f32) {
+        let pitch = (CANVAS_WIDTH - 2.0 * NETWORK_MARGIN) / (self.stages() + 1) as f32;
+        (
+            NETWORK_MARGIN + column as f32 * pitch,
+            NETWORK_TOP + (row as f32 + 0.5) * self.row_pitch(),
+        )
+    }
+    /// Draws the network with the values that have been computed in every column, and the charts of the signal and
+    /// the computed part of its spectrum.
+    fn draw(&self, columns: &[Vec<Option<Complex>>], highlights: &Highlights) -> Diagram {
+        let n = self.samples.len();
+        let stages = self.stages();
+        let pitch = self.row_pitch();
+        let network_bottom = NETWORK_TOP + n as f32 * pitch;
+        let chart_top = network_bottom + 60.0;
+        let mut diagram = Diagram::new(CANVAS_WIDTH, chart_top + CHART_SIZE.1 + 40.0);
+        for (column, values) in columns.iter().enumerate() {
+            let header = match column {
+                0 => "Input".to_string(),
+                1 => "Bit reversed".to_string(),
+                _ => format!("Size {}", 1 << (column - 1)),
+            };
+            diagram.add_node(DiagramNode::text(
+                format!("header-{}", column),
+                header,
+                (self.cell_position(column, 0).0, NETWORK_TOP - 20.0),
+            ));
+            for (row, value) in values.iter().enumerate() {
+                let mut node = DiagramNode::rect(
+                    cell_key(column, row),
+                    value.map(|value| value.to_string()).unwrap_or_default(),
+                    self.cell_position(column, row),
+                    (CELL_WIDTH, pitch - 8.0),
+                );
+                node.state = if highlights.outputs.contains(&(column, row)) {
+                    Some(ElementState::Selected)
+                } else if highlights.inputs.contains(&(column, row)) {
+                    Some(ElementState::Active)
+                } else {
+                    None
+                };
+                diagram.add_node(node);
+            }
+        }
+        for row in 0..n {
+            let (left, y) = self.cell_position(0, row);
+            diagram.add_node(DiagramNode::text(
+                format!("input-{}", row),
+                format!("x[{}]", row),
+                (left - CELL_WIDTH / 2.0 - 30.0, y),
+            ));
+            let (right, _) = self.cell_position(stages + 1, row);
+            diagram.add_node(DiagramNode::text(
+                format!("output-{}", row),
+                format!("X[{}]", row),
+                (right + CELL_WIDTH / 2.0 + 30.0, y),
+            ));
+        }
+        let highlighted = |from: (usize, usize), to: (usize, usize)| {
+            highlights.inputs.contains(&from) && highlights.outputs.contains(&to)
+        };
+        for row in 0..n {
+            let to = (1, bit_reverse(row, stages));
+            let edge = diagram.add_edge(DiagramEdge::arrow(cell_key(0, row), cell_key(to.0, to.1)));
+            if highlighted((0, row), to) {
+                edge.state = Some(ElementState::Active);
+            }
+        }
+        for stage in 1..=stages {
+            let half = 1 << (stage - 1);
+            for top in (0..n).filter(|row| row & half == 0) {
+                let bottom = top + half;
+                let twiddle = twiddle_name(top % half, half * 2);
+                for (from, to, label) in [
+                    (top, top, None),
+                    (top, bottom, None),
+                    (bottom, top, Some(twiddle.clone())),
+                    (bottom, bottom, Some(format!("−{}", twiddle))),
+                ] {
+                    let mut edge =
+                        DiagramEdge::arrow(cell_key(stage, from), cell_key(stage + 1, to));
+                    if let Some(label) = label {
+                        edge = edge.with_label(label);
+                    }
+                    if highlighted((stage, from), (stage + 1, to)) {
+                        edge.state = Some(ElementState::Active);
+                    }
+                    diagram.add_edge(edge);
+                }
+            }
+        }
+        self.draw_charts(&mut diagram, &columns[stages + 1], chart_top, highlights);
+        diagram
+    }
+    fn draw_charts(
+        &self,
+        diagram: &mut Diagram,
+        spectrum: &[Option<Complex>],
+        top: f32,
+        highlights: &Highlights,
+    ) {
+        let n = self.samples.len();
+        let lowest = self.samples.iter().copied().fold(0.0, f64::min);
+        let mut highest = self.samples.iter().copied().fold(0.0, f64::max);
+        if highest == lowest {
+            highest = 1.0;
+        }
+        let signal = Chart::new((60.0, top), CHART_SIZE, ((n - 1) as f32, highest as f32))
+            .with_min((0.0, lowest as f32));
+        signal.draw_axes(
+            diagram,
+            "signal",
+            "Signal x[n]",
+            ("n", format_number(highest)),
+        );
+        signal.draw_line(
+            diagram,
+            &self
+                .samples
+                .iter()
+                .enumerate()
+                .map(|(i, sample)| (i as f32, *sample as f32))
+                .collect::<Vec<(f32, f32)>>(),
+        );
+        for (i, sample) in self.samples.iter().enumerate() {
+            let mut node = DiagramNode::new(
+                format!("sample-{}", i),
+                "",
+                signal.point((i as f32, *sample as f32)),
+                Shape::Circle { radius: 4.0 },
+            );
+            if highlights.inputs.contains(&(0, i)) {
+                node.state = Some(ElementState::Active);
+            }
+            diagram.add_node(node);
+        }
+        let loudest = transform(&self.samples)
+            .iter()
+            .map(|value| value.magnitude())
+            .fold(0.0, f64::max);
+        let magnitudes = Chart::new(
+            (CANVAS_WIDTH - CHART_SIZE.0 - 40.0, top),
+            CHART_SIZE,
+            (n as f32 - 0.5, loudest.max(1.0) as f32),
+        )
+        .with_min((-0.5, 0.0));
+        magnitudes.draw_axes(
+            diagram,
+            "spectrum",
+            "Magnitudes |X[k]|",
+            ("k", format_number(loudest.max(1.0))),
+        );
+        for (k, value) in spectrum.iter().enumerate() {
+            if let Some(value) = value {
+                let bar = magnitudes.draw_bar(diagram, (k as f32, value.magnitude() as f32), 0.6);
+                if highlights.outputs.contains(&(self.stages() + 1, k)) {
+                    bar.state = Some(ElementState::Selected);
+                }
+            }
+        }
+    }
+    fn push(
+        &self,
+        description: String,
+        columns: &[Vec<Option<Complex>>],
+        highlights: Highlights,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(columns, &highlights));
+        step.lists = vec![StepList::new(
+            "Output",
+            columns[self.stages() + 1]
+                .iter()
+                .enumerate()
+                .filter_map(|(k, value)| value.map(|value| format!("X[{}] = {}", k, value)))
+                .collect(),
+        )];
+    }
+    fn empty_columns(&self) -> Vec<Vec<Option<Complex>>> {
+        let mut columns = vec![vec![None; self.samples.len()]; self.stages() + 2];
+        for (cell, sample) in columns[0].iter_mut().zip(&self.samples) {
+            *cell = Some(Complex::new(*sample, 0.0));
+        }
+        columns
+    }
+    fn fast_fourier_transform(&self, steps: &mut DiagramSteps) {
+        let n = self.samples.len();
+        let stages = self.stages();
+        let mut columns = self.empty_columns();
+        self.push(
+            format!(
+                "The signal has {}, which is 2^{}. The transform splits it into its even and odd samples over and over, so first the samples are put in the order that the splits end up in",
+                plural(n, "sample"),
+                stages
+            ),
+            &columns,
+            Highlights::default(),
+            steps,
+        );
+        for row in 0..n {
+            let reversed = bit_reverse(row, stages);
+            columns[1][reversed] = columns[0][row];
+            let binary = format!("{:0width$b}", row, width = stages);
+            let description = if reversed == row {
+                format!(
+                    "{} is {} in binary, which is the same reversed, so x[{}] stays in row {}",
+                    row, binary, row, row
+                )
+            } else {
+                format!(
+                    "{} is {} in binary, and reversed that is {}, so x[{}] goes to row {}",
+                    row,
+                    binary,
+                    format_args!("{:0width$b}", reversed, width = stages),
+                    row,
+                    reversed
+                )
+            };
+            self.push(
+                description,
+                &columns,
+                Highlights {
+                    inputs: vec![(0, row)],
+                    outputs: vec![(1, reversed)],
+                },
+                steps,
+            );
+        }
+        let mut butterflies = 0;
+        for stage in 1..=stages {
+            let half = 1 << (stage - 1);
+            for top in (0..n).filter(|row| row & half == 0) {
+                let bottom = top + half;
+                let twiddle =
+                    Complex::from_angle(-2.0 * PI * (top % half) as f64 / (half * 2) as f64);
+                let (u, v) = (
+                    columns[stage][top].unwrap(),
+                    columns[stage][bottom].unwrap(),
+                );
+                let product = twiddle * v;
+                columns[stage + 1][top] = Some(u + product);
+                columns[stage + 1][bottom] = Some(u - product);
+                butterflies += 1;
+                let name = twiddle_name(top % half, half * 2);
+                self.push(
+                    format!(
+                        "Stage {} combines transforms of size {} into ones of size {}. The butterfly of rows {} and {} multiplies the bottom by the twiddle factor {} = {}, so the top becomes {} + {} = {} and the bottom {} − {} = {}",
+                        stage,
+                        half,
+                        half * 2,
+                        top,
+                        bottom,
+                        name,
+                        twiddle,
+                        u,
+                        product.factor(),
+                        u + product,
+                        u,
+                        product.factor(),
+                        u - product
+                    ),
+                    &columns,
+                    Highlights {
+                        inputs: vec![(stage, top), (stage, bottom)],
+                        outputs: vec![(stage + 1, top), (stage + 1, bottom)],
+                    },
+                    steps,
+                );
+            }
+        }
+        let spectrum = columns[stages + 1]
+            .iter()
+            .map(|value| value.unwrap())
+            .collect::<Vec<Complex>>();
+        // Only up to the middle, since the rest mirror them for a real signal
+        let loudest = (1..=n / 2)
+            .max_by(|a, b| {
+                spectrum[*a]
+                    .magnitude()
+                    .total_cmp(&spectrum[*b].magnitude())
+            })
+            .unwrap();
+        let frequencies = if format_number(spectrum[loudest].magnitude()) == "0" {
+            "The signal is constant, so it has no other frequencies".to_string()
+        } else {
+            format!(
+                "The strongest frequency is {} per {} samples, with |X[{}]| = {}",
+                plural(loudest, "cycle"),
+                n,
+                loudest,
+                format_number(spectrum[loudest].magnitude())
+            )
+        };
+        self.push(
+            format!(
+                "The last column is the spectrum of the signal, where X[0] = {} is the sum of the samples. {}. It took {} with one complex multiplication each, while computing every X[k] directly would take {}² = {} multiplications",
+                spectrum[0],
+                frequencies,
+                if butterflies == 1 {
+                    "1 butterfly".to_string()
+                } else {
+                    format!("{} butterflies", butterflies)
+                },
+                n,
+                n * n
+            ),
+            &columns,
+            Highlights::default(),
+            steps,
+        );
+    }
+}
+impl Visualizer for FastFourierTransform {
+    fn diagram(&self) -> Diagram {
+        self.draw(&self.empty_columns(), &Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Transform",
+                vec![Input::Text {
+                    label: "Samples",
+                    default: self
+                        .samples
+                        .iter()
+                        .map(|sample| sample.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                }],
+            ),
+            Operation::new(
+                "Random signal",
+                vec![Input::Choice {
+                    label: "Length",
+                    choices: LENGTHS.iter().map(|length| length.to_string()).collect(),
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Transform" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                self.samples = parse_samples(&text)?;
+                self.fast_fourier_transform(&mut steps);
+            }
+            "Random signal" => {
+                let length = values.first().map(|value| value.number()).unwrap_or(8);
+                self.samples = random_signal(length.clamp(2, MAX_SAMPLES as i64) as usize);
+                self.fast_fourier_transform(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn cell_key(column: usize, row: usize) -> String {
+    format!("value-{}-{}", column, row)
+}
+
+/// The index with its lowest `bits` binary digits in reverse order.
+fn bit_reverse(index: usize, bits: usize) -> usize {
+    (0..bits).fold(0, |reversed, bit| reversed << 1 | (index >> bit & 1))
+}
+
+/// The name of the twiddle factor `e^(−2πi·k/n)`, like `W₈³`.
+fn twiddle_name(k: usize, n: usize) -> String {
+    let digits = |number: usize, symbols: [char; 10]| {
+        number
+            .to_string()
+            .chars()
+            .map(|digit| symbols[digit.to_digit(10).unwrap() as usize])
+            .collect::<String>()
+    };
+    format!(
+        "W{}{}",
+        digits(n, ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉']),
+        digits(k, ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'])
+    )
+}
+
+/// The transform of the samples without any steps.
+fn transform(samples: &[f64]) -> Vec<Complex> {
+    let n = samples.len();
+    let bits = n.trailing_zeros() as usize;
+    let mut values = vec![Complex::default(); n];
+    for (i, sample) in samples.iter().enumerate() {
+        values[bit_reverse(i, bits)] = Complex::new(*sample, 0.0);
+    }
+    let mut half = 1;
+    while half < n {
+        for top in (0..n).filter(|row| row & half == 0) {
+            let twiddle = Complex::from_angle(-2.0 * PI * (top % half) as f64 / (half * 2) as f64);
+            let (u, v) = (values[top], twiddle * values[top + half]);
+            values[top] = u + v;
+            values[top + half] = u - v;
+        }
+        half *= 2;
+    }
+    values
+}
+
+/// Parses the samples from a list separated by commas or spaces.
+fn parse_samples(text: &str) -> Result<Vec<f64>, String> {
+    let samples = text
+        .split([',', ' '])
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| part.trim().replace('−', "-").parse::<f64>())
+        .collect::<Result<Vec<f64>, _>>()
+        .map_err(|_| "The samples must be numbers separated by commas".to_string())?;
+    if samples
+        .iter()
+        .any(|sample| !sample.is_finite() || sample.abs() > 1000.0)
+    {
+        return Err("The samples must be between −1000 and 1000".to_string());
+    }
+    if samples.len() < 2 || samples.len() > MAX_SAMPLES || !samples.len().is_power_of_two() {
+        return Err(format!(
+            "The number of samples must be a power of two from 2 to {}",
+            MAX_SAMPLES
+        ));
+    }
+    Ok(samples)
+}
+
+/// A sum of a cosine and a sine wave with random frequencies and amplitudes, rounded to two decimals.
+fn random_signal(length: usize) -> Vec<f64> {
+    let mut rng = rand::thread_rng();
+    let waves = [
+        (
+            rng.gen_range(1..=length / 2),
+            rng.gen_range(1..=3) as f64,
+            0.0,
+        ),
+        (
+            rng.gen_range(1..=length / 2),
+            rng.gen_range(1..=3) as f64,
+            PI / 2.0,
+        ),
+    ];
+    (0..length)
+        .map(|i| {
+            let sample = waves
+                .iter()
+                .map(|(frequency, amplitude, phase)| {
+                    amplitude * (2.0 * PI * (frequency * i) as f64 / length as f64 - phase).cos()
+                })
+                .sum::<f64>();
+            let rounded = (sample * 100.0).round() / 100.0;
+            // Without a negative zero, which would be shown as "-0"
+            if rounded == 0.0 {
+                0.0
+            } else {
+                rounded
+            }
+        })
+        .collect()
+}
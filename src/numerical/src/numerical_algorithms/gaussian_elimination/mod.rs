@@ -0,0 +1,508 @@
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const MAX_SIZE: usize = 5;
+const SIZES: [&str; 4] = ["3", "2", "4", "5"];
+const VARIABLES: [&str; MAX_SIZE] = ["x₁", "x₂", "x₃", "x₄", "x₅"];
+const MATRIX_POSITION: (f32, f32) = (100.0, 60.0);
+const CELL_PITCH: (f32, f32) = (84.0, 40.0);
+/// Values closer to zero than this are treated as zero, since rounding errors rarely leave exact zeros.
+const EPSILON: f64 = 1e-9;
+
+/// The highlights of the current step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    /// The states of single cells, as rows and columns of the matrix.
+    cells: Vec<(usize, usize, ElementState)>,
+    /// The states of whole rows.
+    rows: Vec<(usize, ElementState)>,
+    /// The row operation next to the row that it changes.
+    operation: Option<(usize, String)>,
+    solution: Option<usize>,
+}
+
+/// The rows of an augmented matrix, which keep track of the row of the system that they started as, so that rows can
+/// move when they are swapped.
+#[derive(Clone, Debug, PartialEq)]
+struct Matrix {
+    rows: Vec<(usize, Vec<f64>)>,
+}
+
+/// Gaussian elimination, which solves a system of linear equations by subtracting multiples of rows of its augmented
+/// matrix from the rows below them until the matrix is upper triangular, and then solving the variables from the last
+/// one up with back substitution. Partial pivoting swaps the row with the largest entry of every column to the top
+/// of what is left, which keeps the multipliers small.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussianElimination {
+    /// The rows of the augmented matrix, where the last column is the right-hand side of the equations.
+    system: Vec<Vec<f64>>,
+}
+impl GaussianElimination {
+    pub fn new(system: Vec<Vec<f64>>) -> Self {
+        Self { system }
+    }
+    fn draw(&self, matrix: &Matrix, solution: &[Option<f64>], highlights: &Highlights) -> Diagram {
+        let n = self.system.len();
+        let (left, top) = MATRIX_POSITION;
+        let (width, height) = CELL_PITCH;
+        let solution_top = top + (n as f32 + 1.0) * height + 20.0;
+        let mut diagram = Diagram::new(CANVAS_WIDTH, solution_top + height + 40.0);
+        for column in 0..=n {
+            diagram.add_node(DiagramNode::text(
+                format!("header-{}", column),
+                VARIABLES.get(column).copied().unwrap_or("b"),
+                (left + (column as f32 + 0.5) * width, top - height / 2.0),
+            ));
+        }
+        // Between the coefficients and the right-hand side
+        let separator = left + n as f32 * width;
+        diagram.add_figure(
+            Figure::new(FigureKind::Line(
+                (separator, top - 4.0),
+                (separator, top + n as f32 * height + 4.0),
+            ))
+            .dashed(),
+        );
+        for (row, (id, values)) in matrix.rows.iter().enumerate() {
+            let y = top + (row as f32 + 0.5) * height;
+            let row_state = highlights
+                .rows
+                .iter()
+                .find(|(highlighted, _)| *highlighted == row)
+                .map(|(_, state)| *state);
+            diagram.add_node(DiagramNode::text(
+                format!("row-{}", id),
+                format!("R{}", row + 1),
+                (left - 30.0, y),
+            ));
+            for (column, value) in values.iter().enumerate() {
+                let mut node = DiagramNode::rect(
+                    format!("cell-{}-{}", id, column),
+                    format_number(*value),
+                    (left + (column as f32 + 0.5) * width, y),
+                    (width - 6.0, height - 6.0),
+                );
+                node.state = highlights
+                    .cells
+                    .iter()
+                    .find(|(r, c, _)| *r == row && *c == column)
+                    .map(|(_, _, state)| *state)
+                    .or(row_state);
+                diagram.add_node(node);
+            }
+        }
+        if let Some((row, operation)) = &highlights.operation {
+            diagram.add_node(DiagramNode::text(
+                "operation",
+                operation,
+                (separator + width + 90.0, top + (*row as f32 + 0.5) * height),
+            ));
+        }
+        diagram.add_node(DiagramNode::text(
+            "solution-title",
+            "Solution",
+            (left - 30.0, solution_top + height / 2.0),
+        ));
+        for (i, value) in solution.iter().enumerate() {
+            let mut node = DiagramNode::rect(
+                format!("solution-{}", i),
+                match value {
+                    Some(value) => format!("{} = {}", VARIABLES[i], format_number(*value)),
+                    None => VARIABLES[i].to_string(),
+                },
+                (left + (i as f32 + 0.5) * width, solution_top + height / 2.0),
+                (width - 6.0, height - 6.0),
+            );
+            if highlights.solution == Some(i) {
+                node.state = Some(ElementState::Selected);
+            }
+            diagram.add_node(node);
+        }
+        diagram
+    }
+    fn push(
+        &self,
+        description: String,
+        matrix: &Matrix,
+        solution: &[Option<f64>],
+        highlights: Highlights,
+        operations: &[String],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(matrix, solution, &highlights));
+        step.lists = vec![
+            StepList::new("Row operations", operations.to_vec()),
+            StepList::new(
+                "Solution",
+                solution
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, value)| {
+                        value.map(|value| format!("{} = {}", VARIABLES[i], format_number(value)))
+                    })
+                    .collect(),
+            ),
+        ];
+    }
+    fn eliminate(&self, steps: &mut DiagramSteps) {
+        let n = self.system.len();
+        let mut matrix = Matrix {
+            rows: self.system.iter().cloned().enumerate().collect(),
+        };
+        let mut solution = vec![None; n];
+        let mut operations = vec![];
+        self.push(
+            format!(
+                "Write the system of {} as an augmented matrix, where every row is an equation and the last column is its right-hand side. Adding multiples of equations to each other doesn't change the solution",
+                plural(n, "equation")
+            ),
+            &matrix,
+            &solution,
+            Highlights::default(),
+            &operations,
+            steps,
+        );
+        for k in 0..n {
+            // The first one of the largest entries, so that rows are only swapped when it helps
+            let pivot = (k..n)
+                .rev()
+                .max_by(|a, b| {
+                    matrix.rows[*a].1[k]
+                        .abs()
+                        .total_cmp(&matrix.rows[*b].1[k].abs())
+                })
+                .unwrap();
+            let value = matrix.rows[pivot].1[k];
+            let mut cells = (k..n)
+                .map(|row| (row, k, ElementState::Frontier))
+                .collect::<Vec<(usize, usize, ElementState)>>();
+            if value.abs() < EPSILON {
+                self.push(
+                    format!(
+                        "Every entry of column {} from row {} down is zero, so there is no pivot for {}. The matrix is singular, and the system has either no solution or infinitely many of them",
+                        k + 1,
+                        k + 1,
+                        VARIABLES[k]
+                    ),
+                    &matrix,
+                    &solution,
+                    Highlights {
+                        cells,
+                        ..Default::default()
+                    },
+                    &operations,
+                    steps,
+                );
+                return;
+            }
+            cells[pivot - k].2 = ElementState::Selected;
+            self.push(
+                if k + 1 == n {
+                    format!(
+                        "The last pivot is {} in row {}, since there are no rows below it",
+                        format_number(value),
+                        n
+                    )
+                } else {
+                    format!(
+                        "The pivot of column {} is the entry with the largest magnitude from row {} down, which is {} in row {}. Dividing by a large pivot keeps the rounding errors small",
+                        k + 1,
+                        k + 1,
+                        format_number(value),
+                        pivot + 1
+                    )
+                },
+                &matrix,
+                &solution,
+                Highlights {
+                    cells,
+                    ..Default::default()
+                },
+                &operations,
+                steps,
+            );
+            if pivot != k {
+                matrix.rows.swap(k, pivot);
+                operations.push(format!("R{} ↔ R{}", k + 1, pivot + 1));
+                self.push(
+                    format!(
+                        "Swap rows {} and {} to move the pivot to the top of what is left",
+                        k + 1,
+                        pivot + 1
+                    ),
+                    &matrix,
+                    &solution,
+                    Highlights {
+                        rows: vec![(k, ElementState::Active), (pivot, ElementState::Active)],
+                        ..Default::default()
+                    },
+                    &operations,
+                    steps,
+                );
+            }
+            for i in k + 1..n {
+                let entry = matrix.rows[i].1[k];
+                let multiplier = entry / value;
+                let pivot_row = matrix.rows[k].1.clone();
+                let description = if multiplier.abs() < EPSILON {
+                    format!(
+                        "The entry below the pivot in row {} is already zero, so the row stays as it is",
+                        i + 1
+                    )
+                } else {
+                    for (entry, pivot_entry) in matrix.rows[i].1.iter_mut().zip(&pivot_row) {
+                        *entry -= multiplier * pivot_entry;
+                    }
+                    operations.push(format!("R{} ← {}", i + 1, row_operation(i, multiplier, k)));
+                    format!(
+                        "The multiplier of row {} is {} / {} = {}, so replacing it with {} makes its entry in column {} zero",
+                        i + 1,
+                        format_number(entry),
+                        format_number(value),
+                        format_number(multiplier),
+                        row_operation(i, multiplier, k),
+                        k + 1
+                    )
+                };
+                // Set the entry to exactly zero, without the rounding error of the subtraction
+                matrix.rows[i].1[k] = 0.0;
+                self.push(
+                    description,
+                    &matrix,
+                    &solution,
+                    Highlights {
+                        cells: vec![(k, k, ElementState::Selected)],
+                        rows: vec![(i, ElementState::Active)],
+                        operation: (multiplier.abs() >= EPSILON)
+                            .then(|| (i, row_operation(i, multiplier, k))),
+                        ..Default::default()
+                    },
+                    &operations,
+                    steps,
+                );
+            }
+        }
+        self.push(
+            "Every entry below the diagonal is zero, so the matrix is upper triangular. The last equation only has the last variable left, so the variables can be solved from the bottom up".to_string(),
+            &matrix,
+            &solution,
+            Highlights::default(),
+            &operations,
+            steps,
+        );
+        let mut values = vec![0.0; n];
+        for i in (0..n).rev() {
+            let row = &matrix.rows[i].1;
+            let known = (i + 1..n).map(|j| row[j] * values[j]).sum::<f64>();
+            values[i] = (row[n] - known) / row[i];
+            solution[i] = Some(values[i]);
+            // The known variables are moved to the right-hand side
+            let mut numerator = format_number(row[n]);
+            for j in i + 1..n {
+                numerator.push_str(&format!(
+                    " {} {} × {}",
+                    if row[j] < 0.0 { '+' } else { '−' },
+                    format_number(row[j].abs()),
+                    factor(values[j])
+                ));
+            }
+            if i + 1 < n {
+                numerator = format!("({})", numerator);
+            }
+            self.push(
+                format!(
+                    "Row {} gives {} = {} / {} = {}",
+                    i + 1,
+                    VARIABLES[i],
+                    numerator,
+                    factor(row[i]),
+                    format_number(values[i])
+                ),
+                &matrix,
+                &solution,
+                Highlights {
+                    cells: (i..=n).map(|j| (i, j, ElementState::Active)).collect(),
+                    solution: Some(i),
+                    ..Default::default()
+                },
+                &operations,
+                steps,
+            );
+        }
+        self.push(
+            format!(
+                "Every variable has been solved, so the solution is ({}). Elimination took {}, and solving a {} × {} system takes about n³ / 3 = {} multiplications",
+                values
+                    .iter()
+                    .map(|value| format_number(*value))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                plural(operations.len(), "row operation"),
+                n,
+                n,
+                format_number((n * n * n) as f64 / 3.0)
+            ),
+            &matrix,
+            &solution,
+            Highlights::default(),
+            &operations,
+            steps,
+        );
+    }
+}
+impl Visualizer for GaussianElimination {
+    fn diagram(&self) -> Diagram {
+        self.draw(
+            &Matrix {
+                rows: self.system.iter().cloned().enumerate().collect(),
+            },
+            &vec![None; self.system.len()],
+            &Highlights::default(),
+        )
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Eliminate",
+                vec![Input::Text {
+                    label: "Augmented matrix",
+                    default: self
+                        .system
+                        .iter()
+                        .map(|row| {
+                            row.iter()
+                                .map(|value| value.to_string())
+                                .collect::<Vec<String>>()
+                                .join(" ")
+                        })
+                        .collect::<Vec<String>>()
+                        .join("; "),
+                }],
+            ),
+            Operation::new(
+                "Random system",
+                vec![Input::Choice {
+                    label: "Size",
+                    choices: SIZES.iter().map(|size| size.to_string()).collect(),
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Eliminate" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                self.system = parse_system(&text)?;
+                self.eliminate(&mut steps);
+            }
+            "Random system" => {
+                let size = values.first().map(|value| value.number()).unwrap_or(3);
+                self.system = random_system(size.clamp(2, MAX_SIZE as i64) as usize);
+                self.eliminate(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// Subtracting the multiple of row `k` from row `i`, like `R3 − 2 × R1`, or `R3 + 2 × R1` for a negative multiplier.
+fn row_operation(i: usize, multiplier: f64, k: usize) -> String {
+    format!(
+        "R{} {} {} × R{}",
+        i + 1,
+        if multiplier < 0.0 { '+' } else { '−' },
+        format_number(multiplier.abs()),
+        k + 1
+    )
+}
+
+/// The number in parentheses if it is negative, so that it can be multiplied or divided by.
+fn factor(number: f64) -> String {
+    let text = format_number(number);
+    if text.starts_with('−') {
+        format!("({})", text)
+    } else {
+        text
+    }
+}
+
+/// Parses the rows of an augmented matrix, which are separated by semicolons, while the numbers in them are
+/// separated by commas or spaces.
+fn parse_system(text: &str) -> Result<Vec<Vec<f64>>, String> {
+    let system = text
+        .split([';', '\n'])
+        .filter(|row| !row.trim().is_empty())
+        .map(|row| {
+            row.split([',', ' '])
+                .filter(|part| !part.trim().is_empty())
+                .map(|part| part.trim().replace('−', "-").parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+        })
+        .collect::<Result<Vec<Vec<f64>>, _>>()
+        .map_err(|_| {
+            "The matrix must be rows of numbers separated by semicolons, like \"2 1 5; 1 3 10\""
+                .to_string()
+        })?;
+    let n = system.len();
+    if !(2..=MAX_SIZE).contains(&n) {
+        return Err(format!("There must be 2 to {} equations", MAX_SIZE));
+    }
+    if system.iter().any(|row| row.len() != n + 1) {
+        return Err(format!(
+            "Every row of a system of {} equations must have {} coefficients and a right-hand side",
+            n, n
+        ));
+    }
+    if system.iter().flatten().any(|value| value.abs() > 1000.0) {
+        return Err("The numbers must be between −1000 and 1000".to_string());
+    }
+    Ok(system)
+}
+
+/// A system with small whole coefficients and a whole solution.
+fn random_system(n: usize) -> Vec<Vec<f64>> {
+    let mut rng = rand::thread_rng();
+    let solution = (0..n).map(|_| rng.gen_range(-5..=5)).collect::<Vec<i32>>();
+    loop {
+        let coefficients = (0..n)
+            .map(|_| (0..n).map(|_| rng.gen_range(-9..=9)).collect())
+            .collect::<Vec<Vec<i32>>>();
+        if determinant(&coefficients) == 0 {
+            continue;
+        }
+        return coefficients
+            .iter()
+            .map(|row| {
+                let b = row.iter().zip(&solution).map(|(a, x)| a * x).sum::<i32>();
+                row.iter().chain([&b]).map(|value| *value as f64).collect()
+            })
+            .collect();
+    }
+}
+
+/// The determinant of a square matrix from its expansion along the first row, which is fine for small matrices.
+fn determinant(matrix: &[Vec<i32>]) -> i64 {
+    if matrix.len() == 1 {
+        return matrix[0][0] as i64;
+    }
+    (0..matrix.len())
+        .map(|column| {
+            let minor = matrix[1..]
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(j, _)| *j != column)
+                        .map(|(_, value)| *value)
+                        .collect()
+                })
+                .collect::<Vec<Vec<i32>>>();
+            let sign = if column % 2 == 0 { 1 } else { -1 };
+            sign * matrix[0][column] as i64 * determinant(&minor)
+        })
+        .sum()
+}
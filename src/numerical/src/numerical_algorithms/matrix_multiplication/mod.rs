@@ -0,0 +1,540 @@
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const SIZES: [&str; 2] = ["4", "2"];
+const GRIDS_TOP: f32 = 70.0;
+/// The widest that a grid can be, so that four of them fit next to each other.
+const GRID_SIZE: f32 = 160.0;
+const GRID_GAP: f32 = 30.0;
+const CHART_HEIGHT: f32 = 170.0;
+/// The largest size in the chart, as a power of two.
+const CHART_POWERS: u32 = 6;
+const QUADRANTS: [&str; 4] = ["₁₁", "₁₂", "₂₁", "₂₂"];
+const PRODUCTS: [&str; 7] = ["M₁", "M₂", "M₃", "M₄", "M₅", "M₆", "M₇"];
+
+/// A sum of blocks of a matrix, given as their quadrants and signs.
+type BlockSum = &'static [(usize, i64)];
+
+/// The seven products of Strassen's algorithm, as the sums of blocks of A and B that are multiplied.
+const STRASSEN_PRODUCTS: [(BlockSum, BlockSum); 7] = [
+    (&[(0, 1), (3, 1)], &[(0, 1), (3, 1)]),
+    (&[(2, 1), (3, 1)], &[(0, 1)]),
+    (&[(0, 1)], &[(1, 1), (3, -1)]),
+    (&[(3, 1)], &[(2, 1), (0, -1)]),
+    (&[(0, 1), (1, 1)], &[(3, 1)]),
+    (&[(2, 1), (0, -1)], &[(0, 1), (1, 1)]),
+    (&[(1, 1), (3, -1)], &[(2, 1), (3, 1)]),
+];
+/// The quadrants of C as sums of the products.
+const STRASSEN_SUMS: [BlockSum; 4] = [
+    &[(0, 1), (3, 1), (4, -1), (6, 1)],
+    &[(2, 1), (4, 1)],
+    &[(1, 1), (3, 1)],
+    &[(0, 1), (1, -1), (2, 1), (5, 1)],
+];
+
+type Matrix = Vec<Vec<i64>>;
+
+/// The highlights of the current step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    /// The cells of A, B and C that are used, as rows and columns.
+    a: Vec<(usize, usize)>,
+    b: Vec<(usize, usize)>,
+    c: Vec<(usize, usize)>,
+    /// True if the cells are colored by the quadrant that they are in.
+    quadrants: bool,
+    /// The product of Strassen's algorithm that is shown next to C, as its index and value.
+    product: Option<(usize, Matrix)>,
+}
+
+/// Multiplying two square matrices, either naively with a dot product of a row and a column for every cell, or with
+/// Strassen's algorithm, which splits both matrices into four blocks and combines seven products of sums of them
+/// instead of the eight products that the blocks would naively need. The numbers of multiplications that both take
+/// are charted for growing sizes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatrixMultiplication {
+    a: Matrix,
+    b: Matrix,
+}
+impl MatrixMultiplication {
+    pub fn new(a: Matrix, b: Matrix) -> Self {
+        Self { a, b }
+    }
+    fn cell_size(&self) -> f32 {
+        (GRID_SIZE / self.a.len() as f32).min(40.0)
+    }
+    fn grid_left(index: usize) -> f32 {
+        40.0 + index as f32 * (GRID_SIZE + GRID_GAP)
+    }
+    fn draw_grid(
+        &self,
+        diagram: &mut Diagram,
+        key: &str,
+        index: usize,
+        matrix: &[Vec<Option<i64>>],
+        states: (&[(usize, usize)], Option<ElementState>),
+        quadrants: bool,
+    ) {
+        let size = self.cell_size();
+        let left = Self::grid_left(index);
+        let half = (matrix.len() / 2).max(1);
+        diagram.add_node(DiagramNode::text(
+            format!("{}-title", key),
+            key.to_uppercase(),
+            (left + matrix.len() as f32 * size / 2.0, GRIDS_TOP - 24.0),
+        ));
+        for (i, row) in matrix.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                let mut node = DiagramNode::rect(
+                    format!("{}-{}-{}", key, i, j),
+                    value
+                        .map(|value| format_number(value as f64))
+                        .unwrap_or_default(),
+                    (
+                        left + (j as f32 + 0.5) * size,
+                        GRIDS_TOP + (i as f32 + 0.5) * size,
+                    ),
+                    (size - 4.0, size - 4.0),
+                );
+                if quadrants {
+                    node.fill = Fill::Group(i / half * 2 + j / half);
+                }
+                if states.0.contains(&(i, j)) {
+                    node.state = states.1;
+                }
+                diagram.add_node(node);
+            }
+        }
+    }
+    /// Draws A, B and the computed cells of C, and charts the multiplications that the methods take with the size of
+    /// the matrices highlighted.
+    fn draw(&self, c: &[Vec<Option<i64>>], highlights: &Highlights) -> Diagram {
+        let n = self.a.len();
+        let known = |matrix: &Matrix| {
+            matrix
+                .iter()
+                .map(|row| row.iter().map(|value| Some(*value)).collect())
+                .collect::<Vec<Vec<Option<i64>>>>()
+        };
+        let chart_top = GRIDS_TOP + GRID_SIZE + 90.0;
+        let mut diagram = Diagram::new(CANVAS_WIDTH, chart_top + CHART_HEIGHT + 50.0);
+        let active = Some(ElementState::Active);
+        self.draw_grid(
+            &mut diagram,
+            "a",
+            0,
+            &known(&self.a),
+            (&highlights.a, active),
+            highlights.quadrants,
+        );
+        self.draw_grid(
+            &mut diagram,
+            "b",
+            1,
+            &known(&self.b),
+            (&highlights.b, active),
+            highlights.quadrants,
+        );
+        self.draw_grid(
+            &mut diagram,
+            "c",
+            2,
+            c,
+            (&highlights.c, Some(ElementState::Selected)),
+            highlights.quadrants,
+        );
+        let middle = GRIDS_TOP + n as f32 * self.cell_size() / 2.0;
+        for (index, symbol) in ["×", "="].iter().enumerate() {
+            diagram.add_node(DiagramNode::text(
+                format!("symbol-{}", index),
+                symbol,
+                (Self::grid_left(index + 1) - GRID_GAP / 2.0, middle),
+            ));
+        }
+        if let Some((index, product)) = &highlights.product {
+            self.draw_grid(&mut diagram, "m", 3, &known(product), (&[], None), false);
+            diagram.node_mut("m-title").unwrap().label = PRODUCTS[*index].to_string();
+        }
+        let chart = Chart::new(
+            (80.0, chart_top),
+            (CANVAS_WIDTH - 160.0, CHART_HEIGHT),
+            (
+                CHART_POWERS as f32 + 0.5,
+                naive_count(1 << CHART_POWERS) as f32,
+            ),
+        )
+        .with_min((-0.5, 0.0));
+        chart.draw_axes(
+            &mut diagram,
+            "chart",
+            "Multiplications of n × n matrices, naive and Strassen",
+            ("", naive_count(1 << CHART_POWERS)),
+        );
+        for power in 0..=CHART_POWERS {
+            let size = 1 << power;
+            let state = (size == n).then_some(ElementState::Active);
+            chart
+                .draw_bar(
+                    &mut diagram,
+                    (power as f32 - 0.2, naive_count(size) as f32),
+                    0.35,
+                )
+                .state = state;
+            chart
+                .draw_bar(
+                    &mut diagram,
+                    (power as f32 + 0.2, strassen_count(size) as f32),
+                    0.35,
+                )
+                .state = state.or(Some(ElementState::Selected));
+            diagram.add_node(DiagramNode::text(
+                format!("chart-size-{}", power),
+                format!("n = {}", size),
+                (
+                    chart.point((power as f32, 0.0)).0,
+                    chart_top + CHART_HEIGHT + 16.0,
+                ),
+            ));
+        }
+        diagram
+    }
+    fn push(
+        &self,
+        description: String,
+        c: &[Vec<Option<i64>>],
+        highlights: Highlights,
+        multiplications: usize,
+        steps: &mut DiagramSteps,
+    ) {
+        let n = self.a.len();
+        let step = steps.push(description, self.draw(c, &highlights));
+        step.lists = vec![StepList::new(
+            "Multiplications",
+            vec![
+                format!("So far: {}", multiplications),
+                format!("Naive: {}", naive_count(n)),
+                format!("Strassen: {}", strassen_count(n)),
+            ],
+        )];
+    }
+    fn naive(&self, steps: &mut DiagramSteps) {
+        let n = self.a.len();
+        let mut c = vec![vec![None; n]; n];
+        self.push(
+            format!(
+                "Every cell of C is the dot product of a row of A and a column of B, so the {} cells take {} multiplications each",
+                n * n,
+                n
+            ),
+            &c,
+            Highlights::default(),
+            0,
+            steps,
+        );
+        let mut multiplications = 0;
+        for i in 0..n {
+            for j in 0..n {
+                let value = (0..n).map(|k| self.a[i][k] * self.b[k][j]).sum::<i64>();
+                c[i][j] = Some(value);
+                multiplications += n;
+                let terms = (0..n)
+                    .map(|k| format!("{} × {}", self.a[i][k], self.b[k][j]))
+                    .collect::<Vec<String>>()
+                    .join(" + ");
+                self.push(
+                    format!(
+                        "C{}{} is row {} of A times column {} of B: {} = {}",
+                        subscript(i + 1),
+                        subscript(j + 1),
+                        i + 1,
+                        j + 1,
+                        terms,
+                        value
+                    ),
+                    &c,
+                    Highlights {
+                        a: (0..n).map(|k| (i, k)).collect(),
+                        b: (0..n).map(|k| (k, j)).collect(),
+                        c: vec![(i, j)],
+                        ..Default::default()
+                    },
+                    multiplications,
+                    steps,
+                );
+            }
+        }
+        self.push(
+            format!(
+                "Every cell has been computed with {} = {}³ multiplications. Doubling the size makes it 8 times as many",
+                multiplications, n
+            ),
+            &c,
+            Highlights::default(),
+            multiplications,
+            steps,
+        );
+    }
+    fn strassen(&self, steps: &mut DiagramSteps) {
+        let n = self.a.len();
+        let half = n / 2;
+        let mut c = vec![vec![None; n]; n];
+        let quadrant_cells = |quadrant: usize| {
+            let (top, left) = (quadrant / 2 * half, quadrant % 2 * half);
+            (top..top + half)
+                .flat_map(|i| (left..left + half).map(move |j| (i, j)))
+                .collect::<Vec<(usize, usize)>>()
+        };
+        self.push(
+            format!(
+                "Split A, B and C into four blocks of size {} × {}. Multiplying the blocks like numbers would take 8 products of blocks, but Strassen's algorithm gets by with 7 products of sums of them",
+                half, half
+            ),
+            &c,
+            Highlights {
+                quadrants: true,
+                ..Default::default()
+            },
+            0,
+            steps,
+        );
+        let mut multiplications = 0;
+        let mut products = vec![];
+        for (index, (a_terms, b_terms)) in STRASSEN_PRODUCTS.iter().enumerate() {
+            let left = block_sum(&self.a, a_terms);
+            let right = block_sum(&self.b, b_terms);
+            let mut count = 0;
+            let product = strassen_product(&left, &right, &mut count);
+            multiplications += count;
+            let how = if half == 1 {
+                format!(
+                    "which is the product of two numbers: {} × {} = {}",
+                    factor(left[0][0]),
+                    factor(right[0][0]),
+                    format_number(product[0][0] as f64)
+                )
+            } else {
+                format!(
+                    "which is computed recursively with Strassen's algorithm in {}",
+                    plural(count, "multiplication")
+                )
+            };
+            self.push(
+                format!(
+                    "{} = {}{}, {}",
+                    PRODUCTS[index],
+                    sum_name("A", a_terms),
+                    sum_name("B", b_terms),
+                    how
+                ),
+                &c,
+                Highlights {
+                    a: a_terms
+                        .iter()
+                        .flat_map(|(q, _)| quadrant_cells(*q))
+                        .collect(),
+                    b: b_terms
+                        .iter()
+                        .flat_map(|(q, _)| quadrant_cells(*q))
+                        .collect(),
+                    quadrants: true,
+                    product: Some((index, product.clone())),
+                    ..Default::default()
+                },
+                multiplications,
+                steps,
+            );
+            products.push(product);
+        }
+        for (quadrant, terms) in STRASSEN_SUMS.iter().enumerate() {
+            let block = terms
+                .iter()
+                .fold(vec![vec![0; half]; half], |sum, (index, sign)| {
+                    add(&sum, &products[*index], *sign)
+                });
+            let (top, left) = (quadrant / 2 * half, quadrant % 2 * half);
+            for (i, row) in block.iter().enumerate() {
+                for (j, value) in row.iter().enumerate() {
+                    c[top + i][left + j] = Some(*value);
+                }
+            }
+            let name = terms
+                .iter()
+                .enumerate()
+                .map(|(i, (index, sign))| {
+                    let sign = match (i, *sign) {
+                        (0, _) => "",
+                        (_, 1) => " + ",
+                        _ => " − ",
+                    };
+                    format!("{}{}", sign, PRODUCTS[*index])
+                })
+                .collect::<String>();
+            self.push(
+                format!(
+                    "C{} = {}, which only takes additions and subtractions",
+                    QUADRANTS[quadrant], name
+                ),
+                &c,
+                Highlights {
+                    c: quadrant_cells(quadrant),
+                    quadrants: true,
+                    ..Default::default()
+                },
+                multiplications,
+                steps,
+            );
+        }
+        self.push(
+            format!(
+                "C has been computed with {} instead of {}. Doubling the size makes it 7 instead of 8 times as many, so Strassen's algorithm takes O(n^2.81) multiplications, but it needs many more additions, so it only pays off for large matrices",
+                plural(multiplications, "multiplication"),
+                naive_count(n)
+            ),
+            &c,
+            Highlights {
+                quadrants: true,
+                ..Default::default()
+            },
+            multiplications,
+            steps,
+        );
+    }
+}
+impl Visualizer for MatrixMultiplication {
+    fn diagram(&self) -> Diagram {
+        let n = self.a.len();
+        self.draw(&vec![vec![None; n]; n], &Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new("Naive", vec![]),
+            Operation::new("Strassen", vec![]),
+            Operation::new(
+                "Random matrices",
+                vec![Input::Choice {
+                    label: "Size",
+                    choices: SIZES.iter().map(|size| size.to_string()).collect(),
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Naive" => self.naive(&mut steps),
+            "Strassen" => self.strassen(&mut steps),
+            "Random matrices" => {
+                let size = values.first().map(|value| value.number()).unwrap_or(4);
+                let size = if size == 2 { 2 } else { 4 };
+                *self = Self::new(random_matrix(size), random_matrix(size));
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The number in parentheses if it is negative, so that it can be multiplied.
+fn factor(number: i64) -> String {
+    if number < 0 {
+        format!("({})", format_number(number as f64))
+    } else {
+        number.to_string()
+    }
+}
+
+fn subscript(number: usize) -> char {
+    ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉'][number]
+}
+
+/// The number of multiplications of the naive method for matrices of the size.
+fn naive_count(n: usize) -> usize {
+    n * n * n
+}
+
+/// The number of multiplications of Strassen's algorithm for matrices of the size, which must be a power of two.
+fn strassen_count(n: usize) -> usize {
+    7usize.pow(n.trailing_zeros())
+}
+
+/// The name of a sum of blocks, like `(A₁₁ + A₂₂)`, which is only in parentheses if it has more than one block.
+fn sum_name(matrix: &str, terms: BlockSum) -> String {
+    let sum = terms
+        .iter()
+        .enumerate()
+        .map(|(i, (quadrant, sign))| {
+            let sign = match (i, *sign) {
+                (0, _) => "",
+                (_, 1) => " + ",
+                _ => " − ",
+            };
+            format!("{}{}{}", sign, matrix, QUADRANTS[*quadrant])
+        })
+        .collect::<String>();
+    if terms.len() > 1 {
+        format!("({})", sum)
+    } else {
+        sum
+    }
+}
+
+/// The sum of the quadrants of the matrix with their signs, or a zero matrix of the size of a quadrant if there are
+/// no terms.
+fn block_sum(matrix: &Matrix, terms: BlockSum) -> Matrix {
+    let half = matrix.len() / 2;
+    let mut sum = vec![vec![0; half]; half];
+    for (quadrant, sign) in terms {
+        let (top, left) = (quadrant / 2 * half, quadrant % 2 * half);
+        for (i, row) in sum.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value += sign * matrix[top + i][left + j];
+            }
+        }
+    }
+    sum
+}
+
+fn add(a: &Matrix, b: &Matrix, sign: i64) -> Matrix {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| a.iter().zip(b).map(|(a, b)| a + sign * b).collect())
+        .collect()
+}
+
+/// The product of the matrices with Strassen's algorithm, counting the multiplications of numbers.
+fn strassen_product(a: &Matrix, b: &Matrix, count: &mut usize) -> Matrix {
+    let n = a.len();
+    if n == 1 {
+        *count += 1;
+        return vec![vec![a[0][0] * b[0][0]]];
+    }
+    let products = STRASSEN_PRODUCTS
+        .iter()
+        .map(|(a_terms, b_terms)| {
+            strassen_product(&block_sum(a, a_terms), &block_sum(b, b_terms), count)
+        })
+        .collect::<Vec<Matrix>>();
+    let half = n / 2;
+    let mut c = vec![vec![0; n]; n];
+    for (quadrant, terms) in STRASSEN_SUMS.iter().enumerate() {
+        let (top, left) = (quadrant / 2 * half, quadrant % 2 * half);
+        for (index, sign) in terms.iter() {
+            for (i, row) in products[*index].iter().enumerate() {
+                for (j, value) in row.iter().enumerate() {
+                    c[top + i][left + j] += sign * value;
+                }
+            }
+        }
+    }
+    c
+}
+
+fn random_matrix(n: usize) -> Matrix {
+    let mut rng = rand::thread_rng();
+    (0..n)
+        .map(|_| (0..n).map(|_| rng.gen_range(0..=9)).collect())
+        .collect()
+}
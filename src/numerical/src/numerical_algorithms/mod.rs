@@ -0,0 +1,12 @@
+//! A collection of algorithms that compute with real and complex numbers, like transforms of signals.
+mod fast_fourier_transform;
+mod gaussian_elimination;
+mod matrix_multiplication;
+mod monte_carlo_pi;
+mod root_finding;
+
+pub use fast_fourier_transform::FastFourierTransform;
+pub use gaussian_elimination::GaussianElimination;
+pub use matrix_multiplication::MatrixMultiplication;
+pub use monte_carlo_pi::MonteCarloPi;
+pub use root_finding::RootFinding;
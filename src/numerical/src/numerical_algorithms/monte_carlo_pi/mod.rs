@@ -0,0 +1,213 @@
+use std::f64::consts::PI;
+
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const CANVAS_HEIGHT: f32 = 460.0;
+const MAX_POINTS: u64 = 5000;
+const MAX_RATE: u64 = 500;
+/// The most steps that a simulation can take, which keeps the number of diagrams reasonable.
+const MAX_STEPS: u64 = 200;
+const SQUARE_POSITION: (f32, f32) = (20.0, 40.0);
+const SQUARE_SIZE: f32 = 400.0;
+const POINT_SIZE: f32 = 4.0;
+
+/// Estimating π by throwing random points into a square with a circle inside of it. The circle covers π/4 of the
+/// square, so four times the share of the points that land inside of it approaches π as more points are thrown. The
+/// estimate after every batch of points is charted next to the square.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonteCarloPi {
+    points: u64,
+    rate: u64,
+    /// The points that have been thrown, between 0 and 1 on both axes.
+    thrown: Vec<(f64, f64)>,
+}
+impl MonteCarloPi {
+    pub fn new(points: u64, rate: u64) -> Self {
+        Self {
+            points,
+            rate,
+            thrown: vec![],
+        }
+    }
+    /// Draws the first `count` thrown points, where the last `latest` of them are highlighted, and the estimates after
+    /// every batch of them.
+    fn draw(&self, count: usize, latest: usize, estimates: &[(f32, f32)]) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let (left, top) = SQUARE_POSITION;
+        let (right, bottom) = (left + SQUARE_SIZE, top + SQUARE_SIZE);
+        diagram.add_figure(Figure::new(FigureKind::Polyline(vec![
+            (left, top),
+            (right, top),
+            (right, bottom),
+            (left, bottom),
+            (left, top),
+        ])));
+        diagram.add_figure(Figure::new(FigureKind::Circle {
+            center: (left + SQUARE_SIZE / 2.0, top + SQUARE_SIZE / 2.0),
+            radius: SQUARE_SIZE / 2.0,
+        }));
+        diagram.add_node(DiagramNode::text(
+            "square-title",
+            format!("{} thrown", plural(count, "point")),
+            (left + SQUARE_SIZE / 2.0, top - 20.0),
+        ));
+        for (i, (x, y)) in self.thrown.iter().take(count).enumerate() {
+            let state = if i + latest >= count {
+                ElementState::Active
+            } else if inside(*x, *y) {
+                ElementState::Selected
+            } else {
+                ElementState::Rejected
+            };
+            diagram.add_figure(
+                Figure::new(FigureKind::Rect {
+                    position: (
+                        left + *x as f32 * SQUARE_SIZE - POINT_SIZE / 2.0,
+                        top + *y as f32 * SQUARE_SIZE - POINT_SIZE / 2.0,
+                    ),
+                    size: (POINT_SIZE, POINT_SIZE),
+                })
+                .with_state(state),
+            );
+        }
+        let chart = Chart::new(
+            (right + 80.0, top + 40.0),
+            (CANVAS_WIDTH - right - 120.0, SQUARE_SIZE - 80.0),
+            (self.points as f32, 4.0),
+        )
+        .with_min((0.0, 2.0));
+        chart.draw_axes(
+            &mut diagram,
+            "chart",
+            "Estimate of π, and π dashed",
+            (self.points, 4),
+        );
+        chart
+            .draw_line(
+                &mut diagram,
+                &[(0.0, PI as f32), (self.points as f32, PI as f32)],
+            )
+            .dashed = true;
+        if !estimates.is_empty() {
+            chart.draw_line(&mut diagram, estimates).state = Some(ElementState::Selected);
+        }
+        diagram
+    }
+    fn simulate(&mut self, steps: &mut DiagramSteps) {
+        let mut rng = rand::thread_rng();
+        self.thrown = (0..self.points).map(|_| (rng.gen(), rng.gen())).collect();
+        let step = steps.push(
+            format!(
+                "Throw {} into the square at random, {} at a time. The circle covers π/4 of the square, so about π/4 of the points should land inside of it",
+                plural(self.points as usize, "point"),
+                self.rate
+            ),
+            self.draw(0, 0, &[]),
+        );
+        step.lists = vec![estimate_list(0, 0)];
+        let mut inside_count = 0;
+        let mut estimates = vec![];
+        let mut thrown = 0;
+        while thrown < self.thrown.len() {
+            let batch = (self.rate as usize).min(self.thrown.len() - thrown);
+            let hits = self.thrown[thrown..thrown + batch]
+                .iter()
+                .filter(|(x, y)| inside(*x, *y))
+                .count();
+            inside_count += hits;
+            thrown += batch;
+            let estimate = 4.0 * inside_count as f64 / thrown as f64;
+            estimates.push((thrown as f32, estimate as f32));
+            let step = steps.push(
+                format!(
+                    "{} of the {} landed inside the circle. So far {} of {} points are inside, so π ≈ 4 × {} / {} = {:.4}",
+                    hits,
+                    plural(batch, "new point"),
+                    inside_count,
+                    thrown,
+                    inside_count,
+                    thrown,
+                    estimate
+                ),
+                self.draw(thrown, batch, &estimates),
+            );
+            step.lists = vec![estimate_list(thrown, inside_count)];
+        }
+        let estimate = 4.0 * inside_count as f64 / thrown as f64;
+        let step = steps.push(
+            format!(
+                "All of the points have been thrown, and the estimate is {:.4}, which is {:.4} off from π. The error shrinks like 1 / √n, so every correct digit takes about 100 times as many points",
+                estimate,
+                (estimate - PI).abs()
+            ),
+            self.draw(thrown, 0, &estimates),
+        );
+        step.lists = vec![estimate_list(thrown, inside_count)];
+    }
+}
+impl Visualizer for MonteCarloPi {
+    fn diagram(&self) -> Diagram {
+        self.draw(0, 0, &[])
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![Operation::new(
+            "Throw points",
+            vec![
+                Input::Number {
+                    label: "Points",
+                    default: self.points as i64,
+                    min: 1,
+                    max: MAX_POINTS as i64,
+                },
+                Input::Number {
+                    label: "Points per step",
+                    default: self.rate as i64,
+                    min: 1,
+                    max: MAX_RATE as i64,
+                },
+            ],
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Throw points" => {
+                let points = parse_number(values.first(), "number of points", 1, MAX_POINTS)?;
+                let rate = parse_number(values.get(1), "number of points per step", 1, MAX_RATE)?;
+                if points.div_ceil(rate) > MAX_STEPS {
+                    return Err(format!(
+                        "A simulation can take at most {} steps, so at least {} of the {} points must be thrown per step",
+                        MAX_STEPS,
+                        points.div_ceil(MAX_STEPS),
+                        points
+                    ));
+                }
+                *self = Self::new(points, rate);
+                self.simulate(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// True if the point is inside the circle, which touches the sides of the square.
+fn inside(x: f64, y: f64) -> bool {
+    (x - 0.5).powi(2) + (y - 0.5).powi(2) <= 0.25
+}
+
+fn estimate_list(thrown: usize, inside_count: usize) -> StepList {
+    let mut items = vec![
+        format!("Thrown: {}", thrown),
+        format!("Inside: {}", inside_count),
+    ];
+    if thrown > 0 {
+        let estimate = 4.0 * inside_count as f64 / thrown as f64;
+        items.push(format!("π ≈ {:.4}", estimate));
+        items.push(format!("Error: {:.4}", (estimate - PI).abs()));
+    }
+    StepList::new("Estimate", items)
+}
@@ -0,0 +1,627 @@
+use diagram::*;
+
+use crate::*;
+
+const CANVAS_HEIGHT: f32 = 600.0;
+const PLOT_POSITION: (f32, f32) = (70.0, 50.0);
+const PLOT_SIZE: (f32, f32) = (CANVAS_WIDTH - 130.0, 300.0);
+const CONVERGENCE_POSITION: (f32, f32) = (70.0, 430.0);
+const CONVERGENCE_SIZE: (f32, f32) = (CANVAS_WIDTH - 130.0, 120.0);
+/// How many points the curve of the function is drawn through.
+const CURVE_POINTS: usize = 200;
+const POINT_RADIUS: f32 = 5.0;
+const MAX_NEWTON_ITERATIONS: usize = 12;
+const MAX_BISECTION_ITERATIONS: usize = 40;
+/// How close to the root the methods have to get, which is about the precision of the numbers.
+const TOLERANCE: f64 = 1e-12;
+/// The most correct digits that are charted.
+const MAX_DIGITS: f64 = 15.0;
+
+/// A function whose roots are found, with an interval of x to plot and the default start and bracket.
+#[derive(Clone, Copy, Debug)]
+struct Function {
+    name: &'static str,
+    f: fn(f64) -> f64,
+    derivative: fn(f64) -> f64,
+    range: (f64, f64),
+    start: f64,
+    bracket: (f64, f64),
+}
+
+const FUNCTIONS: [Function; 5] = [
+    Function {
+        name: "x³ − 2x − 5",
+        f: |x| x * x * x - 2.0 * x - 5.0,
+        derivative: |x| 3.0 * x * x - 2.0,
+        range: (-1.0, 3.5),
+        start: 3.0,
+        bracket: (2.0, 3.0),
+    },
+    Function {
+        name: "x² − 2",
+        f: |x| x * x - 2.0,
+        derivative: |x| 2.0 * x,
+        range: (0.0, 3.0),
+        start: 3.0,
+        bracket: (1.0, 2.0),
+    },
+    Function {
+        name: "cos x − x",
+        f: |x| x.cos() - x,
+        derivative: |x| -x.sin() - 1.0,
+        range: (-1.0, 2.0),
+        start: 2.0,
+        bracket: (0.0, 1.0),
+    },
+    Function {
+        name: "x³ − 2x + 2",
+        f: |x| x * x * x - 2.0 * x + 2.0,
+        derivative: |x| 3.0 * x * x - 2.0,
+        range: (-2.5, 2.0),
+        start: 0.0,
+        bracket: (-2.0, -1.0),
+    },
+    Function {
+        name: "arctan x",
+        f: |x| x.atan(),
+        derivative: |x| 1.0 / (1.0 + x * x),
+        range: (-6.0, 6.0),
+        start: 1.5,
+        bracket: (-1.0, 2.0),
+    },
+];
+
+/// The method that is being animated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Method {
+    Newton,
+    Bisection,
+}
+
+/// Finding a root of a function, where it is zero, either with Newton's method, which follows the tangent of the
+/// curve at the current guess down to where it crosses zero, or with bisection, which halves an interval where the
+/// function changes sign. Both are plotted on the same curve, and the correct digits that they have after every
+/// iteration are charted below it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RootFinding {
+    function: usize,
+    start: f64,
+    bracket: (f64, f64),
+}
+impl RootFinding {
+    pub fn new() -> Self {
+        let function = FUNCTIONS[0];
+        Self {
+            function: 0,
+            start: function.start,
+            bracket: function.bracket,
+        }
+    }
+    fn function(&self) -> Function {
+        FUNCTIONS[self.function]
+    }
+    fn f(&self, x: f64) -> f64 {
+        (self.function().f)(x)
+    }
+    /// The chart of the function, which fits the curve in its range and always shows zero.
+    fn plot(&self) -> Chart {
+        let (low, high) = self.function().range;
+        let values = (0..=CURVE_POINTS)
+            .map(|i| self.f(low + (high - low) * i as f64 / CURVE_POINTS as f64))
+            .collect::<Vec<f64>>();
+        let bottom = values.iter().copied().fold(0.0, f64::min);
+        let top = values.iter().copied().fold(0.0, f64::max);
+        let margin = (top - bottom) * 0.05;
+        Chart::new(
+            PLOT_POSITION,
+            PLOT_SIZE,
+            (high as f32, (top + margin) as f32),
+        )
+        .with_min((low as f32, (bottom - margin) as f32))
+    }
+    /// Draws the curve of the function, the correct digits after every iteration of both methods, and the figures of
+    /// the current iteration, which are added by `draw_iteration`.
+    fn draw(
+        &self,
+        method: Method,
+        iterations: usize,
+        draw_iteration: impl FnOnce(&mut Diagram, &Chart),
+    ) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+        let function = self.function();
+        let chart = self.plot();
+        chart.draw_axes(
+            &mut diagram,
+            "plot",
+            format!(
+                "f(x) = {} for x from {} to {}",
+                function.name,
+                format_number(function.range.0),
+                format_number(function.range.1)
+            ),
+            ("x", format_number(chart.max.1 as f64)),
+        );
+        let (low, high) = function.range;
+        chart.draw_line(
+            &mut diagram,
+            &(0..=CURVE_POINTS)
+                .map(|i| {
+                    let x = low + (high - low) * i as f64 / CURVE_POINTS as f64;
+                    (x as f32, self.f(x) as f32)
+                })
+                .collect::<Vec<(f32, f32)>>(),
+        );
+        draw_iteration(&mut diagram, &chart);
+        self.draw_convergence(&mut diagram, method, iterations);
+        diagram
+    }
+    /// Charts the correct digits of both methods, with the first `iterations` of the current method highlighted.
+    fn draw_convergence(&self, diagram: &mut Diagram, method: Method, iterations: usize) {
+        let root = self.reference_root();
+        let digits = |guesses: Vec<f64>| {
+            guesses
+                .iter()
+                .enumerate()
+                .map(|(i, x)| {
+                    let error = (x - root).abs();
+                    let digits = if error == 0.0 {
+                        MAX_DIGITS
+                    } else {
+                        (-error.log10()).clamp(0.0, MAX_DIGITS)
+                    };
+                    (i as f32, digits as f32)
+                })
+                .collect::<Vec<(f32, f32)>>()
+        };
+        let newton = digits(self.newton_guesses());
+        let bisection = digits(self.bisection_intervals().iter().map(midpoint).collect());
+        let chart = Chart::new(
+            CONVERGENCE_POSITION,
+            CONVERGENCE_SIZE,
+            (
+                newton.len().max(bisection.len()).max(2) as f32 - 1.0,
+                MAX_DIGITS as f32,
+            ),
+        );
+        chart.draw_axes(
+            diagram,
+            "convergence",
+            "Correct digits after every iteration, Newton's method and bisection",
+            ("iteration", MAX_DIGITS),
+        );
+        for (series, series_method) in [(newton, Method::Newton), (bisection, Method::Bisection)] {
+            let line = if series_method == method {
+                chart.draw_line(diagram, &series[..iterations.min(series.len())])
+            } else {
+                chart.draw_line(diagram, &series)
+            };
+            line.state = match series_method {
+                Method::Newton => Some(ElementState::Selected),
+                Method::Bisection => Some(ElementState::Frontier),
+            };
+            line.dashed = series_method != method;
+        }
+    }
+    /// The root that the digits are compared to, which is found by bisecting the default bracket as far as it goes.
+    fn reference_root(&self) -> f64 {
+        let (mut a, mut b) = self.function().bracket;
+        for _ in 0..200 {
+            let m = (a + b) / 2.0;
+            if self.f(a).signum() == self.f(m).signum() {
+                a = m;
+            } else {
+                b = m;
+            }
+        }
+        (a + b) / 2.0
+    }
+    /// The guesses of Newton's method from the start, until it converges, fails or runs out of iterations.
+    fn newton_guesses(&self) -> Vec<f64> {
+        let function = self.function();
+        let mut guesses = vec![self.start];
+        let mut x = self.start;
+        while guesses.len() <= MAX_NEWTON_ITERATIONS && self.f(x).abs() > TOLERANCE {
+            let slope = (function.derivative)(x);
+            if slope == 0.0 {
+                break;
+            }
+            let next = x - self.f(x) / slope;
+            guesses.push(next);
+            // The guesses can't be followed once they leave the plot
+            if next < function.range.0 || next > function.range.1 || (next - x).abs() < TOLERANCE {
+                break;
+            }
+            x = next;
+        }
+        guesses
+    }
+    /// The intervals of bisection from the bracket, until they are small enough.
+    fn bisection_intervals(&self) -> Vec<(f64, f64)> {
+        let (mut a, mut b) = self.bracket;
+        let mut intervals = vec![(a, b)];
+        while intervals.len() <= MAX_BISECTION_ITERATIONS && b - a > TOLERANCE {
+            let m = (a + b) / 2.0;
+            if self.f(m) == 0.0 {
+                intervals.push((m, m));
+                break;
+            }
+            if self.f(a).signum() == self.f(m).signum() {
+                a = m;
+            } else {
+                b = m;
+            }
+            intervals.push((a, b));
+        }
+        intervals
+    }
+    fn point(&self, diagram: &mut Diagram, chart: &Chart, key: &str, x: f64, state: ElementState) {
+        diagram.add_node(
+            DiagramNode::new(
+                key,
+                "",
+                chart.point((x as f32, self.f(x) as f32)),
+                Shape::Circle {
+                    radius: POINT_RADIUS,
+                },
+            )
+            .with_state(state),
+        );
+    }
+    fn newton(&self, steps: &mut DiagramSteps) {
+        let function = self.function();
+        let guesses = self.newton_guesses();
+        let list = |count: usize| {
+            vec![StepList::new(
+                "Guesses",
+                guesses
+                    .iter()
+                    .take(count)
+                    .enumerate()
+                    .map(|(i, x)| format!("x{} = {}", subscript(i), decimal(*x)))
+                    .collect(),
+            )]
+        };
+        let step = steps.push(
+            format!(
+                "Find a root of f(x) = {} with Newton's method, starting from the guess x₀ = {}",
+                function.name,
+                decimal(self.start)
+            ),
+            self.draw(Method::Newton, 1, |diagram, chart| {
+                self.point(diagram, chart, "guess", self.start, ElementState::Active);
+            }),
+        );
+        step.lists = list(1);
+        for (i, pair) in guesses.windows(2).enumerate() {
+            let (x, next) = (pair[0], pair[1]);
+            let (value, slope) = (self.f(x), (function.derivative)(x));
+            let step = steps.push(
+                format!(
+                    "f(x{}) = {} and the slope there is f'(x{}) = {}, so the tangent crosses zero at x{} = x{} − f(x{}) / f'(x{}) = {}",
+                    subscript(i),
+                    decimal(value),
+                    subscript(i),
+                    decimal(slope),
+                    subscript(i + 1),
+                    subscript(i),
+                    subscript(i),
+                    subscript(i),
+                    decimal(next)
+                ),
+                self.draw(Method::Newton, i + 2, |diagram, chart| {
+                    for (j, guess) in guesses.iter().take(i).enumerate() {
+                        self.point(diagram, chart, &format!("guess-{}", j), *guess, ElementState::Rejected);
+                    }
+                    chart
+                        .draw_line(
+                            diagram,
+                            &[(x as f32, value as f32), (next as f32, 0.0)],
+                        )
+                        .state = Some(ElementState::Active);
+                    chart
+                        .draw_line(
+                            diagram,
+                            &[(next as f32, 0.0), (next as f32, self.f(next) as f32)],
+                        )
+                        .dashed = true;
+                    self.point(diagram, chart, "guess", x, ElementState::Active);
+                    self.point(diagram, chart, &format!("guess-{}", i + 1), next, ElementState::Selected);
+                }),
+            );
+            step.lists = list(i + 2);
+        }
+        let last = *guesses.last().unwrap();
+        let slope = (function.derivative)(last);
+        let description = if self.f(last).abs() <= TOLERANCE {
+            format!(
+                "f({}) is zero to about 12 decimals, so the root is x = {} after {}. Near the root, the number of correct digits roughly doubles with every iteration",
+                decimal(last),
+                decimal(last),
+                plural(guesses.len() - 1, "iteration")
+            )
+        } else if guesses.len() >= 2 && (last - guesses[guesses.len() - 2]).abs() < TOLERANCE {
+            format!(
+                "The guess stopped changing at x = {}, so it is the root to the precision of the numbers",
+                decimal(last)
+            )
+        } else if slope == 0.0 {
+            format!(
+                "The tangent at x = {} is flat, so it never crosses zero and Newton's method fails. A different start is needed",
+                decimal(last)
+            )
+        } else if last < function.range.0 || last > function.range.1 {
+            format!(
+                "The guess x = {} is outside of the plot and moving away from the root, so Newton's method diverges from the start {}",
+                decimal(last),
+                decimal(self.start)
+            )
+        } else {
+            format!(
+                "The guesses still haven't converged after {}. Newton's method can get stuck in a cycle or wander around when the start is far from the root, which bisection never does",
+                plural(guesses.len() - 1, "iteration")
+            )
+        };
+        let step = steps.push(
+            description,
+            self.draw(Method::Newton, guesses.len(), |diagram, chart| {
+                for (j, guess) in guesses.iter().enumerate() {
+                    let state = if j + 1 == guesses.len() {
+                        ElementState::Selected
+                    } else {
+                        ElementState::Rejected
+                    };
+                    self.point(diagram, chart, &format!("guess-{}", j), *guess, state);
+                }
+            }),
+        );
+        step.lists = list(guesses.len());
+    }
+    fn bisection(&self, steps: &mut DiagramSteps) {
+        let function = self.function();
+        let intervals = self.bisection_intervals();
+        let list = |count: usize| {
+            vec![StepList::new(
+                "Intervals",
+                intervals
+                    .iter()
+                    .take(count)
+                    .map(|(a, b)| format!("[{}, {}]", decimal(*a), decimal(*b)))
+                    .collect(),
+            )]
+        };
+        let draw_interval = |diagram: &mut Diagram, chart: &Chart, (a, b): (f64, f64)| {
+            let (left, zero) = chart.point((a as f32, 0.0));
+            let (right, _) = chart.point((b as f32, 0.0));
+            diagram.add_figure(
+                Figure::new(FigureKind::Rect {
+                    position: (left, zero - 4.0),
+                    size: ((right - left).max(2.0), 8.0),
+                })
+                .with_state(ElementState::Frontier),
+            );
+            self.point(diagram, chart, "left", a, ElementState::Frontier);
+            self.point(diagram, chart, "right", b, ElementState::Frontier);
+        };
+        let (a, b) = self.bracket;
+        let step = steps.push(
+            format!(
+                "Find a root of f(x) = {} with bisection. f({}) = {} and f({}) = {} have different signs, so the curve crosses zero between them",
+                function.name,
+                decimal(a),
+                decimal(self.f(a)),
+                decimal(b),
+                decimal(self.f(b))
+            ),
+            self.draw(Method::Bisection, 1, |diagram, chart| {
+                draw_interval(diagram, chart, (a, b));
+            }),
+        );
+        step.lists = list(1);
+        for (i, pair) in intervals.windows(2).enumerate() {
+            let (a, b) = pair[0];
+            let m = midpoint(&pair[0]);
+            let value = self.f(m);
+            let half = if pair[1].0 == m && pair[1].1 == m {
+                "so the midpoint is the root".to_string()
+            } else if pair[1].0 == m {
+                format!(
+                    "which has the same sign as f({}), so keep the right half",
+                    decimal(a)
+                )
+            } else {
+                format!(
+                    "which has the same sign as f({}), so keep the left half",
+                    decimal(b)
+                )
+            };
+            let step = steps.push(
+                format!(
+                    "The midpoint of [{}, {}] is {}, where f = {}, {}",
+                    decimal(a),
+                    decimal(b),
+                    decimal(m),
+                    decimal(value),
+                    half
+                ),
+                self.draw(Method::Bisection, i + 2, |diagram, chart| {
+                    draw_interval(diagram, chart, pair[1]);
+                    chart
+                        .draw_line(diagram, &[(m as f32, 0.0), (m as f32, value as f32)])
+                        .dashed = true;
+                    self.point(diagram, chart, "middle", m, ElementState::Active);
+                }),
+            );
+            step.lists = list(i + 2);
+        }
+        let last = *intervals.last().unwrap();
+        let root = midpoint(&last);
+        let step = steps.push(
+            format!(
+                "The interval is only {:.1e} wide, so the root is x = {} after {}. Every iteration halves the interval, which adds about one correct digit every 3.3 iterations",
+                last.1 - last.0,
+                decimal(root),
+                plural(intervals.len() - 1, "iteration")
+            ),
+            self.draw(Method::Bisection, intervals.len(), |diagram, chart| {
+                draw_interval(diagram, chart, last);
+                self.point(diagram, chart, "middle", root, ElementState::Selected);
+            }),
+        );
+        step.lists = list(intervals.len());
+    }
+    /// Sets the function from its name, which resets the start and the bracket if it changes. Returns true if it
+    /// changed.
+    fn set_function(&mut self, name: &str) -> Result<bool, String> {
+        let function = FUNCTIONS
+            .iter()
+            .position(|function| function.name == name)
+            .ok_or_else(|| format!("Unknown function \"{}\"", name))?;
+        if function == self.function {
+            return Ok(false);
+        }
+        *self = Self {
+            function,
+            start: FUNCTIONS[function].start,
+            bracket: FUNCTIONS[function].bracket,
+        };
+        Ok(true)
+    }
+    /// Reads the ends of an interval where the function changes sign.
+    fn parse_bracket(
+        &self,
+        left: Option<&Value>,
+        right: Option<&Value>,
+    ) -> Result<(f64, f64), String> {
+        let bracket = (
+            self.parse_x(left, "left end")?,
+            self.parse_x(right, "right end")?,
+        );
+        if bracket.0 >= bracket.1 {
+            return Err("The left end must be smaller than the right end".to_string());
+        }
+        if self.f(bracket.0).signum() == self.f(bracket.1).signum() {
+            return Err(
+                "The function must have different signs at the ends, so that it crosses zero between them"
+                    .to_string(),
+            );
+        }
+        Ok(bracket)
+    }
+    /// Reads a decimal number in the range of the function.
+    fn parse_x(&self, value: Option<&Value>, name: &str) -> Result<f64, String> {
+        let (low, high) = self.function().range;
+        let text = value.map(|value| value.text()).unwrap_or_default();
+        match text.trim().replace('−', "-").parse::<f64>() {
+            Ok(x) if x >= low && x <= high => Ok(x),
+            _ => Err(format!(
+                "The {} must be a number between {} and {}",
+                name,
+                format_number(low),
+                format_number(high)
+            )),
+        }
+    }
+}
+impl Default for RootFinding {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Visualizer for RootFinding {
+    fn diagram(&self) -> Diagram {
+        self.draw(Method::Newton, 0, |_, _| {})
+    }
+    fn operations(&self) -> Vec<Operation> {
+        // The current function first, since it is the default choice
+        let mut functions = vec![self.function().name.to_string()];
+        functions.extend(
+            FUNCTIONS
+                .iter()
+                .filter(|function| function.name != self.function().name)
+                .map(|function| function.name.to_string()),
+        );
+        let function = Input::Choice {
+            label: "Function",
+            choices: functions,
+        };
+        vec![
+            Operation::new(
+                "Newton's method",
+                vec![
+                    function.clone(),
+                    Input::Text {
+                        label: "Start",
+                        default: self.start.to_string(),
+                    },
+                ],
+            ),
+            Operation::new(
+                "Bisection",
+                vec![
+                    function,
+                    Input::Text {
+                        label: "Left end",
+                        default: self.bracket.0.to_string(),
+                    },
+                    Input::Text {
+                        label: "Right end",
+                        default: self.bracket.1.to_string(),
+                    },
+                ],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        let name = values.first().map(|value| value.text()).unwrap_or_default();
+        match operation {
+            // The values that were chosen for the previous function are replaced with the defaults of a new function
+            // if they don't work for it
+            "Newton's method" => {
+                let changed = self.set_function(&name)?;
+                match self.parse_x(values.get(1), "start") {
+                    Ok(start) => self.start = start,
+                    Err(_) if changed => {}
+                    Err(error) => return Err(error),
+                }
+                self.newton(&mut steps);
+            }
+            "Bisection" => {
+                let changed = self.set_function(&name)?;
+                match self.parse_bracket(values.get(1), values.get(2)) {
+                    Ok(bracket) => self.bracket = bracket,
+                    Err(_) if changed => {}
+                    Err(error) => return Err(error),
+                }
+                self.bisection(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn midpoint((a, b): &(f64, f64)) -> f64 {
+    (a + b) / 2.0
+}
+
+/// The number with up to ten decimals, without trailing zeros.
+fn decimal(number: f64) -> String {
+    let text = format!("{:.10}", number);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.replace('-', "−")
+    }
+}
+
+fn subscript(number: usize) -> String {
+    number
+        .to_string()
+        .chars()
+        .map(|digit| {
+            ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉'][digit.to_digit(10).unwrap() as usize]
+        })
+        .collect()
+}
@@ -0,0 +1,4 @@
+//! This crate contains my implementations of optimization algorithms, like genetic algorithms, which search for good
+//! solutions to problems that are too large to solve exactly. The problems are placed on the same canvas as the
+//! geometry algorithms, and each algorithm records a diagram of its best solution at every step.
+pub mod optimization_algorithms;
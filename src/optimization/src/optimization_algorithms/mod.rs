@@ -0,0 +1,4 @@
+//! A collection of heuristics that improve solutions step by step instead of trying every one of them.
+mod traveling_salesman;
+
+pub use traveling_salesman::TravelingSalesman;
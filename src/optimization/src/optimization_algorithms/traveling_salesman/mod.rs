@@ -0,0 +1,737 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+use geometry::*;
+use rand::{seq::SliceRandom, Rng};
+
+/// The height of the diagram, which has room for the charts below the cities.
+const DIAGRAM_HEIGHT: f32 = CANVAS_HEIGHT + 170.0;
+const LEFT_CHART_POSITION: (f32, f32) = (70.0, CANVAS_HEIGHT + 55.0);
+const RIGHT_CHART_POSITION: (f32, f32) = (460.0, CANVAS_HEIGHT + 55.0);
+const CHART_SIZE: (f32, f32) = (290.0, 90.0);
+/// The number of bars in the chart of the tour lengths of a population.
+const HISTOGRAM_BINS: usize = 12;
+/// How many random tours compete for every parent.
+const TOURNAMENT_SIZE: usize = 3;
+const MAX_POPULATION: usize = 200;
+const MAX_GENERATIONS: usize = 300;
+const MAX_ITERATIONS: usize = 100_000;
+/// How many steps simulated annealing is shown in, no matter how many iterations it runs.
+const ANNEALING_STEPS: usize = 100;
+/// The temperature at the end of exponential cooling, relative to the start temperature.
+const FINAL_TEMPERATURE: f64 = 0.001;
+const COOLING_SCHEDULES: [&str; 2] = ["Exponential", "Linear"];
+
+/// The tours of a generation of the genetic algorithm.
+#[derive(Clone, Debug, PartialEq)]
+struct Generation {
+    lengths: Vec<f32>,
+    best: Vec<usize>,
+    crossovers: usize,
+    mutations: usize,
+}
+impl Generation {
+    fn best_length(&self) -> f32 {
+        self.lengths.iter().copied().fold(f32::INFINITY, f32::min)
+    }
+    fn mean_length(&self) -> f32 {
+        self.lengths.iter().sum::<f32>() / self.lengths.len() as f32
+    }
+}
+
+/// A stretch of iterations of simulated annealing, which is shown as one step.
+#[derive(Clone, Debug, PartialEq)]
+struct AnnealingStretch {
+    /// The iteration and the temperature at the end of the stretch.
+    iteration: usize,
+    temperature: f64,
+    tour: Vec<usize>,
+    length: f32,
+    shortest_length: f32,
+    /// The edges that the latest accepted longer tour of the stretch added, if they are still in the tour.
+    uphill_edges: Vec<(usize, usize)>,
+    uphill: usize,
+    downhill: usize,
+}
+
+/// The traveling salesman problem: finding the shortest tour that visits every city once and returns to the start.
+/// Trying every tour is only possible for a handful of cities, so heuristics look for short tours instead. The
+/// genetic algorithm evolves a population of tours, where the shorter tours are more likely to become parents,
+/// children mix the orders of their parents, and random mutations reverse parts of the tours. Simulated annealing
+/// improves a single tour by reversing random parts of it, and also accepts longer tours with a probability that
+/// falls as the temperature cools, so that it can climb out of a tour that is only shorter than its neighbors.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TravelingSalesman {
+    cities: Vec<Point>,
+}
+impl TravelingSalesman {
+    pub fn new(cities: &[Point]) -> Self {
+        Self {
+            cities: cities.to_vec(),
+        }
+    }
+    fn tour_length(&self, tour: &[usize]) -> f32 {
+        (0..tour.len())
+            .map(|i| {
+                distance(
+                    self.cities[tour[i]],
+                    self.cities[tour[(i + 1) % tour.len()]],
+                )
+            })
+            .sum()
+    }
+    /// Draws the cities with the tour between them, and the charts below them with `draw_charts`. The new edges are
+    /// highlighted as the edges of a longer tour.
+    fn draw(
+        &self,
+        tour: Option<&[usize]>,
+        new_edges: &[(usize, usize)],
+        draw_charts: impl FnOnce(&mut Diagram),
+    ) -> Diagram {
+        let mut diagram = draw_canvas(&self.cities, &BTreeMap::new());
+        diagram.height = DIAGRAM_HEIGHT;
+        if let Some(tour) = tour {
+            for i in 0..tour.len() {
+                let (a, b) = (tour[i], tour[(i + 1) % tour.len()]);
+                let state = if new_edges.contains(&(a, b)) || new_edges.contains(&(b, a)) {
+                    ElementState::Conflict
+                } else {
+                    ElementState::Selected
+                };
+                diagram.add_edge(DiagramEdge::new(point_key(a), point_key(b)).with_state(state));
+            }
+        }
+        draw_charts(&mut diagram);
+        diagram
+    }
+    /// The tour as the cities in order, starting and ending at city 0.
+    fn tour_list(&self, tour: &[usize]) -> Vec<StepList> {
+        let start = tour.iter().position(|city| *city == 0).unwrap_or(0);
+        let cities = tour[start..]
+            .iter()
+            .chain(tour[..start].iter())
+            .chain(tour[start..].iter().take(1))
+            .map(|city| city.to_string())
+            .collect::<Vec<String>>();
+        vec![StepList::new(
+            "Shortest tour",
+            vec![
+                cities.join(" → "),
+                format!("Length {:.0}", self.tour_length(tour)),
+            ],
+        )]
+    }
+    /// Evolves the population of tours for the given number of generations.
+    fn evolve(
+        &self,
+        population_size: usize,
+        generations: usize,
+        crossover_rate: f64,
+        mutation_rate: f64,
+    ) -> Vec<Generation> {
+        let mut rng = rand::thread_rng();
+        let n = self.cities.len();
+        let mut population = (0..population_size)
+            .map(|_| {
+                let mut tour = (0..n).collect::<Vec<usize>>();
+                tour.shuffle(&mut rng);
+                tour
+            })
+            .collect::<Vec<Vec<usize>>>();
+        let (mut crossovers, mut mutations) = (0, 0);
+        let mut history = vec![];
+        loop {
+            let lengths = population
+                .iter()
+                .map(|tour| self.tour_length(tour))
+                .collect::<Vec<f32>>();
+            let best = (0..population_size)
+                .min_by(|a, b| lengths[*a].total_cmp(&lengths[*b]))
+                .unwrap();
+            history.push(Generation {
+                lengths: lengths.clone(),
+                best: population[best].clone(),
+                crossovers,
+                mutations,
+            });
+            if history.len() > generations {
+                return history;
+            }
+
+            // The best tour always survives, so the shortest tour never gets longer
+            let mut next = vec![population[best].clone()];
+            (crossovers, mutations) = (0, 0);
+            let mut tournament = || {
+                (0..TOURNAMENT_SIZE)
+                    .map(|_| rng.gen_range(0..population_size))
+                    .min_by(|a, b| lengths[*a].total_cmp(&lengths[*b]))
+                    .unwrap()
+            };
+            let mut parents = vec![];
+            while parents.len() < (population_size - 1) * 2 {
+                parents.push(tournament());
+            }
+            for pair in parents.chunks(2) {
+                let mut child = if rng.gen_bool(crossover_rate) {
+                    crossovers += 1;
+                    ordered_crossover(&population[pair[0]], &population[pair[1]], &mut rng)
+                } else {
+                    population[pair[0]].clone()
+                };
+                if rng.gen_bool(mutation_rate) {
+                    mutations += 1;
+                    let (i, j) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                    child[i.min(j)..=i.max(j)].reverse();
+                }
+                next.push(child);
+            }
+            population = next;
+        }
+    }
+    /// Charts how many tours of the generation have lengths in every part of the range of all lengths.
+    fn draw_histogram(&self, diagram: &mut Diagram, history: &[Generation], generation: usize) {
+        let lengths = history
+            .iter()
+            .flat_map(|generation| generation.lengths.iter());
+        let low = lengths.clone().copied().fold(f32::INFINITY, f32::min);
+        let high = lengths.copied().fold(0.0, f32::max);
+        let bin_width = ((high - low) / HISTOGRAM_BINS as f32).max(f32::EPSILON);
+        let bin = |length: f32| (((length - low) / bin_width) as usize).min(HISTOGRAM_BINS - 1);
+        let mut counts = [0; HISTOGRAM_BINS];
+        for length in history[generation].lengths.iter() {
+            counts[bin(*length)] += 1;
+        }
+        let population_size = history[generation].lengths.len();
+        let chart = Chart::new(
+            LEFT_CHART_POSITION,
+            CHART_SIZE,
+            (
+                low + bin_width * HISTOGRAM_BINS as f32,
+                population_size as f32,
+            ),
+        )
+        .with_min((low, 0.0));
+        chart.draw_axes(
+            diagram,
+            "histogram",
+            format!("Tour lengths from {:.0} to {:.0}", low, high),
+            ("length", population_size),
+        );
+        let best_bin = bin(history[generation].best_length());
+        for (i, count) in counts.iter().enumerate() {
+            if *count > 0 {
+                chart
+                    .draw_bar(
+                        diagram,
+                        (low + bin_width * (i as f32 + 0.5), *count as f32),
+                        bin_width * 0.8,
+                    )
+                    .state = Some(if i == best_bin {
+                    ElementState::Selected
+                } else {
+                    ElementState::Frontier
+                });
+            }
+        }
+    }
+    fn genetic_algorithm(
+        &self,
+        population_size: usize,
+        generations: usize,
+        crossover_rate: f64,
+        mutation_rate: f64,
+        steps: &mut DiagramSteps,
+    ) {
+        let history = self.evolve(population_size, generations, crossover_rate, mutation_rate);
+        let series = |length: fn(&Generation) -> f32| {
+            history
+                .iter()
+                .enumerate()
+                .map(|(i, generation)| (i as f32, length(generation)))
+                .collect::<Vec<(f32, f32)>>()
+        };
+        let (shortest, mean) = (
+            series(Generation::best_length),
+            series(Generation::mean_length),
+        );
+        let draw_generation = |generation: usize| {
+            self.draw(Some(&history[generation].best), &[], |diagram| {
+                self.draw_histogram(diagram, &history, generation);
+                draw_lengths(
+                    diagram,
+                    "Shortest and mean tour length",
+                    "generation",
+                    &shortest,
+                    &mean,
+                    generation + 1,
+                );
+            })
+        };
+        for (i, generation) in history.iter().enumerate() {
+            let description = if i == 0 {
+                format!(
+                    "Start with {} random tours. The shortest of them is {:.0} long, and the mean length is {:.0}. Every generation keeps the shortest tour, and makes the rest from parents that win tournaments, where the shortest of {} random tours wins",
+                    population_size,
+                    generation.best_length(),
+                    generation.mean_length(),
+                    TOURNAMENT_SIZE
+                )
+            } else {
+                let improved = generation.best_length() < history[i - 1].best_length();
+                format!(
+                    "Generation {}: {} of the {} children mix the orders of both of their parents, and {} reverse a part of their tour. The shortest tour is {:.0} long{}, and the mean length is {:.0}",
+                    i,
+                    generation.crossovers,
+                    population_size - 1,
+                    generation.mutations,
+                    generation.best_length(),
+                    if improved { ", which is a new record" } else { "" },
+                    generation.mean_length()
+                )
+            };
+            let step = steps.push(description, draw_generation(i));
+            step.lists = self.tour_list(&generation.best);
+        }
+
+        let last = history.last().unwrap();
+        let found = history
+            .iter()
+            .position(|generation| generation.best_length() <= last.best_length())
+            .unwrap();
+        let step = steps.push(
+            format!(
+                "The shortest tour after {} is {:.0} long, and it was first found in generation {}. The genetic algorithm can't tell if there is a shorter tour, since checking that would mean trying {}",
+                plural(generations, "generation"),
+                last.best_length(),
+                found,
+                tour_count_text(self.cities.len())
+            ),
+            draw_generation(history.len() - 1),
+        );
+        step.lists = self.tour_list(&last.best);
+    }
+    /// Runs simulated annealing from a random tour, and splits its iterations into stretches.
+    fn anneal(
+        &self,
+        iterations: usize,
+        start_temperature: f64,
+        linear: bool,
+    ) -> (Vec<usize>, Vec<AnnealingStretch>) {
+        let mut rng = rand::thread_rng();
+        let n = self.cities.len();
+        let mut tour = (0..n).collect::<Vec<usize>>();
+        tour.shuffle(&mut rng);
+        let mut length = self.tour_length(&tour);
+        let (mut shortest, mut shortest_length) = (tour.clone(), length);
+        let stretch_length = (iterations / ANNEALING_STEPS).max(1);
+        let mut stretches = vec![AnnealingStretch {
+            iteration: 0,
+            temperature: start_temperature,
+            tour: tour.clone(),
+            length,
+            shortest_length,
+            uphill_edges: vec![],
+            uphill: 0,
+            downhill: 0,
+        }];
+        let (mut uphill_edges, mut uphill, mut downhill) = (vec![], 0, 0);
+        for iteration in 1..=iterations {
+            let temperature = temperature(start_temperature, linear, iteration, iterations);
+            // Reversing the part from i to j replaces the edges a-b and c-d with a-c and b-d
+            let (i, j) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            let (i, j) = (i.min(j), i.max(j));
+            if i != j && j - i < n - 1 {
+                let (a, b) = (tour[(i + n - 1) % n], tour[i]);
+                let (c, d) = (tour[j], tour[(j + 1) % n]);
+                let city = |index: usize| self.cities[index];
+                let change = distance(city(a), city(c)) + distance(city(b), city(d))
+                    - distance(city(a), city(b))
+                    - distance(city(c), city(d));
+                let accepted = change <= 0.0
+                    || rng.gen_bool((-(change as f64) / temperature.max(f64::MIN_POSITIVE)).exp());
+                if accepted {
+                    tour[i..=j].reverse();
+                    length += change;
+                    if change > 0.0 {
+                        uphill += 1;
+                        uphill_edges = vec![(a, c), (b, d)];
+                    } else if change < 0.0 {
+                        downhill += 1;
+                    }
+                    if length < shortest_length {
+                        (shortest, shortest_length) = (tour.clone(), length);
+                    }
+                }
+            }
+            if iteration % stretch_length == 0 || iteration == iterations {
+                // Later changes can replace the edges again
+                uphill_edges.retain(|(a, b)| {
+                    (0..n).any(|k| {
+                        let edge = (tour[k], tour[(k + 1) % n]);
+                        edge == (*a, *b) || edge == (*b, *a)
+                    })
+                });
+                stretches.push(AnnealingStretch {
+                    iteration,
+                    temperature,
+                    tour: tour.clone(),
+                    length,
+                    shortest_length,
+                    uphill_edges: std::mem::take(&mut uphill_edges),
+                    uphill,
+                    downhill,
+                });
+                (uphill, downhill) = (0, 0);
+            }
+        }
+        (shortest, stretches)
+    }
+    /// Charts the temperature of every iteration, with the iterations up to the stretch highlighted.
+    fn draw_temperature(
+        &self,
+        diagram: &mut Diagram,
+        start_temperature: f64,
+        linear: bool,
+        stretches: &[AnnealingStretch],
+        stretch: usize,
+    ) {
+        let iterations = stretches.last().unwrap().iteration;
+        let chart = Chart::new(
+            LEFT_CHART_POSITION,
+            CHART_SIZE,
+            (iterations.max(1) as f32, start_temperature as f32),
+        );
+        chart.draw_axes(
+            diagram,
+            "temperature",
+            if linear {
+                "Temperature, cooling linearly"
+            } else {
+                "Temperature, cooling exponentially"
+            },
+            ("iteration", start_temperature),
+        );
+        let schedule = stretches
+            .iter()
+            .map(|stretch| (stretch.iteration as f32, stretch.temperature as f32))
+            .collect::<Vec<(f32, f32)>>();
+        let full_line = chart.draw_line(diagram, &schedule);
+        full_line.dashed = true;
+        full_line.state = Some(ElementState::Frontier);
+        chart.draw_line(diagram, &schedule[..=stretch]).state = Some(ElementState::Active);
+    }
+    fn simulated_annealing(
+        &self,
+        iterations: usize,
+        start_temperature: f64,
+        linear: bool,
+        steps: &mut DiagramSteps,
+    ) {
+        let (shortest, stretches) = self.anneal(iterations, start_temperature, linear);
+        let series = |length: fn(&AnnealingStretch) -> f32| {
+            stretches
+                .iter()
+                .map(|stretch| (stretch.iteration as f32, length(stretch)))
+                .collect::<Vec<(f32, f32)>>()
+        };
+        let (shortest_lengths, lengths) = (
+            series(|stretch| stretch.shortest_length),
+            series(|stretch| stretch.length),
+        );
+        let draw_stretch = |tour: &[usize], index: usize| {
+            self.draw(Some(tour), &stretches[index].uphill_edges, |diagram| {
+                self.draw_temperature(diagram, start_temperature, linear, &stretches, index);
+                draw_lengths(
+                    diagram,
+                    "Shortest and current tour length",
+                    "iteration",
+                    &shortest_lengths,
+                    &lengths,
+                    index + 1,
+                );
+            })
+        };
+        for (i, stretch) in stretches.iter().enumerate() {
+            let description = if i == 0 {
+                format!(
+                    "Start from a random tour that is {:.0} long, at the temperature {}. Every iteration reverses a random part of the tour, and keeps the change if the tour gets shorter. A tour that gets longer by d is kept with the probability e^(−d / temperature), which is high while the temperature is high",
+                    stretch.length, start_temperature
+                )
+            } else {
+                let first = stretches[i - 1].iteration + 1;
+                format!(
+                    "{}, cooling from {} to {}: {} of {} made the tour shorter, and {} made it longer{}. The tour is {:.0} long, and the shortest so far is {:.0}",
+                    if first == stretch.iteration {
+                        format!("Iteration {}", first)
+                    } else {
+                        format!("Iterations {} to {}", first, stretch.iteration)
+                    },
+                    format_temperature(stretches[i - 1].temperature),
+                    format_temperature(stretch.temperature),
+                    stretch.downhill,
+                    plural(stretch.iteration - first + 1, "change"),
+                    stretch.uphill,
+                    if stretch.uphill_edges.is_empty() {
+                        ""
+                    } else {
+                        ", like the latest one with the highlighted edges"
+                    },
+                    stretch.length,
+                    stretch.shortest_length
+                )
+            };
+            let step = steps.push(description, draw_stretch(&stretch.tour, i));
+            step.lists = self.tour_list(&stretch.tour);
+            step.lists[0].title = "Tour".to_string();
+        }
+        let uphill = stretches
+            .iter()
+            .map(|stretch| stretch.uphill)
+            .sum::<usize>();
+        let step = steps.push(
+            format!(
+                "The shortest tour that simulated annealing found in {} is {:.0} long. {}",
+                plural(iterations, "iteration"),
+                self.tour_length(&shortest),
+                if uphill == 0 {
+                    "It never accepted a longer tour, so it could get stuck in a tour that no single change can shorten. A higher start temperature or more iterations would help".to_string()
+                } else {
+                    format!("It accepted {} on the way, most of them while the temperature was high, which let it leave tours that no single change could shorten", plural(uphill, "longer tour"))
+                }
+            ),
+            draw_stretch(&shortest, stretches.len() - 1),
+        );
+        step.lists = self.tour_list(&shortest);
+    }
+}
+impl Visualizer for TravelingSalesman {
+    fn diagram(&self) -> Diagram {
+        self.draw(None, &[], |_| {})
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Run genetic algorithm",
+                vec![
+                    Input::Number {
+                        label: "Population",
+                        default: 50,
+                        min: 2,
+                        max: MAX_POPULATION as i64,
+                    },
+                    Input::Number {
+                        label: "Generations",
+                        default: 100,
+                        min: 1,
+                        max: MAX_GENERATIONS as i64,
+                    },
+                    rate_input("Crossover rate", 0.9),
+                    rate_input("Mutation rate", 0.3),
+                ],
+            ),
+            Operation::new(
+                "Run simulated annealing",
+                vec![
+                    Input::Number {
+                        label: "Iterations",
+                        default: 20_000,
+                        min: 1,
+                        max: MAX_ITERATIONS as i64,
+                    },
+                    Input::Slider {
+                        label: "Start temperature",
+                        default: 100.0,
+                        min: 10.0,
+                        max: 1000.0,
+                        step: 10.0,
+                    },
+                    Input::Choice {
+                        label: "Cooling schedule",
+                        choices: COOLING_SCHEDULES
+                            .iter()
+                            .map(|schedule| schedule.to_string())
+                            .collect(),
+                    },
+                ],
+            ),
+            random_points_operation(20),
+            Operation::new("Clear", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Run genetic algorithm" => {
+                check_points(&self.cities, 4)?;
+                let population_size = values.first().map(|value| value.number()).unwrap_or(0);
+                if !(2..=MAX_POPULATION as i64).contains(&population_size) {
+                    return Err(format!(
+                        "The population must have 2 to {} tours",
+                        MAX_POPULATION
+                    ));
+                }
+                let generations = values.get(1).map(|value| value.number()).unwrap_or(0);
+                if !(1..=MAX_GENERATIONS as i64).contains(&generations) {
+                    return Err(format!(
+                        "The number of generations must be between 1 and {}",
+                        MAX_GENERATIONS
+                    ));
+                }
+                self.genetic_algorithm(
+                    population_size as usize,
+                    generations as usize,
+                    parse_rate(values.get(2), "crossover rate")?,
+                    parse_rate(values.get(3), "mutation rate")?,
+                    &mut steps,
+                );
+            }
+            "Run simulated annealing" => {
+                check_points(&self.cities, 4)?;
+                let iterations = values.first().map(|value| value.number()).unwrap_or(0);
+                if !(1..=MAX_ITERATIONS as i64).contains(&iterations) {
+                    return Err(format!(
+                        "The number of iterations must be between 1 and {}",
+                        MAX_ITERATIONS
+                    ));
+                }
+                let start_temperature = values.get(1).map(|value| value.decimal()).unwrap_or(0.0);
+                if start_temperature <= 0.0 {
+                    return Err("The start temperature must be more than 0".to_string());
+                }
+                let linear = values.get(2).map(|value| value.text()).as_deref()
+                    == Some(COOLING_SCHEDULES[1]);
+                self.simulated_annealing(
+                    iterations as usize,
+                    start_temperature,
+                    linear,
+                    &mut steps,
+                );
+            }
+            "Random points" => {
+                self.cities = random_points(parse_point_count(values)?);
+                steps.push(
+                    format!("Place {} cities at random", self.cities.len()),
+                    self.diagram(),
+                );
+            }
+            "Clear" => {
+                self.cities.clear();
+                steps.push("Remove all cities".to_string(), self.diagram());
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+    fn clickable(&self) -> bool {
+        true
+    }
+    fn click(&mut self, position: (f32, f32)) -> Option<DiagramSteps> {
+        // The charts below the cities can't be clicked
+        if position.1 > CANVAS_HEIGHT {
+            return None;
+        }
+        toggle_point(&mut self.cities, position).then(DiagramSteps::new)
+    }
+}
+
+/// Makes a child with a random part of the first parent in place, and the rest of the cities in the order of the
+/// second parent. This is called ordered crossover, and unlike cutting and joining the parents, it always makes a
+/// valid tour.
+fn ordered_crossover(first: &[usize], second: &[usize], rng: &mut impl Rng) -> Vec<usize> {
+    let n = first.len();
+    let (i, j) = (rng.gen_range(0..n), rng.gen_range(0..n));
+    let (start, end) = (i.min(j), i.max(j));
+    let mut child = vec![usize::MAX; n];
+    let mut used = vec![false; n];
+    for k in start..=end {
+        child[k] = first[k];
+        used[first[k]] = true;
+    }
+    let mut position = (end + 1) % n;
+    for k in 0..n {
+        let city = second[(end + 1 + k) % n];
+        if !used[city] {
+            child[position] = city;
+            position = (position + 1) % n;
+        }
+    }
+    child
+}
+
+/// A probability between 0 and 1 that is chosen with a slider.
+fn rate_input(label: &'static str, default: f64) -> Input {
+    Input::Slider {
+        label,
+        default,
+        min: 0.0,
+        max: 1.0,
+        step: 0.05,
+    }
+}
+
+fn parse_rate(value: Option<&Value>, name: &str) -> Result<f64, String> {
+    let rate = value.map(|value| value.decimal()).unwrap_or(-1.0);
+    if !(0.0..=1.0).contains(&rate) {
+        return Err(format!("The {} must be between 0 and 1", name));
+    }
+    Ok(rate)
+}
+
+/// The number of different tours of the cities, (n − 1)! / 2, since a tour can start from any city and go either way.
+fn tour_count_text(cities: usize) -> String {
+    let count = (2..cities).map(|k| k as f64).product::<f64>() / 2.0;
+    if count < 1e6 {
+        format!("all {} tours", count)
+    } else {
+        format!("about 10^{} tours", count.log10().floor())
+    }
+}
+
+/// The temperature at an iteration, which cools from the start temperature to zero in a straight line, or
+/// exponentially to a thousandth of it.
+fn temperature(start: f64, linear: bool, iteration: usize, iterations: usize) -> f64 {
+    let progress = iteration as f64 / iterations as f64;
+    if linear {
+        start * (1.0 - progress)
+    } else {
+        start * FINAL_TEMPERATURE.powf(progress)
+    }
+}
+
+fn format_temperature(temperature: f64) -> String {
+    if temperature == 0.0 {
+        "0".to_string()
+    } else if temperature >= 10.0 {
+        format!("{:.0}", temperature)
+    } else {
+        format!("{:.2}", temperature)
+    }
+}
+
+/// Charts two series of tour lengths, with the first `shown` values of each drawn. The shortest lengths are solid and
+/// the other lengths are dashed, and the chart goes from just below the shortest length to the first other length.
+fn draw_lengths(
+    diagram: &mut Diagram,
+    title: &str,
+    x_label: &str,
+    shortest: &[(f32, f32)],
+    other: &[(f32, f32)],
+    shown: usize,
+) {
+    let low = shortest
+        .iter()
+        .map(|(_, y)| *y)
+        .fold(f32::INFINITY, f32::min);
+    let high = other[0].1.max(low + 1.0);
+    let chart = Chart::new(
+        RIGHT_CHART_POSITION,
+        CHART_SIZE,
+        (other.last().unwrap().0.max(1.0), high),
+    )
+    .with_min((0.0, (low * 0.9).floor()));
+    chart.draw_axes(diagram, "lengths", title, (x_label, format!("{:.0}", high)));
+    let other_line = chart.draw_line(diagram, &other[..shown]);
+    other_line.dashed = true;
+    other_line.state = Some(ElementState::Frontier);
+    chart.draw_line(diagram, &shortest[..shown]).state = Some(ElementState::Selected);
+}
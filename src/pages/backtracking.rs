@@ -0,0 +1,129 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use backtracking::backtracking_algorithms::*;
+use diagram::ElementState;
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+pub fn get_backtracking_algorithms() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "permutations",
+            VisualizerInfo::new("Permutations", || {
+                Box::new(Permutations::new(&["A", "B", "C", "D"]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Swapped items, current call"),
+                (ElementState::Frontier, "Items that the call permutes"),
+                (ElementState::Selected, "Output permutation"),
+            ]).with_tags(&["backtracking", "combinatorics", "O(n · n!)"]),
+        ),
+        (
+            "subsets",
+            VisualizerInfo::new("Subsets", || Box::new(Subsets::new(&["A", "B", "C", "D"])))
+                .with_legend(&[
+                    (ElementState::Active, "Current call"),
+                    (ElementState::Selected, "Included items"),
+                    (ElementState::Rejected, "Left out items"),
+                ]).with_tags(&["backtracking", "combinatorics", "O(2ⁿ)"]),
+        ),
+        (
+            "sudoku",
+            VisualizerInfo::new("Sudoku", || {
+                Box::new(Sudoku::new(
+                    "534.7.9.267.195.4..98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+                ))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current guess"),
+                (ElementState::Frontier, "Forced digit"),
+                (ElementState::Conflict, "Conflicting digits"),
+                (ElementState::Rejected, "Erased cell"),
+                (ElementState::Selected, "Solved cells"),
+            ]).with_tags(&["backtracking", "constraint satisfaction", "grid"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum BacktrackingRoute {
+    #[at("/backtracking")]
+    Backtracking,
+    #[at("/backtracking/:algorithm")]
+    BacktrackingAlgorithm { algorithm: String },
+}
+
+pub fn switch_backtracking(route: BacktrackingRoute) -> Html {
+    match route {
+        BacktrackingRoute::Backtracking => html! {
+            <Redirect<BacktrackingRoute> to={BacktrackingRoute::BacktrackingAlgorithm { algorithm: "sudoku".to_string()} } />
+        },
+        BacktrackingRoute::BacktrackingAlgorithm { algorithm } => {
+            if get_backtracking_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <BacktrackingPage {algorithm} />
+                }
+            } else {
+                html! {
+                    <Backtracking404Page {algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct BacktrackingPageProps {
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn BacktrackingPage(props: &BacktrackingPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let algorithms = get_backtracking_algorithms();
+    let names = algorithms
+        .values()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect::<Vec<String>>();
+    let algorithm = algorithms[props.algorithm.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&BacktrackingRoute::BacktrackingAlgorithm {
+            algorithm: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="Backtracking"
+            section="Backtracking"
+            select_title="Algorithm"
+            {names}
+            visualizer={algorithm}
+            {on_select}
+            readme_directory="backtracking_algorithms"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct Backtracking404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn Backtracking404Page(props: &Backtracking404PageProps) -> Html {
+    use_title("404 - Backtracking".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<BacktrackingRoute> to={BacktrackingRoute::Backtracking}>
+                { "Back to backtracking" }
+            </Link<BacktrackingRoute>>
+        </>
+    }
+}
@@ -0,0 +1,1065 @@
+use crate::{
+    components::{
+        algo_desc::AlgoDesc, cellular_automata::CellGrid, collapsible::Collapsible, input_items::*,
+        sidebar::Sidebar,
+    },
+    utils::prefers_reduced_motion,
+};
+use cellular_automata::cellular_automata_algorithms::{
+    parse_ant_rule, ElementaryAutomaton, LangtonsAnt, Life, ANT_RULES, ELEMENTARY_RULES,
+    LIFE_PATTERNS, MAX_COLORS,
+};
+use pathfinding::Coord;
+use std::{collections::BTreeMap, rc::Rc};
+use yew::prelude::*;
+use yew_hooks::{use_interval, use_title};
+use yew_router::prelude::*;
+
+/// The largest width and height of the grids.
+const MAX_GRID_SIZE: usize = 150;
+/// The color of live cells in the Game of Life and elementary cellular automata.
+const LIFE_COLOR: &str = "#00bbff";
+/// The share of cells that are alive after filling the grid randomly.
+const RANDOM_DENSITY: f64 = 0.3;
+/// The colors of the cells that Langton's ant has visited, in the order of the turns of the rule after the first one.
+const ANT_COLORS: [&str; MAX_COLORS - 1] = [
+    "#00bbff", "#ffa500", "#00ff66", "#ff66b3", "#ffee00", "#aa44ff", "#0066ff", "#66dd99",
+    "#cc0066", "#99e6ff", "#e64dff",
+];
+/// The ways to choose the first generation of an elementary cellular automaton.
+const FIRST_GENERATIONS: [&str; 2] = ["Single cell", "Random cells"];
+/// The shortest time between two updates of a running automaton, which is roughly 30 fps like the step sliders.
+const MIN_INTERVAL_MS: f32 = 33.3333;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CellularAutomaton {
+    pub name: &'static str,
+    /// What the automaton can be found with in the catalog of the home page, like its dimensions or behavior.
+    pub tags: Vec<&'static str>,
+}
+impl CellularAutomaton {
+    fn new(name: &'static str, tags: &[&'static str]) -> Self {
+        Self {
+            name,
+            tags: tags.to_vec(),
+        }
+    }
+}
+
+pub fn get_cellular_automata() -> BTreeMap<&'static str, CellularAutomaton> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "elementary",
+            CellularAutomaton::new(
+                "Elementary cellular automaton",
+                &["one-dimensional", "rules", "fractals"],
+            ),
+        ),
+        (
+            "game-of-life",
+            CellularAutomaton::new("Game of Life", &["grid", "patterns", "Turing complete"]),
+        ),
+        (
+            "langtons-ant",
+            CellularAutomaton::new("Langton's ant", &["grid", "turmite", "emergence"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum CellularAutomataRoute {
+    #[at("/cellular-automata")]
+    CellularAutomata,
+    #[at("/cellular-automata/:automaton")]
+    CellularAutomaton { automaton: String },
+}
+
+pub fn switch_cellular_automata(route: CellularAutomataRoute) -> Html {
+    match route {
+        CellularAutomataRoute::CellularAutomata => html! {
+            <Redirect<CellularAutomataRoute> to={CellularAutomataRoute::CellularAutomaton { automaton: "game-of-life".to_string()} } />
+        },
+        CellularAutomataRoute::CellularAutomaton { automaton } => match automaton.as_str() {
+            "game-of-life" => html! {
+                <GameOfLifePage />
+            },
+            "langtons-ant" => html! {
+                <LangtonsAntPage />
+            },
+            "elementary" => html! {
+                <ElementaryPage />
+            },
+            _ => html! {
+                <CellularAutomata404Page {automaton} />
+            },
+        },
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct AutomatonSelectProps {
+    /// The name of the automaton on the current page.
+    selected: String,
+}
+
+/// Navigates to the page of the chosen automaton.
+#[function_component]
+fn AutomatonSelect(props: &AutomatonSelectProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let automata = get_cellular_automata();
+
+    let onchange = {
+        let automata = automata.clone();
+
+        Callback::from(move |name: String| {
+            if let Some((automaton, _)) = automata.iter().find(|(_, a)| a.name == name) {
+                navigator.push(&CellularAutomataRoute::CellularAutomaton {
+                    automaton: automaton.to_string(),
+                });
+            }
+        })
+    };
+
+    html! {
+        <SelectInput
+            title="Automaton"
+            options={automata.values().map(|automaton| automaton.name.to_string()).collect::<Vec<String>>()}
+            selected_value={props.selected.clone()}
+            {onchange}
+        />
+    }
+}
+
+/// The time between updates and the number of steps per update that run an automaton at the speed, in steps per
+/// second. Fast speeds take many steps per update instead of updating more often than the screen can show.
+fn playback_interval(speed: f32) -> (u32, usize) {
+    let interval_ms = (1000.0 / speed).max(MIN_INTERVAL_MS);
+    (
+        interval_ms as u32,
+        (speed * interval_ms / 1000.0).round().max(1.0) as usize,
+    )
+}
+
+/// Steps the automaton until it has the given number of generations.
+fn draw_generations(automaton: &mut ElementaryAutomaton, generations: usize) {
+    while automaton.rows().len() < generations {
+        automaton.step();
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+struct SimulationControlsProps {
+    running: bool,
+    /// How many steps are taken per second while running.
+    speed: f32,
+    max_speed: f32,
+    /// What the steps are called, like "generations".
+    unit: &'static str,
+    on_toggle_running: Callback<()>,
+    on_step: Callback<()>,
+    on_change_speed: Callback<f32>,
+}
+
+/// The buttons for running the automaton and the speed of the simulation.
+#[function_component]
+fn SimulationControls(props: &SimulationControlsProps) -> Html {
+    let on_toggle_running = props.on_toggle_running.clone();
+    let on_step = props.on_step.clone();
+
+    html! {
+        <>
+            <Button
+                title={if props.running { "Pause" } else { "Run" }}
+                onclick={Callback::from(move |_| on_toggle_running.emit(()))}
+            />
+            <Button title="Step" onclick={Callback::from(move |_| on_step.emit(()))} />
+            <RangeInput<f32>
+                title={format!("Speed ({} {} per second)", props.speed, props.unit)}
+                value={props.speed}
+                min={1.0}
+                max={props.max_speed}
+                step={1.0}
+                oninput={props.on_change_speed.clone()}
+            />
+        </>
+    }
+}
+
+#[function_component]
+fn GameOfLifePage() -> Html {
+    use_title("Game of Life - Cellular automata".to_string());
+
+    let life = use_mut_ref(|| {
+        let mut life = Life::new(60, 40);
+        life.load(&LIFE_PATTERNS[1]);
+        life
+    });
+    let pattern = use_state_eq(|| LIFE_PATTERNS[1].name.to_string());
+    let running = use_state_eq(|| false);
+    let speed = use_state_eq(|| 10.0_f32);
+    // Announced to screen readers when the grid changes for a reason other than running it
+    let status = use_state_eq(|| format!("Loaded the {}", LIFE_PATTERNS[1].name));
+    // Whether the current drag erases cells, which depends on the first cell that is drawn
+    let erasing = use_mut_ref(|| None::<bool>);
+    let force_update = use_force_update();
+
+    let step = {
+        let life = Rc::clone(&life);
+        let running = running.clone();
+        let status = status.clone();
+        let force_update = force_update.clone();
+
+        move |generations: usize| {
+            for _ in 0..generations {
+                let (births, deaths) = life.borrow_mut().step();
+                if births == 0 && deaths == 0 {
+                    running.set(false);
+                    status.set(format!(
+                        "Generation {} is the same as the one before it, so the cells will never change again",
+                        life.borrow().generation()
+                    ));
+                    break;
+                }
+            }
+            force_update.force_update();
+        }
+    };
+
+    {
+        let step = step.clone();
+        let (interval_ms, generations) = playback_interval(*speed);
+        use_interval(
+            move || step(generations),
+            if *running { interval_ms } else { 0 },
+        );
+    }
+
+    let on_toggle_running = {
+        let running = running.clone();
+        Callback::from(move |_| running.set(!*running))
+    };
+
+    let on_step = {
+        let running = running.clone();
+        Callback::from(move |_| {
+            running.set(false);
+            step(1);
+        })
+    };
+
+    let on_change_speed = {
+        let speed = speed.clone();
+        Callback::from(move |new_speed| speed.set(new_speed))
+    };
+
+    let on_click_cell = {
+        let life = Rc::clone(&life);
+        let erasing = Rc::clone(&erasing);
+        let force_update = force_update.clone();
+
+        Callback::from(move |cell: Coord| {
+            let (x, y) = (cell.x as usize, cell.y as usize);
+            let alive = life.borrow().is_alive(x, y);
+            let erasing = *erasing.borrow_mut().get_or_insert(alive);
+            life.borrow_mut().set(x, y, !erasing);
+            force_update.force_update();
+        })
+    };
+
+    let on_draw_end = {
+        let erasing = Rc::clone(&erasing);
+        Callback::from(move |_| *erasing.borrow_mut() = None)
+    };
+
+    let on_change_pattern = {
+        let life = Rc::clone(&life);
+        let pattern = pattern.clone();
+        let status = status.clone();
+        let force_update = force_update.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(new_pattern) = LIFE_PATTERNS.iter().find(|p| p.name == name) {
+                if life.borrow_mut().load(new_pattern) {
+                    status.set(format!("Loaded the {}", new_pattern.name));
+                } else {
+                    status.set(format!(
+                        "The {} doesn't fit, since it is {} by {} cells",
+                        new_pattern.name,
+                        new_pattern.width(),
+                        new_pattern.height()
+                    ));
+                }
+                pattern.set(name);
+                force_update.force_update();
+            }
+        })
+    };
+
+    let on_randomize = {
+        let life = Rc::clone(&life);
+        let status = status.clone();
+        let force_update = force_update.clone();
+
+        Callback::from(move |_| {
+            life.borrow_mut().randomize(RANDOM_DENSITY);
+            status.set("Filled the grid with random cells".to_string());
+            force_update.force_update();
+        })
+    };
+
+    let on_clear = {
+        let life = Rc::clone(&life);
+        let running = running.clone();
+        let status = status.clone();
+        let force_update = force_update.clone();
+
+        Callback::from(move |_| {
+            life.borrow_mut().clear();
+            running.set(false);
+            status.set("Cleared the grid".to_string());
+            force_update.force_update();
+        })
+    };
+
+    let change_grid_width = {
+        let life = Rc::clone(&life);
+        let force_update = force_update.clone();
+
+        Callback::from(move |width: usize| {
+            let height = life.borrow().height();
+            if width > 1 && width <= MAX_GRID_SIZE && width != life.borrow().width() {
+                life.borrow_mut().resize(width, height);
+                force_update.force_update();
+            }
+        })
+    };
+
+    let change_grid_height = {
+        let life = Rc::clone(&life);
+        let force_update = force_update.clone();
+
+        Callback::from(move |height: usize| {
+            let width = life.borrow().width();
+            if height > 1 && height <= MAX_GRID_SIZE && height != life.borrow().height() {
+                life.borrow_mut().resize(width, height);
+                force_update.force_update();
+            }
+        })
+    };
+
+    let toggle_wrap = {
+        let life = Rc::clone(&life);
+        let force_update = force_update.clone();
+
+        Callback::from(move |_| {
+            let wrap = !life.borrow().wrap;
+            life.borrow_mut().wrap = wrap;
+            force_update.force_update();
+        })
+    };
+
+    let life = life.borrow();
+    let cells = life
+        .cells()
+        .iter()
+        .map(|alive| *alive as u8)
+        .collect::<Vec<u8>>();
+
+    html! {
+        <div class="page" id="CellularAutomata">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <AutomatonSelect selected="Game of Life" />
+                    <SimulationControls
+                        running={*running}
+                        speed={*speed}
+                        max_speed={30.0}
+                        unit="generations"
+                        {on_toggle_running}
+                        {on_step}
+                        {on_change_speed}
+                    />
+                </Collapsible>
+
+                <Collapsible title="Cells" open={true} class="config-section">
+                    <SelectInput
+                        title="Pattern"
+                        options={LIFE_PATTERNS.iter().map(|p| p.name.to_string()).collect::<Vec<String>>()}
+                        selected_value={(*pattern).clone()}
+                        onchange={on_change_pattern}
+                    />
+                    <Button title="Random cells" onclick={on_randomize} />
+                    <Button title="Clear" onclick={on_clear} />
+                </Collapsible>
+
+                <Collapsible title="Grid" open={false} class="config-section">
+                    <IntInput<usize>
+                        title="Grid width"
+                        value={life.width()}
+                        min={2}
+                        max={MAX_GRID_SIZE}
+                        oninput={change_grid_width}
+                    />
+                    <IntInput<usize>
+                        title="Grid height"
+                        value={life.height()}
+                        min={2}
+                        max={MAX_GRID_SIZE}
+                        oninput={change_grid_height}
+                    />
+                    <Checkbox
+                        title="Wrap around edges"
+                        value={life.wrap}
+                        oninput={toggle_wrap}
+                    />
+                </Collapsible>
+            </Sidebar>
+
+            <main>
+                <div class="visualization">
+                    <span>{ format!("Generation {}, population {}", life.generation(), life.population()) }</span>
+
+                    <CellGrid
+                        width={life.width()}
+                        height={life.height()}
+                        cells={Rc::new(cells)}
+                        colors={vec![LIFE_COLOR]}
+                        show_grid={life.width().max(life.height()) <= 80}
+                        label="Game of Life grid. Use the arrow keys to move the cursor, enter or space to bring a cell to life or kill it and escape to hide the cursor."
+                        {on_click_cell}
+                        {on_draw_end}
+                    />
+
+                    <p id="cellGridStatus" class="visually-hidden" role="status" aria-live="polite">
+                        { (*status).clone() }
+                    </p>
+                </div>
+
+                <AlgoDesc algorithm="Game of Life" directory="cellular_automata_algorithms" />
+            </main>
+        </div>
+    }
+}
+
+#[function_component]
+fn LangtonsAntPage() -> Html {
+    use_title("Langton's ant - Cellular automata".to_string());
+
+    let ant = use_mut_ref(|| LangtonsAnt::new(120, 90, parse_ant_rule(ANT_RULES[0].0).unwrap()));
+    let rule_text = use_state_eq(|| ANT_RULES[0].0.to_string());
+    let rule_error = use_state_eq(|| None::<String>);
+    let running = use_state_eq(|| false);
+    let speed = use_state_eq(|| 200.0_f32);
+    // Announced to screen readers when the ant stops or the grid changes for a reason other than running it
+    let status = use_state_eq(String::new);
+    let force_update = use_force_update();
+
+    let step = {
+        let ant = Rc::clone(&ant);
+        let running = running.clone();
+        let status = status.clone();
+        let force_update = force_update.clone();
+
+        move |steps: usize| {
+            for _ in 0..steps {
+                if !ant.borrow_mut().step() {
+                    running.set(false);
+                    status.set(format!(
+                        "The ant stopped at the edge of the grid after {} steps",
+                        ant.borrow().steps()
+                    ));
+                    break;
+                }
+            }
+            force_update.force_update();
+        }
+    };
+
+    {
+        let step = step.clone();
+        let (interval_ms, steps) = playback_interval(*speed);
+        use_interval(move || step(steps), if *running { interval_ms } else { 0 });
+    }
+
+    let on_toggle_running = {
+        let running = running.clone();
+        Callback::from(move |_| running.set(!*running))
+    };
+
+    let on_step = {
+        let running = running.clone();
+        let step = step.clone();
+        Callback::from(move |_| {
+            running.set(false);
+            step(1);
+        })
+    };
+
+    let on_skip = Callback::from(move |_| step(1000));
+
+    let on_change_speed = {
+        let speed = speed.clone();
+        Callback::from(move |new_speed| speed.set(new_speed))
+    };
+
+    // Starts over with the rule, or shows why the rule isn't valid
+    let change_rule = {
+        let ant = Rc::clone(&ant);
+        let rule_text = rule_text.clone();
+        let rule_error = rule_error.clone();
+        let running = running.clone();
+        let status = status.clone();
+        let force_update = force_update.clone();
+
+        Callback::from(move |text: String| {
+            match parse_ant_rule(&text) {
+                Ok(rule) => {
+                    let (width, height) = (ant.borrow().width(), ant.borrow().height());
+                    ant.borrow_mut().reset(width, height, rule);
+                    rule_error.set(None);
+                    running.set(false);
+                    status.set(format!(
+                        "Started over with the rule {}",
+                        text.to_uppercase()
+                    ));
+                }
+                Err(error) => rule_error.set(Some(error)),
+            }
+            rule_text.set(text);
+            force_update.force_update();
+        })
+    };
+
+    let on_reset = {
+        let rule_text = rule_text.clone();
+        let change_rule = change_rule.clone();
+        Callback::from(move |_| change_rule.emit((*rule_text).clone()))
+    };
+
+    let on_click_cell = {
+        let ant = Rc::clone(&ant);
+        let force_update = force_update.clone();
+
+        Callback::from(move |cell: Coord| {
+            ant.borrow_mut()
+                .cycle_color(cell.x as usize, cell.y as usize);
+            force_update.force_update();
+        })
+    };
+
+    let change_grid_size = {
+        let ant = Rc::clone(&ant);
+        let running = running.clone();
+        let force_update = force_update.clone();
+
+        move |width: usize, height: usize| {
+            let changed = width != ant.borrow().width() || height != ant.borrow().height();
+            if width > 1
+                && height > 1
+                && width <= MAX_GRID_SIZE
+                && height <= MAX_GRID_SIZE
+                && changed
+            {
+                let rule = ant.borrow().rule().to_vec();
+                ant.borrow_mut().reset(width, height, rule);
+                running.set(false);
+                force_update.force_update();
+            }
+        }
+    };
+
+    let change_grid_width = {
+        let ant = Rc::clone(&ant);
+        let change_grid_size = change_grid_size.clone();
+        Callback::from(move |width| {
+            let height = ant.borrow().height();
+            change_grid_size(width, height);
+        })
+    };
+
+    let change_grid_height = {
+        let ant = Rc::clone(&ant);
+        Callback::from(move |height| {
+            let width = ant.borrow().width();
+            change_grid_size(width, height);
+        })
+    };
+
+    let toggle_wrap = {
+        let ant = Rc::clone(&ant);
+        Callback::from(move |_| {
+            let wrap = !ant.borrow().wrap;
+            ant.borrow_mut().wrap = wrap;
+            force_update.force_update();
+        })
+    };
+
+    let ant = ant.borrow();
+    let (x, y) = ant.ant();
+    let known_rule = ANT_RULES
+        .iter()
+        .find(|(rule, _)| rule.eq_ignore_ascii_case(rule_text.trim()));
+
+    html! {
+        <div class="page" id="CellularAutomata">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <AutomatonSelect selected="Langton's ant" />
+                    <SimulationControls
+                        running={*running}
+                        speed={*speed}
+                        max_speed={1000.0}
+                        unit="steps"
+                        {on_toggle_running}
+                        {on_step}
+                        {on_change_speed}
+                    />
+                    <Button title="Skip 1000 steps" onclick={on_skip} />
+                    <Button title="Start over" onclick={on_reset} />
+                </Collapsible>
+
+                <Collapsible title="Rule" open={true} class="config-section">
+                    <SelectInput
+                        title="Known rules"
+                        options={ANT_RULES.iter().map(|(rule, _)| rule.to_string()).collect::<Vec<String>>()}
+                        selected_value={known_rule.map_or(String::new(), |(rule, _)| rule.to_string())}
+                        onchange={change_rule.clone()}
+                    />
+                    <TextInput
+                        title="Rule"
+                        value={(*rule_text).clone()}
+                        max_length={MAX_COLORS}
+                        oninput={change_rule}
+                    />
+                    <p>
+                        {
+                            match known_rule {
+                                Some((_, description)) => description.to_string(),
+                                None => "Every letter is the turn on a color: L for left, R for right, N for none and U for a U-turn".to_string(),
+                            }
+                        }
+                    </p>
+                    {
+                        if let Some(error) = &*rule_error {
+                            html! { <p class="error" role="alert">{ error }</p> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </Collapsible>
+
+                <Collapsible title="Grid" open={false} class="config-section">
+                    <IntInput<usize>
+                        title="Grid width"
+                        value={ant.width()}
+                        min={2}
+                        max={MAX_GRID_SIZE}
+                        oninput={change_grid_width}
+                    />
+                    <IntInput<usize>
+                        title="Grid height"
+                        value={ant.height()}
+                        min={2}
+                        max={MAX_GRID_SIZE}
+                        oninput={change_grid_height}
+                    />
+                    <Checkbox
+                        title="Wrap around edges"
+                        value={ant.wrap}
+                        oninput={toggle_wrap}
+                    />
+                </Collapsible>
+            </Sidebar>
+
+            <main>
+                <div class="visualization">
+                    <span>{ format!("Step {}, the ant faces {}", ant.steps(), ant.direction().name()) }</span>
+
+                    <CellGrid
+                        width={ant.width()}
+                        height={ant.height()}
+                        cells={Rc::new(ant.cells().to_vec())}
+                        colors={ANT_COLORS[..ant.rule().len() - 1].to_vec()}
+                        show_grid={ant.width().max(ant.height()) <= 80}
+                        marker={Coord::new(x as isize, y as isize)}
+                        label="Langton's ant grid. Use the arrow keys to move the cursor, enter or space to change the color of a cell and escape to hide the cursor."
+                        {on_click_cell}
+                    />
+
+                    <p id="cellGridStatus" class="visually-hidden" role="status" aria-live="polite">
+                        { (*status).clone() }
+                    </p>
+                </div>
+
+                // Without the apostrophe, since the name is also the directory of the README
+                <AlgoDesc algorithm="Langtons ant" directory="cellular_automata_algorithms" />
+            </main>
+        </div>
+    }
+}
+
+#[function_component]
+fn ElementaryPage() -> Html {
+    use_title("Elementary cellular automaton - Cellular automata".to_string());
+
+    let generations = use_state_eq(|| 50);
+    let automaton = use_mut_ref(|| {
+        let mut automaton = ElementaryAutomaton::new(30, ElementaryAutomaton::single_cell(101));
+        if prefers_reduced_motion() {
+            draw_generations(&mut automaton, *generations);
+        }
+        automaton
+    });
+    let first_generation = use_state_eq(|| FIRST_GENERATIONS[0].to_string());
+    // The diagram is drawn right away, and again whenever the rule or the first generation changes.
+    // If reduced motion is preferred, all of the generations are drawn at once instead.
+    let running = use_state_eq(|| !prefers_reduced_motion());
+    let speed = use_state_eq(|| 20.0_f32);
+    // Announced to screen readers when the drawing stops or starts over
+    let status = use_state_eq(String::new);
+    let force_update = use_force_update();
+
+    let step = {
+        let automaton = Rc::clone(&automaton);
+        let generations = *generations;
+        let running = running.clone();
+        let status = status.clone();
+        let force_update = force_update.clone();
+
+        move |rows: usize| {
+            for _ in 0..rows {
+                if automaton.borrow().rows().len() >= generations {
+                    running.set(false);
+                    status.set(format!(
+                        "All {} generations of rule {} have been drawn",
+                        generations,
+                        automaton.borrow().rule()
+                    ));
+                    break;
+                }
+                automaton.borrow_mut().step();
+            }
+            force_update.force_update();
+        }
+    };
+
+    {
+        let step = step.clone();
+        let (interval_ms, rows) = playback_interval(*speed);
+        use_interval(move || step(rows), if *running { interval_ms } else { 0 });
+    }
+
+    let on_toggle_running = {
+        let running = running.clone();
+        Callback::from(move |_| running.set(!*running))
+    };
+
+    let on_step = {
+        let running = running.clone();
+        let step = step.clone();
+        Callback::from(move |_| {
+            running.set(false);
+            step(1);
+        })
+    };
+
+    let on_draw_all = {
+        let generations = *generations;
+        Callback::from(move |_| step(generations))
+    };
+
+    let on_change_speed = {
+        let speed = speed.clone();
+        Callback::from(move |new_speed| speed.set(new_speed))
+    };
+
+    // Draws the diagram again after it has started over
+    let start = {
+        let automaton = Rc::clone(&automaton);
+        let running = running.clone();
+        let force_update = force_update.clone();
+
+        move |generations: usize| {
+            if prefers_reduced_motion() {
+                draw_generations(&mut automaton.borrow_mut(), generations);
+                force_update.force_update();
+            } else {
+                running.set(true);
+            }
+        }
+    };
+
+    let change_rule = {
+        let automaton = Rc::clone(&automaton);
+        let generations = *generations;
+        let status = status.clone();
+        let start = start.clone();
+
+        Callback::from(move |rule: u8| {
+            automaton.borrow_mut().set_rule(rule);
+            status.set(format!("Started over with rule {}", rule));
+            start(generations);
+        })
+    };
+
+    let on_select_rule = {
+        let change_rule = change_rule.clone();
+        Callback::from(move |name: String| {
+            if let Some(rule) = name
+                .strip_prefix("Rule ")
+                .and_then(|rule| rule.parse().ok())
+            {
+                change_rule.emit(rule);
+            }
+        })
+    };
+
+    // Starts over from a new first generation of the chosen kind
+    let restart = {
+        let automaton = Rc::clone(&automaton);
+        let generations = *generations;
+        let status = status.clone();
+        let start = start.clone();
+
+        move |kind: &str, width: usize| {
+            let first_row = if kind == "Random cells" {
+                ElementaryAutomaton::random(width)
+            } else {
+                ElementaryAutomaton::single_cell(width)
+            };
+            automaton.borrow_mut().restart(first_row);
+            status.set(format!("Started over from {}", kind.to_lowercase()));
+            start(generations);
+        }
+    };
+
+    let on_change_first_generation = {
+        let automaton = Rc::clone(&automaton);
+        let first_generation = first_generation.clone();
+        let restart = restart.clone();
+
+        Callback::from(move |kind: String| {
+            let width = automaton.borrow().width();
+            restart(&kind, width);
+            first_generation.set(kind);
+        })
+    };
+
+    let on_reset = {
+        let automaton = Rc::clone(&automaton);
+        let first_generation = first_generation.clone();
+        let restart = restart.clone();
+
+        Callback::from(move |_| {
+            let width = automaton.borrow().width();
+            restart(&first_generation, width);
+        })
+    };
+
+    let on_click_cell = {
+        let automaton = Rc::clone(&automaton);
+        let generations = *generations;
+        let status = status.clone();
+        let start = start.clone();
+
+        Callback::from(move |cell: Coord| {
+            // Only the first generation can be drawn, since the rule decides the rest
+            if cell.y == 0 {
+                automaton.borrow_mut().toggle_first(cell.x as usize);
+                status.set(format!(
+                    "Flipped cell {} of the first generation and started over",
+                    cell.x
+                ));
+                start(generations);
+            }
+        })
+    };
+
+    let change_width = {
+        let automaton = Rc::clone(&automaton);
+        let first_generation = first_generation.clone();
+
+        Callback::from(move |width: usize| {
+            if width > 2 && width <= MAX_GRID_SIZE && width != automaton.borrow().width() {
+                restart(&first_generation, width);
+            }
+        })
+    };
+
+    let change_generations = {
+        let automaton = Rc::clone(&automaton);
+        let generations = generations.clone();
+        let start = start.clone();
+
+        Callback::from(move |new_generations: usize| {
+            if new_generations > 1 && new_generations <= MAX_GRID_SIZE {
+                let first_row = automaton.borrow().rows()[0].clone();
+                automaton.borrow_mut().restart(first_row);
+                generations.set(new_generations);
+                start(new_generations);
+            }
+        })
+    };
+
+    let toggle_wrap = {
+        let automaton = Rc::clone(&automaton);
+        let generations = *generations;
+
+        Callback::from(move |_| {
+            let wrap = !automaton.borrow().wrap;
+            let first_row = automaton.borrow().rows()[0].clone();
+            {
+                let mut automaton = automaton.borrow_mut();
+                automaton.wrap = wrap;
+                automaton.restart(first_row);
+            }
+            start(generations);
+        })
+    };
+
+    let automaton = automaton.borrow();
+    let rule = automaton.rule();
+    let known_rule = ELEMENTARY_RULES.iter().find(|(known, _)| *known == rule);
+    // The new state of every neighborhood, from 111 to 000 like the bits of the rule number
+    let rule_table = (0..8)
+        .rev()
+        .map(|neighborhood: u8| {
+            let alive = ElementaryAutomaton::next_state(
+                rule,
+                neighborhood & 4 != 0,
+                neighborhood & 2 != 0,
+                neighborhood & 1 != 0,
+            );
+            format!("{:03b} → {}", neighborhood, alive as u8)
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+    let mut cells = automaton
+        .rows()
+        .iter()
+        .flatten()
+        .map(|alive| *alive as u8)
+        .collect::<Vec<u8>>();
+    cells.resize(automaton.width() * *generations, 0);
+
+    html! {
+        <div class="page" id="CellularAutomata">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <AutomatonSelect selected="Elementary cellular automaton" />
+                    <SimulationControls
+                        running={*running}
+                        speed={*speed}
+                        max_speed={60.0}
+                        unit="generations"
+                        {on_toggle_running}
+                        {on_step}
+                        {on_change_speed}
+                    />
+                    <Button title="Draw all generations" onclick={on_draw_all} />
+                    <Button title="Start over" onclick={on_reset} />
+                </Collapsible>
+
+                <Collapsible title="Rule" open={true} class="config-section">
+                    <SelectInput
+                        title="Known rules"
+                        options={ELEMENTARY_RULES.iter().map(|(rule, _)| format!("Rule {}", rule)).collect::<Vec<String>>()}
+                        selected_value={known_rule.map_or(String::new(), |(rule, _)| format!("Rule {}", rule))}
+                        onchange={on_select_rule}
+                    />
+                    <IntInput<u8>
+                        title="Rule"
+                        value={rule}
+                        min={0}
+                        max={255}
+                        oninput={change_rule}
+                    />
+                    {
+                        if let Some((_, description)) = known_rule {
+                            html! { <p>{ description }</p> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                    <SelectInput
+                        title="First generation"
+                        options={FIRST_GENERATIONS.iter().map(|kind| kind.to_string()).collect::<Vec<String>>()}
+                        selected_value={(*first_generation).clone()}
+                        onchange={on_change_first_generation}
+                    />
+                </Collapsible>
+
+                <Collapsible title="Grid" open={false} class="config-section">
+                    <IntInput<usize>
+                        title="Cells"
+                        value={automaton.width()}
+                        min={3}
+                        max={MAX_GRID_SIZE}
+                        oninput={change_width}
+                    />
+                    <IntInput<usize>
+                        title="Generations"
+                        value={*generations}
+                        min={2}
+                        max={MAX_GRID_SIZE}
+                        oninput={change_generations}
+                    />
+                    <Checkbox
+                        title="Wrap around edges"
+                        value={automaton.wrap}
+                        oninput={toggle_wrap}
+                    />
+                </Collapsible>
+            </Sidebar>
+
+            <main>
+                <div class="visualization">
+                    <span>{ format!("Rule {}: {}", rule, rule_table) }</span>
+
+                    <CellGrid
+                        width={automaton.width()}
+                        height={*generations}
+                        cells={Rc::new(cells)}
+                        colors={vec![LIFE_COLOR]}
+                        show_grid={automaton.width().max(*generations) <= 80}
+                        label="Space-time diagram of an elementary cellular automaton, with the first generation at the top. Use the arrow keys to move the cursor, enter or space on the first row to flip a cell of the first generation and escape to hide the cursor."
+                        {on_click_cell}
+                    />
+
+                    <p id="cellGridStatus" class="visually-hidden" role="status" aria-live="polite">
+                        { (*status).clone() }
+                    </p>
+                </div>
+
+                <AlgoDesc algorithm="Elementary" directory="cellular_automata_algorithms" />
+            </main>
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct CellularAutomata404PageProps {
+    automaton: String,
+}
+
+#[function_component]
+fn CellularAutomata404Page(props: &CellularAutomata404PageProps) -> Html {
+    use_title("404 - Cellular automata".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The automaton \"{}\" was not found.", props.automaton) }</p>
+            <Link<CellularAutomataRoute> to={CellularAutomataRoute::CellularAutomata}>
+                { "Back to cellular automata" }
+            </Link<CellularAutomataRoute>>
+        </>
+    }
+}
@@ -0,0 +1,403 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use data_structures::structures::*;
+use diagram::ElementState;
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+pub fn get_data_structures() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "avl-tree",
+            VisualizerInfo::new("AVL tree", || {
+                Box::new(AvlTree::from_values(&[40, 20, 60, 10, 30, 50]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Compared or rotated"),
+                (ElementState::Frontier, "Path from the root or taller child"),
+                (ElementState::Selected, "Found, inserted or moved up"),
+                (ElementState::Rejected, "Removed or missing"),
+                (ElementState::Conflict, "Unbalanced or already in the tree"),
+            ])
+            .with_tags(&["tree", "self-balancing", "O(log n)"]),
+        ),
+        (
+            "b-tree",
+            VisualizerInfo::new("B-tree", || {
+                Box::new(BTree::from_values(4, &[10, 20, 30, 40, 50, 60, 70, 80, 90]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Compared, split, merged or filled up"),
+                (ElementState::Frontier, "Path from the root or parent"),
+                (ElementState::Selected, "Found, inserted or moved"),
+                (ElementState::Rejected, "Removed or missing"),
+                (
+                    ElementState::Conflict,
+                    "Too many or too few keys, or already in the tree",
+                ),
+            ])
+            .with_tags(&["tree", "self-balancing", "O(log n)"]),
+        ),
+        (
+            "binary-heap",
+            VisualizerInfo::new("Binary heap", || {
+                Box::new(BinaryHeap::from_values(
+                    false,
+                    &[12, 35, 7, 58, 23, 91, 4, 66, 40],
+                ))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Compared"),
+                (ElementState::Frontier, "Subtree being made a heap"),
+                (ElementState::Selected, "Swapped, inserted or extracted"),
+                (ElementState::Rejected, "Removed"),
+            ])
+            .with_tags(&["tree", "heap", "priority queue", "O(log n)"]),
+        ),
+        (
+            "binary-search-tree",
+            VisualizerInfo::new("Binary search tree", || {
+                Box::new(BinarySearchTree::from_values(&[
+                    50, 30, 70, 20, 40, 60, 80, 35, 65,
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Compared or visited"),
+                (ElementState::Frontier, "Path from the root"),
+                (ElementState::Selected, "Found, inserted or visited"),
+                (ElementState::Rejected, "Removed or missing"),
+                (ElementState::Conflict, "Already in the tree"),
+            ])
+            .with_tags(&["tree", "O(h)"]),
+        ),
+        (
+            "bloom-filter",
+            VisualizerInfo::new("Bloom filter", || {
+                Box::new(BloomFilter::from_words(
+                    32,
+                    3,
+                    &["cat", "owl", "fox", "bee", "ant", "elk"],
+                ))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Checked or already set"),
+                (ElementState::Selected, "Set or probably in the set"),
+                (ElementState::Rejected, "Not set, so not in the set"),
+                (ElementState::Conflict, "False positive"),
+            ])
+            .with_tags(&["hashing", "probabilistic", "O(k)"]),
+        ),
+        (
+            "consistent-hashing",
+            VisualizerInfo::new("Consistent hashing", || {
+                Box::new(ConsistentHashing::from_keys(
+                    &['A', 'B', 'C'],
+                    &[
+                        "kiwi", "apple", "plum", "fig", "lime", "pear", "mango", "grape", "melon",
+                        "peach", "lemon", "cherry",
+                    ],
+                ))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Placed key or walk along the ring"),
+                (ElementState::Selected, "Added point, stored or moved key"),
+                (ElementState::Rejected, "Removed point"),
+                (ElementState::Conflict, "Already on the ring"),
+            ])
+            .with_tags(&["hashing", "distributed"]),
+        ),
+        (
+            "deque",
+            VisualizerInfo::new("Deque", || Box::new(Deque::from_values(&[8, 3, 5])))
+                .with_legend(&[
+                    (ElementState::Selected, "Added"),
+                    (ElementState::Rejected, "Removed"),
+                ])
+                .with_tags(&["linear", "O(1)"]),
+        ),
+        (
+            "fenwick-tree",
+            VisualizerInfo::new("Fenwick tree", || {
+                Box::new(FenwickTree::new(&[5, 8, 6, 3, 2, 7, 2, 6]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current node or range"),
+                (ElementState::Selected, "Added, updated or in the range"),
+                (ElementState::Rejected, "Before the range"),
+            ])
+            .with_tags(&["tree", "prefix sums", "O(log n)"]),
+        ),
+        (
+            "hash-table",
+            VisualizerInfo::new("Hash table", || {
+                Box::new(HashTable::from_keys("Separate chaining", &[12, 7, 22, 31]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Probed or compared"),
+                (ElementState::Frontier, "Slot of the key"),
+                (ElementState::Selected, "Found, inserted or rehashed"),
+                (ElementState::Rejected, "Deleted"),
+                (ElementState::Conflict, "Collision or already in the table"),
+            ])
+            .with_tags(&["hashing", "O(1) average"]),
+        ),
+        (
+            "linked-list",
+            VisualizerInfo::new("Linked list", || {
+                Box::new(LinkedList::from_values(false, &[7, 13, 21, 34, 55]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current node or rewired pointer"),
+                (ElementState::Frontier, "Followed pointer"),
+                (ElementState::Selected, "New node or turned pointer"),
+                (ElementState::Rejected, "Removed"),
+            ])
+            .with_tags(&["linear", "pointers"]),
+        ),
+        (
+            "lru-cache",
+            VisualizerInfo::new("LRU cache", || {
+                Box::new(LruCache::from_accesses(4, &[7, 3, 9]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Hit"),
+                (ElementState::Selected, "Added or moved to the head"),
+                (ElementState::Rejected, "Evicted"),
+            ])
+            .with_tags(&["hashing", "linked list", "O(1)"]),
+        ),
+        (
+            "queue",
+            VisualizerInfo::new("Queue", || Box::new(Queue::from_values(&[8, 3, 5])))
+                .with_legend(&[
+                    (ElementState::Active, "Front or visited vertex"),
+                    (ElementState::Frontier, "Discovered vertex in the queue"),
+                    (ElementState::Selected, "Enqueued or visited"),
+                    (ElementState::Rejected, "Dequeued"),
+                ])
+                .with_tags(&["linear", "FIFO", "O(1)"]),
+        ),
+        (
+            "red-black-tree",
+            VisualizerInfo::new("Red-black tree", || {
+                Box::new(RedBlackTree::from_values(&[40, 20, 60, 10, 30, 50, 70]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Compared, recolored or rotated"),
+                (
+                    ElementState::Frontier,
+                    "Path from the root, uncle or sibling",
+                ),
+                (ElementState::Selected, "Found, inserted or moved up"),
+                (ElementState::Rejected, "Removed or missing"),
+                (
+                    ElementState::Conflict,
+                    "Red nodes in a row, double black or already in the tree",
+                ),
+            ])
+            .with_tags(&["tree", "self-balancing", "O(log n)"]),
+        ),
+        (
+            "segment-tree",
+            VisualizerInfo::new("Segment tree", || {
+                Box::new(SegmentTree::new(&[5, 8, 6, 3, 2, 7, 2, 6]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current range"),
+                (
+                    ElementState::Frontier,
+                    "Partly in the range or path to the leaf",
+                ),
+                (ElementState::Selected, "Used, computed or updated"),
+                (ElementState::Rejected, "Outside of the range"),
+            ])
+            .with_tags(&["tree", "range queries", "O(log n)"]),
+        ),
+        (
+            "skip-list",
+            VisualizerInfo::new("Skip list", || {
+                Box::new(SkipList::from_towers(&[
+                    (3, 1),
+                    (7, 2),
+                    (12, 1),
+                    (19, 4),
+                    (25, 1),
+                    (31, 2),
+                    (40, 1),
+                    (61, 3),
+                    (77, 1),
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current cell, pointer or tower"),
+                (ElementState::Frontier, "Search path"),
+                (ElementState::Selected, "Found, linked or relinked"),
+                (ElementState::Rejected, "Removed or missing"),
+                (ElementState::Conflict, "Already in the list"),
+            ])
+            .with_tags(&["linked list", "randomized", "O(log n) average"]),
+        ),
+        (
+            "splay-tree",
+            VisualizerInfo::new("Splay tree", || {
+                Box::new(SplayTree::from_values(&[50, 30, 70, 20, 40, 60, 80]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Compared, splayed or rotated"),
+                (ElementState::Frontier, "Path from the root"),
+                (ElementState::Selected, "Found, inserted or at the root"),
+                (ElementState::Rejected, "Removed or missing"),
+                (ElementState::Conflict, "Already in the tree"),
+            ])
+            .with_tags(&["tree", "self-adjusting", "amortized O(log n)"]),
+        ),
+        (
+            "stack",
+            VisualizerInfo::new("Stack", || Box::new(Stack::from_values(&[8, 3, 5])))
+                .with_legend(&[
+                    (ElementState::Active, "Top or current bracket"),
+                    (ElementState::Selected, "Pushed"),
+                    (ElementState::Rejected, "Popped"),
+                    (ElementState::Conflict, "Unmatched bracket"),
+                ])
+                .with_tags(&["linear", "LIFO", "O(1)"]),
+        ),
+        (
+            "treap",
+            VisualizerInfo::new("Treap", || {
+                Box::new(Treap::from_pairs(&[
+                    (50, 90),
+                    (30, 70),
+                    (70, 80),
+                    (20, 40),
+                    (40, 60),
+                    (60, 30),
+                    (80, 50),
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Compared or rotated"),
+                (ElementState::Frontier, "Path from the root"),
+                (ElementState::Selected, "Found, inserted or in heap order"),
+                (ElementState::Rejected, "Removed, missing or the split key"),
+                (ElementState::Conflict, "Already in the tree"),
+            ])
+            .with_tags(&["tree", "randomized", "O(log n) average"]),
+        ),
+        (
+            "trie",
+            VisualizerInfo::new("Trie", || {
+                Box::new(Trie::from_words(&[
+                    "to", "tea", "ted", "ten", "i", "in", "inn",
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current node"),
+                (ElementState::Frontier, "Path from the root"),
+                (ElementState::Selected, "Found, added or kept"),
+                (ElementState::Rejected, "Missing or removed"),
+                (ElementState::Conflict, "Already in the trie"),
+            ])
+            .with_tags(&["tree", "strings", "prefixes"]),
+        ),
+        (
+            "union-find",
+            VisualizerInfo::new("Union-find", || {
+                Box::new(UnionFind::from_unions(
+                    10,
+                    &[(0, 1), (2, 3), (0, 2), (4, 5), (6, 7), (4, 6)],
+                ))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current element or rewritten pointer"),
+                (ElementState::Frontier, "Path to the root"),
+                (ElementState::Selected, "Root or joined"),
+                (ElementState::Conflict, "Already in the same set"),
+            ])
+            .with_tags(&["disjoint sets", "forest", "amortized O(α(n))"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum DataStructuresRoute {
+    #[at("/data-structures")]
+    DataStructures,
+    #[at("/data-structures/:structure")]
+    DataStructure { structure: String },
+}
+
+pub fn switch_data_structures(route: DataStructuresRoute) -> Html {
+    match route {
+        DataStructuresRoute::DataStructures => html! {
+            <Redirect<DataStructuresRoute> to={DataStructuresRoute::DataStructure { structure: "binary-search-tree".to_string()} } />
+        },
+        DataStructuresRoute::DataStructure { structure } => {
+            if get_data_structures().contains_key(structure.as_str()) {
+                html! {
+                    <DataStructuresPage {structure} />
+                }
+            } else {
+                html! {
+                    <DataStructures404Page {structure} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct DataStructuresPageProps {
+    pub structure: String,
+}
+
+#[function_component]
+pub fn DataStructuresPage(props: &DataStructuresPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let structures = get_data_structures();
+    let names = structures
+        .values()
+        .map(|structure| structure.name.to_string())
+        .collect::<Vec<String>>();
+    let structure = structures[props.structure.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&DataStructuresRoute::DataStructure {
+            structure: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="DataStructures"
+            section="Data structures"
+            select_title="Data structure"
+            {names}
+            visualizer={structure}
+            {on_select}
+            readme_directory="structures"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct DataStructures404PageProps {
+    structure: String,
+}
+
+#[function_component]
+fn DataStructures404Page(props: &DataStructures404PageProps) -> Html {
+    use_title("404 - Data structures".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The data structure \"{}\" was not found.", props.structure) }</p>
+            <Link<DataStructuresRoute> to={DataStructuresRoute::DataStructures}>
+                { "Back to data structures" }
+            </Link<DataStructuresRoute>>
+        </>
+    }
+}
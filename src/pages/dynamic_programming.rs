@@ -0,0 +1,161 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use diagram::ElementState;
+use dynamic_programming::dynamic_programming_algorithms::*;
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+pub fn get_dynamic_programming_algorithms() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "coin-change",
+            VisualizerInfo::new("Coin change", || Box::new(CoinChange::new(&[1, 3, 4], 10)))
+                .with_legend(&[
+                    (ElementState::Active, "Current amount"),
+                    (ElementState::Frontier, "Amounts left after a coin"),
+                    (ElementState::Selected, "Chosen coin, combination"),
+                    (ElementState::Conflict, "Amount that can't be paid"),
+                ])
+                .with_tags(&["dynamic programming", "O(nk)"]),
+        ),
+        (
+            "fibonacci",
+            VisualizerInfo::new("Fibonacci", || Box::new(Fibonacci::new(5)))
+                .with_legend(&[
+                    (ElementState::Active, "Current call or cell"),
+                    (ElementState::Frontier, "Calls waiting for their children"),
+                    (ElementState::Selected, "Computed values"),
+                    (ElementState::Rejected, "Repeated calls"),
+                ])
+                .with_tags(&["dynamic programming", "memoization", "O(n)"]),
+        ),
+        (
+            "knapsack",
+            VisualizerInfo::new("Knapsack", || {
+                Box::new(Knapsack::new(&[(1, 1), (3, 4), (4, 5), (5, 7)], 7))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current cell"),
+                (ElementState::Frontier, "Cells that it depends on"),
+                (ElementState::Selected, "Chosen option, traceback"),
+            ])
+            .with_tags(&["dynamic programming", "O(nW)"]),
+        ),
+        (
+            "longest-increasing-subsequence",
+            VisualizerInfo::new("Longest increasing subsequence", || {
+                Box::new(LongestIncreasingSubsequence::new(vec![
+                    3, 10, 2, 1, 20, 4, 6, 7, 5, 9,
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current value"),
+                (
+                    ElementState::Frontier,
+                    "Smaller earlier values, predecessor",
+                ),
+                (ElementState::Rejected, "Replaced tail"),
+                (ElementState::Selected, "Subsequence"),
+            ])
+            .with_tags(&["dynamic programming", "binary search", "O(n log n)"]),
+        ),
+        (
+            "matrix-chain",
+            VisualizerInfo::new("Matrix chain", || {
+                Box::new(MatrixChain::new(vec![30, 35, 15, 5, 10, 20, 25]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current chain"),
+                (
+                    ElementState::Frontier,
+                    "Halves of the best split, chains left to split",
+                ),
+                (ElementState::Selected, "Chains of the best order"),
+            ])
+            .with_tags(&["dynamic programming", "matrices", "O(n³)"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum DynamicProgrammingRoute {
+    #[at("/dynamic-programming")]
+    DynamicProgramming,
+    #[at("/dynamic-programming/:algorithm")]
+    DynamicProgrammingAlgorithm { algorithm: String },
+}
+
+pub fn switch_dynamic_programming(route: DynamicProgrammingRoute) -> Html {
+    match route {
+        DynamicProgrammingRoute::DynamicProgramming => html! {
+            <Redirect<DynamicProgrammingRoute> to={DynamicProgrammingRoute::DynamicProgrammingAlgorithm { algorithm: "knapsack".to_string()} } />
+        },
+        DynamicProgrammingRoute::DynamicProgrammingAlgorithm { algorithm } => {
+            if get_dynamic_programming_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <DynamicProgrammingPage {algorithm} />
+                }
+            } else {
+                html! {
+                    <DynamicProgramming404Page {algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct DynamicProgrammingPageProps {
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn DynamicProgrammingPage(props: &DynamicProgrammingPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let algorithms = get_dynamic_programming_algorithms();
+    let names = algorithms
+        .values()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect::<Vec<String>>();
+    let algorithm = algorithms[props.algorithm.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&DynamicProgrammingRoute::DynamicProgrammingAlgorithm {
+            algorithm: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="DynamicProgramming"
+            section="Dynamic programming"
+            select_title="Algorithm"
+            {names}
+            visualizer={algorithm}
+            {on_select}
+            readme_directory="dynamic_programming_algorithms"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct DynamicProgramming404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn DynamicProgramming404Page(props: &DynamicProgramming404PageProps) -> Html {
+    use_title("404 - Dynamic programming".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<DynamicProgrammingRoute> to={DynamicProgrammingRoute::DynamicProgramming}>
+                { "Back to dynamic programming" }
+            </Link<DynamicProgrammingRoute>>
+        </>
+    }
+}
@@ -0,0 +1,251 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use diagram::ElementState;
+use geometry::geometry_algorithms::*;
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+pub fn get_geometry_algorithms() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "closest-pair",
+            VisualizerInfo::new("Closest pair", || {
+                Box::new(ClosestPair::new(&[
+                    (110.0, 240.0),
+                    (190.0, 120.0),
+                    (240.0, 380.0),
+                    (310.0, 210.0),
+                    (360.0, 90.0),
+                    (390.0, 320.0),
+                    (430.0, 240.0),
+                    (450.0, 410.0),
+                    (520.0, 150.0),
+                    (570.0, 290.0),
+                    (610.0, 200.0),
+                    (660.0, 380.0),
+                    (700.0, 110.0),
+                    (720.0, 260.0),
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Splitting line, strip"),
+                (ElementState::Frontier, "Current part"),
+                (ElementState::Selected, "Closest pair"),
+                (ElementState::Rejected, "Not closer"),
+            ])
+            .with_tags(&["points", "divide & conquer", "O(n log n)"]),
+        ),
+        (
+            "convex-hull",
+            VisualizerInfo::new("Convex hull", || {
+                Box::new(ConvexHull::new(&[
+                    (140.0, 260.0),
+                    (230.0, 110.0),
+                    (300.0, 330.0),
+                    (380.0, 200.0),
+                    (420.0, 420.0),
+                    (470.0, 90.0),
+                    (520.0, 290.0),
+                    (600.0, 160.0),
+                    (640.0, 380.0),
+                    (700.0, 250.0),
+                    (330.0, 250.0),
+                    (560.0, 220.0),
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Stack, current point"),
+                (ElementState::Frontier, "Next point, candidate"),
+                (ElementState::Selected, "Convex hull"),
+                (ElementState::Rejected, "Popped or not chosen"),
+            ])
+            .with_tags(&["points", "O(n log n)"]),
+        ),
+        (
+            "delaunay-triangulation",
+            VisualizerInfo::new("Delaunay triangulation", || {
+                Box::new(DelaunayTriangulation::new(&[
+                    (150.0, 140.0),
+                    (260.0, 330.0),
+                    (330.0, 110.0),
+                    (400.0, 250.0),
+                    (460.0, 400.0),
+                    (520.0, 150.0),
+                    (610.0, 280.0),
+                    (680.0, 120.0),
+                    (200.0, 420.0),
+                    (700.0, 410.0),
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Inserted point, new edges"),
+                (ElementState::Frontier, "Checked edge, circumcircle"),
+                (ElementState::Selected, "Flipped edge, Voronoi diagram"),
+                (ElementState::Rejected, "Illegal edge"),
+            ])
+            .with_tags(&["points", "triangulation", "Voronoi diagram", "O(n log n)"]),
+        ),
+        (
+            "flood-fill",
+            VisualizerInfo::new("Flood fill", || {
+                Box::new(FloodFill::new(
+                    &[
+                        "....................",
+                        ".#########..........",
+                        ".#.......#....###...",
+                        ".#.......#...#...#..",
+                        ".#.......#..#.....#.",
+                        ".#...........#...#..",
+                        ".#.......#....#.#...",
+                        ".#.......#.....#....",
+                        ".#########..........",
+                        "......#.............",
+                        "......#.....######..",
+                        "......#.............",
+                    ],
+                    (4, 4),
+                ))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Filled pixel or span"),
+                (
+                    ElementState::Frontier,
+                    "Call stack, queue or seeds, and their size",
+                ),
+                (ElementState::Selected, "Filled region"),
+            ])
+            .with_tags(&["grid", "traversal", "O(n)"]),
+        ),
+        (
+            "polygon",
+            VisualizerInfo::new("Polygon", || {
+                Box::new(Polygon::new(
+                    &[
+                        (160.0, 420.0),
+                        (250.0, 90.0),
+                        (380.0, 250.0),
+                        (470.0, 70.0),
+                        (640.0, 140.0),
+                        (560.0, 260.0),
+                        (680.0, 400.0),
+                        (420.0, 350.0),
+                    ],
+                    Some((300.0, 300.0)),
+                ))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Ray, current vertex"),
+                (ElementState::Frontier, "Neighbors of the vertex"),
+                (ElementState::Selected, "Crossing, ear, inside"),
+                (
+                    ElementState::Rejected,
+                    "Not crossing or not an ear, outside",
+                ),
+            ])
+            .with_tags(&["ray casting", "triangulation", "O(n²)"]),
+        ),
+        (
+            "segment-intersection",
+            VisualizerInfo::new("Segment intersection", || {
+                Box::new(SegmentIntersection::new(&[
+                    segment((80.0, 120.0), (420.0, 380.0)),
+                    segment((120.0, 400.0), (520.0, 90.0)),
+                    segment((200.0, 250.0), (720.0, 300.0)),
+                    segment((360.0, 60.0), (640.0, 440.0)),
+                    segment((460.0, 420.0), (740.0, 140.0)),
+                    segment((540.0, 60.0), (700.0, 200.0)),
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Sweep line, status"),
+                (ElementState::Frontier, "Event, checked neighbors"),
+                (ElementState::Selected, "Intersection"),
+                (ElementState::Rejected, "Don't meet"),
+            ])
+            .with_tags(&["sweep line", "O((n + k) log n)"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum GeometryRoute {
+    #[at("/geometry")]
+    Geometry,
+    #[at("/geometry/:algorithm")]
+    GeometryAlgorithm { algorithm: String },
+}
+
+pub fn switch_geometry(route: GeometryRoute) -> Html {
+    match route {
+        GeometryRoute::Geometry => html! {
+            <Redirect<GeometryRoute> to={GeometryRoute::GeometryAlgorithm { algorithm: "convex-hull".to_string()} } />
+        },
+        GeometryRoute::GeometryAlgorithm { algorithm } => {
+            if get_geometry_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <GeometryPage {algorithm} />
+                }
+            } else {
+                html! {
+                    <Geometry404Page {algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct GeometryPageProps {
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn GeometryPage(props: &GeometryPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let algorithms = get_geometry_algorithms();
+    let names = algorithms
+        .values()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect::<Vec<String>>();
+    let algorithm = algorithms[props.algorithm.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&GeometryRoute::GeometryAlgorithm {
+            algorithm: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="Geometry"
+            section="Geometry algorithms"
+            select_title="Algorithm"
+            {names}
+            visualizer={algorithm}
+            {on_select}
+            readme_directory="geometry_algorithms"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct Geometry404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn Geometry404Page(props: &Geometry404PageProps) -> Html {
+    use_title("404 - Geometry algorithms".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<GeometryRoute> to={GeometryRoute::Geometry}>
+                { "Back to geometry algorithms" }
+            </Link<GeometryRoute>>
+        </>
+    }
+}
@@ -0,0 +1,776 @@
+use crate::components::{
+    algo_desc::AlgoDesc,
+    collapsible::Collapsible,
+    graph::{toolbar::*, *},
+    graph_editor::{GraphTool, GRAPH_HEIGHT, GRAPH_WIDTH},
+    sidebar::Sidebar,
+    step_slider::StepSlider,
+};
+use crate::utils::download_file;
+use graph::{
+    graph_algorithms, AlgorithmOptions, ElementState, Graph, GraphAlgorithmFunc, GraphStep,
+    GraphSteps,
+};
+use std::{collections::BTreeMap, rc::Rc};
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+#[derive(Clone, Debug)]
+pub struct GraphAlgorithm {
+    pub name: String,
+    /// True if the algorithm runs on directed graphs.
+    pub directed: bool,
+    /// True if the algorithm runs on undirected graphs that can have several edges between the same nodes.
+    pub multigraph: bool,
+    /// True if the weights of the edges affect the algorithm.
+    pub uses_weights: bool,
+    /// True if the edges can have zero and negative weights.
+    pub negative_weights: bool,
+    /// True if the algorithm starts from a node that can be chosen.
+    pub uses_start: bool,
+    /// True if the algorithm ends at a node that can be chosen.
+    pub uses_end: bool,
+    /// True if the number of colors that the algorithm can use can be chosen.
+    pub uses_color_count: bool,
+    /// True if the damping factor of the algorithm can be chosen.
+    pub uses_damping: bool,
+    /// True if the algorithm moves the nodes, so that the layout that it finds can be used for the graph.
+    pub moves_nodes: bool,
+    /// What the states of the nodes and edges mean for the algorithm, in the order that they are shown in the legend.
+    pub legend: Vec<(ElementState, &'static str)>,
+    /// What the colors of the node groups mean, if the algorithm groups nodes.
+    pub groups: Option<&'static str>,
+    /// What the algorithm can be found with in the catalog of the home page, like its time complexity or technique.
+    pub tags: Vec<&'static str>,
+    run: GraphAlgorithmFunc,
+}
+// Function pointers can't be compared reliably, so two `GraphAlgorithm`s are considered equal if their names are the same.
+impl PartialEq for GraphAlgorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+impl GraphAlgorithm {
+    pub fn new(name: &str, run: GraphAlgorithmFunc) -> Self {
+        Self {
+            name: name.to_string(),
+            directed: false,
+            multigraph: false,
+            uses_weights: true,
+            negative_weights: false,
+            uses_start: false,
+            uses_end: false,
+            uses_color_count: false,
+            uses_damping: false,
+            moves_nodes: false,
+            legend: vec![],
+            groups: None,
+            tags: vec![],
+            run,
+        }
+    }
+    pub fn directed(mut self) -> Self {
+        self.directed = true;
+        self
+    }
+    /// Runs the algorithm on multigraphs, whose edges don't have weights.
+    pub fn multigraph(mut self) -> Self {
+        self.multigraph = true;
+        self.uses_weights = false;
+        self
+    }
+    pub fn unweighted(mut self) -> Self {
+        self.uses_weights = false;
+        self
+    }
+    pub fn with_negative_weights(mut self) -> Self {
+        self.negative_weights = true;
+        self
+    }
+    pub fn with_start(mut self) -> Self {
+        self.uses_start = true;
+        self
+    }
+    pub fn with_end(mut self) -> Self {
+        self.uses_end = true;
+        self
+    }
+    pub fn with_color_count(mut self) -> Self {
+        self.uses_color_count = true;
+        self
+    }
+    pub fn with_damping(mut self) -> Self {
+        self.uses_damping = true;
+        self
+    }
+    pub fn with_layout(mut self) -> Self {
+        self.moves_nodes = true;
+        self
+    }
+    pub fn with_legend(mut self, legend: &[(ElementState, &'static str)]) -> Self {
+        self.legend = legend.to_vec();
+        self
+    }
+    pub fn with_groups(mut self, groups: &'static str) -> Self {
+        self.groups = Some(groups);
+        self
+    }
+    pub fn with_tags(mut self, tags: &[&'static str]) -> Self {
+        self.tags = tags.to_vec();
+        self
+    }
+    pub fn graph_kind(&self) -> GraphKind {
+        if self.multigraph {
+            GraphKind::Multigraph
+        } else if self.directed {
+            GraphKind::Directed
+        } else {
+            GraphKind::Undirected
+        }
+    }
+    pub fn run(&self, graph: &Graph, options: &AlgorithmOptions) -> GraphSteps {
+        (self.run)(graph, options)
+    }
+}
+impl Default for GraphAlgorithm {
+    fn default() -> Self {
+        get_graph_algorithms().remove("kruskal").unwrap()
+    }
+}
+
+pub fn get_graph_algorithms() -> BTreeMap<&'static str, GraphAlgorithm> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "backtracking-coloring",
+            GraphAlgorithm::new(
+                "Backtracking coloring",
+                graph_algorithms::backtracking_coloring,
+            )
+            .unweighted()
+            .with_color_count()
+            .with_legend(&[
+                (ElementState::Active, "Being colored"),
+                (ElementState::Rejected, "Backtracked from"),
+                (ElementState::Conflict, "Conflict"),
+            ])
+            .with_groups("Colors")
+            .with_tags(&["graph", "coloring", "backtracking"]),
+        ),
+        (
+            "bellman-ford",
+            GraphAlgorithm::new("Bellman-Ford", graph_algorithms::bellman_ford)
+                .directed()
+                .with_negative_weights()
+                .with_start()
+                .with_legend(&[
+                    (ElementState::Active, "Checked edge or updated node"),
+                    (ElementState::Selected, "Cheapest path so far"),
+                    (ElementState::Conflict, "Negative cycle"),
+                ])
+                .with_tags(&["graph", "shortest path", "negative weights", "O(VE)"]),
+        ),
+        (
+            "dfs-topological-sort",
+            GraphAlgorithm::new(
+                "DFS topological sort",
+                graph_algorithms::dfs_topological_sort,
+            )
+            .directed()
+            .unweighted()
+            .with_legend(&[
+                (ElementState::Frontier, "On the stack"),
+                (ElementState::Selected, "Finished"),
+                (ElementState::Rejected, "Edge to a finished node"),
+                (ElementState::Conflict, "Cycle"),
+            ])
+            .with_tags(&["graph", "DAG", "topological sort", "O(V + E)"]),
+        ),
+        (
+            "edmonds-karp",
+            GraphAlgorithm::new("Edmonds-Karp", graph_algorithms::edmonds_karp)
+                .directed()
+                .with_start()
+                .with_end()
+                .with_legend(&[
+                    (ElementState::Active, "Augmenting path"),
+                    (ElementState::Selected, "Carries flow"),
+                    (ElementState::Rejected, "Saturated"),
+                    (ElementState::Conflict, "Minimum cut"),
+                ])
+                .with_groups("Sides of the cut")
+                .with_tags(&["graph", "maximum flow", "O(VE²)"]),
+        ),
+        (
+            "floyd-warshall",
+            GraphAlgorithm::new("Floyd-Warshall", graph_algorithms::floyd_warshall)
+                .directed()
+                .with_negative_weights()
+                .with_legend(&[
+                    (ElementState::Active, "Compared distances"),
+                    (ElementState::Frontier, "Intermediate node"),
+                    (ElementState::Selected, "Shortened through it"),
+                    (ElementState::Conflict, "Negative cycle"),
+                ])
+                .with_tags(&["graph", "shortest path", "dynamic programming", "O(V³)"]),
+        ),
+        (
+            "fruchterman-reingold",
+            GraphAlgorithm::new(
+                "Fruchterman-Reingold",
+                graph_algorithms::fruchterman_reingold,
+            )
+            .unweighted()
+            .with_layout()
+            .with_tags(&["graph", "layout", "force-directed"]),
+        ),
+        (
+            "greedy-coloring",
+            GraphAlgorithm::new("Greedy coloring", graph_algorithms::greedy_coloring)
+                .unweighted()
+                .with_legend(&[(ElementState::Active, "Being colored")])
+                .with_groups("Colors")
+                .with_tags(&["graph", "coloring", "greedy", "O(V + E)"]),
+        ),
+        (
+            "hierholzer",
+            GraphAlgorithm::new("Hierholzer", graph_algorithms::hierholzer)
+                .multigraph()
+                .with_start()
+                .with_legend(&[
+                    (ElementState::Active, "Walked or popped"),
+                    (ElementState::Frontier, "On the stack or partly used"),
+                    (ElementState::Selected, "In the circuit"),
+                    (ElementState::Rejected, "Used"),
+                ])
+                .with_tags(&["graph", "Eulerian circuit", "multigraph", "O(E)"]),
+        ),
+        (
+            "kahn",
+            GraphAlgorithm::new("Kahn", graph_algorithms::kahn)
+                .directed()
+                .unweighted()
+                .with_legend(&[
+                    (ElementState::Frontier, "Queue"),
+                    (ElementState::Selected, "Ordered"),
+                    (ElementState::Rejected, "Removed edge"),
+                    (ElementState::Conflict, "Cycle"),
+                ])
+                .with_tags(&["graph", "DAG", "topological sort", "O(V + E)"]),
+        ),
+        (
+            "kosaraju",
+            GraphAlgorithm::new("Kosaraju", graph_algorithms::kosaraju)
+                .directed()
+                .unweighted()
+                .with_legend(&[
+                    (ElementState::Active, "Joined the component"),
+                    (ElementState::Frontier, "Being searched"),
+                    (ElementState::Selected, "Finished"),
+                ])
+                .with_groups("Components")
+                .with_tags(&["graph", "strongly connected components", "O(V + E)"]),
+        ),
+        (
+            "kruskal",
+            GraphAlgorithm::new("Kruskal", graph_algorithms::kruskal)
+                .with_legend(&[
+                    (ElementState::Active, "Cheapest remaining edge"),
+                    (ElementState::Selected, "Tree"),
+                    (ElementState::Rejected, "Skipped"),
+                ])
+                .with_groups("Sets")
+                .with_tags(&[
+                    "graph",
+                    "minimum spanning tree",
+                    "greedy",
+                    "union-find",
+                    "O(E log E)",
+                ]),
+        ),
+        (
+            "pagerank",
+            GraphAlgorithm::new("PageRank", graph_algorithms::pagerank)
+                .directed()
+                .unweighted()
+                .with_damping()
+                .with_legend(&[
+                    (ElementState::Active, "Changed the most"),
+                    (ElementState::Rejected, "No outgoing edges"),
+                ])
+                .with_tags(&["graph", "ranking", "iterative"]),
+        ),
+        (
+            "prim",
+            GraphAlgorithm::new("Prim", graph_algorithms::prim)
+                .with_start()
+                .with_legend(&[
+                    (ElementState::Active, "Cheapest frontier edge"),
+                    (ElementState::Frontier, "Frontier"),
+                    (ElementState::Selected, "Tree"),
+                    (ElementState::Rejected, "Skipped"),
+                ])
+                .with_tags(&["graph", "minimum spanning tree", "greedy", "O(E log V)"]),
+        ),
+        (
+            "tarjan",
+            GraphAlgorithm::new("Tarjan", graph_algorithms::tarjan)
+                .directed()
+                .unweighted()
+                .with_legend(&[
+                    (ElementState::Active, "Visited"),
+                    (ElementState::Frontier, "On the stack"),
+                    (ElementState::Selected, "Tree edge"),
+                    (ElementState::Rejected, "Edge to a finished component"),
+                ])
+                .with_groups("Components")
+                .with_tags(&["graph", "strongly connected components", "O(V + E)"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum GraphRoute {
+    #[at("/graph")]
+    Graph,
+    #[at("/graph/:algorithm")]
+    GraphAlgorithm { algorithm: String },
+}
+
+pub fn switch_graph(route: GraphRoute) -> Html {
+    match route {
+        GraphRoute::Graph => html! {
+            <Redirect<GraphRoute> to={GraphRoute::GraphAlgorithm { algorithm: "kruskal".to_string()} } />
+        },
+        GraphRoute::GraphAlgorithm { algorithm } => {
+            if get_graph_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <GraphPage algorithm={algorithm} />
+                }
+            } else {
+                html! {
+                    <Graph404Page algorithm={algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphConfig {
+    pub algorithm: GraphAlgorithm,
+    pub playback_time: f32,
+    /// The number of colors that coloring algorithms can use, which is a guess of the chromatic number of the graph.
+    pub color_count: usize,
+    /// The damping factor of PageRank.
+    pub damping: f32,
+}
+impl Default for GraphConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: GraphAlgorithm::default(),
+            playback_time: 10.0,
+            color_count: 3,
+            damping: 0.85,
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct GraphPageProps {
+    #[prop_or("kruskal".to_string())]
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn GraphPage(props: &GraphPageProps) -> Html {
+    let config = {
+        let algorithm_name = props.algorithm.to_string();
+
+        use_mut_ref(|| {
+            let mut config = GraphConfig::default();
+            if let Some(algorithm) = get_graph_algorithms().get(algorithm_name.as_str()) {
+                config.algorithm = algorithm.to_owned();
+            }
+            config
+        })
+    };
+
+    let graphs = use_mut_ref(AlgorithmGraphs::default);
+    let steps = use_mut_ref(GraphSteps::new);
+    let step_index = use_state(|| 0);
+    let active_tool = use_state_eq(|| GraphPageTool::Graph(GraphTool::Edit));
+
+    let route = use_route::<GraphRoute>();
+
+    // Runs the algorithm again and keeps showing the last step if it was being shown
+    let run_algorithm = {
+        let config = Rc::clone(&config);
+        let graphs = Rc::clone(&graphs);
+        let steps = Rc::clone(&steps);
+        let step_index = step_index.clone();
+
+        move || {
+            let old_step_count = steps.borrow().len();
+            let new_steps = {
+                let config = config.borrow();
+                let graphs = graphs.borrow();
+                let graph = graphs.get(config.algorithm.graph_kind());
+                let options = AlgorithmOptions {
+                    start: graph.start,
+                    end: graph.end,
+                    color_count: config.color_count,
+                    damping: config.damping,
+                    positions: graph.graph.positions(),
+                    layout_size: (GRAPH_WIDTH as f32, GRAPH_HEIGHT as f32),
+                };
+                config.algorithm.run(&graph.graph.to_graph(), &options)
+            };
+            let new_step_index = if *step_index >= old_step_count {
+                new_steps.len()
+            } else {
+                0
+            };
+            *steps.borrow_mut() = new_steps;
+            step_index.set(new_step_index);
+        }
+    };
+
+    {
+        let config = Rc::clone(&config);
+        let run_algorithm = run_algorithm.clone();
+
+        use_effect_with_deps(
+            move |route| {
+                let algorithm_name = match route.as_ref().unwrap() {
+                    GraphRoute::GraphAlgorithm { algorithm } => algorithm,
+                    _ => "kruskal",
+                };
+                if let Some(algorithm) = get_graph_algorithms().get(algorithm_name) {
+                    config.borrow_mut().algorithm = algorithm.clone();
+                    run_algorithm();
+                }
+                || ()
+            },
+            route,
+        );
+    }
+
+    use_title(format!(
+        "{} - Graph algorithms",
+        config.borrow().algorithm.name
+    ));
+
+    let on_change_step = {
+        let step_index = step_index.clone();
+
+        Callback::from(move |val| {
+            step_index.set(val);
+        })
+    };
+
+    let on_tool_change = {
+        let active_tool = active_tool.clone();
+
+        Callback::from(move |new_active_tool| {
+            active_tool.set(new_active_tool);
+        })
+    };
+
+    let on_change_graph = {
+        let config = Rc::clone(&config);
+        let graphs = Rc::clone(&graphs);
+        let run_algorithm = run_algorithm.clone();
+
+        Callback::from(move |new_graph| {
+            let kind = config.borrow().algorithm.graph_kind();
+            *graphs.borrow_mut().get_mut(kind) = new_graph;
+            run_algorithm();
+        })
+    };
+
+    let on_click_node = {
+        let active_tool = *active_tool;
+        let config = Rc::clone(&config);
+        let graphs = Rc::clone(&graphs);
+        let run_algorithm = run_algorithm.clone();
+
+        Callback::from(move |id| {
+            {
+                let kind = config.borrow().algorithm.graph_kind();
+                let mut graphs = graphs.borrow_mut();
+                let graph = graphs.get_mut(kind);
+                match active_tool {
+                    GraphPageTool::Start => graph.start = id,
+                    GraphPageTool::End => graph.end = id,
+                    _ => return,
+                }
+            }
+            run_algorithm();
+        })
+    };
+
+    let on_clear_graph = {
+        let config = Rc::clone(&config);
+        let graphs = Rc::clone(&graphs);
+        let run_algorithm = run_algorithm.clone();
+
+        Callback::from(move |_| {
+            let kind = config.borrow().algorithm.graph_kind();
+            graphs.borrow_mut().get_mut(kind).clear();
+            run_algorithm();
+        })
+    };
+
+    let on_change_color_count = {
+        let config = Rc::clone(&config);
+        let run_algorithm = run_algorithm.clone();
+
+        Callback::from(move |color_count| {
+            config.borrow_mut().color_count = color_count;
+            run_algorithm();
+        })
+    };
+
+    let on_change_damping = {
+        let config = Rc::clone(&config);
+        let run_algorithm = run_algorithm.clone();
+
+        Callback::from(move |damping| {
+            config.borrow_mut().damping = damping;
+            run_algorithm();
+        })
+    };
+
+    let on_scatter_nodes = {
+        let config = Rc::clone(&config);
+        let graphs = Rc::clone(&graphs);
+        let run_algorithm = run_algorithm.clone();
+
+        Callback::from(move |_| {
+            let kind = config.borrow().algorithm.graph_kind();
+            graphs.borrow_mut().get_mut(kind).graph.scatter();
+            run_algorithm();
+        })
+    };
+
+    let on_use_layout = {
+        let config = Rc::clone(&config);
+        let graphs = Rc::clone(&graphs);
+        let steps = Rc::clone(&steps);
+        let run_algorithm = run_algorithm.clone();
+
+        Callback::from(move |_| {
+            let Some(last_step) = steps.borrow().steps.last().cloned() else {
+                return;
+            };
+            let kind = config.borrow().algorithm.graph_kind();
+            graphs
+                .borrow_mut()
+                .get_mut(kind)
+                .graph
+                .set_positions(&last_step.node_positions);
+            run_algorithm();
+        })
+    };
+
+    let on_reset_graph = {
+        let config = Rc::clone(&config);
+        let graphs = Rc::clone(&graphs);
+        let run_algorithm = run_algorithm.clone();
+
+        Callback::from(move |_| {
+            let kind = config.borrow().algorithm.graph_kind();
+            *graphs.borrow_mut().get_mut(kind) = AlgorithmGraph::sample(kind);
+            run_algorithm();
+        })
+    };
+
+    let step_count = steps.borrow().len();
+    let step = if *step_index > 0 {
+        steps.borrow().steps[*step_index - 1].clone()
+    } else {
+        GraphStep::default()
+    };
+    let algorithm = config.borrow().algorithm.clone();
+
+    let on_export_json = {
+        let graphs = Rc::clone(&graphs);
+        let kind = algorithm.graph_kind();
+
+        Callback::from(move |_| {
+            let graph = SavedGraph::new(graphs.borrow().get(kind), kind);
+            if let Err(err) = download_file("graph.json", &graph.to_json(), "application/json") {
+                log::error!("Could not export the graph: {:?}", err);
+            }
+        })
+    };
+
+    let on_export_dot = {
+        let graphs = Rc::clone(&graphs);
+        let kind = algorithm.graph_kind();
+        let step = step.clone();
+
+        Callback::from(move |_| {
+            let graph = SavedGraph::new(graphs.borrow().get(kind), kind);
+            if let Err(err) = download_file("graph.dot", &graph.to_dot(&step), "text/vnd.graphviz")
+            {
+                log::error!("Could not export the graph: {:?}", err);
+            }
+        })
+    };
+
+    let on_import_graph = {
+        let graphs = Rc::clone(&graphs);
+        let kind = algorithm.graph_kind();
+        let run_algorithm = run_algorithm.clone();
+
+        Callback::from(move |graph: SavedGraph| {
+            *graphs.borrow_mut().get_mut(kind) = graph.to_algorithm_graph(kind);
+            run_algorithm();
+        })
+    };
+
+    html! {
+        <div class="page" id="Graph">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <GraphControls config={Rc::clone(&config)} />
+                </Collapsible>
+
+                {
+                    if algorithm.uses_color_count {
+                        html! {
+                            <Collapsible title="Coloring" open={true} class="config-section">
+                                <ColoringControls
+                                    color_count={config.borrow().color_count}
+                                    {on_change_color_count}
+                                />
+                            </Collapsible>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                {
+                    if algorithm.uses_damping {
+                        html! {
+                            <Collapsible title="PageRank" open={true} class="config-section">
+                                <DampingControls
+                                    damping={config.borrow().damping}
+                                    {on_change_damping}
+                                />
+                            </Collapsible>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                {
+                    if algorithm.moves_nodes {
+                        html! {
+                            <Collapsible title="Layout" open={true} class="config-section">
+                                <LayoutControls {on_scatter_nodes} {on_use_layout} />
+                            </Collapsible>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                <Collapsible title="Graph" open={true} class="config-section">
+                    <GraphEditControls {on_clear_graph} {on_reset_graph} />
+                </Collapsible>
+
+                <Collapsible title="Save and load" open={false} class="config-section">
+                    <GraphFileControls
+                        kind={algorithm.graph_kind()}
+                        {on_export_json}
+                        {on_export_dot}
+                        on_import={on_import_graph}
+                    />
+                </Collapsible>
+            </Sidebar>
+
+            <main>
+                <div class="visualization">
+                    <GraphToolbar
+                        active_tool={*active_tool}
+                        uses_start={algorithm.uses_start}
+                        uses_end={algorithm.uses_end}
+                        {on_tool_change}
+                    />
+                    <AlgorithmGraphEditor
+                        graph={graphs.borrow().get(algorithm.graph_kind()).clone()}
+                        step={step.clone()}
+                        active_tool={*active_tool}
+                        uses_start={algorithm.uses_start}
+                        uses_end={algorithm.uses_end}
+                        show_weights={algorithm.uses_weights}
+                        negative_weights={algorithm.negative_weights}
+                        parallel_edges={algorithm.multigraph}
+                        on_change={on_change_graph}
+                        {on_click_node}
+                    />
+
+                    <StepLegend states={algorithm.legend.clone()} groups={algorithm.groups} />
+
+                    <p id="graphStepStatus" class="step-description" role="status" aria-live="polite">
+                        { step_summary(&step, *step_index, step_count) }
+                    </p>
+
+                    <StepLists lists={step.lists} />
+                    { step.matrix.map(|matrix| html! { <StepMatrixGrid {matrix} /> }) }
+                    { step.chart.map(|chart| html! { <StepChartPlot {chart} /> }) }
+
+                    <StepSlider
+                        label={format!("Steps ({}/{})", *step_index, step_count)}
+                        active_step_index={*step_index}
+                        max={step_count}
+                        on_change={on_change_step}
+                        playback_time={config.borrow().playback_time}
+                    />
+                </div>
+
+                <AlgoDesc algorithm={algorithm.name} />
+            </main>
+        </div>
+    }
+}
+
+/// A textual summary of the current step, which is also read by screen readers.
+fn step_summary(step: &GraphStep, step_i: usize, step_count: usize) -> String {
+    if step_i == 0 {
+        format!(
+            "Step 0 of {}. Play the steps to run the algorithm.",
+            step_count
+        )
+    } else {
+        format!("Step {} of {}. {}.", step_i, step_count, step.description)
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct Graph404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn Graph404Page(props: &Graph404PageProps) -> Html {
+    use_title("404 - Graph algorithms".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<GraphRoute> to={GraphRoute::Graph}>
+                { "Back to graph algorithms" }
+            </Link<GraphRoute>>
+        </>
+    }
+}
@@ -0,0 +1,117 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use diagram::ElementState;
+use greedy::greedy_algorithms::*;
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+pub fn get_greedy_algorithms() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([(
+        "interval-scheduling",
+        VisualizerInfo::new("Interval scheduling", || {
+            Box::new(IntervalScheduling::new(&[
+                (1, 4),
+                (3, 5),
+                (0, 6),
+                (5, 7),
+                (3, 9),
+                (5, 9),
+                (6, 10),
+                (8, 11),
+                (8, 12),
+                (2, 14),
+                (12, 16),
+            ]))
+        })
+        .with_legend(&[
+            (ElementState::Active, "Chosen interval"),
+            (ElementState::Conflict, "Overlapping chosen interval"),
+            (ElementState::Selected, "Chosen intervals"),
+            (ElementState::Rejected, "Skipped intervals"),
+        ])
+        .with_tags(&["greedy", "intervals", "O(n log n)"]),
+    )])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum GreedyRoute {
+    #[at("/greedy")]
+    Greedy,
+    #[at("/greedy/:algorithm")]
+    GreedyAlgorithm { algorithm: String },
+}
+
+pub fn switch_greedy(route: GreedyRoute) -> Html {
+    match route {
+        GreedyRoute::Greedy => html! {
+            <Redirect<GreedyRoute> to={GreedyRoute::GreedyAlgorithm { algorithm: "interval-scheduling".to_string()} } />
+        },
+        GreedyRoute::GreedyAlgorithm { algorithm } => {
+            if get_greedy_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <GreedyPage {algorithm} />
+                }
+            } else {
+                html! {
+                    <Greedy404Page {algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct GreedyPageProps {
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn GreedyPage(props: &GreedyPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let algorithms = get_greedy_algorithms();
+    let names = algorithms
+        .values()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect::<Vec<String>>();
+    let algorithm = algorithms[props.algorithm.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&GreedyRoute::GreedyAlgorithm {
+            algorithm: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="Greedy"
+            section="Greedy algorithms"
+            select_title="Algorithm"
+            {names}
+            visualizer={algorithm}
+            {on_select}
+            readme_directory="greedy_algorithms"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct Greedy404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn Greedy404Page(props: &Greedy404PageProps) -> Html {
+    use_title("404 - Greedy algorithms".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<GreedyRoute> to={GreedyRoute::Greedy}>
+                { "Back to greedy algorithms" }
+            </Link<GreedyRoute>>
+        </>
+    }
+}
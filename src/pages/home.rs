@@ -1,10 +1,503 @@
+use crate::components::{
+    diagram::{DiagramView, VisualizerInfo},
+    graph::AlgorithmGraph,
+    graph_editor::{GRAPH_HEIGHT, GRAPH_WIDTH},
+    input_items::TextInput,
+};
+use crate::pages::{
+    backtracking::{get_backtracking_algorithms, BacktrackingRoute},
+    cellular_automata::{get_cellular_automata, CellularAutomataRoute},
+    data_structures::{get_data_structures, DataStructuresRoute},
+    dynamic_programming::{get_dynamic_programming_algorithms, DynamicProgrammingRoute},
+    geometry::{get_geometry_algorithms, GeometryRoute},
+    graph::{get_graph_algorithms, GraphAlgorithm, GraphConfig, GraphRoute},
+    greedy::{get_greedy_algorithms, GreedyRoute},
+    machine_learning::{get_machine_learning_algorithms, MachineLearningRoute},
+    number_theory::{get_number_theory_algorithms, NumberTheoryRoute},
+    numerical::{get_numerical_algorithms, NumericalRoute},
+    optimization::{get_optimization_algorithms, OptimizationRoute},
+    pathfinding::{get_pathfinding_algorithms, EdgeType, PathfindingAlgorithm, PathfindingRoute},
+    randomized::{get_randomized_algorithms, RandomizedRoute},
+    searching::{get_searching_algorithms, SearchingRoute},
+    sorting::{get_sorting_algorithms, SortingAlgorithm, SortingRoute},
+    strings::{get_string_algorithms, StringsRoute},
+};
+use crate::Route;
+use cellular_automata::cellular_automata_algorithms::{
+    parse_ant_rule, ElementaryAutomaton, LangtonsAnt, Life, LIFE_PATTERNS,
+};
+use diagram::{Diagram, DiagramEdge, DiagramNode, ElementState, Figure, FigureKind, Fill, Shape};
+use graph::AlgorithmOptions;
+use pathfinding::{generate_graph, Coord, CornerCutting, Heuristic, VertexState};
+use sorting::run_sort_steps;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
 use yew::prelude::*;
-use yew_router::prelude::*;
+use yew_router::{prelude::*, AnyRoute};
 
-use crate::Route;
+/// The items that the thumbnails of the sorting algorithms sort, in the order that they start in.
+const SORTING_ITEMS: [u32; 16] = [9, 3, 14, 6, 1, 12, 8, 16, 4, 11, 2, 15, 7, 10, 5, 13];
+/// The width and height of the grid of the pathfinding thumbnails.
+const PATHFINDING_GRID_SIZE: (usize, usize) = (15, 9);
+/// The side of a cell in the grids of the thumbnails.
+const CELL_SIZE: f32 = 20.0;
+/// How much the sample graphs are shrunk for their thumbnails, so that the lines aren't too thin to see.
+const GRAPH_SCALE: f32 = 0.4;
+
+/// A visualizer as it is listed in the catalog of the home page.
+#[derive(Clone, Debug, PartialEq)]
+struct CatalogEntry {
+    name: String,
+    tags: Vec<&'static str>,
+    /// The path of the page of the visualizer, like "/sorting/quicksort".
+    path: String,
+    /// A small drawing of the visualizer.
+    thumbnail: Diagram,
+}
+impl CatalogEntry {
+    fn new(name: &str, tags: &[&'static str], route: impl Routable, thumbnail: Diagram) -> Self {
+        Self {
+            name: name.to_string(),
+            tags: tags.to_vec(),
+            path: route.to_path(),
+            thumbnail,
+        }
+    }
+    /// True if every word of the query is in the name of the entry, one of its tags or the name of its category.
+    fn matches(&self, category: &str, query: &str) -> bool {
+        let text = format!("{} {} {}", self.name, self.tags.join(" "), category).to_lowercase();
+        query
+            .to_lowercase()
+            .split_whitespace()
+            .all(|word| text.contains(word))
+    }
+}
+
+/// A section of the website, like sorting, along with its visualizers.
+#[derive(Clone, Debug, PartialEq)]
+struct CatalogCategory {
+    name: &'static str,
+    entries: Vec<CatalogEntry>,
+}
+
+/// Lists the visualizers of every section of the website from the registries of the pages.
+fn get_catalog() -> Vec<CatalogCategory> {
+    fn category(name: &'static str, entries: Vec<CatalogEntry>) -> CatalogCategory {
+        CatalogCategory { name, entries }
+    }
+    // The visualizers of the diagram pages draw themselves
+    fn visualizers<R: Routable>(
+        name: &'static str,
+        visualizers: BTreeMap<&'static str, VisualizerInfo>,
+        route: fn(String) -> R,
+    ) -> CatalogCategory {
+        let entries = visualizers
+            .into_iter()
+            .map(|(key, info)| {
+                CatalogEntry::new(
+                    &info.name,
+                    &info.tags,
+                    route(key.to_string()),
+                    info.create().diagram(),
+                )
+            })
+            .collect();
+        category(name, entries)
+    }
+
+    vec![
+        category(
+            "Sorting",
+            get_sorting_algorithms()
+                .into_iter()
+                .map(|(key, algorithm)| {
+                    CatalogEntry::new(
+                        &algorithm.name,
+                        &algorithm.tags,
+                        SortingRoute::SortingAlgorithm {
+                            algorithm: key.to_string(),
+                        },
+                        sorting_thumbnail(&algorithm),
+                    )
+                })
+                .collect(),
+        ),
+        category(
+            "Pathfinding",
+            get_pathfinding_algorithms()
+                .into_iter()
+                .map(|(key, algorithm)| {
+                    CatalogEntry::new(
+                        &algorithm.name,
+                        &algorithm.tags,
+                        PathfindingRoute::PathfindingAlgorithm {
+                            algorithm: key.to_string(),
+                        },
+                        pathfinding_thumbnail(&algorithm),
+                    )
+                })
+                .collect(),
+        ),
+        category(
+            "Graphs",
+            get_graph_algorithms()
+                .into_iter()
+                .map(|(key, algorithm)| {
+                    CatalogEntry::new(
+                        &algorithm.name,
+                        &algorithm.tags,
+                        GraphRoute::GraphAlgorithm {
+                            algorithm: key.to_string(),
+                        },
+                        graph_thumbnail(&algorithm),
+                    )
+                })
+                .collect(),
+        ),
+        visualizers("Data structures", get_data_structures(), |structure| {
+            DataStructuresRoute::DataStructure { structure }
+        }),
+        visualizers("Searching", get_searching_algorithms(), |algorithm| {
+            SearchingRoute::SearchingAlgorithm { algorithm }
+        }),
+        visualizers("Strings", get_string_algorithms(), |algorithm| {
+            StringsRoute::StringAlgorithm { algorithm }
+        }),
+        visualizers("Geometry", get_geometry_algorithms(), |algorithm| {
+            GeometryRoute::GeometryAlgorithm { algorithm }
+        }),
+        visualizers(
+            "Number theory",
+            get_number_theory_algorithms(),
+            |algorithm| NumberTheoryRoute::NumberTheoryAlgorithm { algorithm },
+        ),
+        visualizers("Numerical", get_numerical_algorithms(), |algorithm| {
+            NumericalRoute::NumericalAlgorithm { algorithm }
+        }),
+        visualizers(
+            "Dynamic programming",
+            get_dynamic_programming_algorithms(),
+            |algorithm| DynamicProgrammingRoute::DynamicProgrammingAlgorithm { algorithm },
+        ),
+        visualizers("Backtracking", get_backtracking_algorithms(), |algorithm| {
+            BacktrackingRoute::BacktrackingAlgorithm { algorithm }
+        }),
+        visualizers("Greedy", get_greedy_algorithms(), |algorithm| {
+            GreedyRoute::GreedyAlgorithm { algorithm }
+        }),
+        category(
+            "Cellular automata",
+            get_cellular_automata()
+                .into_iter()
+                .map(|(key, automaton)| {
+                    CatalogEntry::new(
+                        automaton.name,
+                        &automaton.tags,
+                        CellularAutomataRoute::CellularAutomaton {
+                            automaton: key.to_string(),
+                        },
+                        cellular_automaton_thumbnail(key),
+                    )
+                })
+                .collect(),
+        ),
+        visualizers(
+            "Machine learning",
+            get_machine_learning_algorithms(),
+            |algorithm| MachineLearningRoute::MachineLearningAlgorithm { algorithm },
+        ),
+        visualizers("Optimization", get_optimization_algorithms(), |algorithm| {
+            OptimizationRoute::OptimizationAlgorithm { algorithm }
+        }),
+        visualizers("Randomized", get_randomized_algorithms(), |algorithm| {
+            RandomizedRoute::RandomizedAlgorithm { algorithm }
+        }),
+    ]
+}
+
+/// Draws the items halfway through the sort, which shows how the algorithm works, like the sorted runs of merge sort.
+/// The items that are already in their final places are highlighted.
+fn sorting_thumbnail(algorithm: &SortingAlgorithm) -> Diagram {
+    let steps = algorithm
+        .sort(Rc::new(RefCell::new(SORTING_ITEMS.to_vec())))
+        .steps;
+    let mut items = SORTING_ITEMS.to_vec();
+    run_sort_steps(&mut items, &steps[..steps.len() / 2]);
+
+    let (bar_width, unit_height) = (24.0, 8.0);
+    let height = SORTING_ITEMS.len() as f32 * unit_height;
+    let mut diagram = Diagram::new(items.len() as f32 * bar_width, height);
+    for (i, item) in items.iter().enumerate() {
+        let bar_height = *item as f32 * unit_height;
+        let bar = Figure::new(FigureKind::Rect {
+            position: (i as f32 * bar_width + 2.0, height - bar_height),
+            size: (bar_width - 4.0, bar_height),
+        });
+        diagram.add_figure(if *item as usize == i + 1 {
+            bar.with_state(ElementState::Selected)
+        } else {
+            bar
+        });
+    }
+    diagram
+}
+
+/// Draws the cells that the algorithm visited and the path that it found around a wall.
+fn pathfinding_thumbnail(algorithm: &PathfindingAlgorithm<Coord, EdgeType>) -> Diagram {
+    let (width, height) = PATHFINDING_GRID_SIZE;
+    let walls = (0..height as isize - 2)
+        .map(|y| Coord::new(width as isize / 2, y))
+        .collect::<BTreeSet<Coord>>();
+    let graph = generate_graph(
+        width,
+        height,
+        false,
+        CornerCutting::default(),
+        &walls,
+        &BTreeMap::new(),
+    );
+    let (start, end) = (
+        Coord::new(2, height as isize / 2),
+        Coord::new(width as isize - 3, height as isize / 2),
+    );
+    let result = algorithm
+        .find_path(&graph, start, end, Heuristic::Manhattan)
+        .0;
+    let mut states = BTreeMap::new();
+    for step in result.steps.steps.iter() {
+        states.extend(step.states.iter());
+    }
+
+    let mut diagram = Diagram::new(width as f32 * CELL_SIZE, height as f32 * CELL_SIZE);
+    for y in 0..height as isize {
+        for x in 0..width as isize {
+            let coord = Coord::new(x, y);
+            let mut cell = grid_cell(x as usize, y as usize);
+            if walls.contains(&coord) {
+                cell = cell.with_fill(Fill::Black);
+            } else if result.path.contains(&coord) {
+                cell = cell.with_state(ElementState::Selected);
+            } else {
+                match states.get(&coord) {
+                    Some(VertexState::Queued | VertexState::QueuedFromEnd) => {
+                        cell = cell.with_state(ElementState::Active);
+                    }
+                    Some(VertexState::NotVisited) | None => {}
+                    Some(_) => cell = cell.with_state(ElementState::Frontier),
+                }
+            }
+            diagram.add_node(cell);
+        }
+    }
+    diagram
+}
+
+/// Draws the sample graph of the algorithm as it is after the last step.
+fn graph_thumbnail(algorithm: &GraphAlgorithm) -> Diagram {
+    let config = GraphConfig::default();
+    let sample = AlgorithmGraph::sample(algorithm.graph_kind());
+    let options = AlgorithmOptions {
+        start: sample.start,
+        end: sample.end,
+        color_count: config.color_count,
+        damping: config.damping,
+        positions: sample.graph.positions(),
+        layout_size: (GRAPH_WIDTH as f32, GRAPH_HEIGHT as f32),
+    };
+    let steps = algorithm.run(&sample.graph.to_graph(), &options);
+    let last_step = steps.steps.last().cloned().unwrap_or_default();
+    // Values like the ranks of PageRank are shown as heat relative to the largest one
+    let max_value = last_step
+        .node_values
+        .values()
+        .fold(f32::EPSILON, |max, value| max.max(*value));
+
+    let mut diagram = Diagram::new(
+        GRAPH_WIDTH as f32 * GRAPH_SCALE,
+        GRAPH_HEIGHT as f32 * GRAPH_SCALE,
+    );
+    for (id, (x, y)) in options.positions.iter() {
+        let (x, y) = last_step
+            .node_positions
+            .get(id)
+            .copied()
+            .unwrap_or((*x, *y));
+        let mut node = DiagramNode::new(
+            id.to_string(),
+            "",
+            (x * GRAPH_SCALE, y * GRAPH_SCALE),
+            Shape::Circle { radius: 12.0 },
+        );
+        if let Some(group) = last_step.node_groups.get(id) {
+            node = node.with_fill(Fill::Group(*group));
+        } else if let Some(value) = last_step.node_values.get(id) {
+            node = node.with_fill(Fill::Heat(value / max_value));
+        }
+        if let Some(state) = last_step.node_states.get(id) {
+            node = node.with_state(*state);
+        }
+        diagram.add_node(node);
+    }
+    for (a, b) in sample.graph.edges.keys() {
+        let mut edge = if sample.graph.directed {
+            DiagramEdge::arrow(a.to_string(), b.to_string())
+        } else {
+            DiagramEdge::new(a.to_string(), b.to_string())
+        };
+        if let Some(state) = last_step.edge_states.get(&(*a, *b)) {
+            edge = edge.with_state(*state);
+        }
+        diagram.add_edge(edge);
+    }
+    diagram
+}
+
+/// Draws the classic pattern of the automaton, like the glider gun of the Game of Life.
+fn cellular_automaton_thumbnail(automaton: &str) -> Diagram {
+    let cells = match automaton {
+        "elementary" => {
+            let mut automaton = ElementaryAutomaton::new(90, ElementaryAutomaton::single_cell(31));
+            for _ in 0..15 {
+                automaton.step();
+            }
+            automaton
+                .rows()
+                .iter()
+                .map(|row| row.iter().map(|alive| *alive as u8).collect())
+                .collect()
+        }
+        "game-of-life" => {
+            let mut life = Life::new(40, 24);
+            life.load(&LIFE_PATTERNS[1]);
+            for _ in 0..60 {
+                life.step();
+            }
+            life.cells()
+                .chunks(life.width())
+                .map(|row| row.iter().map(|alive| *alive as u8).collect())
+                .collect()
+        }
+        _ => {
+            let mut ant = LangtonsAnt::new(24, 24, parse_ant_rule("LLRR").unwrap());
+            for _ in 0..3000 {
+                ant.step();
+            }
+            ant.cells()
+                .chunks(ant.width())
+                .map(|row| row.to_vec())
+                .collect::<Vec<Vec<u8>>>()
+        }
+    };
+
+    let height = cells.len();
+    let width = cells.first().map_or(0, |row| row.len());
+    let mut diagram = Diagram::new(width as f32 * CELL_SIZE, height as f32 * CELL_SIZE);
+    for (y, row) in cells.iter().enumerate() {
+        for (x, color) in row.iter().enumerate() {
+            // Only the cells that aren't dead or blank are drawn
+            if *color > 0 {
+                diagram.add_node(grid_cell(x, y).with_fill(Fill::Group(*color as usize - 1)));
+            }
+        }
+    }
+    diagram
+}
+
+/// A cell of a grid thumbnail.
+fn grid_cell(x: usize, y: usize) -> DiagramNode {
+    DiagramNode::rect(
+        format!("{},{}", x, y),
+        "",
+        ((x as f32 + 0.5) * CELL_SIZE, (y as f32 + 0.5) * CELL_SIZE),
+        (CELL_SIZE - 2.0, CELL_SIZE - 2.0),
+    )
+}
+
+#[derive(Properties, Clone, PartialEq)]
+struct CatalogProps {
+    categories: Rc<Vec<CatalogCategory>>,
+}
+
+/// The visualizers of every section, which can be searched by their names, tags and categories.
+#[function_component]
+fn Catalog(props: &CatalogProps) -> Html {
+    let query = use_state(String::new);
+
+    let oninput = {
+        let query = query.clone();
+        Callback::from(move |value: String| query.set(value))
+    };
+
+    let categories = props
+        .categories
+        .iter()
+        .filter_map(|category| {
+            let entries = category
+                .entries
+                .iter()
+                .filter(|entry| entry.matches(category.name, &query))
+                .collect::<Vec<&CatalogEntry>>();
+            (!entries.is_empty()).then_some((category.name, entries))
+        })
+        .collect::<Vec<(&str, Vec<&CatalogEntry>)>>();
+
+    let view_entry = |entry: &CatalogEntry| {
+        let tags = entry.tags.iter().map(|&tag| {
+            let query = query.clone();
+            let onclick = Callback::from(move |_| query.set(tag.to_string()));
+            html! {
+                <li><button class="tag" {onclick}>{ tag }</button></li>
+            }
+        });
+
+        html! {
+            <li class="entry" key={entry.path.clone()}>
+                <Link<AnyRoute> to={AnyRoute::new(entry.path.clone())}>
+                    <div class="thumbnail">
+                        <DiagramView
+                            diagram={entry.thumbnail.clone()}
+                            label={format!("A preview of {}", entry.name)}
+                        />
+                    </div>
+                    <h4>{ &entry.name }</h4>
+                </Link<AnyRoute>>
+                <ul class="tags" aria-label="Tags">{ for tags }</ul>
+            </li>
+        }
+    };
+
+    html! {
+        <section class="catalog" aria-label="Algorithms">
+            <h2>{ "Algorithms" }</h2>
+
+            <TextInput title="Search" value={(*query).clone()} {oninput} />
+
+            {
+                if categories.is_empty() {
+                    html! { <p class="no-results">{ format!("Nothing matches \"{}\"", *query) }</p> }
+                } else {
+                    categories.into_iter().map(|(name, entries)| html! {
+                        <div class="category" key={name}>
+                            <h3>{ name }</h3>
+                            <ul class="entries">
+                                { for entries.into_iter().map(view_entry) }
+                            </ul>
+                        </div>
+                    }).collect::<Html>()
+                }
+            }
+        </section>
+    }
+}
 
 #[function_component]
 pub fn HomePage() -> Html {
+    // The thumbnails run the algorithms, so they are only made once
+    let catalog = use_memo(|_| get_catalog(), ());
+
     html! {
         <div id="home">
             <header>
@@ -31,6 +524,8 @@ pub fn HomePage() -> Html {
                 </div>
             </header>
 
+            <Catalog categories={catalog} />
+
             <section class="about">
                 <h2>{ "About" }</h2>
 
@@ -0,0 +1,148 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use diagram::ElementState;
+use machine_learning::machine_learning_algorithms::*;
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+pub fn get_machine_learning_algorithms() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "gradient-descent",
+            VisualizerInfo::new("Gradient descent", || {
+                Box::new(GradientDescent::new(&[
+                    (0.08, 0.22),
+                    (0.15, 0.2),
+                    (0.22, 0.35),
+                    (0.3, 0.31),
+                    (0.38, 0.45),
+                    (0.45, 0.4),
+                    (0.52, 0.55),
+                    (0.6, 0.5),
+                    (0.67, 0.62),
+                    (0.74, 0.68),
+                    (0.82, 0.66),
+                    (0.9, 0.8),
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Selected, "Line, error after every epoch"),
+                (ElementState::Frontier, "Least squares line"),
+            ])
+            .with_tags(&["optimization", "regression", "iterative"]),
+        ),
+        (
+            "perceptron",
+            VisualizerInfo::new("Perceptron", || {
+                Box::new(Perceptron::new(&[
+                    ((0.1, 0.4), true),
+                    ((0.2, 0.75), true),
+                    ((0.3, 0.55), true),
+                    ((0.4, 0.9), true),
+                    ((0.45, 0.65), true),
+                    ((0.6, 0.85), true),
+                    ((0.15, 0.2), true),
+                    ((0.3, 0.1), false),
+                    ((0.4, 0.2), false),
+                    ((0.55, 0.4), false),
+                    ((0.65, 0.6), false),
+                    ((0.7, 0.3), false),
+                    ((0.85, 0.75), false),
+                    ((0.9, 0.5), false),
+                ]))
+            })
+            .with_legend(&[
+                (
+                    ElementState::Selected,
+                    "Boundary, mistakes during every epoch",
+                ),
+                (ElementState::Frontier, "Boundary before the epoch"),
+                (ElementState::Rejected, "Misclassified during the epoch"),
+            ])
+            .with_tags(&["classification", "linear", "iterative"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum MachineLearningRoute {
+    #[at("/machine-learning")]
+    MachineLearning,
+    #[at("/machine-learning/:algorithm")]
+    MachineLearningAlgorithm { algorithm: String },
+}
+
+pub fn switch_machine_learning(route: MachineLearningRoute) -> Html {
+    match route {
+        MachineLearningRoute::MachineLearning => html! {
+            <Redirect<MachineLearningRoute> to={MachineLearningRoute::MachineLearningAlgorithm { algorithm: "gradient-descent".to_string()} } />
+        },
+        MachineLearningRoute::MachineLearningAlgorithm { algorithm } => {
+            if get_machine_learning_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <MachineLearningPage {algorithm} />
+                }
+            } else {
+                html! {
+                    <MachineLearning404Page {algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct MachineLearningPageProps {
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn MachineLearningPage(props: &MachineLearningPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let algorithms = get_machine_learning_algorithms();
+    let names = algorithms
+        .values()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect::<Vec<String>>();
+    let algorithm = algorithms[props.algorithm.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&MachineLearningRoute::MachineLearningAlgorithm {
+            algorithm: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="MachineLearning"
+            section="Machine learning"
+            select_title="Algorithm"
+            {names}
+            visualizer={algorithm}
+            {on_select}
+            readme_directory="machine_learning_algorithms"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct MachineLearning404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn MachineLearning404Page(props: &MachineLearning404PageProps) -> Html {
+    use_title("404 - Machine learning".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<MachineLearningRoute> to={MachineLearningRoute::MachineLearning}>
+                { "Back to machine learning" }
+            </Link<MachineLearningRoute>>
+        </>
+    }
+}
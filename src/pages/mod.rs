@@ -1,3 +1,17 @@
+pub mod backtracking;
+pub mod cellular_automata;
+pub mod data_structures;
+pub mod dynamic_programming;
+pub mod geometry;
+pub mod graph;
+pub mod greedy;
 pub mod home;
+pub mod machine_learning;
+pub mod number_theory;
+pub mod numerical;
+pub mod optimization;
 pub mod pathfinding;
+pub mod randomized;
+pub mod searching;
 pub mod sorting;
+pub mod strings;
@@ -0,0 +1,163 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use diagram::ElementState;
+use number_theory::number_theory_algorithms::*;
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+pub fn get_number_theory_algorithms() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "collatz-sequence",
+            VisualizerInfo::new("Collatz sequence", || {
+                Box::new(CollatzSequence::new(27, 50))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current term or start"),
+                (ElementState::Selected, "Peak, longest so far"),
+            ])
+            .with_tags(&["sequences", "iterative"]),
+        ),
+        (
+            "euclidean-algorithm",
+            VisualizerInfo::new("Euclidean algorithm", || {
+                Box::new(EuclideanAlgorithm::new(252, 105))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Cut squares, current row"),
+                (ElementState::Frontier, "Rest of the rectangle, row below"),
+                (ElementState::Selected, "Gcd, Bézout coefficients"),
+            ])
+            .with_tags(&["GCD", "O(log n)"]),
+        ),
+        (
+            "integer-factorization",
+            VisualizerInfo::new("Integer factorization", || {
+                Box::new(IntegerFactorization::new(8051))
+            })
+            .with_legend(&[
+                (
+                    ElementState::Active,
+                    "Number being factored, current divisor",
+                ),
+                (ElementState::Frontier, "Left to factor"),
+                (ElementState::Selected, "Prime factor, found factor"),
+                (
+                    ElementState::Rejected,
+                    "Not a factor, met modulo every factor",
+                ),
+            ])
+            .with_tags(&["primes", "trial division", "O(√n)"]),
+        ),
+        (
+            "modular-exponentiation",
+            VisualizerInfo::new("Modular exponentiation", || {
+                Box::new(ModularExponentiation::new(5, 117, 19))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current digit"),
+                (
+                    ElementState::Selected,
+                    "Consumed digits, square and multiply",
+                ),
+            ])
+            .with_tags(&["square and multiply", "O(log n)"]),
+        ),
+        (
+            "sieve-of-eratosthenes",
+            VisualizerInfo::new("Sieve of Eratosthenes", || {
+                Box::new(SieveOfEratosthenes::new(100))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Current prime"),
+                (ElementState::Frontier, "Crossed out by it"),
+                (ElementState::Selected, "Prime"),
+                (ElementState::Rejected, "Crossed out"),
+            ])
+            .with_tags(&["primes", "O(n log log n)"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum NumberTheoryRoute {
+    #[at("/number-theory")]
+    NumberTheory,
+    #[at("/number-theory/:algorithm")]
+    NumberTheoryAlgorithm { algorithm: String },
+}
+
+pub fn switch_number_theory(route: NumberTheoryRoute) -> Html {
+    match route {
+        NumberTheoryRoute::NumberTheory => html! {
+            <Redirect<NumberTheoryRoute> to={NumberTheoryRoute::NumberTheoryAlgorithm { algorithm: "sieve-of-eratosthenes".to_string()} } />
+        },
+        NumberTheoryRoute::NumberTheoryAlgorithm { algorithm } => {
+            if get_number_theory_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <NumberTheoryPage {algorithm} />
+                }
+            } else {
+                html! {
+                    <NumberTheory404Page {algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct NumberTheoryPageProps {
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn NumberTheoryPage(props: &NumberTheoryPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let algorithms = get_number_theory_algorithms();
+    let names = algorithms
+        .values()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect::<Vec<String>>();
+    let algorithm = algorithms[props.algorithm.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&NumberTheoryRoute::NumberTheoryAlgorithm {
+            algorithm: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="NumberTheory"
+            section="Number theory algorithms"
+            select_title="Algorithm"
+            {names}
+            visualizer={algorithm}
+            {on_select}
+            readme_directory="number_theory_algorithms"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct NumberTheory404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn NumberTheory404Page(props: &NumberTheory404PageProps) -> Html {
+    use_title("404 - Number theory algorithms".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<NumberTheoryRoute> to={NumberTheoryRoute::NumberTheory}>
+                { "Back to number theory algorithms" }
+            </Link<NumberTheoryRoute>>
+        </>
+    }
+}
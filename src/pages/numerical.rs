@@ -0,0 +1,168 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use diagram::ElementState;
+use numerical::numerical_algorithms::*;
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+pub fn get_numerical_algorithms() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "fast-fourier-transform",
+            VisualizerInfo::new("Fast Fourier transform", || {
+                Box::new(FastFourierTransform::new(vec![
+                    1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0,
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Inputs of the butterfly"),
+                (ElementState::Selected, "Outputs of the butterfly"),
+            ])
+            .with_tags(&["signals", "divide & conquer", "O(n log n)"]),
+        ),
+        (
+            "gaussian-elimination",
+            VisualizerInfo::new("Gaussian elimination", || {
+                Box::new(GaussianElimination::new(vec![
+                    vec![2.0, 1.0, -1.0, 8.0],
+                    vec![-3.0, -1.0, 2.0, -11.0],
+                    vec![-2.0, 1.0, 2.0, -3.0],
+                ]))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Row being changed or solved"),
+                (ElementState::Frontier, "Pivot candidates"),
+                (ElementState::Selected, "Pivot, solved variable"),
+            ])
+            .with_tags(&["linear algebra", "matrices", "O(n³)"]),
+        ),
+        (
+            "matrix-multiplication",
+            VisualizerInfo::new("Matrix multiplication", || {
+                Box::new(MatrixMultiplication::new(
+                    vec![
+                        vec![1, 2, 0, 3],
+                        vec![4, 1, 2, 0],
+                        vec![0, 3, 1, 2],
+                        vec![2, 0, 4, 1],
+                    ],
+                    vec![
+                        vec![2, 1, 0, 1],
+                        vec![0, 3, 1, 2],
+                        vec![1, 0, 2, 3],
+                        vec![3, 2, 1, 0],
+                    ],
+                ))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Used cells, current size"),
+                (ElementState::Selected, "Computed cells, Strassen"),
+            ])
+            .with_tags(&["linear algebra", "matrices", "divide & conquer", "O(n³)"]),
+        ),
+        (
+            "monte-carlo-pi",
+            VisualizerInfo::new("Monte Carlo pi", || Box::new(MonteCarloPi::new(1000, 10)))
+                .with_legend(&[
+                    (ElementState::Active, "Latest points"),
+                    (ElementState::Selected, "Inside the circle, estimate"),
+                    (ElementState::Rejected, "Outside the circle"),
+                ])
+                .with_tags(&["randomized", "probabilistic"]),
+        ),
+        (
+            "root-finding",
+            VisualizerInfo::new("Root finding", || Box::new(RootFinding::new()))
+                .with_legend(&[
+                    (ElementState::Active, "Current guess or midpoint, tangent"),
+                    (ElementState::Frontier, "Interval, bisection"),
+                    (ElementState::Selected, "Next guess, Newton's method"),
+                    (ElementState::Rejected, "Earlier guesses"),
+                ])
+                .with_tags(&["iterative", "equations"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum NumericalRoute {
+    #[at("/numerical")]
+    Numerical,
+    #[at("/numerical/:algorithm")]
+    NumericalAlgorithm { algorithm: String },
+}
+
+pub fn switch_numerical(route: NumericalRoute) -> Html {
+    match route {
+        NumericalRoute::Numerical => html! {
+            <Redirect<NumericalRoute> to={NumericalRoute::NumericalAlgorithm { algorithm: "fast-fourier-transform".to_string()} } />
+        },
+        NumericalRoute::NumericalAlgorithm { algorithm } => {
+            if get_numerical_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <NumericalPage {algorithm} />
+                }
+            } else {
+                html! {
+                    <Numerical404Page {algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct NumericalPageProps {
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn NumericalPage(props: &NumericalPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let algorithms = get_numerical_algorithms();
+    let names = algorithms
+        .values()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect::<Vec<String>>();
+    let algorithm = algorithms[props.algorithm.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&NumericalRoute::NumericalAlgorithm {
+            algorithm: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="Numerical"
+            section="Numerical algorithms"
+            select_title="Algorithm"
+            {names}
+            visualizer={algorithm}
+            {on_select}
+            readme_directory="numerical_algorithms"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct Numerical404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn Numerical404Page(props: &Numerical404PageProps) -> Html {
+    use_title("404 - Numerical algorithms".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<NumericalRoute> to={NumericalRoute::Numerical}>
+                { "Back to numerical algorithms" }
+            </Link<NumericalRoute>>
+        </>
+    }
+}
@@ -0,0 +1,137 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use diagram::ElementState;
+use optimization::optimization_algorithms::*;
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+pub fn get_optimization_algorithms() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([(
+        "traveling-salesman",
+        VisualizerInfo::new("Traveling salesman", || {
+            Box::new(TravelingSalesman::new(&[
+                (90.0, 120.0),
+                (160.0, 300.0),
+                (210.0, 420.0),
+                (250.0, 180.0),
+                (330.0, 90.0),
+                (350.0, 330.0),
+                (420.0, 440.0),
+                (460.0, 220.0),
+                (520.0, 110.0),
+                (560.0, 360.0),
+                (630.0, 250.0),
+                (650.0, 70.0),
+                (700.0, 420.0),
+                (730.0, 190.0),
+                (120.0, 230.0),
+                (400.0, 160.0),
+            ]))
+        })
+        .with_legend(&[
+            (
+                ElementState::Selected,
+                "Tour, shortest length, bin of the shortest tour",
+            ),
+            (
+                ElementState::Frontier,
+                "Tour lengths, mean or current length, temperature schedule",
+            ),
+            (ElementState::Active, "Temperature so far"),
+            (
+                ElementState::Conflict,
+                "New edges of an accepted longer tour",
+            ),
+        ])
+        .with_tags(&[
+            "graph",
+            "NP-hard",
+            "genetic algorithm",
+            "simulated annealing",
+            "randomized",
+        ]),
+    )])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum OptimizationRoute {
+    #[at("/optimization")]
+    Optimization,
+    #[at("/optimization/:algorithm")]
+    OptimizationAlgorithm { algorithm: String },
+}
+
+pub fn switch_optimization(route: OptimizationRoute) -> Html {
+    match route {
+        OptimizationRoute::Optimization => html! {
+            <Redirect<OptimizationRoute> to={OptimizationRoute::OptimizationAlgorithm { algorithm: "traveling-salesman".to_string()} } />
+        },
+        OptimizationRoute::OptimizationAlgorithm { algorithm } => {
+            if get_optimization_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <OptimizationPage {algorithm} />
+                }
+            } else {
+                html! {
+                    <Optimization404Page {algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct OptimizationPageProps {
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn OptimizationPage(props: &OptimizationPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let algorithms = get_optimization_algorithms();
+    let names = algorithms
+        .values()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect::<Vec<String>>();
+    let algorithm = algorithms[props.algorithm.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&OptimizationRoute::OptimizationAlgorithm {
+            algorithm: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="Optimization"
+            section="Optimization"
+            select_title="Algorithm"
+            {names}
+            visualizer={algorithm}
+            {on_select}
+            readme_directory="optimization_algorithms"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct Optimization404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn Optimization404Page(props: &Optimization404PageProps) -> Html {
+    use_title("404 - Optimization".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<OptimizationRoute> to={OptimizationRoute::Optimization}>
+                { "Back to optimization" }
+            </Link<OptimizationRoute>>
+        </>
+    }
+}
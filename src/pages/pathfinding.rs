@@ -1,20 +1,30 @@
 use crate::components::{
     algo_desc::AlgoDesc,
     collapsible::Collapsible,
+    graph_editor::GraphTool,
     pathfinding::{toolbar::*, *},
     sidebar::Sidebar,
     step_slider::StepSlider,
 };
+use crate::utils::download_file;
 use pathfinding::{
-    generate_graph,
+    generate_graph, generate_hex_graph,
     graph::AdjacencyList,
-    maze_generation::{recursive_division, MazeGenerationResult, MazeGenerationStep},
-    pathfinding_algorithms, run_pathfinding, Coord, Edge, PathfindingResult, PathfindingStep,
-    PathfindingSteps, Vertex, VertexState,
+    maze_generation::{
+        eller, kruskal, randomized_prim, recursive_division, MazeGenerationFunc,
+        MazeGenerationResult, MazeGenerationStep,
+    },
+    pathfinding_algorithms::{self, DStarLite},
+    run_pathfinding,
+    waypoints::{find_path_through, optimal_waypoint_order, MAX_WAYPOINTS},
+    Coord, CornerCutting, Edge, Heuristic, Node, PathfindingResult, PathfindingStep,
+    PathfindingSteps, Vertex, VertexScores, VertexState,
 };
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, VecDeque},
     fmt::Debug,
+    iter::once,
     rc::Rc,
 };
 use yew::prelude::*;
@@ -24,20 +34,58 @@ use yew_router::prelude::*;
 pub type EdgeType = f32;
 
 type PathfindingFunc<V, E> =
-    fn(AdjacencyList<V, E>, V, V, PathfindingSteps<V>) -> PathfindingResult<V, E>;
+    fn(AdjacencyList<V, E>, V, V, Heuristic, PathfindingSteps<V>) -> PathfindingResult<V, E>;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// A search on the grid along with the paths between consecutive stops when there are waypoints.
+type GridSearch = (PathfindingResult<Coord, EdgeType>, Vec<Vec<Coord>>);
+
+#[derive(Clone, Debug)]
 pub struct PathfindingAlgorithm<V: Vertex, E: Edge> {
     pub name: String,
+    /// True if the algorithm is guided by a [`Heuristic`] that can be chosen.
+    pub uses_heuristic: bool,
+    /// True if the algorithm can reuse its previous search when the graph changes or the end moves.
+    pub replans_incrementally: bool,
+    /// What the algorithm can be found with in the catalog of the home page, like its time complexity or technique.
+    pub tags: Vec<&'static str>,
     find_path: PathfindingFunc<V, E>,
 }
+// Function pointers can't be compared reliably, so two `PathfindingAlgorithm`s are considered equal if their names are the same.
+impl<V: Vertex, E: Edge> PartialEq for PathfindingAlgorithm<V, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
 impl<V: Vertex, E: Edge> PathfindingAlgorithm<V, E> {
     pub fn new(name: &str, find_path: PathfindingFunc<V, E>) -> Self {
         Self {
             name: name.to_string(),
+            uses_heuristic: false,
+            replans_incrementally: false,
+            tags: vec![],
             find_path,
         }
     }
+    pub fn with_heuristic(mut self) -> Self {
+        self.uses_heuristic = true;
+        self
+    }
+    pub fn with_incremental_replanning(mut self) -> Self {
+        self.replans_incrementally = true;
+        self
+    }
+    pub fn with_tags(mut self, tags: &[&'static str]) -> Self {
+        self.tags = tags.to_vec();
+        self
+    }
+    /// The name of the algorithm along with the heuristic if it uses one, e.g. "A* (Octile)".
+    pub fn label(&self, heuristic: Heuristic) -> String {
+        if self.uses_heuristic {
+            format!("{} ({:?})", self.name, heuristic)
+        } else {
+            self.name.clone()
+        }
+    }
     /// Finds a path from `start` to `end`.
     /// The path is not guaranteed to be the shortest path depending on the algorithm.
     pub fn find_path(
@@ -45,16 +93,23 @@ impl<V: Vertex, E: Edge> PathfindingAlgorithm<V, E> {
         graph: &AdjacencyList<V, E>,
         start: V,
         end: V,
+        heuristic: Heuristic,
     ) -> (PathfindingResult<V, E>, instant::Duration) {
-        run_pathfinding(graph, start, end, self.find_path)
+        run_pathfinding(graph, start, end, heuristic, self.find_path)
+    }
+    /// Finds a path that visits the `stops` in order. Also returns the path between each pair of consecutive stops.
+    pub fn find_path_through(
+        &self,
+        graph: &AdjacencyList<V, E>,
+        stops: &[V],
+        heuristic: Heuristic,
+    ) -> (PathfindingResult<V, E>, Vec<Vec<V>>) {
+        find_path_through(graph, stops, heuristic, self.find_path)
     }
 }
 impl<E: Edge> Default for PathfindingAlgorithm<Coord, E> {
     fn default() -> Self {
-        Self {
-            name: String::from("Dijkstra"),
-            find_path: pathfinding_algorithms::dijkstra::<Coord, E>,
-        }
+        Self::new("Dijkstra", pathfinding_algorithms::dijkstra::<Coord, E>)
     }
 }
 
@@ -63,20 +118,76 @@ pub fn get_pathfinding_algorithms<V: Vertex, E: Edge>(
     // `BTreeMap` because it keeps the order of the items.
     BTreeMap::from([
         (
-            "a*",
-            PathfindingAlgorithm::new("A*", pathfinding_algorithms::a_star::<V, E>),
+            "a-star",
+            PathfindingAlgorithm::new("A*", pathfinding_algorithms::a_star::<V, E>)
+                .with_heuristic()
+                .with_tags(&["graph", "grid", "shortest path", "heuristic"]),
+        ),
+        (
+            "bfs",
+            PathfindingAlgorithm::new("BFS", pathfinding_algorithms::bfs::<V, E>).with_tags(&[
+                "graph",
+                "grid",
+                "shortest path",
+                "unweighted",
+                "O(V + E)",
+            ]),
+        ),
+        (
+            "bidirectional-bfs",
+            PathfindingAlgorithm::new(
+                "Bidirectional BFS",
+                pathfinding_algorithms::bidirectional_bfs::<V, E>,
+            )
+            .with_tags(&[
+                "graph",
+                "grid",
+                "shortest path",
+                "unweighted",
+                "bidirectional",
+            ]),
+        ),
+        (
+            "bidirectional-dijkstra",
+            PathfindingAlgorithm::new(
+                "Bidirectional Dijkstra",
+                pathfinding_algorithms::bidirectional_dijkstra::<V, E>,
+            )
+            .with_tags(&["graph", "grid", "shortest path", "bidirectional"]),
+        ),
+        (
+            "d-star-lite",
+            PathfindingAlgorithm::new("D* Lite", pathfinding_algorithms::d_star_lite::<V, E>)
+                .with_heuristic()
+                .with_incremental_replanning()
+                .with_tags(&["graph", "grid", "shortest path", "heuristic", "incremental"]),
         ),
         (
             "dijkstra",
-            PathfindingAlgorithm::new("Dijkstra", pathfinding_algorithms::dijkstra::<V, E>),
+            PathfindingAlgorithm::new("Dijkstra", pathfinding_algorithms::dijkstra::<V, E>)
+                .with_tags(&["graph", "grid", "shortest path", "greedy", "O(E log V)"]),
         ),
         (
             "dfs",
-            PathfindingAlgorithm::new("DFS", pathfinding_algorithms::dfs::<V, E>),
+            PathfindingAlgorithm::new("DFS", pathfinding_algorithms::dfs::<V, E>).with_tags(&[
+                "graph",
+                "grid",
+                "traversal",
+                "O(V + E)",
+            ]),
         ),
     ])
 }
 
+pub fn get_maze_generators() -> BTreeMap<&'static str, MazeGenerationFunc> {
+    BTreeMap::from([
+        ("Eller's", eller as MazeGenerationFunc),
+        ("Kruskal's", kruskal),
+        ("Randomized Prim's", randomized_prim),
+        ("Recursive division", recursive_division),
+    ])
+}
+
 #[derive(Clone, Debug, Routable, PartialEq, Eq)]
 pub enum PathfindingRoute {
     #[at("/pathfinding")]
@@ -90,6 +201,10 @@ pub fn switch_pathfinding(route: PathfindingRoute) -> Html {
         PathfindingRoute::Pathfinding => html! {
             <Redirect<PathfindingRoute> to={PathfindingRoute::PathfindingAlgorithm { algorithm: "dfs".to_string()} } />
         },
+        // The stars of e.g. A* used to be a part of the links
+        PathfindingRoute::PathfindingAlgorithm { algorithm } if algorithm.contains('*') => html! {
+            <Redirect<PathfindingRoute> to={PathfindingRoute::PathfindingAlgorithm { algorithm: algorithm.replace('*', "-star") }} />
+        },
         PathfindingRoute::PathfindingAlgorithm { algorithm } => {
             if get_pathfinding_algorithms::<Coord, EdgeType>().contains_key(algorithm.as_str()) {
                 html! {
@@ -104,22 +219,84 @@ pub fn switch_pathfinding(route: PathfindingRoute) -> Html {
     }
 }
 
+/// The kind of graph that the algorithms are run on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraphKind {
+    #[default]
+    Grid,
+    /// A graph of nodes and weighted edges drawn by the user.
+    Nodes,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct PathfindingConfig<E: Edge> {
     pub algorithm: PathfindingAlgorithm<Coord, E>,
+    /// The algorithm that is run on the same grid next to `algorithm`, if any. Both grids are played back with the same steps.
+    pub comparison: Option<PathfindingAlgorithm<Coord, E>>,
+    pub graph_kind: GraphKind,
     pub graph_width: usize,
     pub graph_height: usize,
+    /// Whether the grid is made of hexagons instead of squares. Hexagonal grids ignore `move_diagonally`.
+    pub hex_grid: bool,
     pub move_diagonally: bool,
+    pub corner_cutting: CornerCutting,
+    pub heuristic: Heuristic,
+    /// The weights of terrain types other than [`Terrain::Ground`], which always has a weight of 1.
+    pub terrain_weights: BTreeMap<Terrain, E>,
+    /// The name of the maze generator in [`get_maze_generators`].
+    pub maze_generator: String,
     pub playback_time: f32,
+    /// Whether each relaxation of an edge is shown as a step of its own instead of being a part of the expansion's step.
+    pub relaxation_steps: bool,
+    /// Whether the waypoints are visited in the order that gives the cheapest path instead of the order they were placed in.
+    pub optimize_waypoint_order: bool,
+    /// Whether algorithms that support it continue their previous search after the grid changes instead of starting over.
+    pub incremental_replanning: bool,
+}
+impl<E: Edge> PathfindingConfig<E> {
+    /// Gets the cells that can be moved to from `coord` with the current movement rules, ignoring walls.
+    pub fn adjacent(&self, coord: Coord) -> Vec<Coord> {
+        if self.hex_grid {
+            coord.hex_adjacent()
+        } else {
+            coord.adjacent(self.move_diagonally)
+        }
+    }
+    /// Gets the heuristic that gives the exact cost on an empty graph of the current kind and movement rules.
+    pub fn exact_heuristic(&self) -> Heuristic {
+        match self.graph_kind {
+            GraphKind::Nodes => Heuristic::Euclidean,
+            GraphKind::Grid if self.hex_grid => Heuristic::Hex,
+            GraphKind::Grid if self.move_diagonally => Heuristic::Octile,
+            GraphKind::Grid => Heuristic::Manhattan,
+        }
+    }
+    pub fn terrain_weight(&self, terrain: Terrain) -> E {
+        *self.terrain_weights.get(&terrain).unwrap_or(&E::one())
+    }
 }
 impl<E: Edge> Default for PathfindingConfig<E> {
     fn default() -> Self {
         Self {
             algorithm: PathfindingAlgorithm::default(),
+            comparison: None,
+            graph_kind: GraphKind::default(),
             graph_width: 25,
             graph_height: 25,
+            hex_grid: false,
             move_diagonally: false,
+            corner_cutting: CornerCutting::default(),
+            heuristic: Heuristic::Manhattan,
+            terrain_weights: BTreeMap::from([
+                (Terrain::Road, E::from(0.5).unwrap()),
+                (Terrain::Mud, E::from(3.0).unwrap()),
+                (Terrain::Water, E::from(5.0).unwrap()),
+            ]),
+            maze_generator: String::from("Recursive division"),
             playback_time: 5.0,
+            relaxation_steps: true,
+            optimize_waypoint_order: false,
+            incremental_replanning: true,
         }
     }
 }
@@ -150,22 +327,31 @@ pub fn PathfindingPage(props: &PathfindingPageProps) -> Html {
     let active_tool = use_state_eq(|| PathTool::Wall);
 
     let path = use_mut_ref(Vec::<Coord>::new);
+    let scores = use_mut_ref(BTreeMap::<Coord, VertexScores<EdgeType>>::new);
     let walls = use_mut_ref(BTreeSet::new);
+    let terrain = use_mut_ref(BTreeMap::<Coord, Terrain>::new);
+    // The cells that the path has to visit between the start and the end, in the order they were placed in
+    let waypoints = use_mut_ref(Vec::<Coord>::new);
+    // The paths between consecutive stops when there are waypoints
+    let path_segments = use_mut_ref(Vec::<Vec<Coord>>::new);
 
     let graph = use_mut_ref(|| {
-        generate_graph::<EdgeType>(
-            config.borrow().graph_width,
-            config.borrow().graph_height,
-            config.borrow().move_diagonally,
-            &walls.borrow(),
-        )
+        generate_terrain_graph(&config.borrow(), &walls.borrow(), &terrain.borrow())
     });
 
     let pathfinding_steps = use_mut_ref(|| PathfindingSteps::<Coord>::new(vec![]));
+
+    // The search of the compared algorithm, which is played back next to the other one
+    let comparison_path = use_mut_ref(Vec::<Coord>::new);
+    let comparison_segments = use_mut_ref(Vec::<Vec<Coord>>::new);
+    let comparison_scores = use_mut_ref(BTreeMap::<Coord, VertexScores<EdgeType>>::new);
+    let comparison_steps = use_mut_ref(|| PathfindingSteps::<Coord>::new(vec![]));
+    let comparison_graph_at_step = use_mut_ref(BTreeMap::<Coord, VertexState>::new);
     let maze_gen_steps = use_mut_ref(Vec::<MazeGenerationStep>::new);
 
     let graph_at_pathfinding_step = use_mut_ref(BTreeMap::<Coord, VertexState>::new);
     let walls_at_maze_gen_step = use_mut_ref(BTreeSet::new);
+    let frontier_at_maze_gen_step = use_mut_ref(BTreeSet::new);
 
     let pathfinding_step_index = use_state(|| 0);
     let maze_gen_step_index = use_state(|| 0);
@@ -175,45 +361,140 @@ pub fn PathfindingPage(props: &PathfindingPageProps) -> Html {
     // This should only be shown after maze generation when the user hasn't drawn any new walls.
     let show_maze_gen_slider = use_state(|| false);
 
+    let node_graph = use_mut_ref(NodeGraph::default);
+    // Incremented when the node graph is changed from the sidebar so that the path is found again
+    let node_graph_version = use_state(|| 0);
+
+    // The planner of the previous search for algorithms that replan incrementally
+    let planner = use_mut_ref(|| None::<DStarLite<Coord, EdgeType>>);
+
+    // The statistics of the latest runs, newest first
+    let run_history = use_mut_ref(VecDeque::<RunStats>::new);
+    let force_update = use_force_update();
+
     let find_path = {
         let config = config.clone();
         let graph = Rc::clone(&graph);
+        let waypoints = Rc::clone(&waypoints);
+        let planner = Rc::clone(&planner);
+        let run_history = Rc::clone(&run_history);
 
         move |start: Coord, end: Coord| {
-            config
+            let start_time = instant::Instant::now();
+            let config = config.borrow();
+            let graph = graph.borrow();
+            let waypoints = waypoints
                 .borrow()
-                .algorithm
-                .find_path(&graph.borrow(), start, end)
-                .0
+                .iter()
+                .copied()
+                .filter(|waypoint| graph.hash_map.contains_key(waypoint))
+                .collect::<Vec<Coord>>();
+
+            let incremental = config.algorithm.replans_incrementally
+                && config.incremental_replanning
+                && waypoints.is_empty();
+
+            let (mut result, segments) = if incremental {
+                let mut planner = planner.borrow_mut();
+                match planner.as_mut() {
+                    // The previous search can only be continued if it started from the same cell
+                    Some(planner)
+                        if planner.start() == start && planner.heuristic() == config.heuristic =>
+                    {
+                        planner.update_graph(graph.clone());
+                        planner.move_end(end);
+                    }
+                    _ => {
+                        *planner = Some(DStarLite::new(graph.clone(), start, end, config.heuristic))
+                    }
+                }
+                let result = planner
+                    .as_mut()
+                    .unwrap()
+                    .compute_path(PathfindingSteps::new(vec![]));
+                (result, vec![])
+            } else {
+                *planner.borrow_mut() = None;
+                search_grid(&config, &config.algorithm, &graph, start, end, &waypoints)
+            };
+
+            let stats = RunStats::new(
+                config.algorithm.label(config.heuristic),
+                &graph,
+                &result.steps,
+                &result.path,
+                start_time.elapsed(),
+            );
+            push_run_stats(&mut run_history.borrow_mut(), stats);
+
+            let comparison = config.comparison.as_ref().map(|algorithm| {
+                let (mut result, segments) =
+                    search_grid(&config, algorithm, &graph, start, end, &waypoints);
+                if config.relaxation_steps {
+                    result.steps = result.steps.split_relaxations();
+                }
+                (result, segments)
+            });
+
+            if config.relaxation_steps {
+                result.steps = result.steps.split_relaxations();
+            }
+            ((result, segments), comparison)
         }
     };
 
     let update_pathfinding_step = {
         let steps = Rc::clone(&pathfinding_steps);
         let path = Rc::clone(&path);
+        let path_segments = Rc::clone(&path_segments);
+        let scores = Rc::clone(&scores);
         let graph_at_step = graph_at_pathfinding_step.clone();
+        let comparison_steps = Rc::clone(&comparison_steps);
+        let comparison_path = Rc::clone(&comparison_path);
+        let comparison_segments = Rc::clone(&comparison_segments);
+        let comparison_scores = Rc::clone(&comparison_scores);
+        let comparison_graph_at_step = Rc::clone(&comparison_graph_at_step);
         let step_i = pathfinding_step_index.clone();
 
-        move |pathfinding_result: PathfindingResult<Coord, EdgeType>| {
-            let old_step_count = steps.borrow().len();
+        move |((pathfinding_result, segments), comparison): (GridSearch, Option<GridSearch>)| {
+            let old_step_count = steps.borrow().len().max(comparison_steps.borrow().len());
 
+            let (comparison_result, new_comparison_segments) = comparison.unzip();
             *path.borrow_mut() = pathfinding_result.path;
+            *path_segments.borrow_mut() = segments;
+            *scores.borrow_mut() = pathfinding_result.scores;
             *steps.borrow_mut() = pathfinding_result.steps;
+            *comparison_segments.borrow_mut() = new_comparison_segments.unwrap_or_default();
+            if let Some(comparison_result) = comparison_result {
+                *comparison_path.borrow_mut() = comparison_result.path;
+                *comparison_scores.borrow_mut() = comparison_result.scores;
+                *comparison_steps.borrow_mut() = comparison_result.steps;
+            } else {
+                comparison_path.borrow_mut().clear();
+                comparison_scores.borrow_mut().clear();
+                *comparison_steps.borrow_mut() = PathfindingSteps::new(vec![]);
+            }
 
             let old_step_i = *step_i;
             let new_step_i = if old_step_i >= old_step_count {
-                steps.borrow().len()
+                steps.borrow().len().max(comparison_steps.borrow().len())
             } else {
                 0
             };
 
-            update_graph_at_pathfinding_step(
-                &mut graph_at_step.borrow_mut(),
-                &steps.borrow().steps,
-                new_step_i,
-                old_step_i.min(steps.borrow().len()),
-                true,
-            );
+            for (graph_at_step, steps) in [
+                (&graph_at_step, &steps),
+                (&comparison_graph_at_step, &comparison_steps),
+            ] {
+                let step_count = steps.borrow().len();
+                update_graph_at_pathfinding_step(
+                    &mut graph_at_step.borrow_mut(),
+                    &steps.borrow().steps,
+                    new_step_i.min(step_count),
+                    old_step_i.min(step_count),
+                    true,
+                );
+            }
 
             step_i.set(new_step_i);
         }
@@ -225,16 +506,13 @@ pub fn PathfindingPage(props: &PathfindingPageProps) -> Html {
         let (start, end) = (*start, *end);
         let graph = Rc::clone(&graph);
         let walls = Rc::clone(&walls);
+        let terrain = Rc::clone(&terrain);
         let find_path = find_path.clone();
         let update_pathfinding_step = update_pathfinding_step.clone();
 
         Callback::from(move |_| {
-            let new_graph = generate_graph(
-                config.borrow().graph_width,
-                config.borrow().graph_height,
-                config.borrow().move_diagonally,
-                &walls.borrow(),
-            );
+            let new_graph =
+                generate_terrain_graph(&config.borrow(), &walls.borrow(), &terrain.borrow());
             *graph.borrow_mut() = new_graph;
 
             update_pathfinding_step(find_path(start, end));
@@ -268,19 +546,28 @@ pub fn PathfindingPage(props: &PathfindingPageProps) -> Html {
 
     use_title(format!("{} - Pathfinding", config.borrow().algorithm.name));
 
+    // Both grids are moved to the same step, and the one with fewer steps stays at its last step
     let on_change_pathfinding_step = {
         let graph_at_pathfinding_step = graph_at_pathfinding_step.clone();
         let pathfinding_steps = Rc::clone(&pathfinding_steps);
+        let comparison_graph_at_step = Rc::clone(&comparison_graph_at_step);
+        let comparison_steps = Rc::clone(&comparison_steps);
         let pathfinding_step_index = pathfinding_step_index.clone();
 
-        Callback::from(move |val| {
-            update_graph_at_pathfinding_step(
-                &mut graph_at_pathfinding_step.borrow_mut(),
-                &pathfinding_steps.borrow().steps,
-                val,
-                *pathfinding_step_index,
-                false,
-            );
+        Callback::from(move |val: usize| {
+            for (graph_at_step, steps) in [
+                (&graph_at_pathfinding_step, &pathfinding_steps),
+                (&comparison_graph_at_step, &comparison_steps),
+            ] {
+                let step_count = steps.borrow().len();
+                update_graph_at_pathfinding_step(
+                    &mut graph_at_step.borrow_mut(),
+                    &steps.borrow().steps,
+                    val.min(step_count),
+                    (*pathfinding_step_index).min(step_count),
+                    false,
+                );
+            }
             pathfinding_step_index.set(val);
         })
     };
@@ -289,12 +576,13 @@ pub fn PathfindingPage(props: &PathfindingPageProps) -> Html {
         let maze_gen_steps = Rc::clone(&maze_gen_steps);
         let maze_gen_step_index = maze_gen_step_index.clone();
         let walls_at_maze_gen_step = Rc::clone(&walls_at_maze_gen_step);
+        let frontier_at_maze_gen_step = Rc::clone(&frontier_at_maze_gen_step);
 
         Callback::from(move |val: usize| {
-            *walls_at_maze_gen_step.borrow_mut() = maze_gen_steps.borrow()
-                [val.min(maze_gen_steps.borrow().len() - 1)]
-            .walls
-            .clone();
+            let steps = maze_gen_steps.borrow();
+            let step = &steps[val.min(steps.len() - 1)];
+            *walls_at_maze_gen_step.borrow_mut() = step.walls.clone();
+            *frontier_at_maze_gen_step.borrow_mut() = step.frontier.clone();
             maze_gen_step_index.set(val);
         })
     };
@@ -307,39 +595,94 @@ pub fn PathfindingPage(props: &PathfindingPageProps) -> Html {
         })
     };
 
+    // Moves the start to a new cell and finds the path again right away
+    let on_move_start = {
+        let (start, end) = (start.clone(), *end);
+        let graph = Rc::clone(&graph);
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+
+        Callback::from(move |vertex| {
+            if vertex != end && graph.borrow().hash_map.contains_key(&vertex) {
+                start.set(vertex);
+                update_pathfinding_step(find_path(vertex, end));
+            }
+        })
+    };
+
+    // Moves the end to a new cell and finds the path again right away
+    let on_move_end = {
+        let (start, end) = (*start, end.clone());
+        let graph = Rc::clone(&graph);
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+
+        Callback::from(move |vertex| {
+            if vertex != start && graph.borrow().hash_map.contains_key(&vertex) {
+                end.set(vertex);
+                update_pathfinding_step(find_path(start, vertex));
+            }
+        })
+    };
+
+    // Whether walls or waypoints are being erased instead of drawn. This is decided by the first cell of a drawing.
+    let erasing = use_mut_ref(|| None::<bool>);
+
     let on_click_cell = {
-        let (start, end) = (start.clone(), end.clone());
+        let (start, end) = (*start, *end);
         let active_tool = *active_tool;
         let graph = Rc::clone(&graph);
         let walls = Rc::clone(&walls);
+        let terrain = Rc::clone(&terrain);
+        let waypoints = Rc::clone(&waypoints);
+        let erasing = Rc::clone(&erasing);
         let paused = paused.clone();
         let show_maze_gen_slider = show_maze_gen_slider.clone();
-        let update_pathfinding_step = update_pathfinding_step.clone();
-        let find_path = find_path.clone();
+        let on_move_start = on_move_start.clone();
+        let on_move_end = on_move_end.clone();
 
         Callback::from(move |vertex| {
-            if vertex != *start && vertex != *end {
-                if graph.borrow().hash_map.contains_key(&vertex) {
-                    match active_tool {
-                        PathTool::Start => {
-                            start.set(vertex);
-                            update_pathfinding_step(find_path(vertex, *end));
-                        }
-                        PathTool::End => {
-                            end.set(vertex);
-                            update_pathfinding_step(find_path(*start, vertex));
-                        }
-                        PathTool::Wall => {
-                            walls.borrow_mut().insert(vertex);
-                            paused.set(true);
-                            show_maze_gen_slider.set(false);
-                        }
+            if vertex == start || vertex == end {
+                return;
+            }
+            match active_tool {
+                PathTool::Start => on_move_start.emit(vertex),
+                PathTool::End => on_move_end.emit(vertex),
+                PathTool::Wall if waypoints.borrow().contains(&vertex) => (),
+                PathTool::Wall => {
+                    let is_wall = walls.borrow().contains(&vertex);
+                    let erasing = *erasing.borrow_mut().get_or_insert(is_wall);
+                    if erasing {
+                        walls.borrow_mut().remove(&vertex);
+                    } else {
+                        walls.borrow_mut().insert(vertex);
                     }
-                } else if active_tool == PathTool::Wall {
-                    walls.borrow_mut().remove(&vertex);
                     paused.set(true);
                     show_maze_gen_slider.set(false);
                 }
+                PathTool::Terrain(_) if !graph.borrow().hash_map.contains_key(&vertex) => (),
+                PathTool::Terrain(Terrain::Ground) => {
+                    terrain.borrow_mut().remove(&vertex);
+                    paused.set(true);
+                }
+                PathTool::Terrain(new_terrain) => {
+                    terrain.borrow_mut().insert(vertex, new_terrain);
+                    paused.set(true);
+                }
+                PathTool::Waypoint if !graph.borrow().hash_map.contains_key(&vertex) => (),
+                PathTool::Waypoint => {
+                    let is_waypoint = waypoints.borrow().contains(&vertex);
+                    let erasing = *erasing.borrow_mut().get_or_insert(is_waypoint);
+                    if erasing {
+                        waypoints
+                            .borrow_mut()
+                            .retain(|waypoint| *waypoint != vertex);
+                    } else if !is_waypoint && waypoints.borrow().len() < MAX_WAYPOINTS {
+                        waypoints.borrow_mut().push(vertex);
+                    }
+                    paused.set(true);
+                }
+                PathTool::Graph(_) => (),
             }
         })
     };
@@ -350,18 +693,20 @@ pub fn PathfindingPage(props: &PathfindingPageProps) -> Html {
         let active_tool = *active_tool;
         let graph = Rc::clone(&graph);
         let walls = Rc::clone(&walls);
+        let terrain = Rc::clone(&terrain);
         let paused = paused.clone();
         let update_pathfinding_step = update_pathfinding_step.clone();
         let find_path = find_path.clone();
 
         Callback::from(move |_| {
-            if active_tool == PathTool::Wall {
-                let new_graph = generate_graph(
-                    config.borrow().graph_width,
-                    config.borrow().graph_height,
-                    config.borrow().move_diagonally,
-                    &walls.borrow(),
-                );
+            *erasing.borrow_mut() = None;
+
+            if matches!(
+                active_tool,
+                PathTool::Wall | PathTool::Terrain(_) | PathTool::Waypoint
+            ) {
+                let new_graph =
+                    generate_terrain_graph(&config.borrow(), &walls.borrow(), &terrain.borrow());
                 *graph.borrow_mut() = new_graph;
 
                 update_pathfinding_step(find_path(start, end));
@@ -376,6 +721,7 @@ pub fn PathfindingPage(props: &PathfindingPageProps) -> Html {
         let (start, end) = (*start, *end);
         let graph = Rc::clone(&graph);
         let walls = Rc::clone(&walls);
+        let terrain = Rc::clone(&terrain);
         let walls_at_maze_gen_step = Rc::clone(&walls_at_maze_gen_step);
         let maze_gen_steps = Rc::clone(&maze_gen_steps);
         let update_pathfinding_step = update_pathfinding_step.clone();
@@ -387,12 +733,8 @@ pub fn PathfindingPage(props: &PathfindingPageProps) -> Html {
             walls_at_maze_gen_step.borrow_mut().clear();
             maze_gen_steps.borrow_mut().clear();
 
-            let new_graph = generate_graph(
-                config.borrow().graph_width,
-                config.borrow().graph_height,
-                config.borrow().move_diagonally,
-                &walls.borrow(),
-            );
+            let new_graph =
+                generate_terrain_graph(&config.borrow(), &walls.borrow(), &terrain.borrow());
             *graph.borrow_mut() = new_graph;
 
             update_pathfinding_step(find_path(start, end));
@@ -401,35 +743,153 @@ pub fn PathfindingPage(props: &PathfindingPageProps) -> Html {
         })
     };
 
+    let on_clear_terrain = {
+        let config = config.clone();
+        let (start, end) = (*start, *end);
+        let graph = Rc::clone(&graph);
+        let walls = Rc::clone(&walls);
+        let terrain = Rc::clone(&terrain);
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+
+        Callback::from(move |_| {
+            terrain.borrow_mut().clear();
+
+            let new_graph =
+                generate_terrain_graph(&config.borrow(), &walls.borrow(), &terrain.borrow());
+            *graph.borrow_mut() = new_graph;
+
+            update_pathfinding_step(find_path(start, end));
+        })
+    };
+
+    let on_clear_waypoints = {
+        let (start, end) = (*start, *end);
+        let waypoints = Rc::clone(&waypoints);
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+
+        Callback::from(move |_| {
+            waypoints.borrow_mut().clear();
+            update_pathfinding_step(find_path(start, end));
+        })
+    };
+
+    let on_export_grid = {
+        let config = config.clone();
+        let (start, end) = (*start, *end);
+        let walls = Rc::clone(&walls);
+        let terrain = Rc::clone(&terrain);
+        let waypoints = Rc::clone(&waypoints);
+
+        Callback::from(move |_| {
+            let config = config.borrow();
+            let mut terrain_cells = BTreeMap::<Terrain, Vec<(isize, isize)>>::new();
+            for (coord, terrain) in terrain.borrow().iter() {
+                terrain_cells
+                    .entry(*terrain)
+                    .or_default()
+                    .push((coord.x, coord.y));
+            }
+
+            let grid = SavedGrid {
+                width: config.graph_width,
+                height: config.graph_height,
+                hex: config.hex_grid,
+                start: (start.x, start.y),
+                end: (end.x, end.y),
+                walls: walls.borrow().iter().map(|cell| (cell.x, cell.y)).collect(),
+                terrain: terrain_cells,
+                terrain_weights: config.terrain_weights.clone(),
+                waypoints: waypoints
+                    .borrow()
+                    .iter()
+                    .map(|cell| (cell.x, cell.y))
+                    .collect(),
+            };
+            if let Err(err) = download_file("grid.json", &grid.to_json(), "application/json") {
+                log::error!("Could not export the grid: {:?}", err);
+            }
+        })
+    };
+
+    let on_import_grid = {
+        let config = config.clone();
+        let (start, end) = (start.clone(), end.clone());
+        let graph = Rc::clone(&graph);
+        let walls = Rc::clone(&walls);
+        let terrain = Rc::clone(&terrain);
+        let waypoints = Rc::clone(&waypoints);
+        let walls_at_maze_gen_step = Rc::clone(&walls_at_maze_gen_step);
+        let maze_gen_steps = Rc::clone(&maze_gen_steps);
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+        let show_maze_gen_slider = show_maze_gen_slider.clone();
+
+        Callback::from(move |grid: SavedGrid| {
+            let coord = |(x, y): (isize, isize)| Coord::new(x, y);
+            {
+                let mut config = config.borrow_mut();
+                config.graph_width = grid.width;
+                config.graph_height = grid.height;
+                if config.hex_grid != grid.hex {
+                    config.hex_grid = grid.hex;
+                    config.heuristic = config.exact_heuristic();
+                }
+                config.terrain_weights.extend(grid.terrain_weights);
+            }
+
+            *walls.borrow_mut() = grid.walls.into_iter().map(coord).collect();
+            *terrain.borrow_mut() = grid
+                .terrain
+                .into_iter()
+                .flat_map(|(terrain, cells)| {
+                    cells.into_iter().map(move |cell| (coord(cell), terrain))
+                })
+                .collect();
+            *waypoints.borrow_mut() = grid.waypoints.into_iter().map(coord).collect();
+            walls_at_maze_gen_step.borrow_mut().clear();
+            maze_gen_steps.borrow_mut().clear();
+
+            let new_graph =
+                generate_terrain_graph(&config.borrow(), &walls.borrow(), &terrain.borrow());
+            *graph.borrow_mut() = new_graph;
+
+            let (new_start, new_end) = (coord(grid.start), coord(grid.end));
+            start.set(new_start);
+            end.set(new_end);
+            update_pathfinding_step(find_path(new_start, new_end));
+
+            show_maze_gen_slider.set(false);
+        })
+    };
+
     let on_generate_maze = {
         let config = config.clone();
         let (start, end) = (*start, *end);
         let walls = Rc::clone(&walls);
+        let waypoints = Rc::clone(&waypoints);
+        let terrain = Rc::clone(&terrain);
         let show_maze_gen_slider = show_maze_gen_slider.clone();
         let walls_at_maze_gen_step = walls_at_maze_gen_step.clone();
+        let frontier_at_maze_gen_step = frontier_at_maze_gen_step.clone();
         let maze_gen_steps = Rc::clone(&maze_gen_steps);
         let maze_gen_step_index = maze_gen_step_index.clone();
 
         Callback::from(move |_| {
             // Generate maze
-            let res = generate_maze(&config.borrow(), start, end);
+            let res = generate_maze(&config.borrow(), start, end, &waypoints.borrow());
             *walls.borrow_mut() = res.walls;
             *maze_gen_steps.borrow_mut() = res.steps;
 
             maze_gen_step_index.set(maze_gen_steps.borrow().len());
-            *walls_at_maze_gen_step.borrow_mut() = maze_gen_steps.borrow()[maze_gen_steps
-                .borrow()
-                .len()
-                .min(maze_gen_steps.borrow().len() - 1)]
-            .walls
-            .clone();
-
-            let new_graph = generate_graph(
-                config.borrow().graph_width,
-                config.borrow().graph_height,
-                config.borrow().move_diagonally,
-                &walls.borrow(),
-            );
+            if let Some(last_step) = maze_gen_steps.borrow().last() {
+                *walls_at_maze_gen_step.borrow_mut() = last_step.walls.clone();
+                *frontier_at_maze_gen_step.borrow_mut() = last_step.frontier.clone();
+            }
+
+            let new_graph =
+                generate_terrain_graph(&config.borrow(), &walls.borrow(), &terrain.borrow());
             *graph.borrow_mut() = new_graph;
 
             update_pathfinding_step(find_path(start, end));
@@ -438,80 +898,542 @@ pub fn PathfindingPage(props: &PathfindingPageProps) -> Html {
         })
     };
 
-    html! {
-        <div class="page" id="Pathfinding">
-            <Sidebar>
-                <h2>{"Config"}</h2>
+    let on_clear_node_graph = {
+        let node_graph = Rc::clone(&node_graph);
+        let node_graph_version = node_graph_version.clone();
 
-                <Collapsible title="General" open={true} class="config-section">
-                    <PathfindingControls<EdgeType> config={Rc::clone(&config)} {on_update_config} />
-                </Collapsible>
+        Callback::from(move |_| {
+            node_graph.borrow_mut().clear();
+            node_graph_version.set(*node_graph_version + 1);
+        })
+    };
+
+    let on_reset_node_graph = {
+        let node_graph = Rc::clone(&node_graph);
+        let node_graph_version = node_graph_version.clone();
 
+        Callback::from(move |_| {
+            *node_graph.borrow_mut() = NodeGraph::default();
+            node_graph_version.set(*node_graph_version + 1);
+        })
+    };
+
+    let is_node_graph = config.borrow().graph_kind == GraphKind::Nodes;
+
+    let graph_controls = if is_node_graph {
+        html! {
+            <Collapsible title="Graph" open={true} class="config-section">
+                <NodeGraphControls on_clear_graph={on_clear_node_graph} on_reset_graph={on_reset_node_graph} />
+            </Collapsible>
+        }
+    } else {
+        html! {
+            <>
                 <Collapsible title="Walls" open={true} class="config-section">
-                    <WallControls {on_clear_walls} {on_generate_maze} />
+                    <WallControls<EdgeType> config={Rc::clone(&config)} {on_clear_walls} {on_generate_maze} />
                 </Collapsible>
-            </Sidebar>
 
-            <main>
-                <div class="visualization">
-                    <PathToolbar active_tool={*active_tool} {on_tool_change} />
+                <Collapsible title="Terrain" open={false} class="config-section">
+                    <TerrainControls<EdgeType> config={Rc::clone(&config)} on_update_config={on_update_config.clone()} {on_clear_terrain} />
+                </Collapsible>
+
+                <Collapsible title="Waypoints" open={false} class="config-section">
+                    <WaypointControls<EdgeType> config={Rc::clone(&config)} on_update_config={on_update_config.clone()} {on_clear_waypoints} />
+                </Collapsible>
+
+                <Collapsible title="Save and load" open={false} class="config-section">
+                    <GridFileControls on_export={on_export_grid} on_import={on_import_grid} />
+                </Collapsible>
+            </>
+        }
+    };
+
+    // Records the runs on the node graph, which are found in its own component
+    let on_run = {
+        let run_history = Rc::clone(&run_history);
+
+        Callback::from(move |stats: RunStats| {
+            push_run_stats(&mut run_history.borrow_mut(), stats);
+            force_update.force_update();
+        })
+    };
+
+    let visualization = if is_node_graph {
+        html! {
+            <NodeGraphPathfinding
+                algorithm={config.borrow().algorithm.name.clone()}
+                heuristic={config.borrow().heuristic}
+                playback_time={config.borrow().playback_time}
+                relaxation_steps={config.borrow().relaxation_steps}
+                graph={Rc::clone(&node_graph)}
+                graph_version={*node_graph_version}
+                {on_run}
+            />
+        }
+    } else {
+        let step_count = pathfinding_steps
+            .borrow()
+            .len()
+            .max(comparison_steps.borrow().len());
+        let shown_walls = if *show_maze_gen_slider {
+            Rc::clone(&walls_at_maze_gen_step)
+        } else {
+            Rc::clone(&walls)
+        };
+        let shown_frontier = show_maze_gen_slider.then(|| Rc::clone(&frontier_at_maze_gen_step));
+        // Both grids are drawn from the same walls and cells, and editing either of them edits both
+        let path_grid =
+            |graph_at_step: &Rc<RefCell<BTreeMap<Coord, VertexState>>>,
+             step_count: usize,
+             path: &Rc<RefCell<Vec<Coord>>>,
+             path_segments: &Rc<RefCell<Vec<Vec<Coord>>>>,
+             scores: &Rc<RefCell<BTreeMap<Coord, VertexScores<EdgeType>>>>| {
+                html! {
                     <PathGrid
                         width={config.borrow().graph_width}
                         height={config.borrow().graph_height}
-                        graph={Rc::clone(&graph_at_pathfinding_step)}
-                        walls={
-                            if *show_maze_gen_slider {
-                                Rc::clone(&walls_at_maze_gen_step)
-                            } else {
-                                Rc::clone(&walls)
-                            }
-                        }
+                        hex={config.borrow().hex_grid}
+                        graph={Rc::clone(graph_at_step)}
+                        walls={Rc::clone(&shown_walls)}
                         path={
-                            if *pathfinding_step_index >= pathfinding_steps.borrow().len() && path.borrow().len() > 0 {
-                                Some(Rc::clone(&path))
+                            if *pathfinding_step_index >= step_count && !path.borrow().is_empty() {
+                                Some(Rc::clone(path))
                             } else {
                                 None
                             }
                         }
+                        frontier={shown_frontier.clone()}
+                        scores={Rc::clone(scores)}
+                        terrain={Rc::clone(&terrain)}
+                        terrain_weights={config.borrow().terrain_weights.clone()}
+                        waypoints={visited_waypoints(&waypoints.borrow(), &path_segments.borrow())}
+                        path_segments={Rc::clone(path_segments)}
                         start={*start}
                         end={*end}
-                        {on_click_cell}
-                        {on_draw_end}
+                        on_click_cell={on_click_cell.clone()}
+                        on_draw_end={on_draw_end.clone()}
+                        on_move_start={on_move_start.clone()}
+                        on_move_end={on_move_end.clone()}
                     />
+                }
+            };
+        let grid = path_grid(
+            &graph_at_pathfinding_step,
+            pathfinding_steps.borrow().len(),
+            &path,
+            &path_segments,
+            &scores,
+        );
 
-                    <StepSlider
-                        label={format!("Pathfinding steps ({}/{})", *pathfinding_step_index, pathfinding_steps.borrow().len())}
-                        active_step_index={*pathfinding_step_index}
-                        max={pathfinding_steps.borrow().len()}
-                        on_change={on_change_pathfinding_step}
-                        playback_time={config.borrow().playback_time}
-                        disabled={*paused}
-                    />
+        let grids = if let Some(comparison) = config.borrow().comparison.as_ref() {
+            let heuristic = config.borrow().heuristic;
+            let comparison_grid = path_grid(
+                &comparison_graph_at_step,
+                comparison_steps.borrow().len(),
+                &comparison_path,
+                &comparison_segments,
+                &comparison_scores,
+            );
+            html! {
+                <div class="path-grid-comparison">
+                    <figure>
+                        <figcaption>
+                            { comparison_caption(&config.borrow().algorithm.label(heuristic), &graph_at_pathfinding_step.borrow(), *pathfinding_step_index >= pathfinding_steps.borrow().len(), &path.borrow()) }
+                        </figcaption>
+                        { grid }
+                    </figure>
+                    <figure>
+                        <figcaption>
+                            { comparison_caption(&comparison.label(heuristic), &comparison_graph_at_step.borrow(), *pathfinding_step_index >= comparison_steps.borrow().len(), &comparison_path.borrow()) }
+                        </figcaption>
+                        { comparison_grid }
+                    </figure>
+                </div>
+            }
+        } else {
+            grid
+        };
 
-                    {
-                        if *show_maze_gen_slider {
-                            html! {
-                                <StepSlider
-                                    label={format!("Maze generation steps ({}/{})", *maze_gen_step_index, maze_gen_steps.borrow().len())}
-                                    active_step_index={*maze_gen_step_index}
-                                    max={maze_gen_steps.borrow().len()}
-                                    on_change={on_change_maze_gen_step}
-                                    playback_time={config.borrow().playback_time}
-                                    disabled={*paused}
-                                />
-                            }
-                        } else {
-                            html! {}
+        let mut states = used_states(&pathfinding_steps.borrow());
+        for state in used_states(&comparison_steps.borrow()) {
+            if !states.contains(&state) {
+                states.push(state);
+            }
+        }
+
+        html! {
+            <>
+                <PathToolbar active_tool={*active_tool} {on_tool_change} />
+                { grids }
+
+                <StateLegend {states} />
+
+                <p id="pathGridStatus" class="visually-hidden" role="status" aria-live="polite">
+                    { pathfinding_step_summary(&graph_at_pathfinding_step.borrow(), (*pathfinding_step_index).min(pathfinding_steps.borrow().len()), pathfinding_steps.borrow().len(), &path.borrow()) }
+                </p>
+
+                <StepSlider
+                    label={format!("Pathfinding steps ({}/{})", *pathfinding_step_index, step_count)}
+                    active_step_index={*pathfinding_step_index}
+                    max={step_count}
+                    on_change={on_change_pathfinding_step}
+                    playback_time={config.borrow().playback_time}
+                    disabled={*paused}
+                />
+
+                {
+                    if *show_maze_gen_slider {
+                        html! {
+                            <StepSlider
+                                label={format!("Maze generation steps ({}/{})", *maze_gen_step_index, maze_gen_steps.borrow().len())}
+                                active_step_index={*maze_gen_step_index}
+                                max={maze_gen_steps.borrow().len()}
+                                on_change={on_change_maze_gen_step}
+                                playback_time={config.borrow().playback_time}
+                                disabled={*paused}
+                            />
                         }
+                    } else {
+                        html! {}
                     }
+                }
+            </>
+        }
+    };
+
+    html! {
+        <div class="page" id="Pathfinding">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <PathfindingControls<EdgeType> config={Rc::clone(&config)} {on_update_config} />
+                </Collapsible>
+
+                { graph_controls }
+            </Sidebar>
+
+            <main>
+                <div class="visualization">
+                    { visualization }
                 </div>
 
+                <RunStatsPanel history={run_history.borrow().clone()} />
+
                 <AlgoDesc algorithm={config.borrow().algorithm.name.clone()} />
             </main>
         </div>
     }
 }
 
+#[derive(Properties, Clone, PartialEq)]
+struct NodeGraphPathfindingProps {
+    /// The name of the algorithm in [`get_pathfinding_algorithms`].
+    algorithm: String,
+    heuristic: Heuristic,
+    playback_time: f32,
+    relaxation_steps: bool,
+    graph: Rc<RefCell<NodeGraph>>,
+    /// Changes whenever `graph` is changed outside of this component.
+    graph_version: usize,
+    /// Emitted with the statistics of every run.
+    on_run: Callback<RunStats>,
+}
+
+/// Runs the pathfinding algorithms on a node graph that can be edited.
+#[function_component]
+fn NodeGraphPathfinding(props: &NodeGraphPathfindingProps) -> Html {
+    let NodeGraphPathfindingProps {
+        algorithm,
+        heuristic,
+        playback_time,
+        relaxation_steps,
+        graph,
+        graph_version,
+        on_run,
+    } = props.clone();
+
+    let active_tool = use_state_eq(|| PathTool::Graph(GraphTool::Edit));
+
+    let path = use_mut_ref(Vec::<Node>::new);
+    let steps = use_mut_ref(|| PathfindingSteps::<Node>::new(vec![]));
+    let graph_at_step = use_mut_ref(BTreeMap::<Node, VertexState>::new);
+    let step_index = use_state(|| 0);
+
+    // Finds the path again and keeps showing the last step if it was being shown
+    let find_path = {
+        let algorithm = algorithm.clone();
+        let graph = Rc::clone(&graph);
+        let path = Rc::clone(&path);
+        let steps = Rc::clone(&steps);
+        let graph_at_step = Rc::clone(&graph_at_step);
+        let step_i = step_index.clone();
+
+        move || {
+            let Some(algorithm) = get_pathfinding_algorithms::<Node, EdgeType>()
+                .into_values()
+                .find(|a| a.name == algorithm)
+            else {
+                return;
+            };
+            let graph = graph.borrow();
+            let (start, end) = (graph.graph.node(graph.start), graph.graph.node(graph.end));
+            let adjacency_list = graph.graph.to_adjacency_list();
+            let (mut result, duration) =
+                algorithm.find_path(&adjacency_list, start, end, heuristic);
+
+            on_run.emit(RunStats::new(
+                algorithm.label(heuristic),
+                &adjacency_list,
+                &result.steps,
+                &result.path,
+                duration,
+            ));
+
+            if relaxation_steps {
+                result.steps = result.steps.split_relaxations();
+            }
+
+            let old_step_count = steps.borrow().len();
+            *path.borrow_mut() = result.path;
+            *steps.borrow_mut() = result.steps;
+
+            let old_step_i = *step_i;
+            let new_step_i = if old_step_i >= old_step_count {
+                steps.borrow().len()
+            } else {
+                0
+            };
+
+            update_graph_at_pathfinding_step(
+                &mut graph_at_step.borrow_mut(),
+                &steps.borrow().steps,
+                new_step_i,
+                old_step_i.min(steps.borrow().len()),
+                true,
+            );
+
+            step_i.set(new_step_i);
+        }
+    };
+
+    {
+        let find_path = find_path.clone();
+
+        use_effect_with_deps(
+            move |_| {
+                find_path();
+                || ()
+            },
+            (algorithm, heuristic, relaxation_steps, graph_version),
+        );
+    }
+
+    let on_change_step = {
+        let graph_at_step = Rc::clone(&graph_at_step);
+        let steps = Rc::clone(&steps);
+        let step_index = step_index.clone();
+
+        Callback::from(move |val| {
+            update_graph_at_pathfinding_step(
+                &mut graph_at_step.borrow_mut(),
+                &steps.borrow().steps,
+                val,
+                *step_index,
+                false,
+            );
+            step_index.set(val);
+        })
+    };
+
+    let on_tool_change = {
+        let active_tool = active_tool.clone();
+
+        Callback::from(move |new_active_tool| {
+            active_tool.set(new_active_tool);
+        })
+    };
+
+    let on_change = {
+        let graph = Rc::clone(&graph);
+        let find_path = find_path.clone();
+
+        Callback::from(move |new_graph| {
+            *graph.borrow_mut() = new_graph;
+            find_path();
+        })
+    };
+
+    let on_click_node = {
+        let active_tool = *active_tool;
+        let graph = Rc::clone(&graph);
+
+        Callback::from(move |id| {
+            let (start, end) = (graph.borrow().start, graph.borrow().end);
+            match active_tool {
+                PathTool::Start if id != end => graph.borrow_mut().start = id,
+                PathTool::End if id != start => graph.borrow_mut().end = id,
+                _ => return,
+            }
+            find_path();
+        })
+    };
+
+    let step_count = steps.borrow().len();
+
+    // The edges between the latest expanded nodes and the neighbors that they queued during the current step.
+    // The expanded nodes are found from all steps so far, since the relaxations can have steps of their own.
+    let relaxations = if *step_index > 0 && *step_index <= step_count {
+        let steps = steps.borrow();
+        let expanded_states = [VertexState::NewVisited, VertexState::NewVisitedFromEnd];
+        let queued_states = [VertexState::Queued, VertexState::QueuedFromEnd];
+        let expanded = graph_at_step
+            .borrow()
+            .iter()
+            .filter(|(_, state)| expanded_states.contains(state))
+            .map(|(node, _)| node.id)
+            .collect::<Vec<usize>>();
+        let queued = steps.steps[*step_index - 1]
+            .states
+            .iter()
+            .filter(|(_, state)| queued_states.contains(state))
+            .map(|(node, _)| node.id)
+            .collect::<Vec<usize>>();
+
+        expanded
+            .iter()
+            .flat_map(|from| queued.iter().map(move |to| (*from, *to)))
+            .filter(|(from, to)| graph.borrow().graph.weight(*from, *to).is_some())
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let states = graph_at_step
+        .borrow()
+        .iter()
+        .map(|(node, state)| (node.id, *state))
+        .collect::<BTreeMap<usize, VertexState>>();
+
+    let shown_path = if *step_index >= step_count {
+        path.borrow().iter().map(|node| node.id).collect()
+    } else {
+        vec![]
+    };
+
+    html! {
+        <>
+            <PathToolbar active_tool={*active_tool} node_graph={true} {on_tool_change} />
+            <NodeGraphEditor
+                graph={graph.borrow().clone()}
+                {states}
+                path={shown_path}
+                {relaxations}
+                step={*step_index}
+                active_tool={*active_tool}
+                {on_change}
+                {on_click_node}
+            />
+
+            <StateLegend states={used_states(&steps.borrow())} />
+
+            <p id="pathGridStatus" class="visually-hidden" role="status" aria-live="polite">
+                { pathfinding_step_summary(&graph_at_step.borrow(), *step_index, step_count, &path.borrow()) }
+            </p>
+
+            <StepSlider
+                label={format!("Pathfinding steps ({}/{})", *step_index, step_count)}
+                active_step_index={*step_index}
+                max={step_count}
+                on_change={on_change_step}
+                {playback_time}
+            />
+        </>
+    }
+}
+
+/// Gets the waypoints in the order that the path visits them, or in the order they were placed in if there is no path.
+fn visited_waypoints(waypoints: &[Coord], path_segments: &[Vec<Coord>]) -> Vec<Coord> {
+    if path_segments.len() > 1 {
+        path_segments[..path_segments.len() - 1]
+            .iter()
+            .filter_map(|segment| segment.last().copied())
+            .collect()
+    } else {
+        waypoints.to_vec()
+    }
+}
+
+/// Gets the states that appear in any of the steps.
+fn used_states<V: Vertex>(steps: &PathfindingSteps<V>) -> Vec<VertexState> {
+    let mut states = vec![];
+    for state in steps.steps.iter().flat_map(|step| step.states.values()) {
+        if !states.contains(state) {
+            states.push(*state);
+        }
+    }
+    states
+}
+
+/// The number of vertices that have been visited at a step.
+fn visited_count<V: Vertex>(graph: &BTreeMap<V, VertexState>) -> usize {
+    graph
+        .values()
+        .filter(|state| {
+            matches!(
+                state,
+                VertexState::NewVisited
+                    | VertexState::Visited
+                    | VertexState::NewVisitedFromEnd
+                    | VertexState::VisitedFromEnd
+                    | VertexState::Meeting
+                    | VertexState::Path
+            )
+        })
+        .count()
+}
+
+/// The caption of a grid in the comparison, e.g. "BFS: 120 visited, path of 38 moves".
+fn comparison_caption(
+    label: &str,
+    graph: &BTreeMap<Coord, VertexState>,
+    finished: bool,
+    path: &[Coord],
+) -> String {
+    let mut caption = format!("{}: {} visited", label, visited_count(graph));
+    if finished {
+        match path.len() {
+            0 => caption += ", no path",
+            2 => caption += ", path of 1 move",
+            length => caption += &format!(", path of {} moves", length - 1),
+        }
+    }
+    caption
+}
+
+/// A textual summary of the current pathfinding step for screen readers.
+fn pathfinding_step_summary<V: Vertex>(
+    graph: &BTreeMap<V, VertexState>,
+    step_i: usize,
+    step_count: usize,
+    path: &[V],
+) -> String {
+    let mut summary = format!(
+        "Step {} of {}. {} vertices visited.",
+        step_i,
+        step_count,
+        visited_count(graph)
+    );
+    if step_i >= step_count {
+        if path.is_empty() {
+            summary += " No path was found.";
+        } else {
+            summary += &format!(" A path of {} vertices was found.", path.len());
+        }
+    }
+    summary
+}
+
 fn update_graph_at_pathfinding_step<V: Vertex>(
     graph: &mut BTreeMap<V, VertexState>,
     steps: &[PathfindingStep<V>],
@@ -519,80 +1441,99 @@ fn update_graph_at_pathfinding_step<V: Vertex>(
     prev_step_i: usize,
     force_from_start: bool,
 ) {
-    // We use a reference to a slice to avoid cloning
-    let steps_to_execute: &[PathfindingStep<V>];
-    let run_from_start = force_from_start || step_i.abs_diff(0) < step_i.abs_diff(prev_step_i);
-    let mut execute_in_reverse = false;
-
-    // Get steps to execute
-    if !run_from_start {
-        // Execute steps between previous and current step indices
-        execute_in_reverse = step_i < prev_step_i;
-        if !execute_in_reverse {
-            // Going forwards in steps
-            steps_to_execute = &steps[prev_step_i..step_i];
-        } else {
-            // Going backwards in steps
-            steps_to_execute = &steps[step_i..prev_step_i];
-        }
-    } else {
-        // Execute all steps from start to current step index
+    // A vertex's state before a step can't be known from the step alone, so going backwards runs the steps from the start.
+    // We use a reference to a slice to avoid cloning.
+    let steps_to_execute: &[PathfindingStep<V>] = if force_from_start || step_i < prev_step_i {
         graph.clear();
-        steps_to_execute = &steps[0..step_i];
-    }
+        &steps[0..step_i]
+    } else {
+        // Going forwards in steps, execute steps between previous and current step indices
+        &steps[prev_step_i..step_i]
+    };
 
-    execute_steps(graph, steps_to_execute, execute_in_reverse);
+    execute_steps(graph, steps_to_execute);
 }
 
-fn execute_steps<V: Vertex>(
-    graph: &mut BTreeMap<V, VertexState>,
-    steps: &[PathfindingStep<V>],
-    reverse: bool,
-) {
-    if !reverse {
-        for step in steps.iter() {
-            for (vertex, state) in step.states.iter() {
-                graph.insert(*vertex, *state);
-            }
+fn execute_steps<V: Vertex>(graph: &mut BTreeMap<V, VertexState>, steps: &[PathfindingStep<V>]) {
+    for step in steps.iter() {
+        for (vertex, state) in step.states.iter() {
+            graph.insert(*vertex, *state);
         }
+    }
+}
+
+/// Finds the path from `start` to `end` through the `waypoints` without reusing a previous search.
+fn search_grid(
+    config: &PathfindingConfig<EdgeType>,
+    algorithm: &PathfindingAlgorithm<Coord, EdgeType>,
+    graph: &AdjacencyList<Coord, EdgeType>,
+    start: Coord,
+    end: Coord,
+    waypoints: &[Coord],
+) -> GridSearch {
+    if waypoints.is_empty() {
+        let result = algorithm.find_path(graph, start, end, config.heuristic).0;
+        return (result, vec![]);
+    }
+    let waypoints = if config.optimize_waypoint_order {
+        optimal_waypoint_order(graph, start, waypoints, end)
     } else {
-        for step in steps.iter().rev() {
-            for (vertex, state) in step.states.iter() {
-                // Because we are going backwards in steps, we use the "previous" vertex states
-                let state = match *state {
-                    VertexState::NewVisited => VertexState::NotVisited,
-                    VertexState::NotVisited => VertexState::Visited,
-                    VertexState::Visited => VertexState::NewVisited,
-                };
-                graph.insert(*vertex, state);
-            }
-        }
+        waypoints.to_vec()
+    };
+    let stops = once(start)
+        .chain(waypoints)
+        .chain(once(end))
+        .collect::<Vec<Coord>>();
+    algorithm.find_path_through(graph, &stops, config.heuristic)
+}
+
+/// Generates the grid graph with the walls left out and the terrain weights applied.
+fn generate_terrain_graph<E: Edge>(
+    config: &PathfindingConfig<E>,
+    walls: &BTreeSet<Coord>,
+    terrain: &BTreeMap<Coord, Terrain>,
+) -> AdjacencyList<Coord, E> {
+    let weights = terrain
+        .iter()
+        .map(|(coord, terrain)| (*coord, config.terrain_weight(*terrain)))
+        .collect();
+    if config.hex_grid {
+        return generate_hex_graph(config.graph_width, config.graph_height, walls, &weights);
     }
+    generate_graph(
+        config.graph_width,
+        config.graph_height,
+        config.move_diagonally,
+        config.corner_cutting,
+        walls,
+        &weights,
+    )
 }
 
 fn generate_maze<E: Edge>(
     config: &PathfindingConfig<E>,
     start: Coord,
     end: Coord,
+    waypoints: &[Coord],
 ) -> MazeGenerationResult {
     // Generate actual maze
-    let mut maze = recursive_division(
+    let generate = *get_maze_generators()
+        .get(config.maze_generator.as_str())
+        .unwrap_or(&(recursive_division as MazeGenerationFunc));
+    let mut maze = generate(
         config.graph_width,
         config.graph_height,
         vec![MazeGenerationStep::new(BTreeSet::new())], // Empty initial step
     );
 
-    // Remove walls around start and end cells to make sure the path isn't blocked.
+    // Remove walls around start, end and waypoint cells to make sure the path isn't blocked.
 
-    maze.walls.remove(&start);
-    maze.walls.remove(&end);
+    for cell in [start, end].iter().chain(waypoints) {
+        maze.walls.remove(cell);
 
-    for neighbor in start.adjacent(config.move_diagonally) {
-        maze.walls.remove(&neighbor);
-    }
-
-    for neighbor in end.adjacent(config.move_diagonally) {
-        maze.walls.remove(&neighbor);
+        for neighbor in config.adjacent(*cell) {
+            maze.walls.remove(&neighbor);
+        }
     }
 
     // I count removing the walls as a step here even though it's not really a part of the actual maze generation
@@ -0,0 +1,122 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use diagram::ElementState;
+use randomized::randomized_algorithms::*;
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+pub fn get_randomized_algorithms() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "reservoir-sampling",
+            VisualizerInfo::new("Reservoir sampling", || {
+                Box::new(ReservoirSampling::new(20, 5))
+            })
+            .with_legend(&[
+                (
+                    ElementState::Active,
+                    "Arriving item, the slot that it was put in",
+                ),
+                (
+                    ElementState::Selected,
+                    "Item in the reservoir, sampled in the latest run",
+                ),
+                (ElementState::Rejected, "Skipped or replaced item"),
+                (ElementState::Frontier, "Expected count of a uniform sample"),
+            ])
+            .with_tags(&["randomized", "streaming", "O(n)"]),
+        ),
+        (
+            "shuffling",
+            VisualizerInfo::new("Shuffling", || Box::new(Shuffling::new(3)))
+                .with_legend(&[
+                    (ElementState::Active, "Swapped items"),
+                    (ElementState::Selected, "Item at its final position"),
+                ])
+                .with_tags(&["randomized", "permutations", "O(n)"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum RandomizedRoute {
+    #[at("/randomized")]
+    Randomized,
+    #[at("/randomized/:algorithm")]
+    RandomizedAlgorithm { algorithm: String },
+}
+
+pub fn switch_randomized(route: RandomizedRoute) -> Html {
+    match route {
+        RandomizedRoute::Randomized => html! {
+            <Redirect<RandomizedRoute> to={RandomizedRoute::RandomizedAlgorithm { algorithm: "reservoir-sampling".to_string()} } />
+        },
+        RandomizedRoute::RandomizedAlgorithm { algorithm } => {
+            if get_randomized_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <RandomizedPage {algorithm} />
+                }
+            } else {
+                html! {
+                    <Randomized404Page {algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct RandomizedPageProps {
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn RandomizedPage(props: &RandomizedPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let algorithms = get_randomized_algorithms();
+    let names = algorithms
+        .values()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect::<Vec<String>>();
+    let algorithm = algorithms[props.algorithm.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&RandomizedRoute::RandomizedAlgorithm {
+            algorithm: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="Randomized"
+            section="Randomized algorithms"
+            select_title="Algorithm"
+            {names}
+            visualizer={algorithm}
+            {on_select}
+            readme_directory="randomized_algorithms"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct Randomized404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn Randomized404Page(props: &Randomized404PageProps) -> Html {
+    use_title("404 - Randomized algorithms".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<RandomizedRoute> to={RandomizedRoute::Randomized}>
+                { "Back to randomized algorithms" }
+            </Link<RandomizedRoute>>
+        </>
+    }
+}
@@ -0,0 +1,143 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use diagram::ElementState;
+use searching::{searching_algorithms, ArraySearch};
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+/// The array that every algorithm starts with, so that their probes can be compared.
+const VALUES: [i64; 16] = [3, 8, 12, 17, 21, 25, 30, 34, 42, 47, 51, 58, 63, 70, 77, 85];
+const LEGEND: &[(ElementState, &str)] = &[
+    (ElementState::Active, "Probed"),
+    (ElementState::Selected, "Found"),
+    (ElementState::Rejected, "Discarded"),
+];
+
+pub fn get_searching_algorithms() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "binary-search",
+            VisualizerInfo::new("Binary search", || {
+                Box::new(ArraySearch::new(
+                    searching_algorithms::binary_search,
+                    &VALUES,
+                ))
+            })
+            .with_legend(LEGEND)
+            .with_tags(&["sorted", "divide & conquer", "O(log n)"]),
+        ),
+        (
+            "exponential-search",
+            VisualizerInfo::new("Exponential search", || {
+                Box::new(ArraySearch::new(
+                    searching_algorithms::exponential_search,
+                    &VALUES,
+                ))
+            })
+            .with_legend(LEGEND)
+            .with_tags(&["sorted", "unbounded", "O(log n)"]),
+        ),
+        (
+            "interpolation-search",
+            VisualizerInfo::new("Interpolation search", || {
+                Box::new(ArraySearch::new(
+                    searching_algorithms::interpolation_search,
+                    &VALUES,
+                ))
+            })
+            .with_legend(LEGEND)
+            .with_tags(&["sorted", "O(log log n) average"]),
+        ),
+        (
+            "jump-search",
+            VisualizerInfo::new("Jump search", || {
+                Box::new(ArraySearch::new(searching_algorithms::jump_search, &VALUES))
+            })
+            .with_legend(LEGEND)
+            .with_tags(&["sorted", "O(√n)"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum SearchingRoute {
+    #[at("/searching")]
+    Searching,
+    #[at("/searching/:algorithm")]
+    SearchingAlgorithm { algorithm: String },
+}
+
+pub fn switch_searching(route: SearchingRoute) -> Html {
+    match route {
+        SearchingRoute::Searching => html! {
+            <Redirect<SearchingRoute> to={SearchingRoute::SearchingAlgorithm { algorithm: "binary-search".to_string()} } />
+        },
+        SearchingRoute::SearchingAlgorithm { algorithm } => {
+            if get_searching_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <SearchingPage {algorithm} />
+                }
+            } else {
+                html! {
+                    <Searching404Page {algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct SearchingPageProps {
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn SearchingPage(props: &SearchingPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let algorithms = get_searching_algorithms();
+    let names = algorithms
+        .values()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect::<Vec<String>>();
+    let algorithm = algorithms[props.algorithm.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&SearchingRoute::SearchingAlgorithm {
+            algorithm: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="Searching"
+            section="Searching"
+            select_title="Algorithm"
+            {names}
+            visualizer={algorithm}
+            {on_select}
+            readme_directory="searching_algorithms"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct Searching404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn Searching404Page(props: &Searching404PageProps) -> Html {
+    use_title("404 - Searching algorithms".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<SearchingRoute> to={SearchingRoute::Searching}>
+                { "Back to searching algorithms" }
+            </Link<SearchingRoute>>
+        </>
+    }
+}
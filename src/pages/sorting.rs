@@ -24,6 +24,8 @@ type SortSteps = Vec<Vec<SortCommand<u32>>>;
 #[derive(Clone)]
 pub struct SortingAlgorithm {
     pub name: String,
+    /// What the algorithm can be found with in the catalog of the home page, like its time complexity or technique.
+    pub tags: Vec<&'static str>,
     sort: fn(&mut Vec<u32>, &mut SortSteps),
 }
 
@@ -31,10 +33,15 @@ impl SortingAlgorithm {
     fn new(name: &str, sort: fn(&mut Vec<u32>, &mut SortSteps)) -> Self {
         Self {
             name: name.to_string(),
+            tags: vec![],
             sort,
         }
     }
-    fn sort(&self, input: Rc<RefCell<Vec<u32>>>) -> SortResult<u32> {
+    fn with_tags(mut self, tags: &[&'static str]) -> Self {
+        self.tags = tags.to_vec();
+        self
+    }
+    pub fn sort(&self, input: Rc<RefCell<Vec<u32>>>) -> SortResult<u32> {
         run_sort(input, self.sort)
     }
 }
@@ -43,6 +50,7 @@ impl Default for SortingAlgorithm {
     fn default() -> Self {
         Self {
             name: String::from("Bubble sort"),
+            tags: vec![],
             sort: bubble_sort,
         }
     }
@@ -61,21 +69,53 @@ pub fn get_sorting_algorithms() -> BTreeMap<&'static str, SortingAlgorithm> {
     BTreeMap::from([
         (
             "bubble-sort",
-            SortingAlgorithm::new("Bubble sort", bubble_sort),
+            SortingAlgorithm::new("Bubble sort", bubble_sort).with_tags(&[
+                "O(n²)",
+                "comparison",
+                "stable",
+                "in-place",
+            ]),
         ),
         (
             "insertion-sort",
-            SortingAlgorithm::new("Insertion sort", insertion_sort),
+            SortingAlgorithm::new("Insertion sort", insertion_sort).with_tags(&[
+                "O(n²)",
+                "comparison",
+                "stable",
+                "in-place",
+            ]),
         ),
         (
             "merge-sort",
-            SortingAlgorithm::new("Merge sort", merge_sort),
+            SortingAlgorithm::new("Merge sort", merge_sort).with_tags(&[
+                "O(n log n)",
+                "comparison",
+                "stable",
+                "divide & conquer",
+            ]),
+        ),
+        (
+            "heapsort",
+            SortingAlgorithm::new("Heapsort", heapsort).with_tags(&[
+                "O(n log n)",
+                "comparison",
+                "in-place",
+                "heap",
+            ]),
+        ),
+        (
+            "quicksort",
+            SortingAlgorithm::new("Quicksort", quicksort).with_tags(&[
+                "O(n log n)",
+                "comparison",
+                "in-place",
+                "divide & conquer",
+            ]),
         ),
-        ("heapsort", SortingAlgorithm::new("Heapsort", heapsort)),
-        ("quicksort", SortingAlgorithm::new("Quicksort", quicksort)),
         (
             "bucket-sort",
-            SortingAlgorithm::new("Bucket sort", bucket_sort),
+            SortingAlgorithm::new("Bucket sort", bucket_sort)
+                .with_tags(&["O(n + k)", "distribution"]),
         ),
     ])
 }
@@ -111,7 +151,6 @@ pub fn switch_sorting(route: SortingRoute) -> Html {
 pub struct SortConfig {
     pub input_len: usize,
     pub sorting_algorithm: SortingAlgorithm,
-    pub audio_enabled: bool,
     /// How long the playback of steps should take in seconds.
     pub playback_time: f32,
     pub audio_config: AudioConfig,
@@ -121,7 +160,6 @@ impl Default for SortConfig {
         Self {
             input_len: 100,
             sorting_algorithm: SortingAlgorithm::default(),
-            audio_enabled: true,
             playback_time: 10.0,
             audio_config: AudioConfig::default(),
         }
@@ -326,7 +364,7 @@ pub fn SortingAlgorithmsPage(props: &SortingAlgorithmsPageProps) -> Html {
                         playback_time={config.playback_time}
                     />
 
-                    <span class="step-info">
+                    <span class="step-info" role="status" aria-live="polite">
                         <label for="stepSlider">
                             {
                                 if *active_step_index == 0 {
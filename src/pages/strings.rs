@@ -0,0 +1,269 @@
+use crate::components::diagram::{VisualizerInfo, VisualizerPage};
+use diagram::ElementState;
+use std::collections::BTreeMap;
+use strings::string_algorithms::*;
+use yew::prelude::*;
+use yew_hooks::use_title;
+use yew_router::prelude::*;
+
+pub fn get_string_algorithms() -> BTreeMap<&'static str, VisualizerInfo> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        (
+            "aho-corasick",
+            VisualizerInfo::new("Aho-Corasick", || {
+                Box::new(AhoCorasick::new(&["he", "she", "his", "hers"], "ushers"))
+            })
+            .with_legend(&[
+                (
+                    ElementState::Active,
+                    "Current node, transition or character",
+                ),
+                (
+                    ElementState::Frontier,
+                    "Shared prefix or followed failure links",
+                ),
+                (ElementState::Selected, "New node, link target or match"),
+                (ElementState::Rejected, "No edge for the character"),
+            ])
+            .with_tags(&["pattern matching", "automaton", "trie", "O(n + m + z)"]),
+        ),
+        (
+            "boyer-moore",
+            VisualizerInfo::new("Boyer-Moore", || {
+                Box::new(BoyerMoore::new("HERE IS A SIMPLE EXAMPLE", "EXAMPLE"))
+            })
+            .with_legend(&[
+                (
+                    ElementState::Active,
+                    "Used table entry, copy of the suffix or jump",
+                ),
+                (ElementState::Frontier, "Matched suffix"),
+                (
+                    ElementState::Selected,
+                    "Matching character, computed entry or match",
+                ),
+                (ElementState::Rejected, "Mismatch"),
+            ])
+            .with_tags(&["pattern matching", "skip tables", "O(n / m) best"]),
+        ),
+        (
+            "burrows-wheeler",
+            VisualizerInfo::new("Burrows-Wheeler", || {
+                Box::new(BurrowsWheeler::new("banana", "Insertion sort"))
+            })
+            .with_legend(&[
+                (
+                    ElementState::Active,
+                    "Swapped rotations, row being followed",
+                ),
+                (ElementState::Frontier, "First column F, next row"),
+                (
+                    ElementState::Selected,
+                    "Last column L, original text, decoded character",
+                ),
+            ])
+            .with_tags(&["compression", "transform", "sorting"]),
+        ),
+        (
+            "edit-distance",
+            VisualizerInfo::new("Edit distance", || {
+                Box::new(EditDistance::new("kitten", "sitting"))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Chosen neighbor or traced character"),
+                (ElementState::Frontier, "Neighbor"),
+                (
+                    ElementState::Selected,
+                    "Computed cell, same characters or optimal path",
+                ),
+                (ElementState::Rejected, "Different characters or edit"),
+            ])
+            .with_tags(&["dynamic programming", "O(nm)"]),
+        ),
+        (
+            "kmp",
+            VisualizerInfo::new("KMP", || {
+                Box::new(Kmp::new("ABABDABACDABABCABAB", "ABABCABAB"))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Used border"),
+                (ElementState::Frontier, "Matched so far"),
+                (
+                    ElementState::Selected,
+                    "Matching characters, computed value or match",
+                ),
+                (ElementState::Rejected, "Mismatch"),
+            ])
+            .with_tags(&["pattern matching", "failure function", "O(n + m)"]),
+        ),
+        (
+            "longest-common-subsequence",
+            VisualizerInfo::new("Longest common subsequence", || {
+                Box::new(LongestCommonSubsequence::new("ABCBDAB", "BDCABA"))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Chosen neighbor"),
+                (ElementState::Frontier, "Compared neighbor"),
+                (
+                    ElementState::Selected,
+                    "Computed cell, same characters or subsequence",
+                ),
+                (ElementState::Rejected, "Different characters"),
+            ])
+            .with_tags(&["dynamic programming", "O(nm)"]),
+        ),
+        (
+            "lz77",
+            VisualizerInfo::new("LZ77", || Box::new(Lz77::new("aacaacabcabaaac", 6, 4)))
+                .with_legend(&[
+                    (ElementState::Active, "Search window, source of a copy"),
+                    (ElementState::Frontier, "Look-ahead buffer"),
+                    (ElementState::Selected, "Copied characters, emitted triple"),
+                    (ElementState::Conflict, "Literal"),
+                ])
+                .with_tags(&["compression", "sliding window"]),
+        ),
+        (
+            "manacher",
+            VisualizerInfo::new("Manacher", || Box::new(Manacher::new("abaxabaxabb")))
+                .with_legend(&[
+                    (
+                        ElementState::Active,
+                        "Palindrome reaching furthest right, mirrored radius",
+                    ),
+                    (ElementState::Frontier, "Reused from the mirror"),
+                    (ElementState::Selected, "Palindrome around the center"),
+                    (ElementState::Rejected, "Where the expansion stopped"),
+                ])
+                .with_tags(&["palindromes", "O(n)"]),
+        ),
+        (
+            "rabin-karp",
+            VisualizerInfo::new("Rabin-Karp", || {
+                Box::new(RabinKarp::new("2359023141526739921", "31415", 13))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Same hash, being checked"),
+                (ElementState::Frontier, "Current window"),
+                (
+                    ElementState::Selected,
+                    "Verified match or matching character",
+                ),
+                (ElementState::Rejected, "Different hash or mismatch"),
+                (ElementState::Conflict, "Spurious hit"),
+            ])
+            .with_tags(&["pattern matching", "hashing", "O(n + m) average"]),
+        ),
+        (
+            "suffix-array",
+            VisualizerInfo::new("Suffix array", || {
+                Box::new(SuffixArray::new("banana", true))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Sorted prefix or compared suffixes"),
+                (ElementState::Frontier, "Next block or known common prefix"),
+                (ElementState::Selected, "Common prefix or computed LCP"),
+                (ElementState::Rejected, "First different character"),
+            ])
+            .with_tags(&["sorting", "prefix doubling", "O(n log² n)"]),
+        ),
+        (
+            "z-algorithm",
+            VisualizerInfo::new("Z-algorithm", || {
+                Box::new(ZAlgorithm::new("AABXAABXCAABXAAB", "AABX"))
+            })
+            .with_legend(&[
+                (ElementState::Active, "Window or mirrored value"),
+                (ElementState::Frontier, "Known to match from the window"),
+                (
+                    ElementState::Selected,
+                    "Matching character, computed value or match",
+                ),
+                (ElementState::Rejected, "Mismatch"),
+            ])
+            .with_tags(&["pattern matching", "O(n + m)"]),
+        ),
+    ])
+}
+
+#[derive(Clone, Debug, Routable, PartialEq, Eq)]
+pub enum StringsRoute {
+    #[at("/strings")]
+    Strings,
+    #[at("/strings/:algorithm")]
+    StringAlgorithm { algorithm: String },
+}
+
+pub fn switch_strings(route: StringsRoute) -> Html {
+    match route {
+        StringsRoute::Strings => html! {
+            <Redirect<StringsRoute> to={StringsRoute::StringAlgorithm { algorithm: "kmp".to_string()} } />
+        },
+        StringsRoute::StringAlgorithm { algorithm } => {
+            if get_string_algorithms().contains_key(algorithm.as_str()) {
+                html! {
+                    <StringsPage {algorithm} />
+                }
+            } else {
+                html! {
+                    <Strings404Page {algorithm} />
+                }
+            }
+        }
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+pub struct StringsPageProps {
+    pub algorithm: String,
+}
+
+#[function_component]
+pub fn StringsPage(props: &StringsPageProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let algorithms = get_string_algorithms();
+    let names = algorithms
+        .values()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect::<Vec<String>>();
+    let algorithm = algorithms[props.algorithm.as_str()].clone();
+
+    let on_select = Callback::from(move |name: String| {
+        navigator.push(&StringsRoute::StringAlgorithm {
+            algorithm: name.replace(' ', "-").to_lowercase(),
+        });
+    });
+
+    html! {
+        <VisualizerPage
+            id="Strings"
+            section="String algorithms"
+            select_title="Algorithm"
+            {names}
+            visualizer={algorithm}
+            {on_select}
+            readme_directory="string_algorithms"
+        />
+    }
+}
+
+#[derive(Properties, Clone, PartialEq, Eq)]
+struct Strings404PageProps {
+    algorithm: String,
+}
+
+#[function_component]
+fn Strings404Page(props: &Strings404PageProps) -> Html {
+    use_title("404 - String algorithms".to_string());
+
+    html! {
+        <>
+            <h1>{ "404" }</h1>
+            <p>{ format!("The algorithm \"{}\" was not found.", props.algorithm) }</p>
+            <Link<StringsRoute> to={StringsRoute::Strings}>
+                { "Back to string algorithms" }
+            </Link<StringsRoute>>
+        </>
+    }
+}
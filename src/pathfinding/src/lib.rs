@@ -3,12 +3,13 @@
 pub mod graph;
 pub mod maze_generation;
 pub mod pathfinding_algorithms;
+pub mod waypoints;
 
 use core::fmt;
 use graph::*;
 use num_traits::Float;
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::{Debug, Display},
     hash::Hash,
     ops::Add,
@@ -18,6 +19,29 @@ use std::{
 pub trait Distance {
     /// Get the distance from a to b.
     fn distance<T: Float>(&self, from: Self) -> T;
+    /// Estimate the distance from a to b with a given heuristic. Uses the actual distance by default.
+    fn estimate<T: Float>(&self, to: Self, _heuristic: Heuristic) -> T
+    where
+        Self: Sized,
+    {
+        self.distance(to)
+    }
+}
+
+/// Functions that estimate the cost of the cheapest path between two vertices.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Heuristic {
+    /// The sum of the horizontal and vertical distances. Exact for grids without diagonal movement.
+    Manhattan,
+    /// The straight-line distance.
+    #[default]
+    Euclidean,
+    /// The larger of the horizontal and vertical distances, i.e. diagonal moves cost as much as straight ones.
+    Chebyshev,
+    /// Like Chebyshev, but diagonal moves cost sqrt(2). Exact for grids with diagonal movement.
+    Octile,
+    /// The number of moves between two cells of a hexagonal grid. See [`Coord::to_axial`].
+    Hex,
 }
 
 pub trait Line<T: Distance> {
@@ -139,6 +163,25 @@ impl Coord {
     pub fn new(x: isize, y: isize) -> Self {
         Self { x, y }
     }
+    /// Converts the cell of a hexagonal grid to axial coordinates `(q, r)`.
+    ///
+    /// The cells of hexagonal grids are stored in offset coordinates where odd rows are shifted right by half a cell.
+    /// This keeps the grid rectangular, but axial coordinates make distances and neighbors much simpler to compute.
+    pub fn to_axial(self) -> (isize, isize) {
+        (self.x - (self.y - (self.y & 1)) / 2, self.y)
+    }
+    /// Converts axial coordinates `(q, r)` of a hexagonal grid back to offset coordinates. See [`Coord::to_axial`].
+    pub fn from_axial(q: isize, r: isize) -> Self {
+        Self::new(q + (r - (r & 1)) / 2, r)
+    }
+    /// Gets the six neighbors of the cell on a hexagonal grid.
+    pub fn hex_adjacent(self) -> Vec<Self> {
+        let (q, r) = self.to_axial();
+        [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)]
+            .into_iter()
+            .map(|(dq, dr)| Self::from_axial(q + dq, r + dr))
+            .collect()
+    }
     pub fn adjacent(self, diagonals: bool) -> Vec<Self> {
         let Self { x, y } = self;
         if diagonals {
@@ -184,17 +227,73 @@ impl Distance for Coord {
         let y_diff = from.y - self.y;
         T::from((x_diff.pow(2) as f32 + y_diff.pow(2) as f32).sqrt()).unwrap()
     }
+    fn estimate<T: Float>(&self, to: Self, heuristic: Heuristic) -> T {
+        let x_diff = (to.x - self.x).abs() as f32;
+        let y_diff = (to.y - self.y).abs() as f32;
+        let estimate = match heuristic {
+            Heuristic::Manhattan => x_diff + y_diff,
+            Heuristic::Euclidean => (x_diff.powi(2) + y_diff.powi(2)).sqrt(),
+            Heuristic::Chebyshev => x_diff.max(y_diff),
+            Heuristic::Octile => x_diff.max(y_diff) + (2.0_f32.sqrt() - 1.0) * x_diff.min(y_diff),
+            Heuristic::Hex => {
+                let ((q_a, r_a), (q_b, r_b)) = (self.to_axial(), to.to_axial());
+                let (dq, dr) = ((q_b - q_a) as f32, (r_b - r_a) as f32);
+                (dq.abs() + dr.abs() + (dq + dr).abs()) / 2.0
+            }
+        };
+        T::from(estimate).unwrap()
+    }
+}
+
+/// A vertex of a graph that isn't a grid, like one drawn in a node graph editor.
+/// Nodes are identified by their `id`, and their `position` is only used by heuristics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Node {
+    pub id: usize,
+    pub position: Coord,
+}
+impl Node {
+    pub fn new(id: usize, position: Coord) -> Self {
+        Self { id, position }
+    }
+}
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+impl Vertex for Node {}
+impl Distance for Node {
+    fn distance<T: Float>(&self, from: Self) -> T {
+        self.position.distance(from.position)
+    }
+    fn estimate<T: Float>(&self, to: Self, heuristic: Heuristic) -> T {
+        self.position.estimate(to.position, heuristic)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum VertexState {
     NotVisited,
+    /// The vertex has a tentative distance and is waiting to be expanded.
+    Queued,
+    /// The vertex was expanded during the current step.
     NewVisited,
     Visited,
+    /// The vertex is a part of the final path.
+    Path,
+    /// Like [`VertexState::Queued`], but for the search that starts from the end vertex in bidirectional searches.
+    QueuedFromEnd,
+    /// Like [`VertexState::NewVisited`], but for the search that starts from the end vertex in bidirectional searches.
+    NewVisitedFromEnd,
+    /// Like [`VertexState::Visited`], but for the search that starts from the end vertex in bidirectional searches.
+    VisitedFromEnd,
+    /// The vertex where the two searches of a bidirectional search meet.
+    Meeting,
 }
 
 pub type PathfindingFunc<V, E> =
-    fn(AdjacencyList<V, E>, V, V, PathfindingSteps<V>) -> PathfindingResult<V, E>;
+    fn(AdjacencyList<V, E>, V, V, Heuristic, PathfindingSteps<V>) -> PathfindingResult<V, E>;
 
 pub type GraphWeightMap<V, E> = BTreeMap<V, E>;
 
@@ -203,10 +302,17 @@ pub fn run_pathfinding<V: Vertex, E: Clone>(
     graph: &AdjacencyList<V, E>,
     start: V,
     end: V,
+    heuristic: Heuristic,
     algorithm: PathfindingFunc<V, E>,
 ) -> (PathfindingResult<V, E>, instant::Duration) {
     let start_time = instant::Instant::now();
-    let res = algorithm(graph.clone(), start, end, PathfindingSteps::new(vec![]));
+    let res = algorithm(
+        graph.clone(),
+        start,
+        end,
+        heuristic,
+        PathfindingSteps::new(vec![]),
+    );
     let duration = start_time.elapsed();
     (res, duration)
 }
@@ -220,15 +326,17 @@ impl<V: Vertex> PathfindingSteps<V> {
         Self { steps }
     }
     pub fn init_step(&mut self) {
-        // Remove the old visited vertices from this step, and turn previously new visited to old visited.
+        // Only keep the vertices that were new visited in the last step, and turn them to old visited.
         if !self.steps.is_empty() {
             let mut last_step = self.steps.last().unwrap().to_owned();
+            last_step.states.retain(|_, state| {
+                matches!(
+                    state,
+                    VertexState::NewVisited | VertexState::NewVisitedFromEnd
+                )
+            });
             self.steps.push(PathfindingStep::new(
-                last_step
-                    .remove_old_visited()
-                    .new_to_old_visited()
-                    .states
-                    .to_owned(),
+                last_step.new_to_old_visited().states.to_owned(),
                 BTreeSet::new(),
             ));
         } else {
@@ -247,6 +355,35 @@ impl<V: Vertex> PathfindingSteps<V> {
             None
         }
     }
+    /// Adds a step for each vertex of the final path, going from the end to the start.
+    pub fn push_path_steps(&mut self, path: &[V]) {
+        for vertex in path.iter().rev() {
+            self.init_step();
+            self.insert_state_to_last_step(*vertex, VertexState::Path);
+        }
+    }
+    /// Splits the steps so that every vertex queued during a step gets a step of its own after the rest of the step.
+    /// This shows the relaxations of an expanded vertex's edges one at a time instead of all at once.
+    pub fn split_relaxations(self) -> Self {
+        let steps = self
+            .steps
+            .into_iter()
+            .flat_map(|step| {
+                let (queued, rest): (BTreeMap<V, VertexState>, BTreeMap<V, VertexState>) =
+                    step.states.into_iter().partition(|(_, state)| {
+                        matches!(state, VertexState::Queued | VertexState::QueuedFromEnd)
+                    });
+                let rest_step = (!rest.is_empty() || queued.is_empty())
+                    .then(|| PathfindingStep::new(rest, step.path));
+                rest_step
+                    .into_iter()
+                    .chain(queued.into_iter().map(|(vertex, state)| {
+                        PathfindingStep::new(BTreeMap::from([(vertex, state)]), BTreeSet::new())
+                    }))
+            })
+            .collect();
+        Self::new(steps)
+    }
     pub fn get_all(self) -> Vec<PathfindingStep<V>> {
         self.steps
     }
@@ -266,15 +403,18 @@ impl<V: Vertex> PathfindingStep<V> {
     }
     pub fn new_to_old_visited(&mut self) -> &mut Self {
         for state in self.states.values_mut() {
-            if *state == VertexState::NewVisited {
-                *state = VertexState::Visited;
+            match *state {
+                VertexState::NewVisited => *state = VertexState::Visited,
+                VertexState::NewVisitedFromEnd => *state = VertexState::VisitedFromEnd,
+                _ => (),
             }
         }
         self
     }
     pub fn remove_old_visited(&mut self) -> &mut Self {
-        self.states
-            .retain(|_, state| *state != VertexState::Visited);
+        self.states.retain(|_, state| {
+            !matches!(state, VertexState::Visited | VertexState::VisitedFromEnd)
+        });
         self
     }
     pub fn get(&self, vertex: V) -> Option<&VertexState> {
@@ -282,13 +422,124 @@ impl<V: Vertex> PathfindingStep<V> {
     }
 }
 
+/// Follows the `parents` of vertices from `end` to `start` and returns the path from `start` to `end`.
+/// Returns an empty path if `end` can't be reached.
+pub fn path_from_parents<V: Vertex>(parents: &HashMap<V, V>, start: V, end: V) -> Vec<V> {
+    let mut path = vec![end];
+    let mut curr = end;
+
+    while curr != start {
+        match parents.get(&curr) {
+            Some(parent) => {
+                curr = *parent;
+                path.push(curr);
+            }
+            None => return vec![],
+        }
+    }
+
+    path.reverse();
+    path
+}
+
+/// Joins the paths of a bidirectional search that meet at `meeting` into a path from `start` to `end`.
+/// Returns an empty path if either of the paths can't be reconstructed.
+pub fn path_through_meeting<V: Vertex>(
+    forward_parents: &HashMap<V, V>,
+    backward_parents: &HashMap<V, V>,
+    start: V,
+    end: V,
+    meeting: V,
+) -> Vec<V> {
+    let mut path = path_from_parents(forward_parents, start, meeting);
+    let backward_path = path_from_parents(backward_parents, end, meeting);
+
+    if path.is_empty() || backward_path.is_empty() {
+        return vec![];
+    }
+
+    // The meeting vertex is already the last vertex of the forward path
+    path.extend(backward_path.into_iter().rev().skip(1));
+    path
+}
+
+/// Rules for moving diagonally past the corners of walls.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CornerCutting {
+    /// Diagonal moves are always allowed, even between two walls.
+    Always,
+    /// Diagonal moves are allowed if at most one of the two cells next to both cells is a wall.
+    OneWall,
+    /// Diagonal moves are only allowed if neither of the two cells next to both cells is a wall.
+    #[default]
+    Never,
+}
+impl CornerCutting {
+    /// Checks if a diagonal move from `from` to `to` is allowed.
+    pub fn allows(&self, from: Coord, to: Coord, walls: &BTreeSet<Coord>) -> bool {
+        let blocked = [Coord::new(from.x, to.y), Coord::new(to.x, from.y)]
+            .iter()
+            .filter(|corner| walls.contains(corner))
+            .count();
+        match self {
+            CornerCutting::Always => true,
+            CornerCutting::OneWall => blocked <= 1,
+            CornerCutting::Never => blocked == 0,
+        }
+    }
+}
+
+/// Generates a hexagonal grid graph where `walls` are left out. See [`Coord::to_axial`] for how the cells are laid out.
+/// The cost of moving between two cells is the average of their `weights`. Cells without a weight have a weight of 1.
+pub fn generate_hex_graph<E: Edge>(
+    width: usize,
+    height: usize,
+    walls: &BTreeSet<Coord>,
+    weights: &BTreeMap<Coord, E>,
+) -> AdjacencyList<Coord, E> {
+    let mut graph = AdjacencyList::<Coord, E>::new(BTreeMap::new());
+    let weight = |coord: &Coord| *weights.get(coord).unwrap_or(&E::one());
+
+    for y in 0..height as isize {
+        for x in 0..width as isize {
+            let vertex = Coord::new(x, y);
+            if walls.contains(&vertex) {
+                continue;
+            }
+            let mut neighbors = BTreeMap::<Coord, E>::new();
+            for coord in vertex.hex_adjacent() {
+                if walls.contains(&coord) {
+                    continue;
+                }
+                if coord.x >= 0
+                    && coord.x < width as isize
+                    && coord.y >= 0
+                    && coord.y < height as isize
+                {
+                    let average_weight = (weight(&vertex) + weight(&coord)) / E::from(2).unwrap();
+                    neighbors.insert(coord, average_weight);
+                }
+            }
+            graph.add_vertex_with_undirected_edges(vertex, neighbors);
+        }
+    }
+
+    graph
+}
+
+/// Generates a grid graph where `walls` are left out.
+/// The cost of moving between two cells is the distance between them multiplied by the average of their `weights`.
+/// Cells without a weight have a weight of 1.
 pub fn generate_graph<E: Edge>(
     width: usize,
     height: usize,
     diagonals: bool,
+    corner_cutting: CornerCutting,
     walls: &BTreeSet<Coord>,
+    weights: &BTreeMap<Coord, E>,
 ) -> AdjacencyList<Coord, E> {
     let mut graph = AdjacencyList::<Coord, E>::new(BTreeMap::new());
+    let weight = |coord: &Coord| *weights.get(coord).unwrap_or(&E::one());
 
     for y in 0..height as isize {
         for x in 0..width as isize {
@@ -308,12 +559,13 @@ pub fn generate_graph<E: Edge>(
                 {
                     let a_diff = (coord.x - vertex.x).abs();
                     let b_diff = (coord.y - vertex.y).abs();
+                    let average_weight = (weight(&vertex) + weight(&coord)) / E::from(2).unwrap();
                     if a_diff == 0 || b_diff == 0 {
                         // Horizontal or vertical costs 1
-                        neighbors.insert(coord, E::from(1).unwrap());
-                    } else {
+                        neighbors.insert(coord, average_weight);
+                    } else if corner_cutting.allows(vertex, coord, walls) {
                         // Diagonal costs sqrt(2)
-                        neighbors.insert(coord, E::from(2.0_f32.sqrt()).unwrap());
+                        neighbors.insert(coord, E::from(2.0_f32.sqrt()).unwrap() * average_weight);
                     }
                 }
             }
@@ -324,14 +576,43 @@ pub fn generate_graph<E: Edge>(
     graph
 }
 
+/// The costs that a heuristic search assigns to a vertex.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VertexScores<E> {
+    /// The cost of the cheapest known path from the start vertex to this vertex.
+    pub g: E,
+    /// The estimated cost of the cheapest path from this vertex to the target vertex.
+    pub h: E,
+}
+impl<E: Edge> VertexScores<E> {
+    pub fn new(g: E, h: E) -> Self {
+        Self { g, h }
+    }
+    /// The estimated cost of the cheapest path from the start vertex to the target vertex through this vertex.
+    pub fn f(&self) -> E {
+        self.g + self.h
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct PathfindingResult<V: Vertex, E> {
     pub steps: PathfindingSteps<V>,
     pub path: Vec<V>,
     pub costs: GraphWeightMap<V, E>,
+    /// The f, g and h scores of vertices for algorithms that use a heuristic.
+    pub scores: BTreeMap<V, VertexScores<E>>,
 }
 impl<V: Vertex, E> PathfindingResult<V, E> {
     pub fn new(steps: PathfindingSteps<V>, path: Vec<V>, costs: GraphWeightMap<V, E>) -> Self {
-        Self { steps, path, costs }
+        Self {
+            steps,
+            path,
+            costs,
+            scores: BTreeMap::new(),
+        }
+    }
+    pub fn with_scores(mut self, scores: BTreeMap<V, VertexScores<E>>) -> Self {
+        self.scores = scores;
+        self
     }
 }
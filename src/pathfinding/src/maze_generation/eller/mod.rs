@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::Coord;
+
+use super::{between, filled_grid, MazeGenerationResult, MazeGenerationStep};
+
+/// Generates a maze with Eller's algorithm, which carves the maze one row at a time.
+/// Only the sets of the cells on the current row need to be stored.
+pub fn eller(
+    width: usize,
+    height: usize,
+    mut steps: Vec<MazeGenerationStep>,
+) -> MazeGenerationResult {
+    let mut walls = filled_grid(width, height);
+    let mut rng = rand::thread_rng();
+
+    let columns = (1..width as isize - 1).step_by(2).collect::<Vec<isize>>();
+    let rows = (1..height as isize - 1).step_by(2).collect::<Vec<isize>>();
+
+    // The sets of the cells on the current row by column
+    let mut sets = BTreeMap::<isize, usize>::new();
+    let mut next_set = 0;
+
+    for (row_i, y) in rows.iter().enumerate() {
+        let is_last_row = row_i == rows.len() - 1;
+
+        // Cells that weren't connected to the row above get their own sets
+        for x in columns.iter() {
+            sets.entry(*x).or_insert_with(|| {
+                next_set += 1;
+                next_set
+            });
+            walls.remove(&Coord::new(*x, *y));
+        }
+        steps.push(MazeGenerationStep::new(walls.clone()));
+
+        // Randomly join neighboring cells that are in different sets. On the last row, all of them are joined.
+        for pair in columns.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let (set_a, set_b) = (sets[&a], sets[&b]);
+
+            if set_a != set_b && (is_last_row || rng.gen_bool(0.5)) {
+                for set in sets.values_mut() {
+                    if *set == set_b {
+                        *set = set_a;
+                    }
+                }
+                walls.remove(&between(Coord::new(a, *y), Coord::new(b, *y)));
+                steps.push(MazeGenerationStep::new(walls.clone()));
+            }
+        }
+
+        if is_last_row {
+            break;
+        }
+
+        // Each set continues to the next row through at least one random cell
+        let mut columns_by_set = BTreeMap::<usize, Vec<isize>>::new();
+        for (x, set) in sets.iter() {
+            columns_by_set.entry(*set).or_default().push(*x);
+        }
+
+        let mut next_sets = BTreeMap::<isize, usize>::new();
+        for (set, mut set_columns) in columns_by_set {
+            set_columns.shuffle(&mut rng);
+            let count = rng.gen_range(1..=set_columns.len());
+
+            for x in set_columns.into_iter().take(count) {
+                walls.remove(&Coord::new(x, y + 1));
+                next_sets.insert(x, set);
+            }
+        }
+        sets = next_sets;
+        steps.push(MazeGenerationStep::new(walls.clone()));
+    }
+
+    MazeGenerationResult::new(steps, walls)
+}
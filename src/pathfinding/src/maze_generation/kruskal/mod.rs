@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use rand::seq::SliceRandom;
+
+use crate::Coord;
+
+use super::{between, filled_grid, maze_cells, MazeGenerationResult, MazeGenerationStep};
+
+/// Generates a maze with a randomized version of Kruskal's algorithm.
+/// The walls between cells are removed in a random order, unless the cells are already connected.
+pub fn kruskal(
+    width: usize,
+    height: usize,
+    mut steps: Vec<MazeGenerationStep>,
+) -> MazeGenerationResult {
+    let mut walls = filled_grid(width, height);
+    let cells = maze_cells(width, height);
+    let mut sets = DisjointSet::new(&cells);
+
+    // Every cell starts as its own set, surrounded by walls
+    for cell in cells.iter() {
+        walls.remove(cell);
+    }
+    steps.push(MazeGenerationStep::new(walls.clone()));
+
+    // The pairs of cells that are next to each other horizontally or vertically
+    let mut edges = cells
+        .iter()
+        .flat_map(|cell| {
+            [Coord::new(2, 0), Coord::new(0, 2)]
+                .into_iter()
+                .map(move |offset| (*cell, *cell + offset))
+        })
+        .filter(|(_, neighbor)| sets.contains(neighbor))
+        .collect::<Vec<(Coord, Coord)>>();
+    edges.shuffle(&mut rand::thread_rng());
+
+    for (a, b) in edges {
+        // Only join cells that aren't connected yet so that the maze has no loops
+        if sets.union(a, b) {
+            walls.remove(&between(a, b));
+
+            steps.push(MazeGenerationStep::new(walls.clone()));
+        }
+    }
+
+    MazeGenerationResult::new(steps, walls)
+}
+
+/// A union-find data structure for keeping track of which cells are connected.
+struct DisjointSet {
+    parents: HashMap<Coord, Coord>,
+    /// The upper bounds for the heights of the trees, used to keep the trees shallow
+    ranks: HashMap<Coord, usize>,
+}
+impl DisjointSet {
+    fn new(cells: &[Coord]) -> Self {
+        Self {
+            parents: cells.iter().map(|cell| (*cell, *cell)).collect(),
+            ranks: cells.iter().map(|cell| (*cell, 0)).collect(),
+        }
+    }
+    fn contains(&self, cell: &Coord) -> bool {
+        self.parents.contains_key(cell)
+    }
+    /// Finds the root of the set that the cell is in, making the cells on the way point to the root directly.
+    fn find(&mut self, cell: Coord) -> Coord {
+        let parent = self.parents[&cell];
+        if parent == cell {
+            return cell;
+        }
+        let root = self.find(parent);
+        self.parents.insert(cell, root);
+        root
+    }
+    /// Joins the sets of `a` and `b`. Returns false if they were already in the same set.
+    fn union(&mut self, a: Coord, b: Coord) -> bool {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+
+        // Attach the shallower tree to the deeper one
+        let (rank_a, rank_b) = (self.ranks[&root_a], self.ranks[&root_b]);
+        if rank_a < rank_b {
+            self.parents.insert(root_a, root_b);
+        } else {
+            self.parents.insert(root_b, root_a);
+            if rank_a == rank_b {
+                self.ranks.insert(root_a, rank_a + 1);
+            }
+        }
+        true
+    }
+}
@@ -1,18 +1,35 @@
+mod eller;
+mod kruskal;
+mod randomized_prim;
 mod recursive_division;
 
 use std::collections::BTreeSet;
 
+pub use eller::eller;
+pub use kruskal::kruskal;
+pub use randomized_prim::randomized_prim;
 pub use recursive_division::recursive_division;
 
 use crate::Coord;
 
+pub type MazeGenerationFunc = fn(usize, usize, Vec<MazeGenerationStep>) -> MazeGenerationResult;
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct MazeGenerationStep {
     pub walls: BTreeSet<Coord>,
+    /// Cells that the generator is about to carve, for generators that grow the maze from a frontier.
+    pub frontier: BTreeSet<Coord>,
 }
 impl MazeGenerationStep {
     pub fn new(walls: BTreeSet<Coord>) -> Self {
-        Self { walls }
+        Self {
+            walls,
+            frontier: BTreeSet::new(),
+        }
+    }
+    pub fn with_frontier(mut self, frontier: BTreeSet<Coord>) -> Self {
+        self.frontier = frontier;
+        self
     }
 }
 
@@ -26,3 +43,37 @@ impl MazeGenerationResult {
         Self { steps, walls }
     }
 }
+
+/// Gets a grid where every cell is a wall.
+/// Generators that carve passages start from this, and the cells of the maze are on odd coordinates.
+pub(crate) fn filled_grid(width: usize, height: usize) -> BTreeSet<Coord> {
+    (0..height as isize)
+        .flat_map(|y| (0..width as isize).map(move |x| Coord::new(x, y)))
+        .collect()
+}
+
+/// Gets the cells of a carved maze, i.e. the cells on odd coordinates that aren't on the edges of the grid.
+pub(crate) fn maze_cells(width: usize, height: usize) -> Vec<Coord> {
+    (1..height as isize - 1)
+        .step_by(2)
+        .flat_map(|y| {
+            (1..width as isize - 1)
+                .step_by(2)
+                .map(move |x| Coord::new(x, y))
+        })
+        .collect()
+}
+
+/// Gets the maze cells that are two cells away from `cell` horizontally or vertically.
+pub(crate) fn maze_neighbors(cell: Coord, width: usize, height: usize) -> Vec<Coord> {
+    [(0, -2), (2, 0), (0, 2), (-2, 0)]
+        .into_iter()
+        .map(|(x, y)| cell + Coord::new(x, y))
+        .filter(|c| c.x > 0 && c.y > 0 && c.x < width as isize - 1 && c.y < height as isize - 1)
+        .collect()
+}
+
+/// Gets the cell between two maze cells.
+pub(crate) fn between(a: Coord, b: Coord) -> Coord {
+    Coord::new((a.x + b.x) / 2, (a.y + b.y) / 2)
+}
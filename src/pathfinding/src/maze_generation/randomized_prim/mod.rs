@@ -0,0 +1,56 @@
+use std::collections::BTreeSet;
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    between, filled_grid, maze_cells, maze_neighbors, MazeGenerationResult, MazeGenerationStep,
+};
+
+/// Generates a maze with a randomized version of Prim's algorithm.
+/// The maze grows from a random cell by carving a random cell of its frontier at each step.
+pub fn randomized_prim(
+    width: usize,
+    height: usize,
+    mut steps: Vec<MazeGenerationStep>,
+) -> MazeGenerationResult {
+    let mut walls = filled_grid(width, height);
+    let mut rng = rand::thread_rng();
+
+    let Some(first) = maze_cells(width, height).choose(&mut rng).copied() else {
+        return MazeGenerationResult::new(steps, walls);
+    };
+
+    walls.remove(&first);
+
+    // A `Vec` for picking random cells and a set for checking if a cell is already in the frontier
+    let mut frontier = maze_neighbors(first, width, height);
+    let mut in_frontier = frontier.iter().copied().collect::<BTreeSet<_>>();
+
+    steps.push(MazeGenerationStep::new(walls.clone()).with_frontier(in_frontier.clone()));
+
+    while !frontier.is_empty() {
+        let cell = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        in_frontier.remove(&cell);
+
+        // Connect the cell to a random cell that is already a part of the maze
+        let carved = maze_neighbors(cell, width, height)
+            .into_iter()
+            .filter(|neighbor| !walls.contains(neighbor))
+            .collect::<Vec<_>>();
+        if let Some(neighbor) = carved.choose(&mut rng) {
+            walls.remove(&between(cell, *neighbor));
+        }
+        walls.remove(&cell);
+
+        // Grow the frontier with the cell's neighbors that haven't been carved yet
+        for neighbor in maze_neighbors(cell, width, height) {
+            if walls.contains(&neighbor) && in_frontier.insert(neighbor) {
+                frontier.push(neighbor);
+            }
+        }
+
+        steps.push(MazeGenerationStep::new(walls.clone()).with_frontier(in_frontier.clone()));
+    }
+
+    MazeGenerationResult::new(steps, walls)
+}
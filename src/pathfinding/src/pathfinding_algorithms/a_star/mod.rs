@@ -1,6 +1,6 @@
 use std::{
     cmp::Ordering,
-    collections::{BTreeMap, BinaryHeap, HashMap},
+    collections::{BTreeMap, BinaryHeap, HashMap, HashSet},
     hash::Hash,
 };
 
@@ -10,11 +10,20 @@ pub fn a_star<V: Vertex, E: Edge>(
     adjacency_list: AdjacencyList<V, E>,
     start: V,
     end: V,
+    heuristic: Heuristic,
     mut steps: PathfindingSteps<V>,
 ) -> PathfindingResult<V, E> {
+    // The heuristics assume that moving a unit of distance costs 1, so they are scaled by the cheapest cost per estimated distance.
+    // Otherwise they would overestimate costs on cheaper edges, and the path wouldn't always be the shortest one.
+    let heuristic_scale = cheapest_cost_per_estimate(&adjacency_list, heuristic);
+    let estimate = |vertex: V| vertex.estimate::<E>(end, heuristic) * heuristic_scale;
+
     // Discovered vertices that may need to be expanded, beginning with source vertex
     let mut open_set = BinaryHeap::new();
-    open_set.push(VertexWithPriority::new(start, start.distance::<E>(end)));
+    open_set.push(VertexWithPriority::new(start, estimate(start)));
+
+    // Vertices that have already been expanded
+    let mut closed_set = HashSet::new();
 
     // Stores the cost values and "parents" of vertices
     let mut cells = HashMap::<V, Cell<V, E>>::new();
@@ -23,31 +32,39 @@ pub fn a_star<V: Vertex, E: Edge>(
     for vertex in adjacency_list.hash_map.keys() {
         cells.insert(
             *vertex,
-            Cell::new(*vertex, None, E::max_value(), vertex.distance::<E>(end)),
+            Cell::new(*vertex, None, E::max_value(), estimate(*vertex)),
         );
     }
 
     // Initialize source vertex costs correctly
-    cells.insert(
-        start,
-        Cell::new(start, None, E::zero(), start.distance::<E>(end)),
-    );
+    cells.insert(start, Cell::new(start, None, E::zero(), estimate(start)));
 
     // At each step we get the vertex with the smallest estimated cost from the `open_set`
     while let Some(curr) = open_set.pop() {
+        // The vertex was already expanded through a cheaper path
+        if !closed_set.insert(curr.vertex) {
+            continue;
+        }
+
+        steps.init_step();
+        steps.insert_state_to_last_step(curr.vertex, VertexState::NewVisited);
+
         // Return when target is found
         if curr.vertex == end {
-            return PathfindingResult::new(
-                steps,
-                reconstruct_path::<V, E>(cells, curr.vertex),
-                BTreeMap::new(),
-            );
+            let path = reconstruct_path::<V, E>(&cells, curr.vertex);
+            steps.push_path_steps(&path);
+            return PathfindingResult::new(steps, path, BTreeMap::new())
+                .with_scores(get_scores(&cells));
         }
 
         if let Some(neighbors) = adjacency_list.get_neighbors(&curr.vertex) {
             let curr_g = cells.get(&curr.vertex).unwrap().g;
 
             for (neighbor, cost) in neighbors.clone() {
+                if closed_set.contains(&neighbor) {
+                    continue;
+                }
+
                 // Distance from start to neighbor
                 let tentative_g_dist = curr_g + cost;
                 let neighbor_cell = cells.get_mut(&neighbor).unwrap();
@@ -56,15 +73,14 @@ pub fn a_star<V: Vertex, E: Edge>(
                 if tentative_g_dist < neighbor_cell.g {
                     // Update neighbor's `parent` and `g` values
                     neighbor_cell.set_parent(Some(curr.vertex));
-                    neighbor_cell.set_g(tentative_g_dist.into());
+                    neighbor_cell.set_g(tentative_g_dist);
 
-                    steps.init_step();
-                    steps.insert_state_to_last_step(neighbor, VertexState::NewVisited);
+                    steps.insert_state_to_last_step(neighbor, VertexState::Queued);
 
                     // Add neighbor to `open_set`
                     open_set.push(VertexWithPriority::new(
                         neighbor_cell.vertex,
-                        (tentative_g_dist.to_owned() + neighbor_cell.h.to_owned()).into(),
+                        tentative_g_dist + neighbor_cell.h,
                     ));
                 }
             }
@@ -72,18 +88,46 @@ pub fn a_star<V: Vertex, E: Edge>(
     }
 
     // `open_set` is empty and target was never reached
-    PathfindingResult::new(steps, vec![], BTreeMap::new())
+    PathfindingResult::new(steps, vec![], BTreeMap::new()).with_scores(get_scores(&cells))
+}
+
+/// Gets the smallest ratio between the cost of an edge and the heuristic's estimate for it, or 1 if there are no edges.
+pub(super) fn cheapest_cost_per_estimate<V: Vertex, E: Edge>(
+    adjacency_list: &AdjacencyList<V, E>,
+    heuristic: Heuristic,
+) -> E {
+    adjacency_list
+        .hash_map
+        .iter()
+        .flat_map(|(vertex, neighbors)| {
+            neighbors
+                .iter()
+                .map(move |(neighbor, cost)| *cost / vertex.estimate::<E>(*neighbor, heuristic))
+        })
+        .reduce(E::min)
+        .unwrap_or_else(E::one)
 }
 
-fn reconstruct_path<V: Vertex, E: Edge>(cells: HashMap<V, Cell<V, E>>, mut curr: V) -> Vec<V> {
+/// Returns the path from the source vertex to `curr`.
+fn reconstruct_path<V: Vertex, E: Edge>(cells: &HashMap<V, Cell<V, E>>, mut curr: V) -> Vec<V> {
     let mut path = vec![curr];
     while let Some(parent) = cells.get(&curr).unwrap().parent {
         curr = parent;
         path.push(curr);
     }
+    path.reverse();
     path
 }
 
+/// Gets the scores of all vertices that have been discovered.
+fn get_scores<V: Vertex, E: Edge>(cells: &HashMap<V, Cell<V, E>>) -> BTreeMap<V, VertexScores<E>> {
+    cells
+        .values()
+        .filter(|cell| cell.g < E::max_value())
+        .map(|cell| (cell.vertex, VertexScores::new(cell.g, cell.h)))
+        .collect()
+}
+
 #[derive(Clone, PartialEq)]
 struct VertexWithPriority<V: Ord + Eq + Hash, E: PartialOrd> {
     vertex: V,
@@ -97,7 +141,7 @@ impl<V: Ord + Eq + Hash, E: PartialOrd> VertexWithPriority<V, E> {
 impl<V: Ord + Eq + Hash, E: PartialOrd> Eq for VertexWithPriority<V, E> {}
 impl<V: Ord + Eq + Hash, E: PartialOrd> PartialOrd for VertexWithPriority<V, E> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        other.priority.partial_cmp(&self.priority)
+        Some(self.cmp(other))
     }
 }
 impl<V: Ord + Eq + Hash, E: PartialOrd> Ord for VertexWithPriority<V, E> {
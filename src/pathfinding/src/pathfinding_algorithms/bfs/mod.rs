@@ -0,0 +1,43 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use crate::{
+    graph::AdjacencyList, path_from_parents, Edge, Heuristic, PathfindingResult, PathfindingSteps,
+    Vertex, VertexState,
+};
+
+pub fn bfs<V: Vertex, E: Edge>(
+    adjacency_list: AdjacencyList<V, E>,
+    start: V,
+    end: V,
+    _heuristic: Heuristic,
+    mut steps: PathfindingSteps<V>,
+) -> PathfindingResult<V, E> {
+    let mut queue = VecDeque::from([start]);
+    // Map of the discovered vertices and their parents
+    let mut vertex_parents = HashMap::<V, V>::new();
+    // Vertices are marked as discovered when they are added to the queue so that they are only added once
+    let mut discovered = HashSet::from([start]);
+
+    while let Some(vertex) = queue.pop_front() {
+        steps.init_step();
+        steps.insert_state_to_last_step(vertex, VertexState::NewVisited);
+
+        if vertex == end {
+            let path = path_from_parents(&vertex_parents, start, end);
+            steps.push_path_steps(&path);
+            return PathfindingResult::new(steps, path, BTreeMap::new());
+        }
+
+        if let Some(neighbors) = adjacency_list.get_neighbors(&vertex) {
+            for neighbor in neighbors.keys() {
+                if discovered.insert(*neighbor) {
+                    vertex_parents.insert(*neighbor, vertex);
+                    steps.insert_state_to_last_step(*neighbor, VertexState::Queued);
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+    }
+
+    PathfindingResult::new(steps, vec![], BTreeMap::new())
+}
@@ -0,0 +1,99 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use crate::{
+    graph::AdjacencyList, path_through_meeting, Edge, Heuristic, PathfindingResult,
+    PathfindingSteps, Vertex, VertexState,
+};
+
+/// Runs a breadth-first search from both `start` and `end` until the searches meet.
+/// The graph is assumed to be undirected, because the search from `end` follows the same edges as the search from `start`.
+pub fn bidirectional_bfs<V: Vertex, E: Edge>(
+    adjacency_list: AdjacencyList<V, E>,
+    start: V,
+    end: V,
+    _heuristic: Heuristic,
+    mut steps: PathfindingSteps<V>,
+) -> PathfindingResult<V, E> {
+    let mut forward = Frontier::new(start, VertexState::NewVisited, VertexState::Queued);
+    let mut backward = Frontier::new(
+        end,
+        VertexState::NewVisitedFromEnd,
+        VertexState::QueuedFromEnd,
+    );
+
+    // The meeting vertex and the length of the path through it in edges
+    let mut meeting: Option<(V, usize)> = if start == end { Some((start, 0)) } else { None };
+
+    // Expand a whole depth level of one search at a time, so that the best meeting vertex of the level can be chosen
+    while meeting.is_none() && !forward.queue.is_empty() && !backward.queue.is_empty() {
+        // Always expand the search with the smaller frontier
+        let (frontier, other) = if forward.queue.len() <= backward.queue.len() {
+            (&mut forward, &backward)
+        } else {
+            (&mut backward, &forward)
+        };
+
+        for _ in 0..frontier.queue.len() {
+            let vertex = frontier.queue.pop_front().unwrap();
+            let depth = frontier.depths[&vertex];
+
+            steps.init_step();
+            steps.insert_state_to_last_step(vertex, frontier.expanded_state);
+
+            if let Some(neighbors) = adjacency_list.get_neighbors(&vertex) {
+                for neighbor in neighbors.keys() {
+                    if frontier.depths.contains_key(neighbor) {
+                        continue;
+                    }
+
+                    frontier.depths.insert(*neighbor, depth + 1);
+                    frontier.parents.insert(*neighbor, vertex);
+                    frontier.queue.push_back(*neighbor);
+                    steps.insert_state_to_last_step(*neighbor, frontier.queued_state);
+
+                    // The searches meet
+                    if let Some(other_depth) = other.depths.get(neighbor) {
+                        let len = depth + 1 + other_depth;
+                        if meeting.is_none_or(|(_, best_len)| len < best_len) {
+                            meeting = Some((*neighbor, len));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    match meeting {
+        Some((meeting, _)) => {
+            let path =
+                path_through_meeting(&forward.parents, &backward.parents, start, end, meeting);
+            steps.push_path_steps(&path);
+            steps.init_step();
+            steps.insert_state_to_last_step(meeting, VertexState::Meeting);
+            PathfindingResult::new(steps, path, BTreeMap::new())
+        }
+        None => PathfindingResult::new(steps, vec![], BTreeMap::new()),
+    }
+}
+
+/// The state of the search from one end.
+struct Frontier<V: Vertex> {
+    queue: VecDeque<V>,
+    /// The depths of the discovered vertices in edges
+    depths: HashMap<V, usize>,
+    parents: HashMap<V, V>,
+    expanded_state: VertexState,
+    queued_state: VertexState,
+}
+
+impl<V: Vertex> Frontier<V> {
+    fn new(source: V, expanded_state: VertexState, queued_state: VertexState) -> Self {
+        Self {
+            queue: VecDeque::from([source]),
+            depths: HashMap::from([(source, 0)]),
+            parents: HashMap::new(),
+            expanded_state,
+            queued_state,
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+
+use super::dijkstra::Visit;
+use crate::{
+    graph::AdjacencyList, path_through_meeting, Edge, Heuristic, PathfindingResult,
+    PathfindingSteps, Vertex, VertexState,
+};
+
+/// Runs Dijkstra's algorithm from both `start` and `end` until the searches meet.
+/// The graph is assumed to be undirected, because the search from `end` follows the same edges as the search from `start`.
+pub fn bidirectional_dijkstra<V: Vertex, E: Edge>(
+    adjacency_list: AdjacencyList<V, E>,
+    start: V,
+    end: V,
+    _heuristic: Heuristic,
+    mut steps: PathfindingSteps<V>,
+) -> PathfindingResult<V, E> {
+    let mut forward = Search::new(start, VertexState::NewVisited, VertexState::Queued);
+    let mut backward = Search::new(
+        end,
+        VertexState::NewVisitedFromEnd,
+        VertexState::QueuedFromEnd,
+    );
+
+    // The meeting vertex and the cost of the shortest known path through it
+    let mut meeting: Option<(V, E)> = if start == end {
+        Some((start, E::zero()))
+    } else {
+        None
+    };
+
+    while let (Some(forward_min), Some(backward_min)) =
+        (forward.min_distance(), backward.min_distance())
+    {
+        // No path through unexpanded vertices can be shorter than the best known path anymore
+        if let Some((_, best_distance)) = meeting {
+            if forward_min + backward_min >= best_distance {
+                break;
+            }
+        }
+
+        // Always expand the search whose next vertex is closer to its source
+        let (search, other) = if forward_min <= backward_min {
+            (&mut forward, &backward)
+        } else {
+            (&mut backward, &forward)
+        };
+
+        let Visit { vertex, distance } = search.to_visit.pop().unwrap();
+
+        // The vertex was already expanded through a shorter path
+        if !search.visited.insert(vertex) {
+            continue;
+        }
+
+        steps.init_step();
+        steps.insert_state_to_last_step(vertex, search.expanded_state);
+
+        if let Some(neighbors) = adjacency_list.get_neighbors(&vertex) {
+            for (neighbor, cost) in neighbors {
+                if search.visited.contains(neighbor) {
+                    continue;
+                }
+
+                let new_distance = distance + *cost;
+                let is_shorter = search
+                    .distances
+                    .get(neighbor)
+                    .is_none_or(|current| new_distance < *current);
+
+                if is_shorter {
+                    search.distances.insert(*neighbor, new_distance);
+                    search.parents.insert(*neighbor, vertex);
+                    steps.insert_state_to_last_step(*neighbor, search.queued_state);
+                    search.to_visit.push(Visit::new(*neighbor, new_distance));
+                }
+
+                // The searches meet
+                if let Some(other_distance) = other.distances.get(neighbor) {
+                    let total_distance = search.distances[neighbor] + *other_distance;
+                    if meeting.is_none_or(|(_, best)| total_distance < best) {
+                        meeting = Some((*neighbor, total_distance));
+                    }
+                }
+            }
+        }
+    }
+
+    // The costs of vertices from the start vertex
+    let costs = forward.distances.into_iter().collect::<BTreeMap<V, E>>();
+
+    match meeting {
+        Some((meeting, _)) => {
+            let path =
+                path_through_meeting(&forward.parents, &backward.parents, start, end, meeting);
+            steps.push_path_steps(&path);
+            steps.init_step();
+            steps.insert_state_to_last_step(meeting, VertexState::Meeting);
+            PathfindingResult::new(steps, path, costs)
+        }
+        None => PathfindingResult::new(steps, vec![], costs),
+    }
+}
+
+/// The state of the search from one end.
+struct Search<V: Vertex, E: Edge> {
+    /// The tentative distances from the source vertex
+    distances: HashMap<V, E>,
+    /// The vertices that the shortest known paths come from
+    parents: HashMap<V, V>,
+    visited: HashSet<V>,
+    to_visit: BinaryHeap<Visit<V, E>>,
+    expanded_state: VertexState,
+    queued_state: VertexState,
+}
+
+impl<V: Vertex, E: Edge> Search<V, E> {
+    fn new(source: V, expanded_state: VertexState, queued_state: VertexState) -> Self {
+        Self {
+            distances: HashMap::from([(source, E::zero())]),
+            parents: HashMap::new(),
+            visited: HashSet::new(),
+            to_visit: BinaryHeap::from([Visit::new(source, E::zero())]),
+            expanded_state,
+            queued_state,
+        }
+    }
+    /// The distance of the next vertex to expand.
+    fn min_distance(&self) -> Option<E> {
+        self.to_visit.peek().map(|visit| visit.distance)
+    }
+}
@@ -0,0 +1,493 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+use super::a_star::cheapest_cost_per_estimate;
+use crate::{graph::AdjacencyList, *};
+
+pub fn d_star_lite<V: Vertex, E: Edge>(
+    adjacency_list: AdjacencyList<V, E>,
+    start: V,
+    end: V,
+    heuristic: Heuristic,
+    steps: PathfindingSteps<V>,
+) -> PathfindingResult<V, E> {
+    DStarLite::new(adjacency_list, start, end, heuristic).compute_path(steps)
+}
+
+/// An incremental planner that keeps the results of its earlier searches.
+///
+/// The search grows from the start vertex, so the end vertex can be moved and the graph can be changed between searches.
+/// Only the vertices whose costs are affected by the changes are expanded again when the path is computed the next time.
+#[derive(Clone, Debug)]
+pub struct DStarLite<V: Vertex, E: Edge> {
+    graph: AdjacencyList<V, E>,
+    /// The incoming edges of each vertex, since the cost of a vertex depends on its predecessors.
+    predecessors: BTreeMap<V, BTreeMap<V, E>>,
+    start: V,
+    end: V,
+    heuristic: Heuristic,
+    heuristic_scale: E,
+    /// The sum of the estimated distances that the end vertex has moved. It is added to the keys of new entries
+    /// so that the keys of the entries that are already in the open set don't need to be recomputed.
+    key_modifier: E,
+    /// The cost of the cheapest path from the start vertex found so far.
+    g: HashMap<V, E>,
+    /// The cost of the cheapest path from the start vertex based on the `g` values of the predecessors.
+    /// A vertex is inconsistent and needs to be expanded when this differs from its `g` value.
+    rhs: HashMap<V, E>,
+    /// The current keys of the inconsistent vertices. Entries in `open_set` with other keys are outdated.
+    open_keys: HashMap<V, (E, E)>,
+    open_set: BinaryHeap<VertexWithKey<V, E>>,
+    /// Whether the path has already been computed once.
+    searched: bool,
+}
+impl<V: Vertex, E: Edge> DStarLite<V, E> {
+    pub fn new(graph: AdjacencyList<V, E>, start: V, end: V, heuristic: Heuristic) -> Self {
+        let mut planner = Self {
+            heuristic_scale: cheapest_cost_per_estimate(&graph, heuristic),
+            predecessors: predecessors(&graph),
+            graph,
+            start,
+            end,
+            heuristic,
+            key_modifier: E::zero(),
+            g: HashMap::new(),
+            rhs: HashMap::new(),
+            open_keys: HashMap::new(),
+            open_set: BinaryHeap::new(),
+            searched: false,
+        };
+        planner.rhs.insert(start, E::zero());
+        planner.insert_to_open_set(start);
+        planner
+    }
+    pub fn start(&self) -> V {
+        self.start
+    }
+    pub fn end(&self) -> V {
+        self.end
+    }
+    pub fn heuristic(&self) -> Heuristic {
+        self.heuristic
+    }
+    /// Moves the end vertex. The search is continued from where it was left the next time the path is computed.
+    pub fn move_end(&mut self, end: V) {
+        self.key_modifier = self.key_modifier + self.estimate(self.end, end);
+        self.end = end;
+    }
+    /// Replaces the graph and marks the vertices whose incoming edges changed as inconsistent.
+    pub fn update_graph(&mut self, graph: AdjacencyList<V, E>) {
+        // The keys of the open set would overestimate costs if the cheapest edge became cheaper, so the search starts over
+        if cheapest_cost_per_estimate(&graph, self.heuristic) != self.heuristic_scale {
+            *self = Self::new(graph, self.start, self.end, self.heuristic);
+            return;
+        }
+
+        let old_graph = std::mem::replace(&mut self.graph, graph);
+        self.predecessors = predecessors(&self.graph);
+
+        let vertices = old_graph
+            .hash_map
+            .keys()
+            .chain(self.graph.hash_map.keys())
+            .copied()
+            .collect::<BTreeSet<V>>();
+        let no_edges = BTreeMap::new();
+        let mut changed = BTreeSet::new();
+
+        for vertex in vertices {
+            let old_edges = old_graph.get_neighbors(&vertex).unwrap_or(&no_edges);
+            let new_edges = self.graph.get_neighbors(&vertex).unwrap_or(&no_edges);
+            if old_edges == new_edges {
+                continue;
+            }
+            for neighbor in old_edges.keys().chain(new_edges.keys()) {
+                if old_edges.get(neighbor) != new_edges.get(neighbor) {
+                    changed.insert(*neighbor);
+                }
+            }
+            // Walled in start vertices are left out of the graph, but they keep their cost of zero for when they come back
+            if !self.graph.hash_map.contains_key(&vertex) && vertex != self.start {
+                self.g.remove(&vertex);
+                self.rhs.remove(&vertex);
+                self.open_keys.remove(&vertex);
+            }
+        }
+
+        for vertex in changed {
+            self.update_vertex(vertex, &mut PathfindingSteps::new(vec![]));
+        }
+    }
+    /// Expands inconsistent vertices until the cost of the end vertex is known and returns the path.
+    ///
+    /// After the first search, the steps begin with the vertices that earlier searches have already expanded.
+    pub fn compute_path(&mut self, mut steps: PathfindingSteps<V>) -> PathfindingResult<V, E> {
+        if self.searched {
+            steps.init_step();
+            for vertex in self.graph.hash_map.keys() {
+                if self.g(*vertex) < E::infinity() {
+                    steps.insert_state_to_last_step(*vertex, VertexState::Visited);
+                }
+            }
+        }
+        self.searched = true;
+
+        while let Some(top) = self.pop_open_set() {
+            if self.is_after_end(top.key) && self.rhs(self.end) == self.g(self.end) {
+                // The rest of the vertices can't affect the path, so they stay in the open set for later searches
+                self.insert_to_open_set(top.vertex);
+                break;
+            }
+
+            let vertex = top.vertex;
+            let new_key = self.key(vertex);
+            if top.key < new_key {
+                // The end vertex has moved since the vertex was queued, so its key was too small
+                self.insert_to_open_set(vertex);
+                continue;
+            }
+
+            steps.init_step();
+            steps.insert_state_to_last_step(vertex, VertexState::NewVisited);
+
+            let successors = self
+                .graph
+                .get_neighbors(&vertex)
+                .map_or(vec![], |neighbors| neighbors.keys().copied().collect());
+
+            if self.g(vertex) > self.rhs(vertex) {
+                // A cheaper path was found, which can make the paths to the successors cheaper too
+                self.g.insert(vertex, self.rhs(vertex));
+                for successor in successors {
+                    self.update_vertex(successor, &mut steps);
+                }
+            } else {
+                // The path to the vertex got more expensive, so the vertex and its successors have to be checked again
+                self.g.insert(vertex, E::infinity());
+                for successor in successors.into_iter().chain([vertex]) {
+                    self.update_vertex(successor, &mut steps);
+                }
+            }
+        }
+
+        let path = self.reconstruct_path();
+        steps.push_path_steps(&path);
+        PathfindingResult::new(steps, path, BTreeMap::new()).with_scores(self.scores())
+    }
+    fn g(&self, vertex: V) -> E {
+        *self.g.get(&vertex).unwrap_or(&E::infinity())
+    }
+    fn rhs(&self, vertex: V) -> E {
+        *self.rhs.get(&vertex).unwrap_or(&E::infinity())
+    }
+    fn estimate(&self, from: V, to: V) -> E {
+        from.estimate::<E>(to, self.heuristic) * self.heuristic_scale
+    }
+    /// The priority of a vertex in the open set. Vertices with smaller keys are expanded first.
+    fn key(&self, vertex: V) -> (E, E) {
+        let cost = self.g(vertex).min(self.rhs(vertex));
+        (
+            cost + self.estimate(vertex, self.end) + self.key_modifier,
+            cost,
+        )
+    }
+    /// Checks if a vertex with the key can't make the path to the end vertex cheaper.
+    ///
+    /// Exact heuristics give many vertices the same estimated cost as the end vertex, and the same costs summed
+    /// in different orders can come out slightly different. So vertices whose estimated costs are only rounding errors
+    /// away from the cost of the end vertex are always expanded, even if they could be ordered after it.
+    fn is_after_end(&self, key: (E, E)) -> bool {
+        let end_cost = self.key(self.end).0;
+        end_cost.is_finite()
+            && key.0 > end_cost + E::from(1e-4).unwrap() * end_cost.abs().max(E::one())
+    }
+    /// Recomputes the `rhs` value of a vertex and adds it to the open set if it is inconsistent.
+    fn update_vertex(&mut self, vertex: V, steps: &mut PathfindingSteps<V>) {
+        if !self.graph.hash_map.contains_key(&vertex) {
+            return;
+        }
+        if vertex != self.start {
+            let rhs = self
+                .predecessors
+                .get(&vertex)
+                .map_or(E::infinity(), |edges| {
+                    edges
+                        .iter()
+                        .map(|(predecessor, cost)| self.g(*predecessor) + *cost)
+                        .fold(E::infinity(), E::min)
+                });
+            self.rhs.insert(vertex, rhs);
+        }
+
+        self.open_keys.remove(&vertex);
+        if self.g(vertex) != self.rhs(vertex) {
+            self.insert_to_open_set(vertex);
+            steps.insert_state_to_last_step(vertex, VertexState::Queued);
+        }
+    }
+    fn insert_to_open_set(&mut self, vertex: V) {
+        let key = self.key(vertex);
+        self.open_keys.insert(vertex, key);
+        self.open_set.push(VertexWithKey::new(vertex, key));
+    }
+    /// Removes and returns the open vertex with the smallest key, skipping outdated entries.
+    fn pop_open_set(&mut self) -> Option<VertexWithKey<V, E>> {
+        while let Some(entry) = self.open_set.pop() {
+            if self.open_keys.get(&entry.vertex) == Some(&entry.key) {
+                self.open_keys.remove(&entry.vertex);
+                return Some(entry);
+            }
+        }
+        None
+    }
+    /// Returns the path from the start vertex to the end vertex by following the cheapest predecessors backwards.
+    fn reconstruct_path(&self) -> Vec<V> {
+        if self.g(self.end) == E::infinity() {
+            return vec![];
+        }
+        let mut path = vec![self.end];
+        let mut curr = self.end;
+        while curr != self.start {
+            let cheapest = self.predecessors.get(&curr).and_then(|edges| {
+                edges
+                    .iter()
+                    .filter(|(predecessor, _)| self.g(**predecessor) < E::infinity())
+                    .min_by(|(a, cost_a), (b, cost_b)| {
+                        (self.g(**a) + **cost_a)
+                            .partial_cmp(&(self.g(**b) + **cost_b))
+                            .unwrap()
+                    })
+            });
+            match cheapest {
+                // The path can't be longer than the number of vertices unless it goes in a loop
+                Some((predecessor, _)) if path.len() <= self.graph.hash_map.len() => {
+                    curr = *predecessor;
+                    path.push(curr);
+                }
+                _ => return vec![],
+            }
+        }
+        path.reverse();
+        path
+    }
+    /// Gets the scores of all vertices whose cost is known.
+    fn scores(&self) -> BTreeMap<V, VertexScores<E>> {
+        self.g
+            .iter()
+            .filter(|(_, g)| **g < E::infinity())
+            .map(|(vertex, g)| {
+                (
+                    *vertex,
+                    VertexScores::new(*g, self.estimate(*vertex, self.end)),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Gets the incoming edges of each vertex in the graph.
+fn predecessors<V: Vertex, E: Edge>(graph: &AdjacencyList<V, E>) -> BTreeMap<V, BTreeMap<V, E>> {
+    let mut predecessors = BTreeMap::<V, BTreeMap<V, E>>::new();
+    for (vertex, neighbors) in graph.hash_map.iter() {
+        for (neighbor, cost) in neighbors.iter() {
+            predecessors
+                .entry(*neighbor)
+                .or_default()
+                .insert(*vertex, *cost);
+        }
+    }
+    predecessors
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct VertexWithKey<V: Ord + Eq + Hash, E: PartialOrd> {
+    vertex: V,
+    key: (E, E),
+}
+impl<V: Ord + Eq + Hash, E: PartialOrd> VertexWithKey<V, E> {
+    fn new(vertex: V, key: (E, E)) -> Self {
+        Self { vertex, key }
+    }
+}
+impl<V: Ord + Eq + Hash, E: PartialOrd> Eq for VertexWithKey<V, E> {}
+impl<V: Ord + Eq + Hash, E: PartialOrd> PartialOrd for VertexWithKey<V, E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<V: Ord + Eq + Hash, E: PartialOrd> Ord for VertexWithKey<V, E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.partial_cmp(&self.key).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    const HEURISTICS: [Heuristic; 4] = [
+        Heuristic::Manhattan,
+        Heuristic::Euclidean,
+        Heuristic::Chebyshev,
+        Heuristic::Octile,
+    ];
+
+    fn grid(
+        width: usize,
+        height: usize,
+        diagonals: bool,
+        walls: &BTreeSet<Coord>,
+    ) -> AdjacencyList<Coord, f32> {
+        generate_graph(
+            width,
+            height,
+            diagonals,
+            CornerCutting::Never,
+            walls,
+            &BTreeMap::new(),
+        )
+    }
+
+    /// The cost of the path, or `None` if it doesn't go from the start to the end along the edges of the graph.
+    fn path_cost(
+        graph: &AdjacencyList<Coord, f32>,
+        path: &[Coord],
+        start: Coord,
+        end: Coord,
+    ) -> Option<f32> {
+        if path.first() != Some(&start) || path.last() != Some(&end) {
+            return None;
+        }
+        path.windows(2).try_fold(0.0, |cost, pair| {
+            Some(cost + graph.get_neighbors(&pair[0])?.get(&pair[1])?)
+        })
+    }
+
+    /// Checks that the planner finds a path exactly when a new planner does, and that the paths cost the same.
+    fn assert_same_as_new_planner(
+        planner: &mut DStarLite<Coord, f32>,
+        graph: &AdjacencyList<Coord, f32>,
+    ) {
+        let (start, end) = (planner.start(), planner.end());
+        let incremental = planner.compute_path(PathfindingSteps::new(vec![])).path;
+        let new = DStarLite::new(graph.clone(), start, end, planner.heuristic())
+            .compute_path(PathfindingSteps::new(vec![]))
+            .path;
+        match (
+            path_cost(graph, &incremental, start, end),
+            path_cost(graph, &new, start, end),
+        ) {
+            (Some(a), Some(b)) => assert!(
+                (a - b).abs() < 1e-3,
+                "{:?} costs {}, but {:?} costs {}",
+                incremental,
+                a,
+                new,
+                b
+            ),
+            (None, None) => assert!(incremental.is_empty() && new.is_empty()),
+            _ => panic!(
+                "Found {:?} incrementally, but {:?} from scratch",
+                incremental, new
+            ),
+        }
+    }
+
+    /// Toggles the walls one at a time and checks the path after each of them.
+    fn assert_walls_replanned(
+        width: usize,
+        height: usize,
+        diagonals: bool,
+        heuristic: Heuristic,
+        start: Coord,
+        end: Coord,
+        toggled: &[Coord],
+    ) {
+        let mut walls = BTreeSet::new();
+        let mut planner = DStarLite::new(
+            grid(width, height, diagonals, &walls),
+            start,
+            end,
+            heuristic,
+        );
+        planner.compute_path(PathfindingSteps::new(vec![]));
+        for wall in toggled {
+            if !walls.remove(wall) {
+                walls.insert(*wall);
+            }
+            let graph = grid(width, height, diagonals, &walls);
+            planner.update_graph(graph.clone());
+            assert_same_as_new_planner(&mut planner, &graph);
+        }
+    }
+
+    #[test]
+    fn finds_the_start_again_after_it_is_walled_in() {
+        let wall = Coord::new(1, 0);
+        assert_walls_replanned(
+            3,
+            1,
+            false,
+            Heuristic::Manhattan,
+            Coord::new(0, 0),
+            Coord::new(2, 0),
+            &[wall, wall],
+        );
+    }
+
+    #[test]
+    fn repairs_costs_that_increase() {
+        assert_walls_replanned(
+            7,
+            4,
+            true,
+            Heuristic::Octile,
+            Coord::new(2, 3),
+            Coord::new(0, 0),
+            &[Coord::new(3, 0), Coord::new(2, 2)],
+        );
+    }
+
+    #[test]
+    fn matches_new_planners_after_random_changes() {
+        let mut rng = StdRng::seed_from_u64(348);
+        for _ in 0..300 {
+            let (width, height) = (rng.gen_range(1..8), rng.gen_range(1..6));
+            let diagonals = rng.gen_bool(0.5);
+            let heuristic = HEURISTICS[rng.gen_range(0..HEURISTICS.len())];
+            let random_cell = |rng: &mut StdRng| {
+                Coord::new(
+                    rng.gen_range(0..width as isize),
+                    rng.gen_range(0..height as isize),
+                )
+            };
+
+            let mut walls = BTreeSet::new();
+            let start = random_cell(&mut rng);
+            let mut planner = DStarLite::new(
+                grid(width, height, diagonals, &walls),
+                start,
+                random_cell(&mut rng),
+                heuristic,
+            );
+            for _ in 0..20 {
+                if rng.gen_bool(0.2) {
+                    planner.move_end(random_cell(&mut rng));
+                } else {
+                    let cell = random_cell(&mut rng);
+                    if !walls.remove(&cell) {
+                        walls.insert(cell);
+                    }
+                }
+                let graph = grid(width, height, diagonals, &walls);
+                planner.update_graph(graph.clone());
+                assert_same_as_new_planner(&mut planner, &graph);
+            }
+        }
+    }
+}
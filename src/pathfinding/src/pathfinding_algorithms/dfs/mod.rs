@@ -1,11 +1,15 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-use crate::{graph::AdjacencyList, Edge, PathfindingResult, PathfindingSteps, Vertex, VertexState};
+use crate::{
+    graph::AdjacencyList, path_from_parents, Edge, Heuristic, PathfindingResult, PathfindingSteps,
+    Vertex, VertexState,
+};
 
 pub fn dfs<V: Vertex, E: Edge>(
     adjacency_list: AdjacencyList<V, E>,
     start: V,
     end: V,
+    _heuristic: Heuristic,
     mut steps: PathfindingSteps<V>,
 ) -> PathfindingResult<V, E> {
     let path = _iterative_dfs(adjacency_list, start, end, &mut steps);
@@ -26,24 +30,24 @@ fn _iterative_dfs<V: Vertex, E: Edge>(
     while !stack.is_empty() {
         let vertex = stack.pop().unwrap();
 
-        if vertex == end {
-            let mut path = get_path(vertex_parents, vertex);
-            path.insert(0, start);
-            path.push(end);
-            return path;
-        }
-
         if !visited.contains(&vertex) {
             visited.insert(vertex);
 
             steps.init_step();
             steps.insert_state_to_last_step(vertex, VertexState::NewVisited);
 
+            if vertex == end {
+                let path = path_from_parents(&vertex_parents, start, end);
+                steps.push_path_steps(&path);
+                return path;
+            }
+
             if let Some(neighbors) = adjacency_list.get_neighbors(&vertex) {
                 for (neighbor, _) in neighbors.iter().rev() {
                     if !visited.contains(neighbor) {
                         stack.push(*neighbor);
                         vertex_parents.insert(*neighbor, vertex);
+                        steps.insert_state_to_last_step(*neighbor, VertexState::Queued);
                     }
                 }
             }
@@ -53,17 +57,6 @@ fn _iterative_dfs<V: Vertex, E: Edge>(
     vec![]
 }
 
-fn get_path<V: Vertex>(vertex_parents: HashMap<V, V>, mut vertex: V) -> Vec<V> {
-    let mut path = vec![];
-
-    while vertex_parents.contains_key(&vertex) {
-        vertex = *vertex_parents.get(&vertex).unwrap();
-        path.push(vertex);
-    }
-    path.reverse();
-    path
-}
-
 fn _recursive_dfs<V: Vertex, E: Edge>(
     adjacency_list: AdjacencyList<V, E>,
     (vertex, cost): (V, E),
@@ -1,57 +1,64 @@
 use std::{
     cmp::Ordering,
-    collections::{BTreeMap, BinaryHeap, HashSet},
-    fmt::Debug,
+    collections::{BTreeMap, BinaryHeap, HashMap, HashSet},
 };
 
-use crate::{graph::AdjacencyList, Edge, PathfindingResult, PathfindingSteps, Vertex, VertexState};
+use crate::{
+    graph::AdjacencyList, path_from_parents, Edge, Heuristic, PathfindingResult, PathfindingSteps,
+    Vertex, VertexState,
+};
 
 pub fn dijkstra<V: Vertex, E: Edge>(
     adjacency_list: AdjacencyList<V, E>,
     start: V,
     end: V,
+    _heuristic: Heuristic,
     mut steps: PathfindingSteps<V>,
 ) -> PathfindingResult<V, E> {
+    // The tentative distances from the start vertex
     let mut distances = BTreeMap::<V, E>::new();
+    // The vertices that the shortest known paths come from
+    let mut parents = HashMap::<V, V>::new();
     let mut visited = HashSet::new();
     let mut to_visit = BinaryHeap::new();
 
     distances.insert(start, E::zero());
-    to_visit.push(Visit {
-        vertex: start,
-        distance: 0,
-    });
+    to_visit.push(Visit::new(start, E::zero()));
 
     while let Some(Visit { vertex, distance }) = to_visit.pop() {
+        // The vertex was already expanded through a shorter path
+        if !visited.insert(vertex) {
+            continue;
+        }
+
+        // Expand the vertex
         steps.init_step();
         steps.insert_state_to_last_step(vertex, VertexState::NewVisited);
 
-        if !visited.insert(vertex) {
-            continue;
+        // The distance of a vertex is final once it is expanded, so the path to the target is the shortest one
+        if vertex == end {
+            let path = path_from_parents(&parents, start, end);
+            steps.push_path_steps(&path);
+            return PathfindingResult::new(steps, path, distances);
         }
 
         if let Some(neighbors) = adjacency_list.get_neighbors(&vertex) {
             for (neighbor, cost) in neighbors {
-                let new_distance = E::from(distance).unwrap() + cost.to_owned();
+                if visited.contains(neighbor) {
+                    continue;
+                }
+
+                let new_distance = distance + *cost;
                 let is_shorter = distances
                     .get(neighbor)
-                    .map_or(true, |current| new_distance < current.to_owned());
-
-                if *neighbor == end {
-                    distances.insert(*neighbor, new_distance);
-                    return PathfindingResult::new(
-                        steps,
-                        distance_map_shortest_path(&adjacency_list, &distances, start, end),
-                        distances,
-                    );
-                }
+                    .map_or(true, |current| new_distance < *current);
 
+                // Update the tentative distance
                 if is_shorter {
-                    distances.insert(*neighbor, new_distance.to_owned().into());
-                    to_visit.push(Visit {
-                        vertex: *neighbor,
-                        distance: new_distance.to_isize().unwrap(),
-                    });
+                    distances.insert(*neighbor, new_distance);
+                    parents.insert(*neighbor, vertex);
+                    steps.insert_state_to_last_step(*neighbor, VertexState::Queued);
+                    to_visit.push(Visit::new(*neighbor, new_distance));
                 }
             }
         }
@@ -59,60 +66,39 @@ pub fn dijkstra<V: Vertex, E: Edge>(
 
     PathfindingResult::new(steps, vec![], distances)
 }
+
 #[derive(Debug)]
-struct Visit<V, E: Ord> {
-    vertex: V,
-    distance: E,
+pub(crate) struct Visit<V, E: PartialOrd> {
+    pub(crate) vertex: V,
+    pub(crate) distance: E,
+}
+
+impl<V, E: PartialOrd> Visit<V, E> {
+    pub(crate) fn new(vertex: V, distance: E) -> Self {
+        Self { vertex, distance }
+    }
 }
 
-impl<V, E: Ord> Ord for Visit<V, E> {
+// The ordering is reversed so that the `BinaryHeap` is a min-heap.
+impl<V, E: PartialOrd> Ord for Visit<V, E> {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.distance.cmp(&self.distance)
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
     }
 }
 
-impl<V, E: Ord> PartialOrd for Visit<V, E> {
+impl<V, E: PartialOrd> PartialOrd for Visit<V, E> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl<V, E: Ord> PartialEq for Visit<V, E> {
+impl<V, E: PartialOrd> PartialEq for Visit<V, E> {
     fn eq(&self, other: &Self) -> bool {
         self.distance.eq(&other.distance)
     }
 }
 
-impl<V, E: Ord> Eq for Visit<V, E> {}
-
-/// Finds the shortest path from start to end according to a given distance map.
-fn distance_map_shortest_path<V: Vertex, E: Edge>(
-    adjacency_list: &AdjacencyList<V, E>,
-    distances: &BTreeMap<V, E>,
-    start: V,
-    end: V,
-) -> Vec<V> {
-    let mut shortest_path = vec![end];
-    let mut curr_vertex = end;
-    // If neighbor is not found in distance map
-    let default_val = (&start, &E::max_value());
-
-    while curr_vertex != start {
-        if let Some(neighbors) = adjacency_list.get_neighbors(&curr_vertex) {
-            let unvisited_neighbors = neighbors.iter().filter(|n| !shortest_path.contains(n.0));
-            let closest_neighbor = unvisited_neighbors
-                .map(|n| distances.get_key_value(n.0).unwrap_or(default_val))
-                .min_by(|a, b| a.1.partial_cmp(b.1).unwrap());
-
-            if let Some((new_vertex, _)) = closest_neighbor {
-                shortest_path.push(*new_vertex);
-                curr_vertex = *new_vertex;
-            }
-        }
-        if curr_vertex == end {
-            break;
-        }
-    }
-    shortest_path.reverse();
-    shortest_path
-}
+impl<V, E: PartialOrd> Eq for Visit<V, E> {}
@@ -1,8 +1,16 @@
 //! A collection of pathfinding algorithms.
 mod a_star;
+mod bfs;
+mod bidirectional_bfs;
+mod bidirectional_dijkstra;
+mod d_star_lite;
 mod dfs;
 mod dijkstra;
 
 pub use a_star::a_star;
+pub use bfs::bfs;
+pub use bidirectional_bfs::bidirectional_bfs;
+pub use bidirectional_dijkstra::bidirectional_dijkstra;
+pub use d_star_lite::{d_star_lite, DStarLite};
 pub use dfs::dfs;
 pub use dijkstra::dijkstra;
@@ -0,0 +1,168 @@
+//! Finding paths that go through several waypoints.
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    graph::AdjacencyList, pathfinding_algorithms::dijkstra, run_pathfinding, Edge, Heuristic,
+    PathfindingFunc, PathfindingResult, PathfindingStep, PathfindingSteps, Vertex, VertexState,
+};
+
+/// The most waypoints that a path can go through. Finding the best order takes exponential time and memory in the number of waypoints.
+pub const MAX_WAYPOINTS: usize = 8;
+
+/// Finds a path that visits the `stops` in order with the given algorithm. Each pair of consecutive stops is a segment of its own.
+///
+/// The steps of the segments are run one after another, and each segment's search is hidden before the next search begins.
+/// Returns the result and the paths of the segments. The path is empty if any segment can't be found.
+pub fn find_path_through<V: Vertex, E: Edge>(
+    graph: &AdjacencyList<V, E>,
+    stops: &[V],
+    heuristic: Heuristic,
+    algorithm: PathfindingFunc<V, E>,
+) -> (PathfindingResult<V, E>, Vec<Vec<V>>) {
+    let mut steps = PathfindingSteps::new(vec![]);
+    let mut segments: Vec<Vec<V>> = vec![];
+    let mut scores = BTreeMap::new();
+
+    for pair in stops.windows(2) {
+        let result = run_pathfinding(graph, pair[0], pair[1], heuristic, algorithm).0;
+
+        if !steps.steps.is_empty() {
+            // Hide the previous searches, but keep the paths that were found
+            let mut hidden = BTreeMap::new();
+            for step in steps.steps.iter() {
+                for (vertex, state) in step.states.iter() {
+                    if *state != VertexState::Path {
+                        hidden.insert(*vertex, VertexState::NotVisited);
+                    }
+                }
+            }
+            for vertex in segments.iter().flatten() {
+                hidden.remove(vertex);
+            }
+            steps
+                .steps
+                .push(PathfindingStep::new(hidden, BTreeSet::new()));
+        }
+
+        steps.steps.extend(result.steps.steps);
+        scores = result.scores;
+
+        if result.path.is_empty() {
+            return (
+                PathfindingResult::new(steps, vec![], BTreeMap::new()).with_scores(scores),
+                vec![],
+            );
+        }
+        segments.push(result.path);
+    }
+
+    // Later searches can cover the paths of earlier segments, so show all of them again at the end
+    if segments.len() > 1 {
+        let path_states = segments
+            .iter()
+            .flatten()
+            .map(|vertex| (*vertex, VertexState::Path))
+            .collect();
+        steps
+            .steps
+            .push(PathfindingStep::new(path_states, BTreeSet::new()));
+    }
+
+    let mut path = vec![];
+    for segment in segments.iter() {
+        // Consecutive segments share the stop between them
+        let skip = usize::from(!path.is_empty());
+        path.extend(segment.iter().skip(skip));
+    }
+
+    (
+        PathfindingResult::new(steps, path, BTreeMap::new()).with_scores(scores),
+        segments,
+    )
+}
+
+/// Orders the `waypoints` so that the total cost of going from `start` through all of them to `end` is as small as possible.
+///
+/// The costs between all pairs of stops are found with Dijkstra's algorithm, and the best order is then found
+/// with the Held-Karp algorithm, which takes exponential time in the number of waypoints. This is fine for up to [`MAX_WAYPOINTS`] waypoints, and more of them are kept in their order.
+pub fn optimal_waypoint_order<V: Vertex, E: Edge>(
+    graph: &AdjacencyList<V, E>,
+    start: V,
+    waypoints: &[V],
+    end: V,
+) -> Vec<V> {
+    let n = waypoints.len();
+    // A single waypoint has only one order, and too many would take too much time and memory
+    if !(2..=MAX_WAYPOINTS).contains(&n) {
+        return waypoints.to_vec();
+    }
+
+    let cost = |from: V, to: V| {
+        let path = run_pathfinding(graph, from, to, Heuristic::default(), dijkstra)
+            .0
+            .path;
+        path_cost(graph, &path).unwrap_or_else(E::infinity)
+    };
+    let from_start: Vec<E> = waypoints.iter().map(|w| cost(start, *w)).collect();
+    let to_end: Vec<E> = waypoints.iter().map(|w| cost(*w, end)).collect();
+    let between: Vec<Vec<E>> = waypoints
+        .iter()
+        .map(|a| waypoints.iter().map(|b| cost(*a, *b)).collect())
+        .collect();
+
+    // `best[set][last]` is the cheapest cost of starting from `start`, visiting the waypoints in `set` and ending at the waypoint `last`.
+    let mut best = vec![vec![E::infinity(); n]; 1 << n];
+    let mut previous = vec![vec![None::<usize>; n]; 1 << n];
+    for (i, cost) in from_start.iter().enumerate() {
+        best[1 << i][i] = *cost;
+    }
+    for set in 1..(1_usize << n) {
+        for last in (0..n).filter(|last| set & (1 << last) != 0) {
+            if best[set][last] == E::infinity() {
+                continue;
+            }
+            for next in (0..n).filter(|next| set & (1 << next) == 0) {
+                let next_set = set | (1 << next);
+                let cost = best[set][last] + between[last][next];
+                if cost < best[next_set][next] {
+                    best[next_set][next] = cost;
+                    previous[next_set][next] = Some(last);
+                }
+            }
+        }
+    }
+
+    let full_set = (1 << n) - 1;
+    let mut last = (0..n)
+        .min_by(|a, b| {
+            (best[full_set][*a] + to_end[*a])
+                .partial_cmp(&(best[full_set][*b] + to_end[*b]))
+                .unwrap()
+        })
+        .unwrap();
+
+    // Walk back from the last waypoint to get the order
+    let mut order = vec![last];
+    let mut set = full_set;
+    while let Some(prev) = previous[set][last] {
+        set &= !(1 << last);
+        last = prev;
+        order.push(last);
+    }
+    if order.len() < n {
+        // Some waypoints can't be reached, so there is no order that visits all of them
+        return waypoints.to_vec();
+    }
+    order.reverse();
+    order.into_iter().map(|i| waypoints[i]).collect()
+}
+
+/// Gets the sum of the weights of the edges along the path, or `None` if the path is empty or has a missing edge.
+fn path_cost<V: Vertex, E: Edge>(graph: &AdjacencyList<V, E>, path: &[V]) -> Option<E> {
+    if path.is_empty() {
+        return None;
+    }
+    path.windows(2).try_fold(E::zero(), |cost, pair| {
+        Some(cost + *graph.get_neighbors(&pair[0])?.get(&pair[1])?)
+    })
+}
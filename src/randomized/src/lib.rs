@@ -0,0 +1,23 @@
+//! This crate contains my implementations of randomized algorithms, like reservoir sampling.
+//! Each algorithm records a diagram of its random choices at every step, and repeating it many times charts how often
+//! every outcome comes up, so that the chart shows whether the outcomes are as likely as they should be.
+pub mod randomized_algorithms;
+
+use rand::{thread_rng, Rng};
+
+pub const CANVAS_WIDTH: f32 = 800.0;
+
+/// Shuffle a vector with the Fisher-Yates shuffle, aka Knuth shuffle.
+pub fn knuth_shuffle<T>(mut items: Vec<T>) -> Vec<T> {
+    let mut curr_i = items.len();
+    let mut rand_i: usize;
+
+    while curr_i != 0 {
+        rand_i = thread_rng().gen_range(0..curr_i);
+        curr_i -= 1;
+
+        items.swap(curr_i, rand_i);
+    }
+
+    items
+}
@@ -0,0 +1,6 @@
+//! A collection of algorithms that use random numbers, and whose results should be equally likely.
+mod reservoir_sampling;
+mod shuffling;
+
+pub use reservoir_sampling::ReservoirSampling;
+pub use shuffling::Shuffling;
@@ -0,0 +1,423 @@
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const DIAGRAM_HEIGHT: f32 = 580.0;
+pub const MAX_STREAM_LENGTH: usize = 24;
+pub const MAX_RESERVOIR_SIZE: usize = 8;
+pub const MAX_RUNS: usize = 10_000;
+const STREAM_Y: f32 = 80.0;
+const STREAM_ITEM_SIZE: (f32, f32) = (26.0, 30.0);
+const STREAM_SPACING: f32 = 30.0;
+const RESERVOIR_Y: f32 = 200.0;
+const SLOT_SIZE: (f32, f32) = (44.0, 32.0);
+const SLOT_SPACING: f32 = 54.0;
+
+/// What is drawn at a step of sampling the stream.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    /// How many items of the stream have arrived.
+    arrived: usize,
+    /// The index of the item that just arrived.
+    current: Option<usize>,
+    /// The indices of the items in every slot of the reservoir.
+    reservoir: Vec<usize>,
+    /// The indices of the items that were skipped or replaced.
+    rejected: Vec<usize>,
+    /// The slot that the current item was put in.
+    changed_slot: Option<usize>,
+    /// The indices of the items that were sampled in the latest run, which are highlighted in the chart.
+    sampled: Vec<usize>,
+}
+
+/// Reservoir sampling, which picks a uniform random sample of *k* items from a stream whose length isn't known in
+/// advance, while only ever keeping *k* items. The first *k* items fill the reservoir, and after that, item *i* is
+/// kept with the probability *k* / *i*, replacing a random item of the reservoir. Running it many times charts how
+/// often every item ends up in the sample, and every item should come up about as often as the others.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReservoirSampling {
+    stream_length: usize,
+    reservoir_size: usize,
+    /// How many times every item has been in the final sample over all of the runs so far.
+    counts: Vec<usize>,
+    runs: usize,
+}
+impl ReservoirSampling {
+    pub fn new(stream_length: usize, reservoir_size: usize) -> Self {
+        Self {
+            stream_length,
+            reservoir_size,
+            counts: vec![0; stream_length],
+            runs: 0,
+        }
+    }
+    /// The probability that the item at the index is kept when it arrives, like "5/12".
+    fn probability_text(&self, index: usize) -> String {
+        if index < self.reservoir_size {
+            "1".to_string()
+        } else {
+            format!("{}/{}", self.reservoir_size, index + 1)
+        }
+    }
+    fn histogram_chart(&self) -> Chart {
+        let expected = self.expected_count();
+        let max_count = self.counts.iter().copied().max().unwrap_or(0) as f32;
+        Chart::new(
+            (70.0, 330.0),
+            (CANVAS_WIDTH - 130.0, 190.0),
+            (
+                self.stream_length as f32 + 0.5,
+                (max_count.max(expected) * 1.15).max(1.0),
+            ),
+        )
+        .with_min((0.5, 0.0))
+    }
+    /// How many times every item would be in the sample if every item came up exactly as often.
+    fn expected_count(&self) -> f32 {
+        self.runs as f32 * self.reservoir_size as f32 / self.stream_length as f32
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, DIAGRAM_HEIGHT);
+
+        diagram.add_node(DiagramNode::text(
+            "stream-title",
+            "Stream, with the probability that each item is kept when it arrives",
+            (CANVAS_WIDTH / 2.0, STREAM_Y - 45.0),
+        ));
+        let stream_left =
+            CANVAS_WIDTH / 2.0 - (self.stream_length - 1) as f32 * STREAM_SPACING / 2.0;
+        for index in 0..self.stream_length {
+            let mut node = DiagramNode::rect(
+                format!("item-{}", index),
+                index + 1,
+                (stream_left + index as f32 * STREAM_SPACING, STREAM_Y),
+                STREAM_ITEM_SIZE,
+            );
+            if index < highlights.arrived {
+                node = node.with_note(self.probability_text(index));
+            }
+            if highlights.current == Some(index) {
+                node.state = Some(ElementState::Active);
+            } else if highlights.reservoir.contains(&index) {
+                node.state = Some(ElementState::Selected);
+            } else if highlights.rejected.contains(&index) {
+                node.state = Some(ElementState::Rejected);
+            }
+            diagram.add_node(node);
+        }
+
+        diagram.add_node(DiagramNode::text(
+            "reservoir-title",
+            format!("Reservoir of {}", plural(self.reservoir_size, "item")),
+            (CANVAS_WIDTH / 2.0, RESERVOIR_Y - 40.0),
+        ));
+        let reservoir_left =
+            CANVAS_WIDTH / 2.0 - (self.reservoir_size - 1) as f32 * SLOT_SPACING / 2.0;
+        for slot in 0..self.reservoir_size {
+            let mut node = DiagramNode::rect(
+                format!("slot-{}", slot),
+                highlights
+                    .reservoir
+                    .get(slot)
+                    .map_or(String::new(), |index| (index + 1).to_string()),
+                (reservoir_left + slot as f32 * SLOT_SPACING, RESERVOIR_Y),
+                SLOT_SIZE,
+            )
+            .with_note(format!("slot {}", slot + 1));
+            if highlights.changed_slot == Some(slot) {
+                node.state = Some(ElementState::Active);
+            } else if slot < highlights.reservoir.len() {
+                node.state = Some(ElementState::Selected);
+            }
+            diagram.add_node(node);
+        }
+
+        let chart = self.histogram_chart();
+        let max_count = self.counts.iter().copied().max().unwrap_or(0);
+        chart.draw_axes(
+            &mut diagram,
+            "counts",
+            format!(
+                "How many times every item was in the sample over {}",
+                plural(self.runs, "run")
+            ),
+            ("item", max_count),
+        );
+        for (index, count) in self.counts.iter().enumerate() {
+            let x = index as f32 + 1.0;
+            let bar = chart.draw_bar(&mut diagram, (x, *count as f32), 0.7);
+            if highlights.sampled.contains(&index) {
+                bar.state = Some(ElementState::Selected);
+            }
+            let (bar_x, bar_y) = chart.point((x, 0.0));
+            diagram.add_node(DiagramNode::text(
+                format!("bar-{}", index),
+                index + 1,
+                (bar_x, bar_y + 14.0),
+            ));
+        }
+        if self.runs > 0 {
+            let expected = self.expected_count();
+            let line = chart.draw_line(
+                &mut diagram,
+                &[(0.5, expected), (self.stream_length as f32 + 0.5, expected)],
+            );
+            line.dashed = true;
+            line.state = Some(ElementState::Frontier);
+        }
+        diagram
+    }
+    fn push(&self, description: String, highlights: &Highlights, steps: &mut DiagramSteps) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = vec![StepList::new(
+            "Reservoir",
+            highlights
+                .reservoir
+                .iter()
+                .map(|index| format!("Item {}", index + 1))
+                .collect(),
+        )];
+    }
+    /// Samples the stream once and returns the indices of the items in every slot of the reservoir.
+    fn sample(&self, rng: &mut impl Rng) -> Vec<usize> {
+        let mut reservoir = Vec::with_capacity(self.reservoir_size);
+        for index in 0..self.stream_length {
+            if index < self.reservoir_size {
+                reservoir.push(index);
+            } else {
+                let slot = rng.gen_range(0..=index);
+                if slot < self.reservoir_size {
+                    reservoir[slot] = index;
+                }
+            }
+        }
+        reservoir
+    }
+    fn add_sample(&mut self, reservoir: &[usize]) {
+        for index in reservoir.iter() {
+            self.counts[*index] += 1;
+        }
+        self.runs += 1;
+    }
+    fn sample_stream(&mut self, steps: &mut DiagramSteps) {
+        let (n, k) = (self.stream_length, self.reservoir_size);
+        let mut rng = rand::thread_rng();
+        let mut highlights = Highlights::default();
+        self.push(
+            format!(
+                "Items arrive one at a time, and the length of the stream isn't known until it ends. To keep a uniform sample of {}, the first {} fill the reservoir, and after that, item i is kept with the probability {}/i, replacing a random item of the reservoir",
+                plural(k, "item"),
+                k,
+                k
+            ),
+            &highlights,
+            steps,
+        );
+        for index in 0..n {
+            highlights.arrived = index + 1;
+            highlights.current = Some(index);
+            let description = if index < k {
+                highlights.reservoir.push(index);
+                highlights.changed_slot = Some(index);
+                format!(
+                    "Item {}: the reservoir isn't full yet, so the item is kept in slot {}",
+                    index + 1,
+                    index + 1
+                )
+            } else {
+                let number = rng.gen_range(0..=index);
+                let probability = format!(
+                    "Item {} has the probability {} ≈ {:.2} of being kept",
+                    index + 1,
+                    self.probability_text(index),
+                    k as f32 / (index + 1) as f32
+                );
+                if number < k {
+                    let replaced = highlights.reservoir[number];
+                    highlights.reservoir[number] = index;
+                    highlights.rejected.push(replaced);
+                    highlights.changed_slot = Some(number);
+                    format!(
+                        "{}: the random number {} from 1 to {} is at most {}, so the item replaces item {} in slot {}",
+                        probability,
+                        number + 1,
+                        index + 1,
+                        k,
+                        replaced + 1,
+                        number + 1
+                    )
+                } else {
+                    highlights.rejected.push(index);
+                    highlights.changed_slot = None;
+                    format!(
+                        "{}, but the random number {} from 1 to {} is more than {}, so the item is skipped",
+                        probability,
+                        number + 1,
+                        index + 1,
+                        k
+                    )
+                }
+            };
+            self.push(description, &highlights, steps);
+        }
+
+        self.add_sample(&highlights.reservoir);
+        highlights.current = None;
+        highlights.changed_slot = None;
+        highlights.sampled = highlights.reservoir.clone();
+        let mut sample = highlights.reservoir.clone();
+        sample.sort_unstable();
+        self.push(
+            format!(
+                "The stream ended after {}, and the sample is {}. Every item is in it with the same probability {}/{}, since item i is kept with the probability {}/i when it arrives, and every later item j replaces it with the probability 1/j. {}",
+                plural(n, "item"),
+                join_numbers(&sample),
+                k,
+                n,
+                k,
+                self.uniformity_text()
+            ),
+            &highlights,
+            steps,
+        );
+    }
+    /// How evenly the items have come up over the runs so far.
+    fn uniformity_text(&self) -> String {
+        if self.runs == 1 {
+            return "A single run doesn't show that yet, but sampling the stream many times charts how often every item comes up".to_string();
+        }
+        let min = self.counts.iter().copied().min().unwrap_or(0);
+        let max = self.counts.iter().copied().max().unwrap_or(0);
+        format!(
+            "Over {}, every item was in the sample {} to {} times, while a perfectly uniform sample would have each of them {:.1} times",
+            plural(self.runs, "run"),
+            min,
+            max,
+            self.expected_count()
+        )
+    }
+    /// Reads the stream length and the reservoir size, and starts the chart over if either of them changed.
+    fn configure(&mut self, values: &[Value]) -> Result<(), String> {
+        let stream_length = values.first().map(|value| value.number()).unwrap_or(0);
+        if !(2..=MAX_STREAM_LENGTH as i64).contains(&stream_length) {
+            return Err(format!(
+                "The stream must have 2 to {} items",
+                MAX_STREAM_LENGTH
+            ));
+        }
+        let reservoir_size = values.get(1).map(|value| value.number()).unwrap_or(0);
+        if !(1..=MAX_RESERVOIR_SIZE as i64).contains(&reservoir_size) {
+            return Err(format!(
+                "The reservoir must have 1 to {} items",
+                MAX_RESERVOIR_SIZE
+            ));
+        }
+        if reservoir_size >= stream_length {
+            return Err("The reservoir must be smaller than the stream".to_string());
+        }
+        let (stream_length, reservoir_size) = (stream_length as usize, reservoir_size as usize);
+        if (stream_length, reservoir_size) != (self.stream_length, self.reservoir_size) {
+            *self = Self::new(stream_length, reservoir_size);
+        }
+        Ok(())
+    }
+}
+impl Visualizer for ReservoirSampling {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let inputs = vec![
+            Input::Number {
+                label: "Stream length",
+                default: 20,
+                min: 2,
+                max: MAX_STREAM_LENGTH as i64,
+            },
+            Input::Number {
+                label: "Reservoir size",
+                default: 5,
+                min: 1,
+                max: MAX_RESERVOIR_SIZE as i64,
+            },
+        ];
+        vec![
+            Operation::new("Sample stream", inputs.clone()),
+            Operation::new(
+                "Sample many times",
+                inputs
+                    .into_iter()
+                    .chain([Input::Number {
+                        label: "Runs",
+                        default: 1000,
+                        min: 1,
+                        max: MAX_RUNS as i64,
+                    }])
+                    .collect(),
+            ),
+            Operation::new("Reset chart", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Sample stream" => {
+                self.configure(values)?;
+                self.sample_stream(&mut steps);
+            }
+            "Sample many times" => {
+                self.configure(values)?;
+                let runs = values.get(2).map(|value| value.number()).unwrap_or(0);
+                if !(1..=MAX_RUNS as i64).contains(&runs) {
+                    return Err(format!(
+                        "The number of runs must be between 1 and {}",
+                        MAX_RUNS
+                    ));
+                }
+                let mut rng = rand::thread_rng();
+                let mut reservoir = vec![];
+                for _ in 0..runs {
+                    reservoir = self.sample(&mut rng);
+                    self.add_sample(&reservoir);
+                }
+                let highlights = Highlights {
+                    sampled: reservoir.clone(),
+                    reservoir,
+                    ..Default::default()
+                };
+                self.push(
+                    format!(
+                        "Sample the stream {} more. {}",
+                        plural(runs as usize, "time"),
+                        self.uniformity_text()
+                    ),
+                    &highlights,
+                    &mut steps,
+                );
+            }
+            "Reset chart" => {
+                *self = Self::new(self.stream_length, self.reservoir_size);
+                steps.push(
+                    "Forget how often every item was in the sample".to_string(),
+                    self.diagram(),
+                );
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The item numbers of the indices in a list like "1, 4 and 7".
+fn join_numbers(indices: &[usize]) -> String {
+    let numbers = indices
+        .iter()
+        .map(|index| (index + 1).to_string())
+        .collect::<Vec<String>>();
+    match numbers.as_slice() {
+        [] => String::new(),
+        [number] => number.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
@@ -0,0 +1,353 @@
+use diagram::*;
+use rand::Rng;
+
+use crate::*;
+
+const DIAGRAM_HEIGHT: f32 = 600.0;
+pub const MAX_ITEMS: usize = 8;
+pub const MAX_RUNS: usize = 100_000;
+const ARRAY_Y: f32 = 70.0;
+const CELL_SIZE: (f32, f32) = (42.0, 34.0);
+const CELL_SPACING: f32 = 48.0;
+/// The top of the heatmaps, below the labels of their positions.
+const HEATMAP_Y: f32 = 210.0;
+/// The largest width and height of the heatmaps.
+const HEATMAP_SIZE: f32 = 320.0;
+/// How strongly a difference from the uniform probability is colored. An item that ends up at a position a quarter
+/// more often than it should is shown as red, and one that ends up there a quarter less often as blue.
+const HEAT_SCALE: f32 = 0.5;
+const SHUFFLES: [&str; 2] = ["Fisher-Yates", "Naive"];
+
+/// A Fisher-Yates shuffle or a naive shuffle, by its index in [`SHUFFLES`].
+type ShuffleKind = usize;
+const FISHER_YATES: ShuffleKind = 0;
+const NAIVE: ShuffleKind = 1;
+
+/// Compares the Fisher-Yates shuffle with a naive shuffle that swaps every position with a random position. Both of
+/// them are run many times, and a heatmap of every shuffle shows how often every item ended up at every position. The
+/// Fisher-Yates shuffle makes every order equally likely, so its heatmap is even, but the naive shuffle is biased
+/// towards some orders.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shuffling {
+    len: usize,
+    /// How many times every item ended up at every position, by the item and then by the position, for every shuffle.
+    counts: [Vec<Vec<usize>>; 2],
+    runs: [usize; 2],
+}
+impl Shuffling {
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            counts: [vec![vec![0; len]; len], vec![vec![0; len]; len]],
+            runs: [0, 0],
+        }
+    }
+    /// The probability that the item ended up at the position, or `None` before the first run.
+    fn probability(&self, kind: ShuffleKind, item: usize, position: usize) -> Option<f32> {
+        (self.runs[kind] > 0)
+            .then(|| self.counts[kind][item][position] as f32 / self.runs[kind] as f32)
+    }
+    fn add_order(&mut self, kind: ShuffleKind, order: &[usize]) {
+        for (position, item) in order.iter().enumerate() {
+            self.counts[kind][*item][position] += 1;
+        }
+        self.runs[kind] += 1;
+    }
+    /// Draws the items in their current order with the swapped positions active and the final positions selected.
+    fn draw(&self, order: &[usize], swapped: &[usize], fixed: &[usize]) -> Diagram {
+        let mut diagram = Diagram::new(CANVAS_WIDTH, DIAGRAM_HEIGHT);
+        let left = CANVAS_WIDTH / 2.0 - (self.len - 1) as f32 * CELL_SPACING / 2.0;
+        for (position, item) in order.iter().enumerate() {
+            let mut node = DiagramNode::rect(
+                format!("item-{}", item),
+                item_name(*item),
+                (left + position as f32 * CELL_SPACING, ARRAY_Y),
+                CELL_SIZE,
+            )
+            .with_note(position + 1);
+            if swapped.contains(&position) {
+                node.state = Some(ElementState::Active);
+            } else if fixed.contains(&position) {
+                node.state = Some(ElementState::Selected);
+            }
+            diagram.add_node(node);
+        }
+        for kind in [FISHER_YATES, NAIVE] {
+            self.draw_heatmap(&mut diagram, kind);
+        }
+        diagram
+    }
+    /// Draws a grid with a row for every item and a column for every position, colored by how much more or less likely
+    /// the item was to end up at the position than it should be.
+    fn draw_heatmap(&self, diagram: &mut Diagram, kind: ShuffleKind) {
+        let cell_size = (HEATMAP_SIZE / self.len as f32).min(42.0);
+        let center = CANVAS_WIDTH / 4.0 * (1.0 + 2.0 * kind as f32);
+        let left = center - cell_size * self.len as f32 / 2.0;
+        let cell_center = |index: usize| (index as f32 + 0.5) * cell_size;
+        diagram.add_node(DiagramNode::text(
+            format!("heatmap-{}-title", kind),
+            format!(
+                "{} shuffle, {}",
+                SHUFFLES[kind],
+                plural(self.runs[kind], "run")
+            ),
+            (center, HEATMAP_Y - 55.0),
+        ));
+        diagram.add_node(DiagramNode::text(
+            format!("heatmap-{}-positions", kind),
+            "Position",
+            (center, HEATMAP_Y - 32.0),
+        ));
+        for index in 0..self.len {
+            diagram.add_node(DiagramNode::text(
+                format!("heatmap-{}-position-{}", kind, index),
+                index + 1,
+                (left + cell_center(index), HEATMAP_Y - 12.0),
+            ));
+            diagram.add_node(DiagramNode::text(
+                format!("heatmap-{}-item-{}", kind, index),
+                item_name(index),
+                (left - 14.0, HEATMAP_Y + cell_center(index)),
+            ));
+        }
+        for item in 0..self.len {
+            for position in 0..self.len {
+                let probability = self.probability(kind, item, position);
+                let mut node = DiagramNode::rect(
+                    format!("heatmap-{}-{}-{}", kind, item, position),
+                    probability.map_or(String::new(), |probability| {
+                        format!("{:.1}", probability * 100.0)
+                    }),
+                    (left + cell_center(position), HEATMAP_Y + cell_center(item)),
+                    (cell_size - 2.0, cell_size - 2.0),
+                );
+                if let Some(probability) = probability {
+                    let difference = probability * self.len as f32 - 1.0;
+                    node = node.with_fill(Fill::Heat(0.5 + difference / HEAT_SCALE));
+                }
+                diagram.add_node(node);
+            }
+        }
+    }
+    /// The swaps of shuffling the items once, in order. A swap can swap a position with itself.
+    fn swaps(&self, kind: ShuffleKind) -> Vec<(usize, usize)> {
+        let mut rng = rand::thread_rng();
+        match kind {
+            // The same swaps as `knuth_shuffle`
+            FISHER_YATES => (0..self.len)
+                .rev()
+                .map(|position| (position, rng.gen_range(0..=position)))
+                .collect(),
+            _ => (0..self.len)
+                .map(|position| (position, rng.gen_range(0..self.len)))
+                .collect(),
+        }
+    }
+    fn shuffle_once(&mut self, kind: ShuffleKind, steps: &mut DiagramSteps) {
+        let n = self.len;
+        let mut order = (0..n).collect::<Vec<usize>>();
+        steps.push(
+            match kind {
+                FISHER_YATES => "The Fisher-Yates shuffle goes through the positions from the last to the first, and swaps each of them with a random position up to it. After that, the position is final".to_string(),
+                _ => format!(
+                    "The naive shuffle goes through the positions from the first to the last, and swaps each of them with a random position from 1 to {}",
+                    n
+                ),
+            },
+            self.draw(&order, &[], &[]),
+        );
+        let mut fixed = vec![];
+        for (position, other) in self.swaps(kind) {
+            order.swap(position, other);
+            let range = if kind == FISHER_YATES {
+                position + 1
+            } else {
+                n
+            };
+            let mut description = if position == other {
+                format!(
+                    "The random position for position {} is {} from 1 to {}, which is the position itself, so nothing moves",
+                    position + 1,
+                    other + 1,
+                    range
+                )
+            } else {
+                format!(
+                    "Swap {} at position {} with {} at the random position {} from 1 to {}",
+                    item_name(order[other]),
+                    position + 1,
+                    item_name(order[position]),
+                    other + 1,
+                    range
+                )
+            };
+            if kind == FISHER_YATES {
+                fixed.push(position);
+                description += &format!(". Position {} is now final", position + 1);
+            }
+            steps.push(description, self.draw(&order, &[position, other], &fixed));
+        }
+
+        self.add_order(kind, &order);
+        let factorial = (1..=n).product::<usize>();
+        steps.push(
+            format!(
+                "The shuffled order is {}. {}",
+                order.iter().map(|item| item_name(*item)).collect::<String>(),
+                if kind == FISHER_YATES {
+                    format!(
+                        "The Fisher-Yates shuffle picks one of {} equally likely ways to swap, and each of them gives a different one of the {}! = {} orders, so every order is as likely as the others",
+                        factorial, n, factorial
+                    )
+                } else if n > 2 {
+                    format!(
+                        "The naive shuffle picks one of {}^{} = {} equally likely ways to swap, but they can't be split evenly between the {}! = {} orders, so some orders are more likely than others",
+                        n,
+                        n,
+                        n.pow(n as u32),
+                        n,
+                        factorial
+                    )
+                } else {
+                    "With only two items, the naive shuffle happens to be fair, but with more items, some orders are more likely than others".to_string()
+                }
+            ),
+            self.draw(&order, &[], &(0..n).collect::<Vec<usize>>()),
+        );
+    }
+    fn run_many(&mut self, runs: usize, steps: &mut DiagramSteps) {
+        let mut rng = rand::thread_rng();
+        let items = (0..self.len).collect::<Vec<usize>>();
+        for _ in 0..runs {
+            let order = knuth_shuffle(items.clone());
+            self.add_order(FISHER_YATES, &order);
+
+            let mut order = items.clone();
+            for position in 0..self.len {
+                order.swap(position, rng.gen_range(0..self.len));
+            }
+            self.add_order(NAIVE, &order);
+        }
+        steps.push(
+            format!(
+                "Shuffle the items {} with both shuffles. Every item should end up at every position {:.1}% of the time. {}. {}",
+                plural(runs, "time"),
+                100.0 / self.len as f32,
+                self.range_text(FISHER_YATES),
+                self.range_text(NAIVE)
+            ),
+            self.draw(&items, &[], &[]),
+        );
+    }
+    /// The smallest and the largest probability that an item ended up at a position.
+    fn range_text(&self, kind: ShuffleKind) -> String {
+        let probabilities = (0..self.len)
+            .flat_map(|item| (0..self.len).map(move |position| (item, position)))
+            .filter_map(|(item, position)| self.probability(kind, item, position))
+            .collect::<Vec<f32>>();
+        let min = probabilities.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = probabilities.iter().copied().fold(0.0, f32::max);
+        format!(
+            "With the {} shuffle, the items ended up at the positions {:.1}% to {:.1}% of the time",
+            if kind == FISHER_YATES {
+                "Fisher-Yates"
+            } else {
+                "naive"
+            },
+            min * 100.0,
+            max * 100.0
+        )
+    }
+    /// Reads the number of items, and starts the heatmaps over if it changed.
+    fn configure(&mut self, value: Option<&Value>) -> Result<(), String> {
+        let len = value.map(|value| value.number()).unwrap_or(0);
+        if !(2..=MAX_ITEMS as i64).contains(&len) {
+            return Err(format!(
+                "The number of items must be between 2 and {}",
+                MAX_ITEMS
+            ));
+        }
+        if len as usize != self.len {
+            *self = Self::new(len as usize);
+        }
+        Ok(())
+    }
+}
+impl Visualizer for Shuffling {
+    fn diagram(&self) -> Diagram {
+        self.draw(&(0..self.len).collect::<Vec<usize>>(), &[], &[])
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let items_input = Input::Number {
+            label: "Items",
+            default: 3,
+            min: 2,
+            max: MAX_ITEMS as i64,
+        };
+        vec![
+            Operation::new(
+                "Shuffle many times",
+                vec![
+                    items_input.clone(),
+                    Input::Number {
+                        label: "Runs",
+                        default: 10_000,
+                        min: 1,
+                        max: MAX_RUNS as i64,
+                    },
+                ],
+            ),
+            Operation::new(
+                "Shuffle once",
+                vec![
+                    items_input,
+                    Input::Choice {
+                        label: "Shuffle",
+                        choices: SHUFFLES.iter().map(|shuffle| shuffle.to_string()).collect(),
+                    },
+                ],
+            ),
+            Operation::new("Reset heatmaps", vec![]),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Shuffle many times" => {
+                self.configure(values.first())?;
+                let runs = values.get(1).map(|value| value.number()).unwrap_or(0);
+                if !(1..=MAX_RUNS as i64).contains(&runs) {
+                    return Err(format!(
+                        "The number of runs must be between 1 and {}",
+                        MAX_RUNS
+                    ));
+                }
+                self.run_many(runs as usize, &mut steps);
+            }
+            "Shuffle once" => {
+                self.configure(values.first())?;
+                let kind =
+                    if values.get(1).map(|value| value.text()).as_deref() == Some(SHUFFLES[1]) {
+                        NAIVE
+                    } else {
+                        FISHER_YATES
+                    };
+                self.shuffle_once(kind, &mut steps);
+            }
+            "Reset heatmaps" => {
+                *self = Self::new(self.len);
+                steps.push(
+                    "Forget where the items ended up".to_string(),
+                    self.diagram(),
+                );
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The name of the item that starts at the index, like "A".
+fn item_name(index: usize) -> String {
+    ((b'A' + index as u8) as char).to_string()
+}
@@ -0,0 +1,344 @@
+//! This crate contains my implementations of searching algorithms, like binary search.
+//! Each algorithm records a diagram of the sorted array at every step, with pointers to the indices that it uses.
+pub mod searching_algorithms;
+
+use std::collections::BTreeMap;
+
+use diagram::*;
+use rand::Rng;
+
+pub const ARRAY_WIDTH: f32 = 800.0;
+pub const ARRAY_HEIGHT: f32 = 260.0;
+pub const MAX_ARRAY_SIZE: usize = 16;
+pub const MAX_VALUE: i64 = 99;
+/// The y of the row of cells.
+const ARRAY_Y: f32 = 150.0;
+const CELL_SIZE: (f32, f32) = (42.0, 30.0);
+const CELL_SPACING: f32 = 46.0;
+/// The vertical distance between pointers that point to the same index.
+const POINTER_SPACING: f32 = 20.0;
+
+/// Runs a search on the array of a [`Search`] and returns the index of the target if it was found.
+pub type SearchFunc = fn(&mut Search) -> Option<usize>;
+
+/// Records the steps of a search in a sorted array. The indices that the search reads are its probes, and the
+/// indices that can't have the target anymore are shaded as discarded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Search {
+    pub values: Vec<i64>,
+    pub target: i64,
+    pub steps: DiagramSteps,
+    discarded: Vec<bool>,
+    probes: Vec<usize>,
+}
+impl Search {
+    pub fn new(values: &[i64], target: i64) -> Self {
+        Self {
+            values: values.to_vec(),
+            target,
+            steps: DiagramSteps::new(),
+            discarded: vec![false; values.len()],
+            probes: vec![],
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    /// Reads the value at the index, which counts as a probe.
+    pub fn probe(&mut self, index: usize) -> i64 {
+        self.probes.push(index);
+        self.values[index]
+    }
+    pub fn probe_count(&self) -> usize {
+        self.probes.len()
+    }
+    /// Rules out the indices from `start` to `end`, inclusive.
+    pub fn discard(&mut self, start: usize, end: usize) {
+        let end = end.min(self.len() - 1);
+        for discarded in self.discarded[start..=end].iter_mut() {
+            *discarded = true;
+        }
+    }
+    /// Adds a step with pointers to indices, which can be outside of the array after the search has passed its end.
+    /// The cells that aren't given a state are shown as rejected if they have been discarded.
+    pub fn push(
+        &mut self,
+        description: String,
+        pointers: &[(&'static str, i64)],
+        states: &[(usize, ElementState)],
+    ) {
+        let diagram = self.draw(
+            Some(self.target),
+            pointers,
+            &states.iter().copied().collect(),
+        );
+        let probes = self
+            .probes
+            .iter()
+            .map(|index| format!("a[{}] = {}", index, self.values[*index]))
+            .collect();
+        let step = self.steps.push(description, diagram);
+        step.lists = vec![StepList::new("Probes", probes)];
+    }
+    /// Adds the last step of a search that found the target at the index.
+    pub fn found(&mut self, index: usize, pointers: &[(&'static str, i64)]) {
+        self.push(
+            format!(
+                "a[{}] = {}, so {} is found at index {} after {}",
+                index,
+                self.target,
+                self.target,
+                index,
+                probe_text(self.probe_count())
+            ),
+            pointers,
+            &[(index, ElementState::Selected)],
+        );
+    }
+    /// Adds the last step of a search that ruled out every index.
+    pub fn not_found(&mut self, reason: &str, pointers: &[(&'static str, i64)]) {
+        self.discard(0, self.len() - 1);
+        self.push(
+            format!(
+                "{}, so {} isn't in the array. The search took {}",
+                reason,
+                self.target,
+                probe_text(self.probe_count())
+            ),
+            pointers,
+            &[],
+        );
+    }
+    /// Draws all of the probes of the search, numbered in the order that they were made.
+    pub fn draw_probes(&self, found: Option<usize>) -> Diagram {
+        let mut states = self
+            .probes
+            .iter()
+            .map(|index| (*index, ElementState::Active))
+            .collect::<BTreeMap<usize, ElementState>>();
+        if let Some(index) = found {
+            states.insert(index, ElementState::Selected);
+        }
+        let mut diagram =
+            Search::new(&self.values, self.target).draw(Some(self.target), &[], &states);
+        for (order, index) in self.probes.iter().enumerate() {
+            diagram.add_node(DiagramNode::text(
+                format!("probe-{}", order),
+                order + 1,
+                (
+                    cell_x(*index as i64, self.len()),
+                    ARRAY_Y - CELL_SIZE.1 / 2.0 - 16.0,
+                ),
+            ));
+        }
+        diagram
+    }
+    fn draw(
+        &self,
+        target: Option<i64>,
+        pointers: &[(&'static str, i64)],
+        states: &BTreeMap<usize, ElementState>,
+    ) -> Diagram {
+        let mut diagram = Diagram::new(ARRAY_WIDTH, ARRAY_HEIGHT);
+        let size = self.len();
+        // The discarded cells are shaded in runs of neighboring cells
+        let mut start = None;
+        for index in 0..=size {
+            match (start, self.discarded.get(index).copied().unwrap_or(false)) {
+                (None, true) => start = Some(index),
+                (Some(first), false) => {
+                    diagram.add_figure(
+                        Figure::new(FigureKind::Rect {
+                            position: (
+                                cell_x(first as i64, size) - CELL_SIZE.0 / 2.0 - 4.0,
+                                ARRAY_Y - CELL_SIZE.1 / 2.0 - 4.0,
+                            ),
+                            size: (
+                                CELL_SPACING * (index - 1 - first) as f32 + CELL_SIZE.0 + 8.0,
+                                CELL_SIZE.1 + 8.0,
+                            ),
+                        })
+                        .with_state(ElementState::Rejected),
+                    );
+                    start = None;
+                }
+                _ => (),
+            }
+        }
+        if let Some(target) = target {
+            diagram.add_node(DiagramNode::text(
+                "target",
+                format!("Find {}", target),
+                (ARRAY_WIDTH / 2.0, 30.0),
+            ));
+        }
+        for (index, value) in self.values.iter().enumerate() {
+            let mut node = DiagramNode::rect(
+                format!("cell-{}", index),
+                value,
+                (cell_x(index as i64, size), ARRAY_Y),
+                CELL_SIZE,
+            )
+            .with_note(index);
+            node.state = states
+                .get(&index)
+                .copied()
+                .or_else(|| self.discarded[index].then_some(ElementState::Rejected));
+            diagram.add_node(node);
+        }
+        // Pointers to the same index are stacked above it
+        let mut stacked = BTreeMap::<i64, usize>::new();
+        for (name, index) in pointers {
+            let level = stacked.entry(*index).or_default();
+            diagram.add_node(DiagramNode::text(
+                format!("pointer-{}", name),
+                name,
+                (
+                    cell_x(*index, size),
+                    ARRAY_Y - CELL_SIZE.1 / 2.0 - 16.0 - POINTER_SPACING * *level as f32,
+                ),
+            ));
+            *level += 1;
+        }
+        diagram
+    }
+}
+
+/// A visualizer that runs a searching algorithm on a sorted array that can be changed.
+#[derive(Clone, Debug)]
+pub struct ArraySearch {
+    values: Vec<i64>,
+    search: SearchFunc,
+}
+impl ArraySearch {
+    /// The values are sorted, since the algorithms only work on sorted arrays.
+    pub fn new(search: SearchFunc, values: &[i64]) -> Self {
+        let mut values = values.to_vec();
+        values.sort_unstable();
+        Self { values, search }
+    }
+}
+impl Visualizer for ArraySearch {
+    fn diagram(&self) -> Diagram {
+        Search::new(&self.values, 0).draw(None, &[], &BTreeMap::new())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Search",
+                vec![Input::Number {
+                    label: "Value to find",
+                    default: self.values[self.values.len() * 2 / 3],
+                    min: 0,
+                    max: MAX_VALUE,
+                }],
+            ),
+            Operation::new(
+                "Compare probes",
+                vec![Input::Number {
+                    label: "Value to compare",
+                    default: self.values[self.values.len() * 2 / 3],
+                    min: 0,
+                    max: MAX_VALUE,
+                }],
+            ),
+            Operation::new(
+                "Change array",
+                vec![Input::Text {
+                    label: "Values",
+                    default: self
+                        .values
+                        .iter()
+                        .map(|value| value.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                }],
+            ),
+            Operation::new(
+                "Random array",
+                vec![Input::Number {
+                    label: "Number of values",
+                    default: self.values.len() as i64,
+                    min: 1,
+                    max: MAX_ARRAY_SIZE as i64,
+                }],
+            ),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let value = values.first().cloned().unwrap_or(Value::Number(0));
+        match operation {
+            "Search" => {
+                let mut search = Search::new(&self.values, value.number());
+                (self.search)(&mut search);
+                Ok(search.steps)
+            }
+            "Compare probes" => {
+                let mut steps = DiagramSteps::new();
+                let mut counts = vec![];
+                for (name, search_func) in searching_algorithms::ALGORITHMS {
+                    let mut search = Search::new(&self.values, value.number());
+                    let found = search_func(&mut search);
+                    counts.push(format!("{}: {}", name, search.probe_count()));
+                    let outcome = match found {
+                        Some(index) => format!("finds {} at index {}", search.target, index),
+                        None => format!("finds out that {} isn't in the array", search.target),
+                    };
+                    let step = steps.push(
+                        format!(
+                            "{} {} with {}",
+                            name,
+                            outcome,
+                            probe_text(search.probe_count())
+                        ),
+                        search.draw_probes(found),
+                    );
+                    step.lists = vec![StepList::new("Probes", counts.clone())];
+                }
+                Ok(steps)
+            }
+            "Change array" | "Random array" => {
+                self.values = if operation == "Change array" {
+                    parse_values(&value.text(), 0, MAX_VALUE, MAX_ARRAY_SIZE)?
+                } else {
+                    random_values(value.number())?
+                };
+                self.values.sort_unstable();
+                let mut steps = DiagramSteps::new();
+                steps.push(
+                    "Sort the values, since the search needs a sorted array".to_string(),
+                    self.diagram(),
+                );
+                Ok(steps)
+            }
+            _ => Err(format!("Unknown operation \"{}\"", operation)),
+        }
+    }
+}
+
+/// The x of the center of the cell at the index, which can be outside of the array.
+fn cell_x(index: i64, size: usize) -> f32 {
+    ARRAY_WIDTH / 2.0 + CELL_SPACING * (index as f32 - (size as f32 - 1.0) / 2.0)
+}
+
+fn probe_text(count: usize) -> String {
+    if count == 1 {
+        "1 probe".to_string()
+    } else {
+        format!("{} probes", count)
+    }
+}
+
+fn random_values(size: i64) -> Result<Vec<i64>, String> {
+    if !(1..=MAX_ARRAY_SIZE as i64).contains(&size) {
+        return Err(format!(
+            "The array must have 1 to {} values",
+            MAX_ARRAY_SIZE
+        ));
+    }
+    let mut rng = rand::thread_rng();
+    Ok((0..size).map(|_| rng.gen_range(0..=MAX_VALUE)).collect())
+}
@@ -0,0 +1,69 @@
+use diagram::ElementState;
+
+use crate::Search;
+
+/// Halves the range where the target can be by comparing it to the middle value of the range, until the middle
+/// value is the target or the range is empty.
+pub fn binary_search(search: &mut Search) -> Option<usize> {
+    let high = search.len() as i64 - 1;
+    search.push(
+        format!(
+            "The target {} can be anywhere from low to high",
+            search.target
+        ),
+        &[("low", 0), ("high", high)],
+        &[],
+    );
+    search_range(search, 0, high)
+}
+
+/// Runs binary search from `low` to `high`, inclusive, which other searches use after narrowing down the range.
+pub(crate) fn search_range(search: &mut Search, mut low: i64, mut high: i64) -> Option<usize> {
+    let target = search.target;
+    while low <= high {
+        let mid = (low + high) / 2;
+        let pointers = [("low", low), ("mid", mid), ("high", high)];
+        let value = search.probe(mid as usize);
+        if value == target {
+            search.found(mid as usize, &pointers);
+            return Some(mid as usize);
+        }
+        let comparison = if value < target { "<" } else { ">" };
+        search.push(
+            format!(
+                "mid = ({} + {}) / 2 = {}, and a[{}] = {} {} {}",
+                low, high, mid, mid, value, comparison, target
+            ),
+            &pointers,
+            &[(mid as usize, ElementState::Active)],
+        );
+        if value < target {
+            search.discard(low as usize, mid as usize);
+            low = mid + 1;
+            search.push(
+                format!(
+                    "The target is after mid, so discard the left half and move low to {}",
+                    low
+                ),
+                &[("low", low), ("high", high)],
+                &[],
+            );
+        } else {
+            search.discard(mid as usize, high as usize);
+            high = mid - 1;
+            search.push(
+                format!(
+                    "The target is before mid, so discard the right half and move high to {}",
+                    high
+                ),
+                &[("low", low), ("high", high)],
+                &[],
+            );
+        }
+    }
+    search.not_found(
+        &format!("low = {} is past high = {}", low, high),
+        &[("low", low), ("high", high)],
+    );
+    None
+}
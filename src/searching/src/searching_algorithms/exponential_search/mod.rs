@@ -0,0 +1,83 @@
+use diagram::ElementState;
+
+use crate::Search;
+
+use super::binary_search::search_range;
+
+/// Doubles a bound until the value at it isn't smaller than the target, and then runs binary search between the
+/// previous bound and the current one. Searches for values near the start take only a few probes.
+pub fn exponential_search(search: &mut Search) -> Option<usize> {
+    let (target, size) = (search.target, search.len());
+    let first = search.probe(0);
+    if first == target {
+        search.found(0, &[("bound", 0)]);
+        return Some(0);
+    }
+    if first > target {
+        search.not_found(
+            &format!("a[0] = {} > {} is the smallest value", first, target),
+            &[("bound", 0)],
+        );
+        return None;
+    }
+    search.discard(0, 0);
+    search.push(
+        format!(
+            "a[0] = {} < {}, so start doubling the bound from 1",
+            first, target
+        ),
+        &[("bound", 0)],
+        &[(0, ElementState::Active)],
+    );
+    let mut bound = 1;
+    while bound < size {
+        let value = search.probe(bound);
+        if value == target {
+            search.found(bound, &[("bound", bound as i64)]);
+            return Some(bound);
+        }
+        if value > target {
+            search.push(
+                format!(
+                    "a[{}] = {} > {}, so the target can only be between the previous bound {} and {}",
+                    bound,
+                    value,
+                    target,
+                    bound / 2,
+                    bound
+                ),
+                &[("bound", bound as i64)],
+                &[(bound, ElementState::Active)],
+            );
+            search.discard(bound, size - 1);
+            break;
+        }
+        search.discard(bound / 2, bound);
+        search.push(
+            format!(
+                "a[{}] = {} < {}, so double the bound to {}{}",
+                bound,
+                value,
+                target,
+                bound * 2,
+                if bound * 2 >= size {
+                    ", which is past the end of the array"
+                } else {
+                    ""
+                }
+            ),
+            &[("bound", bound as i64)],
+            &[(bound, ElementState::Active)],
+        );
+        bound *= 2;
+    }
+    // The values up to the previous bound are smaller than the target, and the value at the bound is larger
+    let low = bound as i64 / 2 + 1;
+    let high = (bound as i64 - 1).min(size as i64 - 1);
+    search.push(
+        format!("Run binary search from {} to {}", low, high),
+        &[("low", low), ("high", high)],
+        &[],
+    );
+    search_range(search, low, high)
+}
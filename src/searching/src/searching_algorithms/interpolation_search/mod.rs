@@ -0,0 +1,84 @@
+use diagram::ElementState;
+
+use crate::Search;
+
+/// Guesses where the target is from how far it is between the values at the ends of the range, like looking up a
+/// name in a phone book, and then narrows the range like binary search.
+pub fn interpolation_search(search: &mut Search) -> Option<usize> {
+    let target = search.target;
+    let (mut low, mut high) = (0, search.len() as i64 - 1);
+    search.push(
+        format!("The target {} can be anywhere from low to high", target),
+        &[("low", low), ("high", high)],
+        &[],
+    );
+    loop {
+        if low > high {
+            search.not_found(
+                &format!("low = {} is past high = {}", low, high),
+                &[("low", low), ("high", high)],
+            );
+            return None;
+        }
+        let (low_value, high_value) = (search.values[low as usize], search.values[high as usize]);
+        if target < low_value || target > high_value {
+            search.not_found(
+                &format!(
+                    "{} is outside of a[{}] = {} to a[{}] = {}",
+                    target, low, low_value, high, high_value
+                ),
+                &[("low", low), ("high", high)],
+            );
+            return None;
+        }
+        let pos = if high_value == low_value {
+            low
+        } else {
+            low + (target - low_value) * (high - low) / (high_value - low_value)
+        };
+        let pointers = [("low", low), ("pos", pos), ("high", high)];
+        let value = search.probe(pos as usize);
+        let guess = if high_value == low_value {
+            format!(
+                "All values from low to high are {}, so pos = low = {}",
+                low_value, pos
+            )
+        } else {
+            format!(
+                "pos = {} + ({} − {}) · ({} − {}) / ({} − {}) = {}",
+                low, target, low_value, high, low, high_value, low_value, pos
+            )
+        };
+        if value == target {
+            search.push(guess, &pointers, &[(pos as usize, ElementState::Active)]);
+            search.found(pos as usize, &pointers);
+            return Some(pos as usize);
+        }
+        let comparison = if value < target { "<" } else { ">" };
+        search.push(
+            format!(
+                "{}, and a[{}] = {} {} {}",
+                guess, pos, value, comparison, target
+            ),
+            &pointers,
+            &[(pos as usize, ElementState::Active)],
+        );
+        if value < target {
+            search.discard(low as usize, pos as usize);
+            low = pos + 1;
+            search.push(
+                format!("The target is after pos, so move low to {}", low),
+                &[("low", low), ("high", high)],
+                &[],
+            );
+        } else {
+            search.discard(pos as usize, high as usize);
+            high = pos - 1;
+            search.push(
+                format!("The target is before pos, so move high to {}", high),
+                &[("low", low), ("high", high)],
+                &[],
+            );
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use diagram::ElementState;
+
+use crate::Search;
+
+/// Jumps ahead in blocks of √n values until the last value of a block isn't smaller than the target, and then
+/// looks through that block one value at a time.
+pub fn jump_search(search: &mut Search) -> Option<usize> {
+    let (target, size) = (search.target, search.len());
+    let block = ((size as f64).sqrt() as usize).max(1);
+    let mut start = 0;
+    let mut end = block.min(size) - 1;
+    search.push(
+        format!(
+            "The array has {} values, so jump ahead in blocks of √{} ≈ {} values",
+            size, size, block
+        ),
+        &[("start", start as i64), ("end", end as i64)],
+        &[],
+    );
+    loop {
+        let pointers = [("start", start as i64), ("end", end as i64)];
+        let value = search.probe(end);
+        if value >= target {
+            search.push(
+                format!(
+                    "a[{}] = {} ≥ {}, so the target can only be in the block from {} to {}",
+                    end, value, target, start, end
+                ),
+                &pointers,
+                &[(end, ElementState::Active)],
+            );
+            break;
+        }
+        search.discard(start, end);
+        if end == size - 1 {
+            search.not_found(
+                &format!("a[{}] = {} < {} is the last value", end, value, target),
+                &pointers,
+            );
+            return None;
+        }
+        search.push(
+            format!(
+                "a[{}] = {} < {}, so the target is after this block. Jump to the next block",
+                end, value, target
+            ),
+            &pointers,
+            &[(end, ElementState::Active)],
+        );
+        start = end + 1;
+        end = (end + block).min(size - 1);
+    }
+    if end + 1 < size {
+        search.discard(end + 1, size - 1);
+    }
+    for index in start..=end {
+        let pointers = [
+            ("start", start as i64),
+            ("i", index as i64),
+            ("end", end as i64),
+        ];
+        // The last value of the block was already read
+        let value = if index == end {
+            search.values[end]
+        } else {
+            search.probe(index)
+        };
+        if value == target {
+            search.found(index, &pointers);
+            return Some(index);
+        }
+        if value > target {
+            search.not_found(
+                &format!(
+                    "a[{}] = {} > {}, and the values only grow from here",
+                    index, value, target
+                ),
+                &pointers,
+            );
+            return None;
+        }
+        search.discard(index, index);
+        search.push(
+            format!(
+                "a[{}] = {} < {}, so step to the next value",
+                index, value, target
+            ),
+            &pointers,
+            &[(index, ElementState::Active)],
+        );
+    }
+    unreachable!("The last value of the block isn't smaller than the target")
+}
@@ -0,0 +1,20 @@
+//! A collection of algorithms that search for a value in a sorted array.
+mod binary_search;
+mod exponential_search;
+mod interpolation_search;
+mod jump_search;
+
+pub use binary_search::binary_search;
+pub use exponential_search::exponential_search;
+pub use interpolation_search::interpolation_search;
+pub use jump_search::jump_search;
+
+use crate::SearchFunc;
+
+/// All of the algorithms by their names, so that their probes can be compared on the same array.
+pub const ALGORITHMS: [(&str, SearchFunc); 4] = [
+    ("Binary search", binary_search),
+    ("Exponential search", exponential_search),
+    ("Interpolation search", interpolation_search),
+    ("Jump search", jump_search),
+];
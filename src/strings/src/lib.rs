@@ -0,0 +1,202 @@
+//! This crate contains my implementations of string algorithms, like pattern matching.
+//! Each algorithm records a diagram of the strings and its tables at every step so that it can be visualized.
+pub mod string_algorithms;
+
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+pub const STRING_WIDTH: f32 = 800.0;
+/// The largest number of characters in a text, so that they fit side by side.
+pub const MAX_TEXT_LENGTH: usize = 24;
+pub const MAX_PATTERN_LENGTH: usize = 12;
+pub const CHAR_SIZE: (f32, f32) = (28.0, 30.0);
+pub const CHAR_SPACING: f32 = 32.0;
+/// The largest number of characters in the strings of a table, so that the table fits.
+pub const MAX_WORD_LENGTH: usize = 10;
+/// The y of the characters above a table.
+pub const TABLE_Y: f32 = 40.0;
+pub const TABLE_CELL_SIZE: (f32, f32) = (30.0, 24.0);
+/// The distance between the centers of neighboring cells of a table, which leaves room for arrows between them.
+pub const TABLE_SPACING: (f32, f32) = (44.0, 36.0);
+
+/// The x of the center of the character at the index in a centered row of `size` characters.
+/// The index can be outside of the row, like for a pattern that is shifted past the end of a text.
+pub fn char_x(index: i64, size: usize) -> f32 {
+    STRING_WIDTH / 2.0 + CHAR_SPACING * (index as f32 - (size as f32 - 1.0) / 2.0)
+}
+
+/// Draws the characters as cells with the keys `{prefix}-{index}`, shifted by `shift` cells in a centered row of
+/// `size` cells. The indices are shown below the cells if `indices` is true.
+pub fn draw_chars(
+    diagram: &mut Diagram,
+    prefix: &str,
+    chars: &[char],
+    (y, shift, size): (f32, i64, usize),
+    states: &BTreeMap<usize, ElementState>,
+    indices: bool,
+) {
+    for (index, char) in chars.iter().enumerate() {
+        let mut node = DiagramNode::rect(
+            format!("{}-{}", prefix, index),
+            char,
+            (char_x(shift + index as i64, size), y),
+            CHAR_SIZE,
+        );
+        if indices {
+            node.note = Some(index.to_string());
+        }
+        node.state = states.get(&index).copied();
+        diagram.add_node(node);
+    }
+}
+
+/// Draws a row of numbers like the values of a table, where the values that haven't been computed yet are empty.
+/// The label is shown to the left of the row.
+pub fn draw_values(
+    diagram: &mut Diagram,
+    prefix: &str,
+    label: &str,
+    values: &[Option<i64>],
+    (y, shift, size): (f32, i64, usize),
+    states: &BTreeMap<usize, ElementState>,
+) {
+    diagram.add_node(DiagramNode::text(
+        format!("{}-label", prefix),
+        label,
+        (char_x(shift - 1, size) - 12.0, y),
+    ));
+    for (index, value) in values.iter().enumerate() {
+        let mut node = DiagramNode::rect(
+            format!("{}-{}", prefix, index),
+            value.map(|value| value.to_string()).unwrap_or_default(),
+            (char_x(shift + index as i64, size), y),
+            CHAR_SIZE,
+        );
+        node.state = states.get(&index).copied();
+        diagram.add_node(node);
+    }
+}
+
+/// The key of the cell of a table in the row and the column.
+pub fn table_key(row: usize, column: usize) -> String {
+    format!("cell-{}-{}", row, column)
+}
+
+/// The center of the cell of a table of `columns` columns, where the row and the column -1 are the characters
+/// of the strings next to the table.
+pub fn table_position(row: i64, column: i64, columns: usize) -> (f32, f32) {
+    (
+        STRING_WIDTH / 2.0 + TABLE_SPACING.0 * (column as f32 - (columns as f32 - 2.0) / 2.0),
+        TABLE_Y + TABLE_SPACING.1 * (row + 1) as f32,
+    )
+}
+
+/// Draws a table of the prefixes of two strings, where the cell in row *i* and column *j* is for the first *i*
+/// characters of `rows` and the first *j* characters of `columns`. The characters are drawn to the left and above
+/// the table, and the cells that haven't been computed yet are empty.
+pub fn draw_table(
+    diagram: &mut Diagram,
+    (rows, columns): (&[char], &[char]),
+    values: &[Vec<Option<i64>>],
+    states: &BTreeMap<(usize, usize), ElementState>,
+    (row_states, column_states): (
+        &BTreeMap<usize, ElementState>,
+        &BTreeMap<usize, ElementState>,
+    ),
+) {
+    let size = columns.len() + 1;
+    // The empty prefixes
+    diagram.add_node(DiagramNode::text(
+        "row-empty",
+        "ε",
+        table_position(0, -1, size),
+    ));
+    diagram.add_node(DiagramNode::text(
+        "column-empty",
+        "ε",
+        table_position(-1, 0, size),
+    ));
+    for (index, char) in rows.iter().enumerate() {
+        let mut node = DiagramNode::rect(
+            format!("row-{}", index),
+            char,
+            table_position(index as i64 + 1, -1, size),
+            TABLE_CELL_SIZE,
+        );
+        node.state = row_states.get(&index).copied();
+        diagram.add_node(node);
+    }
+    for (index, char) in columns.iter().enumerate() {
+        let mut node = DiagramNode::rect(
+            format!("column-{}", index),
+            char,
+            table_position(-1, index as i64 + 1, size),
+            TABLE_CELL_SIZE,
+        );
+        node.state = column_states.get(&index).copied();
+        diagram.add_node(node);
+    }
+    for (i, row) in values.iter().enumerate() {
+        for (j, value) in row.iter().enumerate() {
+            let mut node = DiagramNode::rect(
+                table_key(i, j),
+                value.map(|value| value.to_string()).unwrap_or_default(),
+                table_position(i as i64, j as i64, size),
+                TABLE_CELL_SIZE,
+            );
+            node.state = states.get(&(i, j)).copied();
+            diagram.add_node(node);
+        }
+    }
+}
+
+/// Checks that the text has 1 to `max_length` characters and returns them.
+pub fn check_text(text: &str, name: &str, max_length: usize) -> Result<Vec<char>, String> {
+    let chars = text
+        .chars()
+        .filter(|char| !char.is_control())
+        .collect::<Vec<char>>();
+    if chars.is_empty() || chars.len() > max_length {
+        return Err(format!(
+            "The {} must have 1 to {} characters",
+            name, max_length
+        ));
+    }
+    Ok(chars)
+}
+
+/// The inputs of a search for a pattern in a text, which start as the current text and pattern.
+pub fn search_inputs(text: &[char], pattern: &[char]) -> Vec<Input> {
+    vec![
+        Input::Text {
+            label: "Text",
+            default: text.iter().collect(),
+        },
+        Input::Text {
+            label: "Pattern",
+            default: pattern.iter().collect(),
+        },
+    ]
+}
+
+/// Checks the text and the pattern of a search, which are the first two values.
+pub fn parse_search(values: &[Value]) -> Result<(Vec<char>, Vec<char>), String> {
+    let text = values.first().map(|value| value.text()).unwrap_or_default();
+    let pattern = values.get(1).map(|value| value.text()).unwrap_or_default();
+    Ok((
+        check_text(&text, "text", MAX_TEXT_LENGTH)?,
+        check_text(&pattern, "pattern", MAX_PATTERN_LENGTH)?,
+    ))
+}
+
+/// The matches that have been found so far, by the indices where they start.
+pub fn matches_list(matches: &[usize]) -> StepList {
+    StepList::new(
+        "Matches",
+        matches
+            .iter()
+            .map(|index| format!("At index {}", index))
+            .collect(),
+    )
+}
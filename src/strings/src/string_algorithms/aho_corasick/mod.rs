@@ -0,0 +1,472 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use diagram::*;
+
+use crate::*;
+
+const MAX_PATTERNS: usize = 6;
+const MAX_AHO_CORASICK_PATTERN_LENGTH: usize = 8;
+const AHO_CORASICK_HEIGHT: f32 = 520.0;
+/// The height of the part of the diagram that the trie is fit in.
+const TRIE_HEIGHT: f32 = 420.0;
+const TEXT_Y: f32 = 470.0;
+const ROOT: usize = 0;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct AutomatonNode {
+    /// The string that the path from the root to the node spells.
+    prefix: String,
+    children: BTreeMap<char, usize>,
+    /// The node of the longest proper suffix of the prefix that is also in the trie.
+    fail: Option<usize>,
+    /// The patterns that end at the node, including the ones that are suffixes of its prefix.
+    outputs: Vec<usize>,
+}
+
+/// The states of the automaton and the text at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    nodes: BTreeMap<usize, ElementState>,
+    /// The goto edges by their parents and children.
+    edges: BTreeMap<(usize, usize), ElementState>,
+    /// The failure links by the nodes that they start from.
+    fails: BTreeMap<usize, ElementState>,
+    text: BTreeMap<usize, ElementState>,
+}
+
+/// The trie of the patterns with the failure links, which is built step by step.
+#[derive(Clone, Debug, PartialEq)]
+struct Automaton {
+    nodes: Vec<AutomatonNode>,
+    patterns: Vec<Vec<char>>,
+}
+impl Automaton {
+    fn node_name(&self, id: usize) -> String {
+        if id == ROOT {
+            "the root".to_string()
+        } else {
+            format!("\"{}\"", self.nodes[id].prefix)
+        }
+    }
+    fn pattern_names(&self, patterns: &[usize]) -> String {
+        let names = patterns
+            .iter()
+            .map(|pattern| format!("\"{}\"", self.patterns[*pattern].iter().collect::<String>()))
+            .collect::<Vec<String>>();
+        match names.len() {
+            0 | 1 => names.concat(),
+            _ => format!(
+                "{} and {}",
+                names[..names.len() - 1].join(", "),
+                names[names.len() - 1]
+            ),
+        }
+    }
+    fn draw(&self, text: &[char], highlights: &Highlights) -> Diagram {
+        let layout = tree_layout(&[ROOT], |id| {
+            self.nodes[id].children.values().copied().collect()
+        });
+        let positions = fit_layout(&layout, (STRING_WIDTH, TRIE_HEIGHT), (80.0, 70.0));
+        let mut diagram = Diagram::new(STRING_WIDTH, AHO_CORASICK_HEIGHT);
+        for (id, node) in self.nodes.iter().enumerate() {
+            let label = node
+                .prefix
+                .chars()
+                .last()
+                .map(String::from)
+                .unwrap_or_default();
+            let mut diagram_node = DiagramNode::circle(node_key(id), label, positions[&id]);
+            if !node.outputs.is_empty() {
+                diagram_node.fill = Fill::Group(0);
+                diagram_node.note = Some(
+                    node.outputs
+                        .iter()
+                        .map(|pattern| self.patterns[*pattern].iter().collect::<String>())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                );
+            }
+            diagram_node.state = highlights.nodes.get(&id).copied();
+            diagram.add_node(diagram_node);
+            for child in node.children.values() {
+                let mut edge = DiagramEdge::arrow(node_key(id), node_key(*child));
+                edge.state = highlights.edges.get(&(id, *child)).copied();
+                diagram.add_edge(edge);
+            }
+            // The failure links to the root are only drawn while they are highlighted, since there are many of them
+            if let Some(fail) = node.fail {
+                let state = highlights.fails.get(&id).copied();
+                if fail != ROOT || state.is_some() {
+                    let mut edge = DiagramEdge::arrow(node_key(id), node_key(fail))
+                        .dashed()
+                        .with_bend(0.2);
+                    edge.state = state;
+                    diagram.add_edge(edge);
+                }
+            }
+        }
+        draw_chars(
+            &mut diagram,
+            "text",
+            text,
+            (TEXT_Y, 0, text.len()),
+            &highlights.text,
+            true,
+        );
+        diagram
+    }
+}
+
+/// The Aho-Corasick algorithm, which finds many patterns in a text at once. The patterns are put in a trie with
+/// failure links that tell where to continue when the next character has no edge, so the text is read only once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AhoCorasick {
+    patterns: Vec<Vec<char>>,
+    text: Vec<char>,
+}
+impl AhoCorasick {
+    pub fn new(patterns: &[&str], text: &str) -> Self {
+        Self {
+            patterns: patterns
+                .iter()
+                .map(|pattern| pattern.chars().collect())
+                .collect(),
+            text: text.chars().collect(),
+        }
+    }
+    fn push(
+        &self,
+        automaton: &Automaton,
+        description: String,
+        highlights: &Highlights,
+        matches: &[String],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, automaton.draw(&self.text, highlights));
+        step.lists = vec![StepList::new("Matches", matches.to_vec())];
+    }
+    fn build_trie(&self, steps: &mut DiagramSteps) -> Automaton {
+        let mut automaton = Automaton {
+            nodes: vec![AutomatonNode::default()],
+            patterns: self.patterns.clone(),
+        };
+        self.push(
+            &automaton,
+            "Start from a trie with only the root".to_string(),
+            &Highlights::default(),
+            &[],
+            steps,
+        );
+        for (index, pattern) in self.patterns.iter().enumerate() {
+            let mut highlights = Highlights::default();
+            let mut id = ROOT;
+            let mut added = 0;
+            for char in pattern {
+                let child = match automaton.nodes[id].children.get(char) {
+                    Some(child) => {
+                        highlights.nodes.insert(*child, ElementState::Frontier);
+                        *child
+                    }
+                    None => {
+                        let child = automaton.nodes.len();
+                        let prefix = format!("{}{}", automaton.nodes[id].prefix, char);
+                        automaton.nodes.push(AutomatonNode {
+                            prefix,
+                            ..Default::default()
+                        });
+                        automaton.nodes[id].children.insert(*char, child);
+                        highlights.nodes.insert(child, ElementState::Selected);
+                        added += 1;
+                        child
+                    }
+                };
+                highlights.edges.insert((id, child), ElementState::Frontier);
+                id = child;
+            }
+            automaton.nodes[id].outputs.push(index);
+            let shared = pattern.len() - added;
+            let description = if added == 0 {
+                format!(
+                    "\"{}\" is already a path in the trie, so only mark its last node as the end of a pattern",
+                    pattern.iter().collect::<String>()
+                )
+            } else if shared == 0 {
+                format!(
+                    "Insert \"{}\" with {}",
+                    pattern.iter().collect::<String>(),
+                    plural(added, "new node")
+                )
+            } else {
+                format!(
+                    "Insert \"{}\". The prefix \"{}\" is already in the trie, so it adds {}",
+                    pattern.iter().collect::<String>(),
+                    pattern[..shared].iter().collect::<String>(),
+                    plural(added, "new node")
+                )
+            };
+            self.push(&automaton, description, &highlights, &[], steps);
+        }
+        automaton
+    }
+    /// Computes the failure links in breadth-first order, since the link of a node is found from the link of its
+    /// parent, which is one level higher.
+    fn build_failure_links(&self, automaton: &mut Automaton, steps: &mut DiagramSteps) {
+        let mut highlights = Highlights::default();
+        let mut queue = VecDeque::new();
+        for child in automaton.nodes[ROOT].children.clone().into_values() {
+            automaton.nodes[child].fail = Some(ROOT);
+            highlights.fails.insert(child, ElementState::Active);
+            queue.push_back(child);
+        }
+        self.push(
+            automaton,
+            "The failure links of the children of the root go to the root, since a single character has no proper suffix"
+                .to_string(),
+            &highlights,
+            &[],
+            steps,
+        );
+        while let Some(parent) = queue.pop_front() {
+            for (char, child) in automaton.nodes[parent].children.clone() {
+                queue.push_back(child);
+                highlights = Highlights::default();
+                highlights.nodes.insert(child, ElementState::Active);
+                // Follow the failure links from the parent until a node has an edge for the character
+                let mut fail = automaton.nodes[parent].fail.unwrap();
+                highlights.nodes.insert(fail, ElementState::Frontier);
+                highlights.fails.insert(parent, ElementState::Frontier);
+                while fail != ROOT && !automaton.nodes[fail].children.contains_key(&char) {
+                    highlights.fails.insert(fail, ElementState::Frontier);
+                    fail = automaton.nodes[fail].fail.unwrap();
+                    highlights.nodes.insert(fail, ElementState::Frontier);
+                }
+                let target = automaton.nodes[fail]
+                    .children
+                    .get(&char)
+                    .copied()
+                    .filter(|target| *target != child)
+                    .unwrap_or(ROOT);
+                automaton.nodes[child].fail = Some(target);
+                let inherited = automaton.nodes[target].outputs.clone();
+                automaton.nodes[child]
+                    .outputs
+                    .extend(inherited.iter().copied());
+                highlights.nodes.insert(target, ElementState::Selected);
+                highlights.fails.insert(child, ElementState::Active);
+                let suffix = if target == ROOT {
+                    format!(
+                        "No proper suffix of \"{}\" is in the trie, so its failure link goes to the root",
+                        automaton.nodes[child].prefix
+                    )
+                } else {
+                    format!(
+                        "The longest proper suffix of \"{}\" in the trie is {}, so its failure link goes there",
+                        automaton.nodes[child].prefix,
+                        automaton.node_name(target)
+                    )
+                };
+                let outputs = if inherited.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        ". The node also reports {}, which ends there",
+                        automaton.pattern_names(&inherited)
+                    )
+                };
+                self.push(
+                    automaton,
+                    format!(
+                        "Follow the failure links from the parent, {}, until a node has an edge for {}. {}{}",
+                        automaton.node_name(parent),
+                        char,
+                        suffix,
+                        outputs
+                    ),
+                    &highlights,
+                    &[],
+                    steps,
+                );
+            }
+        }
+    }
+    fn scan(&self, automaton: &Automaton, steps: &mut DiagramSteps) {
+        let mut matches = vec![];
+        let mut matched = BTreeSet::new();
+        let mut state = ROOT;
+        let mut transitions = 0;
+        for (i, char) in self.text.iter().enumerate() {
+            let text_highlights = |matched: &BTreeSet<usize>| {
+                let mut text = matched
+                    .iter()
+                    .map(|index| (*index, ElementState::Selected))
+                    .collect::<BTreeMap<usize, ElementState>>();
+                text.insert(i, ElementState::Active);
+                text
+            };
+            while state != ROOT && !automaton.nodes[state].children.contains_key(char) {
+                let fail = automaton.nodes[state].fail.unwrap();
+                transitions += 1;
+                self.push(
+                    automaton,
+                    format!(
+                        "t[{}] = {}, but {} has no edge for it, so follow the failure link to {}",
+                        i,
+                        char,
+                        automaton.node_name(state),
+                        automaton.node_name(fail)
+                    ),
+                    &Highlights {
+                        nodes: BTreeMap::from([
+                            (state, ElementState::Rejected),
+                            (fail, ElementState::Active),
+                        ]),
+                        fails: BTreeMap::from([(state, ElementState::Active)]),
+                        text: text_highlights(&matched),
+                        ..Default::default()
+                    },
+                    &matches,
+                    steps,
+                );
+                state = fail;
+            }
+            let Some(child) = automaton.nodes[state].children.get(char).copied() else {
+                self.push(
+                    automaton,
+                    format!(
+                        "t[{}] = {}, but the root has no edge for it, so stay at the root",
+                        i, char
+                    ),
+                    &Highlights {
+                        nodes: BTreeMap::from([(ROOT, ElementState::Active)]),
+                        text: text_highlights(&matched),
+                        ..Default::default()
+                    },
+                    &matches,
+                    steps,
+                );
+                continue;
+            };
+            transitions += 1;
+            let mut highlights = Highlights {
+                nodes: BTreeMap::from([(child, ElementState::Active)]),
+                edges: BTreeMap::from([((state, child), ElementState::Active)]),
+                ..Default::default()
+            };
+            state = child;
+            let outputs = &automaton.nodes[state].outputs;
+            for pattern in outputs {
+                let length = automaton.patterns[*pattern].len();
+                let start = i + 1 - length;
+                matches.push(format!(
+                    "{} at index {}",
+                    automaton.patterns[*pattern].iter().collect::<String>(),
+                    start
+                ));
+                matched.extend(start..=i);
+            }
+            highlights.text = text_highlights(&matched);
+            let description = if outputs.is_empty() {
+                format!(
+                    "t[{}] = {}, so follow the goto edge to {}",
+                    i,
+                    char,
+                    automaton.node_name(state)
+                )
+            } else {
+                highlights.nodes.insert(state, ElementState::Selected);
+                format!(
+                    "t[{}] = {}, so follow the goto edge to {}, which reports {}",
+                    i,
+                    char,
+                    automaton.node_name(state),
+                    automaton.pattern_names(outputs)
+                )
+            };
+            self.push(automaton, description, &highlights, &matches, steps);
+        }
+        self.push(
+            automaton,
+            format!(
+                "The text was read once with {}, and the patterns were found {}",
+                plural(transitions, "transition"),
+                plural(matches.len(), "time")
+            ),
+            &Highlights {
+                text: matched
+                    .iter()
+                    .map(|index| (*index, ElementState::Selected))
+                    .collect(),
+                ..Default::default()
+            },
+            &matches,
+            steps,
+        );
+    }
+}
+impl Visualizer for AhoCorasick {
+    fn diagram(&self) -> Diagram {
+        Automaton {
+            nodes: vec![AutomatonNode::default()],
+            patterns: vec![],
+        }
+        .draw(&self.text, &Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![Operation::new(
+            "Search",
+            vec![
+                Input::Text {
+                    label: "Patterns",
+                    default: self
+                        .patterns
+                        .iter()
+                        .map(|pattern| pattern.iter().collect::<String>())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                },
+                Input::Text {
+                    label: "Text",
+                    default: self.text.iter().collect(),
+                },
+            ],
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Search" => {
+                let patterns = values.first().map(|value| value.text()).unwrap_or_default();
+                let text = values.get(1).map(|value| value.text()).unwrap_or_default();
+                self.patterns = parse_patterns(&patterns)?;
+                self.text = check_text(&text, "text", MAX_TEXT_LENGTH)?;
+                let mut automaton = self.build_trie(&mut steps);
+                self.build_failure_links(&mut automaton, &mut steps);
+                self.scan(&automaton, &mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn node_key(id: usize) -> String {
+    format!("node-{}", id)
+}
+
+/// Parses the patterns from a list separated by commas or spaces. Patterns that are given again are left out.
+fn parse_patterns(text: &str) -> Result<Vec<Vec<char>>, String> {
+    let mut patterns = vec![];
+    for part in text
+        .split([',', ' '])
+        .filter(|part| !part.trim().is_empty())
+    {
+        let pattern = check_text(part.trim(), "patterns", MAX_AHO_CORASICK_PATTERN_LENGTH)?;
+        if !patterns.contains(&pattern) {
+            patterns.push(pattern);
+        }
+    }
+    if patterns.is_empty() || patterns.len() > MAX_PATTERNS {
+        return Err(format!("There must be 1 to {} patterns", MAX_PATTERNS));
+    }
+    Ok(patterns)
+}
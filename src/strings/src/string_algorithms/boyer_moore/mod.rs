@@ -0,0 +1,451 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use diagram::*;
+
+use crate::*;
+
+const BOYER_MOORE_HEIGHT: f32 = 460.0;
+const TEXT_Y: f32 = 50.0;
+const PATTERN_Y: f32 = 130.0;
+/// The y of the line that shows how far the pattern jumped.
+const JUMP_Y: f32 = 162.0;
+const TABLE_CHARS_Y: f32 = 240.0;
+const GOOD_SUFFIX_Y: f32 = 300.0;
+const BAD_CHARS_Y: f32 = 370.0;
+const LAST_Y: f32 = 410.0;
+
+/// The states of the characters and the tables at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    shift: i64,
+    text: BTreeMap<usize, ElementState>,
+    pattern: BTreeMap<usize, ElementState>,
+    table_chars: BTreeMap<usize, ElementState>,
+    good_suffix: BTreeMap<usize, ElementState>,
+    bad_chars: BTreeMap<usize, ElementState>,
+    /// The shifts of the good suffix rule that have been computed.
+    shifts: Vec<Option<i64>>,
+    /// The last indices of the characters of the pattern that have been computed.
+    last: Vec<Option<i64>>,
+    /// The shifts that the pattern jumped from and to.
+    jump: Option<(i64, i64)>,
+}
+
+/// The Boyer-Moore algorithm, which compares the pattern from right to left and uses two rules to jump ahead after
+/// a mismatch. The bad character rule lines up the mismatched character of the text with its last place in the
+/// pattern, and the good suffix rule lines up the matched suffix with an earlier copy of it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoyerMoore {
+    text: Vec<char>,
+    pattern: Vec<char>,
+}
+impl BoyerMoore {
+    pub fn new(text: &str, pattern: &str) -> Self {
+        Self {
+            text: text.chars().collect(),
+            pattern: pattern.chars().collect(),
+        }
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &Highlights,
+        (matches, jumps): (&[usize], &[String]),
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = vec![
+            matches_list(matches),
+            StepList::new("Jumps", jumps.to_vec()),
+        ];
+    }
+    /// The distinct characters of the pattern in sorted order, which are the keys of the bad character table.
+    fn bad_chars(&self) -> Vec<char> {
+        self.pattern
+            .iter()
+            .copied()
+            .collect::<BTreeSet<char>>()
+            .into_iter()
+            .collect()
+    }
+    /// Computes the bad character table with the last index of every character of the pattern.
+    fn build_bad_character_table(
+        &self,
+        highlights: &mut Highlights,
+        steps: &mut DiagramSteps,
+    ) -> BTreeMap<char, usize> {
+        let bad_chars = self.bad_chars();
+        let mut last = BTreeMap::new();
+        for (i, char) in self.pattern.iter().enumerate() {
+            let index = bad_chars.iter().position(|bad| bad == char).unwrap();
+            let description = if last.insert(*char, i).is_some() {
+                format!(
+                    "{} appears again at index {}, so last[{}] = {}",
+                    char, i, char, i
+                )
+            } else {
+                format!("{} appears at index {}, so last[{}] = {}", char, i, char, i)
+            };
+            highlights.last[index] = Some(i as i64);
+            highlights.table_chars = BTreeMap::from([(i, ElementState::Selected)]);
+            highlights.bad_chars = BTreeMap::from([(index, ElementState::Selected)]);
+            self.push(description, highlights, (&[], &[]), steps);
+        }
+        highlights.table_chars.clear();
+        highlights.bad_chars.clear();
+        last
+    }
+    /// Computes the good suffix table, where `shifts[j]` is how far the pattern can jump when `p[j]` mismatches
+    /// after the suffix after it has matched.
+    fn build_good_suffix_table(
+        &self,
+        highlights: &mut Highlights,
+        steps: &mut DiagramSteps,
+    ) -> Vec<usize> {
+        let pattern = &self.pattern;
+        let m = pattern.len();
+        let mut shifts = vec![0; m];
+        for j in (0..m).rev() {
+            let shift = good_suffix_shift(pattern, j);
+            shifts[j] = shift;
+            let suffix = pattern[j + 1..].iter().collect::<String>();
+            // The start of the copy of the suffix, which can be before the start of the pattern
+            let start = j as i64 + 1 - shift as i64;
+            let description = if j + 1 == m {
+                if shift <= j {
+                    format!(
+                        "If p[{}] = {} mismatches right away, the closest character to its left that is different is p[{}] = {}, so shift[{}] = {}",
+                        j, pattern[j], j - shift, pattern[j - shift], j, shift
+                    )
+                } else {
+                    format!(
+                        "If p[{}] = {} mismatches right away, all characters to its left are the same, so shift[{}] = {}",
+                        j, pattern[j], j, shift
+                    )
+                }
+            } else if start > 0 {
+                format!(
+                    "If p[{}] mismatches after \"{}\" matched, \"{}\" appears again at index {} after a character other than {}, so shift[{}] = {}",
+                    j, suffix, suffix, start, pattern[j], j, shift
+                )
+            } else if start == 0 {
+                format!(
+                    "If p[{}] mismatches after \"{}\" matched, \"{}\" is also the start of the pattern, so shift[{}] = {}",
+                    j, suffix, suffix, j, shift
+                )
+            } else if shift < m {
+                format!(
+                    "If p[{}] mismatches after \"{}\" matched, only its end \"{}\" is also the start of the pattern, so shift[{}] = {}",
+                    j,
+                    suffix,
+                    pattern[..m - shift].iter().collect::<String>(),
+                    j,
+                    shift
+                )
+            } else {
+                format!(
+                    "If p[{}] mismatches after \"{}\" matched, no part of it appears earlier, so the pattern can jump past it and shift[{}] = {}",
+                    j, suffix, j, shift
+                )
+            };
+            highlights.shifts[j] = Some(shift as i64);
+            highlights.table_chars = (j + 1..m).map(|k| (k, ElementState::Frontier)).collect();
+            highlights.table_chars.insert(j, ElementState::Rejected);
+            // The copy of the suffix that the pattern would be lined up with, or the different character to the left
+            // if no suffix has matched
+            for k in (j + 1..m).filter(|k| *k >= shift) {
+                highlights
+                    .table_chars
+                    .insert(k - shift, ElementState::Active);
+            }
+            if j + 1 == m && shift <= j {
+                highlights
+                    .table_chars
+                    .insert(j - shift, ElementState::Active);
+            }
+            highlights.good_suffix = BTreeMap::from([(j, ElementState::Selected)]);
+            self.push(description, highlights, (&[], &[]), steps);
+        }
+        highlights.table_chars.clear();
+        highlights.good_suffix.clear();
+        shifts
+    }
+    fn search(&self, steps: &mut DiagramSteps) {
+        let (text, pattern) = (&self.text, &self.pattern);
+        let (n, m) = (text.len(), pattern.len());
+        let bad_chars = self.bad_chars();
+        let mut highlights = Highlights {
+            shifts: vec![None; m],
+            last: vec![None; bad_chars.len()],
+            ..Default::default()
+        };
+        let last = self.build_bad_character_table(&mut highlights, steps);
+        let shifts = self.build_good_suffix_table(&mut highlights, steps);
+        if m > n {
+            self.push(
+                "The pattern is longer than the text, so it can't be found".to_string(),
+                &highlights,
+                (&[], &[]),
+                steps,
+            );
+            return;
+        }
+        let (mut matches, mut jumps) = (vec![], vec![]);
+        let mut read = BTreeSet::new();
+        let mut comparisons = 0;
+        let mut shift = 0;
+        while shift + m <= n {
+            highlights.shift = shift as i64;
+            highlights.text.clear();
+            highlights.pattern.clear();
+            let mut j = m;
+            // The characters are compared from right to left
+            let mismatch = loop {
+                if j == 0 {
+                    break None;
+                }
+                j -= 1;
+                comparisons += 1;
+                read.insert(shift + j);
+                if text[shift + j] != pattern[j] {
+                    break Some(j);
+                }
+                highlights.text.insert(shift + j, ElementState::Selected);
+                highlights.pattern.insert(j, ElementState::Selected);
+                if j > 0 {
+                    self.push(
+                        format!("t[{}] = p[{}] = {}", shift + j, j, pattern[j]),
+                        &highlights,
+                        (&matches, &jumps),
+                        steps,
+                    );
+                }
+            };
+            let (distance, description, rule) = match mismatch {
+                None => {
+                    matches.push(shift);
+                    highlights.good_suffix = BTreeMap::from([(0, ElementState::Active)]);
+                    (
+                        shifts[0],
+                        format!(
+                            "The whole pattern matches at index {}. Jump ahead by shift[0] = {} to the next place where the pattern can start",
+                            shift, shifts[0]
+                        ),
+                        "match",
+                    )
+                }
+                Some(j) => {
+                    let char = text[shift + j];
+                    highlights.text.insert(shift + j, ElementState::Rejected);
+                    highlights.pattern.insert(j, ElementState::Rejected);
+                    highlights.good_suffix = BTreeMap::from([(j, ElementState::Active)]);
+                    let (bad_shift, bad_text) = match last.get(&char) {
+                        Some(k) if *k < j => (
+                            j - k,
+                            format!("{} was last seen at index {}, so lining them up shifts by {}", char, k, j - k),
+                        ),
+                        Some(k) => (
+                            0,
+                            format!("{} was last seen at index {}, which is after the mismatch, so it gives no shift", char, k),
+                        ),
+                        None => (
+                            j + 1,
+                            format!("{} isn't in the pattern, so the pattern can jump past it by {}", char, j + 1),
+                        ),
+                    };
+                    if let Some(index) = bad_chars.iter().position(|bad| *bad == char) {
+                        highlights.bad_chars = BTreeMap::from([(index, ElementState::Active)]);
+                    }
+                    let good_text = if j + 1 == m {
+                        format!("nothing had matched yet, so shift[{}] = {}", j, shifts[j])
+                    } else {
+                        format!(
+                            "\"{}\" had matched, so shift[{}] = {}",
+                            pattern[j + 1..].iter().collect::<String>(),
+                            j,
+                            shifts[j]
+                        )
+                    };
+                    let (distance, rule) = if bad_shift > shifts[j] {
+                        (bad_shift, "bad character")
+                    } else {
+                        (shifts[j], "good suffix")
+                    };
+                    (
+                        distance,
+                        format!(
+                            "t[{}] = {} ≠ p[{}] = {}. Bad character: {}. Good suffix: {}. Jump ahead by the larger shift, {}",
+                            shift + j, char, j, pattern[j], bad_text, good_text, distance
+                        ),
+                        rule,
+                    )
+                }
+            };
+            self.push(description, &highlights, (&matches, &jumps), steps);
+            highlights.bad_chars.clear();
+            highlights.good_suffix.clear();
+            if shift + distance + m > n {
+                break;
+            }
+            jumps.push(format!("By {} from index {} ({})", distance, shift, rule));
+            highlights.jump = Some((shift as i64, (shift + distance) as i64));
+            highlights.shift = (shift + distance) as i64;
+            highlights.text.clear();
+            highlights.pattern.clear();
+            let skipped = if distance > 1 {
+                format!(
+                    ", skipping {} without comparing anything",
+                    plural(distance - 1, "alignment")
+                )
+            } else {
+                String::new()
+            };
+            self.push(
+                format!(
+                    "The pattern jumps ahead by {} to index {}{}",
+                    plural(distance, "character"),
+                    shift + distance,
+                    skipped
+                ),
+                &highlights,
+                (&matches, &jumps),
+                steps,
+            );
+            highlights.jump = None;
+            shift += distance;
+        }
+        highlights.text = matches
+            .iter()
+            .flat_map(|start| *start..start + m)
+            .map(|i| (i, ElementState::Selected))
+            .collect();
+        highlights.pattern.clear();
+        self.push(
+            format!(
+                "The next jump would pass the end of the text, so the search is done after {}. {} of the {} characters of the text were never read, and the pattern was found {}",
+                plural(comparisons, "character comparison"),
+                n - read.len(),
+                n,
+                plural(matches.len(), "time")
+            ),
+            &highlights,
+            (&matches, &jumps),
+            steps,
+        );
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(STRING_WIDTH, BOYER_MOORE_HEIGHT);
+        let size = self.text.len();
+        let m = self.pattern.len();
+        draw_chars(
+            &mut diagram,
+            "text",
+            &self.text,
+            (TEXT_Y, 0, size),
+            &highlights.text,
+            true,
+        );
+        draw_chars(
+            &mut diagram,
+            "pattern",
+            &self.pattern,
+            (PATTERN_Y, highlights.shift, size),
+            &highlights.pattern,
+            false,
+        );
+        if let Some((from, to)) = highlights.jump {
+            let (start, end) = (
+                char_x(from, size) - CHAR_SIZE.0 / 2.0,
+                char_x(to, size) - CHAR_SIZE.0 / 2.0,
+            );
+            diagram.add_figure(
+                Figure::new(FigureKind::Polyline(vec![
+                    (start, JUMP_Y - 8.0),
+                    (start, JUMP_Y),
+                    (end, JUMP_Y),
+                    (end, JUMP_Y - 8.0),
+                ]))
+                .dashed()
+                .with_state(ElementState::Active),
+            );
+            diagram.add_node(DiagramNode::text(
+                "jump",
+                format!("+{}", to - from),
+                ((start + end) / 2.0, JUMP_Y + 14.0),
+            ));
+        }
+        draw_chars(
+            &mut diagram,
+            "table-char",
+            &self.pattern,
+            (TABLE_CHARS_Y, 0, m),
+            &highlights.table_chars,
+            true,
+        );
+        let mut shifts = highlights.shifts.clone();
+        shifts.resize(m, None);
+        draw_values(
+            &mut diagram,
+            "good-suffix",
+            "shift",
+            &shifts,
+            (GOOD_SUFFIX_Y, 0, m),
+            &highlights.good_suffix,
+        );
+        let bad_chars = self.bad_chars();
+        draw_chars(
+            &mut diagram,
+            "bad-char",
+            &bad_chars,
+            (BAD_CHARS_Y, 0, bad_chars.len()),
+            &highlights.bad_chars,
+            false,
+        );
+        let mut last = highlights.last.clone();
+        last.resize(bad_chars.len(), None);
+        draw_values(
+            &mut diagram,
+            "last",
+            "last",
+            &last,
+            (LAST_Y, 0, bad_chars.len()),
+            &highlights.bad_chars,
+        );
+        diagram
+    }
+}
+impl Visualizer for BoyerMoore {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![Operation::new(
+            "Search",
+            search_inputs(&self.text, &self.pattern),
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Search" => {
+                (self.text, self.pattern) = parse_search(values)?;
+                self.search(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The smallest shift that lines up the suffix after `j` with an earlier copy of it that comes after a different
+/// character than `p[j]`. The parts of the copy that are before the start of the pattern always match.
+fn good_suffix_shift(pattern: &[char], j: usize) -> usize {
+    (1..pattern.len())
+        .find(|shift| {
+            (j + 1..pattern.len())
+                .filter(|k| k >= shift)
+                .all(|k| pattern[k - shift] == pattern[k])
+                && (j < *shift || pattern[j - shift] != pattern[j])
+        })
+        .unwrap_or(pattern.len())
+}
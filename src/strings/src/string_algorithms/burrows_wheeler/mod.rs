@@ -0,0 +1,426 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use diagram::*;
+use sorting::*;
+
+use crate::*;
+
+/// The longest text whose rotations fit in the table with the end marker.
+const MAX_LENGTH: usize = 9;
+const END_MARKER: char = '$';
+/// The sorting algorithms that the rotations can be sorted with. They only swap items, so every rotation stays
+/// in one row at a time and can be animated as it moves.
+const SORTS: [&str; 4] = ["Insertion sort", "Quicksort", "Heapsort", "Bubble sort"];
+const BWT_HEIGHT: f32 = 480.0;
+const ROWS_Y: f32 = 60.0;
+const ROW_SPACING: f32 = 36.0;
+const ROW_NUMBERS_X: f32 = 40.0;
+const ROTATIONS_X: f32 = 90.0;
+const FIRST_X: f32 = 560.0;
+const LAST_X: f32 = 660.0;
+const DECODED_Y: f32 = 440.0;
+
+/// A rotation of a text by its start, which is sorted like a string where the end marker is the smallest character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Rotation<'a> {
+    chars: &'a [char],
+    start: usize,
+}
+impl Rotation<'_> {
+    fn char(&self, index: usize) -> char {
+        self.chars[(self.start + index) % self.chars.len()]
+    }
+    fn text(&self) -> String {
+        (0..self.chars.len())
+            .map(|index| self.char(index))
+            .collect()
+    }
+}
+impl Ord for Rotation<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |rotation: &Self| {
+            (0..rotation.chars.len())
+                .map(|index| {
+                    let char = rotation.char(index);
+                    (char != END_MARKER, char)
+                })
+                .collect::<Vec<_>>()
+        };
+        key(self)
+            .cmp(&key(other))
+            .then(self.start.cmp(&other.start))
+    }
+}
+impl PartialOrd for Rotation<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The states of the rotations and the columns at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    /// The start of the rotation in every row.
+    order: Vec<usize>,
+    rows: BTreeMap<usize, ElementState>,
+    /// Whether the rotations are sorted, which shows the first and last columns.
+    sorted: bool,
+    /// Whether the first and last columns are shown on their own with the ranks of their characters.
+    columns: bool,
+    first: BTreeMap<usize, ElementState>,
+    last: BTreeMap<usize, ElementState>,
+    /// The row of the last column that is followed to the same character in the first column.
+    link: Option<(usize, usize)>,
+    decoded: BTreeMap<usize, char>,
+}
+
+/// The Burrows-Wheeler transform, which rearranges the characters of a text so that equal characters tend to be next
+/// to each other. It sorts all rotations of the text with an end marker and takes the last column. The transform can
+/// be inverted, since the last column is enough to find the order of the characters again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BurrowsWheeler {
+    text: Vec<char>,
+    sort: String,
+}
+impl BurrowsWheeler {
+    pub fn new(text: &str, sort: &str) -> Self {
+        Self {
+            text: text.chars().collect(),
+            sort: sort.to_string(),
+        }
+    }
+    /// The text with the end marker.
+    fn marked(&self) -> Vec<char> {
+        let mut chars = self.text.clone();
+        chars.push(END_MARKER);
+        chars
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &Highlights,
+        transform: &[String],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = vec![StepList::new("Transform", transform.to_vec())];
+    }
+    /// Sorts the rotations with the chosen sorting algorithm and returns the last column.
+    fn transform(&self, highlights: &mut Highlights, steps: &mut DiagramSteps) -> Vec<char> {
+        let chars = self.marked();
+        let n = chars.len();
+        highlights.order = (0..n).collect();
+        self.push(
+            format!(
+                "Add the end marker {} and write down all {} rotations of the text",
+                END_MARKER, n
+            ),
+            highlights,
+            &[],
+            steps,
+        );
+        let mut rotations = (0..n)
+            .map(|start| Rotation {
+                chars: &chars,
+                start,
+            })
+            .collect::<Vec<Rotation>>();
+        let mut commands = vec![];
+        let sort = match self.sort.as_str() {
+            "Quicksort" => quicksort,
+            "Heapsort" => heapsort,
+            "Bubble sort" => bubble_sort,
+            _ => insertion_sort,
+        };
+        sort(&mut rotations.clone(), &mut commands);
+        for command in commands {
+            let Some(SortCommand::Swap(from, to)) = command.first().cloned() else {
+                continue;
+            };
+            if from == to {
+                continue;
+            }
+            run_sort_steps(&mut rotations, &[command]);
+            highlights.order = rotations.iter().map(|rotation| rotation.start).collect();
+            highlights.rows =
+                BTreeMap::from([(from, ElementState::Active), (to, ElementState::Active)]);
+            let (first, second) = (from.min(to), from.max(to));
+            self.push(
+                format!(
+                    "{} swaps rows {} and {}, which are now \"{}\" and \"{}\"",
+                    self.sort,
+                    first,
+                    second,
+                    rotations[first].text(),
+                    rotations[second].text()
+                ),
+                highlights,
+                &[],
+                steps,
+            );
+        }
+        highlights.rows.clear();
+        highlights.sorted = true;
+        let last = rotations
+            .iter()
+            .map(|rotation| rotation.char(n - 1))
+            .collect::<Vec<char>>();
+        let original = highlights
+            .order
+            .iter()
+            .position(|start| *start == 0)
+            .unwrap();
+        highlights.rows = BTreeMap::from([(original, ElementState::Selected)]);
+        self.push(
+            format!(
+                "The rotations are sorted. The last column L = \"{}\" is the transform, and the text is in row {}, which is the one that ends with {}",
+                last.iter().collect::<String>(),
+                original,
+                END_MARKER
+            ),
+            highlights,
+            &[last.iter().collect()],
+            steps,
+        );
+        highlights.rows.clear();
+        last
+    }
+    /// Inverts the transform by following the last column back to the same characters in the first column, which
+    /// decodes the text from the end.
+    fn invert(&self, last: &[char], highlights: &mut Highlights, steps: &mut DiagramSteps) {
+        let n = last.len();
+        let transform = [last.iter().collect::<String>()];
+        let mut first = last.to_vec();
+        first.sort_by_key(|char| (*char != END_MARKER, *char));
+        highlights.columns = true;
+        self.push(
+            "To invert the transform, only L is needed, and sorting it gives the first column F. The k-th a in L is the same character of the text as the k-th a in F, since rotating the rows that end with a so that they start with it keeps them in the same order".to_string(),
+            highlights,
+            &transform,
+            steps,
+        );
+        let mut row = 0;
+        for index in (0..n - 1).rev() {
+            let rank = rank(last, row);
+            let next = (0..n)
+                .find(|j| first[*j] == last[row] && self::rank(&first, *j) == rank)
+                .unwrap();
+            highlights.decoded.insert(index, last[row]);
+            highlights.first = BTreeMap::from([(row, ElementState::Active)]);
+            highlights.last = BTreeMap::from([(row, ElementState::Selected)]);
+            highlights.first.insert(next, ElementState::Frontier);
+            highlights.link = Some((row, next));
+            let reason = if row == 0 {
+                format!(
+                    "Row 0 starts with the end marker, so it ends with the last character of the text, {}",
+                    ranked(last, row)
+                )
+            } else {
+                format!(
+                    "Row {} ends with {}, which is right before {} in the text",
+                    row,
+                    ranked(last, row),
+                    ranked(&first, row)
+                )
+            };
+            self.push(
+                format!(
+                    "{}. It is decoded at index {}, and {} is in row {} of F, so continue from there",
+                    reason,
+                    index,
+                    ranked(last, row),
+                    next
+                ),
+                highlights,
+                &transform,
+                steps,
+            );
+            row = next;
+        }
+        highlights.first.clear();
+        highlights.last = BTreeMap::from([(row, ElementState::Selected)]);
+        highlights.link = None;
+        self.push(
+            format!(
+                "Row {} ends with {}, so the whole text \"{}\" is decoded",
+                row,
+                END_MARKER,
+                self.text.iter().collect::<String>()
+            ),
+            highlights,
+            &transform,
+            steps,
+        );
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(STRING_WIDTH, BWT_HEIGHT);
+        let chars = self.marked();
+        let n = chars.len();
+        let row_y = |row: usize| ROWS_Y + ROW_SPACING * row as f32;
+        for (row, start) in highlights.order.iter().enumerate() {
+            diagram.add_node(DiagramNode::text(
+                format!("row-{}", row),
+                row,
+                (ROW_NUMBERS_X, row_y(row)),
+            ));
+            for column in 0..n {
+                let mut node = DiagramNode::rect(
+                    format!("rotation-{}-{}", start, column),
+                    chars[(start + column) % n],
+                    (ROTATIONS_X + CHAR_SPACING * column as f32, row_y(row)),
+                    CHAR_SIZE,
+                );
+                node.state = highlights.rows.get(&row).copied().or_else(|| {
+                    if !highlights.sorted {
+                        None
+                    } else if column == 0 {
+                        Some(ElementState::Frontier)
+                    } else if column == n - 1 {
+                        Some(ElementState::Selected)
+                    } else {
+                        None
+                    }
+                });
+                diagram.add_node(node);
+            }
+        }
+        if highlights.sorted {
+            for (key, column) in [("F", 0), ("L", n - 1)] {
+                diagram.add_node(DiagramNode::text(
+                    format!("rotations-{}", key),
+                    key,
+                    (
+                        ROTATIONS_X + CHAR_SPACING * column as f32,
+                        ROWS_Y - ROW_SPACING,
+                    ),
+                ));
+            }
+        }
+        if highlights.columns {
+            let rotation = |row: usize| Rotation {
+                chars: &chars,
+                start: highlights.order[row],
+            };
+            let first = (0..n).map(|row| rotation(row).char(0)).collect::<Vec<_>>();
+            let last = (0..n)
+                .map(|row| rotation(row).char(n - 1))
+                .collect::<Vec<_>>();
+            for (key, x, column, states) in [
+                ("first", FIRST_X, &first, &highlights.first),
+                ("last", LAST_X, &last, &highlights.last),
+            ] {
+                diagram.add_node(DiagramNode::text(
+                    format!("{}-label", key),
+                    if key == "first" { "F" } else { "L" },
+                    (x, ROWS_Y - ROW_SPACING),
+                ));
+                for row in 0..n {
+                    let mut node = DiagramNode::rect(
+                        format!("{}-{}", key, row),
+                        ranked(column, row),
+                        (x, row_y(row)),
+                        CHAR_SIZE,
+                    );
+                    node.state = states.get(&row).copied();
+                    diagram.add_node(node);
+                }
+            }
+            if let Some((from, to)) = highlights.link {
+                let mut arrow =
+                    DiagramEdge::arrow(format!("last-{}", from), format!("first-{}", to));
+                arrow.state = Some(ElementState::Active);
+                diagram.add_edge(arrow);
+            }
+        }
+        for (index, char) in &highlights.decoded {
+            let mut node = DiagramNode::rect(
+                format!("decoded-{}", index),
+                char,
+                (char_x(*index as i64, self.text.len()), DECODED_Y),
+                CHAR_SIZE,
+            );
+            node.note = Some(index.to_string());
+            node.state = Some(ElementState::Selected);
+            diagram.add_node(node);
+        }
+        diagram
+    }
+}
+impl Visualizer for BurrowsWheeler {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights {
+            order: (0..=self.text.len()).collect(),
+            ..Default::default()
+        })
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let mut sorts = vec![self.sort.clone()];
+        sorts.extend(
+            SORTS
+                .iter()
+                .filter(|sort| **sort != self.sort)
+                .map(|sort| sort.to_string()),
+        );
+        vec![Operation::new(
+            "Transform",
+            vec![
+                Input::Text {
+                    label: "Text",
+                    default: self.text.iter().collect(),
+                },
+                Input::Choice {
+                    label: "Rotation sort",
+                    choices: sorts,
+                },
+            ],
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Transform" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                let text = check_text(&text, "text", MAX_LENGTH)?;
+                if text.contains(&END_MARKER) {
+                    return Err(format!(
+                        "The text can't have {}, since it is the end marker",
+                        END_MARKER
+                    ));
+                }
+                let sort = values.get(1).map(|value| value.text()).unwrap_or_default();
+                if !SORTS.contains(&sort.as_str()) {
+                    return Err(format!("Unknown sort \"{}\"", sort));
+                }
+                (self.text, self.sort) = (text, sort);
+                let mut highlights = Highlights::default();
+                let last = self.transform(&mut highlights, &mut steps);
+                self.invert(&last, &mut highlights, &mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The number of the same characters up to and including the index, starting from 1.
+fn rank(chars: &[char], index: usize) -> usize {
+    chars[..=index]
+        .iter()
+        .filter(|char| **char == chars[index])
+        .count()
+}
+
+/// The character at the index with its rank as a subscript, like a₂. The rank of the end marker is left out, since
+/// there is only one.
+fn ranked(chars: &[char], index: usize) -> String {
+    let char = chars[index];
+    if char == END_MARKER {
+        return char.to_string();
+    }
+    let subscript = rank(chars, index)
+        .to_string()
+        .chars()
+        .map(|digit| char::from_u32('₀' as u32 + digit.to_digit(10).unwrap()).unwrap())
+        .collect::<String>();
+    format!("{}{}", char, subscript)
+}
@@ -0,0 +1,368 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use crate::*;
+
+const ALIGNMENT_Y: f32 = 490.0;
+const ALIGNMENT_SPACING: f32 = 30.0;
+const EDIT_DISTANCE_HEIGHT: f32 = 590.0;
+
+/// The edit that a cell of the table comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Edit {
+    Keep,
+    Replace,
+    Delete,
+    Insert,
+}
+impl Edit {
+    /// The cell that the edit comes from.
+    fn source(&self, (i, j): (usize, usize)) -> (usize, usize) {
+        match self {
+            Edit::Keep | Edit::Replace => (i - 1, j - 1),
+            Edit::Delete => (i - 1, j),
+            Edit::Insert => (i, j - 1),
+        }
+    }
+}
+
+/// The states of the table and the alignment at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    values: Vec<Vec<Option<i64>>>,
+    /// The edits that the computed cells come from.
+    edits: BTreeMap<(usize, usize), Edit>,
+    cells: BTreeMap<(usize, usize), ElementState>,
+    arrows: BTreeMap<(usize, usize), ElementState>,
+    rows: BTreeMap<usize, ElementState>,
+    columns: BTreeMap<usize, ElementState>,
+    /// The characters of both strings that the traceback has aligned so far, with `None` for gaps.
+    alignment: Vec<(Option<char>, Option<char>)>,
+}
+
+/// The Levenshtein distance, which is the smallest number of replacements, deletions and insertions of characters
+/// that turn a string into another. It fills a table of the distances between all prefixes of the strings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditDistance {
+    from: Vec<char>,
+    to: Vec<char>,
+}
+impl EditDistance {
+    pub fn new(from: &str, to: &str) -> Self {
+        Self {
+            from: from.chars().collect(),
+            to: to.chars().collect(),
+        }
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &Highlights,
+        edits: &[String],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = vec![StepList::new("Edits", edits.to_vec())];
+    }
+    fn fill_table(&self, highlights: &mut Highlights, steps: &mut DiagramSteps) {
+        let (a, b) = (&self.from, &self.to);
+        let (n, m) = (a.len(), b.len());
+        highlights.values = vec![vec![None; m + 1]; n + 1];
+        for i in 0..=n {
+            highlights.values[i][0] = Some(i as i64);
+            highlights.cells.insert((i, 0), ElementState::Selected);
+            if i > 0 {
+                highlights.edits.insert((i, 0), Edit::Delete);
+            }
+        }
+        self.push(
+            "d[i][0] = i, since turning the first i characters of the first string into the empty string takes i deletions"
+                .to_string(),
+            highlights,
+            &[],
+            steps,
+        );
+        highlights.cells.clear();
+        for j in 1..=m {
+            highlights.values[0][j] = Some(j as i64);
+            highlights.cells.insert((0, j), ElementState::Selected);
+            highlights.edits.insert((0, j), Edit::Insert);
+        }
+        self.push(
+            "d[0][j] = j, since turning the empty string into the first j characters of the second string takes j insertions"
+                .to_string(),
+            highlights,
+            &[],
+            steps,
+        );
+        for i in 1..=n {
+            for j in 1..=m {
+                let value = |(i, j): (usize, usize)| highlights.values[i][j].unwrap();
+                let same = a[i - 1] == b[j - 1];
+                let diagonal = value((i - 1, j - 1)) + !same as i64;
+                let (up, left) = (value((i - 1, j)) + 1, value((i, j - 1)) + 1);
+                // Ties prefer keeping or replacing, then deleting
+                let (distance, edit) = if diagonal <= up && diagonal <= left {
+                    (diagonal, if same { Edit::Keep } else { Edit::Replace })
+                } else if up <= left {
+                    (up, Edit::Delete)
+                } else {
+                    (left, Edit::Insert)
+                };
+                highlights.values[i][j] = Some(distance);
+                highlights.edits.insert((i, j), edit);
+                highlights.cells = BTreeMap::from([
+                    ((i - 1, j - 1), ElementState::Frontier),
+                    ((i - 1, j), ElementState::Frontier),
+                    ((i, j - 1), ElementState::Frontier),
+                    (edit.source((i, j)), ElementState::Active),
+                    ((i, j), ElementState::Selected),
+                ]);
+                highlights.arrows = BTreeMap::from([((i, j), ElementState::Active)]);
+                let state = if same {
+                    ElementState::Selected
+                } else {
+                    ElementState::Rejected
+                };
+                highlights.rows = BTreeMap::from([(i - 1, state)]);
+                highlights.columns = BTreeMap::from([(j - 1, state)]);
+                self.push(
+                    format!(
+                        "{} {} {}: {} costs {}, deleting costs {} and inserting costs {}, so d[{}][{}] = {} by {}",
+                        a[i - 1],
+                        if same { "=" } else { "≠" },
+                        b[j - 1],
+                        if same { "keeping" } else { "replacing" },
+                        diagonal,
+                        up,
+                        left,
+                        i,
+                        j,
+                        distance,
+                        edit_text(edit, a[i - 1], b[j - 1])
+                    ),
+                    highlights,
+                    &[],
+                    steps,
+                );
+            }
+        }
+        highlights.cells.clear();
+        highlights.arrows.clear();
+        highlights.rows.clear();
+        highlights.columns.clear();
+    }
+    /// Follows the edits back from the last cell to the first one to find an optimal alignment.
+    fn trace_back(&self, highlights: &mut Highlights, steps: &mut DiagramSteps) {
+        let (a, b) = (&self.from, &self.to);
+        let mut cell = (a.len(), b.len());
+        let mut edits = vec![];
+        highlights.cells.insert(cell, ElementState::Selected);
+        self.push(
+            format!(
+                "d[{}][{}] = {} is the edit distance. Follow the arrows back to find the edits",
+                cell.0,
+                cell.1,
+                highlights.values[cell.0][cell.1].unwrap()
+            ),
+            highlights,
+            &edits,
+            steps,
+        );
+        while cell != (0, 0) {
+            let edit = highlights.edits[&cell];
+            let (i, j) = cell;
+            let pair = match edit {
+                Edit::Keep | Edit::Replace => (Some(a[i - 1]), Some(b[j - 1])),
+                Edit::Delete => (Some(a[i - 1]), None),
+                Edit::Insert => (None, Some(b[j - 1])),
+            };
+            highlights.alignment.insert(0, pair);
+            // The missing character of a deletion or an insertion isn't used in the text
+            let text = edit_text(edit, pair.0.unwrap_or_default(), pair.1.unwrap_or_default());
+            match pair {
+                (Some(from), Some(to)) if edit == Edit::Replace => {
+                    edits.insert(0, format!("Replace {} with {}", from, to))
+                }
+                (Some(from), None) => edits.insert(0, format!("Delete {}", from)),
+                (None, Some(to)) => edits.insert(0, format!("Insert {}", to)),
+                _ => (),
+            }
+            highlights.arrows.insert(cell, ElementState::Selected);
+            cell = edit.source(cell);
+            highlights.cells.insert(cell, ElementState::Selected);
+            highlights.rows = match edit {
+                Edit::Insert => BTreeMap::new(),
+                _ => BTreeMap::from([(i - 1, ElementState::Active)]),
+            };
+            highlights.columns = match edit {
+                Edit::Delete => BTreeMap::new(),
+                _ => BTreeMap::from([(j - 1, ElementState::Active)]),
+            };
+            let cost = if edit == Edit::Keep {
+                "free"
+            } else {
+                "one edit"
+            };
+            self.push(
+                format!(
+                    "d[{}][{}] comes from d[{}][{}] by {}, which is {}",
+                    i, j, cell.0, cell.1, text, cost
+                ),
+                highlights,
+                &edits,
+                steps,
+            );
+        }
+        highlights.rows.clear();
+        highlights.columns.clear();
+        let counts = [
+            (Edit::Replace, "replacement"),
+            (Edit::Delete, "deletion"),
+            (Edit::Insert, "insertion"),
+        ]
+        .into_iter()
+        .filter_map(|(edit, word)| {
+            let count = highlights
+                .alignment
+                .iter()
+                .filter(|pair| alignment_edit(**pair) == edit)
+                .count();
+            (count > 0).then(|| plural(count, word))
+        })
+        .collect::<Vec<String>>();
+        let distance = highlights.values[a.len()][b.len()].unwrap();
+        self.push(
+            format!(
+                "The edit distance from \"{}\" to \"{}\" is {}{}",
+                a.iter().collect::<String>(),
+                b.iter().collect::<String>(),
+                distance,
+                match counts.len() {
+                    0 => ", since the strings are the same".to_string(),
+                    1 => format!(": {}", counts[0]),
+                    _ => format!(
+                        ": {} and {}",
+                        counts[..counts.len() - 1].join(", "),
+                        counts[counts.len() - 1]
+                    ),
+                }
+            ),
+            highlights,
+            &edits,
+            steps,
+        );
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(STRING_WIDTH, EDIT_DISTANCE_HEIGHT);
+        let mut values = highlights.values.clone();
+        values.resize(self.from.len() + 1, vec![]);
+        for row in values.iter_mut() {
+            row.resize(self.to.len() + 1, None);
+        }
+        draw_table(
+            &mut diagram,
+            (&self.from, &self.to),
+            &values,
+            &highlights.cells,
+            (&highlights.rows, &highlights.columns),
+        );
+        for (cell, edit) in &highlights.edits {
+            let mut arrow = DiagramEdge::arrow(
+                table_key(cell.0, cell.1),
+                table_key(edit.source(*cell).0, edit.source(*cell).1),
+            );
+            arrow.state = highlights.arrows.get(cell).copied();
+            diagram.add_edge(arrow);
+        }
+        // The alignment is keyed from its end, since the traceback adds to its start
+        let size = highlights.alignment.len();
+        for (index, pair) in highlights.alignment.iter().enumerate() {
+            let key = size - 1 - index;
+            let x = char_x(index as i64, size);
+            let edit = alignment_edit(*pair);
+            let state = if edit == Edit::Keep {
+                ElementState::Selected
+            } else {
+                ElementState::Rejected
+            };
+            for (row, char) in [(0, pair.0), (2, pair.1)] {
+                diagram.add_node(
+                    DiagramNode::rect(
+                        format!("alignment-{}-{}", row, key),
+                        char.unwrap_or('-'),
+                        (x, ALIGNMENT_Y + ALIGNMENT_SPACING * row as f32),
+                        CHAR_SIZE,
+                    )
+                    .with_state(state),
+                );
+            }
+            let symbol = match edit {
+                Edit::Keep => "|",
+                Edit::Replace => "×",
+                Edit::Delete | Edit::Insert => "",
+            };
+            diagram.add_node(DiagramNode::text(
+                format!("alignment-1-{}", key),
+                symbol,
+                (x, ALIGNMENT_Y + ALIGNMENT_SPACING),
+            ));
+        }
+        diagram
+    }
+}
+impl Visualizer for EditDistance {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![Operation::new(
+            "Compute distance",
+            vec![
+                Input::Text {
+                    label: "From",
+                    default: self.from.iter().collect(),
+                },
+                Input::Text {
+                    label: "To",
+                    default: self.to.iter().collect(),
+                },
+            ],
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Compute distance" => {
+                let from = values.first().map(|value| value.text()).unwrap_or_default();
+                let to = values.get(1).map(|value| value.text()).unwrap_or_default();
+                self.from = check_text(&from, "first string", MAX_WORD_LENGTH)?;
+                self.to = check_text(&to, "second string", MAX_WORD_LENGTH)?;
+                let mut highlights = Highlights::default();
+                self.fill_table(&mut highlights, &mut steps);
+                self.trace_back(&mut highlights, &mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn edit_text(edit: Edit, from: char, to: char) -> String {
+    match edit {
+        Edit::Keep => format!("keeping {}", from),
+        Edit::Replace => format!("replacing {} with {}", from, to),
+        Edit::Delete => format!("deleting {}", from),
+        Edit::Insert => format!("inserting {}", to),
+    }
+}
+
+fn alignment_edit(pair: (Option<char>, Option<char>)) -> Edit {
+    match pair {
+        (Some(from), Some(to)) if from == to => Edit::Keep,
+        (Some(_), Some(_)) => Edit::Replace,
+        (Some(_), None) => Edit::Delete,
+        _ => Edit::Insert,
+    }
+}
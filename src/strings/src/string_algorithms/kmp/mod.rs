@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use crate::*;
+
+const KMP_HEIGHT: f32 = 360.0;
+const TEXT_Y: f32 = 50.0;
+const PATTERN_Y: f32 = 130.0;
+const TABLE_CHARS_Y: f32 = 240.0;
+const TABLE_Y: f32 = 300.0;
+
+/// The states of the characters and the failure function at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    /// How many characters the pattern is shifted by under the text.
+    shift: i64,
+    text: BTreeMap<usize, ElementState>,
+    pattern: BTreeMap<usize, ElementState>,
+    table_chars: BTreeMap<usize, ElementState>,
+    table: BTreeMap<usize, ElementState>,
+    /// The values of the failure function that have been computed.
+    fail: Vec<Option<i64>>,
+}
+
+/// The Knuth-Morris-Pratt algorithm, which finds a pattern in a text without ever moving back in the text. A
+/// failure function tells how much of the pattern still matches after a mismatch, so the pattern can slide ahead.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Kmp {
+    text: Vec<char>,
+    pattern: Vec<char>,
+}
+impl Kmp {
+    pub fn new(text: &str, pattern: &str) -> Self {
+        Self {
+            text: text.chars().collect(),
+            pattern: pattern.chars().collect(),
+        }
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &Highlights,
+        matches: &[usize],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = vec![matches_list(matches)];
+    }
+    /// Computes the failure function, where `fail[j]` is the length of the longest proper prefix of the first
+    /// *j + 1* characters of the pattern that is also their suffix.
+    fn build_failure_function(&self, steps: &mut DiagramSteps) -> Vec<usize> {
+        let pattern = &self.pattern;
+        let mut fail = vec![0; pattern.len()];
+        let mut highlights = Highlights {
+            fail: vec![None; pattern.len()],
+            ..Default::default()
+        };
+        highlights.fail[0] = Some(0);
+        highlights.table.insert(0, ElementState::Selected);
+        self.push(
+            "fail[0] = 0, since a single character has no proper prefix that is also its suffix"
+                .to_string(),
+            &highlights,
+            &[],
+            steps,
+        );
+        let mut k = 0;
+        for i in 1..pattern.len() {
+            while k > 0 && pattern[i] != pattern[k] {
+                highlights.table_chars =
+                    BTreeMap::from([(i, ElementState::Rejected), (k, ElementState::Rejected)]);
+                highlights.table = BTreeMap::from([(k - 1, ElementState::Active)]);
+                self.push(
+                    format!(
+                        "p[{}] = {} ≠ p[{}] = {}, so the border of length {} can't grow. Fall back to the border of length fail[{}] = {}",
+                        i, pattern[i], k, pattern[k], k, k - 1, fail[k - 1]
+                    ),
+                    &highlights,
+                    &[],
+                    steps,
+                );
+                k = fail[k - 1];
+            }
+            let description = if pattern[i] == pattern[k] {
+                k += 1;
+                highlights.table_chars =
+                    BTreeMap::from([(i, ElementState::Selected), (k - 1, ElementState::Selected)]);
+                format!(
+                    "p[{}] = p[{}] = {}, so the border grows to {} and fail[{}] = {}",
+                    i,
+                    k - 1,
+                    pattern[i],
+                    plural(k, "character"),
+                    i,
+                    k
+                )
+            } else {
+                highlights.table_chars =
+                    BTreeMap::from([(i, ElementState::Rejected), (0, ElementState::Rejected)]);
+                format!(
+                    "p[{}] = {} ≠ p[0] = {}, so no border is left and fail[{}] = 0",
+                    i, pattern[i], pattern[0], i
+                )
+            };
+            fail[i] = k;
+            highlights.fail[i] = Some(k as i64);
+            highlights.table = BTreeMap::from([(i, ElementState::Selected)]);
+            self.push(description, &highlights, &[], steps);
+        }
+        fail
+    }
+    fn search(&self, steps: &mut DiagramSteps) {
+        let fail = self.build_failure_function(steps);
+        let (text, pattern) = (&self.text, &self.pattern);
+        let mut highlights = Highlights {
+            fail: fail.iter().map(|value| Some(*value as i64)).collect(),
+            ..Default::default()
+        };
+        let mut matches = vec![];
+        let mut comparisons = 0;
+        let mut j = 0;
+        for (i, char) in text.iter().enumerate() {
+            highlights.shift = (i - j) as i64;
+            while j > 0 && *char != pattern[j] {
+                comparisons += 1;
+                highlights.text = matched(i - j, j);
+                highlights.pattern = matched(0, j);
+                highlights.text.insert(i, ElementState::Rejected);
+                highlights.pattern.insert(j, ElementState::Rejected);
+                highlights.table = BTreeMap::from([(j - 1, ElementState::Active)]);
+                let kept = if fail[j - 1] == 0 {
+                    "start over".to_string()
+                } else {
+                    format!(
+                        "keep the first {}",
+                        plural(fail[j - 1], "matched character")
+                    )
+                };
+                self.push(
+                    format!(
+                        "t[{}] = {} ≠ p[{}] = {}. fail[{}] = {}, so slide the pattern ahead by {} and {}",
+                        i,
+                        *char,
+                        j,
+                        pattern[j],
+                        j - 1,
+                        fail[j - 1],
+                        j - fail[j - 1],
+                        kept
+                    ),
+                    &highlights,
+                    &matches,
+                    steps,
+                );
+                j = fail[j - 1];
+                highlights.shift = (i - j) as i64;
+            }
+            comparisons += 1;
+            highlights.table.clear();
+            highlights.text = matched(i - j, j);
+            highlights.pattern = matched(0, j);
+            if *char == pattern[j] {
+                highlights.text.insert(i, ElementState::Selected);
+                highlights.pattern.insert(j, ElementState::Selected);
+                j += 1;
+                if j < pattern.len() {
+                    self.push(
+                        format!("t[{}] = p[{}] = {}", i, j - 1, *char),
+                        &highlights,
+                        &matches,
+                        steps,
+                    );
+                    continue;
+                }
+                let start = i + 1 - pattern.len();
+                matches.push(start);
+                highlights.text = (start..=i).map(|k| (k, ElementState::Selected)).collect();
+                highlights.pattern = (0..j).map(|k| (k, ElementState::Selected)).collect();
+                highlights.table = BTreeMap::from([(j - 1, ElementState::Active)]);
+                self.push(
+                    format!(
+                        "The whole pattern matches at index {}. Continue from the border of length fail[{}] = {}",
+                        start,
+                        j - 1,
+                        fail[j - 1]
+                    ),
+                    &highlights,
+                    &matches,
+                    steps,
+                );
+                j = fail[j - 1];
+            } else {
+                highlights.text.insert(i, ElementState::Rejected);
+                highlights.pattern.insert(j, ElementState::Rejected);
+                self.push(
+                    format!(
+                        "t[{}] = {} ≠ p[0] = {}, so slide the pattern ahead by one",
+                        i, *char, pattern[0]
+                    ),
+                    &highlights,
+                    &matches,
+                    steps,
+                );
+            }
+        }
+        highlights.shift = (text.len() - j) as i64;
+        highlights.text = matches
+            .iter()
+            .flat_map(|start| *start..start + pattern.len())
+            .map(|k| (k, ElementState::Selected))
+            .collect();
+        highlights.pattern.clear();
+        highlights.table.clear();
+        self.push(
+            format!(
+                "The end of the text is reached after {}, which is at most twice its length. The pattern was found {}",
+                plural(comparisons, "character comparison"),
+                plural(matches.len(), "time")
+            ),
+            &highlights,
+            &matches,
+            steps,
+        );
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(STRING_WIDTH, KMP_HEIGHT);
+        let size = self.text.len();
+        draw_chars(
+            &mut diagram,
+            "text",
+            &self.text,
+            (TEXT_Y, 0, size),
+            &highlights.text,
+            true,
+        );
+        draw_chars(
+            &mut diagram,
+            "pattern",
+            &self.pattern,
+            (PATTERN_Y, highlights.shift, size),
+            &highlights.pattern,
+            true,
+        );
+        draw_chars(
+            &mut diagram,
+            "table-char",
+            &self.pattern,
+            (TABLE_CHARS_Y, 0, self.pattern.len()),
+            &highlights.table_chars,
+            true,
+        );
+        let mut fail = highlights.fail.clone();
+        fail.resize(self.pattern.len(), None);
+        draw_values(
+            &mut diagram,
+            "table",
+            "fail",
+            &fail,
+            (TABLE_Y, 0, self.pattern.len()),
+            &highlights.table,
+        );
+        diagram
+    }
+}
+impl Visualizer for Kmp {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![Operation::new(
+            "Search",
+            search_inputs(&self.text, &self.pattern),
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Search" => {
+                (self.text, self.pattern) = parse_search(values)?;
+                self.search(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The characters from `start` that have matched so far.
+fn matched(start: usize, length: usize) -> BTreeMap<usize, ElementState> {
+    (start..start + length)
+        .map(|index| (index, ElementState::Frontier))
+        .collect()
+}
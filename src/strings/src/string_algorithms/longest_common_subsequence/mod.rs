@@ -0,0 +1,289 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use crate::*;
+
+const FIRST_Y: f32 = 500.0;
+const SECOND_Y: f32 = 570.0;
+const LCS_HEIGHT: f32 = 630.0;
+
+/// The neighbor that a cell of the table comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Move {
+    /// The characters are the same and part of the subsequence.
+    Diagonal,
+    Up,
+    Left,
+}
+impl Move {
+    fn source(&self, (i, j): (usize, usize)) -> (usize, usize) {
+        match self {
+            Move::Diagonal => (i - 1, j - 1),
+            Move::Up => (i - 1, j),
+            Move::Left => (i, j - 1),
+        }
+    }
+}
+
+/// The states of the table and the strings at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    values: Vec<Vec<Option<i64>>>,
+    moves: BTreeMap<(usize, usize), Move>,
+    cells: BTreeMap<(usize, usize), ElementState>,
+    arrows: BTreeMap<(usize, usize), ElementState>,
+    rows: BTreeMap<usize, ElementState>,
+    columns: BTreeMap<usize, ElementState>,
+    /// The characters of the strings below the table that are in the subsequence.
+    first: BTreeMap<usize, ElementState>,
+    second: BTreeMap<usize, ElementState>,
+}
+
+/// The longest common subsequence of two strings, which is the longest string whose characters appear in the
+/// same order in both of them, although not necessarily next to each other. It fills a table of the lengths of
+/// the longest common subsequences of all prefixes of the strings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LongestCommonSubsequence {
+    first: Vec<char>,
+    second: Vec<char>,
+}
+impl LongestCommonSubsequence {
+    pub fn new(first: &str, second: &str) -> Self {
+        Self {
+            first: first.chars().collect(),
+            second: second.chars().collect(),
+        }
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &Highlights,
+        subsequence: &[String],
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = vec![StepList::new("Subsequence", subsequence.to_vec())];
+    }
+    fn fill_table(&self, highlights: &mut Highlights, steps: &mut DiagramSteps) {
+        let (a, b) = (&self.first, &self.second);
+        let (n, m) = (a.len(), b.len());
+        highlights.values = vec![vec![None; m + 1]; n + 1];
+        for i in 0..=n {
+            for j in 0..=m {
+                if i == 0 || j == 0 {
+                    highlights.values[i][j] = Some(0);
+                    highlights.cells.insert((i, j), ElementState::Selected);
+                }
+            }
+        }
+        self.push(
+            "L[i][0] = L[0][j] = 0, since an empty string has no characters in common with anything".to_string(),
+            highlights,
+            &[],
+            steps,
+        );
+        for i in 1..=n {
+            for j in 1..=m {
+                let value = |(i, j): (usize, usize)| highlights.values[i][j].unwrap();
+                let (up, left) = (value((i - 1, j)), value((i, j - 1)));
+                let (length, direction, description) = if a[i - 1] == b[j - 1] {
+                    let length = value((i - 1, j - 1)) + 1;
+                    (
+                        length,
+                        Move::Diagonal,
+                        format!(
+                            "{} = {}, so the subsequence of the prefixes before them grows by one: L[{}][{}] = L[{}][{}] + 1 = {}",
+                            a[i - 1], b[j - 1], i, j, i - 1, j - 1, length
+                        ),
+                    )
+                } else {
+                    // Ties go up, which skips the character of the first string
+                    let direction = if up >= left { Move::Up } else { Move::Left };
+                    (
+                        up.max(left),
+                        direction,
+                        format!(
+                            "{} ≠ {}, so one of them isn't in the subsequence: L[{}][{}] = max(L[{}][{}], L[{}][{}]) = max({}, {}) = {}",
+                            a[i - 1], b[j - 1], i, j, i - 1, j, i, j - 1, up, left, up.max(left)
+                        ),
+                    )
+                };
+                highlights.values[i][j] = Some(length);
+                highlights.moves.insert((i, j), direction);
+                let state = if direction == Move::Diagonal {
+                    ElementState::Selected
+                } else {
+                    ElementState::Rejected
+                };
+                highlights.cells = if direction == Move::Diagonal {
+                    BTreeMap::from([((i - 1, j - 1), ElementState::Active)])
+                } else {
+                    BTreeMap::from([
+                        ((i - 1, j), ElementState::Frontier),
+                        ((i, j - 1), ElementState::Frontier),
+                        (direction.source((i, j)), ElementState::Active),
+                    ])
+                };
+                highlights.cells.insert((i, j), ElementState::Selected);
+                highlights.arrows = BTreeMap::from([((i, j), ElementState::Active)]);
+                highlights.rows = BTreeMap::from([(i - 1, state)]);
+                highlights.columns = BTreeMap::from([(j - 1, state)]);
+                self.push(description, highlights, &[], steps);
+            }
+        }
+        highlights.cells.clear();
+        highlights.arrows.clear();
+        highlights.rows.clear();
+        highlights.columns.clear();
+    }
+    /// Follows the arrows back from the last cell and collects the characters of the diagonal moves.
+    fn trace_back(&self, highlights: &mut Highlights, steps: &mut DiagramSteps) {
+        let (a, b) = (&self.first, &self.second);
+        let mut cell = (a.len(), b.len());
+        let mut subsequence = vec![];
+        let length = highlights.values[cell.0][cell.1].unwrap();
+        highlights.cells.insert(cell, ElementState::Selected);
+        self.push(
+            format!(
+                "L[{}][{}] = {} is the length of the longest common subsequence. Follow the arrows back to find its characters",
+                cell.0, cell.1, length
+            ),
+            highlights,
+            &subsequence,
+            steps,
+        );
+        while cell.0 > 0 && cell.1 > 0 {
+            let (i, j) = cell;
+            let direction = highlights.moves[&cell];
+            let description = match direction {
+                Move::Diagonal => {
+                    for states in [&mut highlights.rows, &mut highlights.first] {
+                        states.insert(i - 1, ElementState::Selected);
+                    }
+                    for states in [&mut highlights.columns, &mut highlights.second] {
+                        states.insert(j - 1, ElementState::Selected);
+                    }
+                    subsequence.insert(
+                        0,
+                        format!("{} at indices {} and {}", a[i - 1], i - 1, j - 1),
+                    );
+                    format!(
+                        "a[{}] = b[{}] = {}, so it is part of the subsequence",
+                        i - 1,
+                        j - 1,
+                        a[i - 1]
+                    )
+                }
+                Move::Up => format!(
+                    "L[{}][{}] comes from above, so skip a[{}] = {}",
+                    i,
+                    j,
+                    i - 1,
+                    a[i - 1]
+                ),
+                Move::Left => format!(
+                    "L[{}][{}] comes from the left, so skip b[{}] = {}",
+                    i,
+                    j,
+                    j - 1,
+                    b[j - 1]
+                ),
+            };
+            highlights.arrows.insert(cell, ElementState::Selected);
+            cell = direction.source(cell);
+            highlights.cells.insert(cell, ElementState::Selected);
+            self.push(description, highlights, &subsequence, steps);
+        }
+        let chars = highlights
+            .first
+            .keys()
+            .map(|index| a[*index])
+            .collect::<String>();
+        let description = if length == 0 {
+            "The strings have no characters in common, so the longest common subsequence is empty"
+                .to_string()
+        } else {
+            format!(
+                "The edge of the table is reached. The longest common subsequence of \"{}\" and \"{}\" is \"{}\"",
+                a.iter().collect::<String>(),
+                b.iter().collect::<String>(),
+                chars
+            )
+        };
+        self.push(description, highlights, &subsequence, steps);
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(STRING_WIDTH, LCS_HEIGHT);
+        let mut values = highlights.values.clone();
+        values.resize(self.first.len() + 1, vec![]);
+        for row in values.iter_mut() {
+            row.resize(self.second.len() + 1, None);
+        }
+        draw_table(
+            &mut diagram,
+            (&self.first, &self.second),
+            &values,
+            &highlights.cells,
+            (&highlights.rows, &highlights.columns),
+        );
+        for (cell, direction) in &highlights.moves {
+            let source = direction.source(*cell);
+            let mut arrow =
+                DiagramEdge::arrow(table_key(cell.0, cell.1), table_key(source.0, source.1));
+            arrow.state = highlights.arrows.get(cell).copied();
+            diagram.add_edge(arrow);
+        }
+        // The strings are also drawn below the table, where the characters of the subsequence are highlighted
+        for (prefix, chars, y, states) in [
+            ("first", &self.first, FIRST_Y, &highlights.first),
+            ("second", &self.second, SECOND_Y, &highlights.second),
+        ] {
+            draw_chars(
+                &mut diagram,
+                prefix,
+                chars,
+                (y, 0, chars.len()),
+                states,
+                true,
+            );
+        }
+        diagram
+    }
+}
+impl Visualizer for LongestCommonSubsequence {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![Operation::new(
+            "Find subsequence",
+            vec![
+                Input::Text {
+                    label: "First string",
+                    default: self.first.iter().collect(),
+                },
+                Input::Text {
+                    label: "Second string",
+                    default: self.second.iter().collect(),
+                },
+            ],
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Find subsequence" => {
+                let first = values.first().map(|value| value.text()).unwrap_or_default();
+                let second = values.get(1).map(|value| value.text()).unwrap_or_default();
+                self.first = check_text(&first, "first string", MAX_WORD_LENGTH)?;
+                self.second = check_text(&second, "second string", MAX_WORD_LENGTH)?;
+                let mut highlights = Highlights::default();
+                self.fill_table(&mut highlights, &mut steps);
+                self.trace_back(&mut highlights, &mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
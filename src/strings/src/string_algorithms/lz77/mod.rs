@@ -0,0 +1,383 @@
+use std::{collections::BTreeMap, fmt};
+
+use diagram::*;
+
+use crate::*;
+
+const MAX_WINDOW_SIZE: i64 = 12;
+const MAX_LOOK_AHEAD_SIZE: i64 = 8;
+const LZ77_HEIGHT: f32 = 420.0;
+const TEXT_Y: f32 = 70.0;
+/// The y of the rows of triples, which wrap after `TRIPLES_PER_ROW` triples.
+const TRIPLES_Y: f32 = 170.0;
+const TRIPLES_PER_ROW: usize = 8;
+const TRIPLE_SIZE: (f32, f32) = (84.0, 30.0);
+const TRIPLE_SPACING: (f32, f32) = (92.0, 40.0);
+const OUTPUT_Y: f32 = 330.0;
+/// How far below a row of characters the arrow of a copy is drawn, which leaves room for the indices.
+const COPY_OFFSET: f32 = 44.0;
+
+/// A triple that LZ77 emits, which copies `length` characters from `offset` characters back and adds a literal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Triple {
+    offset: usize,
+    length: usize,
+    literal: char,
+}
+impl fmt::Display for Triple {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.offset, self.length, self.literal)
+    }
+}
+
+/// The states of the text, the triples and the decoded text at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    text: BTreeMap<usize, ElementState>,
+    /// The first and last indices of the search window and the look-ahead buffer.
+    window: Option<(usize, usize)>,
+    look_ahead: Option<(usize, usize)>,
+    triples: Vec<Triple>,
+    triple_states: BTreeMap<usize, ElementState>,
+    output: Vec<char>,
+    output_states: BTreeMap<usize, ElementState>,
+    /// The y of the row and the indices that a copy goes from and to.
+    copy: Option<(f32, usize, usize)>,
+}
+
+/// LZ77, which compresses a text by replacing repeated substrings with references to earlier ones. It slides a window
+/// over the text and emits a triple of the offset and length of the longest match of the look-ahead buffer in the
+/// search window before it, and the next character as a literal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lz77 {
+    text: Vec<char>,
+    window_size: usize,
+    look_ahead_size: usize,
+}
+impl Lz77 {
+    pub fn new(text: &str, window_size: usize, look_ahead_size: usize) -> Self {
+        Self {
+            text: text.chars().collect(),
+            window_size,
+            look_ahead_size,
+        }
+    }
+    fn push(&self, description: String, highlights: &Highlights, steps: &mut DiagramSteps) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = vec![StepList::new(
+            "Triples",
+            highlights
+                .triples
+                .iter()
+                .map(|triple| triple.to_string())
+                .collect(),
+        )];
+    }
+    fn compress(&self, highlights: &mut Highlights, steps: &mut DiagramSteps) {
+        let (text, n) = (&self.text, self.text.len());
+        self.push(
+            format!(
+                "The search window holds the last {} that are already encoded, and the look-ahead buffer holds the next {}",
+                plural(self.window_size, "character"),
+                plural(self.look_ahead_size, "character")
+            ),
+            highlights,
+            steps,
+        );
+        let mut position = 0;
+        while position < n {
+            // The last character is always left for the literal
+            let max_length = self.look_ahead_size.min(n - position - 1);
+            // Ties go to the closest match, since it is found first
+            let (mut offset, mut length) = (0, 0);
+            for candidate in 1..=self.window_size.min(position) {
+                let mut matched = 0;
+                while matched < max_length
+                    && text[position - candidate + matched] == text[position + matched]
+                {
+                    matched += 1;
+                }
+                if matched > length {
+                    (offset, length) = (candidate, matched);
+                }
+            }
+            let literal = text[position + length];
+            let triple = Triple {
+                offset,
+                length,
+                literal,
+            };
+            highlights.window =
+                (position > 0).then(|| (position.saturating_sub(self.window_size), position - 1));
+            highlights.look_ahead = Some((position, (position + self.look_ahead_size).min(n) - 1));
+            highlights.text = (position - offset..position - offset + length)
+                .map(|j| (j, ElementState::Active))
+                .collect();
+            for j in position..position + length {
+                highlights.text.insert(j, ElementState::Selected);
+            }
+            highlights
+                .text
+                .insert(position + length, ElementState::Conflict);
+            highlights.copy = (length > 0).then_some((TEXT_Y, position - offset, position));
+            highlights.triples.push(triple);
+            highlights.triple_states =
+                BTreeMap::from([(highlights.triples.len() - 1, ElementState::Selected)]);
+            let description = if length > 0 {
+                let overlap = if length > offset {
+                    ", which runs into the look-ahead buffer"
+                } else {
+                    ""
+                };
+                format!(
+                    "The longest match of the look-ahead buffer in the window is \"{}\", {} back{}. Emit {} with the next character {} as the literal and move ahead {}",
+                    text[position..position + length].iter().collect::<String>(),
+                    plural(offset, "character"),
+                    overlap,
+                    triple,
+                    literal,
+                    plural(length + 1, "character")
+                )
+            } else if position == 0 {
+                format!(
+                    "The window is empty, so {} is emitted as a literal: {}",
+                    literal, triple
+                )
+            } else if max_length == 0 {
+                format!(
+                    "{} is the last character, and every triple ends with a literal, so it is emitted as one: {}",
+                    literal, triple
+                )
+            } else {
+                format!(
+                    "No character in the window is {}, so it is emitted as a literal: {}",
+                    literal, triple
+                )
+            };
+            self.push(description, highlights, steps);
+            position += length + 1;
+        }
+        highlights.text.clear();
+        highlights.window = None;
+        highlights.look_ahead = None;
+        highlights.copy = None;
+        highlights.triple_states.clear();
+        self.push(
+            format!(
+                "The text of {} is encoded as {}. Decoding replays them from the start",
+                plural(n, "character"),
+                plural(highlights.triples.len(), "triple")
+            ),
+            highlights,
+            steps,
+        );
+    }
+    /// Replays the triples, copying each character from the decoded text one at a time so that copies can overlap
+    /// the characters that they write.
+    fn decompress(&self, highlights: &mut Highlights, steps: &mut DiagramSteps) {
+        for k in 0..highlights.triples.len() {
+            let triple = highlights.triples[k];
+            let start = highlights.output.len();
+            for j in 0..triple.length {
+                let char = highlights.output[start - triple.offset + j];
+                highlights.output.push(char);
+            }
+            highlights.output.push(triple.literal);
+            highlights.output_states = (start - triple.offset
+                ..start - triple.offset + triple.length)
+                .map(|j| (j, ElementState::Active))
+                .collect();
+            for j in start..start + triple.length {
+                highlights.output_states.insert(j, ElementState::Selected);
+            }
+            highlights
+                .output_states
+                .insert(start + triple.length, ElementState::Conflict);
+            highlights.copy =
+                (triple.length > 0).then_some((OUTPUT_Y, start - triple.offset, start));
+            highlights.triple_states = BTreeMap::from([(k, ElementState::Active)]);
+            let description = if triple.length == 0 {
+                format!(
+                    "{} has no match, so only {} is added",
+                    triple, triple.literal
+                )
+            } else {
+                let overlap = if triple.length > triple.offset {
+                    ". The copy overlaps the characters it writes, so they are copied one at a time as they appear"
+                } else {
+                    ""
+                };
+                format!(
+                    "{}: copy {} starting {} back, then add {}{}",
+                    triple,
+                    plural(triple.length, "character"),
+                    triple.offset,
+                    triple.literal,
+                    overlap
+                )
+            };
+            self.push(description, highlights, steps);
+        }
+        highlights.output_states = (0..highlights.output.len())
+            .map(|j| (j, ElementState::Selected))
+            .collect();
+        highlights.copy = None;
+        highlights.triple_states.clear();
+        self.push(
+            format!(
+                "The decoded text \"{}\" is the same as the original",
+                highlights.output.iter().collect::<String>()
+            ),
+            highlights,
+            steps,
+        );
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(STRING_WIDTH, LZ77_HEIGHT);
+        let n = self.text.len();
+        for (key, label, span, state) in [
+            (
+                "window",
+                "Search window",
+                highlights.window,
+                ElementState::Active,
+            ),
+            (
+                "look-ahead",
+                "Look-ahead buffer",
+                highlights.look_ahead,
+                ElementState::Frontier,
+            ),
+        ] {
+            let Some((first, last)) = span else {
+                continue;
+            };
+            let left = char_x(first as i64, n) - CHAR_SIZE.0 / 2.0 - 4.0;
+            let right = char_x(last as i64, n) + CHAR_SIZE.0 / 2.0 + 4.0;
+            diagram.add_figure(
+                Figure::new(FigureKind::Rect {
+                    position: (left, TEXT_Y - CHAR_SIZE.1 / 2.0 - 4.0),
+                    size: (right - left, CHAR_SIZE.1 + 8.0),
+                })
+                .with_state(state),
+            );
+            diagram.add_node(DiagramNode::text(
+                key,
+                label,
+                ((left + right) / 2.0, TEXT_Y - CHAR_SIZE.1 / 2.0 - 18.0),
+            ));
+        }
+        draw_chars(
+            &mut diagram,
+            "char",
+            &self.text,
+            (TEXT_Y, 0, n),
+            &highlights.text,
+            true,
+        );
+        for (index, triple) in highlights.triples.iter().enumerate() {
+            let (row, column) = (index / TRIPLES_PER_ROW, index % TRIPLES_PER_ROW);
+            let mut node = DiagramNode::rect(
+                format!("triple-{}", index),
+                triple,
+                (
+                    STRING_WIDTH / 2.0
+                        + TRIPLE_SPACING.0 * (column as f32 - (TRIPLES_PER_ROW as f32 - 1.0) / 2.0),
+                    TRIPLES_Y + TRIPLE_SPACING.1 * row as f32,
+                ),
+                TRIPLE_SIZE,
+            );
+            node.state = highlights.triple_states.get(&index).copied();
+            diagram.add_node(node);
+        }
+        draw_chars(
+            &mut diagram,
+            "output",
+            &highlights.output,
+            (OUTPUT_Y, 0, n),
+            &highlights.output_states,
+            true,
+        );
+        if let Some((y, from, to)) = highlights.copy {
+            let (start, end, y) = (
+                char_x(from as i64, n),
+                char_x(to as i64, n),
+                y + COPY_OFFSET,
+            );
+            diagram.add_figure(
+                Figure::new(FigureKind::Polyline(vec![
+                    (start, y - 8.0),
+                    (start, y),
+                    (end, y),
+                    (end, y - 8.0),
+                ]))
+                .dashed()
+                .with_state(ElementState::Active),
+            );
+            diagram.add_node(DiagramNode::text(
+                "copy",
+                format!("−{}", to - from),
+                ((start + end) / 2.0, y + 14.0),
+            ));
+        }
+        diagram
+    }
+}
+impl Visualizer for Lz77 {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![Operation::new(
+            "Compress",
+            vec![
+                Input::Text {
+                    label: "Text",
+                    default: self.text.iter().collect(),
+                },
+                Input::Number {
+                    label: "Window size",
+                    default: self.window_size as i64,
+                    min: 1,
+                    max: MAX_WINDOW_SIZE,
+                },
+                Input::Number {
+                    label: "Look-ahead size",
+                    default: self.look_ahead_size as i64,
+                    min: 1,
+                    max: MAX_LOOK_AHEAD_SIZE,
+                },
+            ],
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Compress" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                let text = check_text(&text, "text", MAX_TEXT_LENGTH)?;
+                let window_size = values.get(1).map(|value| value.number()).unwrap_or(1);
+                if !(1..=MAX_WINDOW_SIZE).contains(&window_size) {
+                    return Err(format!(
+                        "The window size must be between 1 and {}",
+                        MAX_WINDOW_SIZE
+                    ));
+                }
+                let look_ahead_size = values.get(2).map(|value| value.number()).unwrap_or(1);
+                if !(1..=MAX_LOOK_AHEAD_SIZE).contains(&look_ahead_size) {
+                    return Err(format!(
+                        "The look-ahead size must be between 1 and {}",
+                        MAX_LOOK_AHEAD_SIZE
+                    ));
+                }
+                (self.text, self.window_size, self.look_ahead_size) =
+                    (text, window_size as usize, look_ahead_size as usize);
+                let mut highlights = Highlights::default();
+                self.compress(&mut highlights, &mut steps);
+                self.decompress(&mut highlights, &mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use crate::*;
+
+/// The longest string whose characters fit side by side with the separators between them.
+const MAX_LENGTH: usize = 11;
+const SEPARATOR: char = '#';
+const MANACHER_HEIGHT: f32 = 330.0;
+const CHARS_Y: f32 = 80.0;
+const RADII_Y: f32 = 170.0;
+const STRING_Y: f32 = 260.0;
+
+/// The states of the strings and the radii at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    chars: BTreeMap<usize, ElementState>,
+    radii: Vec<Option<i64>>,
+    radius_states: BTreeMap<usize, ElementState>,
+    /// The pointers above the characters, like the center and the right boundary.
+    pointers: Vec<(&'static str, usize)>,
+    /// The palindromes that are outlined, by their first and last indices.
+    spans: Vec<(usize, usize, ElementState)>,
+    string: BTreeMap<usize, ElementState>,
+}
+
+/// Manacher's algorithm, which finds the longest palindrome around every center of a string in linear time.
+/// Separators are put between the characters so that palindromes of even length also have a center. Inside the
+/// palindrome that reaches the furthest right, the radius of the mirrored center is reused.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Manacher {
+    string: Vec<char>,
+}
+impl Manacher {
+    pub fn new(string: &str) -> Self {
+        Self {
+            string: string.chars().collect(),
+        }
+    }
+    /// The string with separators between the characters and at both ends.
+    fn separated(&self) -> Vec<char> {
+        let mut chars = vec![SEPARATOR];
+        for char in &self.string {
+            chars.extend([*char, SEPARATOR]);
+        }
+        chars
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &Highlights,
+        longest: (usize, usize),
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(highlights));
+        let (start, length) = longest;
+        step.lists = vec![StepList::new(
+            "Longest palindrome",
+            if length == 0 {
+                vec![]
+            } else {
+                vec![format!(
+                    "{} at index {}",
+                    self.string[start..start + length]
+                        .iter()
+                        .collect::<String>(),
+                    start
+                )]
+            },
+        )];
+    }
+    fn run_manacher(&self, steps: &mut DiagramSteps) {
+        let chars = self.separated();
+        let n = chars.len();
+        let mut radii = vec![0; n];
+        let mut highlights = Highlights {
+            radii: vec![None; n],
+            ..Default::default()
+        };
+        self.push(
+            format!(
+                "Put {} between the characters, so that every palindrome has a center. P[i] is the radius of the longest palindrome around i, which is also its length in the original string",
+                SEPARATOR
+            ),
+            &highlights,
+            (0, 0),
+            steps,
+        );
+        let (mut center, mut right) = (0, 0);
+        // The longest palindrome so far by its start and length in the original string
+        let mut longest = (0, 0);
+        for i in 0..n {
+            // The index mirrored around the center, if the index is inside the palindrome around it
+            let mirror = (i < right).then(|| 2 * center - i);
+            // Whether the radius is copied from the mirror, which happens when it fits inside the palindrome
+            let (start, copied, reason) = match mirror {
+                Some(mirror) => {
+                    let copied = radii[mirror] < right - i;
+                    let start = if copied { radii[mirror] } else { right - i };
+                    let bound = if copied {
+                        format!(
+                            "P[{}] = {} fits inside the palindrome, so it is copied without comparing anything",
+                            mirror, radii[mirror]
+                        )
+                    } else {
+                        format!(
+                            "P[{}] = {} reaches the edge of the palindrome, so P[{}] is only known to be at least R − i = {}",
+                            mirror,
+                            radii[mirror],
+                            i,
+                            right - i
+                        )
+                    };
+                    highlights.radius_states = BTreeMap::from([(mirror, ElementState::Active)]);
+                    (
+                        start,
+                        copied,
+                        format!(
+                            "Index {} is inside the palindrome around C = {}, so it mirrors i' = {}. {}",
+                            i, center, mirror, bound
+                        ),
+                    )
+                }
+                None => {
+                    highlights.radius_states.clear();
+                    (
+                        0,
+                        false,
+                        format!(
+                            "Index {} is at or past the right boundary R = {}, so expand from nothing",
+                            i, right
+                        ),
+                    )
+                }
+            };
+            let mut radius = start;
+            while !copied
+                && radius < i
+                && i + radius + 1 < n
+                && chars[i - radius - 1] == chars[i + radius + 1]
+            {
+                radius += 1;
+            }
+            radii[i] = radius;
+            let expanded = match radius - start {
+                0 if copied || start == 0 => String::new(),
+                0 => ". It can't grow any more".to_string(),
+                grown => format!(". Expanding adds {}", plural(grown, "character")),
+            };
+            let moved = if i + radius > right {
+                (center, right) = (i, i + radius);
+                format!(
+                    ". The palindrome reaches past R, so C = {} and R = {}",
+                    center, right
+                )
+            } else {
+                String::new()
+            };
+            if radius > longest.1 {
+                longest = ((i - radius) / 2, radius);
+            }
+            highlights.radii[i] = Some(radius as i64);
+            highlights.radius_states.insert(i, ElementState::Selected);
+            highlights.chars = (i - radius..=i + radius)
+                .map(|j| {
+                    let state = if j != i && j.abs_diff(i) <= start {
+                        ElementState::Frontier
+                    } else {
+                        ElementState::Selected
+                    };
+                    (j, state)
+                })
+                .collect();
+            // The characters where the expansion stopped
+            if !copied && radius < i && i + radius + 1 < n {
+                highlights
+                    .chars
+                    .insert(i - radius - 1, ElementState::Rejected);
+                highlights
+                    .chars
+                    .insert(i + radius + 1, ElementState::Rejected);
+            }
+            highlights.pointers = vec![("C", center), ("R", right), ("i", i)];
+            if let Some(mirror) = mirror {
+                highlights.pointers.push(("i'", mirror));
+            }
+            highlights.spans = vec![(center - (right - center), right, ElementState::Active)];
+            self.push(
+                format!("{}{}. P[{}] = {}{}", reason, expanded, i, radius, moved),
+                &highlights,
+                longest,
+                steps,
+            );
+        }
+        let (start, length) = longest;
+        let center = 2 * start + length;
+        highlights.chars = (center - length..=center + length)
+            .map(|j| (j, ElementState::Selected))
+            .collect();
+        highlights.radius_states = BTreeMap::from([(center, ElementState::Selected)]);
+        highlights.pointers.clear();
+        highlights.spans = vec![(center - length, center + length, ElementState::Selected)];
+        highlights.string = (start..start + length)
+            .map(|j| (j, ElementState::Selected))
+            .collect();
+        self.push(
+            format!(
+                "All centers are done, and R only moved ahead. The largest radius is P[{}] = {}, so the longest palindromic substring is \"{}\"",
+                center,
+                length,
+                self.string[start..start + length].iter().collect::<String>()
+            ),
+            &highlights,
+            longest,
+            steps,
+        );
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(STRING_WIDTH, MANACHER_HEIGHT);
+        let chars = self.separated();
+        let n = chars.len();
+        for (first, last, state) in &highlights.spans {
+            let left = char_x(*first as i64, n) - CHAR_SIZE.0 / 2.0 - 4.0;
+            diagram.add_figure(
+                Figure::new(FigureKind::Rect {
+                    position: (left, CHARS_Y - CHAR_SIZE.1 / 2.0 - 4.0),
+                    size: (
+                        char_x(*last as i64, n) + CHAR_SIZE.0 / 2.0 + 4.0 - left,
+                        CHAR_SIZE.1 + 8.0,
+                    ),
+                })
+                .with_state(*state),
+            );
+        }
+        draw_chars(
+            &mut diagram,
+            "char",
+            &chars,
+            (CHARS_Y, 0, n),
+            &highlights.chars,
+            true,
+        );
+        // Pointers to the same index are stacked above it
+        let mut stacked = BTreeMap::<usize, usize>::new();
+        for (name, index) in &highlights.pointers {
+            let level = stacked.entry(*index).or_default();
+            diagram.add_node(DiagramNode::text(
+                format!("pointer-{}", name),
+                *name,
+                (
+                    char_x(*index as i64, n),
+                    CHARS_Y - CHAR_SIZE.1 / 2.0 - 18.0 - 18.0 * *level as f32,
+                ),
+            ));
+            *level += 1;
+        }
+        let mut radii = highlights.radii.clone();
+        radii.resize(n, None);
+        draw_values(
+            &mut diagram,
+            "radius",
+            "P",
+            &radii,
+            (RADII_Y, 0, n),
+            &highlights.radius_states,
+        );
+        draw_chars(
+            &mut diagram,
+            "string",
+            &self.string,
+            (STRING_Y, 0, self.string.len()),
+            &highlights.string,
+            true,
+        );
+        diagram
+    }
+}
+impl Visualizer for Manacher {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![Operation::new(
+            "Find palindromes",
+            vec![Input::Text {
+                label: "String",
+                default: self.string.iter().collect(),
+            }],
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Find palindromes" => {
+                let string = values.first().map(|value| value.text()).unwrap_or_default();
+                self.string = check_text(&string, "string", MAX_LENGTH)?;
+                self.run_manacher(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
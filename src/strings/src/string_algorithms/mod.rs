@@ -0,0 +1,24 @@
+//! A collection of algorithms on strings.
+mod aho_corasick;
+mod boyer_moore;
+mod burrows_wheeler;
+mod edit_distance;
+mod kmp;
+mod longest_common_subsequence;
+mod lz77;
+mod manacher;
+mod rabin_karp;
+mod suffix_array;
+mod z_algorithm;
+
+pub use aho_corasick::AhoCorasick;
+pub use boyer_moore::BoyerMoore;
+pub use burrows_wheeler::BurrowsWheeler;
+pub use edit_distance::EditDistance;
+pub use kmp::Kmp;
+pub use longest_common_subsequence::LongestCommonSubsequence;
+pub use lz77::Lz77;
+pub use manacher::Manacher;
+pub use rabin_karp::RabinKarp;
+pub use suffix_array::SuffixArray;
+pub use z_algorithm::ZAlgorithm;
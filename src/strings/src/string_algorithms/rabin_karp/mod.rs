@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use crate::*;
+
+/// The base of the hash, so that strings of digits are hashed as the numbers that they are.
+const BASE: i64 = 10;
+const MAX_MODULUS: i64 = 997;
+const RABIN_KARP_HEIGHT: f32 = 330.0;
+const TEXT_Y: f32 = 50.0;
+const PATTERN_Y: f32 = 130.0;
+const HASHES_Y: f32 = 210.0;
+
+/// The states of the characters and the hashes of the windows at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    shift: i64,
+    text: BTreeMap<usize, ElementState>,
+    pattern: BTreeMap<usize, ElementState>,
+    /// The hashes of the windows of the text by the indices where they start.
+    hashes: Vec<Option<i64>>,
+    hash_states: BTreeMap<usize, ElementState>,
+}
+
+/// The Rabin-Karp algorithm, which compares the hash of the pattern to the hashes of all windows of the text and only
+/// compares the characters of the windows with the same hash. The hash of the next window is rolled from the
+/// previous one in constant time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RabinKarp {
+    text: Vec<char>,
+    pattern: Vec<char>,
+    modulus: i64,
+}
+impl RabinKarp {
+    pub fn new(text: &str, pattern: &str, modulus: i64) -> Self {
+        Self {
+            text: text.chars().collect(),
+            pattern: pattern.chars().collect(),
+            modulus: modulus.clamp(2, MAX_MODULUS),
+        }
+    }
+    fn push(
+        &self,
+        description: String,
+        highlights: &Highlights,
+        (matches, spurious_hits): (&[usize], &[usize]),
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, self.draw(highlights));
+        step.lists = vec![
+            matches_list(matches),
+            StepList::new(
+                "Spurious hits",
+                spurious_hits
+                    .iter()
+                    .map(|index| format!("At index {}", index))
+                    .collect(),
+            ),
+        ];
+    }
+    fn hash(&self, chars: &[char]) -> i64 {
+        chars
+            .iter()
+            .fold(0, |hash, char| (hash * BASE + code(*char)) % self.modulus)
+    }
+    /// The hash with how it is computed, which is shown for strings of digits since they are hashed as numbers.
+    fn hash_text(&self, chars: &[char]) -> String {
+        if chars.iter().all(|char| char.is_ascii_digit()) {
+            format!(
+                "{} mod {} = {}",
+                chars.iter().collect::<String>(),
+                self.modulus,
+                self.hash(chars)
+            )
+        } else {
+            self.hash(chars).to_string()
+        }
+    }
+    fn search(&self, steps: &mut DiagramSteps) {
+        let (text, pattern, q) = (&self.text, &self.pattern, self.modulus);
+        let m = pattern.len();
+        if m > text.len() {
+            self.push(
+                "The pattern is longer than the text, so it can't be found".to_string(),
+                &Highlights::default(),
+                (&[], &[]),
+                steps,
+            );
+            return;
+        }
+        let windows = text.len() - m + 1;
+        let pattern_hash = self.hash(pattern);
+        // The weight of the first character of a window, which is removed when the window rolls ahead
+        let weight = (0..m - 1).fold(1, |weight, _| weight * BASE % q);
+        let mut highlights = Highlights {
+            hashes: vec![None; windows],
+            pattern: (0..m).map(|j| (j, ElementState::Active)).collect(),
+            ..Default::default()
+        };
+        self.push(
+            format!(
+                "hash(p) = {}. Removing the first character of a window takes away its code times {}^{} mod {} = {}",
+                self.hash_text(pattern), BASE, m - 1, q, weight
+            ),
+            &highlights,
+            (&[], &[]),
+            steps,
+        );
+        let (mut matches, mut spurious_hits) = (vec![], vec![]);
+        let mut hash = self.hash(&text[..m]);
+        for shift in 0..windows {
+            let description = if shift == 0 {
+                format!("hash(t[0..{}]) = {}", m, self.hash_text(&text[..m]))
+            } else {
+                let (removed, added) = (code(text[shift - 1]), code(text[shift + m - 1]));
+                let previous = hash;
+                hash = ((previous - removed * weight) * BASE + added).rem_euclid(q);
+                format!(
+                    "Roll the window ahead: ((hash − {} · {}) · {} + {}) mod {} = (({} − {}) · {} + {}) mod {} = {}",
+                    removed, weight, BASE, added, q, previous, removed * weight, BASE, added, q, hash
+                )
+            };
+            highlights.shift = shift as i64;
+            highlights.hashes[shift] = Some(hash);
+            highlights.text = (shift..shift + m)
+                .map(|i| (i, ElementState::Frontier))
+                .collect();
+            highlights.pattern.clear();
+            if hash != pattern_hash {
+                highlights.hash_states.insert(shift, ElementState::Rejected);
+                self.push(
+                    format!(
+                        "{}. {} ≠ {}, so the window can't match",
+                        description, hash, pattern_hash
+                    ),
+                    &highlights,
+                    (&matches, &spurious_hits),
+                    steps,
+                );
+                continue;
+            }
+            highlights.hash_states.insert(shift, ElementState::Active);
+            self.push(
+                format!(
+                    "{}. The hashes are the same, so check the characters of the window",
+                    description
+                ),
+                &highlights,
+                (&matches, &spurious_hits),
+                steps,
+            );
+            let mismatch = (0..m).find(|j| text[shift + j] != pattern[*j]);
+            // The comparisons up to the first mismatch are shown in one step
+            let compared = mismatch.map_or(m, |j| j + 1);
+            for j in 0..compared {
+                let state = if Some(j) == mismatch {
+                    ElementState::Rejected
+                } else {
+                    ElementState::Selected
+                };
+                highlights.text.insert(shift + j, state);
+                highlights.pattern.insert(j, state);
+            }
+            match mismatch {
+                Some(j) => {
+                    spurious_hits.push(shift);
+                    highlights.hash_states.insert(shift, ElementState::Conflict);
+                    self.push(
+                        format!(
+                            "t[{}] = {} ≠ p[{}] = {}, so this is a spurious hit: the window has the same hash but different characters",
+                            shift + j,
+                            text[shift + j],
+                            j,
+                            pattern[j]
+                        ),
+                        &highlights,
+                        (&matches, &spurious_hits),
+                        steps,
+                    );
+                }
+                None => {
+                    matches.push(shift);
+                    highlights.hash_states.insert(shift, ElementState::Selected);
+                    self.push(
+                        format!(
+                            "All {} match, so the pattern is found at index {}",
+                            plural(m, "character"),
+                            shift
+                        ),
+                        &highlights,
+                        (&matches, &spurious_hits),
+                        steps,
+                    );
+                }
+            }
+        }
+        highlights.text = matches
+            .iter()
+            .flat_map(|start| *start..start + m)
+            .map(|i| (i, ElementState::Selected))
+            .collect();
+        highlights.pattern.clear();
+        self.push(
+            format!(
+                "All {} are hashed. The pattern was found {}, and {} had the same hash without matching",
+                plural(windows, "window"),
+                plural(matches.len(), "time"),
+                plural(spurious_hits.len(), "window")
+            ),
+            &highlights,
+            (&matches, &spurious_hits),
+            steps,
+        );
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(STRING_WIDTH, RABIN_KARP_HEIGHT);
+        let size = self.text.len();
+        draw_chars(
+            &mut diagram,
+            "text",
+            &self.text,
+            (TEXT_Y, 0, size),
+            &highlights.text,
+            true,
+        );
+        draw_chars(
+            &mut diagram,
+            "pattern",
+            &self.pattern,
+            (PATTERN_Y, highlights.shift, size),
+            &highlights.pattern,
+            false,
+        );
+        let mut hashes = highlights.hashes.clone();
+        hashes.resize((size + 1).saturating_sub(self.pattern.len()), None);
+        draw_values(
+            &mut diagram,
+            "hash",
+            "hash",
+            &hashes,
+            (HASHES_Y, 0, size),
+            &highlights.hash_states,
+        );
+        diagram.add_node(DiagramNode::text(
+            "info",
+            format!(
+                "hash(p) = {}, base {}, modulus {}",
+                self.hash(&self.pattern),
+                BASE,
+                self.modulus
+            ),
+            (STRING_WIDTH / 2.0, RABIN_KARP_HEIGHT - 40.0),
+        ));
+        diagram
+    }
+}
+impl Visualizer for RabinKarp {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let mut inputs = search_inputs(&self.text, &self.pattern);
+        inputs.push(Input::Number {
+            label: "Modulus",
+            default: self.modulus,
+            min: 2,
+            max: MAX_MODULUS,
+        });
+        vec![Operation::new("Search", inputs)]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Search" => {
+                let (text, pattern) = parse_search(values)?;
+                let modulus = values.get(2).map(|value| value.number()).unwrap_or(2);
+                if !(2..=MAX_MODULUS).contains(&modulus) {
+                    return Err(format!("The modulus must be between 2 and {}", MAX_MODULUS));
+                }
+                (self.text, self.pattern, self.modulus) = (text, pattern, modulus);
+                self.search(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The number that a character is hashed as. Digits are their own values, and other characters are their code points.
+fn code(char: char) -> i64 {
+    char.to_digit(10).unwrap_or(char as u32) as i64
+}
@@ -0,0 +1,431 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use diagram::*;
+
+use crate::*;
+
+const MAX_LENGTH: usize = 12;
+const SUFFIX_ARRAY_HEIGHT: f32 = 570.0;
+const TEXT_Y: f32 = 40.0;
+const RANKS_Y: f32 = 105.0;
+/// The y of the first row of suffixes.
+const ROWS_Y: f32 = 190.0;
+const ROW_SPACING: f32 = 30.0;
+const START_X: f32 = 110.0;
+const PAIR_X: f32 = 190.0;
+const RANK_X: f32 = 265.0;
+const SUFFIX_X: f32 = 320.0;
+const SUFFIX_CHAR_SIZE: (f32, f32) = (20.0, 24.0);
+const SUFFIX_CHAR_SPACING: f32 = 22.0;
+const LCP_X: f32 = 660.0;
+const CELL_SIZE: (f32, f32) = (34.0, 24.0);
+const LCP_CHOICES: [&str; 2] = ["Include", "Leave out"];
+
+/// The states of the suffixes and the arrays at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    /// The starts of the suffixes in the order of the rows.
+    order: Vec<usize>,
+    /// The ranks of the suffixes by their starts.
+    ranks: Vec<Option<i64>>,
+    /// The pairs of ranks that the suffixes are sorted by in a round.
+    pairs: Vec<Option<(i64, i64)>>,
+    /// How many characters of the suffixes the ranks are known for.
+    prefix: usize,
+    rows: BTreeMap<usize, ElementState>,
+    /// The states of the characters of the suffixes by their starts and the indices in them.
+    suffix_chars: BTreeMap<(usize, usize), ElementState>,
+    /// The lengths of the longest common prefixes with the previous suffix by the row.
+    lcp: Vec<Option<i64>>,
+    lcp_states: BTreeMap<usize, ElementState>,
+}
+
+/// A suffix array, which is the starts of all suffixes of a text in sorted order. It is built by prefix doubling,
+/// which sorts the suffixes by their first 2*k* characters using the ranks of their first *k* characters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuffixArray {
+    text: Vec<char>,
+    lcp: bool,
+}
+impl SuffixArray {
+    pub fn new(text: &str, lcp: bool) -> Self {
+        Self {
+            text: text.chars().collect(),
+            lcp,
+        }
+    }
+    fn push(&self, description: String, highlights: &Highlights, steps: &mut DiagramSteps) {
+        let diagram = self.draw(highlights);
+        let suffixes = highlights
+            .order
+            .iter()
+            .enumerate()
+            .map(|(row, start)| format!("SA[{}] = {}: {}", row, start, self.suffix(*start)))
+            .collect();
+        let step = steps.push(description, diagram);
+        step.lists = vec![StepList::new("Suffixes", suffixes)];
+        if highlights.lcp.iter().any(|value| value.is_some()) {
+            step.lists.push(StepList::new(
+                "LCP array",
+                highlights
+                    .lcp
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(row, value)| {
+                        value.map(|value| format!("LCP[{}] = {}", row, value))
+                    })
+                    .collect(),
+            ));
+        }
+    }
+    fn suffix(&self, start: usize) -> String {
+        self.text[start..].iter().collect()
+    }
+    fn build(&self, steps: &mut DiagramSteps) -> Vec<usize> {
+        let (text, n) = (&self.text, self.text.len());
+        let chars = text.iter().copied().collect::<BTreeSet<char>>();
+        let mut ranks = text
+            .iter()
+            .map(|char| chars.iter().position(|other| other == char).unwrap() as i64)
+            .collect::<Vec<i64>>();
+        let mut order = (0..n).collect::<Vec<usize>>();
+        order.sort_by_key(|start| ranks[*start]);
+        let mut highlights = Highlights {
+            order: order.clone(),
+            ranks: ranks.iter().map(|rank| Some(*rank)).collect(),
+            pairs: vec![None; n],
+            prefix: 1,
+            ..Default::default()
+        };
+        self.push(
+            format!(
+                "Rank the suffixes by their first characters. There are {}, so there are {}",
+                plural(chars.len(), "different character"),
+                plural(chars.len(), "different rank")
+            ),
+            &highlights,
+            steps,
+        );
+        let mut k = 1;
+        let mut round = 1;
+        while distinct(&ranks) < n {
+            highlights.pairs = (0..n)
+                .map(|i| Some((ranks[i], ranks.get(i + k).copied().unwrap_or(-1))))
+                .collect();
+            highlights.suffix_chars = order
+                .iter()
+                .flat_map(|start| {
+                    (*start + k..(*start + 2 * k).min(n))
+                        .map(move |i| ((*start, i - start), ElementState::Frontier))
+                })
+                .collect();
+            let next = if k == 1 {
+                "character".to_string()
+            } else {
+                format!("{} characters", k)
+            };
+            self.push(
+                format!(
+                    "Round {}: pair the rank of every suffix with the rank of the suffix {} later, which is the rank of its next {}. The pair is −1 if that suffix is past the end",
+                    round,
+                    plural(k, "character"),
+                    next
+                ),
+                &highlights,
+                steps,
+            );
+            let pairs = highlights
+                .pairs
+                .iter()
+                .map(|pair| pair.unwrap())
+                .collect::<Vec<(i64, i64)>>();
+            order.sort_by_key(|start| pairs[*start]);
+            // Suffixes with the same pair get the same rank, which counts the different pairs before them
+            let mut rank = 0;
+            for (row, start) in order.iter().enumerate() {
+                if row > 0 && pairs[*start] != pairs[order[row - 1]] {
+                    rank += 1;
+                }
+                ranks[*start] = rank;
+            }
+            highlights.order = order.clone();
+            highlights.ranks = ranks.iter().map(|rank| Some(*rank)).collect();
+            highlights.prefix = (2 * k).min(n);
+            highlights.suffix_chars.clear();
+            self.push(
+                format!(
+                    "Sort the suffixes by their pairs, which sorts them by their first {}, and give the same rank to suffixes with the same pair. {} of the {} ranks are different",
+                    plural(2 * k, "character"),
+                    distinct(&ranks),
+                    n
+                ),
+                &highlights,
+                steps,
+            );
+            highlights.pairs = vec![None; n];
+            k *= 2;
+            round += 1;
+        }
+        highlights.prefix = 0;
+        self.push(
+            format!(
+                "All ranks are different, so the suffixes are sorted after {}. The suffix array is [{}]",
+                plural(round - 1, "round"),
+                order
+                    .iter()
+                    .map(|start| start.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            &highlights,
+            steps,
+        );
+        order
+    }
+    /// Builds the LCP array with Kasai's algorithm, which goes through the suffixes from the longest to the
+    /// shortest. The common prefix of a suffix is at most one shorter than that of the previous one.
+    fn build_lcp(&self, order: &[usize], steps: &mut DiagramSteps) {
+        let (text, n) = (&self.text, self.text.len());
+        let mut rank_of = vec![0; n];
+        for (row, start) in order.iter().enumerate() {
+            rank_of[*start] = row;
+        }
+        let mut highlights = Highlights {
+            order: order.to_vec(),
+            ranks: rank_of.iter().map(|row| Some(*row as i64)).collect(),
+            pairs: vec![None; n],
+            lcp: vec![None; n],
+            ..Default::default()
+        };
+        highlights.lcp[0] = Some(0);
+        self.push(
+            "The LCP array has the length of the longest common prefix of every suffix and the one before it in the suffix array. Go through the suffixes from the longest one, since the common prefix of the next one is at most one shorter".to_string(),
+            &highlights,
+            steps,
+        );
+        let mut h = 0;
+        for i in 0..n {
+            let row = rank_of[i];
+            highlights.rows.clear();
+            highlights.suffix_chars.clear();
+            highlights.lcp_states.clear();
+            if row == 0 {
+                h = 0;
+                highlights.rows.insert(i, ElementState::Active);
+                self.push(
+                    format!(
+                        "Suffix {} is the first one in the suffix array, so it has no previous suffix and LCP[0] = 0",
+                        i
+                    ),
+                    &highlights,
+                    steps,
+                );
+                continue;
+            }
+            let j = order[row - 1];
+            let known = h;
+            while i + h < n && j + h < n && text[i + h] == text[j + h] {
+                h += 1;
+            }
+            highlights.rows =
+                BTreeMap::from([(i, ElementState::Active), (j, ElementState::Active)]);
+            for start in [i, j] {
+                for index in 0..h {
+                    let state = if index < known {
+                        ElementState::Frontier
+                    } else {
+                        ElementState::Selected
+                    };
+                    highlights.suffix_chars.insert((start, index), state);
+                }
+                if start + h < n {
+                    highlights
+                        .suffix_chars
+                        .insert((start, h), ElementState::Rejected);
+                }
+            }
+            highlights.lcp[row] = Some(h as i64);
+            highlights.lcp_states.insert(row, ElementState::Selected);
+            let skipped = if known > 0 {
+                format!(
+                    "Comparing after the first {} that are known to match,",
+                    plural(known, "character")
+                )
+            } else {
+                "Comparing from the start,".to_string()
+            };
+            self.push(
+                format!(
+                    "Suffix {} comes after suffix {}. {} the common prefix has {}, so LCP[{}] = {}",
+                    i,
+                    j,
+                    skipped,
+                    plural(h, "character"),
+                    row,
+                    h
+                ),
+                &highlights,
+                steps,
+            );
+            h = h.saturating_sub(1);
+        }
+        highlights.rows.clear();
+        highlights.suffix_chars.clear();
+        highlights.lcp_states.clear();
+        let longest = (0..n).max_by_key(|row| highlights.lcp[*row]).unwrap();
+        let repeat = highlights.lcp[longest].unwrap() as usize;
+        let description = if repeat == 0 {
+            "The LCP array is done. No substring appears more than once".to_string()
+        } else {
+            let start = order[longest];
+            for start in [start, order[longest - 1]] {
+                for index in 0..repeat {
+                    highlights
+                        .suffix_chars
+                        .insert((start, index), ElementState::Selected);
+                }
+            }
+            highlights
+                .lcp_states
+                .insert(longest, ElementState::Selected);
+            format!(
+                "The LCP array is done. Its largest value shows that \"{}\" is the longest substring that appears more than once",
+                text[start..start + repeat].iter().collect::<String>()
+            )
+        };
+        self.push(description, &highlights, steps);
+    }
+    fn draw(&self, highlights: &Highlights) -> Diagram {
+        let mut diagram = Diagram::new(STRING_WIDTH, SUFFIX_ARRAY_HEIGHT);
+        let n = self.text.len();
+        draw_chars(
+            &mut diagram,
+            "text",
+            &self.text,
+            (TEXT_Y, 0, n),
+            &BTreeMap::new(),
+            true,
+        );
+        let mut ranks = highlights.ranks.clone();
+        ranks.resize(n, None);
+        draw_values(
+            &mut diagram,
+            "ranks",
+            "rank",
+            &ranks,
+            (RANKS_Y, 0, n),
+            &BTreeMap::new(),
+        );
+        let header_y = ROWS_Y - ROW_SPACING;
+        for (key, label, x) in [
+            ("start", "start", START_X),
+            ("pair", "pair", PAIR_X),
+            ("rank", "rank", RANK_X),
+            ("suffix", "suffix", SUFFIX_X + SUFFIX_CHAR_SPACING * 2.0),
+            ("lcp", "LCP", LCP_X),
+        ] {
+            if key != "lcp" || highlights.lcp.iter().any(|value| value.is_some()) {
+                diagram.add_node(DiagramNode::text(
+                    format!("{}-header", key),
+                    label,
+                    (x, header_y),
+                ));
+            }
+        }
+        // Before the first step, the suffixes are drawn in the order of their starts
+        let order = if highlights.order.is_empty() {
+            (0..n).collect()
+        } else {
+            highlights.order.clone()
+        };
+        for (row, start) in order.iter().enumerate() {
+            let y = ROWS_Y + ROW_SPACING * row as f32;
+            let mut node =
+                DiagramNode::rect(format!("start-{}", start), start, (START_X, y), CELL_SIZE);
+            node.state = highlights.rows.get(start).copied();
+            diagram.add_node(node);
+            if let Some((first, second)) = highlights.pairs.get(*start).copied().flatten() {
+                diagram.add_node(DiagramNode::text(
+                    format!("pair-{}", start),
+                    format!("({}, {})", first, second).replace('-', "−"),
+                    (PAIR_X, y),
+                ));
+            }
+            if let Some(rank) = ranks[*start] {
+                diagram.add_node(DiagramNode::rect(
+                    format!("rank-{}", start),
+                    rank,
+                    (RANK_X, y),
+                    CELL_SIZE,
+                ));
+            }
+            for (index, char) in self.text[*start..].iter().enumerate() {
+                let mut node = DiagramNode::rect(
+                    format!("suffix-{}-{}", start, index),
+                    char,
+                    (SUFFIX_X + SUFFIX_CHAR_SPACING * index as f32, y),
+                    SUFFIX_CHAR_SIZE,
+                );
+                node.state = highlights
+                    .suffix_chars
+                    .get(&(*start, index))
+                    .copied()
+                    .or_else(|| (index < highlights.prefix).then_some(ElementState::Active));
+                diagram.add_node(node);
+            }
+            if let Some(lcp) = highlights.lcp.get(row).copied().flatten() {
+                let mut node =
+                    DiagramNode::rect(format!("lcp-{}", row), lcp, (LCP_X, y), CELL_SIZE);
+                node.state = highlights.lcp_states.get(&row).copied();
+                diagram.add_node(node);
+            }
+        }
+        diagram
+    }
+}
+impl Visualizer for SuffixArray {
+    fn diagram(&self) -> Diagram {
+        self.draw(&Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        let mut choices = LCP_CHOICES.map(|choice| choice.to_string()).to_vec();
+        if !self.lcp {
+            choices.reverse();
+        }
+        vec![Operation::new(
+            "Build suffix array",
+            vec![
+                Input::Text {
+                    label: "Text",
+                    default: self.text.iter().collect(),
+                },
+                Input::Choice {
+                    label: "LCP array",
+                    choices,
+                },
+            ],
+        )]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Build suffix array" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                self.text = check_text(&text, "text", MAX_LENGTH)?;
+                self.lcp =
+                    values.get(1).map(|value| value.text()).as_deref() == Some(LCP_CHOICES[0]);
+                let order = self.build(&mut steps);
+                if self.lcp {
+                    self.build_lcp(&order, &mut steps);
+                }
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+/// The number of different ranks.
+fn distinct(ranks: &[i64]) -> usize {
+    ranks.iter().collect::<BTreeSet<&i64>>().len()
+}
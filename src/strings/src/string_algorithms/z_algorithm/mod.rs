@@ -0,0 +1,328 @@
+use std::collections::BTreeMap;
+
+use diagram::*;
+
+use crate::*;
+
+const Z_HEIGHT: f32 = 300.0;
+const STRING_Y: f32 = 70.0;
+const PREFIX_Y: f32 = 150.0;
+const Z_Y: f32 = 230.0;
+/// The longest pattern and text that fit side by side in a row when they are joined for a search.
+const MAX_SEARCH_PATTERN_LENGTH: usize = 7;
+const MAX_SEARCH_TEXT_LENGTH: usize = 16;
+/// The characters that can separate the pattern from the text, since it can't be in either of them.
+const SEPARATORS: [char; 4] = ['$', '#', '|', '¤'];
+
+/// The states of the string, the window and the Z array at a step.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Highlights {
+    chars: BTreeMap<usize, ElementState>,
+    /// The index that the prefix of the string is lined up with.
+    prefix_shift: Option<usize>,
+    prefix: BTreeMap<usize, ElementState>,
+    /// The window `[l, r]` of the rightmost match of a prefix found so far.
+    window: Option<(usize, usize)>,
+    z: Vec<Option<i64>>,
+    z_states: BTreeMap<usize, ElementState>,
+}
+
+/// The Z-algorithm, which computes the Z array of a string, where `Z[i]` is the length of the longest substring
+/// starting at *i* that is also a prefix of the string. It keeps the window of the rightmost match of a prefix
+/// found so far, which lets it reuse earlier values instead of comparing characters again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZAlgorithm {
+    text: Vec<char>,
+    pattern: Vec<char>,
+}
+impl ZAlgorithm {
+    pub fn new(text: &str, pattern: &str) -> Self {
+        Self {
+            text: text.chars().collect(),
+            pattern: pattern.chars().collect(),
+        }
+    }
+    fn push(
+        &self,
+        string: &[char],
+        description: String,
+        highlights: &Highlights,
+        matches: Option<&[usize]>,
+        steps: &mut DiagramSteps,
+    ) {
+        let step = steps.push(description, draw(string, highlights));
+        if let Some(matches) = matches {
+            step.lists = vec![matches_list(matches)];
+        }
+    }
+    /// Computes the Z array of the string and counts the character comparisons. The steps have a list of matches
+    /// if the string is a pattern and a text joined for a search.
+    fn compute(
+        &self,
+        string: &[char],
+        highlights: &mut Highlights,
+        search: bool,
+        steps: &mut DiagramSteps,
+    ) -> (Vec<usize>, usize) {
+        let n = string.len();
+        let matches = search.then_some(&[][..]);
+        let mut z = vec![0; n];
+        highlights.z = vec![None; n];
+        self.push(
+            string,
+            "Z[0] is left out, since the whole string is trivially a prefix of itself. The window [l, r] starts empty"
+                .to_string(),
+            highlights,
+            matches,
+            steps,
+        );
+        let (mut l, mut r) = (0, 0);
+        let mut comparisons = 0;
+        for i in 1..n {
+            // The number of characters that are known to match without comparing them
+            let (known, reason) = if i >= r {
+                (
+                    0,
+                    format!(
+                        "Index {} is outside the window, so compare s[{}..] with the start of the string",
+                        i, i
+                    ),
+                )
+            } else {
+                let k = i - l;
+                highlights.z_states = BTreeMap::from([(k, ElementState::Active)]);
+                if z[k] < r - i {
+                    z[i] = z[k];
+                    highlights.z[i] = Some(z[i] as i64);
+                    highlights.z_states.insert(i, ElementState::Selected);
+                    highlights.prefix_shift = Some(i);
+                    highlights.chars = (i..i + z[i]).map(|j| (j, ElementState::Frontier)).collect();
+                    highlights.prefix = (0..z[i]).map(|j| (j, ElementState::Frontier)).collect();
+                    self.push(
+                        string,
+                        format!(
+                            "Index {} is inside the window [{}, {}], which matches s[0..{}], so it mirrors index {}. Z[{}] = {} ends before the window does, so Z[{}] = {} without comparing anything",
+                            i, l, r - 1, r - l, k, k, z[k], i, z[i]
+                        ),
+                        highlights,
+                        matches,
+                        steps,
+                    );
+                    highlights.z_states.clear();
+                    continue;
+                }
+                (
+                    r - i,
+                    format!(
+                        "Index {} is inside the window [{}, {}] and mirrors index {}. Z[{}] = {} reaches the end of the window, so the first {} match and the comparison starts after the window",
+                        i, l, r - 1, k, k, z[k], plural(r - i, "character")
+                    ),
+                )
+            };
+            let mut length = known;
+            while i + length < n {
+                comparisons += 1;
+                if string[length] != string[i + length] {
+                    break;
+                }
+                length += 1;
+            }
+            z[i] = length;
+            highlights.z[i] = Some(length as i64);
+            highlights.z_states.insert(i, ElementState::Selected);
+            highlights.prefix_shift = Some(i);
+            highlights.chars.clear();
+            highlights.prefix.clear();
+            for j in 0..length {
+                let state = if j < known {
+                    ElementState::Frontier
+                } else {
+                    ElementState::Selected
+                };
+                highlights.chars.insert(i + j, state);
+                highlights.prefix.insert(j, state);
+            }
+            if i + length < n {
+                highlights.chars.insert(i + length, ElementState::Rejected);
+                highlights.prefix.insert(length, ElementState::Rejected);
+            }
+            let moved = if length > 0 && i + length > r {
+                (l, r) = (i, i + length);
+                highlights.window = Some((l, r - 1));
+                format!(
+                    ". The match ends after the window, so the window moves to [{}, {}]",
+                    l,
+                    r - 1
+                )
+            } else {
+                String::new()
+            };
+            self.push(
+                string,
+                format!("{}. Z[{}] = {}{}", reason, i, length, moved),
+                highlights,
+                matches,
+                steps,
+            );
+            highlights.z_states.clear();
+        }
+        highlights.chars.clear();
+        highlights.prefix.clear();
+        highlights.prefix_shift = None;
+        highlights.window = None;
+        (z, comparisons)
+    }
+    fn compute_z_array(&self, steps: &mut DiagramSteps) {
+        let mut highlights = Highlights::default();
+        let (_, comparisons) = self.compute(&self.text, &mut highlights, false, steps);
+        self.push(
+            &self.text,
+            format!(
+                "The Z array is done after {}. The window only moves ahead, so there are at most two comparisons per character",
+                plural(comparisons, "character comparison")
+            ),
+            &highlights,
+            None,
+            steps,
+        );
+    }
+    /// Searches for the pattern by computing the Z array of the pattern and the text joined with a separator.
+    /// The pattern appears where the Z value is the length of the pattern.
+    fn search(&self, steps: &mut DiagramSteps) {
+        let separator = SEPARATORS
+            .into_iter()
+            .find(|separator| !self.text.contains(separator) && !self.pattern.contains(separator))
+            .unwrap();
+        let m = self.pattern.len();
+        let string = [&self.pattern[..], &[separator], &self.text[..]].concat();
+        let mut highlights = Highlights::default();
+        let (z, _) = self.compute(&string, &mut highlights, true, steps);
+        let mut matches = vec![];
+        for (i, value) in z.iter().enumerate().skip(m + 1) {
+            if *value == m {
+                matches.push(i - m - 1);
+                highlights.z_states.insert(i, ElementState::Selected);
+                for j in i..i + m {
+                    highlights.chars.insert(j, ElementState::Selected);
+                }
+            }
+        }
+        let description = if matches.is_empty() {
+            format!(
+                "No Z value in the text is {}, so the pattern isn't in the text",
+                m
+            )
+        } else {
+            format!(
+                "The Z values that are {}, the length of the pattern, are matches. The separator {} keeps the values from being any larger. The pattern was found {}",
+                m,
+                separator,
+                plural(matches.len(), "time")
+            )
+        };
+        self.push(&string, description, &highlights, Some(&matches), steps);
+    }
+}
+impl Visualizer for ZAlgorithm {
+    fn diagram(&self) -> Diagram {
+        draw(&self.text, &Highlights::default())
+    }
+    fn operations(&self) -> Vec<Operation> {
+        vec![
+            Operation::new(
+                "Compute Z array",
+                vec![Input::Text {
+                    label: "String",
+                    default: self.text.iter().collect(),
+                }],
+            ),
+            Operation::new("Search", search_inputs(&self.text, &self.pattern)),
+        ]
+    }
+    fn run(&mut self, operation: &str, values: &[Value]) -> Result<DiagramSteps, String> {
+        let mut steps = DiagramSteps::new();
+        match operation {
+            "Compute Z array" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                self.text = check_text(&text, "string", MAX_TEXT_LENGTH)?;
+                self.compute_z_array(&mut steps);
+            }
+            "Search" => {
+                let text = values.first().map(|value| value.text()).unwrap_or_default();
+                let pattern = values.get(1).map(|value| value.text()).unwrap_or_default();
+                let pattern = check_text(&pattern, "pattern", MAX_SEARCH_PATTERN_LENGTH)?;
+                let text = check_text(&text, "text", MAX_SEARCH_TEXT_LENGTH)?;
+                if SEPARATORS
+                    .iter()
+                    .all(|separator| text.contains(separator) || pattern.contains(separator))
+                {
+                    return Err(format!(
+                        "One of the characters {} must be left out, so that it can separate the pattern from the text",
+                        SEPARATORS.map(|separator| separator.to_string()).join(" ")
+                    ));
+                }
+                (self.text, self.pattern) = (text, pattern);
+                self.search(&mut steps);
+            }
+            _ => return Err(format!("Unknown operation \"{}\"", operation)),
+        }
+        Ok(steps)
+    }
+}
+
+fn draw(string: &[char], highlights: &Highlights) -> Diagram {
+    let mut diagram = Diagram::new(STRING_WIDTH, Z_HEIGHT);
+    let size = string.len();
+    draw_chars(
+        &mut diagram,
+        "char",
+        string,
+        (STRING_Y, 0, size),
+        &highlights.chars,
+        true,
+    );
+    if let Some((l, r)) = highlights.window {
+        let left = char_x(l as i64, size) - CHAR_SIZE.0 / 2.0 - 4.0;
+        diagram.add_figure(
+            Figure::new(FigureKind::Rect {
+                position: (left, STRING_Y - CHAR_SIZE.1 / 2.0 - 4.0),
+                size: (
+                    char_x(r as i64, size) + CHAR_SIZE.0 / 2.0 + 4.0 - left,
+                    CHAR_SIZE.1 + 8.0,
+                ),
+            })
+            .with_state(ElementState::Active),
+        );
+        for (key, index) in [("l", l), ("r", r)] {
+            diagram.add_node(DiagramNode::text(
+                format!("window-{}", key),
+                key,
+                (
+                    char_x(index as i64, size),
+                    STRING_Y - CHAR_SIZE.1 / 2.0 - 18.0,
+                ),
+            ));
+        }
+    }
+    if let Some(shift) = highlights.prefix_shift {
+        draw_chars(
+            &mut diagram,
+            "prefix",
+            &string[..size - shift],
+            (PREFIX_Y, shift as i64, size),
+            &highlights.prefix,
+            false,
+        );
+    }
+    let mut z = highlights.z.clone();
+    z.resize(size, None);
+    draw_values(
+        &mut diagram,
+        "z",
+        "Z",
+        &z,
+        (Z_Y, 0, size),
+        &highlights.z_states,
+    );
+    diagram
+}
@@ -1,90 +1,278 @@
 use instant::Duration;
-use std::rc::Rc;
-use web_sys::{AudioContext, DynamicsCompressorNode, GainNode, OscillatorNode, OscillatorType};
+use js_sys::{Array, Object, Reflect};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    AudioContext, AudioWorkletNode, AudioWorkletNodeOptions, DynamicsCompressorNode, GainNode,
+    OscillatorNode, OscillatorType, StereoPannerNode,
+};
+use yew::platform::spawn_local;
+
+/// The URL of the AudioWorklet module that contains the synthesizer's processor.
+const SYNTH_PROCESSOR_URL: &str = "/assets/audio/synth-processor.js";
+const SYNTH_PROCESSOR_NAME: &str = "synth-processor";
 
 /// Used for playing `Note`s.
+///
+/// Notes are sent as events to a persistent AudioWorklet synthesizer, which avoids creating new audio nodes for every step.
+/// Until the worklet module has loaded, or if AudioWorklets aren't supported, oscillator nodes are used as a fallback.
 #[derive(Clone, PartialEq)]
 pub struct Synth {
     pub ctx: Rc<AudioContext>,
     compressor: DynamicsCompressorNode,
-    notes: Vec<Note>,
+    /// The gain node all notes go through. Used for the volume.
+    master_gain: GainNode,
+    /// The AudioWorklet synthesizer. `None` until the worklet module has loaded.
+    worklet: Rc<RefCell<Option<AudioWorkletNode>>>,
+    /// Currently playing oscillators when the worklet isn't available.
+    voices: Vec<OscillatorVoice>,
 }
 
 impl Synth {
-    /// Creates a new `Synth` with an `AudioContext` and a compressor.
+    /// Creates a new `Synth` with an `AudioContext`, a compressor and a master gain node, and starts loading the AudioWorklet synthesizer.
     pub fn new() -> Self {
         let ctx = Rc::new(AudioContext::new().unwrap());
         let compressor = ctx.create_dynamics_compressor().unwrap();
         compressor.knee().set_value(10.0); // Reduce audio distortion
-        compressor
+        let master_gain = ctx.create_gain().unwrap();
+        compressor.connect_with_audio_node(&master_gain).unwrap();
+        master_gain
             .connect_with_audio_node(&ctx.destination())
             .unwrap();
 
+        let worklet = Rc::new(RefCell::new(None));
+
+        if let Ok(promise) = ctx
+            .audio_worklet()
+            .and_then(|audio_worklet| audio_worklet.add_module(SYNTH_PROCESSOR_URL))
+        {
+            let ctx = Rc::clone(&ctx);
+            let compressor = compressor.clone();
+            let worklet = Rc::clone(&worklet);
+
+            spawn_local(async move {
+                if JsFuture::from(promise).await.is_err() {
+                    log::warn!(
+                        "Could not load the AudioWorklet synthesizer, using oscillators instead"
+                    );
+                    return;
+                }
+
+                let mut options = AudioWorkletNodeOptions::new();
+                options
+                    .number_of_inputs(0)
+                    .number_of_outputs(1)
+                    .output_channel_count(&Array::of1(&JsValue::from(2)));
+
+                if let Ok(node) =
+                    AudioWorkletNode::new_with_options(&ctx, SYNTH_PROCESSOR_NAME, &options)
+                {
+                    node.connect_with_audio_node(&compressor).unwrap();
+                    *worklet.borrow_mut() = Some(node);
+                }
+            });
+        }
+
         Self {
             ctx,
             compressor,
-            notes: vec![],
+            master_gain,
+            worklet,
+            voices: vec![],
         }
     }
-    /// Play `Note`s for a given duration.
+    /// Sets the volume of all notes. 0.0 is muted and 1.0 is the full volume.
+    pub fn set_volume(&self, volume: f32) {
+        self.master_gain
+            .gain()
+            .set_target_at_time(volume.clamp(0.0, 1.0), self.ctx.current_time(), 0.05)
+            .unwrap();
+    }
+    /// Play `Note`s for a given duration. Multiple notes are played at the same time as a chord.
     pub fn play(&mut self, notes: Vec<Note>, duration: Duration) {
+        if notes.is_empty() {
+            return;
+        }
+
+        if let Some(worklet) = self.worklet.borrow().as_ref() {
+            let message = Object::new();
+            let note_objects = Array::new();
+
+            for note in notes {
+                let note_object = Object::new();
+                set_property(&note_object, "frequency", note.frequency.into());
+                set_property(&note_object, "pan", note.pan.into());
+                set_property(
+                    &note_object,
+                    "waveform",
+                    waveform_name(note.oscillator_type).into(),
+                );
+                note_objects.push(&note_object);
+            }
+
+            set_property(&message, "type", "play".into());
+            set_property(&message, "duration", duration.as_secs_f64().into());
+            set_property(&message, "notes", note_objects.into());
+
+            worklet.port().unwrap().post_message(&message).unwrap();
+            return;
+        }
+
         for note in notes {
-            note.gain
+            let voice = OscillatorVoice::new(&self.ctx, &note);
+
+            voice
+                .gain
                 .gain()
                 .linear_ramp_to_value_at_time(1.0, self.ctx.current_time() + 0.05)
                 .unwrap();
 
-            note.osc.connect_with_audio_node(&note.gain).unwrap();
-            note.gain.connect_with_audio_node(&self.compressor).unwrap();
+            voice.osc.connect_with_audio_node(&voice.gain).unwrap();
+            voice.gain.connect_with_audio_node(&voice.panner).unwrap();
+            voice
+                .panner
+                .connect_with_audio_node(&self.compressor)
+                .unwrap();
 
-            note.osc.start().unwrap();
+            voice.osc.start().unwrap();
 
-            note.gain
+            voice
+                .gain
                 .gain()
                 .linear_ramp_to_value_at_time(0.0, self.ctx.current_time() + duration.as_secs_f64())
                 .unwrap();
-            note.osc
+            voice
+                .osc
                 .stop_with_when(self.ctx.current_time() + duration.as_secs_f64())
                 .unwrap();
 
-            self.notes.push(note);
+            self.voices.push(voice);
         }
     }
     /// Stops all currently playing notes with a 0.5 second fade.
     pub fn stop_all(&mut self) {
-        for note in &self.notes {
-            note.gain
+        if let Some(worklet) = self.worklet.borrow().as_ref() {
+            let message = Object::new();
+            set_property(&message, "type", "stop".into());
+            worklet.port().unwrap().post_message(&message).unwrap();
+        }
+
+        for voice in &self.voices {
+            voice
+                .gain
                 .gain()
                 .linear_ramp_to_value_at_time(0.0, self.ctx.current_time() + 0.5)
                 .unwrap();
         }
-        self.notes = vec![];
+        self.voices = vec![];
+    }
+}
+
+fn set_property(object: &Object, key: &str, value: JsValue) {
+    Reflect::set(object, &key.into(), &value).unwrap();
+}
+
+/// The name of a waveform in the synthesizer's processor.
+fn waveform_name(oscillator_type: OscillatorType) -> &'static str {
+    match oscillator_type {
+        OscillatorType::Square => "square",
+        OscillatorType::Sawtooth => "sawtooth",
+        OscillatorType::Triangle => "triangle",
+        _ => "sine",
+    }
+}
+
+/// How values are mapped to the frequencies of notes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PitchMapping {
+    /// Frequencies are interpolated linearly between the minimum and maximum frequency.
+    #[default]
+    Linear,
+    /// Frequencies are snapped to the notes of the C major pentatonic scale.
+    Pentatonic,
+    /// Frequencies are snapped to the nearest chromatic MIDI note.
+    Chromatic,
+}
+impl PitchMapping {
+    /// Gets the frequency of a note between `min_frequency` and `max_frequency` for a `ratio` from 0.0 to 1.0.
+    pub fn frequency(&self, ratio: f32, min_frequency: f32, max_frequency: f32) -> f32 {
+        let (min_note, max_note) = (
+            frequency_to_midi(min_frequency.max(1.0)).ceil() as i32,
+            frequency_to_midi(max_frequency.max(1.0)).floor() as i32,
+        );
+
+        match self {
+            PitchMapping::Linear => min_frequency + (max_frequency - min_frequency) * ratio,
+            PitchMapping::Chromatic => midi_to_frequency(
+                (min_note as f32 + (max_note - min_note) as f32 * ratio).round() as i32,
+            ),
+            PitchMapping::Pentatonic => {
+                // The scale degrees of the major pentatonic scale as semitones from C
+                const DEGREES: [i32; 5] = [0, 2, 4, 7, 9];
+                let notes = (min_note..=max_note)
+                    .filter(|note| DEGREES.contains(&note.rem_euclid(12)))
+                    .collect::<Vec<i32>>();
+
+                if notes.is_empty() {
+                    return min_frequency;
+                }
+
+                let i = ((notes.len() - 1) as f32 * ratio).round() as usize;
+                midi_to_frequency(notes[i.min(notes.len() - 1)])
+            }
+        }
     }
 }
 
-/// A container of data related to a note. To play the note, use a `Synth`.
+/// Converts a frequency to a (fractional) MIDI note number, where 69 is A4 at 440 Hz.
+fn frequency_to_midi(frequency: f32) -> f32 {
+    69.0 + 12.0 * (frequency / 440.0).log2()
+}
+
+/// Converts a MIDI note number to a frequency.
+fn midi_to_frequency(note: i32) -> f32 {
+    440.0 * 2.0_f32.powf((note - 69) as f32 / 12.0)
+}
+
+/// The data of a note. To play the note, use a `Synth`.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Note {
-    pub osc: OscillatorNode,
-    pub gain: GainNode,
     pub frequency: f32,
     pub oscillator_type: OscillatorType,
+    /// The stereo position of the note from -1.0 (left) to 1.0 (right).
+    pub pan: f32,
 }
 impl Note {
-    /// Creates a `Note` with an oscillator and gain. Doesn't play the note.
-    pub fn new(ctx: &AudioContext, frequency: f32, oscillator_type: OscillatorType) -> Self {
+    /// Creates a `Note`. Doesn't play the note.
+    pub fn new(frequency: f32, oscillator_type: OscillatorType, pan: f32) -> Self {
+        Self {
+            frequency,
+            oscillator_type,
+            pan: pan.clamp(-1.0, 1.0),
+        }
+    }
+}
+
+/// The audio nodes used for playing a `Note` without the AudioWorklet synthesizer.
+#[derive(Clone, Debug, PartialEq)]
+struct OscillatorVoice {
+    osc: OscillatorNode,
+    gain: GainNode,
+    panner: StereoPannerNode,
+}
+impl OscillatorVoice {
+    /// Creates an oscillator, gain and stereo panner for a `Note`. Doesn't play the note.
+    fn new(ctx: &AudioContext, note: &Note) -> Self {
         let osc = ctx.create_oscillator().unwrap();
         let gain = ctx.create_gain().unwrap();
+        let panner = ctx.create_stereo_panner().unwrap();
 
-        osc.set_type(oscillator_type);
-        osc.frequency().set_value(frequency);
+        osc.set_type(note.oscillator_type);
+        osc.frequency().set_value(note.frequency);
         gain.gain().set_value(0.0); // Muted by default
+        panner.pan().set_value(note.pan);
 
-        Self {
-            osc,
-            gain,
-            frequency,
-            oscillator_type,
-        }
+        Self { osc, gain, panner }
     }
 }
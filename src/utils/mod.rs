@@ -1,6 +1,16 @@
 pub mod audio;
 
-use rand::{thread_rng, Rng};
+pub use randomized::knuth_shuffle;
+
+use gloo_storage::{LocalStorage, Storage};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{window, Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+/// Returns true if the user prefers reduced motion.
+/// The preference is stored in localStorage by a script in `index.html`.
+pub fn prefers_reduced_motion() -> bool {
+    LocalStorage::get("prefers-reduced-motion").unwrap_or(false)
+}
 
 /// Generate a sorted `Vec<u32>` with a given length.
 /// The numbers start from 1, and are in order, e.g. 1, 2, 3...
@@ -8,19 +18,20 @@ pub fn gen_u32_vec(len: usize) -> Vec<u32> {
     (1..=len as u32).collect::<Vec<u32>>()
 }
 
-/// Shuffle a vector with the Fisher-Yates shuffle, aka Knuth shuffle.
-pub fn knuth_shuffle<T>(mut items: Vec<T>) -> Vec<T> {
-    let mut curr_i = items.len();
-    let mut rand_i: usize;
-
-    while curr_i != 0 {
-        rand_i = thread_rng().gen_range(0..curr_i);
-        curr_i -= 1;
+/// Lets the user download `contents` as a file with the given name and MIME type.
+pub fn download_file(file_name: &str, contents: &str, mime_type: &str) -> Result<(), JsValue> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let blob =
+        Blob::new_with_str_sequence_and_options(&parts, BlobPropertyBag::new().type_(mime_type))?;
+    let url = Url::create_object_url_with_blob(&blob)?;
 
-        items.swap(curr_i, rand_i);
-    }
+    let document = window().unwrap().document().unwrap();
+    let link: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    link.set_href(&url);
+    link.set_download(file_name);
+    link.click();
 
-    items
+    Url::revoke_object_url(&url)
 }
 
 /// Fetch from a given url.